@@ -0,0 +1,91 @@
+-- Migration 015: Multi-tenant memory namespaces
+-- Purpose: Isolate memories per tenant/project/agent so one server can be
+-- shared without search results leaking between repositories.
+-- Namespaces are free-form strings such as 'acme/api/reviewer'; existing rows
+-- land in the 'default' namespace.
+
+BEGIN;
+
+-- ========================================
+-- NAMESPACE COLUMNS
+-- ========================================
+
+ALTER TABLE memories
+ADD COLUMN IF NOT EXISTS namespace VARCHAR(255) NOT NULL DEFAULT 'default';
+
+ALTER TABLE frozen_memories
+ADD COLUMN IF NOT EXISTS namespace VARCHAR(255) NOT NULL DEFAULT 'default';
+
+ALTER TABLE memory_clusters
+ADD COLUMN IF NOT EXISTS namespace VARCHAR(255) NOT NULL DEFAULT 'default';
+
+ALTER TABLE harvest_sessions
+ADD COLUMN IF NOT EXISTS namespace VARCHAR(255) NOT NULL DEFAULT 'default';
+
+-- Insights only exist when the codex-dreams schema (014) has been applied
+ALTER TABLE IF EXISTS insights
+ADD COLUMN IF NOT EXISTS namespace VARCHAR(255) NOT NULL DEFAULT 'default';
+
+-- ========================================
+-- DEDUPLICATION IS PER NAMESPACE
+-- ========================================
+
+-- The same content may legitimately be stored by two tenants
+ALTER TABLE memories DROP CONSTRAINT IF EXISTS memories_content_hash_tier_key;
+ALTER TABLE memories DROP CONSTRAINT IF EXISTS memories_namespace_content_hash_tier_key;
+ALTER TABLE memories
+ADD CONSTRAINT memories_namespace_content_hash_tier_key UNIQUE (namespace, content_hash, tier);
+
+CREATE OR REPLACE FUNCTION check_content_duplicate()
+RETURNS TRIGGER AS $$
+BEGIN
+    -- Generate content hash
+    NEW.content_hash = encode(digest(NEW.content, 'sha256'), 'hex');
+
+    -- Check for existing duplicate in same namespace and tier
+    IF EXISTS (
+        SELECT 1 FROM memories
+        WHERE content_hash = NEW.content_hash
+        AND tier = NEW.tier
+        AND namespace = NEW.namespace
+        AND status = 'active'
+        AND id != COALESCE(NEW.id, uuid_generate_v4())
+    ) THEN
+        RAISE EXCEPTION 'Duplicate content already exists in tier %', NEW.tier;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ========================================
+-- INDEXES
+-- ========================================
+
+-- Every repository query filters on namespace first
+CREATE INDEX IF NOT EXISTS idx_memories_namespace_status_tier
+ON memories (namespace, status, tier);
+
+CREATE INDEX IF NOT EXISTS idx_memories_namespace_created_at
+ON memories (namespace, created_at DESC)
+WHERE status = 'active';
+
+CREATE INDEX IF NOT EXISTS idx_frozen_memories_namespace
+ON frozen_memories (namespace);
+
+CREATE INDEX IF NOT EXISTS idx_memory_clusters_namespace
+ON memory_clusters (namespace);
+
+CREATE INDEX IF NOT EXISTS idx_harvest_sessions_namespace_started_at
+ON harvest_sessions (namespace, started_at DESC);
+
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = 'insights') THEN
+        CREATE INDEX IF NOT EXISTS idx_insights_namespace ON insights (namespace);
+    END IF;
+END $$;
+
+COMMENT ON COLUMN memories.namespace IS 'Tenant/project/agent namespace; queries never cross namespaces';
+
+COMMIT;
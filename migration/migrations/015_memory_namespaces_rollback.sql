@@ -0,0 +1,43 @@
+-- Migration 015 Rollback: Remove multi-tenant memory namespaces
+-- WARNING: memories from all namespaces are merged; rows whose content now
+-- collides across namespaces must be resolved before the unique constraint
+-- on (content_hash, tier) can be restored.
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_insights_namespace;
+DROP INDEX IF EXISTS idx_harvest_sessions_namespace_started_at;
+DROP INDEX IF EXISTS idx_memory_clusters_namespace;
+DROP INDEX IF EXISTS idx_frozen_memories_namespace;
+DROP INDEX IF EXISTS idx_memories_namespace_created_at;
+DROP INDEX IF EXISTS idx_memories_namespace_status_tier;
+
+CREATE OR REPLACE FUNCTION check_content_duplicate()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.content_hash = encode(digest(NEW.content, 'sha256'), 'hex');
+
+    IF EXISTS (
+        SELECT 1 FROM memories
+        WHERE content_hash = NEW.content_hash
+        AND tier = NEW.tier
+        AND status = 'active'
+        AND id != COALESCE(NEW.id, uuid_generate_v4())
+    ) THEN
+        RAISE EXCEPTION 'Duplicate content already exists in tier %', NEW.tier;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE memories DROP CONSTRAINT IF EXISTS memories_namespace_content_hash_tier_key;
+ALTER TABLE memories ADD CONSTRAINT memories_content_hash_tier_key UNIQUE (content_hash, tier);
+
+ALTER TABLE IF EXISTS insights DROP COLUMN IF EXISTS namespace;
+ALTER TABLE harvest_sessions DROP COLUMN IF EXISTS namespace;
+ALTER TABLE memory_clusters DROP COLUMN IF EXISTS namespace;
+ALTER TABLE frozen_memories DROP COLUMN IF EXISTS namespace;
+ALTER TABLE memories DROP COLUMN IF EXISTS namespace;
+
+COMMIT;
@@ -0,0 +1,121 @@
+-- Migration 016: Per-memory embedding model tracking
+-- Purpose: Record which model and dimension produced each stored vector so
+-- switching embedding providers does not silently compare incompatible
+-- vectors, and let the re-embed job migrate memories to a new model in
+-- resumable batches.
+
+BEGIN;
+
+-- ========================================
+-- EMBEDDING MODEL COLUMNS
+-- ========================================
+
+-- NULL model means the vector predates model tracking
+ALTER TABLE memories
+ADD COLUMN IF NOT EXISTS embedding_model VARCHAR(255);
+
+ALTER TABLE memories
+ADD COLUMN IF NOT EXISTS embedding_dimension INTEGER;
+
+-- ========================================
+-- MIXED DIMENSIONS
+-- ========================================
+
+-- The embedding column was fixed at 1536 dimensions. Drop the type modifier
+-- so vectors from models of any size can coexist during a migration, and
+-- replace the HNSW indexes with per-dimension expression indexes; the
+-- re-embed job creates the index for a new dimension on first use.
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'memories'
+        AND column_name = 'embedding'
+        AND udt_name = 'vector'
+    ) THEN
+        UPDATE memories
+        SET embedding_dimension = vector_dims(embedding)
+        WHERE embedding IS NOT NULL AND embedding_dimension IS NULL;
+
+        -- The view selects m.* and would block the column type change
+        DROP VIEW IF EXISTS memory_search_enhanced;
+        DROP INDEX IF EXISTS idx_memories_working_embedding;
+        DROP INDEX IF EXISTS idx_memories_warm_embedding;
+
+        ALTER TABLE memories ALTER COLUMN embedding TYPE vector;
+
+        CREATE INDEX IF NOT EXISTS idx_memories_embedding_hnsw_1536 ON memories
+            USING hnsw ((embedding::vector(1536)) vector_cosine_ops)
+            WHERE embedding_dimension = 1536 AND status = 'active';
+
+        CREATE OR REPLACE VIEW memory_search_enhanced AS
+        SELECT
+            m.*,
+            CASE
+                WHEN m.last_accessed_at IS NOT NULL THEN
+                    calculate_recency_score(m.last_accessed_at, m.created_at)
+                ELSE
+                    calculate_recency_score(m.created_at, m.created_at)
+            END as dynamic_recency_score,
+            (
+                0.3 * COALESCE(m.combined_score, 0.5) +
+                0.25 * COALESCE(m.recall_probability, 0.5) +
+                0.2 * m.importance_score +
+                0.15 * COALESCE(m.consolidation_strength / 10.0, 0.1) +
+                0.1 * LEAST(1.0, m.access_count::FLOAT / 100.0)
+            ) as enhanced_ranking_score
+        FROM memories m
+        WHERE m.status = 'active';
+
+        -- Keep the dimension in sync for writers that bypass the repository,
+        -- e.g. unfreeze_memory()
+        CREATE OR REPLACE FUNCTION set_embedding_dimension()
+        RETURNS TRIGGER AS $fn$
+        BEGIN
+            IF NEW.embedding IS NULL THEN
+                NEW.embedding_dimension := NULL;
+            ELSE
+                NEW.embedding_dimension := vector_dims(NEW.embedding);
+            END IF;
+            RETURN NEW;
+        END;
+        $fn$ LANGUAGE plpgsql;
+
+        DROP TRIGGER IF EXISTS memories_embedding_dimension ON memories;
+        CREATE TRIGGER memories_embedding_dimension
+            BEFORE INSERT OR UPDATE OF embedding ON memories
+            FOR EACH ROW EXECUTE FUNCTION set_embedding_dimension();
+    END IF;
+END $$;
+
+-- ========================================
+-- RE-EMBED PROGRESS
+-- ========================================
+
+-- One checkpoint per namespace and target model so an interrupted run
+-- resumes after the last memory it rewrote
+CREATE TABLE IF NOT EXISTS embedding_reembed_progress (
+    namespace VARCHAR(255) NOT NULL,
+    target_model VARCHAR(255) NOT NULL,
+    target_dimension INTEGER NOT NULL,
+    last_memory_id UUID,
+    memories_reembedded BIGINT NOT NULL DEFAULT 0,
+    memories_failed BIGINT NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    PRIMARY KEY (namespace, target_model)
+);
+
+-- ========================================
+-- INDEXES
+-- ========================================
+
+CREATE INDEX IF NOT EXISTS idx_memories_namespace_embedding_model
+ON memories (namespace, embedding_model, embedding_dimension)
+WHERE status = 'active';
+
+COMMENT ON COLUMN memories.embedding_model IS 'Model that produced the embedding; NULL for vectors stored before 016';
+COMMENT ON COLUMN memories.embedding_dimension IS 'Length of the embedding vector';
+
+COMMIT;
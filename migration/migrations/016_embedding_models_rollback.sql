@@ -0,0 +1,58 @@
+-- Migration 016 Rollback: Remove per-memory embedding model tracking
+-- WARNING: restoring vector(1536) fails while memories hold vectors of any
+-- other dimension; re-embed them with a 1536-dimension model first.
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_memories_namespace_embedding_model;
+DROP TABLE IF EXISTS embedding_reembed_progress;
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'memories'
+        AND column_name = 'embedding'
+        AND udt_name = 'vector'
+    ) THEN
+        DROP TRIGGER IF EXISTS memories_embedding_dimension ON memories;
+        DROP FUNCTION IF EXISTS set_embedding_dimension();
+
+        DROP VIEW IF EXISTS memory_search_enhanced;
+        DROP INDEX IF EXISTS idx_memories_embedding_hnsw_1536;
+
+        ALTER TABLE memories ALTER COLUMN embedding TYPE vector(1536);
+
+        CREATE INDEX IF NOT EXISTS idx_memories_working_embedding ON memories
+            USING hnsw (embedding vector_cosine_ops)
+            WHERE tier = 'working' AND status = 'active';
+
+        CREATE INDEX IF NOT EXISTS idx_memories_warm_embedding ON memories
+            USING hnsw (embedding vector_cosine_ops)
+            WHERE tier = 'warm' AND status = 'active';
+
+        CREATE OR REPLACE VIEW memory_search_enhanced AS
+        SELECT
+            m.*,
+            CASE
+                WHEN m.last_accessed_at IS NOT NULL THEN
+                    calculate_recency_score(m.last_accessed_at, m.created_at)
+                ELSE
+                    calculate_recency_score(m.created_at, m.created_at)
+            END as dynamic_recency_score,
+            (
+                0.3 * COALESCE(m.combined_score, 0.5) +
+                0.25 * COALESCE(m.recall_probability, 0.5) +
+                0.2 * m.importance_score +
+                0.15 * COALESCE(m.consolidation_strength / 10.0, 0.1) +
+                0.1 * LEAST(1.0, m.access_count::FLOAT / 100.0)
+            ) as enhanced_ranking_score
+        FROM memories m
+        WHERE m.status = 'active';
+    END IF;
+END $$;
+
+ALTER TABLE memories DROP COLUMN IF EXISTS embedding_dimension;
+ALTER TABLE memories DROP COLUMN IF EXISTS embedding_model;
+
+COMMIT;
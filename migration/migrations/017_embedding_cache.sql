@@ -0,0 +1,25 @@
+-- Migration 017: Persistent embedding cache
+-- Purpose: Reuse embeddings of texts that were already embedded by the same
+-- model, keyed by the SHA-256 content hash, so repeated conversations and
+-- re-stored memories do not hit the embedding provider again.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS embedding_cache (
+    model VARCHAR(255) NOT NULL,
+    content_hash VARCHAR(64) NOT NULL,
+    embedding REAL[] NOT NULL,
+    dimension INTEGER NOT NULL,
+    hit_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (model, content_hash)
+);
+
+-- Supports pruning entries that have not been used recently
+CREATE INDEX IF NOT EXISTS idx_embedding_cache_last_used
+ON embedding_cache (last_used_at);
+
+COMMENT ON TABLE embedding_cache IS 'Embeddings keyed by model and content hash, shared by all namespaces';
+
+COMMIT;
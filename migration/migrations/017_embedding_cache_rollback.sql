@@ -0,0 +1,8 @@
+-- Migration 017 Rollback: Remove persistent embedding cache
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_embedding_cache_last_used;
+DROP TABLE IF EXISTS embedding_cache;
+
+COMMIT;
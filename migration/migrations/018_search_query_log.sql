@@ -0,0 +1,26 @@
+-- Migration 018: Search query log
+-- Purpose: Record searched queries per namespace so query suggestions come
+-- from what users actually search for and which searches found results.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS search_query_log (
+    id BIGSERIAL PRIMARY KEY,
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    query_text TEXT NOT NULL,
+    -- Lower-cased terms joined by single spaces; groups equivalent queries
+    normalized_query TEXT NOT NULL,
+    search_type VARCHAR(20) NOT NULL,
+    result_count INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_search_query_log_namespace_created
+ON search_query_log (namespace, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_search_query_log_terms
+ON search_query_log USING gin (string_to_array(normalized_query, ' '));
+
+COMMENT ON TABLE search_query_log IS 'Searches per namespace, used for query suggestions';
+
+COMMIT;
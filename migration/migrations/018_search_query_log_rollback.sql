@@ -0,0 +1,9 @@
+-- Migration 018 Rollback: Remove search query log
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_search_query_log_terms;
+DROP INDEX IF EXISTS idx_search_query_log_namespace_created;
+DROP TABLE IF EXISTS search_query_log;
+
+COMMIT;
@@ -0,0 +1,34 @@
+-- Migration 019: Memory revisions
+-- Purpose: Keep an immutable revision for every update of a memory so edits
+-- can be attributed, compared and reverted. Revision 1 is the state before
+-- the first recorded update.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS memory_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    memory_id UUID NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    revision INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    content_hash VARCHAR(64) NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}',
+    importance_score FLOAT8 NOT NULL,
+    tier VARCHAR(20) NOT NULL,
+    -- Who made the change, e.g. an MCP client, the harvester or a user id
+    actor VARCHAR(255),
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (memory_id, revision)
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_revisions_namespace_created
+ON memory_revisions (namespace, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_memory_revisions_actor
+ON memory_revisions (actor)
+WHERE actor IS NOT NULL;
+
+COMMENT ON TABLE memory_revisions IS 'Immutable edit history of memories, one row per update';
+
+COMMIT;
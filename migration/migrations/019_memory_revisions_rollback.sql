@@ -0,0 +1,9 @@
+-- Migration 019 Rollback: Remove memory revisions
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_memory_revisions_actor;
+DROP INDEX IF EXISTS idx_memory_revisions_namespace_created;
+DROP TABLE IF EXISTS memory_revisions;
+
+COMMIT;
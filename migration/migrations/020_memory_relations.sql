@@ -0,0 +1,33 @@
+-- Migration 020: Typed relations between memories
+-- Purpose: Explicit, typed edges between memories of the same namespace
+-- (is_a, caused_by, conflicts_with, ...) for neighbor lookups, multi-hop
+-- traversal and graph-expanded search. `parent_id` keeps recording where a
+-- memory was derived from.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS memory_relations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    source_id UUID NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+    target_id UUID NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+    -- Snake-case RelationshipType, e.g. 'caused_by'
+    relation_type VARCHAR(50) NOT NULL,
+    strength FLOAT8 NOT NULL DEFAULT 1.0 CHECK (strength >= 0.0 AND strength <= 1.0),
+    metadata JSONB NOT NULL DEFAULT '{}',
+    created_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (source_id <> target_id),
+    UNIQUE (source_id, target_id, relation_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_relations_source
+ON memory_relations (namespace, source_id, relation_type);
+
+CREATE INDEX IF NOT EXISTS idx_memory_relations_target
+ON memory_relations (namespace, target_id, relation_type);
+
+COMMENT ON TABLE memory_relations IS 'Typed directed edges between memories';
+
+COMMIT;
@@ -0,0 +1,9 @@
+-- Migration 020 Rollback: Remove typed relations between memories
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_memory_relations_target;
+DROP INDEX IF EXISTS idx_memory_relations_source;
+DROP TABLE IF EXISTS memory_relations;
+
+COMMIT;
@@ -0,0 +1,35 @@
+-- Migration 021: Persistent knowledge graph for the reflection engine
+-- Purpose: Scope the knowledge_nodes / knowledge_edges tables from migration
+-- 003 to a namespace and let concept embeddings come from any model, so the
+-- reflection engine can persist its graph and resolve concepts by embedding
+-- similarity across restarts.
+
+BEGIN;
+
+ALTER TABLE knowledge_nodes
+ADD COLUMN IF NOT EXISTS namespace VARCHAR(255) NOT NULL DEFAULT 'default';
+
+ALTER TABLE knowledge_nodes
+ADD COLUMN IF NOT EXISTS embedding_dimension INTEGER;
+
+-- The embedding column was fixed at 384 dimensions; drop the type modifier
+-- like migration 016 did for memories. Similarity lookups filter on
+-- embedding_dimension, so vectors of different sizes are never compared.
+DROP INDEX IF EXISTS knowledge_nodes_embedding_idx;
+ALTER TABLE knowledge_nodes ALTER COLUMN embedding TYPE vector;
+
+UPDATE knowledge_nodes
+SET embedding_dimension = vector_dims(embedding)
+WHERE embedding IS NOT NULL AND embedding_dimension IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_knowledge_nodes_namespace_concept
+ON knowledge_nodes (namespace, node_type, lower(concept));
+
+CREATE INDEX IF NOT EXISTS idx_knowledge_nodes_namespace_dimension
+ON knowledge_nodes (namespace, embedding_dimension)
+WHERE embedding IS NOT NULL;
+
+COMMENT ON COLUMN knowledge_nodes.namespace IS 'Memory namespace the node was learned from';
+COMMENT ON COLUMN knowledge_nodes.embedding_dimension IS 'Length of the concept embedding vector';
+
+COMMIT;
@@ -0,0 +1,17 @@
+-- Migration 021 Rollback: Remove knowledge graph namespaces
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_knowledge_nodes_namespace_dimension;
+DROP INDEX IF EXISTS idx_knowledge_nodes_namespace_concept;
+
+-- Vectors of other sizes cannot be cast back to the original 384 dimensions
+UPDATE knowledge_nodes SET embedding = NULL WHERE embedding_dimension <> 384;
+ALTER TABLE knowledge_nodes ALTER COLUMN embedding TYPE vector(384);
+CREATE INDEX IF NOT EXISTS knowledge_nodes_embedding_idx
+ON knowledge_nodes USING ivfflat (embedding vector_cosine_ops);
+
+ALTER TABLE knowledge_nodes DROP COLUMN IF EXISTS embedding_dimension;
+ALTER TABLE knowledge_nodes DROP COLUMN IF EXISTS namespace;
+
+COMMIT;
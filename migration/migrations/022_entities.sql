@@ -0,0 +1,58 @@
+-- Migration 022: Canonical entities mentioned by memories
+-- Purpose: Resolve the people, projects, tools and places memories talk
+-- about to one record per namespace, whatever alias they are mentioned by
+-- ("pg", "Postgres", "PostgreSQL"), and link memories to them so searches
+-- can be restricted to one entity.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS entities (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    entity_type VARCHAR(20) NOT NULL CHECK (entity_type IN ('person', 'project', 'tool', 'place')),
+    canonical_name VARCHAR(255) NOT NULL,
+    -- Lowercased canonical name without punctuation
+    normalized_name VARCHAR(255) NOT NULL,
+    -- Embedding of the canonical name, for matching aliases never seen before
+    embedding vector,
+    embedding_dimension INTEGER,
+    mention_count INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (namespace, entity_type, normalized_name)
+);
+
+CREATE TABLE IF NOT EXISTS entity_aliases (
+    entity_id UUID NOT NULL REFERENCES entities(id) ON DELETE CASCADE,
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    -- Spelling as first seen
+    alias VARCHAR(255) NOT NULL,
+    normalized_alias VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (entity_id, normalized_alias)
+);
+
+CREATE TABLE IF NOT EXISTS memory_entities (
+    memory_id UUID NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+    entity_id UUID NOT NULL REFERENCES entities(id) ON DELETE CASCADE,
+    -- Name as written in the memory
+    mention VARCHAR(255) NOT NULL,
+    confidence FLOAT8 NOT NULL DEFAULT 1.0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (memory_id, entity_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_entity_aliases_lookup
+ON entity_aliases (namespace, normalized_alias);
+
+CREATE INDEX IF NOT EXISTS idx_entities_namespace_type
+ON entities (namespace, entity_type, mention_count DESC);
+
+CREATE INDEX IF NOT EXISTS idx_memory_entities_entity
+ON memory_entities (entity_id);
+
+COMMENT ON TABLE entities IS 'Canonical people, projects, tools and places mentioned by memories';
+COMMENT ON TABLE entity_aliases IS 'Names an entity has been mentioned by';
+COMMENT ON TABLE memory_entities IS 'Entities each memory mentions';
+
+COMMIT;
@@ -0,0 +1,12 @@
+-- Migration 022 Rollback: Remove entities
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_memory_entities_entity;
+DROP INDEX IF EXISTS idx_entities_namespace_type;
+DROP INDEX IF EXISTS idx_entity_aliases_lookup;
+DROP TABLE IF EXISTS memory_entities;
+DROP TABLE IF EXISTS entity_aliases;
+DROP TABLE IF EXISTS entities;
+
+COMMIT;
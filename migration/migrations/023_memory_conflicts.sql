@@ -0,0 +1,44 @@
+-- Migration 023: Conflicts between contradicting memories
+-- Purpose: Record store-time contradictions between memories and how they
+-- were resolved. A superseded memory is left out of searches in favor of
+-- the memory that replaced it.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS memory_conflicts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    -- Memory whose storage revealed the conflict
+    memory_id UUID NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+    -- Earlier memory it contradicts
+    conflicting_memory_id UUID NOT NULL REFERENCES memories(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'superseded', 'both_valid')),
+    -- One of the two memories, set exactly when superseded
+    superseded_memory_id UUID REFERENCES memories(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    confidence FLOAT8 NOT NULL DEFAULT 1.0 CHECK (confidence >= 0.0 AND confidence <= 1.0),
+    -- Checker that found the contradiction (rules, llm, correction)
+    detector VARCHAR(50) NOT NULL,
+    resolved_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    UNIQUE (memory_id, conflicting_memory_id),
+    CHECK (memory_id <> conflicting_memory_id),
+    CHECK ((status = 'superseded') = (superseded_memory_id IS NOT NULL)),
+    CHECK (superseded_memory_id IS NULL OR superseded_memory_id IN (memory_id, conflicting_memory_id))
+);
+
+CREATE INDEX IF NOT EXISTS idx_memory_conflicts_namespace_status
+ON memory_conflicts (namespace, status, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_memory_conflicts_conflicting
+ON memory_conflicts (conflicting_memory_id);
+
+-- Searches exclude superseded memories
+CREATE INDEX IF NOT EXISTS idx_memory_conflicts_superseded
+ON memory_conflicts (superseded_memory_id)
+WHERE status = 'superseded';
+
+COMMENT ON TABLE memory_conflicts IS 'Contradictions between memories and their resolution';
+
+COMMIT;
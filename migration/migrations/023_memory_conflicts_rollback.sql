@@ -0,0 +1,10 @@
+-- Migration 023 Rollback: Remove memory conflicts
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_memory_conflicts_superseded;
+DROP INDEX IF EXISTS idx_memory_conflicts_conflicting;
+DROP INDEX IF EXISTS idx_memory_conflicts_namespace_status;
+DROP TABLE IF EXISTS memory_conflicts;
+
+COMMIT;
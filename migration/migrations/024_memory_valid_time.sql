@@ -0,0 +1,42 @@
+-- Migration 024: Valid time for memories
+-- Purpose: Record when the fact a memory states held, separately from when
+-- the memory was recorded, so searches can ask what was true at a given
+-- date. A memory without valid_from is valid from created_at; one without
+-- valid_to is still current.
+
+BEGIN;
+
+ALTER TABLE memories
+ADD COLUMN IF NOT EXISTS valid_from TIMESTAMPTZ;
+
+ALTER TABLE memories
+ADD COLUMN IF NOT EXISTS valid_to TIMESTAMPTZ;
+
+-- Memories superseded before this migration stop being valid where the
+-- memory replacing them starts
+UPDATE memories old
+SET valid_to = cur.created_at
+FROM memory_conflicts c
+JOIN memories cur
+  ON cur.id = CASE WHEN c.superseded_memory_id = c.memory_id
+                   THEN c.conflicting_memory_id ELSE c.memory_id END
+WHERE c.status = 'superseded'
+  AND old.id = c.superseded_memory_id
+  AND old.valid_to IS NULL
+  AND old.created_at < cur.created_at;
+
+ALTER TABLE memories
+DROP CONSTRAINT IF EXISTS memories_valid_time_check;
+
+ALTER TABLE memories
+ADD CONSTRAINT memories_valid_time_check
+CHECK (valid_to IS NULL OR valid_to > COALESCE(valid_from, created_at));
+
+CREATE INDEX IF NOT EXISTS idx_memories_namespace_valid_time
+ON memories (namespace, (COALESCE(valid_from, created_at)), valid_to)
+WHERE status = 'active';
+
+COMMENT ON COLUMN memories.valid_from IS 'When the fact became true; NULL means from created_at';
+COMMENT ON COLUMN memories.valid_to IS 'When the fact stopped being true; NULL while current';
+
+COMMIT;
@@ -0,0 +1,10 @@
+-- Migration 024 Rollback: Remove valid time from memories
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_memories_namespace_valid_time;
+ALTER TABLE memories DROP CONSTRAINT IF EXISTS memories_valid_time_check;
+ALTER TABLE memories DROP COLUMN IF EXISTS valid_to;
+ALTER TABLE memories DROP COLUMN IF EXISTS valid_from;
+
+COMMIT;
@@ -0,0 +1,35 @@
+-- Migration 025: PII vault
+-- Purpose: Keep the values that the tokenize PII policy replaces in memory
+-- content. Memories and their embeddings only ever see the token. Values are
+-- encrypted under a data key per subject (the client that wrote them), so
+-- erasing a subject's key crypto-shreds all of their values. Keys are stored
+-- wrapped by the vault's master key, which never reaches the database.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS pii_vault_keys (
+    subject_id VARCHAR(255) PRIMARY KEY,
+    -- AES-256-GCM data key, encrypted with the master key
+    wrapped_key BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS pii_vault (
+    -- Placeholder standing in for the value, e.g. [EMAIL_3f9a0c1b2d4e]
+    token VARCHAR(100) NOT NULL,
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    subject_id VARCHAR(255) NOT NULL REFERENCES pii_vault_keys(subject_id) ON DELETE CASCADE,
+    -- PII pattern the value matched (email, api_key, ...)
+    kind VARCHAR(50) NOT NULL,
+    -- Nonce followed by the value encrypted with the subject's data key
+    ciphertext BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (namespace, token, subject_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pii_vault_subject ON pii_vault (subject_id);
+
+COMMENT ON TABLE pii_vault_keys IS 'Wrapped data keys of PII vault subjects; deleting one crypto-shreds their values';
+COMMENT ON TABLE pii_vault IS 'Encrypted values replaced by tokens under the tokenize PII policy';
+
+COMMIT;
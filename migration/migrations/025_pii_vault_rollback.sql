@@ -0,0 +1,8 @@
+-- Migration 025 Rollback: Remove the PII vault
+
+BEGIN;
+
+DROP TABLE IF EXISTS pii_vault;
+DROP TABLE IF EXISTS pii_vault_keys;
+
+COMMIT;
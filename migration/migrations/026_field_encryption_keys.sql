@@ -0,0 +1,21 @@
+-- Migration 026: Field encryption data keys
+-- Purpose: Store the data keys that encrypt memory content and configured
+-- metadata keys at rest. Each key is wrapped by the master key, which never
+-- reaches the database; rotating the master key re-wraps these rows and
+-- leaves the encrypted memories untouched.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS memory_encryption_keys (
+    -- Referenced by every encrypted value: enc:v1:<key_id>:<ciphertext>
+    key_id VARCHAR(64) PRIMARY KEY,
+    -- AES-256-GCM data key, encrypted with the master key
+    wrapped_key BYTEA NOT NULL,
+    -- Fingerprint of the master key that wrapped it
+    master_key_id VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE memory_encryption_keys IS 'Wrapped data keys for field-level encryption of memories';
+
+COMMIT;
@@ -0,0 +1,8 @@
+-- Migration 026 Rollback: Remove field encryption data keys
+-- Memories encrypted under these keys can no longer be decrypted afterwards.
+
+BEGIN;
+
+DROP TABLE IF EXISTS memory_encryption_keys;
+
+COMMIT;
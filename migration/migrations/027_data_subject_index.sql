@@ -0,0 +1,12 @@
+-- Migration 027: Find memories by data subject
+-- Purpose: Memories stored by an authenticated client carry its id as
+-- metadata.subject_id. GDPR erasure and portability requests look memories
+-- up by it.
+
+BEGIN;
+
+CREATE INDEX IF NOT EXISTS idx_memories_subject_id
+    ON memories ((metadata->>'subject_id'))
+    WHERE metadata ? 'subject_id';
+
+COMMIT;
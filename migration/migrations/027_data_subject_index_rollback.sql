@@ -0,0 +1,7 @@
+-- Migration 027 Rollback: Remove the data subject index
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_memories_subject_id;
+
+COMMIT;
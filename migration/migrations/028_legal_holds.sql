@@ -0,0 +1,20 @@
+-- Migration 028: Legal holds on memories
+-- Purpose: A memory under legal hold must be preserved as it is. Deleting
+-- it is refused, the retention job leaves it alone, and GDPR erasure of its
+-- subject fails until the hold is released.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS legal_holds (
+    memory_id UUID PRIMARY KEY REFERENCES memories(id) ON DELETE CASCADE,
+    namespace VARCHAR(255) NOT NULL DEFAULT 'default',
+    reason TEXT NOT NULL,
+    placed_by VARCHAR(255),
+    placed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_legal_holds_namespace ON legal_holds (namespace);
+
+COMMENT ON TABLE legal_holds IS 'Memories that must not be deleted, anonymized or frozen';
+
+COMMIT;
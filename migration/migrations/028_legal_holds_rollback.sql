@@ -0,0 +1,8 @@
+-- Migration 028 Rollback: Remove legal holds
+-- Held memories can be deleted again afterwards.
+
+BEGIN;
+
+DROP TABLE IF EXISTS legal_holds;
+
+COMMIT;
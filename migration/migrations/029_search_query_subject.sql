@@ -0,0 +1,14 @@
+-- Migration 029: Data subject of logged searches
+-- Purpose: Searches by an authenticated client record its id, so GDPR
+-- erasure and portability requests find the subject's searches without
+-- touching those of others in the same namespace.
+
+BEGIN;
+
+ALTER TABLE search_query_log ADD COLUMN IF NOT EXISTS subject_id VARCHAR(255);
+
+CREATE INDEX IF NOT EXISTS idx_search_query_log_subject_id
+    ON search_query_log (subject_id)
+    WHERE subject_id IS NOT NULL;
+
+COMMIT;
@@ -0,0 +1,8 @@
+-- Migration 029 Rollback: Remove the data subject of logged searches
+
+BEGIN;
+
+DROP INDEX IF EXISTS idx_search_query_log_subject_id;
+ALTER TABLE search_query_log DROP COLUMN IF EXISTS subject_id;
+
+COMMIT;
@@ -237,6 +237,11 @@ pub async fn get_recent_memories(
         include_facets: None,
         ranking_boost: None,
         explain_score: Some(false),
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     match state.repository.search_memories(search_request).await {
@@ -0,0 +1,83 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use serde::Deserialize;
+use tracing::error;
+use uuid::Uuid;
+
+use super::AppState;
+use crate::memory::{KnowledgeGraph, KnowledgeGraphStore, KnowledgePath, KnowledgeSubgraph};
+
+/// Deepest neighborhood or path the API computes
+const MAX_GRAPH_DEPTH: usize = 5;
+
+#[derive(Debug, Deserialize)]
+pub struct NeighborhoodQuery {
+    /// Node id or concept name
+    pub node: String,
+    pub depth: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PathQuery {
+    /// Node id or concept name
+    pub from: String,
+    /// Node id or concept name
+    pub to: String,
+    pub max_depth: Option<usize>,
+}
+
+/// Export the nodes around a knowledge graph node and the edges between them
+pub async fn get_neighborhood(
+    State(state): State<AppState>,
+    Query(params): Query<NeighborhoodQuery>,
+) -> Result<Json<KnowledgeSubgraph>, StatusCode> {
+    let graph = load_graph(&state).await?;
+    let center = resolve_node(&graph, &params.node).ok_or(StatusCode::NOT_FOUND)?;
+    let depth = params.depth.unwrap_or(1).min(MAX_GRAPH_DEPTH);
+
+    graph
+        .neighborhood(center, depth)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Find the shortest path between two knowledge graph nodes
+pub async fn get_shortest_path(
+    State(state): State<AppState>,
+    Query(params): Query<PathQuery>,
+) -> Result<Json<KnowledgePath>, StatusCode> {
+    let graph = load_graph(&state).await?;
+    let from = resolve_node(&graph, &params.from).ok_or(StatusCode::NOT_FOUND)?;
+    let to = resolve_node(&graph, &params.to).ok_or(StatusCode::NOT_FOUND)?;
+    let max_depth = params
+        .max_depth
+        .unwrap_or(MAX_GRAPH_DEPTH)
+        .min(MAX_GRAPH_DEPTH);
+
+    graph
+        .shortest_path(from, to, max_depth)
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+async fn load_graph(state: &AppState) -> Result<KnowledgeGraph, StatusCode> {
+    let store = KnowledgeGraphStore::new(
+        state.repository.pool().clone(),
+        state.repository.namespace(),
+    );
+    store.load().await.map_err(|e| {
+        error!("Failed to load knowledge graph: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Node with the given id, or else the concept node with the given name
+fn resolve_node(graph: &KnowledgeGraph, node: &str) -> Option<Uuid> {
+    match Uuid::parse_str(node) {
+        Ok(id) => graph.node(id).map(|node| node.id),
+        Err(_) => graph.find_concept(node),
+    }
+}
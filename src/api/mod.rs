@@ -1,5 +1,6 @@
 pub mod config_api;
 pub mod harvester_api;
+pub mod knowledge_api;
 
 use axum::{
     response::{Html, Json},
@@ -46,6 +47,12 @@ pub fn create_api_router(state: AppState) -> Router {
             get(harvester_api::get_recent_memories),
         )
         .route("/api/harvester/export", get(harvester_api::export_history))
+        // Knowledge graph API routes
+        .route(
+            "/api/knowledge/neighborhood",
+            get(knowledge_api::get_neighborhood),
+        )
+        .route("/api/knowledge/path", get(knowledge_api::get_shortest_path))
         // Serve static files (HTML, CSS, JS)
         .nest_service("/", ServeDir::new("static"))
         .route("/", get(serve_dashboard))
@@ -1,4 +1,10 @@
 use crate::application::DependencyContainer;
+use crate::mcp_server::HttpTransportConfig;
+use crate::memory::models::DEFAULT_NAMESPACE;
+use crate::memory::{
+    spawn_retention_job, PiiWritePolicy, ReembedJob, ReembedJobConfig, RetentionJob,
+    RetentionJobConfig, RetentionRules,
+};
 use anyhow::Result;
 use std::sync::Arc;
 use tracing::{error, info, warn};
@@ -62,8 +68,8 @@ impl HealthCommandHandler {
     pub async fn run_health_check(&self, detailed: bool) -> Result<()> {
         info!("🏥 Running system health check...");
 
-        if detailed {
-            let health = self.container.health_checker.check_system_health().await?;
+        if let (true, Some(health_checker)) = (detailed, &self.container.health_checker) {
+            let health = health_checker.check_system_health().await?;
 
             info!("📊 System Health: {:?}", health.status);
             info!("⏱️  Uptime: {} seconds", health.uptime_seconds);
@@ -158,6 +164,209 @@ impl DatabaseCommandHandler {
         info!("💡 Use direct SQL or database tools to run migrations");
         Err(anyhow::anyhow!("Migration support not compiled in"))
     }
+
+    pub async fn reembed_status(&self, namespace: Option<&str>) -> Result<()> {
+        let target_model = self.container.embedder.model();
+        let namespace = namespace.unwrap_or(DEFAULT_NAMESPACE);
+        let repository = self
+            .container
+            .postgres("Re-embedding")?
+            .with_namespace(namespace);
+
+        info!(
+            "📊 Embedding models in namespace '{}' (target: {}, {} dimensions)",
+            namespace,
+            target_model,
+            self.container.embedder.embedding_dimension()
+        );
+        for stats in repository.get_embedding_model_stats().await? {
+            info!(
+                "   - {} ({} dimensions): {} memories",
+                stats.embedding_model.as_deref().unwrap_or("untracked"),
+                stats
+                    .embedding_dimension
+                    .map_or_else(|| "?".to_string(), |d| d.to_string()),
+                stats.memory_count
+            );
+        }
+        info!(
+            "   - Awaiting re-embed: {}",
+            repository.count_reembed_remaining(target_model).await?
+        );
+
+        let stale = self
+            .container
+            .postgres("Re-embedding")?
+            .get_namespaces_needing_reembed(target_model)
+            .await?;
+        if !stale.is_empty() {
+            info!("💡 Namespaces needing re-embed: {}", stale.join(", "));
+        }
+        Ok(())
+    }
+
+    pub async fn reembed(&self, config: ReembedJobConfig) -> Result<()> {
+        let job = ReembedJob::new(
+            config,
+            self.container.postgres("Re-embedding")?.clone(),
+            self.container.embedder.clone(),
+        );
+        let job = match self.container.load_field_encryption().await? {
+            Some(encryption) => job.with_field_encryption(encryption),
+            None => job,
+        };
+
+        info!("🔄 Re-embedding memories with {}...", job.target_model());
+        let result = job.run().await?;
+
+        info!(
+            "✅ Re-embedded {} memories in {} batches ({:.1} mem/sec)",
+            result.memories_reembedded, result.batches_processed, result.memories_per_second
+        );
+        if result.memories_failed > 0 {
+            warn!(
+                "⚠️  {} memories failed; run with --restart to retry them",
+                result.memories_failed
+            );
+        }
+        if !result.is_complete() {
+            info!(
+                "💡 {} memories remaining; run the command again to resume",
+                result.memories_remaining
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn rotate_encryption_key(&self) -> Result<()> {
+        let encryption = self
+            .container
+            .load_field_encryption()
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Field encryption is not configured; set CODEX_FIELD_ENCRYPTION_KEY"
+                )
+            })?;
+        let new_secret = std::env::var("CODEX_FIELD_ENCRYPTION_NEW_KEY")
+            .ok()
+            .filter(|secret| !secret.trim().is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Set CODEX_FIELD_ENCRYPTION_NEW_KEY to the new master key")
+            })?;
+
+        let rotated = encryption
+            .rotate_master_key(self.container.storage.as_ref(), &new_secret)
+            .await?;
+        info!(
+            "🔐 Re-wrapped {} data keys under the new master key",
+            rotated
+        );
+        info!("💡 Replace CODEX_FIELD_ENCRYPTION_KEY with the new key before restarting");
+        Ok(())
+    }
+
+    pub async fn retention(&self, dry_run: bool) -> Result<()> {
+        let job = self
+            .container
+            .create_retention_job(
+                RetentionJobConfig::default(),
+                Arc::new(PiiWritePolicy::from_env()?),
+            )
+            .await?;
+        if job.rules().is_empty() {
+            warn!(
+                "⚠️  No retention rules configured; add them to {:?}",
+                RetentionRules::default_path()
+            );
+            return Ok(());
+        }
+
+        if dry_run {
+            info!("🔍 Dry run: no memory will be changed");
+        }
+        let report = job.run(dry_run).await?;
+        for outcome in &report.rules {
+            info!(
+                "   - {} ({:?}): {} matched, {} applied, {} under legal hold",
+                outcome.rule,
+                outcome.action,
+                outcome.matched.len(),
+                outcome.applied,
+                outcome.held.len()
+            );
+            if dry_run {
+                for matched in &outcome.matched {
+                    info!(
+                        "     {} in '{}', created {}",
+                        matched.memory_id, matched.namespace, matched.created_at
+                    );
+                }
+            }
+        }
+        info!(
+            "✅ Scanned {} memories: {} matched, {} actions applied, {} held",
+            report.memories_scanned,
+            report.total_matched(),
+            report.total_applied(),
+            report.total_held()
+        );
+        for failure in &report.failures {
+            warn!(
+                "⚠️  {} failed on memory {}: {}",
+                failure.rule, failure.memory_id, failure.error
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn legal_hold(
+        &self,
+        namespace: &str,
+        memory_id: Option<&str>,
+        reason: Option<String>,
+        release: bool,
+    ) -> Result<()> {
+        let repository = self.container.storage.with_namespace(namespace);
+        let Some(memory_id) = memory_id else {
+            let holds = repository.legal_holds().await?;
+            info!(
+                "⚖️  {} memories under legal hold in namespace '{}'",
+                holds.len(),
+                namespace
+            );
+            for hold in holds {
+                info!(
+                    "   - {} since {} by {}: {}",
+                    hold.memory_id,
+                    hold.placed_at,
+                    hold.placed_by.as_deref().unwrap_or("unknown"),
+                    hold.reason
+                );
+            }
+            return Ok(());
+        };
+        let memory_id = memory_id
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid memory id '{}': {}", memory_id, e))?;
+
+        if release {
+            if repository.release_legal_hold(memory_id).await? {
+                info!("✅ Released the legal hold on memory {}", memory_id);
+            } else {
+                warn!("⚠️  Memory {} was not under legal hold", memory_id);
+            }
+            return Ok(());
+        }
+
+        let reason = reason.ok_or_else(|| anyhow::anyhow!("Give the hold a --reason"))?;
+        let placed_by = std::env::var("USER").ok();
+        repository
+            .place_legal_hold(memory_id, &reason, placed_by.as_deref())
+            .await?;
+        info!("⚖️  Memory {} is under legal hold: {}", memory_id, reason);
+        Ok(())
+    }
 }
 
 pub struct McpCommandHandler {
@@ -171,7 +380,11 @@ impl McpCommandHandler {
 
     pub async fn validate(&self) -> Result<()> {
         info!("🔍 Validating MCP configuration...");
-        match self.container.config.validate_mcp_environment() {
+        match self
+            .container
+            .config
+            .validate_mcp_environment_with_registry(&self.container.embedding_registry)
+        {
             Ok(_) => {
                 info!("✅ MCP configuration is valid");
                 info!(
@@ -204,7 +417,9 @@ impl McpCommandHandler {
 
     pub async fn test(&self) -> Result<()> {
         info!("🧪 Testing MCP server connectivity...");
-        self.container.config.validate_mcp_environment()?;
+        self.container
+            .config
+            .validate_mcp_environment_with_registry(&self.container.embedding_registry)?;
 
         // Test database
         info!("Testing database connectivity...");
@@ -415,15 +630,65 @@ impl ServerCommandHandler {
         info!("🚀 Starting MCP stdio server...");
 
         if !skip_setup {
-            self.container.config.validate()?;
+            self.container.validate_config()?;
         }
 
         let mut mcp_server = self.container.create_mcp_server().await?;
+        let retention = self.schedule_retention(mcp_server.pii_policy()).await?;
         mcp_server.start().await?;
+        if let Some(retention) = retention {
+            retention.stop();
+        }
 
         Ok(())
     }
 
+    pub async fn start_mcp_http(&self, skip_setup: bool, bind: Option<String>) -> Result<()> {
+        info!("🚀 Starting MCP HTTP server...");
+
+        if !skip_setup {
+            self.container.validate_config()?;
+        }
+
+        let mut http_config = HttpTransportConfig::from_env();
+        if let Some(port) = self.container.config.mcp_port {
+            http_config.bind_address.set_port(port);
+        }
+        if let Some(bind) = bind {
+            http_config.bind_address = bind
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid bind address '{}': {}", bind, e))?;
+        }
+
+        let mcp_server = self.container.create_mcp_server().await?;
+        let retention = self.schedule_retention(mcp_server.pii_policy()).await?;
+        mcp_server.start_http(http_config).await?;
+        if let Some(retention) = retention {
+            retention.stop();
+        }
+
+        Ok(())
+    }
+
+    /// Apply the configured retention rules in the background while the
+    /// server runs, redacting with the server's PII policy
+    async fn schedule_retention(
+        &self,
+        pii_policy: &Arc<PiiWritePolicy>,
+    ) -> Result<Option<Arc<RetentionJob>>> {
+        let job = self
+            .container
+            .create_retention_job(RetentionJobConfig::default(), pii_policy.clone())
+            .await?;
+        if job.rules().is_empty() {
+            return Ok(None);
+        }
+        info!("🗂️  Applying {} retention rules", job.rules().rules.len());
+        let job = Arc::new(job);
+        spawn_retention_job(job.clone());
+        Ok(Some(job))
+    }
+
     async fn validate_system(&self) -> Result<()> {
         info!("🔍 Running pre-flight checks...");
 
@@ -1,12 +1,15 @@
 use crate::{
     backup::BackupManager,
+    embedding::{EmbeddingCache, EmbeddingRegistry},
     manager::ServerManager,
     mcp_server::{MCPServer, MCPServerConfig},
     memory::{
-        connection::create_pool, silent_harvester::SilentHarvesterService,
-        tier_manager::TierManager,
+        connect_storage, silent_harvester::SilentHarvesterService, tier_manager::TierManager,
+        EncryptedMemoryStorage, FieldEncryption, FieldEncryptionConfig, MemoryStorage,
+        PiiWritePolicy, RetentionJob, RetentionJobConfig, RetentionRules,
     },
-    monitoring::HealthChecker,
+    monitoring::{HealthChecker, MetricsCollector},
+    security::{SecretsConfig, SecretsManager},
     Config, DatabaseSetup, MemoryRepository, SetupManager, SimpleEmbedder,
 };
 
@@ -26,13 +29,15 @@ pub struct DependencyContainer {
     // Core configuration
     pub config: Config,
 
-    // Database layer
-    pub db_pool: Arc<PgPool>,
+    // Database layer; the pool is `None` unless `DATABASE_URL` is PostgreSQL
+    pub db_pool: Option<Arc<PgPool>>,
 
     // Repository layer
-    pub memory_repository: Arc<MemoryRepository>,
+    pub storage: Arc<dyn MemoryStorage>,
+    pub memory_repository: Option<Arc<MemoryRepository>>,
 
     // Service layer
+    pub embedding_registry: Arc<EmbeddingRegistry>,
     pub embedder: Arc<SimpleEmbedder>,
     pub setup_manager: Arc<SetupManager>,
     pub database_setup: Arc<DatabaseSetup>,
@@ -41,7 +46,8 @@ pub struct DependencyContainer {
     pub harvester_service: Option<Arc<SilentHarvesterService>>,
 
     // Infrastructure layer
-    pub health_checker: Arc<HealthChecker>,
+    pub health_checker: Option<Arc<HealthChecker>>,
+    pub metrics_collector: Arc<MetricsCollector>,
     pub mcp_server: Option<Arc<MCPServer>>,
     pub server_manager: Arc<ServerManager>,
 
@@ -56,7 +62,14 @@ pub struct DependencyContainer {
 
 impl DependencyContainer {
     pub async fn new() -> Result<Self> {
+        Self::with_embedding_registry(EmbeddingRegistry::default()).await
+    }
+
+    /// Build the container with application-provided embedding providers;
+    /// `EMBEDDING_PROVIDER` may name any provider in `embedding_registry`
+    pub async fn with_embedding_registry(embedding_registry: EmbeddingRegistry) -> Result<Self> {
         info!("🔧 Initializing dependency container...");
+        let embedding_registry = Arc::new(embedding_registry);
 
         // Load configuration
         let config = Config::from_env().unwrap_or_else(|_| {
@@ -64,41 +77,61 @@ impl DependencyContainer {
             Config::default()
         });
 
-        // Create database connection pool
-        let db_pool = Arc::new(
-            create_pool(&config.database_url, config.operational.max_db_connections).await?,
-        );
+        // Open the storage backend named by the database URL
+        let storage =
+            connect_storage(&config.database_url, config.operational.max_db_connections).await?;
+        let db_pool = storage
+            .as_postgres()
+            .map(|repository| Arc::new(repository.pool().clone()));
+        info!("💾 Storing memories in {}", storage.backend_name());
 
-        // Repository layer
-        let memory_repository = Arc::new(MemoryRepository::with_config(
-            (*db_pool).clone(),
-            config.clone(),
-        ));
+        let metrics_collector = Arc::new(MetricsCollector::new()?);
 
         // Service layer
-        let embedder = Arc::new(Self::create_embedder(&config)?);
+        let mut embedder = SimpleEmbedder::from_config(&config.embedding, &embedding_registry)?;
+        if config.embedding.cache_enabled {
+            let cache = match &db_pool {
+                Some(db_pool) => EmbeddingCache::postgres((**db_pool).clone()),
+                None => EmbeddingCache::in_memory(),
+            };
+            embedder = embedder.with_cache(cache.with_metrics(metrics_collector.clone()));
+        }
+        let embedder = Arc::new(embedder);
+
+        // Repository layer; stored and query vectors are tagged with the embedder's model
+        let memory_repository = db_pool.as_ref().map(|db_pool| {
+            Arc::new(
+                MemoryRepository::with_config((**db_pool).clone(), config.clone())
+                    .with_embedding_model(embedder.model()),
+            )
+        });
+        let storage: Arc<dyn MemoryStorage> = match &memory_repository {
+            Some(memory_repository) => memory_repository.clone(),
+            None => storage,
+        };
         let setup_manager = Arc::new(SetupManager::new(config.clone()));
         let database_setup = Arc::new(DatabaseSetup::new(config.database_url.clone()));
 
         // Infrastructure layer
-        let health_checker = Arc::new(HealthChecker::new(db_pool.clone()));
+        let health_checker = db_pool
+            .as_ref()
+            .map(|db_pool| Arc::new(HealthChecker::new(db_pool.clone())));
         let server_manager = Arc::new(ServerManager::new());
 
-        // Optional services
-        let backup_manager = if config.backup.enabled {
-            let backup_config = crate::backup::BackupConfig::default(); // Use default config for now
-            Some(Arc::new(BackupManager::new(backup_config, db_pool.clone())))
-        } else {
-            None
+        // Optional services, which only run on PostgreSQL
+        let backup_manager = match &db_pool {
+            Some(db_pool) if config.backup.enabled => {
+                let backup_config = crate::backup::BackupConfig::default(); // Use default config for now
+                Some(Arc::new(BackupManager::new(backup_config, db_pool.clone())))
+            }
+            _ => None,
         };
 
-        let tier_manager = if config.tier_manager.enabled {
-            Some(Arc::new(TierManager::new(
-                memory_repository.clone(),
-                config.tier_manager.clone(),
-            )?))
-        } else {
-            None
+        let tier_manager = match &memory_repository {
+            Some(memory_repository) if config.tier_manager.enabled => Some(Arc::new(
+                TierManager::new(memory_repository.clone(), config.tier_manager.clone())?,
+            )),
+            _ => None,
         };
 
         // TODO: Harvest service requires additional dependencies that need to be properly configured
@@ -146,46 +179,48 @@ impl DependencyContainer {
                 }
             };
 
-            // Create insight storage
-            let insight_storage = Some(Arc::new(InsightStorage::new(
-                db_pool.clone(),
-                embedder.clone(),
-            )));
-            info!("✅ Insight storage initialized");
+            // Create insight storage (only on PostgreSQL)
+            let insight_storage = db_pool.as_ref().map(|db_pool| {
+                info!("✅ Insight storage initialized");
+                Arc::new(InsightStorage::new(db_pool.clone(), embedder.clone()))
+            });
 
             // Create insights processor (only if Ollama client is available)
-            let insights_processor = if let (Some(ollama_client), Some(insight_storage)) =
-                (ollama_client.as_ref(), insight_storage.as_ref())
-            {
-                let processor_config = ProcessorConfig {
-                    batch_size: std::env::var("INSIGHTS_BATCH_SIZE")
-                        .unwrap_or_else(|_| "50".to_string())
-                        .parse()
-                        .unwrap_or(50),
-                    max_retries: 3,
-                    timeout_seconds: 900, // 15 minutes for large model processing
-                    circuit_breaker_threshold: 5,
-                    circuit_breaker_recovery_timeout: 60,
-                    min_confidence_threshold: std::env::var("INSIGHTS_MIN_CONFIDENCE")
-                        .unwrap_or_else(|_| "0.6".to_string())
-                        .parse()
-                        .unwrap_or(0.6),
-                    max_insights_per_batch: 10,
-                };
+            let insights_processor =
+                if let (Some(ollama_client), Some(insight_storage), Some(memory_repository)) = (
+                    ollama_client.as_ref(),
+                    insight_storage.as_ref(),
+                    memory_repository.as_ref(),
+                ) {
+                    let processor_config = ProcessorConfig {
+                        batch_size: std::env::var("INSIGHTS_BATCH_SIZE")
+                            .unwrap_or_else(|_| "50".to_string())
+                            .parse()
+                            .unwrap_or(50),
+                        max_retries: 3,
+                        timeout_seconds: 900, // 15 minutes for large model processing
+                        circuit_breaker_threshold: 5,
+                        circuit_breaker_recovery_timeout: 60,
+                        min_confidence_threshold: std::env::var("INSIGHTS_MIN_CONFIDENCE")
+                            .unwrap_or_else(|_| "0.6".to_string())
+                            .parse()
+                            .unwrap_or(0.6),
+                        max_insights_per_batch: 10,
+                    };
 
-                let processor = InsightsProcessor::new(
-                    memory_repository.clone(),
-                    ollama_client.clone(),
-                    insight_storage.clone(),
-                    processor_config,
-                );
-
-                info!("✅ Insights processor initialized");
-                Some(Arc::new(processor))
-            } else {
-                info!("⚠️  Insights processor disabled (missing dependencies)");
-                None
-            };
+                    let processor = InsightsProcessor::new(
+                        memory_repository.clone(),
+                        ollama_client.clone(),
+                        insight_storage.clone(),
+                        processor_config,
+                    );
+
+                    info!("✅ Insights processor initialized");
+                    Some(Arc::new(processor))
+                } else {
+                    info!("⚠️  Insights processor disabled (missing dependencies)");
+                    None
+                };
 
             (ollama_client, insight_storage, insights_processor)
         };
@@ -195,7 +230,9 @@ impl DependencyContainer {
         Ok(Self {
             config,
             db_pool,
+            storage,
             memory_repository,
+            embedding_registry,
             embedder,
             setup_manager,
             database_setup,
@@ -203,6 +240,7 @@ impl DependencyContainer {
             tier_manager,
             harvester_service,
             health_checker,
+            metrics_collector,
             mcp_server: None, // Created on demand
             server_manager,
             #[cfg(feature = "codex-dreams")]
@@ -214,51 +252,89 @@ impl DependencyContainer {
         })
     }
 
-    fn create_embedder(config: &Config) -> Result<SimpleEmbedder> {
-        match config.embedding.provider.as_str() {
-            "openai" => Ok(SimpleEmbedder::new(config.embedding.api_key.clone())
-                .with_model(config.embedding.model.clone())
-                .with_base_url(config.embedding.base_url.clone())),
-            "ollama" => Ok(SimpleEmbedder::new_ollama(
-                config.embedding.base_url.clone(),
-                config.embedding.model.clone(),
-            )),
-            "mock" => Ok(SimpleEmbedder::new_mock()),
-            _ => Err(anyhow::anyhow!(
-                "Unsupported embedding provider: {}",
-                config.embedding.provider
-            )),
-        }
+    /// Validate the configuration against the registered embedding providers
+    pub fn validate_config(&self) -> Result<()> {
+        self.config.validate_with_registry(&self.embedding_registry)
+    }
+
+    /// The PostgreSQL repository, for `feature` that only it supports
+    pub fn postgres(&self, feature: &str) -> Result<&Arc<MemoryRepository>> {
+        self.memory_repository.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} requires a PostgreSQL DATABASE_URL; memories are stored in {}",
+                feature,
+                self.storage.backend_name()
+            )
+        })
     }
 
     pub async fn create_mcp_server(&self) -> Result<MCPServer> {
         let mcp_config = MCPServerConfig::default();
 
-        #[cfg(feature = "codex-dreams")]
-        {
-            let server = MCPServer::new_with_insights(
-                self.memory_repository.clone(),
+        let server = match &self.memory_repository {
+            #[cfg(feature = "codex-dreams")]
+            Some(memory_repository) => MCPServer::new_with_insights(
+                memory_repository.clone(),
                 self.embedder.clone(),
                 mcp_config,
                 self.insights_processor.clone(),
                 self.insight_storage.clone(),
-            )?;
-            Ok(server)
+            )?,
+            #[cfg(not(feature = "codex-dreams"))]
+            Some(memory_repository) => {
+                MCPServer::new(memory_repository.clone(), self.embedder.clone(), mcp_config)?
+            }
+            None => {
+                MCPServer::for_storage(self.storage.clone(), self.embedder.clone(), mcp_config)?
+            }
+        };
+
+        match self.load_field_encryption().await? {
+            Some(encryption) => Ok(server.with_field_encryption(encryption)),
+            None => Ok(server),
         }
+    }
 
-        #[cfg(not(feature = "codex-dreams"))]
-        {
-            let server = MCPServer::new(
-                self.memory_repository.clone(),
-                self.embedder.clone(),
-                mcp_config,
-            )?;
-            Ok(server)
+    /// Field encryption keyed by the master key secret, if one is configured
+    pub async fn load_field_encryption(&self) -> Result<Option<Arc<FieldEncryption>>> {
+        let secrets = SecretsManager::new(SecretsConfig::default())?;
+        let encryption = FieldEncryption::load(
+            self.storage.as_ref(),
+            &secrets,
+            FieldEncryptionConfig::from_env(),
+        )
+        .await?;
+        if encryption.is_some() {
+            info!("🔐 Memory content is encrypted at rest");
         }
+        Ok(encryption.map(Arc::new))
+    }
+
+    /// Retention job applying the rules in the retention rules file to every
+    /// namespace, seeing memories encrypted at rest as plaintext. Anonymized
+    /// content is redacted with `pii_policy`.
+    pub async fn create_retention_job(
+        &self,
+        config: RetentionJobConfig,
+        pii_policy: Arc<PiiWritePolicy>,
+    ) -> Result<RetentionJob> {
+        let rules = RetentionRules::load_default()?;
+        let storage: Arc<dyn MemoryStorage> = match self.load_field_encryption().await? {
+            Some(encryption) => Arc::new(EncryptedMemoryStorage::new(
+                self.storage.clone(),
+                encryption,
+            )),
+            None => self.storage.clone(),
+        };
+        Ok(RetentionJob::new(config, rules, storage, pii_policy)
+            .with_embedder(self.embedder.clone()))
     }
 
     pub async fn health_check(&self) -> Result<bool> {
         // Quick health check using our services
+        if self.memory_repository.is_none() {
+            return Ok(self.storage.health_check().await.unwrap_or(false));
+        }
         match self.database_setup.health_check().await {
             Ok(health) => Ok(health.is_healthy()),
             Err(_) => Ok(false),
@@ -19,7 +19,7 @@ impl ApplicationLifecycle {
         info!("🚀 Initializing application...");
 
         // Validate configuration
-        self.container.config.validate()?;
+        self.container.validate_config()?;
 
         // Run health checks
         if !self.container.health_check().await? {
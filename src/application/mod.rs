@@ -1,3 +1,4 @@
+use crate::embedding::EmbeddingRegistry;
 use anyhow::Result;
 use std::sync::Arc;
 
@@ -23,7 +24,13 @@ pub struct Application {
 
 impl Application {
     pub async fn new() -> Result<Self> {
-        let container = Arc::new(DependencyContainer::new().await?);
+        Self::with_embedding_registry(EmbeddingRegistry::default()).await
+    }
+
+    /// Build the application with additional embedding providers registered
+    pub async fn with_embedding_registry(embedding_registry: EmbeddingRegistry) -> Result<Self> {
+        let container =
+            Arc::new(DependencyContainer::with_embedding_registry(embedding_registry).await?);
         let service = Arc::new(ApplicationService::new(container.clone()));
         let lifecycle = Arc::new(ApplicationLifecycle::new(container.clone()));
 
@@ -34,6 +34,11 @@ async fn main() -> Result<()> {
         include_metadata: Some(true),
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     println!("Searching for memories containing 'MCP server'...");
@@ -80,6 +85,11 @@ async fn main() -> Result<()> {
         include_metadata: Some(true),
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     println!("\n\nSearching for memories containing 'PID 62513'...");
@@ -126,6 +136,11 @@ async fn main() -> Result<()> {
         include_metadata: Some(true),
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     println!("\n\nSearching for memories containing 'Enhanced Agentic Memory System v2.0'...");
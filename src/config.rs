@@ -1,3 +1,4 @@
+use crate::embedding::EmbeddingRegistry;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::env;
@@ -38,7 +39,7 @@ pub struct Config {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingConfig {
-    /// Embedding provider (openai or ollama)
+    /// Embedding provider (openai, ollama, mock, hashing or a registered provider)
     pub provider: String,
 
     /// Model name to use for embeddings
@@ -52,6 +53,14 @@ pub struct EmbeddingConfig {
 
     /// Request timeout in seconds
     pub timeout_seconds: u64,
+
+    /// Reuse embeddings of previously embedded texts from the database
+    #[serde(default = "default_embedding_cache_enabled")]
+    pub cache_enabled: bool,
+}
+
+fn default_embedding_cache_enabled() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,6 +151,7 @@ impl Default for EmbeddingConfig {
             api_key: String::new(),
             base_url: "http://192.168.1.110:11434".to_string(),
             timeout_seconds: 60,
+            cache_enabled: true,
         }
     }
 }
@@ -219,6 +229,12 @@ impl Config {
                 .map_err(|e| anyhow::anyhow!("Invalid EMBEDDING_TIMEOUT_SECONDS: {}", e))?;
         }
 
+        if let Ok(cache_enabled) = env::var("EMBEDDING_CACHE_ENABLED") {
+            config.embedding.cache_enabled = cache_enabled
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid EMBEDDING_CACHE_ENABLED: {}", e))?;
+        }
+
         // API key is optional (not needed for Ollama)
         if let Ok(api_key) = env::var("OPENAI_API_KEY") {
             config.embedding.api_key = api_key;
@@ -305,16 +321,34 @@ impl Config {
             config.operational.log_level = level;
         }
 
+        // Security configuration
+        if let Ok(secret) = env::var("SEARCH_CURSOR_SECRET") {
+            config.security.search_cursor_secret = Some(secret);
+        }
+
         Ok(config)
     }
 
-    /// Validate the configuration
+    /// Validate the configuration against the built-in embedding providers
     pub fn validate(&self) -> Result<()> {
+        self.validate_with_registry(&EmbeddingRegistry::default())
+    }
+
+    /// Validate the configuration; the embedding provider must be registered
+    /// in `registry`
+    pub fn validate_with_registry(&self, registry: &EmbeddingRegistry) -> Result<()> {
         if self.database_url.is_empty() {
             return Err(anyhow::anyhow!("Database URL is required"));
         }
 
         // Validate embedding configuration
+        if !registry.contains(&self.embedding.provider) {
+            return Err(anyhow::anyhow!(
+                "Invalid embedding provider: {}. Registered providers: {}",
+                self.embedding.provider,
+                registry.providers().join(", ")
+            ));
+        }
         match self.embedding.provider.as_str() {
             "openai" => {
                 if self.embedding.api_key.is_empty() {
@@ -326,14 +360,8 @@ impl Config {
                     return Err(anyhow::anyhow!("Base URL is required for Ollama provider"));
                 }
             }
-            "mock" => {
-                // Mock provider for testing - no additional validation needed
-            }
             _ => {
-                return Err(anyhow::anyhow!(
-                    "Invalid embedding provider: {}. Must be 'openai', 'ollama', or 'mock'",
-                    self.embedding.provider
-                ));
+                // In-process and registered providers validate their own settings
             }
         }
 
@@ -430,8 +458,16 @@ impl Config {
 
     /// Validate MCP environment configuration
     pub fn validate_mcp_environment(&self) -> Result<()> {
+        self.validate_mcp_environment_with_registry(&EmbeddingRegistry::default())
+    }
+
+    /// Validate MCP environment configuration against the providers in `registry`
+    pub fn validate_mcp_environment_with_registry(
+        &self,
+        registry: &EmbeddingRegistry,
+    ) -> Result<()> {
         // Standard validation first
-        self.validate()?;
+        self.validate_with_registry(registry)?;
 
         // MCP-specific validations
         if self.embedding.provider == "openai" && self.embedding.api_key.len() < 20 {
@@ -704,6 +740,10 @@ pub struct SecurityConfiguration {
     /// Input validation configuration
     pub input_validation_enabled: bool,
     pub max_request_size_mb: u32,
+    /// Key for signing search pagination cursors; a random per-process key
+    /// is used when unset
+    #[serde(default)]
+    pub search_cursor_secret: Option<String>,
 }
 
 impl Default for SecurityConfiguration {
@@ -733,6 +773,7 @@ impl Default for SecurityConfiguration {
             vault_token_path: None,
             input_validation_enabled: true,
             max_request_size_mb: 10,
+            search_cursor_secret: None,
         }
     }
 }
@@ -803,6 +844,13 @@ mod tests {
         config.embedding.provider = "invalid".to_string();
         assert!(config.validate().is_err());
 
+        // Providers registered by the application are accepted
+        config.embedding.provider = "onnx-minilm".to_string();
+        assert!(config.validate().is_err());
+        let mut registry = EmbeddingRegistry::default();
+        registry.register("onnx-minilm", crate::embedding::hashing_provider_factory);
+        assert!(config.validate_with_registry(&registry).is_ok());
+
         // Empty model should fail
         config.embedding.provider = "ollama".to_string();
         config.embedding.model = String::new();
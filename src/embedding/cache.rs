@@ -0,0 +1,250 @@
+//! Persistent embedding cache
+//!
+//! Embeddings are keyed by `(model, content hash)`, using the same SHA-256
+//! hash as `Memory::calculate_content_hash`, so repeated texts (re-stored
+//! memories, repeated search queries, harvested conversations that keep
+//! saying the same thing) are embedded once per model. The PostgreSQL
+//! backend shares the cache between processes and survives restarts.
+
+use crate::memory::models::Memory;
+use crate::monitoring::MetricsCollector;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tracing::{debug, warn};
+
+/// Storage behind an `EmbeddingCache`
+#[async_trait]
+pub trait EmbeddingCacheBackend: Send + Sync {
+    async fn get(&self, model: &str, content_hash: &str) -> Result<Option<Vec<f32>>>;
+    async fn put(&self, model: &str, content_hash: &str, embedding: &[f32]) -> Result<()>;
+}
+
+/// Cache table created by migration 017
+pub struct PgEmbeddingCache {
+    pool: PgPool,
+}
+
+impl PgEmbeddingCache {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Delete entries not used for `max_age_days`
+    pub async fn prune_unused(&self, max_age_days: i32) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM embedding_cache WHERE last_used_at < NOW() - make_interval(days => $1)",
+        )
+        .bind(max_age_days)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl EmbeddingCacheBackend for PgEmbeddingCache {
+    async fn get(&self, model: &str, content_hash: &str) -> Result<Option<Vec<f32>>> {
+        let embedding = sqlx::query_scalar::<_, Vec<f32>>(
+            r#"
+            UPDATE embedding_cache
+            SET hit_count = hit_count + 1, last_used_at = NOW()
+            WHERE model = $1 AND content_hash = $2
+            RETURNING embedding
+            "#,
+        )
+        .bind(model)
+        .bind(content_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(embedding)
+    }
+
+    async fn put(&self, model: &str, content_hash: &str, embedding: &[f32]) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO embedding_cache (model, content_hash, embedding, dimension)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (model, content_hash) DO NOTHING
+            "#,
+        )
+        .bind(model)
+        .bind(content_hash)
+        .bind(embedding)
+        .bind(embedding.len() as i32)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Process-local backend for tests and deployments without a database
+#[derive(Default)]
+pub struct InMemoryEmbeddingCache {
+    entries: RwLock<HashMap<(String, String), Vec<f32>>>,
+}
+
+#[async_trait]
+impl EmbeddingCacheBackend for InMemoryEmbeddingCache {
+    async fn get(&self, model: &str, content_hash: &str) -> Result<Option<Vec<f32>>> {
+        let entries = self
+            .entries
+            .read()
+            .map_err(|_| anyhow::anyhow!("Embedding cache lock poisoned"))?;
+        Ok(entries
+            .get(&(model.to_string(), content_hash.to_string()))
+            .cloned())
+    }
+
+    async fn put(&self, model: &str, content_hash: &str, embedding: &[f32]) -> Result<()> {
+        let mut entries = self
+            .entries
+            .write()
+            .map_err(|_| anyhow::anyhow!("Embedding cache lock poisoned"))?;
+        entries.insert(
+            (model.to_string(), content_hash.to_string()),
+            embedding.to_vec(),
+        );
+        Ok(())
+    }
+}
+
+/// Hit/miss counts since the cache was created
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EmbeddingCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl EmbeddingCacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Embedding cache keyed by `(model, content hash)` with hit-rate tracking.
+/// Backend errors are logged and treated as misses so a cache outage never
+/// fails an embedding request.
+#[derive(Clone)]
+pub struct EmbeddingCache {
+    backend: Arc<dyn EmbeddingCacheBackend>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+    metrics: Option<Arc<MetricsCollector>>,
+}
+
+impl EmbeddingCache {
+    pub fn new(backend: Arc<dyn EmbeddingCacheBackend>) -> Self {
+        Self {
+            backend,
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+            metrics: None,
+        }
+    }
+
+    pub fn postgres(pool: PgPool) -> Self {
+        Self::new(Arc::new(PgEmbeddingCache::new(pool)))
+    }
+
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(InMemoryEmbeddingCache::default()))
+    }
+
+    /// Also report hits and misses to `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<MetricsCollector>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub async fn get(&self, model: &str, text: &str) -> Option<Vec<f32>> {
+        let content_hash = Memory::calculate_content_hash(text);
+        let cached = match self.backend.get(model, &content_hash).await {
+            Ok(cached) => cached,
+            Err(e) => {
+                warn!("Embedding cache lookup failed: {}", e);
+                None
+            }
+        };
+
+        let hit = cached.is_some();
+        if hit {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            debug!("Embedding cache hit for {} ({})", content_hash, model);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.record_embedding_cache(hit);
+        }
+        cached
+    }
+
+    pub async fn put(&self, model: &str, text: &str, embedding: &[f32]) {
+        let content_hash = Memory::calculate_content_hash(text);
+        if let Err(e) = self.backend.put(model, &content_hash, embedding).await {
+            warn!("Failed to store embedding in cache: {}", e);
+        }
+    }
+
+    pub fn stats(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl std::fmt::Debug for EmbeddingCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddingCache")
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cache_is_keyed_by_model_and_content() {
+        let cache = EmbeddingCache::in_memory();
+
+        assert!(cache.get("nomic-embed-text", "hello").await.is_none());
+        cache.put("nomic-embed-text", "hello", &[0.5, 0.5]).await;
+
+        assert_eq!(
+            cache.get("nomic-embed-text", "hello").await,
+            Some(vec![0.5, 0.5])
+        );
+        assert!(cache.get("mxbai-embed-large", "hello").await.is_none());
+        assert!(cache.get("nomic-embed-text", "hello!").await.is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 3);
+        assert_eq!(stats.hit_rate(), 0.25);
+    }
+
+    #[tokio::test]
+    async fn test_hits_are_reported_to_metrics() {
+        let metrics = Arc::new(MetricsCollector::new().expect("metrics collector"));
+        let cache = EmbeddingCache::in_memory().with_metrics(metrics.clone());
+
+        cache.put("model", "text", &[1.0]).await;
+        cache.get("model", "text").await;
+        cache.get("model", "other").await;
+
+        assert_eq!(metrics.embedding_cache_hits_total.get(), 1);
+        assert_eq!(metrics.embedding_cache_misses_total.get(), 1);
+    }
+}
@@ -0,0 +1,202 @@
+//! Feature-hashing embedding fallback
+//!
+//! `HashingEmbedder` is not a language model. It turns text into a fixed-size
+//! vector with signed FNV feature hashing over word unigrams, word bigrams and
+//! character trigrams. It needs no model download, no network and no GPU, and
+//! the same text always maps to the same unit vector, so it serves as a
+//! fallback for offline installs and tests. Similarity is lexical only: texts
+//! that share vocabulary end up close in cosine space, but synonyms and
+//! paraphrases do not. Use a real model (`ollama`, `openai` or a provider
+//! registered in an `EmbeddingRegistry`) where semantic recall matters.
+
+use super::EmbeddingService;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Dimension used when the model name does not specify one
+pub const DEFAULT_HASHING_DIMENSION: usize = 768;
+
+/// Prefix of the model names produced by `HashingEmbedder`
+pub const HASHING_MODEL_PREFIX: &str = "hashing";
+
+const UNIGRAM_WEIGHT: f32 = 1.0;
+const BIGRAM_WEIGHT: f32 = 0.5;
+const TRIGRAM_WEIGHT: f32 = 0.25;
+
+/// Deterministic feature-hashing embedder; a lexical fallback, not a trained model
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimension: usize,
+    model: String,
+}
+
+impl HashingEmbedder {
+    pub fn new(dimension: usize) -> Self {
+        let dimension = dimension.max(1);
+        Self {
+            dimension,
+            model: format!("{HASHING_MODEL_PREFIX}-{dimension}"),
+        }
+    }
+
+    /// Build from a configured model name; a trailing `-<n>` (e.g.
+    /// `hashing-384`) selects the dimension
+    pub fn from_model_name(model: &str) -> Self {
+        let dimension = model
+            .rsplit('-')
+            .next()
+            .and_then(|suffix| suffix.parse::<usize>().ok())
+            .filter(|dimension| *dimension > 0)
+            .unwrap_or(DEFAULT_HASHING_DIMENSION);
+        Self::new(dimension)
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Embed `text` synchronously
+    pub fn embed(&self, text: &str) -> Vec<f32> {
+        let mut embedding = vec![0.0f32; self.dimension];
+
+        let lowercase = text.to_lowercase();
+        let tokens: Vec<&str> = lowercase
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .collect();
+
+        if tokens.is_empty() {
+            // Keep empty input on the unit sphere so cosine distance stays defined
+            self.add_feature(&mut embedding, b"\x00empty", UNIGRAM_WEIGHT);
+            return embedding;
+        }
+
+        for token in &tokens {
+            self.add_feature(&mut embedding, token.as_bytes(), UNIGRAM_WEIGHT);
+
+            let padded: Vec<char> = format!("#{token}#").chars().collect();
+            for trigram in padded.windows(3) {
+                let trigram: String = trigram.iter().collect();
+                self.add_feature(&mut embedding, trigram.as_bytes(), TRIGRAM_WEIGHT);
+            }
+        }
+
+        for pair in tokens.windows(2) {
+            let bigram = format!("{} {}", pair[0], pair[1]);
+            self.add_feature(&mut embedding, bigram.as_bytes(), BIGRAM_WEIGHT);
+        }
+
+        let magnitude: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if magnitude > 0.0 {
+            for value in &mut embedding {
+                *value /= magnitude;
+            }
+        }
+
+        embedding
+    }
+
+    fn add_feature(&self, embedding: &mut [f32], feature: &[u8], weight: f32) {
+        let hash = fnv1a(feature);
+        let index = (hash % self.dimension as u64) as usize;
+        // The top bit is independent of the bucket and cancels out collisions on average
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        embedding[index] += sign * weight;
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASHING_DIMENSION)
+    }
+}
+
+/// 64-bit FNV-1a; stable across Rust versions and platforms, unlike `DefaultHasher`
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[async_trait]
+impl EmbeddingService for HashingEmbedder {
+    async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(self.embed(text))
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn provider_name(&self) -> &str {
+        "hashing"
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn embedding_dimension(&self) -> Option<usize> {
+        Some(self.dimension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[test]
+    fn test_embeddings_are_deterministic_unit_vectors() {
+        let embedder = HashingEmbedder::new(384);
+        let first = embedder.embed("The quick brown fox");
+        let second = HashingEmbedder::new(384).embed("The quick brown fox");
+
+        assert_eq!(first.len(), 384);
+        assert_eq!(first, second);
+        assert!((cosine(&first, &first) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_shared_vocabulary_scores_higher() {
+        let embedder = HashingEmbedder::default();
+        let query = embedder.embed("rust async runtime");
+        let related = embedder.embed("Tokio is an async runtime for Rust");
+        let unrelated = embedder.embed("grandma's apple pie recipe");
+
+        assert!(cosine(&query, &related) > cosine(&query, &unrelated));
+    }
+
+    #[test]
+    fn test_empty_text_is_normalized() {
+        let embedding = HashingEmbedder::new(64).embed("  ");
+        let magnitude: f32 = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((magnitude - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_dimension_from_model_name() {
+        assert_eq!(
+            HashingEmbedder::from_model_name("hashing-384").dimension(),
+            384
+        );
+        assert_eq!(
+            HashingEmbedder::from_model_name("nomic-embed-text").dimension(),
+            DEFAULT_HASHING_DIMENSION
+        );
+        assert_eq!(
+            HashingEmbedder::from_model_name("hashing-384").model(),
+            "hashing-384"
+        );
+    }
+}
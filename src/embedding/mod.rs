@@ -1,8 +1,21 @@
+mod cache;
+mod hashing;
+mod registry;
+
+pub use cache::{
+    EmbeddingCache, EmbeddingCacheBackend, EmbeddingCacheStats, InMemoryEmbeddingCache,
+    PgEmbeddingCache,
+};
+pub use hashing::{HashingEmbedder, DEFAULT_HASHING_DIMENSION, HASHING_MODEL_PREFIX};
+pub use registry::{hashing_provider_factory, EmbeddingProviderFactory, EmbeddingRegistry};
+
+use crate::config::EmbeddingConfig;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use backoff::{future::retry, ExponentialBackoff};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 use tracing::{info, warn};
 
@@ -11,6 +24,21 @@ use tracing::{info, warn};
 pub trait EmbeddingService: Send + Sync {
     async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>>;
     async fn health_check(&self) -> Result<()>;
+
+    /// Name the provider is registered under
+    fn provider_name(&self) -> &str {
+        "custom"
+    }
+
+    /// Model producing the vectors
+    fn model_name(&self) -> &str {
+        "unknown"
+    }
+
+    /// Length of the produced vectors, if known without calling the model
+    fn embedding_dimension(&self) -> Option<usize> {
+        None
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -21,6 +49,9 @@ pub struct SimpleEmbedder {
     base_url: String,
     provider: EmbeddingProvider,
     fallback_models: Vec<String>,
+    hashing: Option<HashingEmbedder>,
+    delegate: Option<DelegateService>,
+    cache: Option<EmbeddingCache>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -28,6 +59,22 @@ pub enum EmbeddingProvider {
     OpenAI,
     Ollama,
     Mock, // For testing
+    /// Feature-hashing fallback with no model, see `HashingEmbedder`
+    Hashing,
+    /// A provider registered in an `EmbeddingRegistry`
+    Custom(String),
+}
+
+/// Embedding service created by a registry factory
+#[derive(Clone)]
+struct DelegateService(Arc<dyn EmbeddingService>);
+
+impl std::fmt::Debug for DelegateService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DelegateService")
+            .field(&self.0.model_name())
+            .finish()
+    }
 }
 
 // OpenAI API request/response structures
@@ -91,6 +138,9 @@ impl SimpleEmbedder {
                 "text-embedding-3-large".to_string(),
                 "text-embedding-ada-002".to_string(),
             ],
+            hashing: None,
+            delegate: None,
+            cache: None,
         }
     }
 
@@ -112,6 +162,9 @@ impl SimpleEmbedder {
                 "all-minilm".to_string(),
                 "all-mpnet-base-v2".to_string(),
             ],
+            hashing: None,
+            delegate: None,
+            cache: None,
         }
     }
 
@@ -128,10 +181,80 @@ impl SimpleEmbedder {
             base_url: "http://mock:11434".to_string(),
             provider: EmbeddingProvider::Mock,
             fallback_models: vec!["mock-model-2".to_string()],
+            hashing: None,
+            delegate: None,
+            cache: None,
+        }
+    }
+
+    /// Feature-hashing fallback; `model` may carry the dimension, e.g. `hashing-384`
+    pub fn new_hashing(model: String) -> Self {
+        let hashing = HashingEmbedder::from_model_name(&model);
+
+        Self {
+            client: Client::new(),
+            api_key: String::new(),
+            model: hashing.model().to_string(),
+            base_url: String::new(),
+            provider: EmbeddingProvider::Hashing,
+            fallback_models: Vec::new(),
+            hashing: Some(hashing),
+            delegate: None,
+            cache: None,
+        }
+    }
+
+    /// Wrap a service created by an `EmbeddingRegistry` factory
+    pub fn from_service(provider: &str, service: Arc<dyn EmbeddingService>) -> Self {
+        Self {
+            client: Client::new(),
+            api_key: String::new(),
+            model: service.model_name().to_string(),
+            base_url: String::new(),
+            provider: EmbeddingProvider::Custom(provider.to_string()),
+            fallback_models: Vec::new(),
+            hashing: None,
+            delegate: Some(DelegateService(service)),
+            cache: None,
+        }
+    }
+
+    /// Build one of the built-in providers (`openai`, `ollama`, `mock`, `hashing`)
+    pub fn from_builtin_config(config: &EmbeddingConfig) -> Result<Self> {
+        match config.provider.as_str() {
+            "openai" => Ok(Self::new(config.api_key.clone())
+                .with_model(config.model.clone())
+                .with_base_url(config.base_url.clone())),
+            "ollama" => Ok(Self::new_ollama(
+                config.base_url.clone(),
+                config.model.clone(),
+            )),
+            "mock" => Ok(Self::new_mock()),
+            "hashing" => Ok(Self::new_hashing(config.model.clone())),
+            _ => Err(anyhow::anyhow!(
+                "Unsupported embedding provider: {}",
+                config.provider
+            )),
+        }
+    }
+
+    /// Build the provider selected by `config.provider`, looking up
+    /// non-built-in providers in `registry`
+    pub fn from_config(config: &EmbeddingConfig, registry: &EmbeddingRegistry) -> Result<Self> {
+        match Self::from_builtin_config(config) {
+            Ok(embedder) => Ok(embedder),
+            Err(_) if registry.contains(&config.provider) => Ok(Self::from_service(
+                &config.provider,
+                registry.create(config)?,
+            )),
+            Err(e) => Err(e),
         }
     }
 
     pub fn with_model(mut self, model: String) -> Self {
+        if self.provider == EmbeddingProvider::Hashing {
+            return Self::new_hashing(model);
+        }
         self.model = model;
         self
     }
@@ -141,8 +264,44 @@ impl SimpleEmbedder {
         self
     }
 
-    /// Generate embedding for text with automatic retry
+    /// Reuse embeddings of previously seen texts instead of calling the provider
+    pub fn with_cache(mut self, cache: EmbeddingCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn cache(&self) -> Option<&EmbeddingCache> {
+        self.cache.as_ref()
+    }
+
+    /// Generate embedding for text, served from the cache when possible
     pub async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>> {
+        if let Some(embedding) = self.cached_embedding(text).await {
+            return Ok(embedding);
+        }
+
+        let embedding = self.generate_embedding_uncached(text).await?;
+        self.cache_embedding(text, &embedding).await;
+        Ok(embedding)
+    }
+
+    /// In-process providers are cheaper to run than a cache round trip
+    fn active_cache(&self) -> Option<&EmbeddingCache> {
+        self.cache.as_ref().filter(|_| !self.is_in_process())
+    }
+
+    async fn cached_embedding(&self, text: &str) -> Option<Vec<f32>> {
+        self.active_cache()?.get(&self.model, text).await
+    }
+
+    async fn cache_embedding(&self, text: &str, embedding: &[f32]) {
+        if let Some(cache) = self.active_cache() {
+            cache.put(&self.model, text, embedding).await;
+        }
+    }
+
+    /// Generate embedding for text with automatic retry
+    async fn generate_embedding_uncached(&self, text: &str) -> Result<Vec<f32>> {
         info!("Generating embedding for text of length: {}", text.len());
 
         let operation = || async {
@@ -171,9 +330,28 @@ impl SimpleEmbedder {
             EmbeddingProvider::OpenAI => self.generate_openai_embedding(text).await,
             EmbeddingProvider::Ollama => self.generate_ollama_embedding(text).await,
             EmbeddingProvider::Mock => self.generate_mock_embedding(text).await,
+            EmbeddingProvider::Hashing => match &self.hashing {
+                Some(hashing) => Ok(hashing.embed(text)),
+                None => Err(anyhow::anyhow!("Hashing embedder is not initialized")),
+            },
+            EmbeddingProvider::Custom(ref name) => match &self.delegate {
+                Some(DelegateService(service)) => service.generate_embedding(text).await,
+                None => Err(anyhow::anyhow!(
+                    "Embedding provider '{}' is not initialized",
+                    name
+                )),
+            },
         }
     }
 
+    /// Whether embeddings are computed in this process rather than by a remote API
+    pub fn is_in_process(&self) -> bool {
+        matches!(
+            self.provider,
+            EmbeddingProvider::Mock | EmbeddingProvider::Hashing
+        )
+    }
+
     async fn generate_openai_embedding(&self, text: &str) -> Result<Vec<f32>> {
         let request = OpenAIEmbeddingRequest {
             input: text.to_string(),
@@ -298,8 +476,16 @@ impl SimpleEmbedder {
             let mut chunk_embeddings = Vec::with_capacity(chunk.len());
 
             for text in chunk {
-                match self.generate_embedding(text).await {
-                    Ok(embedding) => chunk_embeddings.push(embedding),
+                if let Some(embedding) = self.cached_embedding(text).await {
+                    chunk_embeddings.push(embedding);
+                    continue;
+                }
+
+                match self.generate_embedding_uncached(text).await {
+                    Ok(embedding) => {
+                        self.cache_embedding(text, &embedding).await;
+                        chunk_embeddings.push(embedding);
+                    }
                     Err(e) => {
                         warn!("Failed to generate embedding for text: {}", e);
                         return Err(e);
@@ -307,7 +493,9 @@ impl SimpleEmbedder {
                 }
 
                 // Small delay to be respectful to the API
-                tokio::time::sleep(Duration::from_millis(100)).await;
+                if !self.is_in_process() {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
             }
 
             embeddings.extend(chunk_embeddings);
@@ -336,9 +524,24 @@ impl SimpleEmbedder {
                 }
             }
             EmbeddingProvider::Mock => 768, // Consistent mock embedding dimension
+            EmbeddingProvider::Hashing => self
+                .hashing
+                .as_ref()
+                .map(HashingEmbedder::dimension)
+                .unwrap_or(DEFAULT_HASHING_DIMENSION),
+            EmbeddingProvider::Custom(_) => self
+                .delegate
+                .as_ref()
+                .and_then(|DelegateService(service)| service.embedding_dimension())
+                .unwrap_or(768),
         }
     }
 
+    /// Model used for embeddings
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
     /// Get the provider type
     pub fn provider(&self) -> &EmbeddingProvider {
         &self.provider
@@ -568,6 +771,24 @@ impl EmbeddingService for SimpleEmbedder {
             ))
         }
     }
+
+    fn provider_name(&self) -> &str {
+        match &self.provider {
+            EmbeddingProvider::OpenAI => "openai",
+            EmbeddingProvider::Ollama => "ollama",
+            EmbeddingProvider::Mock => "mock",
+            EmbeddingProvider::Hashing => "hashing",
+            EmbeddingProvider::Custom(name) => name,
+        }
+    }
+
+    fn model_name(&self) -> &str {
+        &self.model
+    }
+
+    fn embedding_dimension(&self) -> Option<usize> {
+        Some(SimpleEmbedder::embedding_dimension(self))
+    }
 }
 
 /// Information about an embedding model
@@ -665,5 +886,92 @@ mod tests {
 
         let mock_embedder = SimpleEmbedder::new_mock();
         assert_eq!(mock_embedder.provider(), &EmbeddingProvider::Mock);
+
+        let local_embedder = SimpleEmbedder::new_hashing("hashing-384".to_string());
+        assert_eq!(local_embedder.provider(), &EmbeddingProvider::Hashing);
+        assert_eq!(local_embedder.embedding_dimension(), 384);
+    }
+
+    #[tokio::test]
+    async fn test_from_config_uses_registry_for_custom_providers() {
+        let mut config = crate::config::EmbeddingConfig {
+            provider: "onnx-minilm".to_string(),
+            model: "all-minilm-384".to_string(),
+            api_key: String::new(),
+            base_url: String::new(),
+            timeout_seconds: 30,
+            cache_enabled: false,
+        };
+
+        let mut registry = EmbeddingRegistry::default();
+        assert!(SimpleEmbedder::from_config(&config, &registry).is_err());
+
+        registry.register("onnx-minilm", hashing_provider_factory);
+        let embedder =
+            SimpleEmbedder::from_config(&config, &registry).expect("registered provider");
+        assert_eq!(
+            embedder.provider(),
+            &EmbeddingProvider::Custom("onnx-minilm".to_string())
+        );
+        assert_eq!(embedder.embedding_dimension(), 384);
+        let embedding = embedder
+            .generate_embedding("works offline")
+            .await
+            .expect("delegated embedding");
+        assert_eq!(embedding.len(), 384);
+
+        config.provider = "hashing".to_string();
+        let hashing = SimpleEmbedder::from_config(&config, &registry).expect("built-in provider");
+        assert_eq!(hashing.provider(), &EmbeddingProvider::Hashing);
+    }
+
+    struct CountingService {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EmbeddingService for CountingService {
+        async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>> {
+            self.calls
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Ok(vec![text.len() as f32; 4])
+        }
+
+        async fn health_check(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn model_name(&self) -> &str {
+            "counting-4"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_skips_provider_for_repeated_text() {
+        let service = Arc::new(CountingService {
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let cache = EmbeddingCache::in_memory();
+        let embedder =
+            SimpleEmbedder::from_service("counting", service.clone()).with_cache(cache.clone());
+
+        let first = embedder
+            .generate_embedding("same text")
+            .await
+            .expect("embedding");
+        let second = embedder
+            .generate_embedding("same text")
+            .await
+            .expect("embedding");
+        let batch = embedder
+            .generate_embeddings_batch(&["same text".to_string(), "other".to_string()])
+            .await
+            .expect("batch embeddings");
+
+        assert_eq!(first, second);
+        assert_eq!(batch[0], first);
+        assert_eq!(service.calls.load(std::sync::atomic::Ordering::Relaxed), 2);
+        assert_eq!(cache.stats().hits, 2);
+        assert_eq!(cache.stats().misses, 2);
     }
 }
@@ -0,0 +1,150 @@
+//! Embedding provider registry
+//!
+//! Maps the `EmbeddingConfig.provider` name to a factory producing an
+//! `EmbeddingService`. The built-in providers (`openai`, `ollama`, `mock` and
+//! the `hashing` fallback) are registered by default. The crate ships no
+//! embedding model of its own; applications that run one themselves register
+//! it here under their own provider name.
+
+use super::{EmbeddingService, HashingEmbedder, SimpleEmbedder};
+use crate::config::EmbeddingConfig;
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Builds an embedding service from configuration
+pub type EmbeddingProviderFactory =
+    Arc<dyn Fn(&EmbeddingConfig) -> Result<Arc<dyn EmbeddingService>> + Send + Sync>;
+
+/// Registry of embedding providers keyed by provider name
+#[derive(Clone)]
+pub struct EmbeddingRegistry {
+    factories: BTreeMap<String, EmbeddingProviderFactory>,
+}
+
+impl EmbeddingRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// A registry with the `openai`, `ollama`, `mock` and `hashing` providers
+    pub fn with_builtin_providers() -> Self {
+        let mut registry = Self::new();
+        for provider in ["openai", "ollama", "mock", "hashing"] {
+            registry.register(provider, |config: &EmbeddingConfig| {
+                let embedder = SimpleEmbedder::from_builtin_config(config)?;
+                Ok(Arc::new(embedder) as Arc<dyn EmbeddingService>)
+            });
+        }
+        registry
+    }
+
+    /// Register (or replace) the factory for `name`
+    pub fn register<F>(&mut self, name: impl Into<String>, factory: F) -> &mut Self
+    where
+        F: Fn(&EmbeddingConfig) -> Result<Arc<dyn EmbeddingService>> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.into(), Arc::new(factory));
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Registered provider names in alphabetical order
+    pub fn providers(&self) -> Vec<&str> {
+        self.factories.keys().map(String::as_str).collect()
+    }
+
+    /// Create the service selected by `config.provider`
+    pub fn create(&self, config: &EmbeddingConfig) -> Result<Arc<dyn EmbeddingService>> {
+        let factory = self.factories.get(&config.provider).ok_or_else(|| {
+            anyhow!(
+                "Unsupported embedding provider: {}. Registered providers: {}",
+                config.provider,
+                self.providers().join(", ")
+            )
+        })?;
+        factory(config)
+    }
+}
+
+impl Default for EmbeddingRegistry {
+    fn default() -> Self {
+        Self::with_builtin_providers()
+    }
+}
+
+impl std::fmt::Debug for EmbeddingRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddingRegistry")
+            .field("providers", &self.providers())
+            .finish()
+    }
+}
+
+/// Factory for the hashing fallback, for registering it under another name
+pub fn hashing_provider_factory(config: &EmbeddingConfig) -> Result<Arc<dyn EmbeddingService>> {
+    Ok(Arc::new(HashingEmbedder::from_model_name(&config.model)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(provider: &str, model: &str) -> EmbeddingConfig {
+        EmbeddingConfig {
+            provider: provider.to_string(),
+            model: model.to_string(),
+            api_key: String::new(),
+            base_url: "http://localhost:11434".to_string(),
+            timeout_seconds: 30,
+            cache_enabled: false,
+        }
+    }
+
+    #[test]
+    fn test_builtin_providers_registered() {
+        let registry = EmbeddingRegistry::default();
+        assert_eq!(
+            registry.providers(),
+            vec!["hashing", "mock", "ollama", "openai"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_local_provider() {
+        let registry = EmbeddingRegistry::default();
+        let service = registry
+            .create(&config("hashing", "hashing-256"))
+            .expect("hashing provider should be registered");
+
+        assert_eq!(service.provider_name(), "hashing");
+        assert_eq!(service.model_name(), "hashing-256");
+        let embedding = service
+            .generate_embedding("offline embeddings")
+            .await
+            .expect("hashing embedding never fails");
+        assert_eq!(embedding.len(), 256);
+    }
+
+    #[tokio::test]
+    async fn test_register_custom_provider() {
+        let mut registry = EmbeddingRegistry::new();
+        registry.register("custom-hashing", hashing_provider_factory);
+
+        assert!(registry.contains("custom-hashing"));
+        assert!(registry
+            .create(&config("ollama", "nomic-embed-text"))
+            .is_err());
+
+        let service = registry
+            .create(&config("custom-hashing", "hashing-384"))
+            .expect("custom provider should be created");
+        assert_eq!(service.embedding_dimension(), Some(384));
+    }
+}
@@ -195,7 +195,10 @@ mod tests {
             id: Uuid::new_v4(),
             content: "Test memory content".to_string(),
             content_hash: "test_hash".to_string(),
+            namespace: "default".to_string(),
             embedding: None,
+            embedding_model: None,
+            embedding_dimension: None,
             tier,
             status: MemoryStatus::Active,
             importance_score: importance,
@@ -206,6 +209,8 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             expires_at: None,
+            valid_from: None,
+            valid_to: None,
             consolidation_strength: 1.0,
             decay_rate: 1.0,
             recall_probability: Some(0.8),
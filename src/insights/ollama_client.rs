@@ -328,6 +328,129 @@ Requirements:
         }
     }
 
+    /// Send `prompt` to the model and return its answer
+    async fn complete(
+        &self,
+        prompt: String,
+        temperature: f32,
+        max_tokens: u32,
+    ) -> Result<String, OllamaClientError> {
+        let request = OllamaRequest {
+            model: self.config.model.clone(),
+            prompt,
+            stream: self.config.enable_streaming, // Use config setting for streaming
+            options: OllamaOptions {
+                temperature,
+                top_p: 0.9,
+                max_tokens,
+            },
+        };
+
+        let url = format!("{}/api/generate", self.config.base_url);
+
+        self.execute_with_retry(|| async {
+            debug!("Sending request to Ollama: {}", url);
+
+            let response = self.client.post(&url).json(&request).send().await?;
+
+            if !response.status().is_success() {
+                return Err(OllamaClientError::ServiceUnavailable(format!(
+                    "HTTP {}: {}",
+                    response.status(),
+                    response.status()
+                )));
+            }
+
+            let ollama_response: OllamaResponse = response.json().await?;
+
+            if !ollama_response.done {
+                return Err(OllamaClientError::MalformedResponse(
+                    "Received incomplete response from Ollama".to_string(),
+                ));
+            }
+
+            // Use response field if available, otherwise fall back to thinking field
+            // Some models (like gpt-oss) use "thinking" for Chain-of-Thought reasoning
+            let response_text = if !ollama_response.response.is_empty() {
+                ollama_response.response
+            } else if !ollama_response.thinking.is_empty() {
+                ollama_response.thinking
+            } else {
+                return Err(OllamaClientError::MalformedResponse(
+                    "Received empty response from Ollama (both response and thinking fields are empty)".to_string(),
+                ));
+            };
+
+            Ok(response_text)
+        })
+        .await
+    }
+
+    /// Ask the model whether `new` contradicts `existing`. Returns the
+    /// model's reason and confidence when it does.
+    pub async fn check_contradiction(
+        &self,
+        existing: &str,
+        new: &str,
+    ) -> Result<Option<(String, f64)>, OllamaClientError> {
+        let prompt = format!(
+            r#"You check stored facts for contradictions.
+
+Earlier statement: {existing}
+New statement: {new}
+
+Do these statements contradict each other, so that both cannot be true at the same time? Statements about different subjects, or that only add detail, do not contradict.
+
+Respond ONLY with a valid JSON object in this exact format:
+{{
+  "contradicts": true,
+  "confidence": 0.85,
+  "reason": "Brief explanation"
+}}"#
+        );
+
+        let response = self.complete(prompt, 0.0, 200).await?;
+        debug!("Received contradiction check from Ollama: {}", response);
+        self.parse_contradiction_response(&response)
+    }
+
+    /// Parse the model's contradiction verdict
+    fn parse_contradiction_response(
+        &self,
+        response_text: &str,
+    ) -> Result<Option<(String, f64)>, OllamaClientError> {
+        let json_start = response_text.find('{').ok_or_else(|| {
+            OllamaClientError::MalformedResponse("No JSON object found in response".to_string())
+        })?;
+        let json_end = response_text.rfind('}').ok_or_else(|| {
+            OllamaClientError::MalformedResponse("No closing brace found in JSON".to_string())
+        })?;
+
+        #[derive(Deserialize)]
+        struct ParsedVerdict {
+            contradicts: bool,
+            confidence: f64,
+            reason: Option<String>,
+        }
+
+        let parsed: ParsedVerdict = serde_json::from_str(&response_text[json_start..=json_end])
+            .map_err(|e| OllamaClientError::ParseError(format!("JSON parsing failed: {}", e)))?;
+        if !(0.0..=1.0).contains(&parsed.confidence) {
+            return Err(OllamaClientError::ParseError(
+                "Confidence must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+
+        Ok(parsed.contradicts.then(|| {
+            (
+                parsed
+                    .reason
+                    .unwrap_or_else(|| "The model found a contradiction".to_string()),
+                parsed.confidence,
+            )
+        }))
+    }
+
     #[cfg(feature = "codex-dreams")]
     pub async fn health_check(&self) -> bool {
         match self
@@ -388,56 +511,7 @@ impl OllamaClientTrait for OllamaClient {
         let memory_ids: Vec<Uuid> = memories.iter().map(|m| m.id).collect();
         let prompt = self.create_system_prompt(&memories);
 
-        let request = OllamaRequest {
-            model: self.config.model.clone(),
-            prompt,
-            stream: self.config.enable_streaming, // Use config setting for streaming
-            options: OllamaOptions {
-                temperature: 0.7,
-                top_p: 0.9,
-                max_tokens: 1000,
-            },
-        };
-
-        let url = format!("{}/api/generate", self.config.base_url);
-
-        let response = self
-            .execute_with_retry(|| async {
-                debug!("Sending request to Ollama: {}", url);
-
-                let response = self.client.post(&url).json(&request).send().await?;
-
-                if !response.status().is_success() {
-                    return Err(OllamaClientError::ServiceUnavailable(format!(
-                        "HTTP {}: {}",
-                        response.status(),
-                        response.status()
-                    )));
-                }
-
-                let ollama_response: OllamaResponse = response.json().await?;
-
-                if !ollama_response.done {
-                    return Err(OllamaClientError::MalformedResponse(
-                        "Received incomplete response from Ollama".to_string(),
-                    ));
-                }
-
-                // Use response field if available, otherwise fall back to thinking field
-                // Some models (like gpt-oss) use "thinking" for Chain-of-Thought reasoning
-                let response_text = if !ollama_response.response.is_empty() {
-                    ollama_response.response
-                } else if !ollama_response.thinking.is_empty() {
-                    ollama_response.thinking
-                } else {
-                    return Err(OllamaClientError::MalformedResponse(
-                        "Received empty response from Ollama (both response and thinking fields are empty)".to_string(),
-                    ));
-                };
-
-                Ok(response_text)
-            })
-            .await?;
+        let response = self.complete(prompt, 0.7, 1000).await?;
 
         debug!("Received response from Ollama: {}", response);
 
@@ -579,7 +653,10 @@ mod tests {
             id: Uuid::new_v4(),
             content: content.to_string(),
             content_hash: "test_hash".to_string(),
+            namespace: "default".to_string(),
             embedding: None,
+            embedding_model: None,
+            embedding_dimension: None,
             tier: MemoryTier::Working,
             status: MemoryStatus::Active,
             importance_score: 0.8,
@@ -590,6 +667,8 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             expires_at: None,
+            valid_from: None,
+            valid_to: None,
             consolidation_strength: 1.0,
             decay_rate: 1.0,
             recall_probability: Some(0.9),
@@ -763,4 +842,23 @@ mod tests {
         let result = client.parse_insight_response(invalid_type, memory_ids);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_contradiction_response() {
+        let client = OllamaClient::new(OllamaConfig::default()).unwrap();
+
+        let found = client
+            .parse_contradiction_response(
+                r#"Sure. {"contradicts": true, "confidence": 0.9, "reason": "Different cities"}"#,
+            )
+            .unwrap();
+        assert_eq!(found, Some(("Different cities".to_string(), 0.9)));
+
+        let none = client
+            .parse_contradiction_response(r#"{"contradicts": false, "confidence": 0.7}"#)
+            .unwrap();
+        assert_eq!(none, None);
+
+        assert!(client.parse_contradiction_response("no").is_err());
+    }
 }
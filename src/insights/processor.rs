@@ -20,7 +20,7 @@ use super::storage::InsightStorage;
 #[cfg(feature = "codex-dreams")]
 use crate::memory::error::{MemoryError, Result};
 #[cfg(feature = "codex-dreams")]
-use crate::memory::{Memory, MemoryRepository, MemoryStatus};
+use crate::memory::{FieldEncryption, Memory, MemoryRepository, MemoryStatus};
 
 #[cfg(feature = "codex-dreams")]
 use chrono::{DateTime, Duration, Utc};
@@ -29,7 +29,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "codex-dreams")]
 use std::collections::HashMap;
 #[cfg(feature = "codex-dreams")]
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 #[cfg(feature = "codex-dreams")]
 use tokio::sync::Mutex;
 #[cfg(feature = "codex-dreams")]
@@ -126,6 +126,8 @@ pub struct InsightsProcessor {
     config: ProcessorConfig,
     /// Runtime statistics
     stats: Arc<Mutex<ProcessingStats>>,
+    /// Decrypts memories encrypted at rest before they reach a prompt
+    field_encryption: OnceLock<Arc<FieldEncryption>>,
 }
 
 #[cfg(feature = "codex-dreams")]
@@ -256,12 +258,40 @@ impl InsightsProcessor {
             circuit_breaker,
             config,
             stats: Arc::new(Mutex::new(ProcessingStats::default())),
+            field_encryption: OnceLock::new(),
         }
     }
 
+    /// Decrypt memories encrypted at rest before building prompts from them
+    pub fn set_field_encryption(&self, encryption: Arc<FieldEncryption>) {
+        if self.field_encryption.set(encryption).is_err() {
+            warn!("Field encryption of the insights processor was already set");
+        }
+    }
+
+    /// The Ollama client used for generation
+    pub fn ollama_client(&self) -> Arc<OllamaClient> {
+        self.ollama_client.clone()
+    }
+
     /// Process a batch of memories to generate insights
-    #[instrument(skip(self), fields(batch_size = memory_ids.len()))]
     pub async fn process_batch(&self, memory_ids: Vec<Uuid>) -> Result<ProcessingResult> {
+        self.process_batch_with_progress(memory_ids, |_, _| async {})
+            .await
+    }
+
+    /// Process a batch of memories, calling `on_progress(processed, total)`
+    /// after every chunk so callers can report progress to clients
+    #[instrument(skip(self, on_progress), fields(batch_size = memory_ids.len()))]
+    pub async fn process_batch_with_progress<F, Fut>(
+        &self,
+        memory_ids: Vec<Uuid>,
+        on_progress: F,
+    ) -> Result<ProcessingResult>
+    where
+        F: Fn(usize, usize) -> Fut,
+        Fut: std::future::Future<Output = ()>,
+    {
         let start_time = Utc::now();
         info!("Starting batch processing of {} memories", memory_ids.len());
 
@@ -283,8 +313,13 @@ impl InsightsProcessor {
         let mut errors_by_type: HashMap<String, u64> = HashMap::new();
 
         // Process in configured batch sizes
+        let mut attempted = 0;
         for chunk in memory_ids.chunks(self.config.batch_size) {
-            match self.process_memory_chunk(chunk).await {
+            let chunk_result = self.process_memory_chunk(chunk).await;
+            attempted += chunk.len();
+            on_progress(attempted, memory_ids.len()).await;
+
+            match chunk_result {
                 Ok(mut chunk_insights) => {
                     total_processed += chunk.len();
                     let insight_count = chunk_insights.len();
@@ -559,7 +594,13 @@ impl InsightsProcessor {
                 Ok(memory) => {
                     // Only process active memories
                     if matches!(memory.status, MemoryStatus::Active) {
-                        memories.push(memory);
+                        match self.field_encryption.get() {
+                            Some(encryption) => match encryption.decrypt_memory(memory) {
+                                Ok(memory) => memories.push(memory),
+                                Err(e) => error!("Failed to decrypt memory {}: {}", memory_id, e),
+                            },
+                            None => memories.push(memory),
+                        }
                     } else {
                         debug!("Skipping inactive memory {}", memory_id);
                     }
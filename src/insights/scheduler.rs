@@ -692,6 +692,11 @@ impl InsightScheduler {
             include_facets: Some(false),
             ranking_boost: None,
             explain_score: Some(false),
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         debug!("Fetching candidate memories for insights generation");
@@ -700,6 +705,8 @@ impl InsightScheduler {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to search for candidate memories: {}", e))?;
 
+        // Only ids are taken; the processor decrypts memories encrypted at
+        // rest when it fetches them for prompts
         let memory_ids: Vec<uuid::Uuid> = search_results
             .results
             .into_iter()
@@ -17,6 +17,8 @@ use crate::embedding::EmbeddingService;
 #[cfg(feature = "codex-dreams")]
 use crate::memory::error::{MemoryError, Result};
 #[cfg(feature = "codex-dreams")]
+use crate::memory::models::DEFAULT_NAMESPACE;
+#[cfg(feature = "codex-dreams")]
 use chrono::{DateTime, Utc};
 #[cfg(feature = "codex-dreams")]
 use pgvector::Vector;
@@ -60,6 +62,9 @@ pub struct SearchResult {
 pub struct InsightStorage {
     pool: Arc<PgPool>,
     embedder: Arc<dyn EmbeddingService>,
+    // Namespace every read is restricted to; new insights inherit the
+    // namespace of their first source memory
+    namespace: String,
     // Configuration for pruning and quality thresholds
     min_feedback_score: f64,
     max_versions_to_keep: i32,
@@ -72,11 +77,28 @@ impl InsightStorage {
         Self {
             pool,
             embedder,
+            namespace: DEFAULT_NAMESPACE.to_string(),
             min_feedback_score: 0.3, // Configurable threshold for pruning
             max_versions_to_keep: 2, // Current + previous only
         }
     }
 
+    /// Namespace this storage handle is restricted to
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// A handle on the same database restricted to `namespace`
+    pub fn with_namespace(&self, namespace: impl Into<String>) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            embedder: self.embedder.clone(),
+            namespace: namespace.into(),
+            min_feedback_score: self.min_feedback_score,
+            max_versions_to_keep: self.max_versions_to_keep,
+        }
+    }
+
     /// Store a new insight with vector embedding generation
     pub async fn store(&self, mut insight: Insight) -> Result<Uuid> {
         let mut tx = self.pool.begin().await.map_err(MemoryError::Database)?;
@@ -94,9 +116,13 @@ impl InsightStorage {
             INSERT INTO insights (
                 id, content, insight_type, confidence_score, source_memory_ids,
                 metadata, tags, tier, created_at, updated_at, version,
-                previous_version, feedback_score, embedding
+                previous_version, feedback_score, embedding, namespace
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14,
+                COALESCE(
+                    (SELECT namespace FROM memories WHERE id = ($5->>0)::uuid),
+                    $15
+                )
             )
         "#;
 
@@ -121,6 +147,7 @@ impl InsightStorage {
             .bind(&insight.previous_version)
             .bind(&insight.feedback_score)
             .bind(&embedding)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await
             .map_err(MemoryError::Database)?;
@@ -142,12 +169,13 @@ impl InsightStorage {
         let archive_query = r#"
             UPDATE insights 
             SET tier = 'archived', updated_at = $1
-            WHERE id = $2
+            WHERE id = $2 AND namespace = $3
         "#;
 
         sqlx::query(archive_query)
             .bind(Utc::now())
             .bind(id)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await
             .map_err(MemoryError::Database)?;
@@ -181,9 +209,9 @@ impl InsightStorage {
             INSERT INTO insights (
                 id, content, insight_type, confidence_score, source_memory_ids,
                 metadata, tags, tier, created_at, updated_at, version,
-                previous_version_id, feedback_score, embedding
+                previous_version_id, feedback_score, embedding, namespace
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15
             )
         "#;
 
@@ -208,6 +236,7 @@ impl InsightStorage {
             .bind(&new_version.previous_version_id)
             .bind(&new_version.feedback_score)
             .bind(&new_version.embedding)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await
             .map_err(MemoryError::Database)?;
@@ -225,13 +254,14 @@ impl InsightStorage {
                    metadata, tags, tier, created_at, updated_at, version,
                    previous_version_id, feedback_score, embedding
             FROM insights
-            WHERE id = $1 AND tier != 'archived'
+            WHERE id = $1 AND tier != 'archived' AND namespace = $2
             ORDER BY version DESC
             LIMIT 1
         "#;
 
         let row = sqlx::query(query)
             .bind(id)
+            .bind(&self.namespace)
             .fetch_optional(&*self.pool)
             .await
             .map_err(MemoryError::Database)?;
@@ -251,13 +281,14 @@ impl InsightStorage {
                    metadata, tags, tier, created_at, updated_at, version,
                    previous_version_id, feedback_score, embedding
             FROM insights
-            WHERE id = $1 AND tier != 'archived'
+            WHERE id = $1 AND tier != 'archived' AND namespace = $2
             ORDER BY version DESC
             LIMIT 1
         "#;
 
         let row = sqlx::query(query)
             .bind(id)
+            .bind(&self.namespace)
             .fetch_optional(&mut **tx)
             .await
             .map_err(MemoryError::Database)?;
@@ -281,6 +312,7 @@ impl InsightStorage {
             FROM insights
             WHERE tier != 'archived'
               AND embedding IS NOT NULL
+              AND namespace = $3
             ORDER BY similarity_score DESC
             LIMIT $2
         "#;
@@ -288,6 +320,7 @@ impl InsightStorage {
         let rows = sqlx::query(search_query)
             .bind(&query_embedding)
             .bind(limit as i64)
+            .bind(&self.namespace)
             .fetch_all(&*self.pool)
             .await
             .map_err(MemoryError::Database)?;
@@ -354,6 +387,7 @@ impl InsightStorage {
             WHERE feedback_score < $2
               AND tier != 'archived'
               AND created_at < $3  -- Only prune insights older than 1 day
+              AND namespace = $4
         "#;
 
         let one_day_ago = Utc::now() - chrono::Duration::days(1);
@@ -361,6 +395,7 @@ impl InsightStorage {
             .bind(Utc::now())
             .bind(threshold)
             .bind(one_day_ago)
+            .bind(&self.namespace)
             .execute(&*self.pool)
             .await
             .map_err(MemoryError::Database)?;
@@ -378,12 +413,13 @@ impl InsightStorage {
         let query = r#"
             UPDATE insights
             SET tier = 'archived', updated_at = $1
-            WHERE id = $2 AND tier != 'archived'
+            WHERE id = $2 AND tier != 'archived' AND namespace = $3
         "#;
 
         let result = sqlx::query(query)
             .bind(Utc::now())
             .bind(id)
+            .bind(&self.namespace)
             .execute(&*self.pool)
             .await
             .map_err(MemoryError::Database)?;
@@ -586,13 +622,14 @@ impl InsightStorage {
         let query = r#"
             SELECT i.*
             FROM insights i
-            WHERE i.tier != 'archived'
+            WHERE i.tier != 'archived' AND i.namespace = $2
             ORDER BY i.updated_at DESC, i.created_at DESC
             LIMIT $1
         "#;
 
         let rows = sqlx::query(query)
             .bind(limit as i64)
+            .bind(&self.namespace)
             .fetch_all(self.pool.as_ref())
             .await
             .map_err(MemoryError::Database)?;
@@ -621,13 +658,14 @@ impl InsightStorage {
         let update_query = r#"
             UPDATE insights
             SET tier = $1, updated_at = $2
-            WHERE id = $3
+            WHERE id = $3 AND namespace = $4
         "#;
 
         let rows_affected = sqlx::query(update_query)
             .bind(&new_tier)
             .bind(Utc::now())
             .bind(&insight_id)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await
             .map_err(MemoryError::Database)?
@@ -18,7 +18,9 @@ pub mod setup;
 
 pub use config::Config;
 pub use database_setup::{DatabaseHealth, DatabaseSetup};
-pub use embedding::{EmbeddingHealth, EmbeddingModelInfo, SimpleEmbedder};
+pub use embedding::{
+    EmbeddingHealth, EmbeddingModelInfo, EmbeddingRegistry, HashingEmbedder, SimpleEmbedder,
+};
 pub use setup::SetupManager;
 
 // Re-export application layer
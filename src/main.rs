@@ -29,6 +29,15 @@ enum Commands {
         #[arg(long)]
         skip_setup: bool,
     },
+    /// Start MCP server over Streamable HTTP (shared by several agents)
+    McpHttp {
+        /// Skip setup checks and start immediately
+        #[arg(long)]
+        skip_setup: bool,
+        /// Address to listen on, e.g. 127.0.0.1:8081 (defaults to MCP_HTTP_BIND / MCP_PORT)
+        #[arg(long)]
+        bind: Option<String>,
+    },
     /// Setup the memory system
     Setup {
         /// Force setup even if already configured
@@ -81,6 +90,51 @@ enum DatabaseCommands {
     Health,
     /// Run database migrations
     Migrate,
+    /// Re-embed memories whose vectors came from another embedding model
+    Reembed {
+        /// Memories embedded per provider call
+        #[arg(long, default_value = "32")]
+        batch_size: usize,
+        /// Pause between batches in milliseconds
+        #[arg(long, default_value = "250")]
+        delay_ms: u64,
+        /// Stop after this many batches; run again to resume
+        #[arg(long)]
+        max_batches: Option<usize>,
+        /// Only re-embed this namespace
+        #[arg(long)]
+        namespace: Option<String>,
+        /// Ignore saved progress and start from the first memory
+        #[arg(long)]
+        restart: bool,
+        /// Show which models embedded the stored memories and exit
+        #[arg(long)]
+        status: bool,
+    },
+    /// Re-wrap the field encryption data keys under the master key in
+    /// CODEX_FIELD_ENCRYPTION_NEW_KEY
+    RotateEncryptionKey,
+    /// Apply the retention rules to stored memories
+    Retention {
+        /// Report what the rules would do without changing any memory
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Place or release a legal hold, which keeps a memory from being
+    /// deleted; lists the namespace's holds without a memory id
+    LegalHold {
+        /// Memory to hold or release
+        memory_id: Option<String>,
+        /// Why the memory must be preserved
+        #[arg(long)]
+        reason: Option<String>,
+        /// Release the hold instead of placing it
+        #[arg(long)]
+        release: bool,
+        /// Namespace of the memory
+        #[arg(long, default_value = "default")]
+        namespace: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -217,6 +271,10 @@ async fn main() -> Result<()> {
             let handler = ServerCommandHandler::new(app.container.clone());
             handler.start_mcp_stdio(skip_setup).await
         }
+        Some(Commands::McpHttp { skip_setup, bind }) => {
+            let handler = ServerCommandHandler::new(app.container.clone());
+            handler.start_mcp_http(skip_setup, bind).await
+        }
         None => {
             // Default to starting the server
             let handler = ServerCommandHandler::new(app.container.clone());
@@ -231,6 +289,39 @@ async fn handle_database_command(command: DatabaseCommands, app: &Application) -
         DatabaseCommands::Setup => handler.setup().await,
         DatabaseCommands::Health => handler.health().await,
         DatabaseCommands::Migrate => handler.migrate().await,
+        DatabaseCommands::Reembed {
+            batch_size,
+            delay_ms,
+            max_batches,
+            namespace,
+            restart,
+            status,
+        } => {
+            let config = codex_memory::memory::ReembedJobConfig {
+                batch_size,
+                batch_delay_ms: delay_ms,
+                max_batches_per_run: max_batches,
+                namespace,
+                restart,
+            };
+            if status {
+                handler.reembed_status(config.namespace.as_deref()).await
+            } else {
+                handler.reembed(config).await
+            }
+        }
+        DatabaseCommands::RotateEncryptionKey => handler.rotate_encryption_key().await,
+        DatabaseCommands::Retention { dry_run } => handler.retention(dry_run).await,
+        DatabaseCommands::LegalHold {
+            memory_id,
+            reason,
+            release,
+            namespace,
+        } => {
+            handler
+                .legal_hold(&namespace, memory_id.as_deref(), reason, release)
+                .await
+        }
     }
 }
 
@@ -3,6 +3,7 @@
 //! This module provides authentication middleware for MCP requests,
 //! supporting API keys, JWT tokens, and certificate-based authentication.
 
+use crate::memory::models::{validate_namespace, DEFAULT_NAMESPACE};
 use crate::security::{audit::AuditLogger, SecurityError};
 use anyhow::{anyhow, Result};
 use chrono::{Duration, Utc};
@@ -26,6 +27,14 @@ pub struct Claims {
     pub jti: String,        // JWT ID (for revocation)
 }
 
+/// Header a client uses to pick one of its granted namespaces
+pub const NAMESPACE_HEADER: &str = "x-memory-namespace";
+
+/// Scope prefix granting access to a namespace: `namespace:acme/api` grants
+/// exactly that namespace, `namespace:acme/*` the whole `acme` subtree and
+/// `namespace:*` every namespace
+pub const NAMESPACE_SCOPE_PREFIX: &str = "namespace:";
+
 /// Authentication method types
 #[derive(Debug, Clone, PartialEq)]
 pub enum AuthMethod {
@@ -44,6 +53,44 @@ pub struct AuthContext {
     pub scopes: Vec<String>,
     pub expires_at: Option<chrono::DateTime<Utc>>,
     pub request_id: String,
+    /// Namespace the request operates in
+    pub namespace: String,
+}
+
+impl AuthContext {
+    /// Namespace a client lands in when it doesn't ask for one: the first
+    /// granted namespace, or the default namespace without namespace scopes
+    pub fn default_namespace(scopes: &[String]) -> String {
+        scopes
+            .iter()
+            .filter_map(|scope| scope.strip_prefix(NAMESPACE_SCOPE_PREFIX))
+            .map(|granted| granted.strip_suffix("/*").unwrap_or(granted))
+            .find(|granted| *granted != "*")
+            .unwrap_or(DEFAULT_NAMESPACE)
+            .to_string()
+    }
+
+    /// Whether the scopes grant access to `namespace`. Clients without any
+    /// namespace scope are confined to the default namespace.
+    pub fn can_access_namespace(&self, namespace: &str) -> bool {
+        let mut granted = self
+            .scopes
+            .iter()
+            .filter_map(|scope| scope.strip_prefix(NAMESPACE_SCOPE_PREFIX))
+            .peekable();
+
+        if granted.peek().is_none() {
+            return namespace == DEFAULT_NAMESPACE;
+        }
+
+        granted.any(|granted| match granted.strip_suffix('*') {
+            Some("") => true,
+            Some(prefix) => {
+                namespace.starts_with(prefix) || Some(namespace) == prefix.strip_suffix('/')
+            }
+            None => granted == namespace,
+        })
+    }
 }
 
 /// MCP Authentication configuration
@@ -382,6 +429,7 @@ impl MCPAuth {
         } else {
             Err(anyhow!("No authentication credentials provided"))
         };
+        let auth_result = auth_result.and_then(|context| Self::apply_namespace(context, headers));
 
         let elapsed = start_time.elapsed();
 
@@ -463,6 +511,7 @@ impl MCPAuth {
             client_id: claims.client_id,
             user_id: claims.sub,
             method: AuthMethod::JwtToken,
+            namespace: AuthContext::default_namespace(&claims.scope),
             scopes: claims.scope,
             expires_at: chrono::DateTime::from_timestamp(claims.exp, 0),
             request_id: request_id.to_string(),
@@ -496,6 +545,7 @@ impl MCPAuth {
             scopes: api_key_info.scopes.clone(),
             expires_at: api_key_info.expires_at,
             request_id: request_id.to_string(),
+            namespace: AuthContext::default_namespace(&api_key_info.scopes),
         })
     }
 
@@ -549,9 +599,33 @@ impl MCPAuth {
             scopes: cert_info.scopes.clone(),
             expires_at: Some(cert_info.not_after),
             request_id: request_id.to_string(),
+            namespace: AuthContext::default_namespace(&cert_info.scopes),
         })
     }
 
+    /// Switch to the namespace requested via `x-memory-namespace`, if the
+    /// credentials grant it
+    fn apply_namespace(
+        mut context: AuthContext,
+        headers: &HashMap<String, String>,
+    ) -> Result<AuthContext> {
+        let Some(requested) = headers.get(NAMESPACE_HEADER) else {
+            return Ok(context);
+        };
+
+        validate_namespace(requested).map_err(|e| anyhow!(e))?;
+        if !context.can_access_namespace(requested) {
+            return Err(anyhow!(
+                "Client '{}' is not granted namespace '{}'",
+                context.client_id,
+                requested
+            ));
+        }
+
+        context.namespace = requested.clone();
+        Ok(context)
+    }
+
     /// Check if provided scopes meet requirements
     fn has_required_scopes(&self, provided_scopes: &[String]) -> bool {
         if self.config.require_scope.is_empty() {
@@ -599,10 +673,24 @@ impl MCPAuth {
     pub fn validate_tool_access(&self, context: &AuthContext, tool_name: &str) -> Result<()> {
         // Map tools to required scopes
         let required_scope = match tool_name {
-            "store_memory" | "harvest_conversation" | "migrate_memory" | "delete_memory" => {
-                "mcp:write"
-            }
+            "store_memory"
+            | "store_memories"
+            | "update_memory"
+            | "revert_memory"
+            | "link_memories"
+            | "unlink_memories"
+            | "resolve_conflict"
+            | "harvest_conversation"
+            | "migrate_memory"
+            | "delete_memory"
+            | "delete_memories" => "mcp:write",
             "search_memory"
+            | "get_memory"
+            | "memory_history"
+            | "get_neighbors"
+            | "list_memories"
+            | "list_entities"
+            | "list_conflicts"
             | "get_statistics"
             | "what_did_you_remember"
             | "get_harvester_metrics" => "mcp:read",
@@ -794,6 +882,7 @@ mod tests {
             scopes: vec!["mcp:read".to_string()],
             expires_at: None,
             request_id: "test-request".to_string(),
+            namespace: DEFAULT_NAMESPACE.to_string(),
         };
 
         // Should allow read operations
@@ -809,6 +898,65 @@ mod tests {
             .is_err());
     }
 
+    #[test]
+    fn test_namespace_scopes() {
+        let context = |scopes: &[&str]| AuthContext {
+            client_id: "test-client".to_string(),
+            user_id: "test-user".to_string(),
+            method: AuthMethod::ApiKey,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            expires_at: None,
+            request_id: "test-request".to_string(),
+            namespace: AuthContext::default_namespace(
+                &scopes.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            ),
+        };
+
+        let unscoped = context(&["mcp:read"]);
+        assert_eq!(unscoped.namespace, DEFAULT_NAMESPACE);
+        assert!(unscoped.can_access_namespace(DEFAULT_NAMESPACE));
+        assert!(!unscoped.can_access_namespace("acme/api"));
+
+        let subtree = context(&["mcp:read", "namespace:acme/*"]);
+        assert_eq!(subtree.namespace, "acme");
+        assert!(subtree.can_access_namespace("acme"));
+        assert!(subtree.can_access_namespace("acme/api/agent-1"));
+        assert!(!subtree.can_access_namespace("acme-evil"));
+        assert!(!subtree.can_access_namespace(DEFAULT_NAMESPACE));
+
+        let exact = context(&["namespace:acme/api"]);
+        assert_eq!(exact.namespace, "acme/api");
+        assert!(!exact.can_access_namespace("acme/web"));
+
+        let any = context(&["namespace:*"]);
+        assert_eq!(any.namespace, DEFAULT_NAMESPACE);
+        assert!(any.can_access_namespace("anything/at/all"));
+    }
+
+    #[tokio::test]
+    async fn test_namespace_header_requires_grant() {
+        let auth = create_test_auth().await;
+
+        let mut headers = HashMap::new();
+        headers.insert("JSON-RPC-Version".to_string(), "2.0".to_string());
+        headers.insert(
+            "authorization".to_string(),
+            "ApiKey test-key-123".to_string(),
+        );
+        let context = auth
+            .authenticate_request("tools/call", None, &headers)
+            .await
+            .expect("authentication should succeed")
+            .expect("auth is enabled");
+        assert_eq!(context.namespace, DEFAULT_NAMESPACE);
+
+        headers.insert(NAMESPACE_HEADER.to_string(), "other/repo".to_string());
+        let result = auth
+            .authenticate_request("tools/call", None, &headers)
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_token_revocation() {
         let auth = create_test_auth().await;
@@ -4,42 +4,106 @@
 //! including tool execution, initialization, and resource management.
 
 use crate::mcp_server::{
-    auth::{AuthContext, MCPAuth},
+    auth::{AuthContext, MCPAuth, NAMESPACE_HEADER},
     circuit_breaker::{CircuitBreaker, CircuitBreakerError},
-    logging::{LogLevel, MCPLogger},
-    progress::{ProgressHandle, ProgressTracker},
+    http_transport::SESSION_HEADER,
+    logging::{LogLevel, LogMessage, MCPLogger},
+    progress::{progress_token_from_params, ProgressHandle, ProgressReport, ProgressTracker},
+    prompts::{self, PromptRegistry},
     rate_limiter::MCPRateLimiter,
-    tools::MCPTools,
+    resources::{self, CodexResource},
+    tools::{parse_time_arg, MCPTools},
     transport::{
-        create_error_response, create_error_response_with_data, create_success_response,
-        create_text_content, format_tool_error_response, format_tool_response,
-        format_tool_response_with_content,
+        create_error_response, create_error_response_with_data, create_resource_content,
+        create_success_response, create_text_content, format_tool_error_response,
+        format_tool_response, format_tool_response_with_content, REQUEST_CANCELLED_CODE,
     },
 };
-use crate::memory::{models::*, ConversationMessage, MemoryRepository, SilentHarvesterService};
+use crate::memory::{
+    contradictions::{ConflictStatus, MemoryConflict, ResolveConflictRequest},
+    index_memory_entities,
+    models::*,
+    reflection_engine::RelationshipType,
+    relations::{expand_search_results, MemoryRelation},
+    ContradictionDetector, ConversationMessage, EncryptedMemoryStorage, EntityExtractor,
+    EntityType, FieldEncryption, LinkMemoriesRequest, MemoryRepository, MemoryStorage,
+    PiiWritePolicy, RelationDirection, RelationFilter, RevisionDiff, SilentHarvesterService,
+    TimeExpression, TimeExpressionConfig, TimeExpressionParser, TimeRange, TraversalOptions,
+};
+use crate::security::{data_subject, RbacManager, PII_REVEAL_PERMISSION, SUBJECT_ID_KEY};
 use crate::SimpleEmbedder;
 
 #[cfg(feature = "codex-dreams")]
 use crate::insights::processor::InsightsProcessor;
 use anyhow::Result;
 use chrono::{Duration as ChronoDuration, Utc};
-use serde_json::Value;
+use futures::future::{abortable, AbortHandle, Aborted};
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Memories decrypted per page, and in total, when looking for an event
+/// under field encryption
+const EVENT_SCAN_PAGE: i32 = 200;
+const EVENT_SCAN_LIMIT: usize = 2_000;
+
+/// Memory stores restricted to the namespace of a single request
+struct NamespaceScope {
+    namespace: String,
+    /// Authenticated client, recorded as the actor of memory revisions
+    actor: Option<String>,
+    /// HTTP session of the request; its log messages go to that session only
+    session_id: Option<String>,
+    storage: Arc<dyn MemoryStorage>,
+    /// `None` unless the backend is PostgreSQL
+    repository: Option<Arc<MemoryRepository>>,
+    #[cfg(feature = "codex-dreams")]
+    insight_storage: Option<Arc<crate::insights::storage::InsightStorage>>,
+}
+
+impl NamespaceScope {
+    /// The PostgreSQL repository, for `feature` that only it supports
+    fn repository(&self, feature: &str) -> Result<&Arc<MemoryRepository>> {
+        self.repository
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("{feature} requires the PostgreSQL backend"))
+    }
+
+    /// Data subject of the memories this request writes
+    fn data_subject(&self) -> String {
+        data_subject(self.actor.as_deref(), &self.namespace)
+    }
+}
+
 /// MCP request handlers
 pub struct MCPHandlers {
-    repository: Arc<MemoryRepository>,
+    /// PostgreSQL repository for the features only it supports; `None` when
+    /// memories live in another backend
+    repository: Option<Arc<MemoryRepository>>,
+    storage: Arc<dyn MemoryStorage>,
     embedder: Arc<SimpleEmbedder>,
-    harvester_service: Arc<SilentHarvesterService>,
+    /// Conversation harvesting, which needs the PostgreSQL repository
+    harvester_service: Option<Arc<SilentHarvesterService>>,
     circuit_breaker: Option<Arc<CircuitBreaker>>,
     auth: Option<Arc<MCPAuth>>,
     rate_limiter: Option<Arc<MCPRateLimiter>>,
     mcp_logger: Arc<MCPLogger>,
     progress_tracker: Arc<ProgressTracker>,
+    prompt_registry: PromptRegistry,
+    in_flight: Mutex<HashMap<String, AbortHandle>>,
+    entity_extractor: EntityExtractor,
+    contradiction_detector: ContradictionDetector,
+    time_parser: TimeExpressionParser,
+    pii_policy: Arc<PiiWritePolicy>,
+    /// Grants revealing vaulted PII; without it nobody may
+    rbac: Option<Arc<RbacManager>>,
+    /// Encrypts memories at rest, applied to `storage` and to the memories
+    /// read from the repository directly
+    field_encryption: Option<Arc<FieldEncryption>>,
     #[cfg(feature = "codex-dreams")]
     insights_processor: Option<Arc<InsightsProcessor>>,
     #[cfg(feature = "codex-dreams")]
@@ -60,14 +124,23 @@ impl MCPHandlers {
         progress_tracker: Arc<ProgressTracker>,
     ) -> Self {
         Self {
-            repository,
+            storage: repository.clone(),
+            repository: Some(repository),
             embedder,
-            harvester_service,
+            harvester_service: Some(harvester_service),
             circuit_breaker,
             auth,
             rate_limiter,
             mcp_logger,
             progress_tracker,
+            prompt_registry: PromptRegistry::load_default(),
+            in_flight: Mutex::new(HashMap::new()),
+            entity_extractor: EntityExtractor::new(),
+            contradiction_detector: ContradictionDetector::default(),
+            time_parser: TimeExpressionParser::new(TimeExpressionConfig::from_env()),
+            pii_policy: Arc::new(PiiWritePolicy::disabled()),
+            rbac: None,
+            field_encryption: None,
         }
     }
 
@@ -111,23 +184,245 @@ impl MCPHandlers {
         insights_processor: Option<Arc<InsightsProcessor>>,
         insight_storage: Option<Arc<crate::insights::storage::InsightStorage>>,
     ) -> Self {
+        // The insights model double-checks statements the rules let through
+        let contradiction_detector = match &insights_processor {
+            Some(processor) => {
+                ContradictionDetector::default().with_checker(processor.ollama_client())
+            }
+            None => ContradictionDetector::default(),
+        };
+
         Self {
-            repository,
+            storage: repository.clone(),
+            repository: Some(repository),
             embedder,
-            harvester_service,
+            harvester_service: Some(harvester_service),
             circuit_breaker,
             auth,
             rate_limiter,
             mcp_logger,
             progress_tracker,
+            prompt_registry: PromptRegistry::load_default(),
+            in_flight: Mutex::new(HashMap::new()),
+            entity_extractor: EntityExtractor::new(),
+            contradiction_detector,
+            time_parser: TimeExpressionParser::new(TimeExpressionConfig::from_env()),
+            pii_policy: Arc::new(PiiWritePolicy::disabled()),
+            rbac: None,
+            field_encryption: None,
             insights_processor,
             insight_storage,
         }
     }
 
+    /// Create handlers serving memories from a backend other than
+    /// PostgreSQL, such as the embedded SQLite store. Conversation harvesting,
+    /// insights and the review prompt need PostgreSQL and report an error.
+    pub fn for_storage(
+        storage: Arc<dyn MemoryStorage>,
+        embedder: Arc<SimpleEmbedder>,
+        circuit_breaker: Option<Arc<CircuitBreaker>>,
+        auth: Option<Arc<MCPAuth>>,
+        rate_limiter: Option<Arc<MCPRateLimiter>>,
+        mcp_logger: Arc<MCPLogger>,
+        progress_tracker: Arc<ProgressTracker>,
+    ) -> Self {
+        Self {
+            repository: None,
+            storage,
+            embedder,
+            harvester_service: None,
+            circuit_breaker,
+            auth,
+            rate_limiter,
+            mcp_logger,
+            progress_tracker,
+            prompt_registry: PromptRegistry::load_default(),
+            in_flight: Mutex::new(HashMap::new()),
+            entity_extractor: EntityExtractor::new(),
+            contradiction_detector: ContradictionDetector::default(),
+            time_parser: TimeExpressionParser::new(TimeExpressionConfig::from_env()),
+            pii_policy: Arc::new(PiiWritePolicy::disabled()),
+            rbac: None,
+            field_encryption: None,
+            #[cfg(feature = "codex-dreams")]
+            insights_processor: None,
+            #[cfg(feature = "codex-dreams")]
+            insight_storage: None,
+        }
+    }
+
+    /// Replace the user-defined prompt templates
+    pub fn with_prompt_registry(mut self, prompt_registry: PromptRegistry) -> Self {
+        self.prompt_registry = prompt_registry;
+        self
+    }
+
+    /// Replace the sprint length and event dates used by time expressions
+    pub fn with_time_parser(mut self, time_parser: TimeExpressionParser) -> Self {
+        self.time_parser = time_parser;
+        self
+    }
+
+    /// Replace the PII policy applied to stored content
+    pub fn with_pii_policy(mut self, pii_policy: Arc<PiiWritePolicy>) -> Self {
+        self.pii_policy = pii_policy;
+        self
+    }
+
+    /// Authorize clients to reveal vaulted PII by the `pii:reveal` permission
+    pub fn with_rbac(mut self, rbac: Arc<RbacManager>) -> Self {
+        self.rbac = Some(rbac);
+        self
+    }
+
+    /// Encrypt memory content and sensitive metadata before they are stored
+    pub fn with_field_encryption(mut self, encryption: Arc<FieldEncryption>) -> Self {
+        #[cfg(feature = "codex-dreams")]
+        if let Some(processor) = &self.insights_processor {
+            processor.set_field_encryption(encryption.clone());
+        }
+        self.storage = Arc::new(EncryptedMemoryStorage::new(
+            self.storage.clone(),
+            encryption.clone(),
+        ));
+        self.field_encryption = Some(encryption);
+        self
+    }
+
+    /// The conversation harvester, which needs the PostgreSQL backend
+    fn harvester(&self) -> Result<&Arc<SilentHarvesterService>> {
+        self.harvester_service.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Conversation harvesting requires the PostgreSQL backend")
+        })
+    }
+
+    /// Plaintext of a memory read from the repository directly
+    fn decrypt_memory(&self, memory: Memory) -> Result<Memory> {
+        match &self.field_encryption {
+            Some(encryption) => Ok(encryption.decrypt_memory(memory)?),
+            None => Ok(memory),
+        }
+    }
+
+    /// Namespace a request operates in: the authenticated client's namespace,
+    /// else the `x-memory-namespace` header, else the namespace of the storage
+    fn request_namespace(
+        &self,
+        auth_context: Option<&AuthContext>,
+        headers: &HashMap<String, String>,
+    ) -> std::result::Result<String, String> {
+        if let Some(context) = auth_context {
+            return Ok(context.namespace.clone());
+        }
+
+        match headers.get(NAMESPACE_HEADER) {
+            Some(namespace) => {
+                validate_namespace(namespace)?;
+                Ok(namespace.clone())
+            }
+            None => Ok(self.storage.namespace().to_string()),
+        }
+    }
+
+    /// Storage handles restricted to `namespace`
+    fn scope(
+        &self,
+        namespace: String,
+        actor: Option<String>,
+        session_id: Option<String>,
+    ) -> NamespaceScope {
+        NamespaceScope {
+            actor,
+            session_id,
+            storage: self.storage.with_namespace(&namespace),
+            repository: self
+                .repository
+                .as_ref()
+                .map(|repository| Arc::new(repository.with_namespace(namespace.clone()))),
+            #[cfg(feature = "codex-dreams")]
+            insight_storage: self
+                .insight_storage
+                .as_ref()
+                .map(|storage| Arc::new(storage.with_namespace(namespace.clone()))),
+            namespace,
+        }
+    }
+
+    /// Subscribe to `notifications/message` log entries for the client
+    pub fn subscribe_logs(&self) -> broadcast::Receiver<LogMessage> {
+        self.mcp_logger.subscribe()
+    }
+
+    /// Subscribe to `notifications/progress` updates for the client
+    pub fn subscribe_progress(&self) -> broadcast::Receiver<ProgressReport> {
+        self.progress_tracker.subscribe()
+    }
+
+    /// Drop per-session state, such as the log level, of an ended HTTP session
+    pub fn end_session(&self, session_id: &str) {
+        self.mcp_logger.end_session(session_id);
+    }
+
+    /// Handle JSON-RPC notifications from the client (no response is sent)
+    pub async fn handle_notification(
+        &self,
+        method: &str,
+        params: Option<&Value>,
+        headers: &HashMap<String, String>,
+    ) {
+        match method {
+            "notifications/initialized" => {
+                debug!("Client initialized notification received");
+            }
+            "notifications/cancelled" => {
+                let request_id = params.and_then(|p| p.get("requestId"));
+                let reason = params
+                    .and_then(|p| p.get("reason"))
+                    .and_then(|r| r.as_str());
+                match request_id {
+                    Some(request_id) => {
+                        if !self.cancel_request(request_id, headers, reason).await {
+                            debug!(
+                                "Cancellation for unknown or finished request {}",
+                                request_id
+                            );
+                        }
+                    }
+                    None => warn!("Cancellation notification without requestId"),
+                }
+            }
+            _ => {
+                debug!("Unknown notification method: {}", method);
+            }
+        }
+    }
+
+    /// Abort an in-flight `tools/call`; returns false if it already finished
+    pub async fn cancel_request(
+        &self,
+        request_id: &Value,
+        headers: &HashMap<String, String>,
+        reason: Option<&str>,
+    ) -> bool {
+        let key = in_flight_key(request_id, headers);
+        match self.in_flight.lock().await.remove(&key) {
+            Some(handle) => {
+                info!(
+                    "Cancelling request {}: {}",
+                    request_id,
+                    reason.unwrap_or("no reason given")
+                );
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Handle incoming MCP requests with authentication and rate limiting
     pub async fn handle_request(
-        &mut self,
+        &self,
         method: &str,
         params: Option<&Value>,
         id: Option<&Value>,
@@ -216,7 +511,7 @@ impl MCPHandlers {
 
     /// Handle incoming MCP requests with headers for auth/rate limiting
     pub async fn handle_request_with_headers(
-        &mut self,
+        &self,
         method: &str,
         params: Option<&Value>,
         id: Option<&Value>,
@@ -273,16 +568,33 @@ impl MCPHandlers {
             }
         }
 
+        // Every memory operation below is confined to the request's namespace
+        let scope = match self.request_namespace(auth_context.as_ref(), headers) {
+            Ok(namespace) => self.scope(
+                namespace,
+                auth_context.as_ref().map(|c| c.client_id.clone()),
+                headers.get(SESSION_HEADER).cloned(),
+            ),
+            Err(e) => return create_error_response(id, -32602, &format!("Invalid namespace: {e}")),
+        };
+
         // Proceed with request handling - all methods now require authentication
         match method {
             "initialize" => self.handle_initialize(id, params).await,
             "tools/list" => self.handle_tools_list(id).await,
             "tools/call" => {
-                self.handle_tools_call(id, params, auth_context.as_ref())
+                self.handle_tools_call(&scope, id, params, auth_context.as_ref(), headers)
                     .await
             }
-            "resources/list" => self.handle_resources_list(id).await,
+            "logging/setLevel" => {
+                self.handle_logging_set_level(id, params, scope.session_id.as_deref())
+                    .await
+            }
+            "resources/list" => self.handle_resources_list(&scope, id).await,
+            "resources/read" => self.handle_resources_read(&scope, id, params).await,
+            "resources/templates/list" => self.handle_resource_templates_list(id).await,
             "prompts/list" => self.handle_prompts_list(id).await,
+            "prompts/get" => self.handle_prompts_get(&scope, id, params).await,
             _ => {
                 warn!("Unknown method: {}", method);
                 create_error_response(id, -32601, "Method not found")
@@ -305,13 +617,40 @@ impl MCPHandlers {
             "capabilities": {
                 "tools": {},
                 "resources": {},
-                "prompts": {}
+                "prompts": {},
+                "logging": {}
             }
         });
 
         create_success_response(id, basic_capabilities)
     }
 
+    /// Handle logging/setLevel request; over HTTP the level applies to the
+    /// calling session only
+    async fn handle_logging_set_level(
+        &self,
+        id: Option<&Value>,
+        params: Option<&Value>,
+        session_id: Option<&str>,
+    ) -> Value {
+        let level = params
+            .and_then(|p| p.get("level"))
+            .cloned()
+            .and_then(|level| serde_json::from_value::<LogLevel>(level).ok());
+
+        match level {
+            Some(level) => {
+                info!("Client set MCP log level to {:?}", level);
+                match session_id {
+                    Some(session_id) => self.mcp_logger.set_session_level(session_id, level),
+                    None => self.mcp_logger.set_min_level(level),
+                }
+                create_success_response(id, json!({}))
+            }
+            None => create_error_response(id, -32602, "Invalid or missing log level"),
+        }
+    }
+
     /// Handle tools/list request
     async fn handle_tools_list(&self, id: Option<&Value>) -> Value {
         debug!("Listing available tools");
@@ -319,23 +658,365 @@ impl MCPHandlers {
     }
 
     /// Handle resources/list request
-    async fn handle_resources_list(&self, id: Option<&Value>) -> Value {
-        debug!("Listing resources (empty)");
-        create_success_response(id, MCPTools::get_resources_list())
+    async fn handle_resources_list(&self, scope: &NamespaceScope, id: Option<&Value>) -> Value {
+        debug!("Listing resources");
+        let mut result = MCPTools::get_resources_list();
+
+        // Recent memories are listed individually so clients can attach them directly
+        match self.recent_memories(scope).await {
+            Ok(memories) => {
+                if let Some(list) = result["resources"].as_array_mut() {
+                    list.extend(memories.iter().map(resources::memory_resource_entry));
+                }
+            }
+            Err(e) => warn!("Failed to list recent memory resources: {}", e),
+        }
+
+        create_success_response(id, result)
+    }
+
+    /// Handle resources/templates/list request
+    async fn handle_resource_templates_list(&self, id: Option<&Value>) -> Value {
+        debug!("Listing resource templates");
+        create_success_response(id, MCPTools::get_resource_templates_list())
+    }
+
+    /// Handle resources/read request
+    async fn handle_resources_read(
+        &self,
+        scope: &NamespaceScope,
+        id: Option<&Value>,
+        params: Option<&Value>,
+    ) -> Value {
+        let uri = match params.and_then(|p| p.get("uri")).and_then(|u| u.as_str()) {
+            Some(uri) => uri,
+            None => return create_error_response(id, -32602, "Missing required 'uri' parameter"),
+        };
+
+        let resource = match CodexResource::parse(uri) {
+            Ok(resource) => resource,
+            Err(e) => {
+                return create_error_response_with_data(
+                    id,
+                    -32002,
+                    "Resource not found",
+                    Some(serde_json::json!({ "uri": uri, "reason": e })),
+                )
+            }
+        };
+
+        debug!("Reading resource {}", uri);
+        match self.read_resource(scope, &resource).await {
+            Ok(Some(body)) => {
+                let text = serde_json::to_string_pretty(&body).unwrap_or_default();
+                let content = create_resource_content(
+                    uri,
+                    Some(resources::RESOURCE_MIME_TYPE),
+                    Some(&text),
+                    None,
+                );
+                create_success_response(
+                    id,
+                    serde_json::json!({ "contents": [content["resource"].clone()] }),
+                )
+            }
+            Ok(None) => create_error_response_with_data(
+                id,
+                -32002,
+                "Resource not found",
+                Some(serde_json::json!({ "uri": uri })),
+            ),
+            Err(e) => {
+                error!("Failed to read resource {}: {}", uri, e);
+                create_error_response(id, -32603, &format!("Failed to read resource: {e}"))
+            }
+        }
+    }
+
+    /// Load the JSON body of a resource, or `None` if it does not exist
+    async fn read_resource(
+        &self,
+        scope: &NamespaceScope,
+        resource: &CodexResource,
+    ) -> Result<Option<Value>> {
+        match resource {
+            CodexResource::Memory(memory_id) => match scope.storage.get_memory(*memory_id).await {
+                Ok(memory) => Ok(Some(resources::memory_resource_body(&memory))),
+                Err(crate::memory::error::MemoryError::NotFound { .. }) => Ok(None),
+                Err(e) => Err(e.into()),
+            },
+            CodexResource::Tier(tier) => {
+                let memories = scope
+                    .storage
+                    .get_memories_by_tier(*tier, Some(resources::TIER_RESOURCE_LIMIT))
+                    .await?;
+                Ok(Some(Value::Array(
+                    memories
+                        .iter()
+                        .map(resources::memory_resource_body)
+                        .collect(),
+                )))
+            }
+            CodexResource::Recent => {
+                let memories = self.recent_memories(scope).await?;
+                Ok(Some(Value::Array(
+                    memories
+                        .iter()
+                        .map(resources::memory_resource_body)
+                        .collect(),
+                )))
+            }
+            CodexResource::Insight(insight_id) => {
+                self.read_insight_resource(scope, *insight_id).await
+            }
+        }
+    }
+
+    #[cfg(feature = "codex-dreams")]
+    async fn read_insight_resource(
+        &self,
+        scope: &NamespaceScope,
+        insight_id: Uuid,
+    ) -> Result<Option<Value>> {
+        let storage = scope
+            .insight_storage
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Insight storage is not configured"))?;
+        match storage.get_by_id(insight_id).await? {
+            Some(insight) => Ok(Some(serde_json::to_value(insight)?)),
+            None => Ok(None),
+        }
+    }
+
+    #[cfg(not(feature = "codex-dreams"))]
+    async fn read_insight_resource(
+        &self,
+        _scope: &NamespaceScope,
+        _insight_id: Uuid,
+    ) -> Result<Option<Value>> {
+        Err(anyhow::anyhow!(
+            "Insight resources require the codex-dreams feature"
+        ))
+    }
+
+    /// Most recently created memories across all tiers
+    async fn recent_memories(&self, scope: &NamespaceScope) -> Result<Vec<Memory>> {
+        let response = scope
+            .storage
+            .search_memories(SearchRequest {
+                search_type: Some(SearchType::Temporal),
+                limit: Some(resources::RECENT_RESOURCE_LIMIT),
+                ..Default::default()
+            })
+            .await?;
+        Ok(response.results.into_iter().map(|r| r.memory).collect())
     }
 
     /// Handle prompts/list request
     async fn handle_prompts_list(&self, id: Option<&Value>) -> Value {
-        debug!("Listing prompts (empty)");
-        create_success_response(id, MCPTools::get_prompts_list())
+        debug!("Listing prompts");
+        create_success_response(
+            id,
+            serde_json::json!({ "prompts": self.prompt_registry.list() }),
+        )
+    }
+
+    /// Handle prompts/get request
+    async fn handle_prompts_get(
+        &self,
+        scope: &NamespaceScope,
+        id: Option<&Value>,
+        params: Option<&Value>,
+    ) -> Value {
+        let name = match params.and_then(|p| p.get("name")).and_then(|n| n.as_str()) {
+            Some(name) => name,
+            None => return create_error_response(id, -32602, "Missing required 'name' parameter"),
+        };
+        let args = prompts::parse_arguments(params);
+
+        let result = match name {
+            prompts::RECALL_CONTEXT_PROMPT => self.render_recall_context_prompt(scope, &args).await,
+            prompts::SUMMARIZE_ABOUT_ME_PROMPT => {
+                self.render_summarize_about_me_prompt(scope).await
+            }
+            prompts::REVIEW_DUE_MEMORIES_PROMPT => {
+                self.render_review_due_memories_prompt(scope, &args).await
+            }
+            _ => match self.prompt_registry.get(name) {
+                Some(template) => {
+                    if let Err(e) = template.validate_arguments(&args) {
+                        return create_error_response(id, -32602, &e);
+                    }
+                    self.render_user_prompt(scope, template, &args).await
+                }
+                None => {
+                    return create_error_response(id, -32602, &format!("Unknown prompt: {name}"))
+                }
+            },
+        };
+
+        match result {
+            Ok(prompt) => create_success_response(id, prompt),
+            Err(e) => {
+                error!("Failed to render prompt {}: {}", name, e);
+                create_error_response(id, -32603, &format!("Failed to render prompt: {e}"))
+            }
+        }
+    }
+
+    /// Semantic search used to pull memories into prompts
+    async fn search_prompt_memories(
+        &self,
+        scope: &NamespaceScope,
+        query: &str,
+        limit: i32,
+    ) -> Result<Vec<Memory>> {
+        let embedding = self.embedder.generate_embedding(query).await?;
+        let response = scope
+            .storage
+            .search_memories(SearchRequest {
+                query_text: Some(query.to_string()),
+                query_embedding: Some(embedding),
+                limit: Some(limit),
+                similarity_threshold: Some(0.3),
+                subject_id: scope.actor.clone(),
+                ..Default::default()
+            })
+            .await?;
+        Ok(response.results.into_iter().map(|r| r.memory).collect())
+    }
+
+    async fn render_recall_context_prompt(
+        &self,
+        scope: &NamespaceScope,
+        args: &HashMap<String, String>,
+    ) -> Result<Value> {
+        let topic = args
+            .get("topic")
+            .filter(|t| !t.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing required argument 'topic'"))?;
+        let limit = args
+            .get("limit")
+            .and_then(|l| l.parse::<i32>().ok())
+            .unwrap_or(10)
+            .clamp(1, 50);
+
+        let memories = self.search_prompt_memories(scope, topic, limit).await?;
+        let text = format!(
+            "Here is what I have stored about \"{topic}\". Use it as background context \
+             for our conversation and point out anything that looks outdated.\n\n{}",
+            prompts::format_memories(&memories)
+        );
+        Ok(prompts::prompt_result(
+            &format!("Recalled context for {topic}"),
+            &text,
+        ))
+    }
+
+    async fn render_summarize_about_me_prompt(&self, scope: &NamespaceScope) -> Result<Value> {
+        use crate::memory::MemoryPatternType;
+
+        let memories = self
+            .search_prompt_memories(
+                scope,
+                "about the user: preferences, facts, decisions, goals, skills, relationships",
+                100,
+            )
+            .await?;
+
+        let pattern_types = [
+            MemoryPatternType::Preference,
+            MemoryPatternType::Fact,
+            MemoryPatternType::Decision,
+            MemoryPatternType::Correction,
+            MemoryPatternType::Goal,
+            MemoryPatternType::Skill,
+            MemoryPatternType::Relationship,
+            MemoryPatternType::Emotion,
+        ];
+
+        let mut sections = Vec::new();
+        for pattern_type in pattern_types {
+            let label = serde_json::to_value(&pattern_type)?;
+            let matching: Vec<Memory> = memories
+                .iter()
+                .filter(|m| m.metadata.get("pattern_type") == Some(&label))
+                .cloned()
+                .collect();
+            if !matching.is_empty() {
+                sections.push(format!(
+                    "## {}\n{}",
+                    label.as_str().unwrap_or_default(),
+                    prompts::format_memories(&matching)
+                ));
+            }
+        }
+
+        let body = if sections.is_empty() {
+            "(no harvested preferences, facts or goals yet)".to_string()
+        } else {
+            sections.join("\n\n")
+        };
+        let text = format!(
+            "Summarize what you know about me from these stored memories. \
+             Group the summary by theme and flag contradictions.\n\n{body}"
+        );
+        Ok(prompts::prompt_result(
+            "Summary of what is known about the user",
+            &text,
+        ))
+    }
+
+    async fn render_review_due_memories_prompt(
+        &self,
+        scope: &NamespaceScope,
+        args: &HashMap<String, String>,
+    ) -> Result<Value> {
+        let limit = args
+            .get("limit")
+            .and_then(|l| l.parse::<i32>().ok())
+            .unwrap_or(10)
+            .clamp(1, 50);
+
+        let memories = scope
+            .repository("The review prompt")?
+            .get_memories_due_for_review(Some(limit))
+            .await?
+            .into_iter()
+            .map(|memory| self.decrypt_memory(memory))
+            .collect::<Result<Vec<_>>>()?;
+        let text = format!(
+            "These memories are due for spaced-repetition review. Quiz me on each one, \
+             one at a time, and tell me which I got wrong.\n\n{}",
+            prompts::format_memories(&memories)
+        );
+        Ok(prompts::prompt_result("Memories due for review", &text))
+    }
+
+    async fn render_user_prompt(
+        &self,
+        scope: &NamespaceScope,
+        template: &prompts::PromptTemplate,
+        args: &HashMap<String, String>,
+    ) -> Result<Value> {
+        let memories = match template.render_search(args) {
+            Some(query) => Some(prompts::format_memories(
+                &self.search_prompt_memories(scope, &query, 10).await?,
+            )),
+            None => None,
+        };
+        let text = template.render(args, memories.as_deref());
+        Ok(prompts::prompt_result(&template.description, &text))
     }
 
     /// Handle tools/call request
     async fn handle_tools_call(
-        &mut self,
+        &self,
+        scope: &NamespaceScope,
         id: Option<&Value>,
         params: Option<&Value>,
         auth_context: Option<&AuthContext>,
+        headers: &HashMap<String, String>,
     ) -> Value {
         let params = match params {
             Some(p) => p,
@@ -374,48 +1055,136 @@ impl MCPHandlers {
 
         debug!("Executing tool: {} with args: {}", tool_name, arguments);
 
-        // Execute tool with circuit breaker protection if enabled
-        if let Some(ref circuit_breaker) = self.circuit_breaker {
-            match circuit_breaker
-                .call(|| async { self.execute_tool(tool_name, arguments).await })
-                .await
-            {
-                Ok(result) => create_success_response(id, result),
-                Err(CircuitBreakerError::CircuitOpen) => create_error_response(
-                    id,
-                    -32603,
-                    "Service temporarily unavailable (circuit breaker open)",
-                ),
-                Err(CircuitBreakerError::HalfOpenLimitExceeded) => create_error_response(
-                    id,
-                    -32603,
-                    "Service temporarily unavailable (half-open limit exceeded)",
-                ),
+        // Honor the client's progress token, if any
+        let progress = match progress_token_from_params(Some(params)) {
+            Some(token) => {
+                self.progress_tracker
+                    .start_session_operation(
+                        scope.session_id.clone(),
+                        token.clone(),
+                        Some(format!("Running {tool_name}")),
+                    )
+                    .await;
+                Some(ProgressHandle::for_session(
+                    self.progress_tracker.clone(),
+                    scope.session_id.clone(),
+                    token,
+                ))
             }
-        } else {
-            match self.execute_tool(tool_name, arguments).await {
-                Ok(result) => create_success_response(id, result),
-                Err(e) => {
-                    error!("Tool execution failed: {}", e);
-                    create_error_response(id, -32603, &format!("Tool execution failed: {e}"))
+            None => None,
+        };
+
+        // Execute tool with circuit breaker protection if enabled
+        let execution = async {
+            if let Some(ref circuit_breaker) = self.circuit_breaker {
+                match circuit_breaker
+                    .call(|| async {
+                        self.execute_tool(scope, tool_name, arguments, progress.as_ref())
+                            .await
+                    })
+                    .await
+                {
+                    Ok(result) => create_success_response(id, result),
+                    Err(CircuitBreakerError::CircuitOpen) => create_error_response(
+                        id,
+                        -32603,
+                        "Service temporarily unavailable (circuit breaker open)",
+                    ),
+                    Err(CircuitBreakerError::HalfOpenLimitExceeded) => create_error_response(
+                        id,
+                        -32603,
+                        "Service temporarily unavailable (half-open limit exceeded)",
+                    ),
+                }
+            } else {
+                match self
+                    .execute_tool(scope, tool_name, arguments, progress.as_ref())
+                    .await
+                {
+                    Ok(result) => create_success_response(id, result),
+                    Err(e) => {
+                        error!("Tool execution failed: {}", e);
+                        create_error_response(id, -32603, &format!("Tool execution failed: {e}"))
+                    }
                 }
             }
+        };
+
+        // Register the call so `notifications/cancelled` can abort it
+        let (execution, abort_handle) = abortable(execution);
+        let key = id.map(|id| in_flight_key(id, headers));
+        if let Some(ref key) = key {
+            self.in_flight
+                .lock()
+                .await
+                .insert(key.clone(), abort_handle);
         }
-    }
 
-    /// Execute a specific tool
-    async fn execute_tool(&self, tool_name: &str, arguments: &Value) -> Result<Value> {
-        match tool_name {
-            "store_memory" => self.execute_store_memory(arguments).await,
-            "search_memory" => self.execute_search_memory(arguments).await,
-            "get_statistics" => self.execute_get_statistics(arguments).await,
-            "what_did_you_remember" => self.execute_what_did_you_remember(arguments).await,
-            "harvest_conversation" => self.execute_harvest_conversation(arguments).await,
+        let result = execution.await;
+
+        if let Some(ref key) = key {
+            self.in_flight.lock().await.remove(key);
+        }
+
+        match result {
+            Ok(response) => response,
+            Err(Aborted) => {
+                info!("Tool {} cancelled by client", tool_name);
+                if let Some(ref progress) = progress {
+                    let _ = progress.cancel(Some("Request cancelled".to_string())).await;
+                }
+                create_error_response(id, REQUEST_CANCELLED_CODE, "Request cancelled")
+            }
+        }
+    }
+
+    /// Execute a specific tool
+    async fn execute_tool(
+        &self,
+        scope: &NamespaceScope,
+        tool_name: &str,
+        arguments: &Value,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
+        match tool_name {
+            "store_memory" => self.execute_store_memory(scope, arguments).await,
+            "search_memory" => self.execute_search_memory(scope, arguments).await,
+            "get_statistics" => self.execute_get_statistics(scope, arguments).await,
+            "what_did_you_remember" => self.execute_what_did_you_remember(scope, arguments).await,
+            "harvest_conversation" => {
+                self.execute_harvest_conversation(scope, arguments, progress)
+                    .await
+            }
             "get_harvester_metrics" => self.execute_get_harvester_metrics().await,
-            "migrate_memory" => self.execute_migrate_memory(arguments).await,
-            "delete_memory" => self.execute_delete_memory(arguments).await,
+            "migrate_memory" => {
+                self.execute_migrate_memory(scope, arguments, progress)
+                    .await
+            }
+            "delete_memory" => self.execute_delete_memory(scope, arguments).await,
+            "get_memory" => self.execute_get_memory(scope, arguments).await,
+            "update_memory" => self.execute_update_memory(scope, arguments).await,
+            "memory_history" => self.execute_memory_history(scope, arguments).await,
+            "link_memories" => self.execute_link_memories(scope, arguments).await,
+            "unlink_memories" => self.execute_unlink_memories(scope, arguments).await,
+            "get_neighbors" => self.execute_get_neighbors(scope, arguments).await,
+            "revert_memory" => self.execute_revert_memory(scope, arguments).await,
+            "list_memories" => self.execute_list_memories(scope, arguments).await,
+            "list_entities" => self.execute_list_entities(scope, arguments).await,
+            "list_conflicts" => self.execute_list_conflicts(scope, arguments).await,
+            "resolve_conflict" => self.execute_resolve_conflict(scope, arguments).await,
+            "store_memories" => {
+                self.execute_store_memories(scope, arguments, progress)
+                    .await
+            }
+            "delete_memories" => {
+                self.execute_delete_memories(scope, arguments, progress)
+                    .await
+            }
             #[cfg(feature = "codex-dreams")]
-            "generate_insights" => self.execute_generate_insights(arguments).await,
+            "generate_insights" => {
+                self.execute_generate_insights(scope, arguments, progress)
+                    .await
+            }
             #[cfg(feature = "codex-dreams")]
             "show_insights" => self.execute_show_insights(arguments).await,
             #[cfg(feature = "codex-dreams")]
@@ -423,7 +1192,7 @@ impl MCPHandlers {
             #[cfg(feature = "codex-dreams")]
             "insight_feedback" => self.execute_insight_feedback(arguments).await,
             #[cfg(feature = "codex-dreams")]
-            "export_insights" => self.execute_export_insights(arguments).await,
+            "export_insights" => self.execute_export_insights(scope, arguments).await,
             #[cfg(feature = "codex-dreams")]
             "reset_circuit_breaker" => self.execute_reset_circuit_breaker(arguments).await,
             _ => Err(anyhow::anyhow!("Unknown tool: {}", tool_name)),
@@ -431,7 +1200,7 @@ impl MCPHandlers {
     }
 
     /// Execute store_memory tool
-    async fn execute_store_memory(&self, args: &Value) -> Result<Value> {
+    async fn execute_store_memory(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
         let content = args
             .get("content")
             .and_then(|c| c.as_str())
@@ -445,17 +1214,16 @@ impl MCPHandlers {
 
         let importance_score = args.get("importance_score").and_then(|s| s.as_f64());
 
-        let tags = args.get("tags").and_then(|t| t.as_array()).map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect::<Vec<String>>()
-        });
+        let valid_from = parse_time_arg(args, "valid_from").map_err(|e| anyhow::anyhow!(e))?;
+        let valid_to = parse_time_arg(args, "valid_to").map_err(|e| anyhow::anyhow!(e))?;
 
-        let metadata = if let Some(tags) = tags {
-            Some(serde_json::json!({ "tags": tags }))
-        } else {
-            args.get("metadata").cloned()
-        };
+        // PII is dealt with before the content reaches the embedder
+        let screening = self
+            .pii_policy
+            .screen(scope.storage.as_ref(), content, scope.actor.as_deref())
+            .await?;
+        let content = screening.content.as_str();
+        let metadata = screening.annotate(new_memory_metadata(args, &scope.data_subject()));
 
         // Generate embedding
         let embedding = self.embedder.generate_embedding(content).await?;
@@ -469,17 +1237,27 @@ impl MCPHandlers {
             parent_id: None,
             metadata,
             expires_at: None,
+            valid_from,
+            valid_to,
+            content_hash: None,
         };
 
         // Store memory
-        match self.repository.create_memory(request).await {
+        match scope.storage.create_memory(request).await {
             Ok(memory) => {
-                let response_text = format!(
-                    "Successfully stored memory with ID: {}\nContent: {}\nTier: {:?}",
+                self.index_entities(scope, &memory).await;
+                let conflicts = self.detect_conflicts(scope, &memory).await;
+                let mut response_text = format!(
+                    "Successfully stored memory with ID: {}\nContent: {}\nTier: {:?}\nNamespace: {}",
                     memory.id,
                     content.chars().take(100).collect::<String>(),
-                    memory.tier
+                    memory.tier,
+                    memory.namespace
                 );
+                if let Some(pii) = screening.describe() {
+                    response_text.push_str(&format!("\nPII: {pii}"));
+                }
+                push_conflicts(&mut response_text, &conflicts, memory.id);
                 Ok(format_tool_response(&response_text))
             }
             Err(crate::memory::error::MemoryError::StorageExhausted { tier, limit }) => {
@@ -494,7 +1272,7 @@ impl MCPHandlers {
     }
 
     /// Execute search_memory tool with progressive response
-    async fn execute_search_memory(&self, args: &Value) -> Result<Value> {
+    async fn execute_search_memory(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
         let query = args
             .get("query")
             .and_then(|q| q.as_str())
@@ -522,6 +1300,62 @@ impl MCPHandlers {
             .and_then(|m| m.as_bool())
             .unwrap_or(true);
 
+        let search_type = match args.get("search_type").and_then(|t| t.as_str()) {
+            None | Some("hybrid") => SearchType::Hybrid,
+            Some("semantic") => SearchType::Semantic,
+            Some("fulltext") => SearchType::FullText,
+            Some("temporal") => SearchType::Temporal,
+            Some(other) => return Err(anyhow::anyhow!("Invalid search_type: {}", other)),
+        };
+        let needs_embedding = matches!(search_type, SearchType::Semantic | SearchType::Hybrid);
+
+        let rrf_k = args.get("rrf_k").and_then(|k| k.as_f64()).map(|k| k as f32);
+
+        let explain = args
+            .get("explain")
+            .and_then(|e| e.as_bool())
+            .unwrap_or(false);
+
+        let cursor = args
+            .get("cursor")
+            .and_then(|c| c.as_str())
+            .map(|c| c.to_string());
+
+        let entity = args
+            .get("entity")
+            .and_then(|e| e.as_str())
+            .map(|e| e.to_string());
+
+        let include_superseded = args.get("include_superseded").and_then(|s| s.as_bool());
+        let as_of = parse_time_arg(args, "as_of").map_err(|e| anyhow::anyhow!(e))?;
+        let time_range = match args.get("time_range").and_then(|r| r.as_str()) {
+            Some(text) => Some(self.resolve_time_expression(scope, text).await?),
+            None => None,
+        };
+        let date_range = time_range.and_then(TimeRange::to_date_range);
+        // Echoed back so callers see how the expression was read
+        let range_note = time_range
+            .map(|range| format!(" ({})", range.describe()))
+            .unwrap_or_default();
+
+        // Linked memories are pulled in after ranking, following relations
+        // from every result
+        let expansion = args
+            .get("expand_relations")
+            .and_then(|e| e.as_bool())
+            .unwrap_or(false)
+            .then(|| TraversalOptions {
+                filter: RelationFilter {
+                    relation_types: parse_relation_types(args),
+                    ..Default::default()
+                },
+                max_depth: args
+                    .get("expand_depth")
+                    .and_then(|d| d.as_u64())
+                    .unwrap_or(1) as usize,
+                max_nodes: 10,
+            });
+
         // Quick mode for immediate response
         let quick_mode = args
             .get("quick_mode")
@@ -531,40 +1365,51 @@ impl MCPHandlers {
         if quick_mode {
             // Start async search and return immediately
             let embedder = self.embedder.clone();
-            let repository = self.repository.clone();
+            let storage = scope.storage.clone();
             let query_owned = query.to_string();
+            let subject_id = scope.actor.clone();
 
             tokio::spawn(async move {
                 // Generate embedding in background
-                match embedder.generate_embedding(&query_owned).await {
+                let embedding = if needs_embedding {
+                    embedder.generate_embedding(&query_owned).await.map(Some)
+                } else {
+                    Ok(None)
+                };
+                match embedding {
                     Ok(embedding) => {
                         // Create search request
                         let search_req = SearchRequest {
                             query_text: Some(query_owned.clone()),
-                            query_embedding: Some(embedding),
+                            query_embedding: embedding,
                             limit: Some(limit),
                             offset: None,
                             tier,
                             tags: None,
-                            date_range: None,
+                            date_range,
                             importance_range: None,
                             metadata_filters: None,
                             similarity_threshold: Some(similarity_threshold),
-                            search_type: None,
+                            search_type: Some(search_type),
                             hybrid_weights: None,
-                            cursor: None,
+                            cursor,
                             include_facets: None,
                             include_metadata: Some(include_metadata),
                             ranking_boost: None,
-                            explain_score: None,
+                            explain_score: Some(explain),
+                            rrf_k,
+                            entity,
+                            include_superseded,
+                            as_of,
+                            subject_id,
                         };
 
                         // Perform search
-                        match repository.search_memories_simple(search_req).await {
-                            Ok(results) => {
+                        match storage.search_memories(search_req).await {
+                            Ok(response) => {
                                 info!(
                                     "Search completed: {} results for '{}'",
-                                    results.len(),
+                                    response.results.len(),
                                     query_owned
                                 );
                             }
@@ -581,55 +1426,69 @@ impl MCPHandlers {
 
             // Return minimal response immediately
             Ok(format_tool_response(&format!(
-                "🔍 Searching for: {}",
-                query
+                "🔍 Searching for: {}{}",
+                query, range_note
             )))
         } else {
             // Normal mode - generate embedding and search (with timeout protection)
-            let embedding = match tokio::time::timeout(
-                Duration::from_secs(30), // Increased for large model
-                self.embedder.generate_embedding(query),
-            )
-            .await
-            {
-                Ok(Ok(emb)) => emb,
-                Ok(Err(e)) => {
-                    return Ok(format_tool_response(&format!("⚠️ Embedding failed: {}", e)));
-                }
-                Err(_) => {
-                    return Ok(format_tool_response("⚠️ Embedding generation timed out"));
+            let embedding = if needs_embedding {
+                let generated = match tokio::time::timeout(
+                    Duration::from_secs(30), // Increased for large model
+                    self.embedder.generate_embedding(query),
+                )
+                .await
+                {
+                    Ok(Ok(emb)) => Ok(emb),
+                    Ok(Err(e)) => Err(format!("⚠️ Embedding failed: {}", e)),
+                    Err(_) => Err("⚠️ Embedding generation timed out".to_string()),
+                };
+                match generated {
+                    Ok(emb) => Some(emb),
+                    // Hybrid search can still rank by keywords alone
+                    Err(message) if matches!(search_type, SearchType::Hybrid) => {
+                        warn!("{}; falling back to keyword ranking", message);
+                        None
+                    }
+                    Err(message) => return Ok(format_tool_response(&message)),
                 }
+            } else {
+                None
             };
 
             // Create search request
             let search_req = SearchRequest {
                 query_text: Some(query.to_string()),
-                query_embedding: Some(embedding),
+                query_embedding: embedding,
                 limit: Some(limit),
                 offset: None,
                 tier,
                 tags: None,
-                date_range: None,
+                date_range,
                 importance_range: None,
                 metadata_filters: None,
                 similarity_threshold: Some(similarity_threshold),
-                search_type: None,
+                search_type: Some(search_type),
                 hybrid_weights: None,
-                cursor: None,
+                cursor,
                 include_facets: None,
                 include_metadata: Some(include_metadata),
                 ranking_boost: None,
-                explain_score: None,
+                explain_score: Some(explain),
+                rrf_k,
+                entity,
+                include_superseded,
+                as_of,
+                subject_id: scope.actor.clone(),
             };
 
             // Perform search with timeout
-            let results = match tokio::time::timeout(
+            let (results, next_cursor) = match tokio::time::timeout(
                 Duration::from_secs(60), // Increased for complex searches
-                self.repository.search_memories_simple(search_req),
+                scope.storage.search_memories(search_req),
             )
             .await
             {
-                Ok(Ok(res)) => res,
+                Ok(Ok(res)) => (res.results, res.next_cursor),
                 Ok(Err(e)) => {
                     return Ok(format_tool_response(&format!("⚠️ Search failed: {}", e)));
                 }
@@ -638,9 +1497,22 @@ impl MCPHandlers {
                 }
             };
 
+            let linked = match &expansion {
+                Some(options) => {
+                    match expand_search_results(scope.storage.as_ref(), &results, options).await {
+                        Ok(linked) => linked,
+                        Err(e) => {
+                            warn!("Relation expansion failed: {}", e);
+                            Vec::new()
+                        }
+                    }
+                }
+                None => Vec::new(),
+            };
+
             if results.is_empty() {
                 Ok(format_tool_response(&format!(
-                    "No memories found for query: {query}"
+                    "No memories found for query: {query}{range_note}"
                 )))
             } else {
                 // Return minimal results to avoid timeout
@@ -650,30 +1522,65 @@ impl MCPHandlers {
                     .map(|r| {
                         let content_preview =
                             r.memory.content.chars().take(100).collect::<String>();
-                        format!("[{:.2}] {}...", r.similarity_score, content_preview)
+                        let line = format!("[{:.2}] {}...", r.similarity_score, content_preview);
+                        match &r.score_explanation {
+                            Some(explanation) => {
+                                format!("{line}\n    ↳ {}", describe_score(explanation))
+                            }
+                            None => line,
+                        }
                     })
                     .collect::<Vec<String>>()
                     .join("\n");
 
-                let response_text = format!(
-                    "Found {} memories (showing top {}):\n{}",
+                let mut response_text = format!(
+                    "Found {} memories{} (showing top {}):\n{}",
                     results.len(),
+                    range_note,
                     results.len().min(3),
                     formatted_results
                 );
+                if !linked.is_empty() {
+                    let formatted_linked = linked
+                        .iter()
+                        .map(|(neighbor, score)| {
+                            format!(
+                                "[{:.2}] {}... ({})",
+                                score,
+                                neighbor
+                                    .memory
+                                    .content
+                                    .chars()
+                                    .take(100)
+                                    .collect::<String>(),
+                                describe_relation(&neighbor.relation)
+                            )
+                        })
+                        .collect::<Vec<String>>()
+                        .join("\n");
+                    response_text.push_str(&format!(
+                        "\n\nLinked memories ({}):\n{}",
+                        linked.len(),
+                        formatted_linked
+                    ));
+                }
+                if let Some(next_cursor) = next_cursor {
+                    response_text
+                        .push_str(&format!("\n\nMore results: pass cursor \"{next_cursor}\""));
+                }
                 Ok(format_tool_response(&response_text))
             }
         }
     }
 
     /// Execute get_statistics tool
-    async fn execute_get_statistics(&self, args: &Value) -> Result<Value> {
+    async fn execute_get_statistics(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
         let detailed = args
             .get("detailed")
             .and_then(|d| d.as_bool())
             .unwrap_or(false);
 
-        let stats = self.repository.get_statistics().await?;
+        let stats = scope.storage.get_statistics().await?;
 
         let stats_text = if detailed {
             format!(
@@ -730,7 +1637,11 @@ impl MCPHandlers {
     }
 
     /// Execute what_did_you_remember tool - query recent memories
-    async fn execute_what_did_you_remember(&self, args: &Value) -> Result<Value> {
+    async fn execute_what_did_you_remember(
+        &self,
+        scope: &NamespaceScope,
+        args: &Value,
+    ) -> Result<Value> {
         let context = args
             .get("context")
             .and_then(|c| c.as_str())
@@ -747,15 +1658,8 @@ impl MCPHandlers {
             .map(|l| l as i32)
             .unwrap_or(10);
 
-        // Calculate date range
         let now = Utc::now();
-        let start_date = match time_range {
-            "last_hour" => now - ChronoDuration::hours(1),
-            "last_day" => now - ChronoDuration::days(1),
-            "last_week" => now - ChronoDuration::weeks(1),
-            "last_month" => now - ChronoDuration::days(30),
-            _ => now - ChronoDuration::days(1),
-        };
+        let range = self.resolve_time_expression(scope, time_range).await?;
 
         // Search for recent memories
         let search_req = SearchRequest {
@@ -765,10 +1669,7 @@ impl MCPHandlers {
             offset: None,
             tier: None,
             tags: None,
-            date_range: Some(DateRange {
-                start: Some(start_date),
-                end: Some(now),
-            }),
+            date_range: range.to_date_range(),
             importance_range: None,
             metadata_filters: Some(serde_json::json!({
                 "context": context
@@ -781,6 +1682,11 @@ impl MCPHandlers {
             include_metadata: Some(true),
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: scope.actor.clone(),
         };
 
         // Generate embedding for context search
@@ -791,14 +1697,15 @@ impl MCPHandlers {
         let mut search_req = search_req;
         search_req.query_embedding = Some(embedding);
 
-        let results = self.repository.search_memories_simple(search_req).await?;
+        let results = scope.storage.search_memories(search_req).await?.results;
 
         if results.is_empty() {
             let response_text = format!(
-                "I haven't remembered anything specific about {} in the {}. \
+                "I haven't remembered anything specific about {} in the {} ({}). \
                  You might want to check if memories were properly harvested or stored.",
                 context,
-                time_range.replace('_', " ")
+                time_range.replace('_', " "),
+                range.describe()
             );
             Ok(format_tool_response(&response_text))
         } else {
@@ -824,9 +1731,10 @@ impl MCPHandlers {
                 .join("\n\n");
 
             let response_text = format!(
-                "Here's what I remembered about {} from the {}:\n\n{}",
+                "Here's what I remembered about {} from the {} ({}):\n\n{}",
                 context,
                 time_range.replace('_', " "),
+                range.describe(),
                 formatted_memories
             );
             Ok(format_tool_response(&response_text))
@@ -834,7 +1742,12 @@ impl MCPHandlers {
     }
 
     /// Execute harvest_conversation tool with progressive responses
-    async fn execute_harvest_conversation(&self, args: &Value) -> Result<Value> {
+    async fn execute_harvest_conversation(
+        &self,
+        scope: &NamespaceScope,
+        args: &Value,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
         let message = args.get("message").and_then(|m| m.as_str());
 
         let context = args
@@ -879,11 +1792,21 @@ impl MCPHandlers {
                     .map(|chunk| chunk.join(" "))
                     .collect();
 
+                // With a progress token the client is waiting for the result, so
+                // harvest inline (cancellable) and report each chunk
+                if progress.is_some() {
+                    return self
+                        .harvest_chunks_with_progress(scope, chunks, role, context, progress)
+                        .await;
+                }
+
                 // Process chunks asynchronously
-                let harvester = self.harvester_service.clone();
+                let harvester = self.harvester()?.clone();
                 let chunk_count = chunks.len();
                 let role_owned = role.to_string();
                 let context_owned = context.to_string();
+                let namespace = scope.namespace.clone();
+                let subject_id = scope.actor.clone();
 
                 tokio::spawn(async move {
                     for (i, chunk) in chunks.iter().enumerate() {
@@ -893,6 +1816,8 @@ impl MCPHandlers {
                             timestamp: Utc::now(),
                             role: role_owned.clone(),
                             context: format!("{}_chunk_{}", context_owned, i + 1),
+                            namespace: namespace.clone(),
+                            subject_id: subject_id.clone(),
                         };
 
                         if let Err(e) = harvester.add_message(conversation_message).await {
@@ -920,19 +1845,29 @@ impl MCPHandlers {
                     timestamp: Utc::now(),
                     role: role.to_string(),
                     context: context.to_string(),
+                    namespace: scope.namespace.clone(),
+                    subject_id: scope.actor.clone(),
                 };
 
-                self.harvester_service
-                    .add_message(conversation_message)
-                    .await?;
+                self.harvester()?.add_message(conversation_message).await?;
             }
         }
 
         // Force harvest if requested
         if force_harvest {
+            if progress.is_some() {
+                report_progress(progress, 0, 1, "Harvesting conversation".to_string()).await;
+                let result = self.harvester()?.force_harvest().await?;
+                report_progress(progress, 1, 1, "Harvest complete".to_string()).await;
+                return Ok(format_tool_response(&format!(
+                    "✓ Harvested {} messages, stored {} patterns",
+                    result.messages_processed, result.patterns_stored
+                )));
+            }
+
             if quick_mode {
                 // Ultra-minimal response mode - start harvest and return immediately
-                let harvester = self.harvester_service.clone();
+                let harvester = self.harvester()?.clone();
                 let harvest_id = Uuid::new_v4();
 
                 tokio::spawn(async move {
@@ -953,10 +1888,10 @@ impl MCPHandlers {
                 Ok(format_tool_response("✓"))
             } else {
                 // Normal async mode with slightly more detail
-                let harvester = self.harvester_service.clone();
+                let harvester = self.harvester()?.clone();
 
                 // Get quick stats before starting
-                let stats = self.repository.get_statistics().await.ok();
+                let stats = scope.storage.get_statistics().await.ok();
                 let pre_count = stats.as_ref().and_then(|s| s.total_active).unwrap_or(0);
 
                 tokio::spawn(async move {
@@ -973,124 +1908,1148 @@ impl MCPHandlers {
                     }
                 });
 
-                // Return summary statistics instead of full details
-                let response_text = if silent_mode {
-                    format!("✓ Harvesting ({} existing)", pre_count)
-                } else {
-                    format!(
-                        "✓ Harvest started\n• Current memories: {}\n• Processing in background",
-                        pre_count
-                    )
-                };
-                Ok(format_tool_response(&response_text))
-            }
-        } else {
-            // Silent queuing mode
-            let response_text = if message.is_some() {
-                if quick_mode {
-                    "✓ Queued"
-                } else {
-                    "Message queued for background harvesting"
-                }
-            } else {
-                if quick_mode {
-                    "✓ Active"
-                } else {
-                    "Background harvesting is active and monitoring conversations"
-                }
+                // Return summary statistics instead of full details
+                let response_text = if silent_mode {
+                    format!("✓ Harvesting ({} existing)", pre_count)
+                } else {
+                    format!(
+                        "✓ Harvest started\n• Current memories: {}\n• Processing in background",
+                        pre_count
+                    )
+                };
+                Ok(format_tool_response(&response_text))
+            }
+        } else {
+            // Silent queuing mode
+            let response_text = if message.is_some() {
+                if quick_mode {
+                    "✓ Queued"
+                } else {
+                    "Message queued for background harvesting"
+                }
+            } else {
+                if quick_mode {
+                    "✓ Active"
+                } else {
+                    "Background harvesting is active and monitoring conversations"
+                }
+            };
+            Ok(format_tool_response(response_text))
+        }
+    }
+
+    /// Harvest chunks of a large message in the foreground, one progress step per chunk
+    async fn harvest_chunks_with_progress(
+        &self,
+        scope: &NamespaceScope,
+        chunks: Vec<String>,
+        role: &str,
+        context: &str,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
+        let chunk_count = chunks.len();
+        let mut patterns_stored = 0;
+
+        self.mcp_logger.log_for_session(
+            scope.session_id.as_deref(),
+            LogLevel::Info,
+            Some("harvester".to_string()),
+            json!({ "message": "Harvesting conversation in chunks", "chunks": chunk_count }),
+        );
+
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let conversation_message = ConversationMessage {
+                id: Uuid::new_v4().to_string(),
+                content: chunk,
+                timestamp: Utc::now(),
+                role: role.to_string(),
+                context: format!("{}_chunk_{}", context, i + 1),
+                namespace: scope.namespace.clone(),
+                subject_id: scope.actor.clone(),
+            };
+
+            self.harvester()?.add_message(conversation_message).await?;
+            patterns_stored += self.harvester()?.force_harvest().await?.patterns_stored;
+
+            report_progress(
+                progress,
+                i + 1,
+                chunk_count,
+                format!("Harvested chunk {}/{}", i + 1, chunk_count),
+            )
+            .await;
+        }
+
+        Ok(format_tool_response(&format!(
+            "✓ Harvested {chunk_count} chunks, stored {patterns_stored} patterns"
+        )))
+    }
+
+    /// Execute get_harvester_metrics tool
+    async fn execute_get_harvester_metrics(&self) -> Result<Value> {
+        let metrics = self.harvester()?.get_metrics().await;
+
+        let metrics_text = format!(
+            "Silent Harvester Metrics:\n\n\
+             📊 Processing Stats:\n\
+             • Messages Processed: {}\n\
+             • Patterns Extracted: {}\n\
+             • Memories Stored: {}\n\
+             • Duplicates Filtered: {}\n\n\
+             ⚙️ Performance:\n\
+             • Average Extraction Time: {}ms\n\
+             • Average Batch Processing Time: {}ms\n\
+             • Last Harvest: {}",
+            metrics.messages_processed,
+            metrics.patterns_extracted,
+            metrics.memories_stored,
+            metrics.duplicates_filtered,
+            metrics.avg_extraction_time_ms,
+            metrics.avg_batch_processing_time_ms,
+            metrics
+                .last_harvest_time
+                .map(|t| format!("{} ago", format_duration(Utc::now() - t)))
+                .unwrap_or_else(|| "Never".to_string())
+        );
+
+        Ok(format_tool_response(&metrics_text))
+    }
+
+    /// Execute migrate_memory tool
+    async fn execute_migrate_memory(
+        &self,
+        scope: &NamespaceScope,
+        args: &Value,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
+        let target_tier = args
+            .get("target_tier")
+            .and_then(|t| t.as_str())
+            .and_then(|t| t.parse::<MemoryTier>().ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!("Missing or invalid required 'target_tier' parameter")
+            })?;
+
+        let reason = args
+            .get("reason")
+            .and_then(|r| r.as_str())
+            .map(String::from);
+
+        if let Some(ids) = args.get("memory_ids").and_then(|ids| ids.as_array()) {
+            return self
+                .execute_bulk_migration(scope, ids, target_tier, reason, progress)
+                .await;
+        }
+
+        let memory_id_str = args
+            .get("memory_id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
+        let memory_id = Uuid::parse_str(memory_id_str)?;
+
+        // Perform migration
+        let updated_memory = scope
+            .storage
+            .migrate_memory(memory_id, target_tier, reason.clone())
+            .await?;
+
+        let response_text = format!(
+            "Successfully migrated memory {} to {:?} tier\n\
+             Content: {}\n\
+             Reason: {}",
+            memory_id,
+            target_tier,
+            updated_memory.content.chars().take(100).collect::<String>(),
+            reason.unwrap_or_else(|| "No reason provided".to_string())
+        );
+
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Migrate many memories, reporting progress after each one
+    async fn execute_bulk_migration(
+        &self,
+        scope: &NamespaceScope,
+        ids: &[Value],
+        target_tier: MemoryTier,
+        reason: Option<String>,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
+        let total = ids.len();
+        let mut migrated = 0;
+        let mut failures = Vec::new();
+
+        self.mcp_logger.log_for_session(
+            scope.session_id.as_deref(),
+            LogLevel::Info,
+            Some("migration".to_string()),
+            json!({ "message": "Bulk migration started", "count": total, "target_tier": target_tier }),
+        );
+
+        for (index, id) in ids.iter().enumerate() {
+            let result = match id.as_str().map(Uuid::parse_str) {
+                Some(Ok(memory_id)) => scope
+                    .storage
+                    .migrate_memory(memory_id, target_tier, reason.clone())
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+                _ => Err("invalid memory id".to_string()),
+            };
+
+            match result {
+                Ok(()) => migrated += 1,
+                Err(e) => {
+                    warn!("Bulk migration of {} failed: {}", id, e);
+                    failures.push(format!("{id}: {e}"));
+                }
+            }
+
+            report_progress(
+                progress,
+                index + 1,
+                total,
+                format!("Migrated {migrated}/{total} memories"),
+            )
+            .await;
+        }
+
+        if !failures.is_empty() {
+            self.mcp_logger.log_for_session(
+                scope.session_id.as_deref(),
+                LogLevel::Warning,
+                Some("migration".to_string()),
+                json!({ "message": "Some memories failed to migrate", "failures": failures }),
+            );
+        }
+
+        let mut response_text = format!(
+            "Migrated {} of {} memories to {:?} tier\nReason: {}",
+            migrated,
+            total,
+            target_tier,
+            reason.unwrap_or_else(|| "No reason provided".to_string())
+        );
+        if !failures.is_empty() {
+            response_text.push_str(&format!("\nFailed:\n• {}", failures.join("\n• ")));
+        }
+
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute delete_memory tool
+    async fn execute_delete_memory(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let memory_id_str = args
+            .get("memory_id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
+        let memory_id = Uuid::parse_str(memory_id_str)?;
+
+        // Perform deletion
+        scope.storage.delete_memory(memory_id).await?;
+
+        let response_text = format!("Successfully deleted memory {memory_id}");
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute get_memory tool
+    async fn execute_get_memory(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let memory_id_str = args
+            .get("memory_id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
+        let memory_id = Uuid::parse_str(memory_id_str)?;
+        let reveal_pii = args
+            .get("reveal_pii")
+            .and_then(|r| r.as_bool())
+            .unwrap_or(false);
+        if reveal_pii {
+            self.authorize_pii_reveal(scope)?;
+        }
+
+        let mut memory = scope.storage.get_memory(memory_id).await?;
+        if reveal_pii {
+            memory.content = self
+                .pii_policy
+                .reveal(
+                    scope.storage.as_ref(),
+                    &memory.content,
+                    scope.actor.as_deref(),
+                )
+                .await?;
+        }
+        let entities = scope.storage.get_memory_entities(memory_id).await?;
+        let conflicts = scope
+            .storage
+            .list_conflicts(None, Some(memory_id), 20)
+            .await?;
+        let conflicts = conflicts
+            .iter()
+            .filter(|c| c.status != ConflictStatus::BothValid)
+            .map(|c| describe_conflict(c, memory_id))
+            .collect::<Vec<String>>();
+        let entities = if entities.is_empty() {
+            "none".to_string()
+        } else {
+            entities
+                .iter()
+                .map(|e| format!("{} ({})", e.canonical_name, e.entity_type.as_str()))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let response_text = format!(
+            "Memory {}\n\
+             Tier: {:?}\n\
+             Importance: {:.2}\n\
+             Accessed: {} times\n\
+             Created: {}\n\
+             Updated: {}\n\
+             Valid: {}\n\
+             Entities: {}\n\
+             Conflicts: {}\n\
+             Metadata: {}\n\n\
+             {}",
+            memory.id,
+            memory.tier,
+            memory.importance_score,
+            memory.access_count,
+            memory.created_at.format("%Y-%m-%d %H:%M UTC"),
+            memory.updated_at.format("%Y-%m-%d %H:%M UTC"),
+            describe_valid_time(&memory),
+            entities,
+            if conflicts.is_empty() {
+                "none".to_string()
+            } else {
+                format!("\n{}", conflicts.join("\n"))
+            },
+            memory.metadata,
+            memory.content
+        );
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Fails unless the client has been granted the `pii:reveal` permission
+    fn authorize_pii_reveal(&self, scope: &NamespaceScope) -> Result<()> {
+        let (Some(rbac), Some(actor)) = (&self.rbac, scope.actor.as_deref()) else {
+            return Err(anyhow::anyhow!(
+                "Revealing PII requires an authenticated client and role-based access control"
+            ));
+        };
+        let check = rbac.check_permission(actor, PII_REVEAL_PERMISSION);
+        if !check.allowed {
+            return Err(anyhow::anyhow!(
+                "Not allowed to reveal PII: {}",
+                check.reason
+            ));
+        }
+        Ok(())
+    }
+
+    /// Execute update_memory tool
+    async fn execute_update_memory(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let memory_id_str = args
+            .get("memory_id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
+        let memory_id = Uuid::parse_str(memory_id_str)?;
+
+        let content = args
+            .get("content")
+            .and_then(|c| c.as_str())
+            .map(String::from);
+
+        let tier = match args.get("tier") {
+            Some(tier) => Some(
+                tier.as_str()
+                    .and_then(|t| t.parse::<MemoryTier>().ok())
+                    .ok_or_else(|| anyhow::anyhow!("Invalid tier: {tier}"))?,
+            ),
+            None => None,
+        };
+
+        let tags = parse_tags(args);
+        let stored = if args.get("metadata").is_some() || tags.is_some() || content.is_some() {
+            peek_memory(scope, memory_id).await?.metadata
+        } else {
+            json!({})
+        };
+
+        // Tags live in the metadata; replacing them keeps the other keys
+        let mut metadata = match args.get("metadata") {
+            Some(metadata) => Some(keep_subject_id(metadata.clone(), &stored)?),
+            None => None,
+        };
+        if let Some(tags) = tags {
+            let mut base = metadata.take().unwrap_or_else(|| stored.clone());
+            if !base.is_object() {
+                base = json!({});
+            }
+            base["tags"] = json!(tags);
+            metadata = Some(base);
+        }
+
+        let content = match content {
+            Some(content) => {
+                let screening = self
+                    .pii_policy
+                    .screen(scope.storage.as_ref(), &content, scope.actor.as_deref())
+                    .await?;
+                if let Some(pii) = screening.metadata() {
+                    let mut base = metadata.take().unwrap_or_else(|| stored.clone());
+                    if !base.is_object() {
+                        base = json!({});
+                    }
+                    base["pii"] = pii;
+                    metadata = Some(base);
+                }
+                Some(screening.content)
+            }
+            None => None,
+        };
+
+        let embedding = match &content {
+            Some(content) => Some(self.embedder.generate_embedding(content).await?),
+            None => None,
+        };
+
+        let content_changed = content.is_some();
+        let request = UpdateMemoryRequest {
+            content,
+            embedding,
+            tier,
+            importance_score: args.get("importance_score").and_then(|s| s.as_f64()),
+            metadata,
+            expires_at: None,
+            valid_from: parse_time_arg(args, "valid_from").map_err(|e| anyhow::anyhow!(e))?,
+            valid_to: parse_time_arg(args, "valid_to").map_err(|e| anyhow::anyhow!(e))?,
+            actor: scope.actor.clone(),
+            reason: args
+                .get("reason")
+                .and_then(|r| r.as_str())
+                .map(String::from),
+            content_hash: None,
+        };
+
+        let memory = scope.storage.update_memory(memory_id, request).await?;
+        let conflicts = if content_changed {
+            self.reindex_entities(scope, &memory).await;
+            self.detect_conflicts(scope, &memory).await
+        } else {
+            Vec::new()
+        };
+
+        let mut response_text = format!(
+            "Successfully updated memory {}\nContent: {}\nTier: {:?}\nImportance: {:.2}",
+            memory.id,
+            memory.content.chars().take(100).collect::<String>(),
+            memory.tier,
+            memory.importance_score
+        );
+        push_conflicts(&mut response_text, &conflicts, memory.id);
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute memory_history tool: list revisions, or diff two of them
+    async fn execute_memory_history(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let memory_id_str = args
+            .get("memory_id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
+        let memory_id = Uuid::parse_str(memory_id_str)?;
+
+        let from = args.get("from_revision").and_then(|r| r.as_i64());
+        let to = args.get("to_revision").and_then(|r| r.as_i64());
+        if let (Some(from), Some(to)) = (from, to) {
+            let from = scope
+                .storage
+                .get_memory_revision(memory_id, from as i32)
+                .await?;
+            let to = scope
+                .storage
+                .get_memory_revision(memory_id, to as i32)
+                .await?;
+            let diff = RevisionDiff::between(&from, &to);
+            if diff.is_empty() {
+                return Ok(format_tool_response(&format!(
+                    "Revisions {} and {} of memory {} are identical",
+                    diff.from_revision, diff.to_revision, memory_id
+                )));
+            }
+
+            let mut lines = vec![format!(
+                "Memory {} revision {} → {}",
+                memory_id, diff.from_revision, diff.to_revision
+            )];
+            if !diff.content.is_empty() {
+                lines.push(format!("Content: {}", diff.render_content()));
+            }
+            for change in &diff.metadata {
+                let show = |value: &Option<Value>| match value {
+                    Some(value) => value.to_string(),
+                    None => "(none)".to_string(),
+                };
+                lines.push(format!(
+                    "Metadata {}: {} → {}",
+                    change.key,
+                    show(&change.before),
+                    show(&change.after)
+                ));
+            }
+            if let Some((before, after)) = diff.importance_score {
+                lines.push(format!("Importance: {before:.2} → {after:.2}"));
+            }
+            if let Some((before, after)) = diff.tier {
+                lines.push(format!("Tier: {before:?} → {after:?}"));
+            }
+            return Ok(format_tool_response(&lines.join("\n")));
+        }
+
+        let limit = args.get("limit").and_then(|l| l.as_i64()).unwrap_or(20);
+        let revisions = scope.storage.memory_history(memory_id, Some(limit)).await?;
+        if revisions.is_empty() {
+            return Ok(format_tool_response(&format!(
+                "Memory {memory_id} has not been changed since it was stored"
+            )));
+        }
+
+        let lines = revisions
+            .iter()
+            .map(|r| {
+                format!(
+                    "• Revision {} [{}] by {}: {}\n  {}",
+                    r.revision,
+                    r.created_at.format("%Y-%m-%d %H:%M UTC"),
+                    r.actor.as_deref().unwrap_or("unknown"),
+                    r.reason.as_deref().unwrap_or("no reason given"),
+                    r.content.chars().take(80).collect::<String>()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let response_text = format!(
+            "{} revisions of memory {}, newest first:\n{}",
+            revisions.len(),
+            memory_id,
+            lines
+        );
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute revert_memory tool, re-embedding the restored content
+    async fn execute_revert_memory(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let memory_id_str = args
+            .get("memory_id")
+            .and_then(|id| id.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
+        let memory_id = Uuid::parse_str(memory_id_str)?;
+        let revision = args
+            .get("revision")
+            .and_then(|r| r.as_i64())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'revision' parameter"))?
+            as i32;
+
+        let target = scope
+            .storage
+            .get_memory_revision(memory_id, revision)
+            .await?;
+        let current = peek_memory(scope, memory_id).await?;
+        let content_changed = target.content_hash != current.content_hash;
+        let embedding = if content_changed {
+            Some(self.embedder.generate_embedding(&target.content).await?)
+        } else {
+            None
+        };
+
+        let memory = scope
+            .storage
+            .revert_memory(memory_id, revision, embedding, scope.actor.clone())
+            .await?;
+        let conflicts = if content_changed {
+            self.reindex_entities(scope, &memory).await;
+            self.detect_conflicts(scope, &memory).await
+        } else {
+            Vec::new()
+        };
+
+        let mut response_text = format!(
+            "Reverted memory {} to revision {}\nContent: {}\nImportance: {:.2}",
+            memory.id,
+            revision,
+            memory.content.chars().take(100).collect::<String>(),
+            memory.importance_score
+        );
+        push_conflicts(&mut response_text, &conflicts, memory.id);
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute link_memories tool
+    async fn execute_link_memories(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let source_id = parse_uuid_arg(args, "source_id")?;
+        let target_id = parse_uuid_arg(args, "target_id")?;
+        let relation_type = args
+            .get("relation")
+            .and_then(|r| r.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'relation' parameter"))?
+            .parse::<RelationshipType>()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let request = LinkMemoriesRequest {
+            source_id,
+            target_id,
+            relation_type,
+            strength: args.get("strength").and_then(|s| s.as_f64()),
+            metadata: args.get("metadata").cloned(),
+            created_by: scope.actor.clone(),
+        };
+        let relation = scope.storage.link_memories(request).await?;
+
+        let response_text = format!(
+            "Linked memories: {}\nStrength: {:.2}",
+            describe_relation(&relation),
+            relation.strength
+        );
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute unlink_memories tool
+    async fn execute_unlink_memories(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let source_id = parse_uuid_arg(args, "source_id")?;
+        let target_id = parse_uuid_arg(args, "target_id")?;
+        let relation_type = args
+            .get("relation")
+            .and_then(|r| r.as_str())
+            .map(|r| r.parse::<RelationshipType>())
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let removed = scope
+            .storage
+            .unlink_memories(source_id, target_id, relation_type)
+            .await?;
+
+        let response_text = if removed == 0 {
+            format!("No relation from {source_id} to {target_id} found")
+        } else {
+            format!("Removed {removed} relation(s) from {source_id} to {target_id}")
+        };
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute get_neighbors tool
+    async fn execute_get_neighbors(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let memory_id = parse_uuid_arg(args, "memory_id")?;
+        let direction = match args.get("direction").and_then(|d| d.as_str()) {
+            Some(direction) => direction
+                .parse::<RelationDirection>()
+                .map_err(|e| anyhow::anyhow!(e))?,
+            None => RelationDirection::Both,
+        };
+
+        let options = TraversalOptions {
+            filter: RelationFilter {
+                relation_types: parse_relation_types(args),
+                direction,
+                min_strength: args.get("min_strength").and_then(|s| s.as_f64()),
+            },
+            max_depth: args.get("depth").and_then(|d| d.as_u64()).unwrap_or(1) as usize,
+            max_nodes: args.get("limit").and_then(|l| l.as_u64()).unwrap_or(20) as usize,
+        };
+        let neighbors = scope
+            .storage
+            .traverse_relations(memory_id, &options)
+            .await?;
+        if neighbors.is_empty() {
+            return Ok(format_tool_response(&format!(
+                "No related memories found for {memory_id}"
+            )));
+        }
+
+        let lines = neighbors
+            .iter()
+            .map(|n| {
+                format!(
+                    "• {} [depth {}, strength {:.2}] {}\n  {}",
+                    n.memory.id,
+                    n.depth,
+                    n.path_strength,
+                    describe_relation(&n.relation),
+                    n.memory.content.chars().take(80).collect::<String>()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let response_text = format!(
+            "{} memories related to {}:\n{}",
+            neighbors.len(),
+            memory_id,
+            lines
+        );
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute list_entities tool
+    async fn execute_list_entities(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let entity_type = match args.get("entity_type").and_then(|t| t.as_str()) {
+            Some(entity_type) => Some(
+                entity_type
+                    .parse::<EntityType>()
+                    .map_err(|e| anyhow::anyhow!(e))?,
+            ),
+            None => None,
+        };
+        let limit = args.get("limit").and_then(|l| l.as_i64()).unwrap_or(20);
+
+        let entities = scope.storage.list_entities(entity_type, limit).await?;
+        if entities.is_empty() {
+            return Ok(format_tool_response("No entities found"));
+        }
+
+        let lines = entities
+            .iter()
+            .map(|e| {
+                format!(
+                    "• {} [{}, {} memories] aliases: {}",
+                    e.canonical_name,
+                    e.entity_type.as_str(),
+                    e.mention_count,
+                    e.aliases.join(", ")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let response_text = format!("{} entities:\n{}", entities.len(), lines);
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute list_conflicts tool
+    async fn execute_list_conflicts(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let status = match args.get("status").and_then(|s| s.as_str()) {
+            Some(status) => Some(
+                status
+                    .parse::<ConflictStatus>()
+                    .map_err(|e| anyhow::anyhow!(e))?,
+            ),
+            None => None,
+        };
+        let memory_id = match args.get("memory_id") {
+            Some(_) => Some(parse_uuid_arg(args, "memory_id")?),
+            None => None,
+        };
+        let limit = args.get("limit").and_then(|l| l.as_i64()).unwrap_or(20);
+
+        let conflicts = scope
+            .storage
+            .list_conflicts(status, memory_id, limit)
+            .await?;
+        if conflicts.is_empty() {
+            return Ok(format_tool_response("No conflicts found"));
+        }
+
+        let lines = conflicts
+            .iter()
+            .map(|c| {
+                format!(
+                    "• {} [{}, {} {:.2}] {} vs {}\n  {}",
+                    c.id,
+                    c.status.as_str(),
+                    c.detector,
+                    c.confidence,
+                    c.memory_id,
+                    c.conflicting_memory_id,
+                    c.reason
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let response_text = format!("{} conflicts:\n{}", conflicts.len(), lines);
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Execute resolve_conflict tool
+    async fn execute_resolve_conflict(
+        &self,
+        scope: &NamespaceScope,
+        args: &Value,
+    ) -> Result<Value> {
+        let conflict_id = parse_uuid_arg(args, "conflict_id")?;
+        let status = args
+            .get("resolution")
+            .and_then(|r| r.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'resolution' parameter"))?
+            .parse::<ConflictStatus>()
+            .map_err(|e| anyhow::anyhow!(e))?;
+        let current_memory_id = match args.get("current_memory_id") {
+            Some(_) => Some(parse_uuid_arg(args, "current_memory_id")?),
+            None => None,
+        };
+
+        let conflict = scope
+            .storage
+            .resolve_conflict(ResolveConflictRequest {
+                conflict_id,
+                status,
+                current_memory_id,
+                resolved_by: scope.actor.clone(),
+            })
+            .await?;
+
+        let response_text = match conflict.current_memory_id() {
+            Some(current) => format!(
+                "Conflict {} resolved: {} supersedes {}",
+                conflict.id,
+                current,
+                conflict.superseded_memory_id.unwrap_or_default()
+            ),
+            None => format!(
+                "Conflict {} marked {}",
+                conflict.id,
+                conflict.status.as_str()
+            ),
+        };
+        Ok(format_tool_response(&response_text))
+    }
+
+    /// Check a newly stored or changed memory for contradictions with stored
+    /// ones. Failures are logged; the memory is stored either way.
+    async fn detect_conflicts(
+        &self,
+        scope: &NamespaceScope,
+        memory: &Memory,
+    ) -> Vec<MemoryConflict> {
+        match self
+            .contradiction_detector
+            .detect(scope.storage.as_ref(), memory)
+            .await
+        {
+            Ok(conflicts) => conflicts,
+            Err(e) => {
+                warn!("Failed to check memory {} for conflicts: {}", memory.id, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Link a newly stored memory to the entities it mentions. Failures
+    /// only cost the entity links, never the memory.
+    async fn index_entities(&self, scope: &NamespaceScope, memory: &Memory) {
+        if let Err(e) = index_memory_entities(
+            scope.storage.as_ref(),
+            &self.entity_extractor,
+            Some(self.embedder.as_ref()),
+            memory,
+        )
+        .await
+        {
+            warn!("Failed to index entities of memory {}: {}", memory.id, e);
+        }
+    }
+
+    /// Replace the entity links of a memory whose content changed
+    async fn reindex_entities(&self, scope: &NamespaceScope, memory: &Memory) {
+        if let Err(e) = scope.storage.unlink_memory_entities(memory.id).await {
+            warn!("Failed to unlink entities of memory {}: {}", memory.id, e);
+            return;
+        }
+        self.index_entities(scope, memory).await;
+    }
+
+    /// Resolve a natural-language time expression. Events without a
+    /// configured date are dated by the earliest memory that mentions them.
+    async fn resolve_time_expression(
+        &self,
+        scope: &NamespaceScope,
+        text: &str,
+    ) -> Result<TimeRange> {
+        let (name, relation) = match self.time_parser.parse(text) {
+            Ok(TimeExpression::Range(range)) => return Ok(range),
+            Ok(TimeExpression::Event { name, relation }) => (name, relation),
+            Err(message) => return Err(anyhow::anyhow!(message)),
+        };
+
+        let needle = name.to_lowercase();
+        let happened_at = self
+            .event_mentions(scope, &name)
+            .await?
+            .iter()
+            .filter(|memory| memory.content.to_lowercase().contains(&needle))
+            .map(|memory| memory.valid_since())
+            .min()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No memory mentions '{name}'; give its date in CODEX_TIME_EVENTS \
+                     or use an explicit date"
+                )
+            })?;
+        Ok(relation.around(happened_at))
+    }
+
+    /// Memories that may mention an event. Encrypted content cannot be
+    /// matched in the database, so with field encryption the most recent
+    /// memories are decrypted and scanned instead of running a full-text search
+    async fn event_mentions(&self, scope: &NamespaceScope, name: &str) -> Result<Vec<Memory>> {
+        let request = SearchRequest {
+            limit: Some(50),
+            include_superseded: Some(true),
+            subject_id: scope.actor.clone(),
+            ..Default::default()
+        };
+        if self.field_encryption.is_none() {
+            let request = SearchRequest {
+                query_text: Some(name.to_string()),
+                search_type: Some(SearchType::FullText),
+                ..request
             };
-            Ok(format_tool_response(response_text))
+            let response = scope.storage.search_memories(request).await?;
+            return Ok(response.results.into_iter().map(|r| r.memory).collect());
+        }
+
+        let mut memories = Vec::new();
+        let mut cursor = None;
+        while memories.len() < EVENT_SCAN_LIMIT {
+            let request = SearchRequest {
+                search_type: Some(SearchType::Temporal),
+                limit: Some(EVENT_SCAN_PAGE),
+                cursor,
+                ..request.clone()
+            };
+            let response = scope.storage.search_memories(request).await?;
+            memories.extend(response.results.into_iter().map(|r| r.memory));
+            cursor = response.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
         }
+        Ok(memories)
     }
 
-    /// Execute get_harvester_metrics tool
-    async fn execute_get_harvester_metrics(&self) -> Result<Value> {
-        let metrics = self.harvester_service.get_metrics().await;
+    /// Execute list_memories tool
+    async fn execute_list_memories(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
+        let tier = args
+            .get("tier")
+            .and_then(|t| t.as_str())
+            .and_then(|t| t.parse::<MemoryTier>().ok());
 
-        let metrics_text = format!(
-            "Silent Harvester Metrics:\n\n\
-             📊 Processing Stats:\n\
-             • Messages Processed: {}\n\
-             • Patterns Extracted: {}\n\
-             • Memories Stored: {}\n\
-             • Duplicates Filtered: {}\n\n\
-             ⚙️ Performance:\n\
-             • Average Extraction Time: {}ms\n\
-             • Average Batch Processing Time: {}ms\n\
-             • Last Harvest: {}",
-            metrics.messages_processed,
-            metrics.patterns_extracted,
-            metrics.memories_stored,
-            metrics.duplicates_filtered,
-            metrics.avg_extraction_time_ms,
-            metrics.avg_batch_processing_time_ms,
-            metrics
-                .last_harvest_time
-                .map(|t| format!("{} ago", format_duration(Utc::now() - t)))
-                .unwrap_or_else(|| "Never".to_string())
-        );
+        let limit = args
+            .get("limit")
+            .and_then(|l| l.as_i64())
+            .map(|l| l as i32)
+            .unwrap_or(20);
 
-        Ok(format_tool_response(&metrics_text))
+        // A temporal search without a query lists every matching memory,
+        // newest first, and pages with the same cursors as search_memory
+        let request = SearchRequest {
+            search_type: Some(SearchType::Temporal),
+            tier,
+            tags: parse_tags(args),
+            limit: Some(limit),
+            cursor: args
+                .get("cursor")
+                .and_then(|c| c.as_str())
+                .map(String::from),
+            include_metadata: Some(true),
+            ..Default::default()
+        };
+
+        let response = scope.storage.search_memories(request).await?;
+        if response.results.is_empty() {
+            return Ok(format_tool_response("No memories found"));
+        }
+
+        let lines = response
+            .results
+            .iter()
+            .map(|r| {
+                format!(
+                    "• {} [{:?}, {}] {}",
+                    r.memory.id,
+                    r.memory.tier,
+                    r.memory.created_at.format("%Y-%m-%d"),
+                    r.memory.content.chars().take(80).collect::<String>()
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let mut response_text = format!("{} memories:\n{}", response.results.len(), lines);
+        if let Some(next_cursor) = response.next_cursor {
+            response_text.push_str(&format!("\n\nMore memories: pass cursor \"{next_cursor}\""));
+        }
+        Ok(format_tool_response(&response_text))
     }
 
-    /// Execute migrate_memory tool
-    async fn execute_migrate_memory(&self, args: &Value) -> Result<Value> {
-        let memory_id_str = args
-            .get("memory_id")
-            .and_then(|id| id.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
-        let memory_id = Uuid::parse_str(memory_id_str)?;
+    /// Execute store_memories tool, embedding every memory in one batch
+    async fn execute_store_memories(
+        &self,
+        scope: &NamespaceScope,
+        args: &Value,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
+        let items = args
+            .get("memories")
+            .and_then(|m| m.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memories' parameter"))?;
+
+        let contents = items
+            .iter()
+            .map(|item| {
+                item.get("content")
+                    .and_then(|c| c.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Every memory needs 'content'"))
+            })
+            .collect::<Result<Vec<&str>>>()?;
+
+        let total = items.len();
+        let mut stored = Vec::new();
+        let mut failures = Vec::new();
+        let mut conflict_count = 0;
+
+        // Memories the PII policy rejects are neither embedded nor stored
+        let mut screened = Vec::with_capacity(total);
+        for (index, (item, content)) in items.iter().zip(contents).enumerate() {
+            match self
+                .pii_policy
+                .screen(scope.storage.as_ref(), content, scope.actor.as_deref())
+                .await
+            {
+                Ok(screening) => screened.push((index, item, screening)),
+                Err(e) => failures.push(format!("#{index}: {e}")),
+            }
+        }
 
-        let target_tier = args
-            .get("target_tier")
-            .and_then(|t| t.as_str())
-            .and_then(|t| t.parse::<MemoryTier>().ok())
-            .ok_or_else(|| {
-                anyhow::anyhow!("Missing or invalid required 'target_tier' parameter")
-            })?;
+        let contents = screened
+            .iter()
+            .map(|(_, _, screening)| screening.content.clone())
+            .collect::<Vec<String>>();
+        let embeddings = self.embedder.generate_embeddings_batch(&contents).await?;
+
+        for ((index, item, screening), embedding) in screened.into_iter().zip(embeddings) {
+            let request = CreateMemoryRequest {
+                metadata: screening.annotate(new_memory_metadata(item, &scope.data_subject())),
+                content: screening.content,
+                embedding: Some(embedding),
+                tier: item
+                    .get("tier")
+                    .and_then(|t| t.as_str())
+                    .and_then(|t| t.parse::<MemoryTier>().ok()),
+                importance_score: item.get("importance_score").and_then(|s| s.as_f64()),
+                parent_id: None,
+                expires_at: None,
+                valid_from: parse_time_arg(item, "valid_from").map_err(|e| anyhow::anyhow!(e))?,
+                valid_to: parse_time_arg(item, "valid_to").map_err(|e| anyhow::anyhow!(e))?,
+                content_hash: None,
+            };
 
-        let reason = args
-            .get("reason")
-            .and_then(|r| r.as_str())
-            .map(String::from);
+            match scope.storage.create_memory(request).await {
+                Ok(memory) => {
+                    self.index_entities(scope, &memory).await;
+                    conflict_count += self.detect_conflicts(scope, &memory).await.len();
+                    stored.push(memory.id)
+                }
+                Err(e) => {
+                    warn!("Batch store of memory {} failed: {}", index, e);
+                    failures.push(format!("#{index}: {e}"));
+                }
+            }
 
-        // Perform migration
-        let updated_memory = self
-            .repository
-            .migrate_memory(memory_id, target_tier, reason.clone())
-            .await?;
+            report_progress(
+                progress,
+                index + 1,
+                total,
+                format!("Stored {}/{total} memories", stored.len()),
+            )
+            .await;
+        }
 
-        let response_text = format!(
-            "Successfully migrated memory {} to {:?} tier\n\
-             Content: {}\n\
-             Reason: {}",
-            memory_id,
-            target_tier,
-            updated_memory.content.chars().take(100).collect::<String>(),
-            reason.unwrap_or_else(|| "No reason provided".to_string())
+        let mut response_text = format!(
+            "Stored {} of {} memories in namespace {}",
+            stored.len(),
+            total,
+            scope.namespace
         );
+        if !stored.is_empty() {
+            let ids = stored
+                .iter()
+                .map(Uuid::to_string)
+                .collect::<Vec<String>>()
+                .join("\n• ");
+            response_text.push_str(&format!("\nIDs:\n• {ids}"));
+        }
+        if !failures.is_empty() {
+            response_text.push_str(&format!("\nFailed:\n• {}", failures.join("\n• ")));
+        }
+        if conflict_count > 0 {
+            response_text.push_str(&format!(
+                "\n⚠️ {conflict_count} conflicts with existing memories; see list_conflicts"
+            ));
+        }
 
         Ok(format_tool_response(&response_text))
     }
 
-    /// Execute delete_memory tool
-    async fn execute_delete_memory(&self, args: &Value) -> Result<Value> {
-        let memory_id_str = args
-            .get("memory_id")
-            .and_then(|id| id.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_id' parameter"))?;
-        let memory_id = Uuid::parse_str(memory_id_str)?;
+    /// Execute delete_memories tool
+    async fn execute_delete_memories(
+        &self,
+        scope: &NamespaceScope,
+        args: &Value,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
+        let ids = args
+            .get("memory_ids")
+            .and_then(|ids| ids.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Missing required 'memory_ids' parameter"))?;
+
+        let total = ids.len();
+        let mut deleted = 0;
+        let mut failures = Vec::new();
+
+        for (index, id) in ids.iter().enumerate() {
+            let result = match id.as_str().map(Uuid::parse_str) {
+                Some(Ok(memory_id)) => scope
+                    .storage
+                    .delete_memory(memory_id)
+                    .await
+                    .map_err(|e| e.to_string()),
+                _ => Err("invalid memory id".to_string()),
+            };
 
-        // Perform deletion
-        self.repository.delete_memory(memory_id).await?;
+            match result {
+                Ok(()) => deleted += 1,
+                Err(e) => {
+                    warn!("Bulk deletion of {} failed: {}", id, e);
+                    failures.push(format!("{id}: {e}"));
+                }
+            }
+
+            report_progress(
+                progress,
+                index + 1,
+                total,
+                format!("Deleted {deleted}/{total} memories"),
+            )
+            .await;
+        }
+
+        let mut response_text = format!("Deleted {deleted} of {total} memories");
+        if !failures.is_empty() {
+            response_text.push_str(&format!("\nFailed:\n• {}", failures.join("\n• ")));
+        }
 
-        let response_text = format!("Successfully deleted memory {memory_id}");
         Ok(format_tool_response(&response_text))
     }
 
     #[cfg(feature = "codex-dreams")]
     /// Execute generate_insights tool
-    async fn execute_generate_insights(&self, args: &Value) -> Result<Value> {
+    async fn execute_generate_insights(
+        &self,
+        scope: &NamespaceScope,
+        args: &Value,
+        progress: Option<&ProgressHandle>,
+    ) -> Result<Value> {
         // Parse parameters
         let time_period = args
             .get("time_period")
@@ -1117,247 +3076,37 @@ impl MCPHandlers {
 
                 debug!("Insights processor available, starting memory retrieval process");
 
-                // Get memories based on time period using same approach as what_did_you_remember
-                // Generate embedding first, then use semantic search with date filter (proven to work)
-                let memories = match time_period {
-                    "all" => {
-                        debug!("Searching all memories without date filter");
-                        let embedding = self
-                            .embedder
-                            .generate_embedding("context:conversation")
-                            .await?;
-                        self.repository
-                            .search_memories_simple(SearchRequest {
-                                query_text: Some("context:conversation".to_string()),
-                                query_embedding: Some(embedding),
-                                limit: Some(5000), // Higher limit for all memories
-                                offset: None,
-                                tier: None,
-                                tags: None,
-                                date_range: None, // No date filter - search all time
-                                importance_range: None,
-                                metadata_filters: None,
-                                similarity_threshold: Some(0.3),
-                                search_type: None,
-                                hybrid_weights: None,
-                                cursor: None,
-                                include_facets: None,
-                                include_metadata: Some(true),
-                                ranking_boost: None,
-                                explain_score: None,
-                            })
-                            .await
-                            .map(|results| SearchResponse {
-                                results,
-                                total_count: None,
-                                execution_time_ms: 0,
-                                facets: None,
-                                next_cursor: None,
-                                suggestions: None,
-                            })
-                    }
-                    "last_hour" => {
-                        debug!("Searching memories from last hour");
-                        let since = Utc::now() - ChronoDuration::hours(1);
-                        let embedding = self
-                            .embedder
-                            .generate_embedding("context:conversation")
-                            .await?;
-                        self.repository
-                            .search_memories_simple(SearchRequest {
-                                query_text: Some("context:conversation".to_string()),
-                                query_embedding: Some(embedding),
-                                limit: Some(100),
-                                offset: None,
-                                tier: None,
-                                tags: None,
-                                date_range: Some(DateRange {
-                                    start: Some(since),
-                                    end: Some(Utc::now()),
-                                }),
-                                importance_range: None,
-                                metadata_filters: None,
-                                similarity_threshold: Some(0.3),
-                                search_type: None,
-                                hybrid_weights: None,
-                                cursor: None,
-                                include_facets: None,
-                                include_metadata: Some(true),
-                                ranking_boost: None,
-                                explain_score: None,
-                            })
-                            .await
-                            .map(|results| SearchResponse {
-                                results,
-                                total_count: None,
-                                execution_time_ms: 0,
-                                facets: None,
-                                next_cursor: None,
-                                suggestions: None,
-                            })
-                    }
-                    "last_day" => {
-                        debug!("Searching memories from last day");
-                        let since = Utc::now() - ChronoDuration::days(1);
-                        let embedding = self
-                            .embedder
-                            .generate_embedding("context:conversation")
-                            .await?;
-                        self.repository
-                            .search_memories_simple(SearchRequest {
-                                query_text: Some("context:conversation".to_string()),
-                                query_embedding: Some(embedding),
-                                limit: Some(200),
-                                offset: None,
-                                tier: None,
-                                tags: None,
-                                date_range: Some(DateRange {
-                                    start: Some(since),
-                                    end: Some(Utc::now()),
-                                }),
-                                importance_range: None,
-                                metadata_filters: None,
-                                similarity_threshold: Some(0.3),
-                                search_type: None,
-                                hybrid_weights: None,
-                                cursor: None,
-                                include_facets: None,
-                                include_metadata: Some(true),
-                                ranking_boost: None,
-                                explain_score: None,
-                            })
-                            .await
-                            .map(|results| SearchResponse {
-                                results,
-                                total_count: None,
-                                execution_time_ms: 0,
-                                facets: None,
-                                next_cursor: None,
-                                suggestions: None,
-                            })
-                    }
-                    "last_week" => {
-                        debug!("Searching memories from last week");
-                        let since = Utc::now() - ChronoDuration::weeks(1);
-                        let embedding = self
-                            .embedder
-                            .generate_embedding("context:conversation")
-                            .await?;
-                        self.repository
-                            .search_memories_simple(SearchRequest {
-                                query_text: Some("context:conversation".to_string()),
-                                query_embedding: Some(embedding),
-                                limit: Some(500),
-                                offset: None,
-                                tier: None,
-                                tags: None,
-                                date_range: Some(DateRange {
-                                    start: Some(since),
-                                    end: Some(Utc::now()),
-                                }),
-                                importance_range: None,
-                                metadata_filters: None,
-                                similarity_threshold: Some(0.3),
-                                search_type: None,
-                                hybrid_weights: None,
-                                cursor: None,
-                                include_facets: None,
-                                include_metadata: Some(true),
-                                ranking_boost: None,
-                                explain_score: None,
-                            })
-                            .await
-                            .map(|results| SearchResponse {
-                                results,
-                                total_count: None,
-                                execution_time_ms: 0,
-                                facets: None,
-                                next_cursor: None,
-                                suggestions: None,
-                            })
-                    }
-                    "last_month" => {
-                        debug!("Searching memories from last month");
-                        let since = Utc::now() - ChronoDuration::days(30);
-                        let embedding = self
-                            .embedder
-                            .generate_embedding("context:conversation")
-                            .await?;
-                        self.repository
-                            .search_memories_simple(SearchRequest {
-                                query_text: Some("context:conversation".to_string()),
-                                query_embedding: Some(embedding),
-                                limit: Some(1000),
-                                offset: None,
-                                tier: None,
-                                tags: None,
-                                date_range: Some(DateRange {
-                                    start: Some(since),
-                                    end: Some(Utc::now()),
-                                }),
-                                importance_range: None,
-                                metadata_filters: None,
-                                similarity_threshold: Some(0.3),
-                                search_type: None,
-                                hybrid_weights: None,
-                                cursor: None,
-                                include_facets: None,
-                                include_metadata: Some(true),
-                                ranking_boost: None,
-                                explain_score: None,
-                            })
-                            .await
-                            .map(|results| SearchResponse {
-                                results,
-                                total_count: None,
-                                execution_time_ms: 0,
-                                facets: None,
-                                next_cursor: None,
-                                suggestions: None,
-                            })
-                    }
-                    _ => {
-                        warn!("Unknown time_period '{}', falling back to 'last_day'", time_period);
-                        debug!("Searching memories from last day (fallback for unknown time period)");
-                        let since = Utc::now() - ChronoDuration::days(1);
-                        let embedding = self
-                            .embedder
-                            .generate_embedding("context:conversation")
-                            .await?;
-                        self.repository
-                            .search_memories_simple(SearchRequest {
-                                query_text: Some("context:conversation".to_string()),
-                                query_embedding: Some(embedding),
-                                limit: Some(200),
-                                offset: None,
-                                tier: None,
-                                tags: None,
-                                date_range: Some(DateRange {
-                                    start: Some(since),
-                                    end: Some(Utc::now()),
-                                }),
-                                importance_range: None,
-                                metadata_filters: None,
-                                similarity_threshold: Some(0.3),
-                                search_type: None,
-                                hybrid_weights: None,
-                                cursor: None,
-                                include_facets: None,
-                                include_metadata: Some(true),
-                                ranking_boost: None,
-                                explain_score: None,
-                            })
-                            .await
-                            .map(|results| SearchResponse {
-                                results,
-                                total_count: None,
-                                execution_time_ms: 0,
-                                facets: None,
-                                next_cursor: None,
-                                suggestions: None,
-                            })
-                    }
-                }?;
+                // Same approach as what_did_you_remember: semantic search
+                // for conversation memories within the requested range
+                let range = self.resolve_time_expression(scope, time_period).await?;
+                debug!("Searching memories {}", range.describe());
+                let embedding = self
+                    .embedder
+                    .generate_embedding("context:conversation")
+                    .await?;
+                let mut memories = scope
+                    .repository("Insight generation")?
+                    .search_memories_simple(SearchRequest {
+                        query_text: Some("context:conversation".to_string()),
+                        query_embedding: Some(embedding),
+                        limit: Some(insight_search_limit(&range)),
+                        date_range: range.to_date_range(),
+                        similarity_threshold: Some(0.3),
+                        include_metadata: Some(true),
+                        ..Default::default()
+                    })
+                    .await
+                    .map(|results| SearchResponse {
+                        results,
+                        total_count: None,
+                        execution_time_ms: 0,
+                        facets: None,
+                        next_cursor: None,
+                        suggestions: None,
+                    })?;
+                for result in &mut memories.results {
+                    result.memory = self.decrypt_memory(std::mem::take(&mut result.memory))?;
+                }
 
                 debug!(
                     "Memory search completed for time_period '{}': found {} results, execution_time={}ms",
@@ -1367,7 +3116,7 @@ impl MCPHandlers {
                 );
 
                 if memories.results.is_empty() {
-                    let suggestions = if time_period == "all" {
+                    let suggestions = if range == TimeRange::all() {
                         "• Adding some memories first with store_memory\n\
                         • Using harvest_conversation to process conversations\n\
                         • Checking if memories exist with search_memory"
@@ -1378,11 +3127,12 @@ impl MCPHandlers {
                     };
                     let response_text = format!(
                         "★ Insights Generation\n\
-                        📭 No memories found in the specified time period: {}\n\
+                        📭 No memories found in the specified time period: {} ({})\n\
                         \n\
                         💡 Try:\n\
                         {}",
                         time_period,
+                        range.describe(),
                         suggestions
                     );
                     return Ok(format_tool_response(&response_text));
@@ -1407,11 +3157,12 @@ impl MCPHandlers {
                 if filtered_memories.is_empty() {
                     let response_text = format!(
                         "★ Insights Generation\n\
-                        🔍 No memories found matching topic '{}' in time period: {}\n\
+                        🔍 No memories found matching topic '{}' in time period: {} ({})\n\
                         \n\
                         💡 Try a different topic or broader search terms.",
                         topic.unwrap_or(""),
-                        time_period
+                        time_period,
+                        range.describe()
                     );
                     return Ok(format_tool_response(&response_text));
                 }
@@ -1428,12 +3179,35 @@ impl MCPHandlers {
                     memory_ids.len()
                 );
 
+                self.mcp_logger.log_for_session(
+                    scope.session_id.as_deref(),
+                    LogLevel::Info,
+                    Some("insights".to_string()),
+                    json!({
+                        "message": "Generating insights",
+                        "memories": memory_ids.len(),
+                        "time_period": time_period,
+                        "time_range": range.describe()
+                    }),
+                );
+
                 // Use the insights processor to generate insights
-                match processor.process_batch(memory_ids).await {
+                let on_chunk = |processed: usize, total: usize| {
+                    report_progress(
+                        progress,
+                        processed,
+                        total,
+                        format!("Processed {processed}/{total} memories"),
+                    )
+                };
+                match processor
+                    .process_batch_with_progress(memory_ids, on_chunk)
+                    .await
+                {
                     Ok(processing_result) => {
                         let response_text = format!(
                             "★ Insights Generated Successfully\n\
-                            📊 Processed {} memories from time period: {}\n\
+                            📊 Processed {} memories from time period: {} ({})\n\
                             🔍 Topic filter: {}\n\
                             💡 Generated {} insights\n\
                             ⚡ Success rate: {:.1}%\n\
@@ -1442,6 +3216,7 @@ impl MCPHandlers {
                             Insights summary:\n{}",
                             processing_result.report.memories_processed,
                             time_period,
+                            range.describe(),
                             topic.unwrap_or("none"),
                             processing_result.insights.len(),
                             processing_result.report.success_rate * 100.0,
@@ -1653,7 +3428,7 @@ impl MCPHandlers {
 
     #[cfg(feature = "codex-dreams")]
     /// Execute export_insights tool
-    async fn execute_export_insights(&self, args: &Value) -> Result<Value> {
+    async fn execute_export_insights(&self, scope: &NamespaceScope, args: &Value) -> Result<Value> {
         let format = args
             .get("format")
             .and_then(|f| f.as_str())
@@ -1678,7 +3453,7 @@ impl MCPHandlers {
         #[cfg(feature = "codex-dreams")]
         {
             // Get insights from storage if available
-            let (insights, summary) = if let Some(storage) = &self.insight_storage {
+            let (insights, summary) = if let Some(storage) = &scope.insight_storage {
                 // First, get all insights using a broad search (empty query gets all)
                 let search_results = storage
                     .search("", 1000) // Large limit to get all insights
@@ -2040,6 +3815,198 @@ fn format_duration(duration: ChronoDuration) -> String {
     }
 }
 
+/// Tags from a tool's `tags` argument
+fn parse_tags(args: &Value) -> Option<Vec<String>> {
+    args.get("tags").and_then(|t| t.as_array()).map(|arr| {
+        arr.iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect::<Vec<String>>()
+    })
+}
+
+/// UUID from a required string argument
+fn parse_uuid_arg(args: &Value, name: &str) -> Result<Uuid> {
+    let value = args
+        .get(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("Missing required '{}' parameter", name))?;
+    Ok(Uuid::parse_str(value)?)
+}
+
+/// Relation types from a tool's `relation_types` argument, ignoring unknown names
+fn parse_relation_types(args: &Value) -> Option<Vec<RelationshipType>> {
+    args.get("relation_types")
+        .and_then(|t| t.as_array())
+        .map(|types| {
+            types
+                .iter()
+                .filter_map(|t| t.as_str().and_then(|t| t.parse().ok()))
+                .collect()
+        })
+}
+
+/// `<source> <relation> <target>` with shortened ids
+fn describe_relation(relation: &MemoryRelation) -> String {
+    let short = |id: Uuid| id.to_string().chars().take(8).collect::<String>();
+    format!(
+        "{} {} {}",
+        short(relation.source_id),
+        relation.relation_type.as_str(),
+        short(relation.target_id)
+    )
+}
+
+/// Metadata for a new memory; tags take the place of explicit metadata
+fn new_memory_metadata(args: &Value, subject: &str) -> Option<Value> {
+    let mut metadata = match parse_tags(args) {
+        Some(tags) => Some(json!({ "tags": tags })),
+        None => args.get("metadata").cloned(),
+    };
+    // Corrections supersede the stored facts they contradict
+    if args.get("correction").and_then(|c| c.as_bool()) == Some(true) {
+        if let Some(object) = metadata.get_or_insert_with(|| json!({})).as_object_mut() {
+            object.insert("correction".to_string(), json!(true));
+        }
+    }
+    // Data subject requests find the memories of a client by its id, which
+    // only the server sets
+    if let Some(object) = metadata.get_or_insert_with(|| json!({})).as_object_mut() {
+        object.insert(SUBJECT_ID_KEY.to_string(), json!(subject));
+    }
+    metadata
+}
+
+/// A stored memory of the scope, read without counting as an access
+async fn peek_memory(scope: &NamespaceScope, id: Uuid) -> Result<Memory> {
+    scope
+        .storage
+        .get_memories_by_ids(&[id])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| crate::memory::error::MemoryError::NotFound { id: id.to_string() }.into())
+}
+
+/// Append the conflicts found for `memory_id` to a tool response
+fn push_conflicts(response_text: &mut String, conflicts: &[MemoryConflict], memory_id: Uuid) {
+    if conflicts.is_empty() {
+        return;
+    }
+    let lines = conflicts
+        .iter()
+        .map(|c| describe_conflict(c, memory_id))
+        .collect::<Vec<String>>()
+        .join("\n");
+    response_text.push_str(&format!("\n\n⚠️ Conflicts:\n{lines}"));
+}
+
+/// Replacement metadata for a memory, carrying over the data subject it was
+/// stored for; clients may leave `subject_id` out but not change it
+fn keep_subject_id(mut metadata: Value, stored: &Value) -> Result<Value> {
+    let stored_subject = stored.get(SUBJECT_ID_KEY).cloned();
+    if let Some(subject) = metadata.get(SUBJECT_ID_KEY) {
+        if Some(subject) != stored_subject.as_ref() {
+            return Err(anyhow::anyhow!(
+                "The '{}' metadata key is set by the server and cannot be changed",
+                SUBJECT_ID_KEY
+            ));
+        }
+    }
+    if let (Some(object), Some(subject)) = (metadata.as_object_mut(), stored_subject) {
+        object.insert(SUBJECT_ID_KEY.to_string(), subject);
+    }
+    Ok(metadata)
+}
+
+/// Memories to consider for insights: wider ranges need more
+#[cfg(feature = "codex-dreams")]
+fn insight_search_limit(range: &TimeRange) -> i32 {
+    match range.span() {
+        Some(span) if span <= ChronoDuration::hours(1) => 100,
+        Some(span) if span <= ChronoDuration::days(1) => 200,
+        Some(span) if span <= ChronoDuration::weeks(1) => 500,
+        Some(span) if span <= ChronoDuration::days(31) => 1000,
+        _ => 5000,
+    }
+}
+
+/// `<start> – <end>` of a memory's valid time
+fn describe_valid_time(memory: &Memory) -> String {
+    let since = memory.valid_since().format("%Y-%m-%d %H:%M UTC");
+    match memory.valid_to {
+        Some(valid_to) => format!("{since} – {}", valid_to.format("%Y-%m-%d %H:%M UTC")),
+        None => format!("{since} – current"),
+    }
+}
+
+/// One-line summary of a conflict from the point of view of `memory_id`
+fn describe_conflict(conflict: &MemoryConflict, memory_id: Uuid) -> String {
+    let other = conflict.other_memory(memory_id);
+    let state = match conflict.superseded_memory_id {
+        Some(superseded) if superseded == memory_id => format!("superseded by {other}"),
+        Some(_) => format!("supersedes {other}"),
+        None => format!("{} with {other}", conflict.status.as_str()),
+    };
+    format!("• {state} ({}, conflict {})", conflict.reason, conflict.id)
+}
+
+/// One-line summary of how a search result was ranked
+fn describe_score(explanation: &ScoreExplanation) -> String {
+    match &explanation.rank_fusion {
+        Some(fusion) => {
+            let rank = |rank: Option<usize>, score: Option<f32>| match (rank, score) {
+                (Some(rank), Some(score)) => format!("#{rank} ({score:.3})"),
+                (Some(rank), None) => format!("#{rank}"),
+                _ => "no match".to_string(),
+            };
+            format!(
+                "semantic {}, keyword {}, rrf {:.4} (k={})",
+                rank(fusion.semantic_rank, fusion.semantic_score),
+                rank(fusion.fulltext_rank, fusion.fulltext_score),
+                explanation.total_score,
+                fusion.k
+            )
+        }
+        None => format!(
+            "score {:.3} ({})",
+            explanation.total_score,
+            explanation.factors.join(", ")
+        ),
+    }
+}
+
+/// Key of an in-flight request; JSON-RPC ids are only unique per session
+fn in_flight_key(id: &Value, headers: &HashMap<String, String>) -> String {
+    let session = headers
+        .get(SESSION_HEADER)
+        .map(String::as_str)
+        .unwrap_or("stdio");
+    format!("{session}:{id}")
+}
+
+/// Report `current` of `total` steps on an optional progress handle
+async fn report_progress(
+    progress: Option<&ProgressHandle>,
+    current: usize,
+    total: usize,
+    message: String,
+) {
+    if let Some(progress) = progress {
+        let fraction = current as f64 / total.max(1) as f64;
+        if let Err(e) = progress
+            .update(
+                fraction,
+                Some(current as u64),
+                Some(total as u64),
+                Some(message),
+            )
+            .await
+        {
+            debug!("Progress update dropped: {}", e);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2052,6 +4019,134 @@ mod tests {
         assert_eq!(format_duration(ChronoDuration::days(3)), "3d");
     }
 
+    #[test]
+    fn test_in_flight_key_is_scoped_by_session() {
+        let mut headers = HashMap::new();
+        assert_eq!(in_flight_key(&json!(7), &headers), "stdio:7");
+
+        headers.insert(SESSION_HEADER.to_string(), "abc".to_string());
+        assert_eq!(in_flight_key(&json!(7), &headers), "abc:7");
+        assert_ne!(
+            in_flight_key(&json!("7"), &headers),
+            in_flight_key(&json!(7), &headers)
+        );
+    }
+
+    #[test]
+    fn test_describe_score_for_rank_fusion() {
+        let explanation = ScoreExplanation {
+            semantic_contribution: 0.0,
+            temporal_contribution: 0.0,
+            importance_contribution: 0.0,
+            access_frequency_contribution: 0.0,
+            total_score: 1.0 / 61.0,
+            factors: vec!["full-text rank 1".to_string()],
+            rank_fusion: Some(RankFusionExplanation {
+                k: 60.0,
+                semantic_rank: None,
+                semantic_score: None,
+                fulltext_rank: Some(1),
+                fulltext_score: Some(0.5),
+            }),
+        };
+
+        assert_eq!(
+            describe_score(&explanation),
+            "semantic no match, keyword #1 (0.500), rrf 0.0164 (k=60)"
+        );
+    }
+
+    #[test]
+    fn test_clients_cannot_set_the_subject_id() {
+        let args = json!({"content": "x", "metadata": {"subject_id": "bob", "source": "chat"}});
+        let metadata = new_memory_metadata(&args, "alice").unwrap();
+        assert_eq!(metadata["subject_id"], "alice");
+        assert_eq!(metadata["source"], "chat");
+        let anonymous = data_subject(None, "team");
+        let metadata = new_memory_metadata(&json!({"content": "x"}), &anonymous).unwrap();
+        assert_eq!(metadata["subject_id"], "anonymous:team");
+
+        let stored = json!({"subject_id": "alice", "source": "chat"});
+        let replaced = keep_subject_id(json!({"source": "email"}), &stored).unwrap();
+        assert_eq!(replaced, json!({"subject_id": "alice", "source": "email"}));
+        assert!(keep_subject_id(json!({"subject_id": "bob"}), &stored).is_err());
+        assert!(keep_subject_id(json!({"subject_id": "bob"}), &json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_content_updates_reindex_without_counting_an_access() {
+        let storage: Arc<dyn MemoryStorage> = Arc::new(
+            crate::memory::SqliteMemoryStorage::connect("sqlite::memory:")
+                .await
+                .unwrap(),
+        );
+        let handlers = MCPHandlers::for_storage(
+            storage.clone(),
+            Arc::new(SimpleEmbedder::new_hashing("hashing-64".to_string())),
+            None,
+            None,
+            None,
+            Arc::new(MCPLogger::new(LogLevel::Info)),
+            Arc::new(ProgressTracker::new()),
+        );
+        let scope = NamespaceScope {
+            namespace: storage.namespace().to_string(),
+            actor: None,
+            session_id: None,
+            storage: storage.clone(),
+            repository: None,
+            #[cfg(feature = "codex-dreams")]
+            insight_storage: None,
+        };
+        let entity_names = |entities: Vec<crate::memory::Entity>| {
+            let mut names: Vec<String> = entities.into_iter().map(|e| e.canonical_name).collect();
+            names.sort();
+            names
+        };
+
+        let memory = storage
+            .create_memory(CreateMemoryRequest {
+                content: "I asked Tom about Redis.".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        handlers.index_entities(&scope, &memory).await;
+
+        let update = json!({
+            "memory_id": memory.id.to_string(),
+            "content": "I asked Tom about Postgres.",
+            "tags": ["infra"],
+        });
+        handlers
+            .execute_update_memory(&scope, &update)
+            .await
+            .unwrap();
+        let entities = storage.get_memory_entities(memory.id).await.unwrap();
+        assert_eq!(entity_names(entities), vec!["PostgreSQL", "Tom"]);
+        let updated = peek_memory(&scope, memory.id).await.unwrap();
+        assert_eq!(updated.metadata["tags"], json!(["infra"]));
+
+        let bad_tier = json!({ "memory_id": memory.id.to_string(), "tier": 2 });
+        assert!(handlers
+            .execute_update_memory(&scope, &bad_tier)
+            .await
+            .is_err());
+
+        let revert = json!({ "memory_id": memory.id.to_string(), "revision": 1 });
+        handlers
+            .execute_revert_memory(&scope, &revert)
+            .await
+            .unwrap();
+        let entities = storage.get_memory_entities(memory.id).await.unwrap();
+        assert_eq!(entity_names(entities), vec!["Redis", "Tom"]);
+
+        assert_eq!(
+            peek_memory(&scope, memory.id).await.unwrap().access_count,
+            0
+        );
+    }
+
     #[tokio::test]
     async fn test_initialize_handler() {
         // This would need proper test setup with mock dependencies
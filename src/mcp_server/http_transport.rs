@@ -0,0 +1,1032 @@
+//! Streamable HTTP transport for MCP
+//!
+//! Implements the MCP 2025-06-18 "Streamable HTTP" transport on a single
+//! endpoint (default `/mcp`) so several agents can share one server instance:
+//!
+//! - `POST` carries JSON-RPC requests, notifications and batches. Responses are
+//!   returned as JSON, or as an SSE stream when the client only accepts
+//!   `text/event-stream`.
+//! - `GET` opens an SSE stream for server-initiated messages. Every event has
+//!   a per-session id, and `Last-Event-ID` replays buffered events so clients
+//!   can resume after a dropped connection.
+//! - `DELETE` ends a session.
+//!
+//! Sessions are created by `initialize` and identified by the `Mcp-Session-Id`
+//! header. The caller's real HTTP headers are handed to
+//! `MCPHandlers::handle_request_with_headers`, so `MCPAuth` and
+//! `MCPRateLimiter` authenticate and throttle each agent individually.
+
+use crate::mcp_server::handlers::MCPHandlers;
+use crate::mcp_server::logging::MCPLogger;
+use crate::mcp_server::progress::{progress_token_from_params, ProgressTracker};
+use crate::mcp_server::transport::{
+    create_error_response, create_error_response_with_data, is_cancelled_response,
+    validate_jsonrpc_message,
+};
+use anyhow::Result;
+use axum::{
+    body::Bytes,
+    extract::{DefaultBodyLimit, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::get,
+    Json, Router,
+};
+use futures::stream::{self, Stream, StreamExt};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+pub const SESSION_HEADER: &str = "mcp-session-id";
+pub const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// Protocol versions accepted in the `MCP-Protocol-Version` header
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 2] = ["2025-06-18", "2025-03-26"];
+
+/// Streamable HTTP transport configuration
+#[derive(Debug, Clone)]
+pub struct HttpTransportConfig {
+    /// Address the HTTP listener binds to
+    pub bind_address: SocketAddr,
+    /// Path of the MCP endpoint
+    pub endpoint_path: String,
+    /// Timeout for a single JSON-RPC request
+    pub request_timeout_ms: u64,
+    /// Maximum POST body size in bytes
+    pub max_request_size: usize,
+    /// Sessions without activity for this long are dropped
+    pub session_idle_timeout_secs: u64,
+    /// Number of events kept per session for `Last-Event-ID` replay
+    pub event_buffer_size: usize,
+    /// Maximum number of concurrent sessions
+    pub max_sessions: usize,
+    /// Browser origins allowed to call the endpoint (localhost is always allowed)
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for HttpTransportConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: SocketAddr::from(([127, 0, 0, 1], 8081)),
+            endpoint_path: "/mcp".to_string(),
+            request_timeout_ms: 600000,
+            max_request_size: 10 * 1024 * 1024, // 10MB
+            session_idle_timeout_secs: 3600,
+            event_buffer_size: 256,
+            max_sessions: 1000,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+impl HttpTransportConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(addr) = std::env::var("MCP_HTTP_BIND") {
+            match addr.parse() {
+                Ok(addr) => config.bind_address = addr,
+                Err(e) => warn!("Ignoring invalid MCP_HTTP_BIND '{}': {}", addr, e),
+            }
+        }
+
+        if let Ok(path) = std::env::var("MCP_HTTP_PATH") {
+            if path.starts_with('/') {
+                config.endpoint_path = path;
+            }
+        }
+
+        if let Ok(timeout) = std::env::var("MCP_HTTP_SESSION_TIMEOUT_SECS") {
+            if let Ok(timeout) = timeout.parse() {
+                config.session_idle_timeout_secs = timeout;
+            }
+        }
+
+        if let Ok(size) = std::env::var("MCP_HTTP_EVENT_BUFFER") {
+            if let Ok(size) = size.parse() {
+                config.event_buffer_size = size;
+            }
+        }
+
+        if let Ok(origins) = std::env::var("MCP_HTTP_ALLOWED_ORIGINS") {
+            config.allowed_origins = origins
+                .split(',')
+                .map(|o| o.trim().to_string())
+                .filter(|o| !o.is_empty())
+                .collect();
+        }
+
+        config
+    }
+}
+
+/// A message delivered on a session's SSE stream
+#[derive(Debug, Clone)]
+pub struct SessionEvent {
+    pub id: u64,
+    pub message: Value,
+}
+
+struct McpSession {
+    last_seen: Instant,
+    next_event_id: u64,
+    events: VecDeque<SessionEvent>,
+    sender: broadcast::Sender<SessionEvent>,
+    progress_tokens: HashSet<Value>,
+}
+
+impl McpSession {
+    fn new(buffer_size: usize) -> Self {
+        let (sender, _) = broadcast::channel(buffer_size.max(1));
+        Self {
+            last_seen: Instant::now(),
+            next_event_id: 1,
+            events: VecDeque::new(),
+            sender,
+            progress_tokens: HashSet::new(),
+        }
+    }
+}
+
+/// Active HTTP sessions and their resumable event logs
+pub struct SessionStore {
+    sessions: RwLock<HashMap<String, McpSession>>,
+    idle_timeout: Duration,
+    event_buffer_size: usize,
+    max_sessions: usize,
+}
+
+impl SessionStore {
+    pub fn new(idle_timeout: Duration, event_buffer_size: usize, max_sessions: usize) -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+            idle_timeout,
+            event_buffer_size,
+            max_sessions,
+        }
+    }
+
+    /// Create a new session and return its id
+    pub async fn create(&self) -> Result<String> {
+        let mut sessions = self.sessions.write().await;
+        let idle_timeout = self.idle_timeout;
+        sessions.retain(|_, session| session.last_seen.elapsed() < idle_timeout);
+
+        if sessions.len() >= self.max_sessions {
+            return Err(anyhow::anyhow!(
+                "Maximum number of MCP sessions ({}) reached",
+                self.max_sessions
+            ));
+        }
+
+        let session_id = Uuid::new_v4().simple().to_string();
+        sessions.insert(session_id.clone(), McpSession::new(self.event_buffer_size));
+        debug!("Created MCP HTTP session {}", session_id);
+        Ok(session_id)
+    }
+
+    /// Mark a session as active; returns false if it does not exist or expired
+    pub async fn touch(&self, session_id: &str) -> bool {
+        let mut sessions = self.sessions.write().await;
+        match sessions.get_mut(session_id) {
+            Some(session) if session.last_seen.elapsed() < self.idle_timeout => {
+                session.last_seen = Instant::now();
+                true
+            }
+            Some(_) => {
+                sessions.remove(session_id);
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub async fn remove(&self, session_id: &str) -> bool {
+        self.sessions.write().await.remove(session_id).is_some()
+    }
+
+    pub async fn len(&self) -> usize {
+        self.sessions.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.sessions.read().await.is_empty()
+    }
+
+    /// Assign the next event id to a message, buffer it for replay and push it
+    /// to any open streams of the session
+    pub async fn record_event(&self, session_id: &str, message: Value) -> Option<SessionEvent> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions.get_mut(session_id)?;
+
+        let event = SessionEvent {
+            id: session.next_event_id,
+            message,
+        };
+        session.next_event_id += 1;
+
+        session.events.push_back(event.clone());
+        while session.events.len() > self.event_buffer_size {
+            session.events.pop_front();
+        }
+
+        // No receivers simply means no stream is open right now; the event
+        // stays in the buffer for replay
+        let _ = session.sender.send(event.clone());
+        Some(event)
+    }
+
+    /// Send a message to every open session
+    pub async fn broadcast(&self, message: Value) -> usize {
+        let session_ids: Vec<String> = self.sessions.read().await.keys().cloned().collect();
+        let mut delivered = 0;
+        for session_id in session_ids {
+            if self
+                .record_event(&session_id, message.clone())
+                .await
+                .is_some()
+            {
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+
+    /// Route `notifications/progress` for `token` to this session
+    pub async fn register_progress_token(&self, session_id: &str, token: Value) {
+        if let Some(session) = self.sessions.write().await.get_mut(session_id) {
+            session.progress_tokens.insert(token);
+        }
+    }
+
+    /// Deliver a progress notification to `session_id` if one of its
+    /// requests supplied `token`
+    pub async fn send_progress(
+        &self,
+        session_id: &str,
+        token: &Value,
+        notification: Value,
+        finished: bool,
+    ) -> bool {
+        {
+            let mut sessions = self.sessions.write().await;
+            let Some(session) = sessions.get_mut(session_id) else {
+                return false;
+            };
+            let registered = if finished {
+                session.progress_tokens.remove(token)
+            } else {
+                session.progress_tokens.contains(token)
+            };
+            if !registered {
+                return false;
+            }
+        }
+
+        self.record_event(session_id, notification).await.is_some()
+    }
+
+    /// Drop sessions that were idle too long and return their ids
+    pub async fn purge_expired(&self) -> Vec<String> {
+        let mut sessions = self.sessions.write().await;
+        let expired: Vec<String> = sessions
+            .iter()
+            .filter(|(_, session)| session.last_seen.elapsed() >= self.idle_timeout)
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+        for session_id in &expired {
+            sessions.remove(session_id);
+        }
+        expired
+    }
+
+    /// Subscribe to a session, returning buffered events after `last_event_id`
+    /// followed by a receiver for new ones
+    pub async fn subscribe(
+        &self,
+        session_id: &str,
+        last_event_id: Option<u64>,
+    ) -> Option<(Vec<SessionEvent>, broadcast::Receiver<SessionEvent>)> {
+        let sessions = self.sessions.read().await;
+        let session = sessions.get(session_id)?;
+
+        let replay = match last_event_id {
+            Some(last_id) => session
+                .events
+                .iter()
+                .filter(|event| event.id > last_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Some((replay, session.sender.subscribe()))
+    }
+}
+
+/// Shared state for the HTTP endpoint
+#[derive(Clone)]
+struct HttpState {
+    handlers: Arc<MCPHandlers>,
+    sessions: Arc<SessionStore>,
+    config: Arc<HttpTransportConfig>,
+}
+
+/// Streamable HTTP transport serving one `MCPHandlers` to many clients
+pub struct HttpTransport {
+    config: HttpTransportConfig,
+    sessions: Arc<SessionStore>,
+}
+
+impl HttpTransport {
+    pub fn new(config: HttpTransportConfig) -> Self {
+        let sessions = Arc::new(SessionStore::new(
+            Duration::from_secs(config.session_idle_timeout_secs),
+            config.event_buffer_size,
+            config.max_sessions,
+        ));
+        Self { config, sessions }
+    }
+
+    /// Session store, e.g. for pushing server-initiated notifications
+    pub fn sessions(&self) -> Arc<SessionStore> {
+        self.sessions.clone()
+    }
+
+    /// Build the axum router for the MCP endpoint
+    pub fn router(&self, handlers: Arc<MCPHandlers>) -> Router {
+        let state = HttpState {
+            handlers,
+            sessions: self.sessions.clone(),
+            config: Arc::new(self.config.clone()),
+        };
+
+        Router::new()
+            .route(
+                &self.config.endpoint_path,
+                get(handle_get).post(handle_post).delete(handle_delete),
+            )
+            .layer(DefaultBodyLimit::max(self.config.max_request_size))
+            .with_state(state)
+    }
+
+    /// Push log and progress notifications to the session whose request
+    /// produced them
+    pub fn spawn_notification_forwarders(&self, handlers: &MCPHandlers) {
+        let sessions = self.sessions.clone();
+        let mut logs = handlers.subscribe_logs();
+        tokio::spawn(async move {
+            loop {
+                match logs.recv().await {
+                    Ok(message) => {
+                        // Messages outside any request belong to no session
+                        if let Some(session_id) = &message.session_id {
+                            sessions
+                                .record_event(
+                                    session_id,
+                                    MCPLogger::create_log_notification(&message),
+                                )
+                                .await;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Dropped {} log notifications", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        let sessions = self.sessions.clone();
+        let mut progress = handlers.subscribe_progress();
+        tokio::spawn(async move {
+            loop {
+                match progress.recv().await {
+                    Ok(report) => {
+                        let Some(session_id) = &report.session_id else {
+                            continue;
+                        };
+                        let notification = ProgressTracker::create_progress_notification(&report);
+                        sessions
+                            .send_progress(
+                                session_id,
+                                &report.progress_token,
+                                notification,
+                                report.progress >= 1.0,
+                            )
+                            .await;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Dropped {} progress notifications", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Bind the listener and serve until the process is stopped
+    pub async fn serve(&self, handlers: Arc<MCPHandlers>) -> Result<()> {
+        self.spawn_notification_forwarders(&handlers);
+
+        let listener = tokio::net::TcpListener::bind(self.config.bind_address).await?;
+        info!(
+            "MCP Streamable HTTP transport listening on http://{}{}",
+            listener.local_addr()?,
+            self.config.endpoint_path
+        );
+
+        axum::serve(listener, self.router(handlers)).await?;
+        Ok(())
+    }
+}
+
+/// Rejection returned before any JSON-RPC processing happens
+fn http_error(status: StatusCode, code: i32, message: &str) -> Response {
+    (status, Json(create_error_response(None, code, message))).into_response()
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn accepts(headers: &HeaderMap, mime: &str) -> bool {
+    header_str(headers, header::ACCEPT.as_str())
+        .map(|accept| accept.contains(mime) || accept.contains("*/*"))
+        .unwrap_or(false)
+}
+
+/// Reject cross-site browser requests (DNS rebinding protection)
+fn origin_allowed(headers: &HeaderMap, config: &HttpTransportConfig) -> bool {
+    let Some(origin) = header_str(headers, header::ORIGIN.as_str()) else {
+        return true;
+    };
+
+    if config
+        .allowed_origins
+        .iter()
+        .any(|allowed| allowed == origin)
+    {
+        return true;
+    }
+
+    let authority = origin
+        .split("://")
+        .nth(1)
+        .unwrap_or(origin)
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    let host = match authority.find(']') {
+        Some(end) if authority.starts_with('[') => &authority[..=end],
+        _ => authority.split(':').next().unwrap_or_default(),
+    };
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// Common checks for every method on the endpoint
+fn check_request_headers(headers: &HeaderMap, config: &HttpTransportConfig) -> Option<Response> {
+    if !origin_allowed(headers, config) {
+        warn!("Rejected MCP HTTP request from disallowed origin");
+        return Some(http_error(
+            StatusCode::FORBIDDEN,
+            -32600,
+            "Origin not allowed",
+        ));
+    }
+
+    if let Some(version) = header_str(headers, PROTOCOL_VERSION_HEADER) {
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&version) {
+            return Some(http_error(
+                StatusCode::BAD_REQUEST,
+                -32600,
+                &format!("Unsupported MCP protocol version: {version}"),
+            ));
+        }
+    }
+
+    None
+}
+
+/// Validate the session header against the store
+async fn require_session(
+    headers: &HeaderMap,
+    sessions: &SessionStore,
+    handlers: &MCPHandlers,
+) -> Result<String, Response> {
+    let Some(session_id) = header_str(headers, SESSION_HEADER) else {
+        return Err(http_error(
+            StatusCode::BAD_REQUEST,
+            -32600,
+            "Missing Mcp-Session-Id header",
+        ));
+    };
+
+    if !sessions.touch(session_id).await {
+        // An expired session is removed by `touch`; forget its state too
+        handlers.end_session(session_id);
+        return Err(http_error(
+            StatusCode::NOT_FOUND,
+            -32001,
+            "Session not found",
+        ));
+    }
+
+    Ok(session_id.to_string())
+}
+
+/// Convert HTTP headers to the map consumed by `MCPAuth` and the rate limiter
+fn headers_for_handlers(
+    headers: &HeaderMap,
+    session_id: &str,
+    message: &Value,
+) -> HashMap<String, String> {
+    let mut map: HashMap<String, String> = headers
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|v| (name.as_str().to_string(), v.to_string()))
+        })
+        .collect();
+
+    map.insert("Content-Type".to_string(), "application/json".to_string());
+    map.insert("Transport".to_string(), "http".to_string());
+    map.insert(SESSION_HEADER.to_string(), session_id.to_string());
+
+    if let Some(version) = message.get("jsonrpc").and_then(|v| v.as_str()) {
+        map.insert("JSON-RPC-Version".to_string(), version.to_string());
+    }
+
+    map
+}
+
+/// Process one JSON-RPC message; notifications and client responses yield `None`
+async fn process_message(
+    state: &HttpState,
+    session_id: &str,
+    message: &Value,
+    headers: &HeaderMap,
+) -> Option<Value> {
+    // Responses to server-initiated requests carry no method
+    if message.get("method").is_none()
+        && (message.get("result").is_some() || message.get("error").is_some())
+    {
+        debug!("Ignoring client response message on HTTP transport");
+        return None;
+    }
+
+    if let Err(validation_error) = validate_jsonrpc_message(message) {
+        error!("JSON-RPC validation failed: {}", validation_error);
+        return Some(create_error_response(
+            message.get("id"),
+            -32600,
+            "Invalid Request",
+        ));
+    }
+
+    let method = message.get("method").and_then(|m| m.as_str())?;
+    let id = message.get("id");
+    let handler_headers = headers_for_handlers(headers, session_id, message);
+
+    if id.is_none() {
+        debug!("Received JSON-RPC notification over HTTP: {}", method);
+        state
+            .handlers
+            .handle_notification(method, message.get("params"), &handler_headers)
+            .await;
+        return None;
+    }
+
+    if let Some(token) = progress_token_from_params(message.get("params")) {
+        state
+            .sessions
+            .register_progress_token(session_id, token)
+            .await;
+    }
+
+    let response = tokio::time::timeout(
+        Duration::from_millis(state.config.request_timeout_ms),
+        state.handlers.handle_request_with_headers(
+            method,
+            message.get("params"),
+            id,
+            &handler_headers,
+        ),
+    )
+    .await;
+
+    match response {
+        // The client cancelled this request and no longer expects a response
+        Ok(resp) if is_cancelled_response(&resp) => None,
+        Ok(resp) => Some(resp),
+        Err(_) => {
+            error!("Request processing timeout for method: {}", method);
+            Some(create_error_response_with_data(
+                id,
+                -32603,
+                "Internal error",
+                Some(json!({
+                    "type": "timeout",
+                    "details": "Request processing timeout exceeded"
+                })),
+            ))
+        }
+    }
+}
+
+fn sse_event(event: &SessionEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .event("message")
+        .data(event.message.to_string())
+}
+
+/// Stream buffered events followed by live ones until the session ends
+fn session_stream(
+    replay: Vec<SessionEvent>,
+    receiver: broadcast::Receiver<SessionEvent>,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    let replayed = stream::iter(replay.into_iter().map(|event| Ok(sse_event(&event))));
+    let live = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => return Some((Ok(sse_event(&event)), receiver)),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("SSE stream lagged, {} events dropped", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    replayed.chain(live)
+}
+
+fn with_session_header(mut response: Response, session_id: &str) -> Response {
+    if let Ok(value) = HeaderValue::from_str(session_id) {
+        response.headers_mut().insert(SESSION_HEADER, value);
+    }
+    response
+}
+
+/// POST: JSON-RPC requests, notifications and batches
+async fn handle_post(State(state): State<HttpState>, headers: HeaderMap, body: Bytes) -> Response {
+    if let Some(rejection) = check_request_headers(&headers, &state.config) {
+        return rejection;
+    }
+
+    let payload: Value = match serde_json::from_slice(&body) {
+        Ok(value) => value,
+        Err(e) => {
+            error!("Failed to parse JSON over HTTP: {}", e);
+            return http_error(StatusCode::BAD_REQUEST, -32700, "Parse error");
+        }
+    };
+
+    let (messages, is_batch) = match payload {
+        Value::Array(messages) if messages.is_empty() => {
+            return http_error(StatusCode::BAD_REQUEST, -32600, "Invalid Request");
+        }
+        Value::Array(messages) => (messages, true),
+        message => (vec![message], false),
+    };
+
+    let is_initialize = messages
+        .iter()
+        .any(|m| m.get("method").and_then(|v| v.as_str()) == Some("initialize"));
+
+    // `initialize` starts a new session; everything else must belong to one
+    let (session_id, new_session) = if is_initialize {
+        if is_batch {
+            return http_error(
+                StatusCode::BAD_REQUEST,
+                -32600,
+                "initialize must not be sent in a batch",
+            );
+        }
+        for expired in state.sessions.purge_expired().await {
+            state.handlers.end_session(&expired);
+        }
+        match state.sessions.create().await {
+            Ok(session_id) => (session_id, true),
+            Err(e) => {
+                warn!("Rejecting MCP HTTP session: {}", e);
+                return http_error(StatusCode::SERVICE_UNAVAILABLE, -32000, &e.to_string());
+            }
+        }
+    } else {
+        match require_session(&headers, &state.sessions, &state.handlers).await {
+            Ok(session_id) => (session_id, false),
+            Err(rejection) => return rejection,
+        }
+    };
+
+    let mut responses = Vec::new();
+    for message in &messages {
+        if let Some(response) = process_message(&state, &session_id, message, &headers).await {
+            responses.push(response);
+        }
+    }
+
+    // A failed initialize must not leave a dangling session behind
+    if new_session && responses.iter().any(|r| r.get("error").is_some()) {
+        state.sessions.remove(&session_id).await;
+        let response = responses.pop().unwrap_or(Value::Null);
+        return (StatusCode::OK, Json(response)).into_response();
+    }
+
+    if responses.is_empty() {
+        return with_session_header(StatusCode::ACCEPTED.into_response(), &session_id);
+    }
+
+    // Clients that only accept SSE get a resumable event stream
+    if accepts(&headers, "text/event-stream") && !accepts(&headers, "application/json") {
+        let mut events = Vec::with_capacity(responses.len());
+        for response in responses {
+            if let Some(event) = state.sessions.record_event(&session_id, response).await {
+                events.push(event);
+            }
+        }
+
+        let stream = stream::iter(
+            events
+                .into_iter()
+                .map(|event| Ok::<_, Infallible>(sse_event(&event))),
+        );
+        return with_session_header(Sse::new(stream).into_response(), &session_id);
+    }
+
+    let body = if is_batch {
+        Value::Array(responses)
+    } else {
+        responses.pop().unwrap_or(Value::Null)
+    };
+
+    with_session_header((StatusCode::OK, Json(body)).into_response(), &session_id)
+}
+
+/// GET: server-to-client SSE stream, resumable via `Last-Event-ID`
+async fn handle_get(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Some(rejection) = check_request_headers(&headers, &state.config) {
+        return rejection;
+    }
+
+    if !accepts(&headers, "text/event-stream") {
+        return http_error(
+            StatusCode::METHOD_NOT_ALLOWED,
+            -32600,
+            "GET requires Accept: text/event-stream",
+        );
+    }
+
+    let session_id = match require_session(&headers, &state.sessions, &state.handlers).await {
+        Ok(session_id) => session_id,
+        Err(rejection) => return rejection,
+    };
+
+    let last_event_id = header_str(&headers, LAST_EVENT_ID_HEADER).and_then(|v| v.parse().ok());
+
+    let Some((replay, receiver)) = state.sessions.subscribe(&session_id, last_event_id).await
+    else {
+        return http_error(StatusCode::NOT_FOUND, -32001, "Session not found");
+    };
+
+    debug!(
+        "Opening SSE stream for session {} (replaying {} events)",
+        session_id,
+        replay.len()
+    );
+
+    let sse = Sse::new(session_stream(replay, receiver)).keep_alive(KeepAlive::default());
+    with_session_header(sse.into_response(), &session_id)
+}
+
+/// DELETE: explicit session termination
+async fn handle_delete(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Some(rejection) = check_request_headers(&headers, &state.config) {
+        return rejection;
+    }
+
+    let Some(session_id) = header_str(&headers, SESSION_HEADER) else {
+        return http_error(
+            StatusCode::BAD_REQUEST,
+            -32600,
+            "Missing Mcp-Session-Id header",
+        );
+    };
+
+    if state.sessions.remove(session_id).await {
+        state.handlers.end_session(session_id);
+        info!("Terminated MCP HTTP session {}", session_id);
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        http_error(StatusCode::NOT_FOUND, -32001, "Session not found")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> SessionStore {
+        SessionStore::new(Duration::from_secs(60), 3, 2)
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let sessions = store();
+        let id = sessions.create().await.expect("session should be created");
+
+        assert!(sessions.touch(&id).await);
+        assert!(!sessions.touch("unknown").await);
+        assert!(sessions.remove(&id).await);
+        assert!(!sessions.touch(&id).await);
+        assert!(sessions.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_session_limit() {
+        let sessions = store();
+        sessions.create().await.expect("first session");
+        sessions.create().await.expect("second session");
+        assert!(sessions.create().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_event_ids_and_replay() {
+        let sessions = store();
+        let id = sessions.create().await.expect("session should be created");
+
+        for n in 0..5 {
+            let event = sessions
+                .record_event(&id, json!({ "n": n }))
+                .await
+                .expect("session exists");
+            assert_eq!(event.id, n + 1);
+        }
+
+        // Only the last `event_buffer_size` events are kept
+        let (replay, _) = sessions
+            .subscribe(&id, Some(0))
+            .await
+            .expect("session exists");
+        let ids: Vec<u64> = replay.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![3, 4, 5]);
+
+        let (replay, _) = sessions
+            .subscribe(&id, Some(4))
+            .await
+            .expect("session exists");
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].message["n"], 4);
+
+        let (replay, _) = sessions.subscribe(&id, None).await.expect("session exists");
+        assert!(replay.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_live_events_reach_subscribers() {
+        let sessions = store();
+        let id = sessions.create().await.expect("session should be created");
+        let (_, mut receiver) = sessions.subscribe(&id, None).await.expect("session exists");
+
+        assert_eq!(sessions.broadcast(json!({"method": "ping"})).await, 1);
+        let event = receiver.recv().await.expect("event should be delivered");
+        assert_eq!(event.id, 1);
+        assert_eq!(event.message["method"], "ping");
+    }
+
+    #[tokio::test]
+    async fn test_progress_routed_to_requesting_session() {
+        let sessions = store();
+        let first = sessions.create().await.expect("first session");
+        let second = sessions.create().await.expect("second session");
+        sessions
+            .register_progress_token(&first, json!("tok-1"))
+            .await;
+
+        assert!(
+            sessions
+                .send_progress(
+                    &first,
+                    &json!("tok-1"),
+                    json!({"method": "notifications/progress"}),
+                    false
+                )
+                .await
+        );
+        // The other session never supplied the token
+        assert!(
+            !sessions
+                .send_progress(&second, &json!("tok-1"), json!({}), false)
+                .await
+        );
+        let (first_events, _) = sessions.subscribe(&first, Some(0)).await.expect("exists");
+        let (second_events, _) = sessions.subscribe(&second, Some(0)).await.expect("exists");
+        assert_eq!(first_events.len(), 1);
+        assert!(second_events.is_empty());
+
+        // The token is released once the operation finishes
+        assert!(
+            sessions
+                .send_progress(&first, &json!("tok-1"), json!({}), true)
+                .await
+        );
+        assert!(
+            !sessions
+                .send_progress(&first, &json!("tok-1"), json!({}), false)
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sessions_reusing_a_progress_token() {
+        let sessions = store();
+        let first = sessions.create().await.expect("first session");
+        let second = sessions.create().await.expect("second session");
+        for session_id in [&first, &second] {
+            sessions.register_progress_token(session_id, json!(1)).await;
+        }
+
+        // Finishing the first session's operation leaves the second one's token
+        assert!(
+            sessions
+                .send_progress(&first, &json!(1), json!({}), true)
+                .await
+        );
+        assert!(
+            sessions
+                .send_progress(&second, &json!(1), json!({}), false)
+                .await
+        );
+        let (first_events, _) = sessions.subscribe(&first, Some(0)).await.expect("exists");
+        let (second_events, _) = sessions.subscribe(&second, Some(0)).await.expect("exists");
+        assert_eq!(first_events.len(), 1);
+        assert_eq!(second_events.len(), 1);
+    }
+
+    #[test]
+    fn test_headers_for_handlers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("ApiKey abc"));
+        headers.insert("x-client-id", HeaderValue::from_static("agent-1"));
+
+        let map = headers_for_handlers(
+            &headers,
+            "session-1",
+            &json!({"jsonrpc": "2.0", "method": "x"}),
+        );
+        assert_eq!(
+            map.get("authorization").map(String::as_str),
+            Some("ApiKey abc")
+        );
+        assert_eq!(map.get("x-client-id").map(String::as_str), Some("agent-1"));
+        assert_eq!(map.get("JSON-RPC-Version").map(String::as_str), Some("2.0"));
+        assert_eq!(map.get("Transport").map(String::as_str), Some("http"));
+        assert_eq!(
+            map.get(SESSION_HEADER).map(String::as_str),
+            Some("session-1")
+        );
+    }
+
+    #[test]
+    fn test_origin_validation() {
+        let config = HttpTransportConfig {
+            allowed_origins: vec!["https://agents.example.com".to_string()],
+            ..HttpTransportConfig::default()
+        };
+
+        let with_origin = |origin: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ORIGIN, HeaderValue::from_static(origin));
+            headers
+        };
+
+        assert!(origin_allowed(&HeaderMap::new(), &config));
+        assert!(origin_allowed(
+            &with_origin("http://localhost:3000"),
+            &config
+        ));
+        assert!(origin_allowed(&with_origin("http://[::1]:8081"), &config));
+        assert!(origin_allowed(
+            &with_origin("https://agents.example.com"),
+            &config
+        ));
+        assert!(!origin_allowed(
+            &with_origin("https://evil.example.com"),
+            &config
+        ));
+    }
+}
@@ -5,7 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::RwLock;
 use tokio::sync::broadcast;
 use tracing::{debug, error, info, trace, warn};
 
@@ -42,28 +43,50 @@ pub struct LogMessage {
     pub level: LogLevel,
     pub logger: Option<String>,
     pub data: Value,
+    /// HTTP session whose request produced the message; `None` for stdio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
 }
 
 /// MCP Logging service
 pub struct MCPLogger {
     sender: broadcast::Sender<LogMessage>,
-    min_level: LogLevel,
+    min_level: RwLock<LogLevel>,
+    /// Levels chosen with `logging/setLevel` by individual HTTP sessions
+    session_levels: RwLock<HashMap<String, LogLevel>>,
 }
 
 impl MCPLogger {
     /// Create a new MCP logger with the specified minimum log level
     pub fn new(min_level: LogLevel) -> Self {
         let (sender, _) = broadcast::channel(1000);
-        Self { sender, min_level }
+        Self {
+            sender,
+            min_level: RwLock::new(min_level),
+            session_levels: RwLock::new(HashMap::new()),
+        }
     }
 
     /// Log a message at the specified level
     pub fn log(&self, level: LogLevel, logger: Option<String>, data: Value) {
-        if level >= self.min_level {
+        self.log_for_session(None, level, logger, data);
+    }
+
+    /// Log a message produced while serving `session_id`; it is delivered to
+    /// that session only and filtered by the level the session chose
+    pub fn log_for_session(
+        &self,
+        session_id: Option<&str>,
+        level: LogLevel,
+        logger: Option<String>,
+        data: Value,
+    ) {
+        if level >= self.session_level(session_id) {
             let message = LogMessage {
                 level,
                 logger,
                 data,
+                session_id: session_id.map(str::to_string),
             };
 
             // Also log through tracing for local debugging
@@ -116,14 +139,45 @@ impl MCPLogger {
         self.sender.subscribe()
     }
 
-    /// Set minimum log level
-    pub fn set_min_level(&mut self, level: LogLevel) {
-        self.min_level = level;
+    /// Set minimum log level (`logging/setLevel`)
+    pub fn set_min_level(&self, level: LogLevel) {
+        match self.min_level.write() {
+            Ok(mut min_level) => *min_level = level,
+            Err(poisoned) => *poisoned.into_inner() = level,
+        }
     }
 
     /// Get current minimum log level
-    pub fn min_level(&self) -> &LogLevel {
-        &self.min_level
+    pub fn min_level(&self) -> LogLevel {
+        match self.min_level.read() {
+            Ok(min_level) => min_level.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Set the minimum log level of one HTTP session (`logging/setLevel`)
+    pub fn set_session_level(&self, session_id: &str, level: LogLevel) {
+        match self.session_levels.write() {
+            Ok(mut levels) => levels.insert(session_id.to_string(), level),
+            Err(poisoned) => poisoned.into_inner().insert(session_id.to_string(), level),
+        };
+    }
+
+    /// Minimum log level of a session, falling back to the server-wide level
+    pub fn session_level(&self, session_id: Option<&str>) -> LogLevel {
+        let level = session_id.and_then(|session_id| match self.session_levels.read() {
+            Ok(levels) => levels.get(session_id).cloned(),
+            Err(poisoned) => poisoned.into_inner().get(session_id).cloned(),
+        });
+        level.unwrap_or_else(|| self.min_level())
+    }
+
+    /// Forget the level of a session that ended
+    pub fn end_session(&self, session_id: &str) {
+        match self.session_levels.write() {
+            Ok(mut levels) => levels.remove(session_id),
+            Err(poisoned) => poisoned.into_inner().remove(session_id),
+        };
     }
 
     /// Create MCP notification message for log
@@ -161,7 +215,22 @@ mod tests {
     #[tokio::test]
     async fn test_logger_creation() {
         let logger = MCPLogger::new(LogLevel::Debug);
-        assert_eq!(logger.min_level(), &LogLevel::Debug);
+        assert_eq!(logger.min_level(), LogLevel::Debug);
+    }
+
+    #[test]
+    fn test_set_min_level() {
+        let logger = MCPLogger::new(LogLevel::Info);
+        let mut receiver = logger.subscribe();
+
+        logger.set_min_level(LogLevel::Error);
+        assert_eq!(logger.min_level(), LogLevel::Error);
+
+        logger.warning(None, json!({"message": "filtered"}));
+        logger.error(None, json!({"message": "delivered"}));
+
+        let message = receiver.try_recv().expect("error should be delivered");
+        assert_eq!(message.data["message"], "delivered");
     }
 
     #[tokio::test]
@@ -183,6 +252,7 @@ mod tests {
             level: LogLevel::Error,
             logger: Some("memory".to_string()),
             data: json!({"error": "Failed to store memory", "id": "123"}),
+            session_id: None,
         };
 
         let notification = MCPLogger::create_log_notification(&message);
@@ -217,4 +287,34 @@ mod tests {
             other => panic!("Expected empty channel, got: {:?}", other),
         }
     }
+
+    #[test]
+    fn test_session_levels_are_independent() {
+        let logger = MCPLogger::new(LogLevel::Info);
+        let mut receiver = logger.subscribe();
+
+        logger.set_session_level("quiet", LogLevel::Error);
+        logger.log_for_session(
+            Some("quiet"),
+            LogLevel::Info,
+            None,
+            json!({"message": "filtered"}),
+        );
+        logger.log_for_session(
+            Some("chatty"),
+            LogLevel::Info,
+            None,
+            json!({"message": "delivered"}),
+        );
+        assert_eq!(logger.min_level(), LogLevel::Info);
+
+        let message = receiver
+            .try_recv()
+            .expect("chatty session keeps the default level");
+        assert_eq!(message.session_id.as_deref(), Some("chatty"));
+        assert!(receiver.try_recv().is_err());
+
+        logger.end_session("quiet");
+        assert_eq!(logger.session_level(Some("quiet")), LogLevel::Info);
+    }
 }
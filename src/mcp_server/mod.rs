@@ -9,9 +9,12 @@
 pub mod auth;
 pub mod circuit_breaker;
 pub mod handlers;
+pub mod http_transport;
 pub mod logging;
 pub mod progress;
+pub mod prompts;
 pub mod rate_limiter;
+pub mod resources;
 pub mod tools;
 pub mod transport;
 
@@ -26,17 +29,20 @@ pub use circuit_breaker::{
     CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState,
 };
 pub use handlers::MCPHandlers;
+pub use http_transport::{HttpTransport, HttpTransportConfig};
 pub use logging::{LogLevel, LogMessage, MCPLogger};
 pub use progress::{ProgressHandle, ProgressReport, ProgressTracker};
+pub use prompts::{PromptRegistry, PromptTemplate};
 pub use rate_limiter::{MCPRateLimitConfig, MCPRateLimiter, RateLimitStats};
+pub use resources::CodexResource;
 pub use tools::MCPTools;
 pub use transport::StdioTransport;
 
 use crate::memory::{
-    ImportanceAssessmentConfig, ImportanceAssessmentPipeline, MemoryRepository,
-    SilentHarvesterService,
+    FieldEncryption, ImportanceAssessmentConfig, ImportanceAssessmentPipeline, MemoryRepository,
+    MemoryStorage, PiiWritePolicy, SilentHarvesterService,
 };
-use crate::security::{audit::AuditLogger, AuditConfig};
+use crate::security::{audit::AuditLogger, AuditConfig, RbacConfig, RbacManager};
 use crate::SimpleEmbedder;
 
 #[cfg(feature = "codex-dreams")]
@@ -57,6 +63,8 @@ pub struct MCPServerConfig {
     pub enable_rate_limiting: bool,
     pub rate_limiting: MCPRateLimitConfig,
     pub audit: AuditConfig,
+    /// Permissions of authenticated clients; only consulted for revealing PII
+    pub rbac: RbacConfig,
 }
 
 impl Default for MCPServerConfig {
@@ -77,6 +85,20 @@ impl Default for MCPServerConfig {
                 .unwrap_or(true),
             rate_limiting: MCPRateLimitConfig::from_env(),
             audit: AuditConfig::default(),
+            rbac: RbacConfig {
+                enabled: std::env::var("MCP_RBAC_ENABLED")
+                    .map(|s| s.parse().unwrap_or(false))
+                    .unwrap_or(false),
+                admin_users: std::env::var("MCP_RBAC_ADMINS")
+                    .map(|s| {
+                        s.split(',')
+                            .map(|user| user.trim().to_string())
+                            .filter(|user| !user.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                ..RbacConfig::default()
+            },
         }
     }
 }
@@ -84,15 +106,18 @@ impl Default for MCPServerConfig {
 /// Main MCP Server implementation
 pub struct MCPServer {
     config: MCPServerConfig,
-    repository: Arc<MemoryRepository>,
+    storage: Arc<dyn MemoryStorage>,
     embedder: Arc<SimpleEmbedder>,
     handlers: MCPHandlers,
     transport: StdioTransport,
     circuit_breaker: Option<Arc<CircuitBreaker>>,
-    harvester_service: Arc<SilentHarvesterService>,
+    /// `None` unless memories live in PostgreSQL
+    harvester_service: Option<Arc<SilentHarvesterService>>,
     auth: Option<Arc<MCPAuth>>,
     rate_limiter: Option<Arc<MCPRateLimiter>>,
     audit_logger: Arc<AuditLogger>,
+    /// Applied to stored and harvested content alike
+    pii_policy: Arc<PiiWritePolicy>,
     mcp_logger: Arc<MCPLogger>,
     progress_tracker: Arc<ProgressTracker>,
     #[cfg(feature = "codex-dreams")]
@@ -107,7 +132,14 @@ impl MCPServer {
         embedder: Arc<SimpleEmbedder>,
         config: MCPServerConfig,
     ) -> Result<Self> {
-        Self::new_impl(repository, embedder, config, None, None)
+        Self::new_impl(
+            repository.clone(),
+            Some(repository),
+            embedder,
+            config,
+            None,
+            None,
+        )
     }
 
     /// Create a new MCP server instance with insights processor
@@ -117,7 +149,14 @@ impl MCPServer {
         embedder: Arc<SimpleEmbedder>,
         config: MCPServerConfig,
     ) -> Result<Self> {
-        Self::new_impl(repository, embedder, config, None, None)
+        Self::new_impl(
+            repository.clone(),
+            Some(repository),
+            embedder,
+            config,
+            None,
+            None,
+        )
     }
 
     /// Create a new MCP server instance with insights processor
@@ -130,7 +169,8 @@ impl MCPServer {
         insight_storage: Option<Arc<crate::insights::storage::InsightStorage>>,
     ) -> Result<Self> {
         Self::new_impl(
-            repository,
+            repository.clone(),
+            Some(repository),
             embedder,
             config,
             insights_processor,
@@ -138,9 +178,21 @@ impl MCPServer {
         )
     }
 
+    /// Create an MCP server over a storage backend other than PostgreSQL,
+    /// such as the embedded SQLite store. Conversation harvesting and
+    /// insights need PostgreSQL and are unavailable.
+    pub fn for_storage(
+        storage: Arc<dyn MemoryStorage>,
+        embedder: Arc<SimpleEmbedder>,
+        config: MCPServerConfig,
+    ) -> Result<Self> {
+        Self::new_impl(storage, None, embedder, config, None, None)
+    }
+
     /// Internal implementation for creating MCP server
     fn new_impl(
-        repository: Arc<MemoryRepository>,
+        storage: Arc<dyn MemoryStorage>,
+        repository: Option<Arc<MemoryRepository>>,
         embedder: Arc<SimpleEmbedder>,
         config: MCPServerConfig,
         #[cfg(feature = "codex-dreams")] insights_processor: Option<Arc<InsightsProcessor>>,
@@ -153,6 +205,11 @@ impl MCPServer {
         // Initialize audit logger
         let audit_logger = Arc::new(AuditLogger::new(config.audit.clone())?);
 
+        // One policy for every writer, so all tokenize under the same vault
+        // key; detections are audited with the server's events
+        let pii_policy =
+            Arc::new(PiiWritePolicy::from_env()?.with_audit_logger(audit_logger.clone()));
+
         // Initialize MCP logger and progress tracker
         let mcp_logger = Arc::new(MCPLogger::new(LogLevel::Info));
         let progress_tracker = Arc::new(ProgressTracker::new());
@@ -177,20 +234,29 @@ impl MCPServer {
             None
         };
         // Initialize Silent Harvester Service
-        let importance_config = ImportanceAssessmentConfig::default();
-        let importance_pipeline = Arc::new(ImportanceAssessmentPipeline::new(
-            importance_config,
-            embedder.clone(),
-            prometheus::default_registry(),
-        )?);
-
-        let harvester_service = Arc::new(SilentHarvesterService::new(
-            repository.clone(),
-            importance_pipeline,
-            embedder.clone(),
-            None, // Use default config
-            prometheus::default_registry(),
-        )?);
+        let harvester_service = match &repository {
+            Some(repository) => {
+                let importance_config = ImportanceAssessmentConfig::default();
+                let importance_pipeline = Arc::new(ImportanceAssessmentPipeline::new(
+                    importance_config,
+                    embedder.clone(),
+                    prometheus::default_registry(),
+                )?);
+
+                let harvester_service = SilentHarvesterService::new(
+                    repository.clone(),
+                    importance_pipeline,
+                    embedder.clone(),
+                    None, // Use default config
+                    prometheus::default_registry(),
+                )?;
+                harvester_service
+                    .engine()
+                    .set_pii_policy(pii_policy.clone());
+                Some(Arc::new(harvester_service))
+            }
+            None => None,
+        };
 
         // Initialize circuit breaker if enabled
         let circuit_breaker = if config.enable_circuit_breaker {
@@ -202,38 +268,56 @@ impl MCPServer {
         };
 
         // Create handlers
-        #[cfg(feature = "codex-dreams")]
-        let handlers = MCPHandlers::new_with_insights(
-            repository.clone(),
-            embedder.clone(),
-            harvester_service.clone(),
-            circuit_breaker.clone(),
-            auth.clone(),
-            rate_limiter.clone(),
-            mcp_logger.clone(),
-            progress_tracker.clone(),
-            insights_processor.clone(),
-            insight_storage.clone(),
-        );
-
-        #[cfg(not(feature = "codex-dreams"))]
-        let handlers = MCPHandlers::new(
-            repository.clone(),
-            embedder.clone(),
-            harvester_service.clone(),
-            circuit_breaker.clone(),
-            auth.clone(),
-            rate_limiter.clone(),
-            mcp_logger.clone(),
-            progress_tracker.clone(),
-        );
+        let handlers = match (&repository, &harvester_service) {
+            #[cfg(feature = "codex-dreams")]
+            (Some(repository), Some(harvester_service)) => MCPHandlers::new_with_insights(
+                repository.clone(),
+                embedder.clone(),
+                harvester_service.clone(),
+                circuit_breaker.clone(),
+                auth.clone(),
+                rate_limiter.clone(),
+                mcp_logger.clone(),
+                progress_tracker.clone(),
+                insights_processor.clone(),
+                insight_storage.clone(),
+            ),
+            #[cfg(not(feature = "codex-dreams"))]
+            (Some(repository), Some(harvester_service)) => MCPHandlers::new(
+                repository.clone(),
+                embedder.clone(),
+                harvester_service.clone(),
+                circuit_breaker.clone(),
+                auth.clone(),
+                rate_limiter.clone(),
+                mcp_logger.clone(),
+                progress_tracker.clone(),
+            ),
+            _ => MCPHandlers::for_storage(
+                storage.clone(),
+                embedder.clone(),
+                circuit_breaker.clone(),
+                auth.clone(),
+                rate_limiter.clone(),
+                mcp_logger.clone(),
+                progress_tracker.clone(),
+            ),
+        };
+
+        let handlers = handlers.with_pii_policy(pii_policy.clone());
+        // Disabled RBAC would allow everything, so PII stays sealed without it
+        let handlers = if config.rbac.enabled {
+            handlers.with_rbac(Arc::new(RbacManager::new(config.rbac.clone())))
+        } else {
+            handlers
+        };
 
         // Create transport
         let transport = StdioTransport::new(config.request_timeout_ms)?;
 
         Ok(Self {
             config,
-            repository,
+            storage,
             embedder,
             handlers,
             transport,
@@ -242,6 +326,7 @@ impl MCPServer {
             auth,
             rate_limiter,
             audit_logger,
+            pii_policy,
             mcp_logger,
             progress_tracker,
             #[cfg(feature = "codex-dreams")]
@@ -249,6 +334,23 @@ impl MCPServer {
         })
     }
 
+    /// Encrypt memory content and sensitive metadata at rest, both for
+    /// memory tools and for harvested memories
+    pub fn with_field_encryption(mut self, encryption: Arc<FieldEncryption>) -> Self {
+        if let Some(harvester_service) = &self.harvester_service {
+            harvester_service
+                .engine()
+                .set_field_encryption(encryption.clone());
+        }
+        self.handlers = self.handlers.with_field_encryption(encryption);
+        self
+    }
+
+    /// The PII policy of the server, for other writers to share
+    pub fn pii_policy(&self) -> &Arc<PiiWritePolicy> {
+        &self.pii_policy
+    }
+
     /// Start the MCP server
     pub async fn start(&mut self) -> Result<()> {
         info!("Starting MCP server with stdio transport");
@@ -256,13 +358,31 @@ impl MCPServer {
         info!("Capabilities: tools");
 
         // Start the transport layer
-        self.transport.start(&mut self.handlers).await
+        self.transport.start(&self.handlers).await
+    }
+
+    /// Start the MCP server with the Streamable HTTP transport so several
+    /// agents can share this instance
+    pub async fn start_http(self, http_config: HttpTransportConfig) -> Result<()> {
+        info!("Starting MCP server with Streamable HTTP transport");
+        info!("Protocol version: 2025-06-18");
+
+        let http_config = HttpTransportConfig {
+            request_timeout_ms: self.config.request_timeout_ms,
+            max_request_size: self.config.max_request_size,
+            ..http_config
+        };
+        let transport = HttpTransport::new(http_config);
+        transport.serve(Arc::new(self.handlers)).await
     }
 
     /// Get server statistics
     pub async fn get_stats(&self) -> Result<serde_json::Value> {
-        let repo_stats = self.repository.get_statistics().await?;
-        let harvester_metrics = self.harvester_service.get_metrics().await;
+        let repo_stats = self.storage.get_statistics().await?;
+        let harvester_metrics = match &self.harvester_service {
+            Some(harvester_service) => Some(harvester_service.get_metrics().await),
+            None => None,
+        };
 
         let circuit_breaker_stats = if let Some(ref cb) = self.circuit_breaker {
             Some(cb.get_stats().await)
@@ -12,16 +12,28 @@ use uuid::Uuid;
 /// Progress report for a long-running operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressReport {
-    pub progress_token: String,
+    /// The token exactly as the client sent it, a string or an integer
+    pub progress_token: Value,
     pub progress: f64, // 0.0 to 1.0
     pub total: Option<u64>,
     pub current: Option<u64>,
     pub message: Option<String>,
+    /// HTTP session whose request owns the token; `None` for stdio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Operations are keyed by session and token, since clients pick tokens
+/// independently and two sessions may use the same one
+type OperationKey = (Option<String>, Value);
+
+fn operation_key(session_id: Option<&str>, token: &Value) -> OperationKey {
+    (session_id.map(str::to_string), token.clone())
 }
 
 /// Progress tracker for managing multiple ongoing operations
 pub struct ProgressTracker {
-    operations: Arc<RwLock<std::collections::HashMap<String, ProgressReport>>>,
+    operations: Arc<RwLock<std::collections::HashMap<OperationKey, ProgressReport>>>,
     sender: broadcast::Sender<ProgressReport>,
 }
 
@@ -39,17 +51,18 @@ impl ProgressTracker {
     pub async fn start_operation(&self, message: Option<String>) -> String {
         let token = Uuid::new_v4().to_string();
         let report = ProgressReport {
-            progress_token: token.clone(),
+            progress_token: Value::String(token.clone()),
             progress: 0.0,
             total: None,
             current: None,
             message,
+            session_id: None,
         };
 
         self.operations
             .write()
             .await
-            .insert(token.clone(), report.clone());
+            .insert(operation_key(None, &report.progress_token), report.clone());
 
         // Send initial progress notification
         let _ = self.sender.send(report);
@@ -57,6 +70,35 @@ impl ProgressTracker {
         token
     }
 
+    /// Start tracking an operation under a client-supplied `progressToken`
+    pub async fn start_operation_with_token(&self, token: Value, message: Option<String>) {
+        self.start_session_operation(None, token, message).await;
+    }
+
+    /// Start tracking an operation under the `progressToken` of a request
+    /// from `session_id`
+    pub async fn start_session_operation(
+        &self,
+        session_id: Option<String>,
+        token: Value,
+        message: Option<String>,
+    ) {
+        let report = ProgressReport {
+            progress_token: token.clone(),
+            progress: 0.0,
+            total: None,
+            current: None,
+            message,
+            session_id: session_id.clone(),
+        };
+
+        self.operations
+            .write()
+            .await
+            .insert((session_id, token), report.clone());
+        let _ = self.sender.send(report);
+    }
+
     /// Update progress for an operation
     pub async fn update_progress(
         &self,
@@ -65,10 +107,30 @@ impl ProgressTracker {
         current: Option<u64>,
         total: Option<u64>,
         message: Option<String>,
+    ) -> Result<(), String> {
+        self.update_session_progress(
+            None,
+            &Value::String(token.to_string()),
+            progress,
+            current,
+            total,
+            message,
+        )
+        .await
+    }
+
+    async fn update_session_progress(
+        &self,
+        session_id: Option<&str>,
+        token: &Value,
+        progress: f64,
+        current: Option<u64>,
+        total: Option<u64>,
+        message: Option<String>,
     ) -> Result<(), String> {
         let mut operations = self.operations.write().await;
 
-        if let Some(report) = operations.get_mut(token) {
+        if let Some(report) = operations.get_mut(&operation_key(session_id, token)) {
             report.progress = progress.clamp(0.0, 1.0);
             report.current = current;
             report.total = total;
@@ -86,9 +148,18 @@ impl ProgressTracker {
 
     /// Complete an operation and remove it from tracking
     pub async fn complete_operation(&self, token: &str) -> Result<(), String> {
+        self.complete_session_operation(None, &Value::String(token.to_string()))
+            .await
+    }
+
+    async fn complete_session_operation(
+        &self,
+        session_id: Option<&str>,
+        token: &Value,
+    ) -> Result<(), String> {
         let mut operations = self.operations.write().await;
 
-        if let Some(mut report) = operations.remove(token) {
+        if let Some(mut report) = operations.remove(&operation_key(session_id, token)) {
             report.progress = 1.0;
             report.message = Some("Operation completed".to_string());
 
@@ -105,10 +176,20 @@ impl ProgressTracker {
         &self,
         token: &str,
         reason: Option<String>,
+    ) -> Result<(), String> {
+        self.cancel_session_operation(None, &Value::String(token.to_string()), reason)
+            .await
+    }
+
+    async fn cancel_session_operation(
+        &self,
+        session_id: Option<&str>,
+        token: &Value,
+        reason: Option<String>,
     ) -> Result<(), String> {
         let mut operations = self.operations.write().await;
 
-        if let Some(mut report) = operations.remove(token) {
+        if let Some(mut report) = operations.remove(&operation_key(session_id, token)) {
             report.message = reason.or_else(|| Some("Operation cancelled".to_string()));
 
             // Send cancellation notification
@@ -121,7 +202,11 @@ impl ProgressTracker {
 
     /// Get current progress for an operation
     pub async fn get_progress(&self, token: &str) -> Option<ProgressReport> {
-        self.operations.read().await.get(token).cloned()
+        self.operations
+            .read()
+            .await
+            .get(&operation_key(None, &Value::String(token.to_string())))
+            .cloned()
     }
 
     /// List all active operations
@@ -156,16 +241,41 @@ impl Default for ProgressTracker {
     }
 }
 
+/// Extract the client's `_meta.progressToken` from request params.
+///
+/// Tokens may be strings or integers and are kept as sent, so the string
+/// `"42"` and the integer `42` stay distinct and are echoed back unchanged.
+pub fn progress_token_from_params(params: Option<&Value>) -> Option<Value> {
+    match params?.get("_meta")?.get("progressToken")? {
+        token @ (Value::String(_) | Value::Number(_)) => Some(token.clone()),
+        _ => None,
+    }
+}
+
 /// Progress tracking handle for a specific operation
 pub struct ProgressHandle {
     tracker: Arc<ProgressTracker>,
-    token: String,
+    token: Value,
+    session_id: Option<String>,
 }
 
 impl ProgressHandle {
     /// Create a new progress handle
     pub fn new(tracker: Arc<ProgressTracker>, token: String) -> Self {
-        Self { tracker, token }
+        Self::for_session(tracker, None, Value::String(token))
+    }
+
+    /// Create a handle for an operation started with `start_session_operation`
+    pub fn for_session(
+        tracker: Arc<ProgressTracker>,
+        session_id: Option<String>,
+        token: Value,
+    ) -> Self {
+        Self {
+            tracker,
+            token,
+            session_id,
+        }
     }
 
     /// Update progress
@@ -177,22 +287,33 @@ impl ProgressHandle {
         message: Option<String>,
     ) -> Result<(), String> {
         self.tracker
-            .update_progress(&self.token, progress, current, total, message)
+            .update_session_progress(
+                self.session_id.as_deref(),
+                &self.token,
+                progress,
+                current,
+                total,
+                message,
+            )
             .await
     }
 
     /// Complete the operation
     pub async fn complete(&self) -> Result<(), String> {
-        self.tracker.complete_operation(&self.token).await
+        self.tracker
+            .complete_session_operation(self.session_id.as_deref(), &self.token)
+            .await
     }
 
     /// Cancel the operation
     pub async fn cancel(&self, reason: Option<String>) -> Result<(), String> {
-        self.tracker.cancel_operation(&self.token, reason).await
+        self.tracker
+            .cancel_session_operation(self.session_id.as_deref(), &self.token, reason)
+            .await
     }
 
     /// Get the progress token
-    pub fn token(&self) -> &str {
+    pub fn token(&self) -> &Value {
         &self.token
     }
 }
@@ -203,8 +324,11 @@ impl Drop for ProgressHandle {
         // This is fire-and-forget to avoid blocking the drop
         let tracker = self.tracker.clone();
         let token = self.token.clone();
+        let session_id = self.session_id.clone();
         tokio::spawn(async move {
-            let _ = tracker.complete_operation(&token).await;
+            let _ = tracker
+                .complete_session_operation(session_id.as_deref(), &token)
+                .await;
         });
     }
 }
@@ -304,11 +428,12 @@ mod tests {
     #[tokio::test]
     async fn test_progress_notification_format() {
         let report = ProgressReport {
-            progress_token: "test-123".to_string(),
+            progress_token: json!("test-123"),
             progress: 0.75,
             total: Some(100),
             current: Some(75),
             message: Some("Processing...".to_string()),
+            session_id: None,
         };
 
         let notification = ProgressTracker::create_progress_notification(&report);
@@ -320,6 +445,47 @@ mod tests {
         assert_eq!(notification["params"]["message"], "Processing...");
     }
 
+    #[tokio::test]
+    async fn test_client_supplied_progress_token() {
+        let params = json!({"name": "generate_insights", "_meta": {"progressToken": 42}});
+        let token = progress_token_from_params(Some(&params)).expect("token present");
+        assert_eq!(token, json!(42));
+        assert!(progress_token_from_params(Some(&json!({"name": "x"}))).is_none());
+        assert!(
+            progress_token_from_params(Some(&json!({"_meta": {"progressToken": null}}))).is_none()
+        );
+
+        let tracker = Arc::new(ProgressTracker::new());
+        let mut receiver = tracker.subscribe();
+        tracker.start_operation_with_token(token, None).await;
+        let notification =
+            ProgressTracker::create_progress_notification(&receiver.recv().await.unwrap());
+        assert_eq!(notification["params"]["progressToken"], json!(42));
+    }
+
+    #[tokio::test]
+    async fn test_string_token_echoed_as_sent() {
+        let params = json!({"_meta": {"progressToken": "42"}});
+        let token = progress_token_from_params(Some(&params)).expect("token present");
+        assert_eq!(token, json!("42"));
+
+        let tracker = Arc::new(ProgressTracker::new());
+        let mut receiver = tracker.subscribe();
+        tracker.start_operation_with_token(json!(42), None).await;
+        tracker
+            .start_operation_with_token(token.clone(), None)
+            .await;
+        assert_eq!(tracker.list_operations().await.len(), 2);
+
+        let handle = ProgressHandle::for_session(tracker.clone(), None, token);
+        handle.update(0.5, None, None, None).await.unwrap();
+        let echoed: Vec<_> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|report| ProgressTracker::create_progress_notification(&report))
+            .map(|notification| notification["params"]["progressToken"].clone())
+            .collect();
+        assert_eq!(echoed, vec![json!(42), json!("42"), json!("42")]);
+    }
+
     #[tokio::test]
     async fn test_handle_drop_completion() {
         let tracker = Arc::new(ProgressTracker::new());
@@ -358,4 +524,34 @@ mod tests {
         let progress = tracker.get_progress(&token).await.unwrap();
         assert_eq!(progress.progress, 1.0);
     }
+
+    #[tokio::test]
+    async fn test_same_token_in_two_sessions() {
+        let tracker = Arc::new(ProgressTracker::new());
+        let mut receiver = tracker.subscribe();
+        for session in ["a", "b"] {
+            tracker
+                .start_session_operation(Some(session.to_string()), json!("1"), None)
+                .await;
+        }
+
+        let handle =
+            ProgressHandle::for_session(tracker.clone(), Some("b".to_string()), json!("1"));
+        handle.update(0.5, None, None, None).await.unwrap();
+        handle.complete().await.unwrap();
+
+        let sessions: Vec<_> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|report| (report.session_id.unwrap(), report.progress))
+            .collect();
+        assert_eq!(
+            sessions,
+            vec![
+                ("a".to_string(), 0.0),
+                ("b".to_string(), 0.0),
+                ("b".to_string(), 0.5),
+                ("b".to_string(), 1.0)
+            ]
+        );
+        assert_eq!(tracker.list_operations().await.len(), 1);
+    }
 }
@@ -0,0 +1,422 @@
+//! MCP Prompts
+//!
+//! Built-in memory-aware prompts plus user-defined prompt templates. Built-ins
+//! are rendered by `MCPHandlers` because they need the memory store; user
+//! templates are TOML files loaded from the prompts config directory
+//! (`$CODEX_PROMPTS_DIR`, or `<config dir>/codex-memory/prompts`):
+//!
+//! ```toml
+//! name = "standup"
+//! description = "Draft a standup update"
+//! # Optional: memories matching this query are injected as {{memories}}
+//! search = "{{project}} progress"
+//! template = "Write my standup for {{project}}.\n\n{{memories}}"
+//!
+//! [[arguments]]
+//! name = "project"
+//! description = "Project name"
+//! required = true
+//! ```
+
+use crate::memory::models::Memory;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+pub const RECALL_CONTEXT_PROMPT: &str = "recall_context";
+pub const SUMMARIZE_ABOUT_ME_PROMPT: &str = "summarize_about_me";
+pub const REVIEW_DUE_MEMORIES_PROMPT: &str = "review_due_memories";
+
+/// Placeholder replaced with the memories found by a template's `search`
+pub const MEMORIES_PLACEHOLDER: &str = "memories";
+
+/// A prompt argument as advertised through `prompts/list`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// A user-defined prompt template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub arguments: Vec<PromptArgument>,
+    /// Optional search query (may reference arguments) whose results fill `{{memories}}`
+    #[serde(default)]
+    pub search: Option<String>,
+    pub template: String,
+}
+
+impl PromptTemplate {
+    /// Check required arguments are present
+    pub fn validate_arguments(&self, args: &HashMap<String, String>) -> Result<(), String> {
+        for argument in self.arguments.iter().filter(|a| a.required) {
+            if args.get(&argument.name).is_none_or(|v| v.trim().is_empty()) {
+                return Err(format!("Missing required argument '{}'", argument.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the search query, if the template defines one
+    pub fn render_search(&self, args: &HashMap<String, String>) -> Option<String> {
+        self.search
+            .as_deref()
+            .map(|search| substitute(search, args))
+            .filter(|query| !query.trim().is_empty())
+    }
+
+    /// Render the template body with arguments and the formatted memories
+    pub fn render(&self, args: &HashMap<String, String>, memories: Option<&str>) -> String {
+        let mut values = args.clone();
+        values.insert(
+            MEMORIES_PLACEHOLDER.to_string(),
+            memories.unwrap_or_default().to_string(),
+        );
+        substitute(&self.template, &values)
+    }
+
+    fn to_list_entry(&self) -> Value {
+        json!({
+            "name": self.name,
+            "description": self.description,
+            "arguments": self.arguments
+        })
+    }
+}
+
+/// Replace `{{name}}` placeholders; unknown placeholders render as empty strings
+fn substitute(template: &str, values: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let key = after[..end].trim();
+                if let Some(value) = values.get(key) {
+                    output.push_str(value);
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Registry of user-defined prompt templates
+#[derive(Debug, Clone, Default)]
+pub struct PromptRegistry {
+    templates: Vec<PromptTemplate>,
+}
+
+impl PromptRegistry {
+    /// Load templates from `$CODEX_PROMPTS_DIR` or the default config directory.
+    /// A missing directory simply yields no user templates.
+    pub fn load_default() -> Self {
+        let dir = Self::default_dir();
+        match Self::load_from_dir(&dir) {
+            Ok(registry) => registry,
+            Err(e) => {
+                warn!("Failed to load prompt templates from {:?}: {}", dir, e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn default_dir() -> PathBuf {
+        std::env::var("CODEX_PROMPTS_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                dirs::config_dir()
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join("codex-memory")
+                    .join("prompts")
+            })
+    }
+
+    /// Load every `*.toml` template in `dir`; invalid files are skipped with a warning
+    pub fn load_from_dir(dir: &Path) -> Result<Self> {
+        if !dir.exists() {
+            return Ok(Self::default());
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read prompts directory {dir:?}"))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut registry = Self::default();
+        for path in paths {
+            let parsed = fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| toml::from_str::<PromptTemplate>(&content).map_err(Into::into));
+
+            match parsed {
+                Ok(template) => {
+                    if let Err(e) = registry.register(template) {
+                        warn!("Skipping prompt template {:?}: {}", path, e);
+                    }
+                }
+                Err(e) => warn!("Skipping invalid prompt template {:?}: {}", path, e),
+            }
+        }
+
+        debug!("Loaded {} user prompt templates", registry.templates.len());
+        Ok(registry)
+    }
+
+    /// Add a template; names must be unique and may not shadow built-in prompts
+    pub fn register(&mut self, template: PromptTemplate) -> Result<(), String> {
+        if is_builtin_prompt(&template.name) {
+            return Err(format!(
+                "'{}' is reserved for a built-in prompt",
+                template.name
+            ));
+        }
+        if self.get(&template.name).is_some() {
+            return Err(format!("Duplicate prompt template '{}'", template.name));
+        }
+        self.templates.push(template);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.iter().find(|t| t.name == name)
+    }
+
+    /// Built-in prompts followed by user templates, in `prompts/list` format
+    pub fn list(&self) -> Vec<Value> {
+        let mut prompts = builtin_prompts();
+        prompts.extend(self.templates.iter().map(PromptTemplate::to_list_entry));
+        prompts
+    }
+}
+
+pub fn is_builtin_prompt(name: &str) -> bool {
+    matches!(
+        name,
+        RECALL_CONTEXT_PROMPT | SUMMARIZE_ABOUT_ME_PROMPT | REVIEW_DUE_MEMORIES_PROMPT
+    )
+}
+
+/// Built-in prompts rendered server-side from stored memories
+pub fn builtin_prompts() -> Vec<Value> {
+    vec![
+        json!({
+            "name": RECALL_CONTEXT_PROMPT,
+            "description": "Recall stored context relevant to a topic",
+            "arguments": [
+                {
+                    "name": "topic",
+                    "description": "Topic to recall context for",
+                    "required": true
+                },
+                {
+                    "name": "limit",
+                    "description": "Maximum number of memories to include (default: 10)",
+                    "required": false
+                }
+            ]
+        }),
+        json!({
+            "name": SUMMARIZE_ABOUT_ME_PROMPT,
+            "description": "Summarize what is known about the user, grouped by preferences, facts, goals and other harvested patterns",
+            "arguments": []
+        }),
+        json!({
+            "name": REVIEW_DUE_MEMORIES_PROMPT,
+            "description": "Review memories that are due for spaced repetition",
+            "arguments": [
+                {
+                    "name": "limit",
+                    "description": "Maximum number of memories to review (default: 10)",
+                    "required": false
+                }
+            ]
+        }),
+    ]
+}
+
+/// Convert `prompts/get` arguments (a JSON object) into string values
+pub fn parse_arguments(params: Option<&Value>) -> HashMap<String, String> {
+    params
+        .and_then(|p| p.get("arguments"))
+        .and_then(|a| a.as_object())
+        .map(|args| {
+            args.iter()
+                .map(|(key, value)| {
+                    let value = value
+                        .as_str()
+                        .map(String::from)
+                        .unwrap_or_else(|| value.to_string());
+                    (key.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Bullet list of memories for inclusion in a prompt
+pub fn format_memories(memories: &[Memory]) -> String {
+    if memories.is_empty() {
+        return "(no matching memories)".to_string();
+    }
+
+    memories
+        .iter()
+        .map(|m| {
+            format!(
+                "- [{}] {} (tier: {:?}, importance: {:.2})",
+                m.created_at.format("%Y-%m-%d"),
+                m.content,
+                m.tier,
+                m.importance_score
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// `prompts/get` result with a single user message
+pub fn prompt_result(description: &str, text: &str) -> Value {
+    json!({
+        "description": description,
+        "messages": [
+            {
+                "role": "user",
+                "content": {
+                    "type": "text",
+                    "text": text
+                }
+            }
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn template() -> PromptTemplate {
+        toml::from_str(
+            r#"
+            name = "standup"
+            description = "Draft a standup update"
+            search = "{{project}} progress"
+            template = "Standup for {{ project }}:\n{{memories}}{{unknown}}"
+
+            [[arguments]]
+            name = "project"
+            required = true
+            "#,
+        )
+        .expect("template should parse")
+    }
+
+    #[test]
+    fn test_template_rendering() {
+        let template = template();
+        let values = args(&[("project", "codex")]);
+
+        assert_eq!(
+            template.render_search(&values).as_deref(),
+            Some("codex progress")
+        );
+        assert_eq!(
+            template.render(&values, Some("- a memory")),
+            "Standup for codex:\n- a memory"
+        );
+    }
+
+    #[test]
+    fn test_required_arguments() {
+        let template = template();
+        assert!(template.validate_arguments(&args(&[])).is_err());
+        assert!(template
+            .validate_arguments(&args(&[("project", " ")]))
+            .is_err());
+        assert!(template
+            .validate_arguments(&args(&[("project", "codex")]))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_registry_rejects_builtin_and_duplicate_names() {
+        let mut registry = PromptRegistry::default();
+        assert!(registry.register(template()).is_ok());
+        assert!(registry.register(template()).is_err());
+
+        let mut builtin = template();
+        builtin.name = RECALL_CONTEXT_PROMPT.to_string();
+        assert!(registry.register(builtin).is_err());
+
+        let names: Vec<String> = registry
+            .list()
+            .iter()
+            .filter_map(|p| p["name"].as_str().map(String::from))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                RECALL_CONTEXT_PROMPT,
+                SUMMARIZE_ABOUT_ME_PROMPT,
+                REVIEW_DUE_MEMORIES_PROMPT,
+                "standup"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_from_dir_skips_invalid_files() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        fs::write(
+            dir.path().join("standup.toml"),
+            "name = \"standup\"\ntemplate = \"hi {{name}}\"\n",
+        )
+        .expect("write template");
+        fs::write(dir.path().join("broken.toml"), "name = ").expect("write template");
+        fs::write(dir.path().join("notes.txt"), "ignored").expect("write file");
+
+        let registry = PromptRegistry::load_from_dir(dir.path()).expect("load");
+        assert!(registry.get("standup").is_some());
+        assert_eq!(registry.list().len(), builtin_prompts().len() + 1);
+    }
+
+    #[test]
+    fn test_parse_arguments_stringifies_values() {
+        let params =
+            json!({ "name": "recall_context", "arguments": { "topic": "rust", "limit": 5 } });
+        let parsed = parse_arguments(Some(&params));
+        assert_eq!(parsed.get("topic").map(String::as_str), Some("rust"));
+        assert_eq!(parsed.get("limit").map(String::as_str), Some("5"));
+    }
+}
@@ -396,14 +396,37 @@ fn test_tool_list_schema_compliance() {
     assert!(tool_names.contains(&"what_did_you_remember"));
 }
 
-/// Test empty resources and prompts compliance
+/// Test resources, resource templates and prompts compliance
 #[test]
-fn test_empty_resources_and_prompts() {
+fn test_resources_and_prompts() {
     let resources = MCPTools::get_resources_list();
-    assert!(resources["resources"].as_array().unwrap().is_empty());
+    let resources = resources["resources"]
+        .as_array()
+        .expect("resources should be an array");
+    assert!(resources
+        .iter()
+        .any(|r| r["uri"] == "codex://recent" && r["mimeType"] == "application/json"));
+    assert!(resources.iter().any(|r| r["uri"] == "codex://tier/working"));
+
+    let templates = MCPTools::get_resource_templates_list();
+    let templates = templates["resourceTemplates"]
+        .as_array()
+        .expect("resourceTemplates should be an array");
+    assert!(templates
+        .iter()
+        .any(|t| t["uriTemplate"] == "codex://memory/{id}"));
+    assert!(templates
+        .iter()
+        .any(|t| t["uriTemplate"] == "codex://tier/{tier}"));
 
     let prompts = MCPTools::get_prompts_list();
-    assert!(prompts["prompts"].as_array().unwrap().is_empty());
+    let prompts = prompts["prompts"]
+        .as_array()
+        .expect("prompts should be an array");
+    assert!(prompts.iter().any(|p| p["name"] == "recall_context"));
+    assert!(prompts
+        .iter()
+        .all(|p| p["name"].is_string() && p["arguments"].is_array()));
 }
 
 /// Test MCP specification compliance end-to-end
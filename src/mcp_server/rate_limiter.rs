@@ -97,6 +97,19 @@ impl MCPRateLimitConfig {
         rates.insert("get_harvester_metrics".to_string(), 10);
         rates.insert("migrate_memory".to_string(), 20);
         rates.insert("delete_memory".to_string(), 10);
+        rates.insert("get_memory".to_string(), 200);
+        rates.insert("update_memory".to_string(), 50);
+        rates.insert("memory_history".to_string(), 100);
+        rates.insert("revert_memory".to_string(), 10);
+        rates.insert("link_memories".to_string(), 50);
+        rates.insert("unlink_memories".to_string(), 20);
+        rates.insert("get_neighbors".to_string(), 100);
+        rates.insert("list_memories".to_string(), 100);
+        rates.insert("list_entities".to_string(), 100);
+        rates.insert("list_conflicts".to_string(), 100);
+        rates.insert("resolve_conflict".to_string(), 50);
+        rates.insert("store_memories".to_string(), 10);
+        rates.insert("delete_memories".to_string(), 5);
 
         // Load custom rates from environment
         if let Ok(custom_rates) = env::var("MCP_TOOL_RATE_LIMITS") {
@@ -121,6 +134,19 @@ impl MCPRateLimitConfig {
         bursts.insert("get_harvester_metrics".to_string(), 1);
         bursts.insert("migrate_memory".to_string(), 2);
         bursts.insert("delete_memory".to_string(), 1);
+        bursts.insert("get_memory".to_string(), 20);
+        bursts.insert("update_memory".to_string(), 5);
+        bursts.insert("memory_history".to_string(), 10);
+        bursts.insert("revert_memory".to_string(), 1);
+        bursts.insert("link_memories".to_string(), 5);
+        bursts.insert("unlink_memories".to_string(), 2);
+        bursts.insert("get_neighbors".to_string(), 10);
+        bursts.insert("list_memories".to_string(), 10);
+        bursts.insert("list_entities".to_string(), 10);
+        bursts.insert("list_conflicts".to_string(), 10);
+        bursts.insert("resolve_conflict".to_string(), 5);
+        bursts.insert("store_memories".to_string(), 1);
+        bursts.insert("delete_memories".to_string(), 1);
 
         // Load custom burst sizes from environment
         if let Ok(custom_bursts) = env::var("MCP_TOOL_BURST_SIZES") {
@@ -676,6 +702,7 @@ mod tests {
             scopes: vec!["mcp:read".to_string(), "mcp:write".to_string()],
             expires_at: None,
             request_id: "test-request".to_string(),
+            namespace: crate::memory::models::DEFAULT_NAMESPACE.to_string(),
         }
     }
 
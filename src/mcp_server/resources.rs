@@ -0,0 +1,215 @@
+//! MCP Resources
+//!
+//! Memories, tiers and insights are exposed as read-only `codex://` resources
+//! so clients can attach them to context without spending a tool call:
+//!
+//! - `codex://memory/{id}` - a single memory
+//! - `codex://tier/{tier}` - active memories in a tier
+//! - `codex://insight/{id}` - a single insight (requires `codex-dreams`)
+//! - `codex://recent` - the most recently created memories
+
+use crate::memory::models::{Memory, MemoryTier};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+pub const RESOURCE_SCHEME: &str = "codex://";
+pub const RESOURCE_MIME_TYPE: &str = "application/json";
+
+/// Number of memories returned by `codex://recent`
+pub const RECENT_RESOURCE_LIMIT: i32 = 20;
+
+/// Number of memories returned by `codex://tier/{tier}`
+pub const TIER_RESOURCE_LIMIT: i64 = 50;
+
+/// A parsed `codex://` resource URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodexResource {
+    Memory(Uuid),
+    Tier(MemoryTier),
+    Insight(Uuid),
+    Recent,
+}
+
+impl CodexResource {
+    /// Parse a `codex://` URI
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let path = uri
+            .strip_prefix(RESOURCE_SCHEME)
+            .ok_or_else(|| format!("Unsupported resource URI scheme: {uri}"))?;
+
+        let mut parts = path.splitn(2, '/');
+        let kind = parts.next().unwrap_or_default();
+        let value = parts.next();
+
+        match (kind, value) {
+            ("recent", None) => Ok(Self::Recent),
+            ("memory", Some(id)) => Uuid::parse_str(id)
+                .map(Self::Memory)
+                .map_err(|_| format!("Invalid memory id in resource URI: {uri}")),
+            ("insight", Some(id)) => Uuid::parse_str(id)
+                .map(Self::Insight)
+                .map_err(|_| format!("Invalid insight id in resource URI: {uri}")),
+            ("tier", Some(tier)) => tier.parse::<MemoryTier>().map(Self::Tier),
+            _ => Err(format!("Unknown resource: {uri}")),
+        }
+    }
+
+    /// Canonical URI for this resource
+    pub fn uri(&self) -> String {
+        match self {
+            Self::Memory(id) => format!("{RESOURCE_SCHEME}memory/{id}"),
+            Self::Tier(tier) => format!("{RESOURCE_SCHEME}tier/{}", tier_name(*tier)),
+            Self::Insight(id) => format!("{RESOURCE_SCHEME}insight/{id}"),
+            Self::Recent => format!("{RESOURCE_SCHEME}recent"),
+        }
+    }
+}
+
+fn tier_name(tier: MemoryTier) -> &'static str {
+    match tier {
+        MemoryTier::Working => "working",
+        MemoryTier::Warm => "warm",
+        MemoryTier::Cold => "cold",
+        MemoryTier::Frozen => "frozen",
+    }
+}
+
+/// Resources that always exist, independent of stored data
+pub fn static_resources() -> Vec<Value> {
+    let mut resources = vec![json!({
+        "uri": CodexResource::Recent.uri(),
+        "name": "Recent memories",
+        "description": format!("The {RECENT_RESOURCE_LIMIT} most recently stored memories"),
+        "mimeType": RESOURCE_MIME_TYPE
+    })];
+
+    for tier in [MemoryTier::Working, MemoryTier::Warm, MemoryTier::Cold] {
+        resources.push(json!({
+            "uri": CodexResource::Tier(tier).uri(),
+            "name": format!("{} tier", tier_name(tier)),
+            "description": format!("Active memories in the {} tier", tier_name(tier)),
+            "mimeType": RESOURCE_MIME_TYPE
+        }));
+    }
+
+    resources
+}
+
+/// Resource templates advertised via `resources/templates/list`
+pub fn resource_templates() -> Vec<Value> {
+    let mut templates = vec![
+        json!({
+            "uriTemplate": "codex://memory/{id}",
+            "name": "Memory",
+            "description": "A single memory by its UUID",
+            "mimeType": RESOURCE_MIME_TYPE
+        }),
+        json!({
+            "uriTemplate": "codex://tier/{tier}",
+            "name": "Memory tier",
+            "description": "Active memories in a tier (working, warm, cold, frozen)",
+            "mimeType": RESOURCE_MIME_TYPE
+        }),
+    ];
+
+    if cfg!(feature = "codex-dreams") {
+        templates.push(json!({
+            "uriTemplate": "codex://insight/{id}",
+            "name": "Insight",
+            "description": "A single generated insight by its UUID",
+            "mimeType": RESOURCE_MIME_TYPE
+        }));
+    }
+
+    templates
+}
+
+/// Resource list entry for an individual memory
+pub fn memory_resource_entry(memory: &Memory) -> Value {
+    let preview: String = memory.content.chars().take(60).collect();
+    json!({
+        "uri": CodexResource::Memory(memory.id).uri(),
+        "name": preview,
+        "description": format!(
+            "{} tier memory (importance {:.2})",
+            tier_name(memory.tier),
+            memory.importance_score
+        ),
+        "mimeType": RESOURCE_MIME_TYPE
+    })
+}
+
+/// JSON body of a memory resource; embeddings are omitted to keep context small
+pub fn memory_resource_body(memory: &Memory) -> Value {
+    json!({
+        "id": memory.id,
+        "content": memory.content,
+        "tier": tier_name(memory.tier),
+        "importance_score": memory.importance_score,
+        "access_count": memory.access_count,
+        "metadata": memory.metadata,
+        "parent_id": memory.parent_id,
+        "created_at": memory.created_at,
+        "updated_at": memory.updated_at,
+        "last_accessed_at": memory.last_accessed_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_uris() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            CodexResource::parse(&format!("codex://memory/{id}")),
+            Ok(CodexResource::Memory(id))
+        );
+        assert_eq!(
+            CodexResource::parse(&format!("codex://insight/{id}")),
+            Ok(CodexResource::Insight(id))
+        );
+        assert_eq!(
+            CodexResource::parse("codex://tier/warm"),
+            Ok(CodexResource::Tier(MemoryTier::Warm))
+        );
+        assert_eq!(
+            CodexResource::parse("codex://recent"),
+            Ok(CodexResource::Recent)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_uris() {
+        assert!(CodexResource::parse("file:///etc/passwd").is_err());
+        assert!(CodexResource::parse("codex://memory/not-a-uuid").is_err());
+        assert!(CodexResource::parse("codex://tier/lukewarm").is_err());
+        assert!(CodexResource::parse("codex://recent/extra").is_err());
+        assert!(CodexResource::parse("codex://unknown/1").is_err());
+    }
+
+    #[test]
+    fn test_uri_roundtrip() {
+        for resource in [
+            CodexResource::Memory(Uuid::new_v4()),
+            CodexResource::Tier(MemoryTier::Cold),
+            CodexResource::Insight(Uuid::new_v4()),
+            CodexResource::Recent,
+        ] {
+            assert_eq!(CodexResource::parse(&resource.uri()), Ok(resource));
+        }
+    }
+
+    #[test]
+    fn test_memory_resource_body_omits_embedding() {
+        let memory = Memory {
+            content: "remember this".to_string(),
+            embedding: Some(pgvector::Vector::from(vec![0.1, 0.2])),
+            ..Memory::default()
+        };
+        let body = memory_resource_body(&memory);
+        assert_eq!(body["content"], "remember this");
+        assert!(body.get("embedding").is_none());
+    }
+}
@@ -5,6 +5,7 @@
 //! is secure against various attack vectors.
 
 use super::{auth::*, rate_limiter::*, transport::*};
+use crate::memory::models::DEFAULT_NAMESPACE;
 use crate::security::AuditConfig;
 use anyhow::Result;
 use chrono::{Duration, Utc};
@@ -322,6 +323,7 @@ async fn test_scope_validation_security() {
         scopes: vec!["mcp:read".to_string()], // Only read access
         expires_at: None,
         request_id: Uuid::new_v4().to_string(),
+        namespace: DEFAULT_NAMESPACE.to_string(),
     };
 
     // Test read operation (should succeed)
@@ -351,6 +353,7 @@ async fn test_scope_validation_security() {
         scopes: vec![], // No scopes
         expires_at: None,
         request_id: Uuid::new_v4().to_string(),
+        namespace: DEFAULT_NAMESPACE.to_string(),
     };
 
     assert!(auth
@@ -591,6 +594,7 @@ fn create_rate_limit_auth_context(client_id: &str, scopes: Vec<String>) -> AuthC
         scopes,
         expires_at: None,
         request_id: format!("req-{}", Uuid::new_v4()),
+        namespace: DEFAULT_NAMESPACE.to_string(),
     }
 }
 
@@ -3,6 +3,13 @@
 //! This module defines the tools exposed through the MCP protocol,
 //! including their schemas and capabilities for memory management.
 
+use crate::mcp_server::{prompts, resources};
+use crate::memory::contradictions::ConflictStatus;
+use crate::memory::entities::EntityType;
+use crate::memory::reflection_engine::RelationshipType;
+use crate::memory::relations::{RelationDirection, MAX_TRAVERSAL_DEPTH};
+use crate::memory::time_expressions::TimeExpressionParser;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde_json::{json, Value};
 
 /// MCP Tools registry and schema definitions
@@ -42,6 +49,19 @@ impl MCPTools {
                         "metadata": {
                             "type": "object",
                             "description": "Optional additional metadata to store with the memory"
+                        },
+                        "correction": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "The memory corrects an earlier fact; contradicted memories are superseded instead of queued as conflicts"
+                        },
+                        "valid_from": {
+                            "type": "string",
+                            "description": "When the fact became true (RFC 3339 or YYYY-MM-DD); defaults to now"
+                        },
+                        "valid_to": {
+                            "type": "string",
+                            "description": "When the fact stopped being true, for facts about the past (RFC 3339 or YYYY-MM-DD)"
                         }
                     },
                     "required": ["content"]
@@ -49,7 +69,7 @@ impl MCPTools {
             }),
             json!({
                 "name": "search_memory",
-                "description": "Search memories using semantic similarity",
+                "description": "Search memories by combining semantic similarity with keyword matching",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -81,10 +101,68 @@ impl MCPTools {
                             "items": {"type": "string"},
                             "description": "Optional tags to filter results by"
                         },
+                        "entity": {
+                            "type": "string",
+                            "description": "Only return memories mentioning this person, project, tool or place; any alias works (e.g. 'pg' for PostgreSQL)"
+                        },
+                        "include_superseded": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Also return memories superseded by a later, contradicting memory"
+                        },
+                        "as_of": {
+                            "type": "string",
+                            "description": "Only return facts that held at this date (RFC 3339 or YYYY-MM-DD), including ones since superseded"
+                        },
+                        "time_range": {
+                            "type": "string",
+                            "description": "Only return memories created in this range, in plain words (e.g. 'last week', 'yesterday afternoon', 'since the v0.1.50 release'); the resolved range is echoed back"
+                        },
                         "include_metadata": {
                             "type": "boolean",
                             "default": true,
                             "description": "Whether to include metadata in results"
+                        },
+                        "search_type": {
+                            "type": "string",
+                            "enum": ["hybrid", "semantic", "fulltext", "temporal"],
+                            "default": "hybrid",
+                            "description": "hybrid fuses semantic and keyword rankings, which finds exact identifiers such as error codes and file names"
+                        },
+                        "rrf_k": {
+                            "type": "number",
+                            "minimum": 0.0,
+                            "default": 60,
+                            "description": "Reciprocal rank fusion constant for hybrid search; lower values favor top-ranked matches"
+                        },
+                        "explain": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Show how each result was ranked"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "Cursor from a previous search with the same query and filters, to fetch the next page"
+                        },
+                        "expand_relations": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Also return memories linked to the results"
+                        },
+                        "relation_types": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": RelationshipType::ALL.map(|r| r.as_str())
+                            },
+                            "description": "Only follow these relations when expanding; all by default"
+                        },
+                        "expand_depth": {
+                            "type": "integer",
+                            "default": 1,
+                            "minimum": 1,
+                            "maximum": 3,
+                            "description": "Number of hops to follow when expanding"
                         }
                     },
                     "required": ["query"]
@@ -118,8 +196,7 @@ impl MCPTools {
                         },
                         "time_range": {
                             "type": "string",
-                            "enum": ["last_hour", "last_day", "last_week", "last_month"],
-                            "description": "Time range to search within",
+                            "description": "Time range to search within, in plain words: 'last_day', 'yesterday afternoon', 'since the v0.1.50 release', 'between March and May', 'two sprints ago'",
                             "default": "last_day"
                         },
                         "limit": {
@@ -179,7 +256,7 @@ impl MCPTools {
             }),
             json!({
                 "name": "migrate_memory",
-                "description": "Move a memory between tiers in the hierarchical system",
+                "description": "Move a memory, or many memories at once, between tiers in the hierarchical system. Bulk migrations report progress when a progressToken is supplied.",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -187,6 +264,11 @@ impl MCPTools {
                             "type": "string",
                             "description": "UUID of the memory to migrate"
                         },
+                        "memory_ids": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "UUIDs of memories to migrate in bulk (instead of memory_id)"
+                        },
                         "target_tier": {
                             "type": "string",
                             "enum": ["working", "warm", "cold", "frozen"],
@@ -197,7 +279,7 @@ impl MCPTools {
                             "description": "Optional reason for migration"
                         }
                     },
-                    "required": ["memory_id", "target_tier"]
+                    "required": ["target_tier"]
                 }
             }),
             json!({
@@ -208,15 +290,400 @@ impl MCPTools {
                     "properties": {
                         "memory_id": {
                             "type": "string",
-                            "description": "UUID of the memory to delete"
+                            "description": "UUID of the memory to delete"
+                        },
+                        "confirm": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Confirmation flag to prevent accidental deletions"
+                        }
+                    },
+                    "required": ["memory_id", "confirm"]
+                }
+            }),
+            json!({
+                "name": "get_memory",
+                "description": "Fetch a single memory by ID with its full content and metadata",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memory_id": {
+                            "type": "string",
+                            "description": "UUID of the memory to fetch"
+                        },
+                        "reveal_pii": {
+                            "type": "boolean",
+                            "description": "Replace PII vault tokens with the values they stand for. Requires the pii:reveal permission; every reveal is audited.",
+                            "default": false
+                        }
+                    },
+                    "required": ["memory_id"]
+                }
+            }),
+            json!({
+                "name": "update_memory",
+                "description": "Correct or adjust an existing memory in place. Only the given fields change; new content is re-embedded.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memory_id": {
+                            "type": "string",
+                            "description": "UUID of the memory to update"
+                        },
+                        "content": {
+                            "type": "string",
+                            "description": "Replacement content"
+                        },
+                        "tier": {
+                            "type": "string",
+                            "enum": ["working", "warm", "cold"],
+                            "description": "New tier for the memory"
+                        },
+                        "importance_score": {
+                            "type": "number",
+                            "minimum": 0.0,
+                            "maximum": 1.0,
+                            "description": "New importance score (0.0 to 1.0)"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Replacement tags; other metadata is kept"
+                        },
+                        "metadata": {
+                            "type": "object",
+                            "description": "Replacement metadata"
+                        },
+                        "reason": {
+                            "type": "string",
+                            "description": "Why the memory is changed, kept in its edit history"
+                        }
+                    },
+                    "required": ["memory_id"]
+                }
+            }),
+            json!({
+                "name": "memory_history",
+                "description": "Show who changed a memory, when and why. Pass from_revision and to_revision to diff two revisions.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memory_id": {
+                            "type": "string",
+                            "description": "UUID of the memory"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "default": 20,
+                            "minimum": 1,
+                            "maximum": 100,
+                            "description": "Maximum number of revisions to list, newest first"
+                        },
+                        "from_revision": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Older revision to diff from"
+                        },
+                        "to_revision": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Newer revision to diff to"
+                        }
+                    },
+                    "required": ["memory_id"]
+                }
+            }),
+            json!({
+                "name": "revert_memory",
+                "description": "Restore the content, metadata and importance of an earlier revision of a memory. The revert is recorded as a new revision.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memory_id": {
+                            "type": "string",
+                            "description": "UUID of the memory to revert"
+                        },
+                        "revision": {
+                            "type": "integer",
+                            "minimum": 1,
+                            "description": "Revision to restore, as listed by memory_history"
+                        },
+                        "confirm": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Confirmation flag to prevent accidental reverts"
+                        }
+                    },
+                    "required": ["memory_id", "revision", "confirm"]
+                }
+            }),
+            json!({
+                "name": "list_entities",
+                "description": "List the people, projects, tools and places memories mention, most mentioned first, with their aliases",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "entity_type": {
+                            "type": "string",
+                            "enum": EntityType::ALL.map(|t| t.as_str()),
+                            "description": "Only list entities of this type"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "default": 20,
+                            "minimum": 1,
+                            "maximum": 100,
+                            "description": "Maximum number of entities to return"
+                        }
+                    },
+                    "required": []
+                }
+            }),
+            json!({
+                "name": "list_conflicts",
+                "description": "List memories that contradict each other, newest first",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "status": {
+                            "type": "string",
+                            "enum": ConflictStatus::ALL.map(|s| s.as_str()),
+                            "description": "Only list conflicts in this state"
+                        },
+                        "memory_id": {
+                            "type": "string",
+                            "description": "Only list conflicts involving this memory"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "default": 20,
+                            "minimum": 1,
+                            "maximum": 100,
+                            "description": "Maximum number of conflicts to return"
+                        }
+                    },
+                    "required": []
+                }
+            }),
+            json!({
+                "name": "resolve_conflict",
+                "description": "Resolve a conflict: mark one memory as superseding the other, mark both as valid, or reopen it",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "conflict_id": {
+                            "type": "string",
+                            "description": "ID of the conflict to resolve"
+                        },
+                        "resolution": {
+                            "type": "string",
+                            "enum": ConflictStatus::ALL.map(|s| s.as_str()),
+                            "description": "superseded hides the outdated memory from search, both_valid keeps both, open reopens the conflict"
+                        },
+                        "current_memory_id": {
+                            "type": "string",
+                            "description": "Memory that holds the current fact when superseding (defaults to the newer one)"
+                        }
+                    },
+                    "required": ["conflict_id", "resolution"]
+                }
+            }),
+            json!({
+                "name": "list_memories",
+                "description": "List memories newest first, optionally filtered by tier and tags, one page at a time",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "tier": {
+                            "type": "string",
+                            "enum": ["working", "warm", "cold"],
+                            "description": "Only list memories in this tier"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "description": "Only list memories carrying all of these tags"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "default": 20,
+                            "minimum": 1,
+                            "maximum": 100,
+                            "description": "Maximum number of memories per page"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "Cursor from the previous page with the same filters"
+                        }
+                    },
+                    "required": []
+                }
+            }),
+            json!({
+                "name": "store_memories",
+                "description": "Store many memories in one call; embeddings are generated in a single batch",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memories": {
+                            "type": "array",
+                            "minItems": 1,
+                            "maxItems": 100,
+                            "description": "Memories to store, each with the same fields as store_memory",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "content": {"type": "string"},
+                                    "tier": {
+                                        "type": "string",
+                                        "enum": ["working", "warm", "cold"]
+                                    },
+                                    "tags": {
+                                        "type": "array",
+                                        "items": {"type": "string"}
+                                    },
+                                    "importance_score": {
+                                        "type": "number",
+                                        "minimum": 0.0,
+                                        "maximum": 1.0
+                                    },
+                                    "metadata": {"type": "object"},
+                                    "correction": {"type": "boolean"},
+                                    "valid_from": {"type": "string"},
+                                    "valid_to": {"type": "string"}
+                                },
+                                "required": ["content"]
+                            }
+                        }
+                    },
+                    "required": ["memories"]
+                }
+            }),
+            json!({
+                "name": "delete_memories",
+                "description": "Delete several memories in one call",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memory_ids": {
+                            "type": "array",
+                            "items": {"type": "string"},
+                            "minItems": 1,
+                            "maxItems": 100,
+                            "description": "UUIDs of the memories to delete"
+                        },
+                        "confirm": {
+                            "type": "boolean",
+                            "default": false,
+                            "description": "Confirmation flag to prevent accidental deletions"
+                        }
+                    },
+                    "required": ["memory_ids", "confirm"]
+                }
+            }),
+            json!({
+                "name": "link_memories",
+                "description": "Record a typed relation from one memory to another, e.g. that an outage was caused_by an expired certificate",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "source_id": {
+                            "type": "string",
+                            "description": "UUID of the memory the relation starts at"
+                        },
+                        "target_id": {
+                            "type": "string",
+                            "description": "UUID of the memory the relation points to"
+                        },
+                        "relation": {
+                            "type": "string",
+                            "enum": RelationshipType::ALL.map(|r| r.as_str()),
+                            "description": "How the source relates to the target"
+                        },
+                        "strength": {
+                            "type": "number",
+                            "default": 1.0,
+                            "minimum": 0.0,
+                            "maximum": 1.0,
+                            "description": "Confidence in the relation"
+                        },
+                        "metadata": {
+                            "type": "object",
+                            "description": "Additional information about the relation"
+                        }
+                    },
+                    "required": ["source_id", "target_id", "relation"]
+                }
+            }),
+            json!({
+                "name": "unlink_memories",
+                "description": "Remove relations from one memory to another",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "source_id": {
+                            "type": "string",
+                            "description": "UUID of the memory the relation starts at"
+                        },
+                        "target_id": {
+                            "type": "string",
+                            "description": "UUID of the memory the relation points to"
+                        },
+                        "relation": {
+                            "type": "string",
+                            "enum": RelationshipType::ALL.map(|r| r.as_str()),
+                            "description": "Only remove this relation; all relations between the two by default"
+                        }
+                    },
+                    "required": ["source_id", "target_id"]
+                }
+            }),
+            json!({
+                "name": "get_neighbors",
+                "description": "List memories related to a memory, following relations up to a number of hops",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "memory_id": {
+                            "type": "string",
+                            "description": "UUID of the memory to start from"
+                        },
+                        "relation_types": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": RelationshipType::ALL.map(|r| r.as_str())
+                            },
+                            "description": "Only follow these relations; all by default"
+                        },
+                        "direction": {
+                            "type": "string",
+                            "enum": ["outgoing", "incoming", "both"],
+                            "default": "both",
+                            "description": "Follow relations starting at the memory, ending at it, or both"
                         },
-                        "confirm": {
-                            "type": "boolean",
-                            "default": false,
-                            "description": "Confirmation flag to prevent accidental deletions"
+                        "depth": {
+                            "type": "integer",
+                            "default": 1,
+                            "minimum": 1,
+                            "maximum": MAX_TRAVERSAL_DEPTH,
+                            "description": "Number of hops to follow"
+                        },
+                        "min_strength": {
+                            "type": "number",
+                            "minimum": 0.0,
+                            "maximum": 1.0,
+                            "description": "Ignore weaker relations"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "default": 20,
+                            "minimum": 1,
+                            "maximum": 100,
+                            "description": "Maximum number of memories to return"
                         }
                     },
-                    "required": ["memory_id", "confirm"]
+                    "required": ["memory_id"]
                 }
             }),
         ];
@@ -233,8 +700,7 @@ impl MCPTools {
                         "properties": {
                             "time_period": {
                                 "type": "string",
-                                "enum": ["last_hour", "last_day", "last_week", "last_month", "all"],
-                                "description": "Time period to analyze for insights",
+                                "description": "Time period to analyze for insights, e.g. 'last_week', 'all', 'this sprint' or 'since March'",
                                 "default": "last_day"
                             },
                             "topic": {
@@ -406,17 +872,24 @@ impl MCPTools {
         })
     }
 
-    /// Get empty resources list (codex-memory doesn't expose resources)
+    /// Get the static resources list (recent memories and tier views)
     pub fn get_resources_list() -> Value {
         json!({
-            "resources": []
+            "resources": resources::static_resources()
+        })
+    }
+
+    /// Get resource templates for memories, tiers and insights
+    pub fn get_resource_templates_list() -> Value {
+        json!({
+            "resourceTemplates": resources::resource_templates()
         })
     }
 
-    /// Get empty prompts list (codex-memory doesn't use prompts)
+    /// Get the built-in prompts list (user templates are added by the handlers)
     pub fn get_prompts_list() -> Value {
         json!({
-            "prompts": []
+            "prompts": prompts::builtin_prompts()
         })
     }
 
@@ -480,6 +953,8 @@ impl MCPTools {
                         return Err("Importance score must be between 0.0 and 1.0".to_string());
                     }
                 }
+
+                validate_valid_time_args(args)?;
             }
             "search_memory" => {
                 if args
@@ -512,13 +987,42 @@ impl MCPTools {
                         );
                     }
                 }
+
+                if let Some(search_type) = args.get("search_type").and_then(|t| t.as_str()) {
+                    if !["hybrid", "semantic", "fulltext", "temporal"].contains(&search_type) {
+                        return Err(
+                            "Invalid search_type. Must be 'hybrid', 'semantic', 'fulltext', or 'temporal'"
+                                .to_string(),
+                        );
+                    }
+                }
+
+                if let Some(k) = args.get("rrf_k").and_then(|k| k.as_f64()) {
+                    if k < 0.0 {
+                        return Err("rrf_k must not be negative".to_string());
+                    }
+                }
+
+                parse_time_arg(args, "as_of")?;
+                validate_time_expression_arg(args, "time_range")?;
+
+                validate_relation_types(args)?;
+                if let Some(depth) = args.get("expand_depth").and_then(|d| d.as_i64()) {
+                    if !(1..=3).contains(&depth) {
+                        return Err("expand_depth must be between 1 and 3".to_string());
+                    }
+                }
             }
             "migrate_memory" => {
-                if args
+                let has_single_id = args
                     .get("memory_id")
                     .and_then(|id| id.as_str())
-                    .is_none_or(|s| s.is_empty())
-                {
+                    .is_some_and(|s| !s.is_empty());
+                let has_bulk_ids = args
+                    .get("memory_ids")
+                    .and_then(|ids| ids.as_array())
+                    .is_some_and(|ids| !ids.is_empty());
+                if !has_single_id && !has_bulk_ids {
                     return Err("Memory ID is required".to_string());
                 }
 
@@ -547,14 +1051,257 @@ impl MCPTools {
                     return Err("Confirmation required for deletion".to_string());
                 }
             }
-            "what_did_you_remember" => {
-                // Validate time_range if provided
-                if let Some(range) = args.get("time_range").and_then(|r| r.as_str()) {
-                    if !["last_hour", "last_day", "last_week", "last_month"].contains(&range) {
-                        return Err("Invalid time range".to_string());
+            "get_memory" => {
+                if args
+                    .get("memory_id")
+                    .and_then(|id| id.as_str())
+                    .is_none_or(|s| s.is_empty())
+                {
+                    return Err("Memory ID is required".to_string());
+                }
+            }
+            "update_memory" => {
+                if args
+                    .get("memory_id")
+                    .and_then(|id| id.as_str())
+                    .is_none_or(|s| s.is_empty())
+                {
+                    return Err("Memory ID is required".to_string());
+                }
+
+                let changes = [
+                    "content",
+                    "tier",
+                    "importance_score",
+                    "tags",
+                    "metadata",
+                    "valid_from",
+                    "valid_to",
+                ];
+                if !changes.iter().any(|field| args.get(field).is_some()) {
+                    return Err("At least one field to update is required".to_string());
+                }
+
+                if let Some(content) = args.get("content").and_then(|c| c.as_str()) {
+                    if content.is_empty() {
+                        return Err("Content cannot be empty".to_string());
+                    }
+                }
+
+                if let Some(tier) = args.get("tier") {
+                    if !tier
+                        .as_str()
+                        .is_some_and(|tier| ["working", "warm", "cold"].contains(&tier))
+                    {
+                        return Err(
+                            "Invalid tier. Must be 'working', 'warm', or 'cold'".to_string()
+                        );
+                    }
+                }
+
+                if let Some(score) = args.get("importance_score").and_then(|s| s.as_f64()) {
+                    if !(0.0..=1.0).contains(&score) {
+                        return Err("Importance score must be between 0.0 and 1.0".to_string());
+                    }
+                }
+
+                validate_valid_time_args(args)?;
+            }
+            "memory_history" => {
+                if args
+                    .get("memory_id")
+                    .and_then(|id| id.as_str())
+                    .is_none_or(|s| s.is_empty())
+                {
+                    return Err("Memory ID is required".to_string());
+                }
+
+                if let Some(limit) = args.get("limit").and_then(|l| l.as_i64()) {
+                    if !(1..=100).contains(&limit) {
+                        return Err("Limit must be between 1 and 100".to_string());
+                    }
+                }
+
+                let from = args.get("from_revision").map(|r| r.as_i64());
+                let to = args.get("to_revision").map(|r| r.as_i64());
+                match (from, to) {
+                    (None, None) => {}
+                    (Some(Some(from)), Some(Some(to))) if from >= 1 && to >= 1 => {}
+                    (Some(_), Some(_)) => {
+                        return Err("Revisions must be positive integers".to_string());
+                    }
+                    _ => {
+                        return Err(
+                            "from_revision and to_revision must be given together".to_string()
+                        );
+                    }
+                }
+            }
+            "revert_memory" => {
+                if args
+                    .get("memory_id")
+                    .and_then(|id| id.as_str())
+                    .is_none_or(|s| s.is_empty())
+                {
+                    return Err("Memory ID is required".to_string());
+                }
+
+                if args
+                    .get("revision")
+                    .and_then(|r| r.as_i64())
+                    .is_none_or(|r| r < 1)
+                {
+                    return Err("Revision must be a positive integer".to_string());
+                }
+
+                if !args
+                    .get("confirm")
+                    .and_then(|c| c.as_bool())
+                    .unwrap_or(false)
+                {
+                    return Err("Confirmation required for revert".to_string());
+                }
+            }
+            "link_memories" | "unlink_memories" => {
+                for field in ["source_id", "target_id"] {
+                    if args
+                        .get(field)
+                        .and_then(|id| id.as_str())
+                        .is_none_or(|s| s.is_empty())
+                    {
+                        return Err(format!("{field} is required"));
+                    }
+                }
+
+                match args.get("relation").and_then(|r| r.as_str()) {
+                    Some(relation) => {
+                        relation.parse::<RelationshipType>()?;
+                    }
+                    None if tool_name == "link_memories" => {
+                        return Err("Relation is required".to_string());
+                    }
+                    None => {}
+                }
+
+                if let Some(strength) = args.get("strength").and_then(|s| s.as_f64()) {
+                    if !(0.0..=1.0).contains(&strength) {
+                        return Err("Strength must be between 0.0 and 1.0".to_string());
+                    }
+                }
+            }
+            "get_neighbors" => {
+                if args
+                    .get("memory_id")
+                    .and_then(|id| id.as_str())
+                    .is_none_or(|s| s.is_empty())
+                {
+                    return Err("Memory ID is required".to_string());
+                }
+
+                validate_relation_types(args)?;
+                if let Some(direction) = args.get("direction").and_then(|d| d.as_str()) {
+                    direction.parse::<RelationDirection>()?;
+                }
+                if let Some(depth) = args.get("depth").and_then(|d| d.as_i64()) {
+                    if !(1..=MAX_TRAVERSAL_DEPTH as i64).contains(&depth) {
+                        return Err(format!("Depth must be between 1 and {MAX_TRAVERSAL_DEPTH}"));
+                    }
+                }
+                if let Some(limit) = args.get("limit").and_then(|l| l.as_i64()) {
+                    if !(1..=100).contains(&limit) {
+                        return Err("Limit must be between 1 and 100".to_string());
+                    }
+                }
+            }
+            "list_entities" => {
+                if let Some(entity_type) = args.get("entity_type").and_then(|t| t.as_str()) {
+                    entity_type.parse::<EntityType>()?;
+                }
+
+                if let Some(limit) = args.get("limit").and_then(|l| l.as_i64()) {
+                    if !(1..=100).contains(&limit) {
+                        return Err("Limit must be between 1 and 100".to_string());
+                    }
+                }
+            }
+            "list_conflicts" => {
+                if let Some(status) = args.get("status").and_then(|s| s.as_str()) {
+                    status.parse::<ConflictStatus>()?;
+                }
+
+                if let Some(limit) = args.get("limit").and_then(|l| l.as_i64()) {
+                    if !(1..=100).contains(&limit) {
+                        return Err("Limit must be between 1 and 100".to_string());
+                    }
+                }
+            }
+            "resolve_conflict" => {
+                if args
+                    .get("conflict_id")
+                    .and_then(|id| id.as_str())
+                    .is_none_or(|s| s.is_empty())
+                {
+                    return Err("Conflict ID is required".to_string());
+                }
+
+                match args.get("resolution").and_then(|r| r.as_str()) {
+                    Some(resolution) => {
+                        resolution.parse::<ConflictStatus>()?;
+                    }
+                    None => return Err("Resolution is required".to_string()),
+                }
+            }
+            "list_memories" => {
+                if let Some(tier) = args.get("tier").and_then(|t| t.as_str()) {
+                    if !["working", "warm", "cold"].contains(&tier) {
+                        return Err(
+                            "Invalid tier. Must be 'working', 'warm', or 'cold'".to_string()
+                        );
+                    }
+                }
+
+                if let Some(limit) = args.get("limit").and_then(|l| l.as_i64()) {
+                    if !(1..=100).contains(&limit) {
+                        return Err("Limit must be between 1 and 100".to_string());
                     }
                 }
             }
+            "store_memories" => {
+                let memories = args
+                    .get("memories")
+                    .and_then(|m| m.as_array())
+                    .filter(|m| !m.is_empty())
+                    .ok_or_else(|| "At least one memory is required".to_string())?;
+                if memories.len() > 100 {
+                    return Err("At most 100 memories can be stored per call".to_string());
+                }
+
+                for (index, memory) in memories.iter().enumerate() {
+                    Self::validate_tool_args("store_memory", memory)
+                        .map_err(|e| format!("Memory {index}: {e}"))?;
+                }
+            }
+            "delete_memories" => {
+                let ids = args
+                    .get("memory_ids")
+                    .and_then(|ids| ids.as_array())
+                    .filter(|ids| !ids.is_empty())
+                    .ok_or_else(|| "At least one memory ID is required".to_string())?;
+                if ids.len() > 100 {
+                    return Err("At most 100 memories can be deleted per call".to_string());
+                }
+
+                if !args
+                    .get("confirm")
+                    .and_then(|c| c.as_bool())
+                    .unwrap_or(false)
+                {
+                    return Err("Confirmation required for deletion".to_string());
+                }
+            }
+            "what_did_you_remember" => {
+                validate_time_expression_arg(args, "time_range")?;
+            }
             "harvest_conversation" => {
                 // Validate role if provided
                 if let Some(role) = args.get("role").and_then(|r| r.as_str()) {
@@ -570,14 +1317,7 @@ impl MCPTools {
             }
             #[cfg(feature = "codex-dreams")]
             "generate_insights" => {
-                // Validate time_period if provided
-                if let Some(period) = args.get("time_period").and_then(|p| p.as_str()) {
-                    if !["last_hour", "last_day", "last_week", "last_month", "all"]
-                        .contains(&period)
-                    {
-                        return Err("Invalid time period".to_string());
-                    }
-                }
+                validate_time_expression_arg(args, "time_period")?;
 
                 // Validate insight_type if provided
                 if let Some(itype) = args.get("insight_type").and_then(|t| t.as_str()) {
@@ -744,6 +1484,64 @@ impl MCPTools {
     }
 }
 
+/// Timestamp from an optional RFC 3339 or `YYYY-MM-DD` argument; dates
+/// mean midnight UTC
+pub(crate) fn parse_time_arg(args: &Value, name: &str) -> Result<Option<DateTime<Utc>>, String> {
+    let Some(value) = args.get(name) else {
+        return Ok(None);
+    };
+    let text = value
+        .as_str()
+        .ok_or_else(|| format!("{name} must be a date string"))?;
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(Some(time.with_timezone(&Utc)));
+    }
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map(|date| Some(date.and_time(Default::default()).and_utc()))
+        .map_err(|_| format!("{name} must be an RFC 3339 timestamp or a YYYY-MM-DD date"))
+}
+
+/// A time expression argument must parse; named events are checked when
+/// the tool runs
+fn validate_time_expression_arg(args: &Value, name: &str) -> Result<(), String> {
+    match args.get(name) {
+        Some(value) => {
+            let text = value
+                .as_str()
+                .ok_or_else(|| format!("{name} must be a string"))?;
+            TimeExpressionParser::default().parse(text).map(|_| ())
+        }
+        None => Ok(()),
+    }
+}
+
+/// `valid_from` and `valid_to` must parse and, when both are given, form a
+/// non-empty interval
+fn validate_valid_time_args(args: &Value) -> Result<(), String> {
+    let valid_from = parse_time_arg(args, "valid_from")?;
+    let valid_to = parse_time_arg(args, "valid_to")?;
+    if let (Some(valid_from), Some(valid_to)) = (valid_from, valid_to) {
+        if valid_to <= valid_from {
+            return Err("valid_to must be after valid_from".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Every entry of an optional `relation_types` argument must name a relation
+fn validate_relation_types(args: &Value) -> Result<(), String> {
+    if let Some(types) = args.get("relation_types").and_then(|t| t.as_array()) {
+        for relation in types {
+            relation
+                .as_str()
+                .ok_or_else(|| "Relation types must be strings".to_string())?
+                .parse::<RelationshipType>()?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -808,6 +1606,157 @@ mod tests {
         assert!(MCPTools::validate_tool_args("unknown_tool", &valid_args).is_err());
     }
 
+    #[test]
+    fn test_memory_crud_tool_validation() {
+        let id = "123e4567-e89b-12d3-a456-426614174000";
+
+        assert!(MCPTools::validate_tool_args("get_memory", &json!({ "memory_id": id })).is_ok());
+        assert!(MCPTools::validate_tool_args("get_memory", &json!({})).is_err());
+
+        let update = json!({ "memory_id": id, "content": "Corrected fact" });
+        assert!(MCPTools::validate_tool_args("update_memory", &update).is_ok());
+        let no_changes = json!({ "memory_id": id });
+        assert!(MCPTools::validate_tool_args("update_memory", &no_changes).is_err());
+        let bad_score = json!({ "memory_id": id, "importance_score": 1.5 });
+        assert!(MCPTools::validate_tool_args("update_memory", &bad_score).is_err());
+        let bad_tier = json!({ "memory_id": id, "tier": "frozen" });
+        assert!(MCPTools::validate_tool_args("update_memory", &bad_tier).is_err());
+        let non_string_tier = json!({ "memory_id": id, "tier": 2 });
+        assert!(MCPTools::validate_tool_args("update_memory", &non_string_tier).is_err());
+
+        assert!(MCPTools::validate_tool_args("list_memories", &json!({})).is_ok());
+        let bad_limit = json!({ "tier": "warm", "limit": 500 });
+        assert!(MCPTools::validate_tool_args("list_memories", &bad_limit).is_err());
+
+        let batch = json!({ "memories": [{ "content": "a" }, { "content": "b", "tier": "warm" }] });
+        assert!(MCPTools::validate_tool_args("store_memories", &batch).is_ok());
+        let bad_item = json!({ "memories": [{ "content": "a" }, { "content": "" }] });
+        let error = MCPTools::validate_tool_args("store_memories", &bad_item)
+            .expect_err("empty content should be rejected");
+        assert!(error.starts_with("Memory 1:"));
+        assert!(
+            MCPTools::validate_tool_args("store_memories", &json!({ "memories": [] })).is_err()
+        );
+
+        let delete = json!({ "memory_ids": [id], "confirm": true });
+        assert!(MCPTools::validate_tool_args("delete_memories", &delete).is_ok());
+        let unconfirmed = json!({ "memory_ids": [id] });
+        assert!(MCPTools::validate_tool_args("delete_memories", &unconfirmed).is_err());
+    }
+
+    #[test]
+    fn test_memory_history_tool_validation() {
+        let id = "123e4567-e89b-12d3-a456-426614174000";
+
+        assert!(
+            MCPTools::validate_tool_args("memory_history", &json!({ "memory_id": id })).is_ok()
+        );
+        let diff = json!({ "memory_id": id, "from_revision": 1, "to_revision": 3 });
+        assert!(MCPTools::validate_tool_args("memory_history", &diff).is_ok());
+        let half_diff = json!({ "memory_id": id, "from_revision": 1 });
+        assert!(MCPTools::validate_tool_args("memory_history", &half_diff).is_err());
+        let bad_revision = json!({ "memory_id": id, "from_revision": 0, "to_revision": 2 });
+        assert!(MCPTools::validate_tool_args("memory_history", &bad_revision).is_err());
+
+        let revert = json!({ "memory_id": id, "revision": 2, "confirm": true });
+        assert!(MCPTools::validate_tool_args("revert_memory", &revert).is_ok());
+        let unconfirmed = json!({ "memory_id": id, "revision": 2 });
+        assert!(MCPTools::validate_tool_args("revert_memory", &unconfirmed).is_err());
+    }
+
+    #[test]
+    fn test_relation_tool_validation() {
+        let a = "123e4567-e89b-12d3-a456-426614174000";
+        let b = "123e4567-e89b-12d3-a456-426614174001";
+
+        let link = json!({ "source_id": a, "target_id": b, "relation": "caused_by" });
+        assert!(MCPTools::validate_tool_args("link_memories", &link).is_ok());
+        let untyped = json!({ "source_id": a, "target_id": b });
+        assert!(MCPTools::validate_tool_args("link_memories", &untyped).is_err());
+        assert!(MCPTools::validate_tool_args("unlink_memories", &untyped).is_ok());
+        let unknown = json!({ "source_id": a, "target_id": b, "relation": "likes" });
+        assert!(MCPTools::validate_tool_args("link_memories", &unknown).is_err());
+
+        let neighbors = json!({
+            "memory_id": a,
+            "relation_types": ["is_a", "part_of"],
+            "direction": "outgoing",
+            "depth": 3
+        });
+        assert!(MCPTools::validate_tool_args("get_neighbors", &neighbors).is_ok());
+        let too_deep = json!({ "memory_id": a, "depth": 9 });
+        assert!(MCPTools::validate_tool_args("get_neighbors", &too_deep).is_err());
+
+        let expanded =
+            json!({ "query": "outage", "expand_relations": true, "relation_types": ["nope"] });
+        assert!(MCPTools::validate_tool_args("search_memory", &expanded).is_err());
+    }
+
+    #[test]
+    fn test_entity_tool_validation() {
+        assert!(MCPTools::validate_tool_args("list_entities", &json!({})).is_ok());
+        let tools = json!({ "entity_type": "tool", "limit": 5 });
+        assert!(MCPTools::validate_tool_args("list_entities", &tools).is_ok());
+        let unknown = json!({ "entity_type": "animal" });
+        assert!(MCPTools::validate_tool_args("list_entities", &unknown).is_err());
+
+        let by_entity = json!({ "query": "migrations", "entity": "pg" });
+        assert!(MCPTools::validate_tool_args("search_memory", &by_entity).is_ok());
+    }
+
+    #[test]
+    fn test_valid_time_validation() {
+        let past = json!({ "content": "The team used MySQL", "valid_from": "2021-03-01", "valid_to": "2024-06-30T12:00:00Z" });
+        assert!(MCPTools::validate_tool_args("store_memory", &past).is_ok());
+        let reversed =
+            json!({ "content": "x", "valid_from": "2024-01-01", "valid_to": "2023-01-01" });
+        assert!(MCPTools::validate_tool_args("store_memory", &reversed).is_err());
+        let garbled = json!({ "query": "database", "as_of": "last spring" });
+        assert!(MCPTools::validate_tool_args("search_memory", &garbled).is_err());
+
+        let as_of = parse_time_arg(&json!({ "as_of": "2023-05-01" }), "as_of").unwrap();
+        assert_eq!(as_of.unwrap().to_rfc3339(), "2023-05-01T00:00:00+00:00");
+        let closed =
+            json!({ "memory_id": uuid::Uuid::new_v4().to_string(), "valid_to": "2024-06-30" });
+        assert!(MCPTools::validate_tool_args("update_memory", &closed).is_ok());
+    }
+
+    #[test]
+    fn test_time_expression_validation() {
+        for range in [
+            "last_week",
+            "yesterday afternoon",
+            "since the v0.1.50 release",
+        ] {
+            let args = json!({ "time_range": range });
+            assert!(MCPTools::validate_tool_args("what_did_you_remember", &args).is_ok());
+        }
+        let garbled = json!({ "time_range": "whenever" });
+        assert!(MCPTools::validate_tool_args("what_did_you_remember", &garbled).is_err());
+
+        let search = json!({ "query": "deploy", "time_range": "between March and May" });
+        assert!(MCPTools::validate_tool_args("search_memory", &search).is_ok());
+        let search = json!({ "query": "deploy", "time_range": 7 });
+        assert!(MCPTools::validate_tool_args("search_memory", &search).is_err());
+    }
+
+    #[test]
+    fn test_conflict_tool_validation() {
+        assert!(MCPTools::validate_tool_args("list_conflicts", &json!({})).is_ok());
+        let open = json!({ "status": "open", "limit": 10 });
+        assert!(MCPTools::validate_tool_args("list_conflicts", &open).is_ok());
+        let unknown = json!({ "status": "disputed" });
+        assert!(MCPTools::validate_tool_args("list_conflicts", &unknown).is_err());
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let resolve = json!({ "conflict_id": id, "resolution": "both_valid" });
+        assert!(MCPTools::validate_tool_args("resolve_conflict", &resolve).is_ok());
+        let missing = json!({ "conflict_id": id });
+        assert!(MCPTools::validate_tool_args("resolve_conflict", &missing).is_err());
+        let bad = json!({ "conflict_id": id, "resolution": "ignored" });
+        assert!(MCPTools::validate_tool_args("resolve_conflict", &bad).is_err());
+    }
+
     #[test]
     fn test_server_capabilities() {
         let capabilities = MCPTools::get_server_capabilities();
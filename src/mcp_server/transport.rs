@@ -4,9 +4,13 @@
 //! handling JSON-RPC messages over standard input/output streams with
 //! transport-level security including rate limiting and connection throttling.
 
-use crate::mcp_server::{handlers::MCPHandlers, rate_limiter::MCPRateLimiter};
+use crate::mcp_server::{
+    handlers::MCPHandlers, logging::MCPLogger, progress::ProgressTracker,
+    rate_limiter::MCPRateLimiter,
+};
 use crate::security::SecurityError;
 use anyhow::Result;
+use futures::stream::{FuturesUnordered, StreamExt};
 use governor::{
     clock::DefaultClock,
     middleware::NoOpMiddleware,
@@ -20,9 +24,15 @@ use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tracing::{debug, error, warn};
 
+/// JSON-RPC error code for requests aborted by `notifications/cancelled`
+pub const REQUEST_CANCELLED_CODE: i32 = -32800;
+
+/// Messages queued for the stdout writer task
+type Outbox = mpsc::UnboundedSender<Value>;
+
 /// Transport-level connection state tracking
 #[derive(Debug)]
 struct ConnectionState {
@@ -129,46 +139,63 @@ impl StdioTransport {
         })
     }
 
-    /// Start the transport layer and begin processing requests
-    pub async fn start(&mut self, handlers: &mut MCPHandlers) -> Result<()> {
+    /// Start the transport layer and begin processing requests.
+    ///
+    /// Requests are processed concurrently so that `notifications/cancelled`
+    /// can reach a running tool, and progress/log notifications are written
+    /// to stdout while requests are in flight.
+    pub async fn start(&mut self, handlers: &MCPHandlers) -> Result<()> {
         debug!("Starting MCP stdio transport");
 
-        // Set up stdin/stdout
-        let stdin = tokio::io::stdin();
-        let mut reader = BufReader::new(stdin);
-        let mut stdout = tokio::io::stdout();
+        // A single writer task owns stdout so responses and notifications
+        // never interleave mid-line
+        let (outbox, outgoing) = mpsc::unbounded_channel();
+        let writer = tokio::spawn(write_messages(outgoing));
+
+        forward_notifications(handlers.subscribe_logs(), outbox.clone(), |message| {
+            MCPLogger::create_log_notification(message)
+        });
+        forward_notifications(handlers.subscribe_progress(), outbox.clone(), |report| {
+            ProgressTracker::create_progress_notification(report)
+        });
 
         // Process messages from stdin
-        let mut line = String::new();
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut in_flight = FuturesUnordered::new();
         loop {
-            line.clear();
-
-            // Read line with timeout
-            let read_result =
-                tokio::time::timeout(self.request_timeout, reader.read_line(&mut line)).await;
-
-            match read_result {
-                Ok(Ok(0)) => {
-                    debug!("EOF received, shutting down transport");
-                    break; // EOF
-                }
-                Ok(Ok(_)) => {
-                    // Process the received message
-                    if let Err(e) = self.process_message(&line, handlers, &mut stdout).await {
+            tokio::select! {
+                line = lines.next_line() => match line {
+                    Ok(Some(line)) => {
+                        in_flight.push(self.process_message(line, handlers, &outbox));
+                    }
+                    Ok(None) => {
+                        debug!("EOF received, shutting down transport");
+                        break;
+                    }
+                    Err(e) => {
+                        error!("IO error reading from stdin: {}", e);
+                        break;
+                    }
+                },
+                Some(result) = in_flight.next(), if !in_flight.is_empty() => {
+                    if let Err(e) = result {
                         error!("Error processing message: {}", e);
                     }
                 }
-                Ok(Err(e)) => {
-                    error!("IO error reading from stdin: {}", e);
-                    break;
-                }
-                Err(_) => {
-                    warn!("Read timeout exceeded, continuing...");
-                    continue;
-                }
             }
         }
 
+        // Let requests that are still running deliver their responses
+        while let Some(result) = in_flight.next().await {
+            if let Err(e) = result {
+                error!("Error processing message: {}", e);
+            }
+        }
+
+        drop(in_flight);
+        drop(outbox);
+        let _ = writer.await;
+
         debug!("Stdio transport stopped");
         Ok(())
     }
@@ -261,9 +288,9 @@ impl StdioTransport {
     /// Process a single message, handling both single requests and batch requests
     async fn process_message(
         &self,
-        line: &str,
-        handlers: &mut MCPHandlers,
-        stdout: &mut tokio::io::Stdout,
+        line: String,
+        handlers: &MCPHandlers,
+        outbox: &Outbox,
     ) -> Result<()> {
         let line = line.trim();
         if line.is_empty() {
@@ -298,17 +325,17 @@ impl StdioTransport {
                     return Ok(());
                 }
 
-                self.send_parse_error(stdout, None).await?;
+                self.send_parse_error(outbox, None).await?;
                 return Ok(());
             }
         };
 
         // Handle batch requests (array) vs single requests (object)
         if request.is_array() {
-            self.process_batch_request(&request, handlers, stdout)
+            self.process_batch_request(&request, handlers, outbox)
                 .await?;
         } else {
-            self.process_single_request(&request, handlers, stdout)
+            self.process_single_request(&request, handlers, outbox)
                 .await?;
         }
 
@@ -319,14 +346,14 @@ impl StdioTransport {
     async fn process_batch_request(
         &self,
         requests: &Value,
-        handlers: &mut MCPHandlers,
-        stdout: &mut tokio::io::Stdout,
+        handlers: &MCPHandlers,
+        outbox: &Outbox,
     ) -> Result<()> {
         let request_array = match requests.as_array() {
             Some(arr) if !arr.is_empty() => arr,
             _ => {
                 // Empty batch is invalid
-                self.send_invalid_request_error(stdout, None).await?;
+                self.send_invalid_request_error(outbox, None).await?;
                 return Ok(());
             }
         };
@@ -348,7 +375,7 @@ impl StdioTransport {
         // Send batch response (only if we have responses)
         if !responses.is_empty() {
             let batch_response = Value::Array(responses);
-            self.send_response(stdout, &batch_response).await?;
+            self.send_response(outbox, &batch_response).await?;
         }
 
         Ok(())
@@ -358,14 +385,14 @@ impl StdioTransport {
     async fn process_single_request(
         &self,
         request: &Value,
-        handlers: &mut MCPHandlers,
-        stdout: &mut tokio::io::Stdout,
+        handlers: &MCPHandlers,
+        outbox: &Outbox,
     ) -> Result<()> {
         if let Some(response) = self
             .process_single_request_internal(request, handlers)
             .await
         {
-            self.send_response(stdout, &response).await?;
+            self.send_response(outbox, &response).await?;
         }
         Ok(())
     }
@@ -374,7 +401,7 @@ impl StdioTransport {
     async fn process_single_request_internal(
         &self,
         request: &Value,
-        handlers: &mut MCPHandlers,
+        handlers: &MCPHandlers,
     ) -> Option<Value> {
         // Validate JSON-RPC structure according to specification
         if let Err(validation_error) = self.validate_jsonrpc_request(request) {
@@ -398,7 +425,9 @@ impl StdioTransport {
         // Handle notifications (no response needed) - proper JSON-RPC 2.0 notification detection
         if id.is_none() {
             debug!("Received JSON-RPC notification: {}", method);
-            self.handle_notification(method, request.get("params"))
+            let headers = self.extract_headers_from_request(request);
+            handlers
+                .handle_notification(method, request.get("params"), &headers)
                 .await;
             return None;
         }
@@ -414,6 +443,8 @@ impl StdioTransport {
         .await;
 
         match response {
+            // Cancelled requests get no response, as the client already gave up
+            Ok(resp) if is_cancelled_response(&resp) => None,
             Ok(resp) => Some(resp),
             Err(_) => {
                 error!("Request processing timeout for method: {}", method);
@@ -430,51 +461,15 @@ impl StdioTransport {
         }
     }
 
-    /// Handle JSON-RPC 2.0 notifications (no response expected)
-    async fn handle_notification(&self, method: &str, params: Option<&Value>) {
-        debug!(
-            "Processing notification: {} with params: {:?}",
-            method, params
-        );
-
-        match method {
-            "notifications/initialized" => {
-                debug!("Client initialized notification received");
-            }
-            "notifications/cancelled" => {
-                if let Some(params) = params {
-                    if let Some(request_id) = params.get("requestId") {
-                        debug!("Request cancellation notification: {:?}", request_id);
-                        // TODO: Implement request cancellation logic
-                    }
-                }
-            }
-            _ => {
-                debug!("Unknown notification method: {}", method);
-            }
-        }
-    }
-
-    /// Send a response to stdout
-    async fn send_response(&self, stdout: &mut tokio::io::Stdout, response: &Value) -> Result<()> {
-        let response_str = serde_json::to_string(response)?;
-        stdout.write_all(response_str.as_bytes()).await?;
-        stdout.write_all(b"\n").await?;
-        stdout.flush().await?;
-
-        debug!(
-            "Sent response: {}",
-            response_str.chars().take(200).collect::<String>()
-        );
-        Ok(())
+    /// Queue a response for the stdout writer
+    async fn send_response(&self, outbox: &Outbox, response: &Value) -> Result<()> {
+        outbox
+            .send(response.clone())
+            .map_err(|_| anyhow::anyhow!("stdout writer has stopped"))
     }
 
     /// Send a parse error response
-    async fn send_parse_error(
-        &self,
-        stdout: &mut tokio::io::Stdout,
-        id: Option<&Value>,
-    ) -> Result<()> {
+    async fn send_parse_error(&self, outbox: &Outbox, id: Option<&Value>) -> Result<()> {
         let error_response = serde_json::json!({
             "jsonrpc": "2.0",
             "id": id,
@@ -483,15 +478,11 @@ impl StdioTransport {
                 "message": "Parse error"
             }
         });
-        self.send_response(stdout, &error_response).await
+        self.send_response(outbox, &error_response).await
     }
 
     /// Send an invalid request error
-    async fn send_invalid_request_error(
-        &self,
-        stdout: &mut tokio::io::Stdout,
-        id: Option<&Value>,
-    ) -> Result<()> {
+    async fn send_invalid_request_error(&self, outbox: &Outbox, id: Option<&Value>) -> Result<()> {
         let error_response = serde_json::json!({
             "jsonrpc": "2.0",
             "id": id,
@@ -500,15 +491,11 @@ impl StdioTransport {
                 "message": "Invalid Request"
             }
         });
-        self.send_response(stdout, &error_response).await
+        self.send_response(outbox, &error_response).await
     }
 
     /// Send a timeout error
-    async fn send_timeout_error(
-        &self,
-        stdout: &mut tokio::io::Stdout,
-        id: Option<&Value>,
-    ) -> Result<()> {
+    async fn send_timeout_error(&self, outbox: &Outbox, id: Option<&Value>) -> Result<()> {
         let error_response = create_error_response_with_data(
             id,
             -32603,
@@ -518,13 +505,13 @@ impl StdioTransport {
                 "details": "Request processing timeout exceeded"
             })),
         );
-        self.send_response(stdout, &error_response).await
+        self.send_response(outbox, &error_response).await
     }
 
     /// Send an internal error response
     async fn send_internal_error(
         &self,
-        stdout: &mut tokio::io::Stdout,
+        outbox: &Outbox,
         id: Option<&Value>,
         details: &str,
     ) -> Result<()> {
@@ -537,50 +524,12 @@ impl StdioTransport {
                 "details": details
             })),
         );
-        self.send_response(stdout, &error_response).await
+        self.send_response(outbox, &error_response).await
     }
 
     /// Validate JSON-RPC request structure according to specification
     pub fn validate_jsonrpc_request(&self, request: &Value) -> Result<(), String> {
-        // Check required jsonrpc field
-        match request.get("jsonrpc") {
-            Some(version) => {
-                if version.as_str() != Some("2.0") {
-                    return Err("Invalid JSON-RPC version, must be '2.0'".to_string());
-                }
-            }
-            None => {
-                return Err("Missing required 'jsonrpc' field".to_string());
-            }
-        }
-
-        // Validate method field exists (for regular requests)
-        if request.get("method").is_none() {
-            return Err("Missing required 'method' field".to_string());
-        }
-
-        // Validate method is a string
-        if let Some(method) = request.get("method") {
-            if method.as_str().is_none() {
-                return Err("Method field must be a string".to_string());
-            }
-        }
-
-        // Validate id field if present (can be string, number, or null)
-        if let Some(id) = request.get("id") {
-            if !id.is_string() && !id.is_number() && !id.is_null() {
-                return Err("ID field must be a string, number, or null".to_string());
-            }
-        }
-
-        // Validate params field if present (must be object or array)
-        if let Some(params) = request.get("params") {
-            if !params.is_object() && !params.is_array() {
-                return Err("Params field must be an object or array".to_string());
-            }
-        }
-
-        Ok(())
+        validate_jsonrpc_message(request)
     }
 
     /// Extract headers from JSON-RPC request extensions or provide defaults for stdio
@@ -615,6 +564,117 @@ impl StdioTransport {
     }
 }
 
+/// Write queued messages to stdout, one JSON document per line
+async fn write_messages(mut outgoing: mpsc::UnboundedReceiver<Value>) {
+    let mut stdout = tokio::io::stdout();
+    while let Some(message) = outgoing.recv().await {
+        let line = match serde_json::to_string(&message) {
+            Ok(line) => line,
+            Err(e) => {
+                error!("Failed to serialize outgoing message: {}", e);
+                continue;
+            }
+        };
+
+        let written = async {
+            stdout.write_all(line.as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await
+        }
+        .await;
+
+        if let Err(e) = written {
+            error!("Failed to write to stdout: {}", e);
+            break;
+        }
+
+        debug!(
+            "Sent message: {}",
+            line.chars().take(200).collect::<String>()
+        );
+    }
+}
+
+/// Forward server-side events to the client as JSON-RPC notifications
+fn forward_notifications<T, F>(
+    mut events: broadcast::Receiver<T>,
+    outbox: Outbox,
+    to_notification: F,
+) where
+    T: Clone + Send + 'static,
+    F: Fn(&T) -> Value + Send + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if outbox.send(to_notification(&event)).is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Dropped {} notifications for a slow client", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+/// Whether a response reports a request aborted via `notifications/cancelled`
+pub fn is_cancelled_response(response: &Value) -> bool {
+    response
+        .get("error")
+        .and_then(|e| e.get("code"))
+        .and_then(|c| c.as_i64())
+        == Some(REQUEST_CANCELLED_CODE as i64)
+}
+
+/// Validate JSON-RPC request structure according to specification.
+///
+/// Shared by the stdio and HTTP transports.
+pub fn validate_jsonrpc_message(request: &Value) -> Result<(), String> {
+    // Check required jsonrpc field
+    match request.get("jsonrpc") {
+        Some(version) => {
+            if version.as_str() != Some("2.0") {
+                return Err("Invalid JSON-RPC version, must be '2.0'".to_string());
+            }
+        }
+        None => {
+            return Err("Missing required 'jsonrpc' field".to_string());
+        }
+    }
+
+    // Validate method field exists (for regular requests)
+    if request.get("method").is_none() {
+        return Err("Missing required 'method' field".to_string());
+    }
+
+    // Validate method is a string
+    if let Some(method) = request.get("method") {
+        if method.as_str().is_none() {
+            return Err("Method field must be a string".to_string());
+        }
+    }
+
+    // Validate id field if present (can be string, number, or null)
+    if let Some(id) = request.get("id") {
+        if !id.is_string() && !id.is_number() && !id.is_null() {
+            return Err("ID field must be a string, number, or null".to_string());
+        }
+    }
+
+    // Validate params field if present (must be object or array)
+    if let Some(params) = request.get("params") {
+        if !params.is_object() && !params.is_array() {
+            return Err("Params field must be an object or array".to_string());
+        }
+    }
+
+    Ok(())
+}
+
 /// Helper function to create JSON-RPC error responses with optional data
 pub fn create_error_response(id: Option<&Value>, code: i32, message: &str) -> Value {
     create_error_response_with_data(id, code, message, None)
@@ -774,6 +834,23 @@ mod tests {
         assert_eq!(response["content"][0]["text"], "Test message");
     }
 
+    #[test]
+    fn test_cancelled_responses_are_detected() {
+        let cancelled = create_error_response(
+            Some(&serde_json::json!(3)),
+            REQUEST_CANCELLED_CODE,
+            "Request cancelled",
+        );
+        assert!(is_cancelled_response(&cancelled));
+
+        let other = create_error_response(Some(&serde_json::json!(3)), -32603, "Internal error");
+        assert!(!is_cancelled_response(&other));
+        assert!(!is_cancelled_response(&create_success_response(
+            Some(&serde_json::json!(3)),
+            serde_json::json!({})
+        )));
+    }
+
     #[tokio::test]
     async fn test_transport_creation() {
         let transport = StdioTransport::new(5000).unwrap();
@@ -87,6 +87,11 @@ impl AutoTieringEngine {
                     importance_score: Some(new_importance),
                     metadata: None,
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: Some("auto-tiering".to_string()),
+                    reason: Some("Importance recalculated".to_string()),
+                    content_hash: None,
                 };
                 self.repository
                     .update_memory(memory.id, update_request)
@@ -147,6 +152,11 @@ impl AutoTieringEngine {
                     importance_score: None,
                     metadata: None,
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 };
                 self.repository
                     .update_memory(memory.id, update_request)
@@ -41,6 +41,7 @@ use super::models::*;
 use super::reflection_engine::{Insight, ReflectionConfig, ReflectionEngine, ReflectionSession};
 use super::repository::MemoryRepository;
 
+use crate::embedding::SimpleEmbedder;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -225,11 +226,13 @@ impl BackgroundReflectionService {
         repository: Arc<MemoryRepository>,
         reflection_config: ReflectionConfig,
         loop_prevention_config: super::insight_loop_prevention::LoopPreventionConfig,
+        embedder: Option<Arc<SimpleEmbedder>>,
     ) -> Self {
-        let reflection_engine = Arc::new(RwLock::new(ReflectionEngine::new(
-            reflection_config,
-            repository.clone(),
-        )));
+        let mut reflection_engine = ReflectionEngine::new(reflection_config, repository.clone());
+        if let Some(embedder) = embedder {
+            reflection_engine = reflection_engine.with_embedder(embedder);
+        }
+        let reflection_engine = Arc::new(RwLock::new(reflection_engine));
 
         let loop_prevention_engine = Arc::new(RwLock::new(LoopPreventionEngine::new(
             loop_prevention_config,
@@ -742,6 +745,7 @@ impl BackgroundReflectionService {
             metadata: Some(metadata),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         self.repository.create_memory(create_request).await
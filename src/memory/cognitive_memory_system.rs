@@ -26,7 +26,7 @@
 //! ```rust
 //! use codex::memory::CognitiveMemorySystem;
 //!
-//! let mut system = CognitiveMemorySystem::new(repository).await?;
+//! let mut system = CognitiveMemorySystem::new(repository, config, Some(embedder)).await?;
 //!
 //! // Store memory with cognitive enhancement
 //! let memory = system.store_memory_with_cognitive_processing(
@@ -58,9 +58,11 @@ use super::insight_loop_prevention::{
 use super::models::*;
 use super::reflection_engine::{Insight, ReflectionConfig, ReflectionEngine, ReflectionSession};
 use super::repository::MemoryRepository;
+use super::storage::MemoryStorage;
 use super::three_component_scoring::{
     EnhancedSearchService, ScoringContext, ThreeComponentConfig, ThreeComponentEngine,
 };
+use crate::embedding::SimpleEmbedder;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -187,6 +189,9 @@ pub struct CognitiveFlags {
 /// Main cognitive memory system orchestrator
 pub struct CognitiveMemorySystem {
     repository: Arc<MemoryRepository>,
+    storage: Arc<dyn MemoryStorage>,
+    /// Whether `storage` is another backend than `repository`
+    external_storage: bool,
     config: CognitiveMemoryConfig,
 
     // Cognitive engines
@@ -204,10 +209,12 @@ pub struct CognitiveMemorySystem {
 }
 
 impl CognitiveMemorySystem {
-    /// Create a new cognitive memory system
+    /// Create a new cognitive memory system; `embedder` lets reflection
+    /// match knowledge graph concepts by meaning
     pub async fn new(
         repository: Arc<MemoryRepository>,
         config: CognitiveMemoryConfig,
+        embedder: Option<Arc<SimpleEmbedder>>,
     ) -> Result<Self> {
         info!("Initializing Cognitive Memory System with enhanced features");
 
@@ -215,10 +222,12 @@ impl CognitiveMemorySystem {
         let scoring_engine = ThreeComponentEngine::new(config.scoring_config.clone())?;
         let consolidation_engine =
             CognitiveConsolidationEngine::new(config.consolidation_config.clone());
-        let reflection_engine = Arc::new(RwLock::new(ReflectionEngine::new(
-            config.reflection_config.clone(),
-            repository.clone(),
-        )));
+        let mut reflection_engine =
+            ReflectionEngine::new(config.reflection_config.clone(), repository.clone());
+        if let Some(embedder) = &embedder {
+            reflection_engine = reflection_engine.with_embedder(embedder.clone());
+        }
+        let reflection_engine = Arc::new(RwLock::new(reflection_engine));
         let loop_prevention_engine = Arc::new(RwLock::new(LoopPreventionEngine::new(
             config.loop_prevention_config.clone(),
         )));
@@ -230,6 +239,7 @@ impl CognitiveMemorySystem {
             repository.clone(),
             config.reflection_config.clone(),
             config.loop_prevention_config.clone(),
+            embedder,
         ));
 
         let performance_metrics = Arc::new(RwLock::new(CognitivePerformanceMetrics::default()));
@@ -239,6 +249,8 @@ impl CognitiveMemorySystem {
         info!("Cognitive Memory System initialized successfully");
 
         let system = Self {
+            storage: repository.clone(),
+            external_storage: false,
             repository,
             config,
             scoring_engine,
@@ -261,6 +273,15 @@ impl CognitiveMemorySystem {
         Ok(system)
     }
 
+    /// Store and search memories through another storage backend. The
+    /// reflection engines keep using the PostgreSQL repository, and immediate
+    /// consolidation, which logs to it, is skipped for such memories.
+    pub fn with_storage(mut self, storage: Arc<dyn MemoryStorage>) -> Self {
+        self.storage = storage;
+        self.external_storage = true;
+        self
+    }
+
     /// Store memory with full cognitive processing
     pub async fn store_memory_with_cognitive_processing(
         &self,
@@ -280,9 +301,10 @@ impl CognitiveMemorySystem {
             metadata: request.metadata.clone(),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
-        let mut memory = self.repository.create_memory(create_request).await?;
+        let mut memory = self.storage.create_memory(create_request).await?;
 
         // Apply three-component scoring
         let scoring_context = ScoringContext {
@@ -305,37 +327,38 @@ impl CognitiveMemorySystem {
         }
 
         // Apply cognitive consolidation if enabled
-        let consolidation_result = if request.enable_immediate_consolidation {
-            let similar_memories = self.find_similar_memories(&memory, 10).await?;
-
-            match self
-                .consolidation_engine
-                .calculate_cognitive_consolidation(
-                    &memory,
-                    &request.retrieval_context,
-                    &similar_memories,
-                )
-                .await
-            {
-                Ok(result) => {
-                    self.consolidation_engine
-                        .apply_consolidation_results(&mut memory, &result, &self.repository)
-                        .await?;
-                    cognitive_flags.consolidation_applied = true;
-                    debug!(
-                        "Cognitive consolidation applied: strength {:.3}",
-                        result.new_consolidation_strength
-                    );
-                    Some(result)
-                }
-                Err(e) => {
-                    warn!("Consolidation failed: {}", e);
-                    None
+        let consolidation_result =
+            if request.enable_immediate_consolidation && !self.external_storage {
+                let similar_memories = self.find_similar_memories(&memory, 10).await?;
+
+                match self
+                    .consolidation_engine
+                    .calculate_cognitive_consolidation(
+                        &memory,
+                        &request.retrieval_context,
+                        &similar_memories,
+                    )
+                    .await
+                {
+                    Ok(result) => {
+                        self.consolidation_engine
+                            .apply_consolidation_results(&mut memory, &result, &self.repository)
+                            .await?;
+                        cognitive_flags.consolidation_applied = true;
+                        debug!(
+                            "Cognitive consolidation applied: strength {:.3}",
+                            result.new_consolidation_strength
+                        );
+                        Some(result)
+                    }
+                    Err(e) => {
+                        warn!("Consolidation failed: {}", e);
+                        None
+                    }
                 }
-            }
-        } else {
-            None
-        };
+            } else {
+                None
+            };
 
         // Quality assessment if enabled
         let quality_assessment = if request.enable_quality_assessment {
@@ -472,7 +495,7 @@ impl CognitiveMemorySystem {
         };
 
         // Perform base search
-        let search_response = self.repository.search_memories(search_request).await?;
+        let search_response = self.storage.search_memories(search_request).await?;
 
         // Apply cognitive ranking
         let enhanced_results =
@@ -683,7 +706,7 @@ impl CognitiveMemorySystem {
             ..Default::default()
         };
 
-        let search_response = self.repository.search_memories(search_request).await?;
+        let search_response = self.storage.search_memories(search_request).await?;
 
         Ok(search_response
             .results
@@ -728,7 +751,7 @@ mod tests {
         let repository = create_test_repository().await;
         let config = CognitiveMemoryConfig::default();
 
-        let system = CognitiveMemorySystem::new(repository, config).await;
+        let system = CognitiveMemorySystem::new(repository, config, None).await;
         assert!(system.is_ok());
     }
 
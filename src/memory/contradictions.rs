@@ -0,0 +1,668 @@
+//! Contradictions between stored facts
+//!
+//! When a memory is stored, its closest semantic neighbors are checked for
+//! statements it contradicts ("Alice lives in Berlin" / "Alice lives in
+//! Lisbon"). Every contradiction is recorded as a conflict: corrections
+//! supersede the fact they contradict right away, other conflicts stay open
+//! until resolved as superseded or both valid. Searches leave out superseded
+//! facts unless asked for them, so the current fact is the one returned.
+
+use super::error::{MemoryError, Result};
+use super::models::{Memory, SearchRequest, SearchType};
+use super::storage::MemoryStorage;
+use crate::embedding::EmbeddingService;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// State of a conflict between two memories
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStatus {
+    /// Detected and waiting for a resolution
+    Open,
+    /// One memory replaces the other, which searches leave out
+    Superseded,
+    /// The memories do not actually contradict each other
+    BothValid,
+}
+
+impl ConflictStatus {
+    pub const ALL: [ConflictStatus; 3] = [
+        ConflictStatus::Open,
+        ConflictStatus::Superseded,
+        ConflictStatus::BothValid,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictStatus::Open => "open",
+            ConflictStatus::Superseded => "superseded",
+            ConflictStatus::BothValid => "both_valid",
+        }
+    }
+}
+
+impl std::str::FromStr for ConflictStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        ConflictStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == name)
+            .ok_or_else(|| format!("Invalid conflict status: {s}"))
+    }
+}
+
+/// Two memories that state contradicting facts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryConflict {
+    pub id: Uuid,
+    pub namespace: String,
+    /// Memory whose storage revealed the conflict
+    pub memory_id: Uuid,
+    /// Earlier memory it contradicts
+    pub conflicting_memory_id: Uuid,
+    pub status: ConflictStatus,
+    /// Memory replaced by the other one, set when superseded
+    pub superseded_memory_id: Option<Uuid>,
+    pub reason: String,
+    pub confidence: f64,
+    /// Checker that found the contradiction
+    pub detector: String,
+    pub resolved_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl MemoryConflict {
+    /// The memory of the conflict that is not `memory_id`
+    pub fn other_memory(&self, memory_id: Uuid) -> Uuid {
+        if self.memory_id == memory_id {
+            self.conflicting_memory_id
+        } else {
+            self.memory_id
+        }
+    }
+
+    /// Memory that remains current once the conflict is superseded
+    pub fn current_memory_id(&self) -> Option<Uuid> {
+        self.superseded_memory_id.map(|id| self.other_memory(id))
+    }
+}
+
+/// Conflict to record between a new memory and an earlier one
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewMemoryConflict {
+    pub memory_id: Uuid,
+    pub conflicting_memory_id: Uuid,
+    pub status: ConflictStatus,
+    pub superseded_memory_id: Option<Uuid>,
+    pub reason: String,
+    pub confidence: f64,
+    pub detector: String,
+}
+
+/// Resolution of an open conflict
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolveConflictRequest {
+    pub conflict_id: Uuid,
+    /// `Superseded` or `BothValid`; `Open` reopens the conflict
+    pub status: ConflictStatus,
+    /// Memory that stays current when superseding; defaults to the newer one
+    pub current_memory_id: Option<Uuid>,
+    pub resolved_by: Option<String>,
+}
+
+impl ResolveConflictRequest {
+    /// Memory to leave out of searches once the resolution is applied
+    pub fn superseded_memory_id(&self, conflict: &MemoryConflict) -> Result<Option<Uuid>> {
+        if self.status != ConflictStatus::Superseded {
+            return Ok(None);
+        }
+        let current = self.current_memory_id.unwrap_or(conflict.memory_id);
+        if current != conflict.memory_id && current != conflict.conflicting_memory_id {
+            return Err(MemoryError::InvalidRequest {
+                message: format!(
+                    "Memory {current} is not part of conflict {}",
+                    conflict.id
+                ),
+            });
+        }
+        Ok(Some(conflict.other_memory(current)))
+    }
+}
+
+/// Why a checker considers two memories contradictory
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContradictionVerdict {
+    pub reason: String,
+    pub confidence: f64,
+}
+
+/// Decides whether a new memory contradicts an existing one
+#[async_trait]
+pub trait ContradictionChecker: Send + Sync {
+    /// Name recorded as the conflict's detector
+    fn name(&self) -> &'static str;
+
+    /// The contradiction between `new` and `existing`, if any
+    async fn check(&self, new: &Memory, existing: &Memory) -> Result<Option<ContradictionVerdict>>;
+}
+
+/// Verbs a subject can only have one value for at a time, with their
+/// inflections: "Alice lives in Berlin" contradicts "Alice lives in Lisbon"
+const SINGLE_VALUED_VERBS: &[(&str, &[&str])] = &[
+    ("lives in", &["lives in", "live in", "lived in"]),
+    ("works at", &["works at", "work at", "worked at"]),
+    ("prefers", &["prefers", "prefer"]),
+    ("is set to", &["is set to", "are set to"]),
+    ("defaults to", &["defaults to", "default to"]),
+    ("runs on", &["runs on", "run on"]),
+    ("is called", &["is called", "is named"]),
+];
+
+/// Verbs whose negation contradicts the plain statement: "the cache is
+/// enabled" / "the cache is not enabled"
+const NEGATABLE_VERBS: &[(&str, &[&str])] = &[
+    ("is", &["is", "are", "was", "were"]),
+    ("uses", &["uses", "use"]),
+    ("likes", &["likes", "like"]),
+    ("supports", &["supports", "support"]),
+    ("has", &["has", "have"]),
+];
+
+/// Words that flip a statement's meaning when swapped for each other
+const ANTONYMS: &[(&str, &str)] = &[
+    ("enabled", "disabled"),
+    ("true", "false"),
+    ("on", "off"),
+    ("always", "never"),
+    ("allowed", "forbidden"),
+    ("like", "dislike"),
+    ("likes", "dislikes"),
+    ("love", "hate"),
+    ("loves", "hates"),
+    ("increase", "decrease"),
+    ("before", "after"),
+    ("sync", "async"),
+    ("public", "private"),
+];
+
+/// Negations that come before the verb
+const NEGATIONS_BEFORE_VERB: &[&str] = &[
+    "does not", "do not", "did not", "doesn't", "don't", "didn't", "no longer", "never", "not",
+];
+
+/// Openers that do not belong to a statement's subject
+const FILLER_PREFIXES: &[&str] = &["actually", "correction", "update", "note", "fyi", "btw"];
+
+/// One "subject verb value" statement of a sentence
+#[derive(Debug, Clone, PartialEq)]
+struct Claim {
+    subject: String,
+    verb: &'static str,
+    negated: bool,
+    value: String,
+}
+
+/// Contradiction rules over the statements two memories make
+pub struct RuleBasedChecker {
+    statement: Regex,
+}
+
+impl RuleBasedChecker {
+    pub fn new() -> Self {
+        let mut verbs: Vec<&str> = SINGLE_VALUED_VERBS
+            .iter()
+            .chain(NEGATABLE_VERBS)
+            .flat_map(|(_, forms)| forms.iter().copied())
+            .collect();
+        // Longest first so "is set to" wins over "is"
+        verbs.sort_by_key(|verb| std::cmp::Reverse(verb.len()));
+        let verbs = verbs
+            .iter()
+            .map(|verb| verb.replace(' ', r"\s+"))
+            .collect::<Vec<_>>()
+            .join("|");
+
+        let pattern = format!(
+            r"^(?P<subject>.+?)\s+(?P<verb>{verbs})\s+(?P<neg>(?:not|no longer|never)\s+)?(?P<value>.+)$"
+        );
+        Self {
+            statement: Regex::new(&pattern).expect("statement pattern is a valid regex"),
+        }
+    }
+
+    fn claims(&self, content: &str) -> Vec<Claim> {
+        sentences(content)
+            .iter()
+            .filter_map(|sentence| {
+                let captures = self.statement.captures(sentence)?;
+                let verb = canonical_verb(&captures["verb"])?;
+                // "We no longer use Redis": the negation precedes the verb
+                let (subject, negated_before) = strip_negation(&captures["subject"]);
+                let subject = normalize_phrase(subject);
+                // Value ends at the first clause break: "Lisbon, not Berlin"
+                let value = captures["value"]
+                    .split([',', ';', '('])
+                    .next()
+                    .unwrap_or_default()
+                    .split(" but ")
+                    .next()
+                    .unwrap_or_default();
+                let value = normalize_phrase(value);
+                (!subject.is_empty() && !value.is_empty()).then_some(Claim {
+                    subject,
+                    verb,
+                    negated: negated_before || captures.name("neg").is_some(),
+                    value,
+                })
+            })
+            .collect()
+    }
+
+    /// Contradiction between the statements of two texts
+    pub fn compare(&self, new: &str, existing: &str) -> Option<ContradictionVerdict> {
+        let new_claims = self.claims(new);
+        let existing_claims = self.claims(existing);
+
+        for a in &new_claims {
+            for b in existing_claims
+                .iter()
+                .filter(|b| b.subject == a.subject && b.verb == a.verb)
+            {
+                if a.value == b.value && a.negated != b.negated {
+                    return Some(ContradictionVerdict {
+                        reason: format!(
+                            "'{} {}' is both affirmed and negated",
+                            a.subject, a.verb
+                        ),
+                        confidence: 0.9,
+                    });
+                }
+                let single_valued = SINGLE_VALUED_VERBS.iter().any(|(verb, _)| *verb == a.verb);
+                if single_valued && !a.negated && !b.negated && a.value != b.value {
+                    return Some(ContradictionVerdict {
+                        reason: format!(
+                            "'{} {}' was '{}' and is now '{}'",
+                            a.subject, a.verb, b.value, a.value
+                        ),
+                        confidence: 0.75,
+                    });
+                }
+            }
+        }
+
+        for a in sentences(new) {
+            for b in sentences(existing) {
+                if let Some((x, y)) = antonym_swap(&a, &b) {
+                    return Some(ContradictionVerdict {
+                        reason: format!("'{y}' became '{x}' in an otherwise identical statement"),
+                        confidence: 0.8,
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Default for RuleBasedChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ContradictionChecker for RuleBasedChecker {
+    fn name(&self) -> &'static str {
+        "rules"
+    }
+
+    async fn check(&self, new: &Memory, existing: &Memory) -> Result<Option<ContradictionVerdict>> {
+        Ok(self.compare(&new.content, &existing.content))
+    }
+}
+
+/// Lowercased sentences without filler openers
+fn sentences(content: &str) -> Vec<String> {
+    content
+        .split(['.', '!', '?', '\n'])
+        .map(|sentence| {
+            let mut sentence = sentence.trim().to_lowercase();
+            for filler in FILLER_PREFIXES {
+                if let Some(rest) = sentence.strip_prefix(filler) {
+                    if rest.starts_with([',', ':', ' ']) {
+                        sentence = rest.trim_start_matches([',', ':', ' ']).to_string();
+                    }
+                }
+            }
+            sentence
+        })
+        .filter(|sentence| !sentence.is_empty())
+        .collect()
+}
+
+/// Subject without a trailing negation, and whether there was one
+fn strip_negation(subject: &str) -> (&str, bool) {
+    NEGATIONS_BEFORE_VERB
+        .iter()
+        .find_map(|negation| {
+            subject
+                .strip_suffix(negation)
+                .filter(|rest| rest.ends_with(char::is_whitespace))
+                .map(|rest| (rest.trim_end(), true))
+        })
+        .unwrap_or((subject, false))
+}
+
+fn canonical_verb(verb: &str) -> Option<&'static str> {
+    let verb = verb.split_whitespace().collect::<Vec<_>>().join(" ");
+    SINGLE_VALUED_VERBS
+        .iter()
+        .chain(NEGATABLE_VERBS)
+        .find(|(_, forms)| forms.contains(&verb.as_str()))
+        .map(|(canonical, _)| *canonical)
+}
+
+/// Words of a phrase without punctuation and leading articles
+fn normalize_phrase(phrase: &str) -> String {
+    let words: Vec<String> = phrase
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_string()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+    let start = words
+        .iter()
+        .position(|word| !matches!(word.as_str(), "the" | "a" | "an"))
+        .unwrap_or(words.len());
+    words[start..].join(" ")
+}
+
+/// The antonym pair `(new word, old word)` if the sentences differ only by
+/// one word swapped for its antonym
+fn antonym_swap(a: &str, b: &str) -> Option<(String, String)> {
+    let a_words: Vec<&str> = a.split_whitespace().collect();
+    let b_words: Vec<&str> = b.split_whitespace().collect();
+    if a_words.len() != b_words.len() || a_words.len() < 3 {
+        return None;
+    }
+
+    let mut differences = a_words.iter().zip(&b_words).filter(|(x, y)| x != y);
+    let (x, y) = differences.next()?;
+    if differences.next().is_some() {
+        return None;
+    }
+    let x = x.trim_matches(|c: char| !c.is_alphanumeric());
+    let y = y.trim_matches(|c: char| !c.is_alphanumeric());
+    ANTONYMS
+        .iter()
+        .any(|&(p, q)| (x == p && y == q) || (x == q && y == p))
+        .then(|| (x.to_string(), y.to_string()))
+}
+
+/// Store-time contradiction detection settings
+#[derive(Debug, Clone)]
+pub struct ContradictionConfig {
+    /// Minimum similarity of an existing memory to be checked
+    pub similarity_threshold: f32,
+    /// Number of nearest memories checked
+    pub max_candidates: usize,
+    /// Similarity above which a correction supersedes its nearest memory
+    /// even when no checker finds the contradiction
+    pub correction_similarity_threshold: f32,
+}
+
+impl Default for ContradictionConfig {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: 0.6,
+            max_candidates: 5,
+            correction_similarity_threshold: 0.85,
+        }
+    }
+}
+
+/// Checks newly stored memories against their semantic neighbors and
+/// records the contradictions found
+pub struct ContradictionDetector {
+    config: ContradictionConfig,
+    /// Tried in order; the first verdict wins
+    checkers: Vec<Arc<dyn ContradictionChecker>>,
+    embedder: Option<Arc<dyn EmbeddingService>>,
+}
+
+impl ContradictionDetector {
+    /// Detector using the rule-based checker only
+    pub fn new(config: ContradictionConfig) -> Self {
+        Self {
+            config,
+            checkers: vec![Arc::new(RuleBasedChecker::new())],
+            embedder: None,
+        }
+    }
+
+    /// Also ask `checker` when the earlier checkers find nothing
+    pub fn with_checker(mut self, checker: Arc<dyn ContradictionChecker>) -> Self {
+        self.checkers.push(checker);
+        self
+    }
+
+    /// Embed memories stored without an embedding to find their neighbors
+    pub fn with_embedder(mut self, embedder: Arc<dyn EmbeddingService>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Whether the memory corrects an earlier statement, which it then
+    /// supersedes instead of leaving the conflict open
+    pub fn is_correction(memory: &Memory) -> bool {
+        let pattern_type = memory.metadata.get("pattern_type").and_then(|t| t.as_str());
+        let flagged = memory
+            .metadata
+            .get("correction")
+            .and_then(|c| c.as_bool())
+            .unwrap_or(false);
+        flagged || pattern_type.is_some_and(|t| t.eq_ignore_ascii_case("correction"))
+    }
+
+    /// Check `memory` against its nearest neighbors and record a conflict
+    /// for each contradiction found
+    pub async fn detect<S: MemoryStorage + ?Sized>(
+        &self,
+        storage: &S,
+        memory: &Memory,
+    ) -> Result<Vec<MemoryConflict>> {
+        let embedding = match (&memory.embedding, &self.embedder) {
+            (Some(embedding), _) => embedding.as_slice().to_vec(),
+            (None, Some(embedder)) => embedder
+                .generate_embedding(&memory.content)
+                .await
+                .map_err(|e| MemoryError::EmbeddingGenerationError(e.to_string()))?,
+            (None, None) => {
+                debug!("Memory {} has no embedding; skipping contradiction check", memory.id);
+                return Ok(Vec::new());
+            }
+        };
+
+        let neighbors = storage
+            .search_memories(SearchRequest {
+                query_embedding: Some(embedding),
+                search_type: Some(SearchType::Semantic),
+                similarity_threshold: Some(self.config.similarity_threshold),
+                limit: Some(self.config.max_candidates as i32 + 1),
+                ..Default::default()
+            })
+            .await?
+            .results;
+
+        let correction = Self::is_correction(memory);
+        let mut conflicts = Vec::new();
+        for neighbor in neighbors.iter().filter(|n| n.memory.id != memory.id) {
+            let Some((verdict, detector)) = self
+                .check(memory, &neighbor.memory, correction, neighbor.similarity_score)
+                .await
+            else {
+                continue;
+            };
+
+            let (status, superseded) = if correction {
+                (ConflictStatus::Superseded, Some(neighbor.memory.id))
+            } else {
+                (ConflictStatus::Open, None)
+            };
+            let conflict = storage
+                .record_conflict(NewMemoryConflict {
+                    memory_id: memory.id,
+                    conflicting_memory_id: neighbor.memory.id,
+                    status,
+                    superseded_memory_id: superseded,
+                    reason: verdict.reason,
+                    confidence: verdict.confidence,
+                    detector: detector.to_string(),
+                })
+                .await?;
+            info!(
+                "Memory {} conflicts with {} ({})",
+                memory.id,
+                neighbor.memory.id,
+                conflict.status.as_str()
+            );
+            conflicts.push(conflict);
+        }
+        Ok(conflicts)
+    }
+
+    async fn check(
+        &self,
+        memory: &Memory,
+        existing: &Memory,
+        correction: bool,
+        similarity: f32,
+    ) -> Option<(ContradictionVerdict, &'static str)> {
+        for checker in &self.checkers {
+            match checker.check(memory, existing).await {
+                Ok(Some(verdict)) => return Some((verdict, checker.name())),
+                Ok(None) => {}
+                Err(e) => warn!("Contradiction checker {} failed: {}", checker.name(), e),
+            }
+        }
+
+        (correction && similarity >= self.config.correction_similarity_threshold).then(|| {
+            (
+                ContradictionVerdict {
+                    reason: "Corrected by a later statement".to_string(),
+                    confidence: similarity as f64,
+                },
+                "correction",
+            )
+        })
+    }
+}
+
+impl Default for ContradictionDetector {
+    fn default() -> Self {
+        Self::new(ContradictionConfig::default())
+    }
+}
+
+/// Ask the insights model whether two memories contradict each other
+#[cfg(feature = "codex-dreams")]
+#[async_trait]
+impl ContradictionChecker for crate::insights::ollama_client::OllamaClient {
+    fn name(&self) -> &'static str {
+        "llm"
+    }
+
+    async fn check(&self, new: &Memory, existing: &Memory) -> Result<Option<ContradictionVerdict>> {
+        self.check_contradiction(&existing.content, &new.content)
+            .await
+            .map(|verdict| {
+                verdict.map(|(reason, confidence)| ContradictionVerdict { reason, confidence })
+            })
+            .map_err(|e| MemoryError::OllamaError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verdict(new: &str, existing: &str) -> Option<ContradictionVerdict> {
+        RuleBasedChecker::new().compare(new, existing)
+    }
+
+    #[test]
+    fn test_single_valued_statements_conflict() {
+        let found = verdict(
+            "Actually, Alice lives in Lisbon, not Berlin.",
+            "Alice lives in Berlin.",
+        )
+        .expect("different cities contradict");
+        assert!(found.reason.contains("berlin"));
+
+        assert!(verdict("The team prefers tabs", "The team prefers spaces").is_some());
+        assert!(verdict("Alice lives in Berlin", "Alice lives in Berlin").is_none());
+        assert!(verdict("Alice lives in Berlin", "Bob lives in Lisbon").is_none());
+    }
+
+    #[test]
+    fn test_negation_and_antonyms_conflict() {
+        assert!(verdict("The cache is not enabled", "The cache is enabled").is_some());
+        assert!(verdict("Tracing is disabled in production", "Tracing is enabled in production").is_some());
+        // Multi-valued verbs only conflict by negation
+        assert!(verdict("We use Redis", "We use Postgres").is_none());
+        assert!(verdict("We no longer use Redis", "We use Redis").is_some());
+    }
+
+    #[test]
+    fn test_resolution_picks_superseded_memory() {
+        let conflict = MemoryConflict {
+            id: Uuid::new_v4(),
+            namespace: "default".to_string(),
+            memory_id: Uuid::new_v4(),
+            conflicting_memory_id: Uuid::new_v4(),
+            status: ConflictStatus::Open,
+            superseded_memory_id: None,
+            reason: String::new(),
+            confidence: 0.9,
+            detector: "rules".to_string(),
+            resolved_by: None,
+            created_at: Utc::now(),
+            resolved_at: None,
+        };
+        let mut request = ResolveConflictRequest {
+            conflict_id: conflict.id,
+            status: ConflictStatus::Superseded,
+            current_memory_id: None,
+            resolved_by: None,
+        };
+        assert_eq!(
+            request.superseded_memory_id(&conflict).unwrap(),
+            Some(conflict.conflicting_memory_id)
+        );
+
+        request.current_memory_id = Some(conflict.conflicting_memory_id);
+        assert_eq!(
+            request.superseded_memory_id(&conflict).unwrap(),
+            Some(conflict.memory_id)
+        );
+
+        request.current_memory_id = Some(Uuid::new_v4());
+        assert!(request.superseded_memory_id(&conflict).is_err());
+
+        request.status = ConflictStatus::BothValid;
+        assert_eq!(request.superseded_memory_id(&conflict).unwrap(), None);
+        assert_eq!("both_valid".parse(), Ok(ConflictStatus::BothValid));
+    }
+}
@@ -0,0 +1,433 @@
+//! Entities mentioned by memories
+//!
+//! Memory content is scanned for the people, projects, tools and places it
+//! talks about. Every mention is resolved to a canonical entity record of the
+//! namespace: first by alias ("pg", "Postgres" and "PostgreSQL" are all
+//! aliases of one tool), then by embedding similarity of the names, and
+//! otherwise a new entity is created. Memories are linked to the entities
+//! they mention, so searches can be restricted to a single entity.
+
+use super::error::Result;
+use super::models::Memory;
+use super::storage::MemoryStorage;
+use crate::embedding::EmbeddingService;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Minimum cosine similarity for a mention to join an entity whose aliases
+/// do not include it
+pub const ENTITY_SIMILARITY_THRESHOLD: f32 = 0.9;
+
+/// Kinds of entities memories are linked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityType {
+    Person,
+    Project,
+    Tool,
+    Place,
+}
+
+impl EntityType {
+    pub const ALL: [EntityType; 4] = [
+        EntityType::Person,
+        EntityType::Project,
+        EntityType::Tool,
+        EntityType::Place,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityType::Person => "person",
+            EntityType::Project => "project",
+            EntityType::Tool => "tool",
+            EntityType::Place => "place",
+        }
+    }
+}
+
+impl std::str::FromStr for EntityType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        EntityType::ALL
+            .into_iter()
+            .find(|entity_type| entity_type.as_str() == name)
+            .ok_or_else(|| format!("Invalid entity type: {s}"))
+    }
+}
+
+/// Canonical record of a person, project, tool or place
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entity {
+    pub id: Uuid,
+    pub namespace: String,
+    pub entity_type: EntityType,
+    pub canonical_name: String,
+    /// Every spelling the entity has been mentioned by, canonical name included
+    pub aliases: Vec<String>,
+    #[serde(skip)]
+    pub embedding: Option<Vec<f32>>,
+    /// Number of memories linked to the entity
+    pub mention_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An entity as written in one memory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityMention {
+    /// Name as it appears in the content
+    pub name: String,
+    pub entity_type: EntityType,
+    pub confidence: f64,
+    /// Embedding of the canonical name, used to match unknown aliases
+    #[serde(skip)]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl EntityMention {
+    /// Name the entity is recorded under if no entity exists yet
+    pub fn canonical_name(&self) -> String {
+        canonical_name(&self.name, self.entity_type)
+    }
+
+    /// Normalized aliases an existing entity may be recorded under
+    pub fn lookup_keys(&self) -> Vec<String> {
+        lookup_keys(&self.name)
+    }
+}
+
+/// Tools with well-known alternative names: canonical name and the
+/// normalized aliases that refer to it
+const KNOWN_TOOLS: &[(&str, &[&str])] = &[
+    (
+        "PostgreSQL",
+        &["postgresql", "postgres", "pg", "psql", "pgsql"],
+    ),
+    ("pgvector", &["pgvector"]),
+    ("SQLite", &["sqlite", "sqlite3"]),
+    ("MySQL", &["mysql"]),
+    ("Redis", &["redis"]),
+    ("MongoDB", &["mongodb", "mongo"]),
+    ("Kubernetes", &["kubernetes", "k8s", "kube"]),
+    ("Docker", &["docker"]),
+    ("Terraform", &["terraform"]),
+    ("Git", &["git"]),
+    ("GitHub", &["github"]),
+    ("GitLab", &["gitlab"]),
+    ("AWS", &["aws", "amazon web services"]),
+    ("Rust", &["rust", "rustlang"]),
+    ("Python", &["python", "python3"]),
+    ("JavaScript", &["javascript"]),
+    ("TypeScript", &["typescript"]),
+    ("Go", &["golang"]),
+    ("Node.js", &["node.js", "nodejs"]),
+    ("Tokio", &["tokio"]),
+    ("Ollama", &["ollama"]),
+    (
+        "Visual Studio Code",
+        &["vscode", "vs code", "visual studio code"],
+    ),
+];
+
+/// Capitalized words that start sentences or name things other than people
+const NOT_NAMES: &[&str] = &[
+    "I",
+    "It",
+    "He",
+    "She",
+    "We",
+    "They",
+    "You",
+    "This",
+    "That",
+    "The",
+    "A",
+    "An",
+    "My",
+    "Our",
+    "Their",
+    "His",
+    "Her",
+    "Everyone",
+    "Someone",
+    "Nobody",
+    "Today",
+    "Yesterday",
+    "Tomorrow",
+];
+
+/// Lowercased name with punctuation and repeated whitespace removed, used
+/// to compare aliases
+pub fn normalize_alias(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Known tool a name refers to
+fn known_tool(name: &str) -> Option<&'static str> {
+    let normalized = normalize_alias(name);
+    KNOWN_TOOLS
+        .iter()
+        .find(|(_, aliases)| aliases.contains(&normalized.as_str()))
+        .map(|(canonical, _)| *canonical)
+}
+
+/// Name a new entity is recorded under: the well-known name of a tool, or
+/// the mention with surrounding whitespace and punctuation removed
+pub fn canonical_name(name: &str, entity_type: EntityType) -> String {
+    if entity_type == EntityType::Tool {
+        if let Some(canonical) = known_tool(name) {
+            return canonical.to_string();
+        }
+    }
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string()
+}
+
+/// Normalized aliases to look an entity up by: the name itself and, for
+/// well-known tools, the canonical name
+pub fn lookup_keys(name: &str) -> Vec<String> {
+    let mut keys = vec![normalize_alias(name)];
+    if let Some(canonical) = known_tool(name) {
+        let canonical = normalize_alias(canonical);
+        if !keys.contains(&canonical) {
+            keys.push(canonical);
+        }
+    }
+    keys.retain(|key| !key.is_empty());
+    keys
+}
+
+/// Rule-based extraction of entity mentions from memory content
+pub struct EntityExtractor {
+    patterns: Vec<(EntityType, Regex, f64)>,
+}
+
+impl EntityExtractor {
+    pub fn new() -> Self {
+        let name = r"([A-Z][a-z]+(?:\s[A-Z][a-z]+)?)";
+        let rules = [
+            (
+                EntityType::Person,
+                format!(
+                    r"\b(?i:my\s+(?:friend|colleague|coworker|co-worker|teammate|boss|manager|partner|wife|husband|brother|sister|mother|father|mom|dad|client|mentor|lead))\s+{name}"
+                ),
+                0.85,
+            ),
+            (
+                EntityType::Person,
+                format!(
+                    r"\b(?i:talked to|spoke with|spoke to|met with|meeting with|working with|work with|paired with|pairing with|asked|told)\s+{name}"
+                ),
+                0.7,
+            ),
+            (
+                EntityType::Person,
+                r"\b([A-Z][a-z]+)\s+(?:said|says|told me|thinks|mentioned|suggested|prefers|wants|asked me)\b"
+                    .to_string(),
+                0.7,
+            ),
+            (
+                EntityType::Project,
+                r"\b[Pp]roject\s+([A-Z][\w.-]*)".to_string(),
+                0.85,
+            ),
+            (
+                EntityType::Project,
+                r"\b(?i:the)\s+([A-Z][\w.-]*)\s+(?:project|repo|repository|codebase|service)\b"
+                    .to_string(),
+                0.75,
+            ),
+            (
+                EntityType::Place,
+                format!(
+                    r"\b(?i:live in|lives in|living in|based in|moved to|located in|office in|visiting|travelling to|traveling to|flying to)\s+{name}"
+                ),
+                0.75,
+            ),
+        ];
+
+        let patterns = rules
+            .into_iter()
+            .map(|(entity_type, pattern, confidence)| {
+                let regex = Regex::new(&pattern).expect("entity patterns are valid regexes");
+                (entity_type, regex, confidence)
+            })
+            .collect();
+
+        Self { patterns }
+    }
+
+    /// Entities mentioned in `content`, each canonical entity at most once
+    pub fn extract(&self, content: &str) -> Vec<EntityMention> {
+        let mut mentions = Vec::new();
+        let mut seen = HashSet::new();
+        let mut push = |name: &str, entity_type: EntityType, confidence: f64| {
+            let key = (
+                entity_type,
+                normalize_alias(&canonical_name(name, entity_type)),
+            );
+            if !key.1.is_empty() && seen.insert(key) {
+                mentions.push(EntityMention {
+                    name: name.to_string(),
+                    entity_type,
+                    confidence,
+                    embedding: None,
+                });
+            }
+        };
+
+        // Well-known tools, by single words and word pairs
+        let words: Vec<&str> = content
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+            .collect();
+        for (index, word) in words.iter().enumerate() {
+            if let Some(pair) = words.get(index + 1).map(|next| format!("{word} {next}")) {
+                if known_tool(&pair).is_some() {
+                    push(&pair, EntityType::Tool, 0.9);
+                    continue;
+                }
+            }
+            if !word.is_empty() && known_tool(word).is_some() {
+                push(word, EntityType::Tool, 0.9);
+            }
+        }
+
+        for (entity_type, regex, confidence) in &self.patterns {
+            for captures in regex.captures_iter(content) {
+                let Some(found) = captures.get(1) else {
+                    continue;
+                };
+                let name = found.as_str();
+                let first_word = name.split_whitespace().next().unwrap_or_default();
+                if NOT_NAMES.contains(&first_word) || known_tool(name).is_some() {
+                    continue;
+                }
+                push(name, *entity_type, *confidence);
+            }
+        }
+
+        mentions
+    }
+}
+
+impl Default for EntityExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cosine similarity of two embeddings; 0.0 when their lengths differ
+pub fn embedding_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Extract the entities `memory` mentions, embed their canonical names with
+/// `embedder` if given, and link the memory to the resolved entities
+pub async fn index_memory_entities<S: MemoryStorage + ?Sized>(
+    storage: &S,
+    extractor: &EntityExtractor,
+    embedder: Option<&dyn EmbeddingService>,
+    memory: &Memory,
+) -> Result<Vec<Entity>> {
+    let mut mentions = extractor.extract(&memory.content);
+    if mentions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if let Some(embedder) = embedder {
+        for mention in &mut mentions {
+            match embedder.generate_embedding(&mention.canonical_name()).await {
+                Ok(embedding) => mention.embedding = Some(embedding),
+                Err(e) => warn!("Failed to embed entity '{}': {}", mention.name, e),
+            }
+        }
+    }
+
+    storage.link_memory_entities(memory.id, mentions).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(mentions: &[EntityMention], entity_type: EntityType) -> Vec<String> {
+        mentions
+            .iter()
+            .filter(|m| m.entity_type == entity_type)
+            .map(|m| m.canonical_name())
+            .collect()
+    }
+
+    #[test]
+    fn test_tool_aliases_are_canonicalized() {
+        for alias in ["Postgres", "PostgreSQL", "pg", "postgres,"] {
+            assert_eq!(canonical_name(alias, EntityType::Tool), "PostgreSQL");
+            assert!(lookup_keys(alias).contains(&"postgresql".to_string()));
+        }
+        assert_eq!(canonical_name("  Alice  ", EntityType::Person), "Alice");
+        assert_eq!(normalize_alias("Node.js"), "node.js");
+    }
+
+    #[test]
+    fn test_extracts_each_entity_type() {
+        let extractor = EntityExtractor::new();
+        let mentions = extractor.extract(
+            "My colleague Priya Patel moved the Atlas service from Postgres to pg 16 \
+             and k8s. Project Falcon ships next week; I moved to Lisbon.",
+        );
+
+        assert_eq!(names(&mentions, EntityType::Person), vec!["Priya Patel"]);
+        assert_eq!(
+            names(&mentions, EntityType::Tool),
+            vec!["PostgreSQL", "Kubernetes"]
+        );
+        let mut projects = names(&mentions, EntityType::Project);
+        projects.sort();
+        assert_eq!(projects, vec!["Atlas", "Falcon"]);
+        assert_eq!(names(&mentions, EntityType::Place), vec!["Lisbon"]);
+    }
+
+    #[test]
+    fn test_sentence_starts_are_not_people() {
+        let extractor = EntityExtractor::new();
+        let mentions = extractor.extract("This said nothing. I asked Tom about Redis.");
+        assert_eq!(names(&mentions, EntityType::Person), vec!["Tom"]);
+        assert_eq!(names(&mentions, EntityType::Tool), vec!["Redis"]);
+    }
+
+    #[test]
+    fn test_embedding_similarity() {
+        assert!((embedding_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(embedding_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(embedding_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!("Tool".parse::<EntityType>(), Ok(EntityType::Tool));
+    }
+}
@@ -70,7 +70,6 @@ pub enum MemoryError {
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
 
-    #[cfg(feature = "codex-dreams")]
     #[error("Embedding generation error: {0}")]
     EmbeddingGenerationError(String),
 
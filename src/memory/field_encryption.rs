@@ -0,0 +1,880 @@
+//! Field-level encryption of memories at rest
+//!
+//! Memory content and configured metadata keys are encrypted with AES-256-GCM
+//! under a data key, and data keys are stored wrapped by a master key looked
+//! up through `SecretsManager`. Rotating the master key re-wraps the data keys
+//! and leaves every memory's ciphertext as it is.
+//!
+//! `EncryptedMemoryStorage` applies the encryption around any
+//! `MemoryStorage`, so its callers only ever see plaintext. Content hashes
+//! are taken of the plaintext, so duplicate detection keeps working.
+//! Encryption is deterministic, with the nonce derived from the plaintext
+//! under a key of its own; equal values encrypt to equal ciphertexts, which
+//! is all it reveals. Embeddings are computed from the plaintext before it
+//! is stored and are kept unencrypted unless the embedding policy omits
+//! them, so semantic and temporal search keep working. Full-text and hybrid
+//! queries, and filters on encrypted metadata keys, are refused rather than
+//! matched against ciphertext; tag facets are empty while `tags` is
+//! encrypted.
+
+use super::contradictions::{
+    ConflictStatus, MemoryConflict, NewMemoryConflict, ResolveConflictRequest,
+};
+use super::entities::{Entity, EntityMention, EntityType};
+use super::error::{MemoryError, Result};
+use super::models::*;
+use super::pii_policy::VaultedPiiValue;
+use super::reflection_engine::RelationshipType;
+use super::relations::{LinkMemoriesRequest, MemoryRelation, RelationFilter};
+use super::repository::MemoryStatistics;
+use super::retention_job::LegalHold;
+use super::search_backend::{refinement_suggestions, MAX_SUGGESTIONS};
+use super::storage::MemoryStorage;
+use crate::security::envelope::{self, EnvelopeKey};
+use crate::security::{SecretsManager, SecurityError};
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+/// Secret holding the master key, looked up through `SecretsManager`
+pub const DEFAULT_MASTER_KEY_SECRET: &str = "CODEX_FIELD_ENCRYPTION_KEY";
+
+/// Marks an encrypted field: `enc:v1:<key id>:<base64 nonce and ciphertext>`
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// What is stored of the embeddings of encrypted memories
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddingPolicy {
+    /// Stored unencrypted, so semantic search keeps working
+    Keep,
+    /// Not stored; encrypted memories are only found by metadata and time
+    Omit,
+}
+
+impl std::str::FromStr for EmbeddingPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "keep" => Ok(EmbeddingPolicy::Keep),
+            "omit" => Ok(EmbeddingPolicy::Omit),
+            _ => Err(format!("Invalid embedding policy: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldEncryptionConfig {
+    /// Name of the secret holding the master key
+    pub master_key_secret: String,
+    /// Top-level metadata keys whose values are encrypted
+    pub metadata_keys: Vec<String>,
+    pub embeddings: EmbeddingPolicy,
+}
+
+impl Default for FieldEncryptionConfig {
+    fn default() -> Self {
+        Self {
+            master_key_secret: DEFAULT_MASTER_KEY_SECRET.to_string(),
+            metadata_keys: Vec::new(),
+            embeddings: EmbeddingPolicy::Keep,
+        }
+    }
+}
+
+impl FieldEncryptionConfig {
+    /// Read `CODEX_ENCRYPTED_METADATA_KEYS`, a comma-separated list of
+    /// metadata keys, and `CODEX_ENCRYPTED_EMBEDDINGS` (`keep` or `omit`)
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(keys) = std::env::var("CODEX_ENCRYPTED_METADATA_KEYS") {
+            config.metadata_keys = keys
+                .split(',')
+                .map(|key| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+                .collect();
+        }
+        if let Ok(policy) = std::env::var("CODEX_ENCRYPTED_EMBEDDINGS") {
+            match policy.parse() {
+                Ok(policy) => config.embeddings = policy,
+                Err(e) => tracing::warn!("Invalid CODEX_ENCRYPTED_EMBEDDINGS: {}", e),
+            }
+        }
+        config
+    }
+}
+
+/// Data key as stored, wrapped by the master key `master_key_id` identifies
+#[derive(Debug, Clone)]
+pub struct WrappedDataKey {
+    pub key_id: String,
+    pub wrapped_key: Vec<u8>,
+    pub master_key_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Unwrapped data keys and the fields they encrypt
+pub struct FieldEncryption {
+    config: FieldEncryptionConfig,
+    data_keys: HashMap<String, EnvelopeKey>,
+    /// Key new values are encrypted with, the newest one
+    active_key_id: String,
+}
+
+impl FieldEncryption {
+    /// Encryption keyed by the master key secret of `config`; `None` when
+    /// `secrets` does not have it, which leaves memories unencrypted
+    pub async fn load(
+        storage: &dyn MemoryStorage,
+        secrets: &SecretsManager,
+        config: FieldEncryptionConfig,
+    ) -> Result<Option<Self>> {
+        match secrets.get_secret(&config.master_key_secret).await {
+            Ok(secret) => Self::with_master_secret(storage, &secret, config)
+                .await
+                .map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Unwrap the stored data keys with the master key derived from
+    /// `secret`, creating the first data key if there is none
+    pub async fn with_master_secret(
+        storage: &dyn MemoryStorage,
+        secret: &str,
+        config: FieldEncryptionConfig,
+    ) -> Result<Self> {
+        let master_key = envelope::master_key(secret);
+        let mut stored = storage.encryption_keys().await?;
+        if stored.is_empty() {
+            let first = WrappedDataKey {
+                key_id: "1".to_string(),
+                wrapped_key: envelope::wrap_key(&master_key, &envelope::generate_key())
+                    .map_err(key_error)?,
+                master_key_id: master_key_id(&master_key),
+                created_at: Utc::now(),
+            };
+            // Another process may have added it first; either way, read back
+            // the key that was kept
+            storage.add_encryption_key(&first).await?;
+            stored = storage.encryption_keys().await?;
+            info!("Created the first data key for field encryption");
+        }
+
+        let mut data_keys = HashMap::new();
+        for key in &stored {
+            if key.master_key_id != master_key_id(&master_key) {
+                return Err(MemoryError::Configuration(format!(
+                    "Data key {} is wrapped by master key {}, not by the configured {}",
+                    key.key_id,
+                    key.master_key_id,
+                    master_key_id(&master_key)
+                )));
+            }
+            let data_key =
+                envelope::unwrap_key(&master_key, &key.wrapped_key).map_err(key_error)?;
+            data_keys.insert(key.key_id.clone(), data_key);
+        }
+        let active_key_id = stored
+            .iter()
+            .max_by_key(|key| key.created_at)
+            .map(|key| key.key_id.clone())
+            .ok_or_else(|| MemoryError::Configuration("No data key was stored".to_string()))?;
+
+        Ok(Self {
+            config,
+            data_keys,
+            active_key_id,
+        })
+    }
+
+    pub fn config(&self) -> &FieldEncryptionConfig {
+        &self.config
+    }
+
+    /// Re-wrap every data key under the master key derived from
+    /// `new_secret`, all or none. Stored memories are untouched; once this
+    /// returns, the old master key no longer unlocks anything. Returns the
+    /// number of data keys re-wrapped.
+    pub async fn rotate_master_key(
+        &self,
+        storage: &dyn MemoryStorage,
+        new_secret: &str,
+    ) -> Result<usize> {
+        let new_master_key = envelope::master_key(new_secret);
+        let rewrapped = self
+            .data_keys
+            .iter()
+            .map(|(key_id, data_key)| {
+                envelope::wrap_key(&new_master_key, data_key)
+                    .map(|wrapped| (key_id.clone(), wrapped))
+                    .map_err(key_error)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        storage
+            .rewrap_encryption_keys(&master_key_id(&new_master_key), &rewrapped)
+            .await?;
+        Ok(rewrapped.len())
+    }
+
+    /// `plaintext` of the named field, encrypted with the active data key
+    pub fn encrypt_field(&self, field: &str, plaintext: &str) -> Result<String> {
+        let data_key = &self.data_keys[&self.active_key_id];
+        let sealed = envelope::seal_deterministic(data_key, plaintext.as_bytes(), field.as_bytes())
+            .map_err(key_error)?;
+        Ok(format!(
+            "{ENCRYPTED_PREFIX}{}:{}",
+            self.active_key_id,
+            BASE64_STANDARD.encode(sealed)
+        ))
+    }
+
+    /// Plaintext of the named field; values stored before encryption was
+    /// enabled are returned as they are
+    pub fn decrypt_field(&self, field: &str, value: &str) -> Result<String> {
+        let Some(encrypted) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let invalid = |message: &str| MemoryError::InvalidData {
+            message: format!("Encrypted {field}: {message}"),
+        };
+        let (key_id, sealed) = encrypted
+            .split_once(':')
+            .ok_or_else(|| invalid("missing key id"))?;
+        let data_key = self
+            .data_keys
+            .get(key_id)
+            .ok_or_else(|| invalid(&format!("unknown data key {key_id}")))?;
+        let sealed = BASE64_STANDARD
+            .decode(sealed)
+            .map_err(|_| invalid("not base64"))?;
+        let plaintext = envelope::open(data_key, &sealed, field.as_bytes())
+            .map_err(|e| invalid(&e.to_string()))?;
+        String::from_utf8(plaintext).map_err(|_| invalid("not UTF-8"))
+    }
+
+    /// `metadata` with the values of the configured keys encrypted
+    pub fn encrypt_metadata(&self, metadata: Value) -> Result<Value> {
+        let Value::Object(mut object) = metadata else {
+            return Ok(metadata);
+        };
+        for key in &self.config.metadata_keys {
+            if let Some(value) = object.get_mut(key) {
+                let plaintext = serde_json::to_string(value)?;
+                *value = Value::String(self.encrypt_field(&metadata_field(key), &plaintext)?);
+            }
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// `metadata` with every encrypted value decrypted
+    pub fn decrypt_metadata(&self, metadata: Value) -> Result<Value> {
+        let Value::Object(mut object) = metadata else {
+            return Ok(metadata);
+        };
+        for (key, value) in object.iter_mut() {
+            if let Some(encrypted) = value.as_str().filter(|v| v.starts_with(ENCRYPTED_PREFIX)) {
+                let plaintext = self.decrypt_field(&metadata_field(key), encrypted)?;
+                *value = serde_json::from_str(&plaintext)?;
+            }
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Memory content as it is stored
+    pub fn encrypt_content(&self, plaintext: &str) -> Result<String> {
+        self.encrypt_field(CONTENT_FIELD, plaintext)
+    }
+
+    /// Plaintext of memory content read from storage
+    pub fn decrypt_content(&self, content: &str) -> Result<String> {
+        self.decrypt_field(CONTENT_FIELD, content)
+    }
+
+    /// Plaintext of a memory read from storage
+    pub fn decrypt_memory(&self, mut memory: Memory) -> Result<Memory> {
+        memory.content = self.decrypt_content(&memory.content)?;
+        memory.metadata = self.decrypt_metadata(memory.metadata)?;
+        Ok(memory)
+    }
+
+    fn decrypt_memories(&self, memories: Vec<Memory>) -> Result<Vec<Memory>> {
+        memories
+            .into_iter()
+            .map(|memory| self.decrypt_memory(memory))
+            .collect()
+    }
+
+    fn decrypt_revision(&self, mut revision: MemoryRevision) -> Result<MemoryRevision> {
+        revision.content = self.decrypt_field(CONTENT_FIELD, &revision.content)?;
+        revision.metadata = self.decrypt_metadata(revision.metadata)?;
+        Ok(revision)
+    }
+
+    /// Refuses searches that would match ciphertext: full-text and hybrid
+    /// queries over content, and filters on encrypted metadata keys
+    fn check_searchable(&self, request: &SearchRequest) -> Result<()> {
+        let has_query_text = request
+            .query_text
+            .as_deref()
+            .is_some_and(|text| !text.trim().is_empty());
+        match request.search_type {
+            Some(SearchType::FullText) => {
+                return Err(unsearchable("Full-text search over memory content"))
+            }
+            Some(SearchType::Hybrid) if has_query_text => {
+                return Err(unsearchable("Hybrid search over memory content"))
+            }
+            _ => {}
+        }
+
+        let encrypted_key = |key: &str| self.config.metadata_keys.iter().any(|k| k == key);
+        if let Some(Value::Object(filters)) = &request.metadata_filters {
+            if let Some(key) = filters.keys().find(|key| encrypted_key(key)) {
+                return Err(unsearchable(&format!("Filtering on metadata key '{key}'")));
+            }
+        }
+        if request.tags.as_ref().is_some_and(|tags| !tags.is_empty()) && encrypted_key("tags") {
+            return Err(unsearchable("Filtering on tags"));
+        }
+        Ok(())
+    }
+
+    fn encrypt_embedding(&self, embedding: Option<Vec<f32>>) -> Option<Vec<f32>> {
+        match self.config.embeddings {
+            EmbeddingPolicy::Keep => embedding,
+            EmbeddingPolicy::Omit => None,
+        }
+    }
+}
+
+const CONTENT_FIELD: &str = "content";
+
+fn metadata_field(key: &str) -> String {
+    format!("metadata.{key}")
+}
+
+/// Short fingerprint of a master key, stored with the keys it wraps
+fn master_key_id(master_key: &EnvelopeKey) -> String {
+    envelope::keyed_digest(master_key.as_slice(), &[b"codex-memory master key id"])[..16]
+        .to_string()
+}
+
+fn unsearchable(what: &str) -> MemoryError {
+    MemoryError::InvalidRequest {
+        message: format!(
+            "{what} is unavailable while it is encrypted at rest; use semantic or temporal search"
+        ),
+    }
+}
+
+fn key_error(e: SecurityError) -> MemoryError {
+    MemoryError::Configuration(format!("Field encryption key error: {e}"))
+}
+
+/// Storage that encrypts memory content and metadata on the way in and
+/// decrypts them on the way out
+#[derive(Clone)]
+pub struct EncryptedMemoryStorage {
+    inner: Arc<dyn MemoryStorage>,
+    encryption: Arc<FieldEncryption>,
+}
+
+impl EncryptedMemoryStorage {
+    pub fn new(inner: Arc<dyn MemoryStorage>, encryption: Arc<FieldEncryption>) -> Self {
+        Self { inner, encryption }
+    }
+}
+
+#[async_trait]
+impl MemoryStorage for EncryptedMemoryStorage {
+    fn backend_name(&self) -> &'static str {
+        self.inner.backend_name()
+    }
+
+    fn namespace(&self) -> &str {
+        self.inner.namespace()
+    }
+
+    fn with_namespace(&self, namespace: &str) -> Arc<dyn MemoryStorage> {
+        Arc::new(Self::new(
+            self.inner.with_namespace(namespace),
+            self.encryption.clone(),
+        ))
+    }
+
+    async fn create_memory(&self, mut request: CreateMemoryRequest) -> Result<Memory> {
+        request.content_hash = Some(Memory::calculate_content_hash(&request.content));
+        request.content = self
+            .encryption
+            .encrypt_field(CONTENT_FIELD, &request.content)?;
+        request.metadata = request
+            .metadata
+            .map(|metadata| self.encryption.encrypt_metadata(metadata))
+            .transpose()?;
+        request.embedding = self.encryption.encrypt_embedding(request.embedding);
+        let memory = self.inner.create_memory(request).await?;
+        self.encryption.decrypt_memory(memory)
+    }
+
+    async fn get_memory(&self, id: Uuid) -> Result<Memory> {
+        let memory = self.inner.get_memory(id).await?;
+        self.encryption.decrypt_memory(memory)
+    }
+
+    async fn update_memory(&self, id: Uuid, mut request: UpdateMemoryRequest) -> Result<Memory> {
+        request.content_hash = request
+            .content
+            .as_deref()
+            .map(Memory::calculate_content_hash);
+        request.content = request
+            .content
+            .map(|content| self.encryption.encrypt_field(CONTENT_FIELD, &content))
+            .transpose()?;
+        request.metadata = request
+            .metadata
+            .map(|metadata| self.encryption.encrypt_metadata(metadata))
+            .transpose()?;
+        request.embedding = self.encryption.encrypt_embedding(request.embedding);
+        let memory = self.inner.update_memory(id, request).await?;
+        self.encryption.decrypt_memory(memory)
+    }
+
+    async fn delete_memory(&self, id: Uuid) -> Result<()> {
+        self.inner.delete_memory(id).await
+    }
+
+    async fn search_memories(&self, request: SearchRequest) -> Result<SearchResponse> {
+        self.encryption.check_searchable(&request)?;
+        let query_text = request.query_text.clone();
+        let mut response = self.inner.search_memories(request).await?;
+
+        // Refinements the backend drew from ciphertext are replaced by ones
+        // drawn from the plaintext; suggestions from the query log are kept
+        let query_text = query_text.filter(|_| response.suggestions.is_some());
+        if let (Some(query_text), Some(suggestions)) = (&query_text, &mut response.suggestions) {
+            let ciphertext_refinements =
+                refinement_suggestions(query_text, &response.results, MAX_SUGGESTIONS);
+            suggestions.retain(|suggestion| !ciphertext_refinements.contains(suggestion));
+        }
+        for result in &mut response.results {
+            let memory = std::mem::take(&mut result.memory);
+            result.memory = self.encryption.decrypt_memory(memory)?;
+        }
+        if let (Some(query_text), Some(suggestions)) = (&query_text, &mut response.suggestions) {
+            for refinement in refinement_suggestions(query_text, &response.results, MAX_SUGGESTIONS)
+            {
+                if suggestions.len() >= MAX_SUGGESTIONS {
+                    break;
+                }
+                if !suggestions.contains(&refinement) {
+                    suggestions.push(refinement);
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    async fn get_memories_by_tier(
+        &self,
+        tier: MemoryTier,
+        limit: Option<i64>,
+    ) -> Result<Vec<Memory>> {
+        let memories = self.inner.get_memories_by_tier(tier, limit).await?;
+        self.encryption.decrypt_memories(memories)
+    }
+
+    async fn migrate_memory(
+        &self,
+        id: Uuid,
+        to_tier: MemoryTier,
+        reason: Option<String>,
+    ) -> Result<Memory> {
+        let memory = self.inner.migrate_memory(id, to_tier, reason).await?;
+        self.encryption.decrypt_memory(memory)
+    }
+
+    async fn freeze_memory(
+        &self,
+        memory_id: Uuid,
+        reason: Option<String>,
+    ) -> Result<FreezeMemoryResponse> {
+        self.inner.freeze_memory(memory_id, reason).await
+    }
+
+    async fn unfreeze_memory(
+        &self,
+        frozen_id: Uuid,
+        target_tier: Option<MemoryTier>,
+    ) -> Result<UnfreezeMemoryResponse> {
+        self.inner.unfreeze_memory(frozen_id, target_tier).await
+    }
+
+    async fn get_statistics(&self) -> Result<MemoryStatistics> {
+        self.inner.get_statistics().await
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        self.inner.health_check().await
+    }
+
+    async fn memory_history(&self, id: Uuid, limit: Option<i64>) -> Result<Vec<MemoryRevision>> {
+        self.inner
+            .memory_history(id, limit)
+            .await?
+            .into_iter()
+            .map(|revision| self.encryption.decrypt_revision(revision))
+            .collect()
+    }
+
+    async fn get_memory_revision(&self, id: Uuid, revision: i32) -> Result<MemoryRevision> {
+        let revision = self.inner.get_memory_revision(id, revision).await?;
+        self.encryption.decrypt_revision(revision)
+    }
+
+    async fn get_memories_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Memory>> {
+        let memories = self.inner.get_memories_by_ids(ids).await?;
+        self.encryption.decrypt_memories(memories)
+    }
+
+    async fn link_memories(&self, request: LinkMemoriesRequest) -> Result<MemoryRelation> {
+        self.inner.link_memories(request).await
+    }
+
+    async fn unlink_memories(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+        relation_type: Option<RelationshipType>,
+    ) -> Result<u64> {
+        self.inner
+            .unlink_memories(source_id, target_id, relation_type)
+            .await
+    }
+
+    async fn get_relations(
+        &self,
+        ids: &[Uuid],
+        filter: &RelationFilter,
+    ) -> Result<Vec<MemoryRelation>> {
+        self.inner.get_relations(ids, filter).await
+    }
+
+    async fn link_memory_entities(
+        &self,
+        memory_id: Uuid,
+        mentions: Vec<EntityMention>,
+    ) -> Result<Vec<Entity>> {
+        self.inner.link_memory_entities(memory_id, mentions).await
+    }
+
+    async fn find_entity(&self, name: &str) -> Result<Option<Entity>> {
+        self.inner.find_entity(name).await
+    }
+
+    async fn list_entities(
+        &self,
+        entity_type: Option<EntityType>,
+        limit: i64,
+    ) -> Result<Vec<Entity>> {
+        self.inner.list_entities(entity_type, limit).await
+    }
+
+    async fn get_memory_entities(&self, memory_id: Uuid) -> Result<Vec<Entity>> {
+        self.inner.get_memory_entities(memory_id).await
+    }
+
+    async fn record_conflict(&self, conflict: NewMemoryConflict) -> Result<MemoryConflict> {
+        self.inner.record_conflict(conflict).await
+    }
+
+    async fn list_conflicts(
+        &self,
+        status: Option<ConflictStatus>,
+        memory_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<MemoryConflict>> {
+        self.inner.list_conflicts(status, memory_id, limit).await
+    }
+
+    async fn resolve_conflict(&self, request: ResolveConflictRequest) -> Result<MemoryConflict> {
+        self.inner.resolve_conflict(request).await
+    }
+
+    async fn pii_subject_key(&self, subject_id: &str, new_key: &[u8]) -> Result<Vec<u8>> {
+        self.inner.pii_subject_key(subject_id, new_key).await
+    }
+
+    async fn vault_pii_value(
+        &self,
+        token: &str,
+        subject_id: &str,
+        kind: &str,
+        ciphertext: &[u8],
+    ) -> Result<()> {
+        self.inner
+            .vault_pii_value(token, subject_id, kind, ciphertext)
+            .await
+    }
+
+    async fn vaulted_pii_values(&self, token: &str) -> Result<Vec<VaultedPiiValue>> {
+        self.inner.vaulted_pii_values(token).await
+    }
+
+    async fn encryption_keys(&self) -> Result<Vec<WrappedDataKey>> {
+        self.inner.encryption_keys().await
+    }
+
+    async fn add_encryption_key(&self, key: &WrappedDataKey) -> Result<()> {
+        self.inner.add_encryption_key(key).await
+    }
+
+    async fn rewrap_encryption_keys(
+        &self,
+        master_key_id: &str,
+        wrapped_keys: &[(String, Vec<u8>)],
+    ) -> Result<()> {
+        self.inner
+            .rewrap_encryption_keys(master_key_id, wrapped_keys)
+            .await
+    }
+
+    async fn list_namespaces(&self) -> Result<Vec<String>> {
+        self.inner.list_namespaces().await
+    }
+
+    async fn memories_created_before(
+        &self,
+        before: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Memory>> {
+        let memories = self
+            .inner
+            .memories_created_before(before, after, limit)
+            .await?;
+        self.encryption.decrypt_memories(memories)
+    }
+
+    async fn purge_memory_history(&self, id: Uuid) -> Result<u64> {
+        self.inner.purge_memory_history(id).await
+    }
+
+    async fn unlink_memory_entities(&self, memory_id: Uuid) -> Result<u64> {
+        self.inner.unlink_memory_entities(memory_id).await
+    }
+
+    async fn forget_pii_values(&self, subject_id: &str, tokens: &[String]) -> Result<u64> {
+        self.inner.forget_pii_values(subject_id, tokens).await
+    }
+
+    async fn place_legal_hold(
+        &self,
+        id: Uuid,
+        reason: &str,
+        placed_by: Option<&str>,
+    ) -> Result<LegalHold> {
+        self.inner.place_legal_hold(id, reason, placed_by).await
+    }
+
+    async fn release_legal_hold(&self, id: Uuid) -> Result<bool> {
+        self.inner.release_legal_hold(id).await
+    }
+
+    async fn legal_holds(&self) -> Result<Vec<LegalHold>> {
+        self.inner.legal_holds().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemoryStorage;
+    use serde_json::json;
+
+    fn config() -> FieldEncryptionConfig {
+        FieldEncryptionConfig {
+            metadata_keys: vec!["customer".to_string()],
+            ..FieldEncryptionConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memories_are_encrypted_at_rest() {
+        let sqlite = SqliteMemoryStorage::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let encryption = FieldEncryption::with_master_secret(&sqlite, "master secret", config())
+            .await
+            .unwrap();
+        let storage = EncryptedMemoryStorage::new(Arc::new(sqlite.clone()), Arc::new(encryption));
+
+        let memory = storage
+            .create_memory(CreateMemoryRequest {
+                content: "Acme's renewal is due in March".to_string(),
+                metadata: Some(json!({"customer": {"name": "Acme"}, "source": "call"})),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(memory.content, "Acme's renewal is due in March");
+        assert_eq!(memory.metadata["customer"]["name"], "Acme");
+
+        let stored = sqlite.get_memory(memory.id).await.unwrap();
+        assert!(stored.content.starts_with(ENCRYPTED_PREFIX));
+        assert!(!stored.content.contains("Acme"));
+        assert!(!stored.metadata.to_string().contains("Acme"));
+        assert_eq!(stored.metadata["source"], "call");
+        assert_eq!(
+            stored.content_hash,
+            Memory::calculate_content_hash("Acme's renewal is due in March")
+        );
+
+        // Plaintext hashes keep duplicate detection working
+        let duplicate = storage
+            .create_memory(CreateMemoryRequest {
+                content: "Acme's renewal is due in March".to_string(),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(
+            duplicate,
+            Err(MemoryError::DuplicateContent { .. })
+        ));
+
+        let updated = storage
+            .update_memory(
+                memory.id,
+                UpdateMemoryRequest {
+                    content: Some("Acme renewed in April".to_string()),
+                    embedding: None,
+                    tier: None,
+                    importance_score: None,
+                    metadata: None,
+                    expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.content, "Acme renewed in April");
+        let history = storage.memory_history(memory.id, None).await.unwrap();
+        assert!(history
+            .iter()
+            .any(|revision| revision.content == "Acme's renewal is due in March"));
+    }
+
+    #[tokio::test]
+    async fn test_master_key_rotation_rewraps_data_keys() {
+        let sqlite = SqliteMemoryStorage::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let encryption = FieldEncryption::with_master_secret(&sqlite, "old secret", config())
+            .await
+            .unwrap();
+        let storage = EncryptedMemoryStorage::new(Arc::new(sqlite.clone()), Arc::new(encryption));
+        let memory = storage
+            .create_memory(CreateMemoryRequest {
+                content: "Contract value is 40k".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let ciphertext = sqlite.get_memory(memory.id).await.unwrap().content;
+
+        assert_eq!(
+            storage
+                .encryption
+                .rotate_master_key(&sqlite, "new secret")
+                .await
+                .unwrap(),
+            1
+        );
+        // The old master key no longer unlocks the data keys
+        assert!(
+            FieldEncryption::with_master_secret(&sqlite, "old secret", config())
+                .await
+                .is_err()
+        );
+
+        let rotated = FieldEncryption::with_master_secret(&sqlite, "new secret", config())
+            .await
+            .unwrap();
+        let storage = EncryptedMemoryStorage::new(Arc::new(sqlite.clone()), Arc::new(rotated));
+        assert_eq!(
+            storage.get_memory(memory.id).await.unwrap().content,
+            "Contract value is 40k"
+        );
+        // Rows were not re-encrypted
+        assert_eq!(
+            sqlite.get_memory(memory.id).await.unwrap().content,
+            ciphertext
+        );
+    }
+
+    #[tokio::test]
+    async fn test_searches_that_would_match_ciphertext_are_refused() {
+        let sqlite = SqliteMemoryStorage::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let encryption = FieldEncryption::with_master_secret(&sqlite, "master secret", config())
+            .await
+            .unwrap();
+        let storage = EncryptedMemoryStorage::new(Arc::new(sqlite), Arc::new(encryption));
+        storage
+            .create_memory(CreateMemoryRequest {
+                content: "Acme renewal call notes".to_string(),
+                metadata: Some(json!({"customer": "Acme"})),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        for search_type in [SearchType::FullText, SearchType::Hybrid] {
+            let refused = storage
+                .search_memories(SearchRequest {
+                    query_text: Some("renewal".to_string()),
+                    search_type: Some(search_type),
+                    ..Default::default()
+                })
+                .await;
+            assert!(matches!(refused, Err(MemoryError::InvalidRequest { .. })));
+        }
+        let refused = storage
+            .search_memories(SearchRequest {
+                search_type: Some(SearchType::Temporal),
+                metadata_filters: Some(json!({"customer": "Acme"})),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(refused, Err(MemoryError::InvalidRequest { .. })));
+
+        let response = storage
+            .search_memories(SearchRequest {
+                query_text: Some("renewal".to_string()),
+                search_type: Some(SearchType::Temporal),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            response.results[0].memory.content,
+            "Acme renewal call notes"
+        );
+        assert!(response
+            .suggestions
+            .unwrap_or_default()
+            .iter()
+            .all(|suggestion| !suggestion.contains("enc")));
+    }
+}
@@ -12,7 +12,7 @@ use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
 
@@ -166,9 +166,15 @@ pub struct ReferenceEmbedding {
     /// Name of the reference pattern
     pub name: String,
 
-    /// Pre-computed embedding vector
+    /// Pre-computed embedding vector; leave empty to embed `text` instead
+    #[serde(default)]
     pub embedding: Vec<f32>,
 
+    /// Reference text, embedded on first use through the embedding service
+    /// (and its persistent cache) when no vector is given
+    #[serde(default)]
+    pub text: Option<String>,
+
     /// Importance weight for this reference
     pub weight: f64,
 
@@ -1094,6 +1100,7 @@ pub struct ImportanceAssessmentPipeline {
     pattern_matcher: OptimizedPatternMatcher,
     embedding_service: Arc<dyn EmbeddingService>,
     embedding_cache: EmbeddingCache,
+    reference_vectors: OnceCell<Vec<Vec<f32>>>,
     circuit_breaker: CircuitBreaker,
     metrics: ImportanceAssessmentMetrics,
     http_client: reqwest::Client,
@@ -1126,12 +1133,41 @@ impl ImportanceAssessmentPipeline {
             pattern_matcher,
             embedding_service,
             embedding_cache,
+            reference_vectors: OnceCell::new(),
             circuit_breaker,
             metrics,
             http_client,
         })
     }
 
+    /// Reference vectors in config order, embedding text-only references once
+    async fn reference_vectors(&self) -> Result<&[Vec<f32>], ImportanceAssessmentError> {
+        let vectors = self
+            .reference_vectors
+            .get_or_try_init(|| async {
+                let mut vectors = Vec::with_capacity(self.config.stage2.reference_embeddings.len());
+                for reference in &self.config.stage2.reference_embeddings {
+                    let vector = match &reference.text {
+                        Some(text) if reference.embedding.is_empty() => self
+                            .embedding_service
+                            .generate_embedding(text)
+                            .await
+                            .map_err(|e| {
+                                ImportanceAssessmentError::Stage2Failed(format!(
+                                    "Failed to embed reference '{}': {e}",
+                                    reference.name
+                                ))
+                            })?,
+                        _ => reference.embedding.clone(),
+                    };
+                    vectors.push(vector);
+                }
+                Ok::<_, ImportanceAssessmentError>(vectors)
+            })
+            .await?;
+        Ok(vectors)
+    }
+
     /// Assess the importance of a memory content string
     pub async fn assess_importance(
         &self,
@@ -1395,9 +1431,16 @@ impl ImportanceAssessmentPipeline {
             let mut total_weighted_score = 0.0;
             let mut total_weight = 0.0;
 
-            for reference in &self.config.stage2.reference_embeddings {
+            let reference_vectors = self.reference_vectors().await?;
+            for (reference, reference_embedding) in self
+                .config
+                .stage2
+                .reference_embeddings
+                .iter()
+                .zip(reference_vectors)
+            {
                 let similarity =
-                    self.calculate_cosine_similarity(&content_embedding, &reference.embedding);
+                    self.calculate_cosine_similarity(&content_embedding, reference_embedding);
 
                 if similarity >= self.config.stage2.similarity_threshold {
                     let weighted_score = similarity as f64 * reference.weight;
@@ -289,6 +289,7 @@ impl ImportanceAssessmentConfigLoader {
                     .map(|r| ReferenceEmbedding {
                         name: r.name,
                         embedding: r.embedding,
+                        text: r.text,
                         weight: r.weight,
                         category: r.category,
                     })
@@ -360,6 +361,7 @@ impl ImportanceAssessmentConfigLoader {
                             .map(|r| ReferenceEmbeddingFile {
                                 name: r.name.clone(),
                                 embedding: r.embedding.clone(),
+                                text: r.text.clone(),
                                 weight: r.weight,
                                 category: r.category.clone(),
                             })
@@ -584,7 +586,10 @@ struct Stage2ConfigFile {
 #[derive(Debug, Serialize, Deserialize)]
 struct ReferenceEmbeddingFile {
     name: String,
+    #[serde(default)]
     embedding: Vec<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
     weight: f64,
     category: String,
 }
@@ -0,0 +1,162 @@
+//! Persistent storage for the reflection engine's knowledge graph
+//!
+//! Nodes and edges live in the `knowledge_nodes` and `knowledge_edges` tables
+//! from migration 003, scoped to a memory namespace by migration 021. The
+//! reflection engine loads the graph once and then writes every node and edge
+//! a session touches, so the graph grows incrementally and survives restarts.
+
+use super::error::{MemoryError, Result};
+use super::reflection_engine::{KnowledgeEdge, KnowledgeGraph, KnowledgeNode};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+/// Longest concept `knowledge_nodes.concept` holds
+const MAX_CONCEPT_LENGTH: usize = 500;
+
+/// Knowledge graph tables of one namespace
+#[derive(Clone)]
+pub struct KnowledgeGraphStore {
+    pool: PgPool,
+    namespace: String,
+}
+
+impl KnowledgeGraphStore {
+    pub fn new(pool: PgPool, namespace: impl Into<String>) -> Self {
+        Self {
+            pool,
+            namespace: namespace.into(),
+        }
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Every node of the namespace and the edges between them
+    pub async fn load(&self) -> Result<KnowledgeGraph> {
+        let mut graph = KnowledgeGraph::new();
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, concept, node_type, embedding, confidence, created_at
+            FROM knowledge_nodes
+            WHERE namespace = $1
+            "#,
+        )
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+        for row in rows {
+            let node_type: String = row.try_get("node_type")?;
+            let created_at: Option<DateTime<Utc>> = row.try_get("created_at")?;
+            graph.add_node(KnowledgeNode {
+                id: row.try_get("id")?,
+                concept: row.try_get("concept")?,
+                node_type: node_type
+                    .parse()
+                    .map_err(|message| MemoryError::InvalidData { message })?,
+                embedding: row.try_get("embedding")?,
+                confidence: row.try_get("confidence")?,
+                connections: Vec::new(),
+                created_at: created_at.unwrap_or_else(Utc::now),
+            });
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT e.source_node_id, e.target_node_id, e.relationship_type,
+                   e.strength, e.evidence_memories
+            FROM knowledge_edges e
+            JOIN knowledge_nodes s ON s.id = e.source_node_id
+            WHERE s.namespace = $1
+            "#,
+        )
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+        for row in rows {
+            let relationship_type: String = row.try_get("relationship_type")?;
+            let evidence_memories: Option<Vec<Uuid>> = row.try_get("evidence_memories")?;
+            graph.add_edge(
+                row.try_get("source_node_id")?,
+                KnowledgeEdge {
+                    target_node_id: row.try_get("target_node_id")?,
+                    relationship_type: relationship_type
+                        .parse()
+                        .map_err(|message| MemoryError::InvalidData { message })?,
+                    strength: row.try_get("strength")?,
+                    evidence_memories: evidence_memories.unwrap_or_default(),
+                },
+            );
+        }
+
+        Ok(graph)
+    }
+
+    /// Insert a node, or update the concept, embedding and confidence of the
+    /// node with the same id
+    pub async fn save_node(&self, node: &KnowledgeNode) -> Result<()> {
+        let concept: String = node.concept.chars().take(MAX_CONCEPT_LENGTH).collect();
+        let embedding_dimension = node.embedding.as_ref().map(|e| e.as_slice().len() as i32);
+
+        sqlx::query(
+            r#"
+            INSERT INTO knowledge_nodes (
+                id, namespace, concept, node_type, embedding, embedding_dimension,
+                confidence, created_at, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 'reflection_engine')
+            ON CONFLICT (id) DO UPDATE
+            SET concept = EXCLUDED.concept,
+                embedding = COALESCE(EXCLUDED.embedding, knowledge_nodes.embedding),
+                embedding_dimension = COALESCE(
+                    EXCLUDED.embedding_dimension, knowledge_nodes.embedding_dimension
+                ),
+                confidence = EXCLUDED.confidence,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(node.id)
+        .bind(&self.namespace)
+        .bind(concept)
+        .bind(node.node_type.as_str())
+        .bind(node.embedding.as_ref())
+        .bind(embedding_dimension)
+        .bind(node.confidence.clamp(0.0, 1.0))
+        .bind(node.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Insert an edge starting at `source_id`. An existing edge between the
+    /// same nodes with the same relationship keeps the higher strength and
+    /// gains the new evidence.
+    pub async fn save_edge(&self, source_id: Uuid, edge: &KnowledgeEdge) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO knowledge_edges (
+                source_node_id, target_node_id, relationship_type, strength, evidence_memories
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (source_node_id, target_node_id, relationship_type) DO UPDATE
+            SET strength = GREATEST(knowledge_edges.strength, EXCLUDED.strength),
+                evidence_memories = ARRAY(
+                    SELECT DISTINCT unnest(
+                        array_cat(knowledge_edges.evidence_memories, EXCLUDED.evidence_memories)
+                    )
+                ),
+                updated_at = NOW()
+            "#,
+        )
+        .bind(source_id)
+        .bind(edge.target_node_id)
+        .bind(edge.relationship_type.as_str())
+        .bind(edge.strength.clamp(0.0, 1.0))
+        .bind(&edge.evidence_memories)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -2,19 +2,31 @@ pub mod auto_tiering;
 pub mod compression;
 pub mod connection;
 pub mod consolidation_job;
+pub mod contradictions;
 pub mod enhanced_retrieval;
+pub mod entities;
 pub mod error;
+pub mod field_encryption;
 pub mod forgetting_job;
 pub mod importance_assessment;
 pub mod importance_assessment_config;
 pub mod math_engine;
 pub mod models;
+pub mod pii_policy;
+pub mod reembed_job;
+pub mod relations;
 pub mod repository;
+pub mod retention_job;
 pub mod search_backend;
+pub mod search_cursor;
 pub mod semantic_deduplication;
 pub mod simple_consolidation;
+pub mod sqlite_storage;
+pub mod storage;
 pub mod testing_effect;
 pub mod tier_manager;
+pub mod time_expressions;
+pub mod versioning;
 
 // Cognitive enhancement modules
 pub mod background_reflection_service;
@@ -22,6 +34,7 @@ pub mod cognitive_consolidation;
 pub mod cognitive_memory_system;
 pub mod event_triggers;
 pub mod insight_loop_prevention;
+pub mod knowledge_graph_store;
 pub mod reflection_engine;
 pub mod silent_harvester;
 pub mod three_component_scoring;
@@ -35,7 +48,15 @@ pub use consolidation_job::{
     spawn_consolidation_job, ConsolidationJob, ConsolidationJobConfig, ConsolidationJobResult,
     ConsolidationPerformanceMetrics,
 };
+pub use contradictions::{
+    ConflictStatus, ContradictionChecker, ContradictionConfig, ContradictionDetector,
+    MemoryConflict, RuleBasedChecker,
+};
+pub use entities::{index_memory_entities, Entity, EntityExtractor, EntityMention, EntityType};
 pub use error::MemoryError;
+pub use field_encryption::{
+    EmbeddingPolicy, EncryptedMemoryStorage, FieldEncryption, FieldEncryptionConfig, WrappedDataKey,
+};
 pub use forgetting_job::{
     spawn_forgetting_job, ForgettingBatchResult, ForgettingJob, ForgettingJobConfig,
     ForgettingJobResult, ForgettingPerformanceMetrics,
@@ -44,7 +65,24 @@ pub use math_engine::{MathEngine, MathEngineConfig, MemoryParameters};
 pub use models::{
     CreateMemoryRequest, Memory, MemoryStatus, MemoryTier, SearchRequest, SearchType,
 };
+pub use pii_policy::{PiiAction, PiiPolicyConfig, PiiScreening, PiiWritePolicy};
+pub use reembed_job::{ReembedJob, ReembedJobConfig, ReembedJobResult};
+pub use relations::{
+    LinkMemoriesRequest, MemoryNeighbor, MemoryRelation, RelationDirection, RelationFilter,
+    TraversalOptions,
+};
 pub use repository::MemoryRepository;
+pub use retention_job::{
+    spawn_retention_job, LegalHold, RetentionAction, RetentionJob, RetentionJobConfig,
+    RetentionReport, RetentionRule, RetentionRules,
+};
+pub use search_cursor::{CursorSigner, SearchCursor};
+pub use sqlite_storage::SqliteMemoryStorage;
+pub use storage::{connect_storage, MemoryStorage};
+pub use time_expressions::{
+    EventRelation, TimeExpression, TimeExpressionConfig, TimeExpressionParser, TimeRange,
+};
+pub use versioning::RevisionDiff;
 pub use simple_consolidation::{
     ConsolidationBatchResult, ConsolidationProcessor, SimpleConsolidationConfig,
     SimpleConsolidationEngine, SimpleConsolidationResult,
@@ -63,9 +101,10 @@ pub use insight_loop_prevention::{
     LoopDetectionResult, LoopPreventionConfig, LoopPreventionEngine, PreventionStatistics,
     QualityAssessment,
 };
+pub use knowledge_graph_store::KnowledgeGraphStore;
 pub use reflection_engine::{
-    Insight, InsightType, KnowledgeGraph, KnowledgeNode, MemoryCluster, ReflectionConfig,
-    ReflectionEngine, ReflectionSession,
+    GraphEdge, Insight, InsightType, KnowledgeGraph, KnowledgeNode, KnowledgePath,
+    KnowledgeSubgraph, MemoryCluster, ReflectionConfig, ReflectionEngine, ReflectionSession,
 };
 pub use three_component_scoring::{
     EnhancedSearchResult, EnhancedSearchService, ScoringContext, ScoringResult,
@@ -66,12 +66,49 @@ pub enum MemoryStatus {
     Deleted,
 }
 
+/// Namespace used when a caller does not select one
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// Maximum length of a namespace identifier (matches the column width)
+pub const MAX_NAMESPACE_LENGTH: usize = 255;
+
+/// Validate a namespace identifier such as `acme/api/reviewer`.
+///
+/// Namespaces are made of `/`-separated segments of ASCII letters, digits,
+/// `-`, `_` and `.`, so they are safe to log and to use in headers.
+pub fn validate_namespace(namespace: &str) -> Result<(), String> {
+    if namespace.is_empty() || namespace.len() > MAX_NAMESPACE_LENGTH {
+        return Err(format!(
+            "Namespace must be between 1 and {MAX_NAMESPACE_LENGTH} characters"
+        ));
+    }
+
+    let valid_segment = |segment: &str| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    if !namespace.split('/').all(valid_segment) {
+        return Err(format!("Invalid namespace: {namespace}"));
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct Memory {
     pub id: Uuid,
     pub content: String,
     pub content_hash: String,
+    /// Tenant/project/agent namespace the memory belongs to
+    pub namespace: String,
     pub embedding: Option<Vector>,
+    /// Model that produced `embedding`; `None` for vectors stored before model tracking
+    #[sqlx(default)]
+    pub embedding_model: Option<String>,
+    #[sqlx(default)]
+    pub embedding_dimension: Option<i32>,
     pub tier: MemoryTier,
     pub status: MemoryStatus,
     pub importance_score: f64,
@@ -82,6 +119,12 @@ pub struct Memory {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// When the memory's fact became true; `None` means from `created_at`
+    #[sqlx(default)]
+    pub valid_from: Option<DateTime<Utc>>,
+    /// When the fact stopped being true; `None` while it is still current
+    #[sqlx(default)]
+    pub valid_to: Option<DateTime<Utc>>,
     // Consolidation fields for memory decay and strengthening
     pub consolidation_strength: f64,
     pub decay_rate: f64,
@@ -107,11 +150,14 @@ impl Serialize for Memory {
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("Memory", 29)?;
+        let mut state = serializer.serialize_struct("Memory", 34)?;
         state.serialize_field("id", &self.id)?;
         state.serialize_field("content", &self.content)?;
         state.serialize_field("content_hash", &self.content_hash)?;
+        state.serialize_field("namespace", &self.namespace)?;
         state.serialize_field("embedding", &self.embedding.as_ref().map(|v| v.as_slice()))?;
+        state.serialize_field("embedding_model", &self.embedding_model)?;
+        state.serialize_field("embedding_dimension", &self.embedding_dimension)?;
         state.serialize_field("tier", &self.tier)?;
         state.serialize_field("status", &self.status)?;
         state.serialize_field("importance_score", &self.importance_score)?;
@@ -122,6 +168,8 @@ impl Serialize for Memory {
         state.serialize_field("created_at", &self.created_at)?;
         state.serialize_field("updated_at", &self.updated_at)?;
         state.serialize_field("expires_at", &self.expires_at)?;
+        state.serialize_field("valid_from", &self.valid_from)?;
+        state.serialize_field("valid_to", &self.valid_to)?;
         state.serialize_field("consolidation_strength", &self.consolidation_strength)?;
         state.serialize_field("decay_rate", &self.decay_rate)?;
         state.serialize_field("recall_probability", &self.recall_probability)?;
@@ -265,6 +313,16 @@ pub struct CreateMemoryRequest {
     pub metadata: Option<serde_json::Value>,
     pub parent_id: Option<Uuid>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// When the fact became true, if earlier or later than now
+    #[serde(default)]
+    pub valid_from: Option<DateTime<Utc>>,
+    /// When the fact stopped being true, for memories about the past
+    #[serde(default)]
+    pub valid_to: Option<DateTime<Utc>>,
+    /// Hash of the plaintext when `content` is stored encrypted, so duplicate
+    /// detection compares plaintexts; set by `EncryptedMemoryStorage` only
+    #[serde(skip)]
+    pub content_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -275,6 +333,57 @@ pub struct UpdateMemoryRequest {
     pub importance_score: Option<f64>,
     pub metadata: Option<serde_json::Value>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// New start of the memory's valid time
+    #[serde(default)]
+    pub valid_from: Option<DateTime<Utc>>,
+    /// New end of the memory's valid time, e.g. when a fact stops holding
+    #[serde(default)]
+    pub valid_to: Option<DateTime<Utc>>,
+    /// Who is making the change, recorded on the revision
+    #[serde(default)]
+    pub actor: Option<String>,
+    /// Why the change is made, recorded on the revision
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Hash of the plaintext when `content` is stored encrypted; set by
+    /// `EncryptedMemoryStorage` only
+    #[serde(skip)]
+    pub content_hash: Option<String>,
+}
+
+/// Reject a valid-time interval that ends before `valid_since`, the start
+/// of the memory's valid time
+pub fn validate_valid_time(
+    valid_since: DateTime<Utc>,
+    valid_to: Option<DateTime<Utc>>,
+) -> super::error::Result<()> {
+    match valid_to {
+        Some(valid_to) if valid_to <= valid_since => {
+            Err(super::error::MemoryError::InvalidRequest {
+                message: format!("valid_to ({valid_to}) must be after valid_from ({valid_since})"),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Immutable snapshot of a memory written on every update that changes its
+/// content, metadata or importance
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MemoryRevision {
+    pub id: Uuid,
+    pub memory_id: Uuid,
+    pub namespace: String,
+    /// 1 for the state before the first recorded update, then increasing
+    pub revision: i32,
+    pub content: String,
+    pub content_hash: String,
+    pub metadata: serde_json::Value,
+    pub importance_score: f64,
+    pub tier: MemoryTier,
+    pub actor: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -293,6 +402,13 @@ pub struct SearchRequest {
     pub importance_range: Option<RangeFilter<f32>>,
     pub metadata_filters: Option<serde_json::Value>,
     pub tags: Option<Vec<String>>,
+    /// Only memories linked to the entity with this name or alias
+    pub entity: Option<String>,
+    /// Also return memories superseded by a later, contradicting memory
+    pub include_superseded: Option<bool>,
+    /// Only memories whose fact held at this time, regardless of when they
+    /// were recorded
+    pub as_of: Option<DateTime<Utc>>,
 
     // Result configuration
     pub limit: Option<i32>,
@@ -305,12 +421,20 @@ pub struct SearchRequest {
     // Ranking configuration
     pub ranking_boost: Option<RankingBoost>,
     pub explain_score: Option<bool>,
+    /// Reciprocal rank fusion constant for hybrid search (default 60)
+    pub rrf_k: Option<f32>,
+
+    /// Data subject running the search, logged with the query so that
+    /// erasure and portability requests find it; set by the server only
+    #[serde(skip)]
+    pub subject_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Hash)]
 pub enum SearchType {
     Semantic,
     Temporal,
+    /// Semantic and full-text rankings fused with reciprocal rank fusion
     Hybrid,
     FullText,
 }
@@ -362,6 +486,20 @@ pub struct ScoreExplanation {
     pub access_frequency_contribution: f32,
     pub total_score: f32,
     pub factors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank_fusion: Option<RankFusionExplanation>,
+}
+
+/// Where a hybrid search result ranked in each retriever
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankFusionExplanation {
+    pub k: f32,
+    /// 1-based rank among semantic matches, if matched
+    pub semantic_rank: Option<usize>,
+    pub semantic_score: Option<f32>,
+    /// 1-based rank among full-text matches, if matched
+    pub fulltext_rank: Option<usize>,
+    pub fulltext_score: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -403,7 +541,10 @@ impl Default for Memory {
             id: Uuid::new_v4(),
             content: String::new(),
             content_hash: String::new(),
+            namespace: DEFAULT_NAMESPACE.to_string(),
             embedding: None,
+            embedding_model: None,
+            embedding_dimension: None,
             tier: MemoryTier::Working,
             status: MemoryStatus::Active,
             importance_score: 0.5,
@@ -414,6 +555,8 @@ impl Default for Memory {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             expires_at: None,
+            valid_from: None,
+            valid_to: None,
             consolidation_strength: 1.0,
             decay_rate: 1.0,
             recall_probability: None,
@@ -434,6 +577,16 @@ impl Default for Memory {
 }
 
 impl Memory {
+    /// Start of the memory's valid time
+    pub fn valid_since(&self) -> DateTime<Utc> {
+        self.valid_from.unwrap_or(self.created_at)
+    }
+
+    /// Whether the memory's fact held at `at`
+    pub fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        self.valid_since() <= at && self.valid_to.is_none_or(|valid_to| at < valid_to)
+    }
+
     pub fn calculate_content_hash(content: &str) -> String {
         use sha2::{Digest, Sha256};
         let mut hasher = Sha256::new();
@@ -0,0 +1,641 @@
+//! Write-time PII policy
+//!
+//! Content is screened with `PiiManager` before it is embedded and stored,
+//! so secrets pasted into a conversation never reach the vector store. Each
+//! namespace has an action: store the content but flag it, redact the
+//! matches, replace them with tokens whose values are kept in the PII vault,
+//! or reject the memory. Detections are recorded in the memory's `pii`
+//! metadata and reported as audit events; neither includes the matched text.
+//!
+//! Vaulted values are encrypted under a data key of their subject, the
+//! client that wrote them (or the namespace for anonymous writes), so erasing
+//! a subject's key makes their values unrecoverable. Tokens can be resolved
+//! back with [`PiiWritePolicy::reveal`] by callers allowed to see them.
+
+use super::error::{MemoryError, Result};
+use super::storage::MemoryStorage;
+use crate::security::{
+    data_subject, AuditConfig, AuditLogger, AuditSeverity, PiiConfig, PiiManager, PiiMatch,
+    PiiSeverity, PiiVaultCipher, SecretsManager, SecurityError,
+};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::warn;
+
+/// What happens to content in which PII is detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PiiAction {
+    /// Content is not scanned
+    Off,
+    /// Stored unchanged, with the detections recorded
+    Flag,
+    /// Matches are replaced with placeholders such as `[EMAIL]`
+    Redact,
+    /// Matches are replaced with tokens; the values are kept in the vault
+    Tokenize,
+    /// The memory is not stored
+    Reject,
+}
+
+impl PiiAction {
+    pub const ALL: [PiiAction; 5] = [
+        PiiAction::Off,
+        PiiAction::Flag,
+        PiiAction::Redact,
+        PiiAction::Tokenize,
+        PiiAction::Reject,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiAction::Off => "off",
+            PiiAction::Flag => "flag",
+            PiiAction::Redact => "redact",
+            PiiAction::Tokenize => "tokenize",
+            PiiAction::Reject => "reject",
+        }
+    }
+
+    /// Past tense recorded in metadata and audit events
+    fn outcome(&self) -> &'static str {
+        match self {
+            PiiAction::Off => "unchecked",
+            PiiAction::Flag => "flagged",
+            PiiAction::Redact => "redacted",
+            PiiAction::Tokenize => "tokenized",
+            PiiAction::Reject => "rejected",
+        }
+    }
+}
+
+impl std::str::FromStr for PiiAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        PiiAction::ALL
+            .into_iter()
+            .find(|action| action.as_str() == name)
+            .ok_or_else(|| format!("Invalid PII action: {s}"))
+    }
+}
+
+/// Actions by namespace
+#[derive(Debug, Clone)]
+pub struct PiiPolicyConfig {
+    /// Action for namespaces without one of their own
+    pub default_action: PiiAction,
+    /// Actions keyed by namespace; `acme/*` covers the `acme` subtree and
+    /// `*` every namespace. The most specific key wins.
+    pub namespaces: HashMap<String, PiiAction>,
+}
+
+impl Default for PiiPolicyConfig {
+    fn default() -> Self {
+        Self {
+            default_action: PiiAction::Off,
+            namespaces: HashMap::new(),
+        }
+    }
+}
+
+impl PiiPolicyConfig {
+    /// Read `CODEX_PII_POLICY`, the default action, and
+    /// `CODEX_PII_NAMESPACE_POLICIES`, a JSON object of namespaces to
+    /// actions, e.g. `{"acme/*": "redact"}`. Invalid values are logged and
+    /// ignored.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(action) = std::env::var("CODEX_PII_POLICY") {
+            match action.parse() {
+                Ok(action) => config.default_action = action,
+                Err(e) => warn!("Invalid CODEX_PII_POLICY: {}", e),
+            }
+        }
+        if let Ok(policies) = std::env::var("CODEX_PII_NAMESPACE_POLICIES") {
+            match serde_json::from_str::<HashMap<String, String>>(&policies) {
+                Ok(policies) => {
+                    for (namespace, action) in policies {
+                        match action.parse() {
+                            Ok(action) => {
+                                config.namespaces.insert(namespace, action);
+                            }
+                            Err(e) => warn!("Invalid PII action for '{}': {}", namespace, e),
+                        }
+                    }
+                }
+                Err(e) => warn!("Invalid CODEX_PII_NAMESPACE_POLICIES: {}", e),
+            }
+        }
+
+        config
+    }
+
+    fn tokenizes(&self) -> bool {
+        self.default_action == PiiAction::Tokenize
+            || self.namespaces.values().any(|a| *a == PiiAction::Tokenize)
+    }
+
+    /// Action applying to `namespace`
+    pub fn action_for(&self, namespace: &str) -> PiiAction {
+        self.namespaces
+            .iter()
+            .filter(|(pattern, _)| namespace_matches(pattern, namespace))
+            // An exact name beats any wildcard, a longer prefix a shorter one
+            .max_by_key(|(pattern, _)| (!pattern.ends_with('*'), pattern.len()))
+            .map(|(_, action)| *action)
+            .unwrap_or(self.default_action)
+    }
+}
+
+/// Whether a namespace key such as `acme/*` covers `namespace`
+pub(crate) fn namespace_matches(pattern: &str, namespace: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => {
+            namespace.starts_with(prefix) || Some(namespace) == prefix.strip_suffix('/')
+        }
+        None => pattern == namespace,
+    }
+}
+
+/// Content after screening, with what was detected
+#[derive(Debug, Clone)]
+pub struct PiiScreening {
+    /// Content to embed and store
+    pub content: String,
+    pub action: PiiAction,
+    /// Number of matches by pattern name
+    pub matches: BTreeMap<String, usize>,
+    /// Highest severity among the matches
+    pub severity: Option<PiiSeverity>,
+}
+
+impl PiiScreening {
+    fn clean(content: &str, action: PiiAction) -> Self {
+        Self {
+            content: content.to_string(),
+            action,
+            matches: BTreeMap::new(),
+            severity: None,
+        }
+    }
+
+    pub fn found_pii(&self) -> bool {
+        !self.matches.is_empty()
+    }
+
+    /// Value of the memory's `pii` metadata key, if anything was detected
+    pub fn metadata(&self) -> Option<Value> {
+        self.found_pii().then(|| {
+            json!({
+                "action": self.action.outcome(),
+                "matches": self.matches,
+                "severity": self.severity,
+            })
+        })
+    }
+
+    /// `metadata` with the detections recorded under `pii`
+    pub fn annotate(&self, metadata: Option<Value>) -> Option<Value> {
+        let Some(pii) = self.metadata() else {
+            return metadata;
+        };
+        let mut metadata = match metadata {
+            Some(metadata) if metadata.is_object() => metadata,
+            _ => json!({}),
+        };
+        metadata["pii"] = pii;
+        Some(metadata)
+    }
+
+    /// One-line summary such as `2 PII matches redacted (api_key, email)`
+    pub fn describe(&self) -> Option<String> {
+        self.found_pii().then(|| {
+            format!(
+                "{} PII matches {} ({})",
+                self.matches.values().sum::<usize>(),
+                self.action.outcome(),
+                self.matches.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+}
+
+/// Encrypted value kept under a vault token, with the wrapped data key of
+/// the subject it was vaulted for
+#[derive(Debug, Clone)]
+pub struct VaultedPiiValue {
+    pub subject_id: String,
+    pub kind: String,
+    pub ciphertext: Vec<u8>,
+    pub wrapped_key: Vec<u8>,
+}
+
+/// Screens content for PII by the policy of the namespace it is written to
+pub struct PiiWritePolicy {
+    config: PiiPolicyConfig,
+    manager: PiiManager,
+    cipher: PiiVaultCipher,
+    token_pattern: Regex,
+    audit_logger: Arc<AuditLogger>,
+}
+
+impl PiiWritePolicy {
+    pub fn new(config: PiiPolicyConfig, cipher: PiiVaultCipher) -> Self {
+        // The built-in patterns already cover the defaults' custom patterns
+        let pii_config = PiiConfig {
+            enabled: true,
+            detect_patterns: Vec::new(),
+            ..PiiConfig::default()
+        };
+        Self {
+            config,
+            manager: PiiManager::new(pii_config).expect("built-in PII patterns compile"),
+            cipher,
+            token_pattern: Regex::new(r"\[[A-Z][A-Z_]*_[0-9a-f]{12}\]").expect("valid token regex"),
+            audit_logger: Arc::new(
+                AuditLogger::new(AuditConfig::default()).expect("audit logger without storage"),
+            ),
+        }
+    }
+
+    /// Policy that screens nothing, for stores built without a configured one
+    pub fn disabled() -> Self {
+        Self::new(PiiPolicyConfig::default(), random_cipher())
+    }
+
+    /// Policy from the environment, with the vault keyed by
+    /// `CODEX_PII_VAULT_KEY`. Tokenizing without that key is refused: each
+    /// process would vault under a key of its own, lost on restart.
+    pub fn from_env() -> Result<Self> {
+        let config = PiiPolicyConfig::from_env();
+        let cipher = match PiiVaultCipher::from_env() {
+            Some(cipher) => cipher,
+            None if config.tokenizes() => {
+                return Err(MemoryError::Configuration(
+                    "CODEX_PII_VAULT_KEY must be set when a PII policy tokenizes".to_string(),
+                ))
+            }
+            // Nothing is vaulted, so the key is never used
+            None => random_cipher(),
+        };
+        Ok(Self::new(config, cipher))
+    }
+
+    /// Report detections through `audit_logger`
+    pub fn with_audit_logger(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    pub fn config(&self) -> &PiiPolicyConfig {
+        &self.config
+    }
+
+    /// Apply the policy of `storage`'s namespace to `content` written by
+    /// `actor`. Fails with `SafetyViolation` when the policy rejects it.
+    pub async fn screen(
+        &self,
+        storage: &dyn MemoryStorage,
+        content: &str,
+        actor: Option<&str>,
+    ) -> Result<PiiScreening> {
+        let namespace = storage.namespace();
+        let action = self.config.action_for(namespace);
+        if action == PiiAction::Off {
+            return Ok(PiiScreening::clean(content, action));
+        }
+
+        let found = self.manager.find_matches(content);
+        if found.is_empty() {
+            return Ok(PiiScreening::clean(content, action));
+        }
+
+        let mut matches = BTreeMap::new();
+        for m in &found {
+            *matches.entry(m.pattern_name.clone()).or_insert(0) += 1;
+        }
+        let screened = match action {
+            PiiAction::Off | PiiAction::Flag | PiiAction::Reject => content.to_string(),
+            PiiAction::Redact => replace_matches(content, &found, |m| {
+                PiiManager::placeholder(&m.pattern_name).to_string()
+            }),
+            PiiAction::Tokenize => {
+                let subject_id = &data_subject(actor, namespace);
+                let wrapped_key = storage
+                    .pii_subject_key(
+                        subject_id,
+                        &self.cipher.generate_wrapped_key().map_err(vault_error)?,
+                    )
+                    .await?;
+                for m in &found {
+                    let token = self
+                        .cipher
+                        .token(namespace, &m.pattern_name, &m.matched_text);
+                    let ciphertext = self
+                        .cipher
+                        .encrypt(&wrapped_key, &token, &m.matched_text)
+                        .map_err(vault_error)?;
+                    storage
+                        .vault_pii_value(&token, subject_id, &m.pattern_name, &ciphertext)
+                        .await?;
+                }
+                replace_matches(content, &found, |m| {
+                    self.cipher
+                        .token(namespace, &m.pattern_name, &m.matched_text)
+                })
+            }
+        };
+        let screening = PiiScreening {
+            content: screened,
+            action,
+            matches,
+            severity: Some(self.manager.max_match_severity(&found)),
+        };
+
+        self.audit(namespace, actor, &screening).await;
+        if action == PiiAction::Reject {
+            return Err(MemoryError::SafetyViolation {
+                message: format!(
+                    "content contains PII ({}), which namespace '{}' does not store",
+                    screening
+                        .matches
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    namespace
+                ),
+            });
+        }
+        Ok(screening)
+    }
+
+    /// `content` with the vault tokens of `storage`'s namespace replaced by
+    /// the values they stand for. Whether `actor` may see them is for the
+    /// caller to check; each reveal is audited. Tokens whose subjects have
+    /// been erased are left in place.
+    pub async fn reveal(
+        &self,
+        storage: &dyn MemoryStorage,
+        content: &str,
+        actor: Option<&str>,
+    ) -> Result<String> {
+        let mut revealed = String::with_capacity(content.len());
+        let mut kinds = BTreeMap::new();
+        let mut position = 0;
+        for token in self.token_pattern.find_iter(content) {
+            let value = storage
+                .vaulted_pii_values(token.as_str())
+                .await?
+                .into_iter()
+                .find_map(|vaulted| {
+                    self.cipher
+                        .decrypt(&vaulted.wrapped_key, token.as_str(), &vaulted.ciphertext)
+                        .ok()
+                        .map(|value| (vaulted.kind, value))
+                });
+            if let Some((kind, value)) = value {
+                revealed.push_str(&content[position..token.start()]);
+                revealed.push_str(&value);
+                position = token.end();
+                *kinds.entry(kind).or_insert(0) += 1;
+            }
+        }
+        revealed.push_str(&content[position..]);
+
+        if !kinds.is_empty() {
+            let details = HashMap::from([
+                ("namespace".to_string(), json!(storage.namespace())),
+                ("matches".to_string(), json!(kinds)),
+            ]);
+            self.audit_logger
+                .log_security_event("pii_revealed", AuditSeverity::High, actor, details)
+                .await;
+        }
+        Ok(revealed)
+    }
+
+    /// Vault tokens appearing in `content`
+    pub fn tokens(&self, content: &str) -> Vec<String> {
+        self.token_pattern
+            .find_iter(content)
+            .map(|token| token.as_str().to_string())
+            .collect()
+    }
+
+    /// `content` with every PII match replaced by a placeholder and every
+    /// vault token by the placeholder of its kind, whatever the namespace's
+    /// policy. Nothing in the result can be revealed again.
+    pub fn redact(&self, content: &str) -> String {
+        let detokenized = self
+            .token_pattern
+            .replace_all(content, |token: &regex::Captures| {
+                // `[EMAIL_3f9a0c1b2d4e]` becomes `[EMAIL]`
+                let token = &token[0];
+                format!("{}]", &token[..token.len() - TOKEN_SUFFIX_LEN])
+            });
+        let found = self.manager.find_matches(&detokenized);
+        replace_matches(&detokenized, &found, |m| {
+            PiiManager::placeholder(&m.pattern_name).to_string()
+        })
+    }
+
+    async fn audit(&self, namespace: &str, actor: Option<&str>, screening: &PiiScreening) {
+        let severity = match screening.severity {
+            Some(PiiSeverity::Critical) => AuditSeverity::Critical,
+            Some(PiiSeverity::High) => AuditSeverity::High,
+            Some(PiiSeverity::Medium) => AuditSeverity::Medium,
+            Some(PiiSeverity::Low) | None => AuditSeverity::Low,
+        };
+        let details = HashMap::from([
+            ("namespace".to_string(), json!(namespace)),
+            ("action".to_string(), json!(screening.action.outcome())),
+            ("matches".to_string(), json!(screening.matches)),
+            (
+                "match_count".to_string(),
+                json!(screening.matches.values().sum::<usize>()),
+            ),
+        ]);
+        self.audit_logger
+            .log_security_event("pii_detected", severity, actor, details)
+            .await;
+    }
+}
+
+/// Length of the `_<digest>]` ending a vault token
+const TOKEN_SUFFIX_LEN: usize = 14;
+
+/// Vault keys that fail to unwrap point at a changed `CODEX_PII_VAULT_KEY`
+fn vault_error(e: SecurityError) -> MemoryError {
+    MemoryError::Configuration(e.to_string())
+}
+
+/// `content` with each of the non-overlapping, ordered `matches` replaced
+fn replace_matches(
+    content: &str,
+    matches: &[PiiMatch],
+    mut replacement: impl FnMut(&PiiMatch) -> String,
+) -> String {
+    let mut replaced = String::with_capacity(content.len());
+    let mut position = 0;
+    for m in matches {
+        replaced.push_str(&content[position..m.start]);
+        replaced.push_str(&replacement(m));
+        position = m.end;
+    }
+    replaced.push_str(&content[position..]);
+    replaced
+}
+
+fn random_cipher() -> PiiVaultCipher {
+    PiiVaultCipher::new(&SecretsManager::generate_encryption_key())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemoryStorage;
+
+    const CONTENT: &str =
+        "Deploy with api_key: sk_live_abcdefghijklmnopqrstuv and ping ops@example.com";
+
+    fn policy(action: PiiAction) -> PiiWritePolicy {
+        PiiWritePolicy::new(
+            PiiPolicyConfig {
+                default_action: PiiAction::Off,
+                namespaces: HashMap::from([("acme/*".to_string(), action)]),
+            },
+            PiiVaultCipher::new("vault secret"),
+        )
+    }
+
+    #[test]
+    fn test_namespace_actions() {
+        let config = PiiPolicyConfig {
+            default_action: PiiAction::Flag,
+            namespaces: HashMap::from([
+                ("*".to_string(), PiiAction::Redact),
+                ("acme/*".to_string(), PiiAction::Tokenize),
+                ("acme/hr".to_string(), PiiAction::Reject),
+            ]),
+        };
+        assert_eq!(config.action_for("acme/hr"), PiiAction::Reject);
+        assert_eq!(config.action_for("acme/api"), PiiAction::Tokenize);
+        assert_eq!(config.action_for("acme"), PiiAction::Tokenize);
+        assert_eq!(config.action_for("other"), PiiAction::Redact);
+        assert_eq!(
+            PiiPolicyConfig::default().action_for("acme"),
+            PiiAction::Off
+        );
+        assert_eq!("Tokenize".parse::<PiiAction>(), Ok(PiiAction::Tokenize));
+        assert!("mask".parse::<PiiAction>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_screening_actions() {
+        let storage = SqliteMemoryStorage::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let acme = storage.with_namespace("acme/api");
+
+        let redacted = policy(PiiAction::Redact)
+            .screen(acme.as_ref(), CONTENT, None)
+            .await
+            .unwrap();
+        assert_eq!(redacted.content, "Deploy with [API_KEY] and ping [EMAIL]");
+        let metadata = redacted.annotate(None).unwrap();
+        assert_eq!(metadata["pii"]["action"], "redacted");
+        assert_eq!(metadata["pii"]["matches"]["email"], 1);
+        assert!(!metadata.to_string().contains("example.com"));
+
+        let flagged = policy(PiiAction::Flag)
+            .screen(acme.as_ref(), CONTENT, None)
+            .await
+            .unwrap();
+        assert_eq!(flagged.content, CONTENT);
+        assert!(flagged.found_pii());
+
+        let rejected = policy(PiiAction::Reject)
+            .screen(acme.as_ref(), CONTENT, None)
+            .await;
+        assert!(matches!(rejected, Err(MemoryError::SafetyViolation { .. })));
+
+        // Other namespaces fall back to the default action
+        let unchecked = policy(PiiAction::Reject)
+            .screen(&storage, CONTENT, None)
+            .await
+            .unwrap();
+        assert_eq!(unchecked.content, CONTENT);
+        assert!(unchecked.annotate(None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tokens_resolve_through_the_vault() {
+        let storage = SqliteMemoryStorage::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let acme = storage.with_namespace("acme/api");
+        let policy = policy(PiiAction::Tokenize);
+
+        let first = policy
+            .screen(acme.as_ref(), CONTENT, Some("alice"))
+            .await
+            .unwrap();
+        assert!(!first.content.contains("ops@example.com"));
+        let token = first
+            .content
+            .split_whitespace()
+            .find(|word| word.starts_with("[EMAIL_"))
+            .unwrap()
+            .to_string();
+        let vaulted = acme.vaulted_pii_values(&token).await.unwrap();
+        assert_eq!(vaulted.len(), 1);
+        assert_eq!(vaulted[0].subject_id, "alice");
+        assert!(!String::from_utf8_lossy(&vaulted[0].ciphertext).contains("example.com"));
+        assert_eq!(
+            policy
+                .reveal(acme.as_ref(), &first.content, Some("admin"))
+                .await
+                .unwrap(),
+            CONTENT
+        );
+        // Redaction drops tokens and plaintext matches alike
+        let redacted = "Deploy with [API_KEY] and ping [EMAIL]";
+        assert_eq!(policy.redact(&first.content), redacted);
+        assert_eq!(policy.redact(CONTENT), redacted);
+        // Tokens are namespaced
+        assert!(storage.vaulted_pii_values(&token).await.unwrap().is_empty());
+
+        // The same value keeps its token, whoever writes it
+        let second = policy
+            .screen(acme.as_ref(), "Mail ops@example.com again", Some("bob"))
+            .await
+            .unwrap();
+        assert_eq!(second.content, format!("Mail {token} again"));
+
+        // Anonymous writes are vaulted for the subject their memories carry
+        let anonymous = policy
+            .screen(acme.as_ref(), "Mail sales@example.com", None)
+            .await
+            .unwrap();
+        let token = anonymous.content.trim_start_matches("Mail ");
+        let vaulted = acme.vaulted_pii_values(token).await.unwrap();
+        assert_eq!(vaulted[0].subject_id, "anonymous:acme/api");
+
+        // Erasing a subject's key shreds only the values vaulted for them
+        sqlx::query("DELETE FROM pii_vault_keys WHERE subject_id = 'alice'")
+            .execute(storage.pool())
+            .await
+            .unwrap();
+        let revealed = policy
+            .reveal(acme.as_ref(), &first.content, None)
+            .await
+            .unwrap();
+        assert!(revealed.contains("ops@example.com"));
+        assert!(revealed.contains("[API_KEY_"));
+    }
+}
@@ -0,0 +1,391 @@
+//! Embedding Model Migration Job
+//!
+//! Switching embedding provider or model leaves existing vectors in a space
+//! that new query vectors cannot be compared with. This job rewrites the
+//! embeddings of every active memory not yet produced by the current model.
+//!
+//! Key features:
+//! - Batched through `SimpleEmbedder::generate_embeddings_batch`
+//! - Throttled with a configurable pause between batches
+//! - Resumable: a per-namespace checkpoint records the last rewritten memory
+//! - Visits every namespace that still holds stale embeddings
+
+use super::error::{MemoryError, Result};
+use super::field_encryption::{EmbeddingPolicy, FieldEncryption};
+use super::repository::{MemoryRepository, ReembedCandidate, ReembedProgress};
+use crate::embedding::SimpleEmbedder;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Configuration for the re-embed job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReembedJobConfig {
+    /// Memories embedded per call to the embedding provider
+    pub batch_size: usize,
+
+    /// Pause between batches to stay under provider rate limits
+    pub batch_delay_ms: u64,
+
+    /// Stop after this many batches; the next run resumes from the checkpoint
+    pub max_batches_per_run: Option<usize>,
+
+    /// Only migrate this namespace instead of every namespace
+    pub namespace: Option<String>,
+
+    /// Ignore existing checkpoints and start from the first memory
+    pub restart: bool,
+}
+
+impl Default for ReembedJobConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            batch_delay_ms: 250,
+            max_batches_per_run: None,
+            namespace: None,
+            restart: false,
+        }
+    }
+}
+
+/// Result of a re-embed run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReembedJobResult {
+    pub run_id: Uuid,
+    pub target_model: String,
+    pub target_dimension: usize,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    pub namespaces_processed: usize,
+    pub memories_reembedded: usize,
+    pub memories_failed: usize,
+    pub batches_processed: usize,
+    /// Memories still not embedded by the target model
+    pub memories_remaining: i64,
+    pub memories_per_second: f64,
+}
+
+impl ReembedJobResult {
+    pub fn is_complete(&self) -> bool {
+        self.memories_remaining == 0
+    }
+}
+
+/// Rewrites stale embeddings with the embedder's current model
+pub struct ReembedJob {
+    config: ReembedJobConfig,
+    repository: Arc<MemoryRepository>,
+    embedder: Arc<SimpleEmbedder>,
+    /// Decrypts content encrypted at rest before it is embedded
+    field_encryption: Option<Arc<FieldEncryption>>,
+    is_running: AtomicBool,
+}
+
+impl ReembedJob {
+    pub fn new(
+        config: ReembedJobConfig,
+        repository: Arc<MemoryRepository>,
+        embedder: Arc<SimpleEmbedder>,
+    ) -> Self {
+        Self {
+            config,
+            repository,
+            embedder,
+            field_encryption: None,
+            is_running: AtomicBool::new(false),
+        }
+    }
+
+    /// Embed the plaintext of memories encrypted at rest
+    pub fn with_field_encryption(mut self, encryption: Arc<FieldEncryption>) -> Self {
+        self.field_encryption = Some(encryption);
+        self
+    }
+
+    /// Model every memory is migrated to
+    pub fn target_model(&self) -> &str {
+        self.embedder.model()
+    }
+
+    /// Request the running job to stop after the current batch
+    pub fn stop(&self) {
+        info!("Stopping re-embed job");
+        self.is_running.store(false, Ordering::Relaxed);
+    }
+
+    /// Migrate stale embeddings until done, stopped or out of batches
+    pub async fn run(&self) -> Result<ReembedJobResult> {
+        if self.config.batch_size == 0 {
+            return Err(MemoryError::InvalidRequest {
+                message: "Re-embed batch size must be greater than zero".to_string(),
+            });
+        }
+        if let Some(encryption) = &self.field_encryption {
+            if encryption.config().embeddings == EmbeddingPolicy::Omit {
+                return Err(MemoryError::InvalidRequest {
+                    message: "Embeddings of encrypted memories are omitted, so there is nothing to re-embed".to_string(),
+                });
+            }
+        }
+        if self.is_running.swap(true, Ordering::Relaxed) {
+            return Err(MemoryError::InvalidRequest {
+                message: "Re-embed job is already running".to_string(),
+            });
+        }
+
+        let result = self.run_inner().await;
+        self.is_running.store(false, Ordering::Relaxed);
+        result
+    }
+
+    async fn run_inner(&self) -> Result<ReembedJobResult> {
+        let run_id = Uuid::new_v4();
+        let started_at = Utc::now();
+        let start_time = Instant::now();
+        let target_model = self.target_model().to_string();
+        let target_dimension = self.embedder.embedding_dimension();
+
+        info!(
+            "Starting re-embed run {} to {} ({} dimensions)",
+            run_id, target_model, target_dimension
+        );
+
+        if let Err(e) = self
+            .repository
+            .ensure_embedding_index(target_dimension)
+            .await
+        {
+            warn!(
+                "Could not create vector index for {} dimensions: {}",
+                target_dimension, e
+            );
+        }
+
+        let namespaces = match &self.config.namespace {
+            Some(namespace) => vec![namespace.clone()],
+            None => {
+                self.repository
+                    .get_namespaces_needing_reembed(&target_model)
+                    .await?
+            }
+        };
+
+        let mut reembedded = 0;
+        let mut failed = 0;
+        let mut batches_processed = 0;
+        let mut namespaces_processed = 0;
+        let mut remaining = 0;
+
+        for namespace in &namespaces {
+            let repository = self.repository.with_namespace(namespace.clone());
+            let mut progress = self
+                .load_progress(&repository, &target_model, target_dimension)
+                .await?;
+
+            while self.is_running.load(Ordering::Relaxed)
+                && self
+                    .config
+                    .max_batches_per_run
+                    .is_none_or(|max| batches_processed < max)
+            {
+                let candidates = repository
+                    .get_reembed_candidates(
+                        &target_model,
+                        progress.last_memory_id,
+                        self.config.batch_size as i64,
+                    )
+                    .await?;
+
+                let Some(last) = candidates.last() else {
+                    progress.completed_at = Some(Utc::now());
+                    break;
+                };
+                progress.last_memory_id = Some(last.id);
+
+                match self.reembed_batch(&repository, &candidates).await {
+                    Ok(updated) => {
+                        reembedded += updated;
+                        progress.memories_reembedded += updated as i64;
+                    }
+                    Err(e) => {
+                        // Skip past the batch; a later run started over picks it up again
+                        warn!(
+                            "Failed to re-embed {} memories in namespace {}: {}",
+                            candidates.len(),
+                            namespace,
+                            e
+                        );
+                        failed += candidates.len();
+                        progress.memories_failed += candidates.len() as i64;
+                    }
+                }
+
+                batches_processed += 1;
+                repository.save_reembed_progress(&progress).await?;
+                debug!(
+                    "Re-embed batch {} in namespace {}: {} memories rewritten so far",
+                    batches_processed, namespace, progress.memories_reembedded
+                );
+
+                if self.config.batch_delay_ms > 0 {
+                    tokio::time::sleep(Duration::from_millis(self.config.batch_delay_ms)).await;
+                }
+            }
+
+            repository.save_reembed_progress(&progress).await?;
+            remaining += repository.count_reembed_remaining(&target_model).await?;
+            namespaces_processed += 1;
+
+            if progress.completed_at.is_none() {
+                // Stopped or out of batches; later namespaces wait for the next run
+                break;
+            }
+        }
+
+        let elapsed = start_time.elapsed().as_secs_f64();
+        let result = ReembedJobResult {
+            run_id,
+            target_model,
+            target_dimension,
+            started_at,
+            completed_at: Utc::now(),
+            namespaces_processed,
+            memories_reembedded: reembedded,
+            memories_failed: failed,
+            batches_processed,
+            memories_remaining: remaining,
+            memories_per_second: if elapsed > 0.0 {
+                reembedded as f64 / elapsed
+            } else {
+                0.0
+            },
+        };
+
+        info!(
+            "Re-embed run {} finished: {} rewritten, {} failed, {} remaining",
+            run_id, result.memories_reembedded, result.memories_failed, result.memories_remaining
+        );
+        Ok(result)
+    }
+
+    /// Resume an unfinished checkpoint for the same dimension, otherwise start over
+    async fn load_progress(
+        &self,
+        repository: &MemoryRepository,
+        target_model: &str,
+        target_dimension: usize,
+    ) -> Result<ReembedProgress> {
+        let existing = repository.get_reembed_progress(target_model).await?;
+        let fresh = || {
+            let now = Utc::now();
+            ReembedProgress {
+                namespace: repository.namespace().to_string(),
+                target_model: target_model.to_string(),
+                target_dimension: target_dimension as i32,
+                last_memory_id: None,
+                memories_reembedded: 0,
+                memories_failed: 0,
+                started_at: now,
+                updated_at: now,
+                completed_at: None,
+            }
+        };
+
+        Ok(match existing {
+            Some(progress) if resumable(&progress, target_dimension, self.config.restart) => {
+                info!(
+                    "Resuming re-embed of namespace {} after memory {:?}",
+                    progress.namespace, progress.last_memory_id
+                );
+                progress
+            }
+            _ => fresh(),
+        })
+    }
+
+    async fn reembed_batch(
+        &self,
+        repository: &MemoryRepository,
+        candidates: &[ReembedCandidate],
+    ) -> Result<usize> {
+        let texts = candidates
+            .iter()
+            .map(|c| match &self.field_encryption {
+                Some(encryption) => encryption.decrypt_content(&c.content),
+                None => Ok(c.content.clone()),
+            })
+            .collect::<Result<Vec<String>>>()?;
+        let embeddings = self
+            .embedder
+            .generate_embeddings_batch(&texts)
+            .await
+            .map_err(|e| MemoryError::EmbeddingGenerationError(e.to_string()))?;
+
+        let expected = self.embedder.embedding_dimension();
+        if let Some(embedding) = embeddings.iter().find(|e| e.len() != expected) {
+            return Err(MemoryError::EmbeddingGenerationError(format!(
+                "Model {} returned {} dimensions, expected {}",
+                self.target_model(),
+                embedding.len(),
+                expected
+            )));
+        }
+
+        let pairs: Vec<(ReembedCandidate, Vec<f32>)> =
+            candidates.iter().cloned().zip(embeddings).collect();
+        repository
+            .apply_reembedded(self.target_model(), &pairs)
+            .await
+    }
+}
+
+/// A checkpoint is resumed only if it is unfinished and for the same dimension
+fn resumable(progress: &ReembedProgress, target_dimension: usize, restart: bool) -> bool {
+    !restart
+        && progress.completed_at.is_none()
+        && progress.target_dimension as usize == target_dimension
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(completed: bool, dimension: i32) -> ReembedProgress {
+        let now = Utc::now();
+        ReembedProgress {
+            namespace: "default".to_string(),
+            target_model: "nomic-embed-text".to_string(),
+            target_dimension: dimension,
+            last_memory_id: Some(Uuid::new_v4()),
+            memories_reembedded: 64,
+            memories_failed: 0,
+            started_at: now,
+            updated_at: now,
+            completed_at: completed.then_some(now),
+        }
+    }
+
+    #[test]
+    fn test_reembed_job_config_defaults() {
+        let config = ReembedJobConfig::default();
+
+        assert_eq!(config.batch_size, 32);
+        assert_eq!(config.batch_delay_ms, 250);
+        assert!(config.max_batches_per_run.is_none());
+        assert!(config.namespace.is_none());
+        assert!(!config.restart);
+    }
+
+    #[test]
+    fn test_checkpoint_resumption() {
+        assert!(resumable(&progress(false, 768), 768, false));
+        assert!(!resumable(&progress(false, 768), 768, true));
+        assert!(!resumable(&progress(true, 768), 768, false));
+        assert!(!resumable(&progress(false, 1536), 768, false));
+    }
+}
@@ -34,14 +34,18 @@
 //! - Validation: Ensures insights are novel and meaningful
 //!
 //! ### 4. Knowledge Graph Builder
-//! Creates and maintains bidirectional relationships between memories and insights
+//! Creates and maintains bidirectional relationships between memories and insights.
+//! The graph is persisted through `KnowledgeGraphStore` and merged into by every
+//! reflection session.
 //!
 //! ### 5. Meta-Memory Manager
 //! Handles insight storage, retrieval, and relationship tracking
 
 use super::error::{MemoryError, Result};
+use super::knowledge_graph_store::KnowledgeGraphStore;
 use super::models::*;
 use super::repository::MemoryRepository;
+use crate::embedding::SimpleEmbedder;
 use chrono::{DateTime, Duration, Utc};
 use pgvector::Vector;
 use serde::{Deserialize, Serialize};
@@ -79,6 +83,10 @@ pub struct ReflectionConfig {
 
     /// Cooldown period between reflections (hours)
     pub reflection_cooldown_hours: i64,
+
+    /// Minimum embedding similarity for a concept to be merged into an
+    /// existing knowledge graph node
+    pub concept_similarity_threshold: f64,
 }
 
 impl Default for ReflectionConfig {
@@ -93,6 +101,7 @@ impl Default for ReflectionConfig {
             min_cluster_size: 3,
             temporal_analysis_window_days: 30,
             reflection_cooldown_hours: 6,
+            concept_similarity_threshold: 0.85,
         }
     }
 }
@@ -155,7 +164,7 @@ pub struct KnowledgeNode {
 }
 
 /// Types of knowledge nodes
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum NodeType {
     Concept,
     Entity,
@@ -164,6 +173,34 @@ pub enum NodeType {
     Memory,
 }
 
+impl NodeType {
+    /// Name stored in `knowledge_nodes.node_type`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeType::Concept => "concept",
+            NodeType::Entity => "entity",
+            NodeType::Relationship => "relationship",
+            NodeType::Insight => "insight",
+            NodeType::Memory => "memory",
+        }
+    }
+}
+
+impl std::str::FromStr for NodeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "concept" => Ok(NodeType::Concept),
+            "entity" => Ok(NodeType::Entity),
+            "relationship" => Ok(NodeType::Relationship),
+            "insight" => Ok(NodeType::Insight),
+            "memory" => Ok(NodeType::Memory),
+            _ => Err(format!("Invalid knowledge node type: {s}")),
+        }
+    }
+}
+
 /// Edges in the knowledge graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KnowledgeEdge {
@@ -174,7 +211,7 @@ pub struct KnowledgeEdge {
 }
 
 /// Types of relationships in the knowledge graph
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum RelationshipType {
     IsA,
     PartOf,
@@ -188,6 +225,49 @@ pub enum RelationshipType {
     TemporallyPrecedes,
 }
 
+impl RelationshipType {
+    pub const ALL: [RelationshipType; 10] = [
+        RelationshipType::IsA,
+        RelationshipType::PartOf,
+        RelationshipType::CausedBy,
+        RelationshipType::SimilarTo,
+        RelationshipType::ConflictsWith,
+        RelationshipType::Enables,
+        RelationshipType::Requires,
+        RelationshipType::Exemplifies,
+        RelationshipType::GeneralizedBy,
+        RelationshipType::TemporallyPrecedes,
+    ];
+
+    /// Snake-case name used in storage and tool arguments
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationshipType::IsA => "is_a",
+            RelationshipType::PartOf => "part_of",
+            RelationshipType::CausedBy => "caused_by",
+            RelationshipType::SimilarTo => "similar_to",
+            RelationshipType::ConflictsWith => "conflicts_with",
+            RelationshipType::Enables => "enables",
+            RelationshipType::Requires => "requires",
+            RelationshipType::Exemplifies => "exemplifies",
+            RelationshipType::GeneralizedBy => "generalized_by",
+            RelationshipType::TemporallyPrecedes => "temporally_precedes",
+        }
+    }
+}
+
+impl std::str::FromStr for RelationshipType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        RelationshipType::ALL
+            .into_iter()
+            .find(|relation| relation.as_str() == name)
+            .ok_or_else(|| format!("Invalid relationship type: {s}"))
+    }
+}
+
 /// Memory cluster for insight generation
 #[derive(Debug, Clone)]
 pub struct MemoryCluster {
@@ -225,21 +305,42 @@ pub enum ReflectionStatus {
 pub struct ReflectionEngine {
     config: ReflectionConfig,
     repository: Arc<MemoryRepository>,
-    #[allow(dead_code)]
     knowledge_graph: KnowledgeGraph,
+    knowledge_store: KnowledgeGraphStore,
+    /// Whether `knowledge_graph` holds the persisted graph yet
+    knowledge_graph_loaded: bool,
+    /// Embeds concepts so they are matched by meaning rather than by name
+    embedder: Option<Arc<SimpleEmbedder>>,
     last_reflection_time: Option<DateTime<Utc>>,
 }
 
 impl ReflectionEngine {
     pub fn new(config: ReflectionConfig, repository: Arc<MemoryRepository>) -> Self {
+        let knowledge_store =
+            KnowledgeGraphStore::new(repository.pool().clone(), repository.namespace());
         Self {
             config,
             repository,
             knowledge_graph: KnowledgeGraph::new(),
+            knowledge_store,
+            knowledge_graph_loaded: false,
+            embedder: None,
             last_reflection_time: None,
         }
     }
 
+    /// Embed knowledge graph concepts with `embedder`; without one, concepts
+    /// are only merged when their names match
+    pub fn with_embedder(mut self, embedder: Arc<SimpleEmbedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Knowledge graph built up by reflection sessions so far
+    pub fn knowledge_graph(&self) -> &KnowledgeGraph {
+        &self.knowledge_graph
+    }
+
     /// Check if reflection should be triggered based on accumulated importance
     pub async fn should_trigger_reflection(&self) -> Result<Option<String>> {
         // Check cooldown period
@@ -302,6 +403,21 @@ impl ReflectionEngine {
             completion_status: ReflectionStatus::InProgress,
         };
 
+        if !self.knowledge_graph_loaded {
+            match self.knowledge_store.load().await {
+                Ok(graph) => {
+                    info!(
+                        "Loaded knowledge graph with {} nodes for namespace {}",
+                        graph.node_count(),
+                        self.knowledge_store.namespace()
+                    );
+                    self.knowledge_graph = graph;
+                    self.knowledge_graph_loaded = true;
+                }
+                Err(e) => warn!("Failed to load knowledge graph: {}", e),
+            }
+        }
+
         match self.execute_reflection_pipeline(&mut session).await {
             Ok(_) => {
                 session.completion_status = ReflectionStatus::Completed;
@@ -325,7 +441,7 @@ impl ReflectionEngine {
     }
 
     /// Main reflection pipeline execution
-    async fn execute_reflection_pipeline(&self, session: &mut ReflectionSession) -> Result<()> {
+    async fn execute_reflection_pipeline(&mut self, session: &mut ReflectionSession) -> Result<()> {
         // Step 1: Gather memories for analysis
         session.analyzed_memories = self.gather_reflection_memories().await?;
 
@@ -516,9 +632,11 @@ impl ReflectionEngine {
         Ok(insights)
     }
 
-    /// Update the knowledge graph with new insights
-    async fn update_knowledge_graph(&self, insights: &[Insight]) -> Result<Vec<KnowledgeNode>> {
-        let mut new_nodes = Vec::new();
+    /// Merge new insights into the knowledge graph and persist every node
+    /// and edge they touch. Related concepts are resolved to existing concept
+    /// nodes by embedding similarity, falling back to the concept name.
+    async fn update_knowledge_graph(&mut self, insights: &[Insight]) -> Result<Vec<KnowledgeNode>> {
+        let mut touched: Vec<Uuid> = Vec::new();
 
         for insight in insights {
             // Create node for the insight itself
@@ -526,36 +644,90 @@ impl ReflectionEngine {
                 id: insight.id,
                 concept: insight.content.clone(),
                 node_type: NodeType::Insight,
-                embedding: None, // Would generate embedding in production
+                embedding: self.embed_concept(&insight.content).await,
                 confidence: insight.confidence_score,
                 connections: Vec::new(),
                 created_at: insight.generated_at,
             };
+            self.persist_node(&insight_node).await;
+            self.knowledge_graph.add_node(insight_node);
+            touched.push(insight.id);
 
-            new_nodes.push(insight_node);
-
-            // Create nodes for related concepts
+            // Link related concepts, reusing nodes learned in earlier sessions
             for concept in &insight.related_concepts {
-                let concept_node = KnowledgeNode {
-                    id: Uuid::new_v4(),
-                    concept: concept.clone(),
-                    node_type: NodeType::Concept,
-                    embedding: None,
-                    confidence: 0.8,
-                    connections: vec![KnowledgeEdge {
-                        target_node_id: insight.id,
-                        relationship_type: RelationshipType::Exemplifies,
-                        strength: 0.9,
-                        evidence_memories: insight.source_memory_ids.clone(),
-                    }],
-                    created_at: Utc::now(),
+                let embedding = self.embed_concept(concept).await;
+                let existing = embedding
+                    .as_ref()
+                    .and_then(|embedding| {
+                        self.knowledge_graph.find_similar_concept(
+                            embedding,
+                            self.config.concept_similarity_threshold,
+                        )
+                    })
+                    .map(|(id, _)| id)
+                    .or_else(|| self.knowledge_graph.find_concept(concept));
+
+                let concept_id = match existing {
+                    Some(id) => id,
+                    None => {
+                        let concept_node = KnowledgeNode {
+                            id: Uuid::new_v4(),
+                            concept: concept.clone(),
+                            node_type: NodeType::Concept,
+                            embedding,
+                            confidence: 0.8,
+                            connections: Vec::new(),
+                            created_at: Utc::now(),
+                        };
+                        let id = concept_node.id;
+                        self.persist_node(&concept_node).await;
+                        self.knowledge_graph.add_node(concept_node);
+                        id
+                    }
+                };
+
+                let edge = KnowledgeEdge {
+                    target_node_id: insight.id,
+                    relationship_type: RelationshipType::Exemplifies,
+                    strength: 0.9,
+                    evidence_memories: insight.source_memory_ids.clone(),
                 };
+                if let Err(e) = self.knowledge_store.save_edge(concept_id, &edge).await {
+                    warn!(
+                        "Failed to persist knowledge edge {} -> {}: {}",
+                        concept_id, insight.id, e
+                    );
+                }
+                self.knowledge_graph.add_edge(concept_id, edge);
 
-                new_nodes.push(concept_node);
+                if !touched.contains(&concept_id) {
+                    touched.push(concept_id);
+                }
             }
         }
 
-        Ok(new_nodes)
+        Ok(touched
+            .iter()
+            .filter_map(|id| self.knowledge_graph.node(*id).cloned())
+            .collect())
+    }
+
+    /// Embedding of a knowledge graph concept, if an embedder is configured
+    async fn embed_concept(&self, concept: &str) -> Option<Vector> {
+        let embedder = self.embedder.as_ref()?;
+        match embedder.generate_embedding(concept).await {
+            Ok(embedding) => Some(Vector::from(embedding)),
+            Err(e) => {
+                warn!("Failed to embed knowledge graph concept: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn persist_node(&self, node: &KnowledgeNode) {
+        if let Err(e) = self.knowledge_store.save_node(node).await {
+            warn!("Failed to persist knowledge node {}: {}", node.id, e);
+        }
     }
 
     /// Store insights as high-importance meta-memories
@@ -583,6 +755,7 @@ impl ReflectionEngine {
                 metadata: Some(metadata),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
 
             match self.repository.create_memory(create_request).await {
@@ -869,35 +1042,7 @@ impl ReflectionEngine {
     }
 
     fn calculate_cosine_similarity(&self, vec1: &Vector, vec2: &Vector) -> Result<f64> {
-        let slice1 = vec1.as_slice();
-        let slice2 = vec2.as_slice();
-
-        if slice1.len() != slice2.len() {
-            return Ok(0.0);
-        }
-
-        let dot_product: f64 = slice1
-            .iter()
-            .zip(slice2.iter())
-            .map(|(a, b)| (*a as f64) * (*b as f64))
-            .sum();
-
-        let norm1: f64 = slice1
-            .iter()
-            .map(|x| (*x as f64).powi(2))
-            .sum::<f64>()
-            .sqrt();
-        let norm2: f64 = slice2
-            .iter()
-            .map(|x| (*x as f64).powi(2))
-            .sum::<f64>()
-            .sqrt();
-
-        if norm1 == 0.0 || norm2 == 0.0 {
-            return Ok(0.0);
-        }
-
-        Ok(dot_product / (norm1 * norm2))
+        Ok(cosine_similarity(vec1, vec2))
     }
 
     fn calculate_centroid_embedding(&self, memories: &[Memory]) -> Result<Option<Vector>> {
@@ -1004,6 +1149,87 @@ impl ReflectionEngine {
     }
 }
 
+/// Cosine similarity of two vectors; 0.0 when their lengths differ
+fn cosine_similarity(vec1: &Vector, vec2: &Vector) -> f64 {
+    let slice1 = vec1.as_slice();
+    let slice2 = vec2.as_slice();
+
+    if slice1.len() != slice2.len() {
+        return 0.0;
+    }
+
+    let dot_product: f64 = slice1
+        .iter()
+        .zip(slice2.iter())
+        .map(|(a, b)| (*a as f64) * (*b as f64))
+        .sum();
+
+    let norm1: f64 = slice1
+        .iter()
+        .map(|x| (*x as f64).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    let norm2: f64 = slice2
+        .iter()
+        .map(|x| (*x as f64).powi(2))
+        .sum::<f64>()
+        .sqrt();
+
+    if norm1 == 0.0 || norm2 == 0.0 {
+        return 0.0;
+    }
+
+    dot_product / (norm1 * norm2)
+}
+
+/// Key of a concept in the concept index
+fn concept_key(concept: &str) -> String {
+    concept.trim().to_lowercase()
+}
+
+/// A knowledge graph edge together with the node it starts at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub source_node_id: Uuid,
+    pub target_node_id: Uuid,
+    pub relationship_type: RelationshipType,
+    pub strength: f64,
+    pub evidence_memories: Vec<Uuid>,
+}
+
+impl GraphEdge {
+    fn new(source_node_id: Uuid, edge: &KnowledgeEdge) -> Self {
+        Self {
+            source_node_id,
+            target_node_id: edge.target_node_id,
+            relationship_type: edge.relationship_type,
+            strength: edge.strength,
+            evidence_memories: edge.evidence_memories.clone(),
+        }
+    }
+}
+
+/// Shortest chain of edges between two nodes, followed in either direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgePath {
+    /// Nodes from start to end, without their connections
+    pub nodes: Vec<KnowledgeNode>,
+    /// `edges[i]` joins `nodes[i]` and `nodes[i + 1]`
+    pub edges: Vec<GraphEdge>,
+    /// Product of the edge strengths
+    pub strength: f64,
+}
+
+/// Nodes within a number of hops of a center node and the edges between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeSubgraph {
+    pub center_id: Uuid,
+    pub depth: usize,
+    /// Nodes without their connections; edges are listed separately
+    pub nodes: Vec<KnowledgeNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
 /// Knowledge graph management
 pub struct KnowledgeGraph {
     nodes: HashMap<Uuid, KnowledgeNode>,
@@ -1020,21 +1246,92 @@ impl KnowledgeGraph {
 
     pub fn add_node(&mut self, node: KnowledgeNode) {
         // Index by concept for fast lookup
-        self.concept_index
-            .entry(node.concept.clone())
-            .or_default()
-            .push(node.id);
+        let ids = self
+            .concept_index
+            .entry(concept_key(&node.concept))
+            .or_default();
+        if !ids.contains(&node.id) {
+            ids.push(node.id);
+        }
 
         self.nodes.insert(node.id, node);
     }
 
+    /// Add an edge starting at `source_id`. An existing edge to the same node
+    /// with the same relationship keeps the higher strength and gains the new
+    /// evidence. Returns false if `source_id` is not in the graph.
+    pub fn add_edge(&mut self, source_id: Uuid, edge: KnowledgeEdge) -> bool {
+        let Some(node) = self.nodes.get_mut(&source_id) else {
+            return false;
+        };
+
+        match node.connections.iter_mut().find(|existing| {
+            existing.target_node_id == edge.target_node_id
+                && existing.relationship_type == edge.relationship_type
+        }) {
+            Some(existing) => {
+                existing.strength = existing.strength.max(edge.strength);
+                for memory_id in edge.evidence_memories {
+                    if !existing.evidence_memories.contains(&memory_id) {
+                        existing.evidence_memories.push(memory_id);
+                    }
+                }
+            }
+            None => node.connections.push(edge),
+        }
+        true
+    }
+
+    pub fn node(&self, id: Uuid) -> Option<&KnowledgeNode> {
+        self.nodes.get(&id)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Every edge in the graph
+    pub fn edges(&self) -> impl Iterator<Item = GraphEdge> + '_ {
+        self.nodes.values().flat_map(|node| {
+            node.connections
+                .iter()
+                .map(move |edge| GraphEdge::new(node.id, edge))
+        })
+    }
+
+    /// Concept node named `concept`, ignoring case and surrounding whitespace
+    pub fn find_concept(&self, concept: &str) -> Option<Uuid> {
+        self.concept_index
+            .get(&concept_key(concept))?
+            .iter()
+            .copied()
+            .find(|id| {
+                self.nodes
+                    .get(id)
+                    .is_some_and(|node| node.node_type == NodeType::Concept)
+            })
+    }
+
+    /// Concept node whose embedding is most similar to `embedding`, if that
+    /// similarity reaches `threshold`
+    pub fn find_similar_concept(&self, embedding: &Vector, threshold: f64) -> Option<(Uuid, f64)> {
+        self.nodes
+            .values()
+            .filter(|node| node.node_type == NodeType::Concept)
+            .filter_map(|node| {
+                let similarity = cosine_similarity(node.embedding.as_ref()?, embedding);
+                (similarity >= threshold).then_some((node.id, similarity))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
     pub fn find_related_concepts(&self, concept: &str, max_depth: usize) -> Vec<Uuid> {
         let mut related = Vec::new();
         let mut visited = HashSet::new();
         let mut queue = VecDeque::new();
 
         // Start with direct matches
-        if let Some(direct_matches) = self.concept_index.get(concept) {
+        if let Some(direct_matches) = self.concept_index.get(&concept_key(concept)) {
             for &node_id in direct_matches {
                 queue.push_back((node_id, 0));
             }
@@ -1060,6 +1357,130 @@ impl KnowledgeGraph {
 
         related
     }
+
+    /// Fewest-hop path from `from` to `to` of at most `max_depth` edges
+    pub fn shortest_path(&self, from: Uuid, to: Uuid, max_depth: usize) -> Option<KnowledgePath> {
+        self.nodes.get(&from)?;
+        self.nodes.get(&to)?;
+
+        let adjacency = self.adjacency();
+        let mut previous: HashMap<Uuid, (Uuid, GraphEdge)> = HashMap::new();
+        let mut visited = HashSet::from([from]);
+        let mut queue = VecDeque::from([(from, 0)]);
+
+        while let Some((node_id, depth)) = queue.pop_front() {
+            if node_id == to {
+                break;
+            }
+            if depth >= max_depth {
+                continue;
+            }
+            for (next, edge) in adjacency.get(&node_id).into_iter().flatten() {
+                if visited.insert(*next) {
+                    previous.insert(*next, (node_id, (*edge).clone()));
+                    queue.push_back((*next, depth + 1));
+                }
+            }
+        }
+
+        if from != to && !previous.contains_key(&to) {
+            return None;
+        }
+
+        let mut node_ids = vec![to];
+        let mut edges = Vec::new();
+        let mut current = to;
+        while let Some((prior, edge)) = previous.get(&current) {
+            node_ids.push(*prior);
+            edges.push(edge.clone());
+            current = *prior;
+        }
+        node_ids.reverse();
+        edges.reverse();
+
+        Some(KnowledgePath {
+            nodes: node_ids
+                .iter()
+                .filter_map(|id| self.detached_node(*id))
+                .collect(),
+            strength: edges.iter().map(|edge| edge.strength).product(),
+            edges,
+        })
+    }
+
+    /// Nodes within `depth` hops of `center`, following edges in either
+    /// direction, and every edge between them
+    pub fn neighborhood(&self, center: Uuid, depth: usize) -> Option<KnowledgeSubgraph> {
+        self.nodes.get(&center)?;
+
+        let adjacency = self.adjacency();
+        let mut included = HashSet::from([center]);
+        let mut frontier = vec![center];
+        for _ in 0..depth {
+            let mut next_frontier = Vec::new();
+            for node_id in &frontier {
+                for (next, _) in adjacency.get(node_id).into_iter().flatten() {
+                    if included.insert(*next) {
+                        next_frontier.push(*next);
+                    }
+                }
+            }
+            frontier = next_frontier;
+        }
+
+        let mut nodes: Vec<KnowledgeNode> = included
+            .iter()
+            .filter_map(|id| self.detached_node(*id))
+            .collect();
+        nodes.sort_by_key(|node| node.created_at);
+
+        let edges = included
+            .iter()
+            .filter_map(|id| self.nodes.get(id))
+            .flat_map(|node| {
+                node.connections
+                    .iter()
+                    .filter(|edge| included.contains(&edge.target_node_id))
+                    .map(move |edge| GraphEdge::new(node.id, edge))
+            })
+            .collect();
+
+        Some(KnowledgeSubgraph {
+            center_id: center,
+            depth,
+            nodes,
+            edges,
+        })
+    }
+
+    /// Edges of every node in both directions, keyed by the node and paired
+    /// with the node on the other end
+    fn adjacency(&self) -> HashMap<Uuid, Vec<(Uuid, GraphEdge)>> {
+        let mut adjacency: HashMap<Uuid, Vec<(Uuid, GraphEdge)>> = HashMap::new();
+        for edge in self.edges() {
+            if !self.nodes.contains_key(&edge.target_node_id) {
+                continue;
+            }
+            adjacency
+                .entry(edge.source_node_id)
+                .or_default()
+                .push((edge.target_node_id, edge.clone()));
+            adjacency
+                .entry(edge.target_node_id)
+                .or_default()
+                .push((edge.source_node_id, edge));
+        }
+        adjacency
+    }
+
+    /// Copy of a node without its connections, for exports that list edges
+    /// separately
+    fn detached_node(&self, id: Uuid) -> Option<KnowledgeNode> {
+        self.nodes.get(&id).map(|node| KnowledgeNode {
+            connections: Vec::new(),
+            ..node.clone()
+        })
+    }
 }
 
 impl Default for KnowledgeGraph {
@@ -1101,6 +1522,105 @@ mod tests {
         assert!(graph.concept_index.contains_key("test_concept"));
     }
 
+    fn concept_node(concept: &str, embedding: Option<Vec<f32>>) -> KnowledgeNode {
+        KnowledgeNode {
+            id: Uuid::new_v4(),
+            concept: concept.to_string(),
+            node_type: NodeType::Concept,
+            embedding: embedding.map(Vector::from),
+            confidence: 0.8,
+            connections: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn edge(target_node_id: Uuid, strength: f64) -> KnowledgeEdge {
+        KnowledgeEdge {
+            target_node_id,
+            relationship_type: RelationshipType::Exemplifies,
+            strength,
+            evidence_memories: vec![Uuid::new_v4()],
+        }
+    }
+
+    #[test]
+    fn test_knowledge_graph_concept_lookup() {
+        let mut graph = KnowledgeGraph::new();
+        let database = concept_node("Database", Some(vec![1.0, 0.0, 0.0]));
+        let cooking = concept_node("cooking", Some(vec![0.0, 1.0, 0.0]));
+        let database_id = database.id;
+        graph.add_node(database);
+        graph.add_node(cooking);
+
+        assert_eq!(graph.find_concept("  database "), Some(database_id));
+        assert_eq!(graph.find_concept("databases"), None);
+
+        let postgres = Vector::from(vec![0.95, 0.1, 0.0]);
+        let (id, similarity) = graph.find_similar_concept(&postgres, 0.85).unwrap();
+        assert_eq!(id, database_id);
+        assert!(similarity > 0.9);
+        assert!(graph
+            .find_similar_concept(&Vector::from(vec![0.0, 0.0, 1.0]), 0.85)
+            .is_none());
+    }
+
+    #[test]
+    fn test_knowledge_graph_edges_merge() {
+        let mut graph = KnowledgeGraph::new();
+        let source = concept_node("source", None);
+        let target = concept_node("target", None);
+        let (source_id, target_id) = (source.id, target.id);
+        graph.add_node(source);
+        graph.add_node(target);
+
+        assert!(graph.add_edge(source_id, edge(target_id, 0.4)));
+        assert!(graph.add_edge(source_id, edge(target_id, 0.9)));
+        assert!(!graph.add_edge(Uuid::new_v4(), edge(target_id, 0.5)));
+
+        let edges: Vec<GraphEdge> = graph.edges().collect();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].strength, 0.9);
+        assert_eq!(edges[0].evidence_memories.len(), 2);
+    }
+
+    #[test]
+    fn test_knowledge_graph_path_and_neighborhood() {
+        // a -> b <- c -> d, plus an unconnected e
+        let mut graph = KnowledgeGraph::new();
+        let nodes: Vec<KnowledgeNode> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|concept| concept_node(concept, None))
+            .collect();
+        let ids: Vec<Uuid> = nodes.iter().map(|node| node.id).collect();
+        for node in nodes {
+            graph.add_node(node);
+        }
+        graph.add_edge(ids[0], edge(ids[1], 0.5));
+        graph.add_edge(ids[2], edge(ids[1], 0.8));
+        graph.add_edge(ids[2], edge(ids[3], 1.0));
+
+        let path = graph.shortest_path(ids[0], ids[3], 5).unwrap();
+        let path_ids: Vec<Uuid> = path.nodes.iter().map(|node| node.id).collect();
+        assert_eq!(path_ids, vec![ids[0], ids[1], ids[2], ids[3]]);
+        assert_eq!(path.edges.len(), 3);
+        assert!((path.strength - 0.4).abs() < 1e-9);
+
+        assert!(graph.shortest_path(ids[0], ids[3], 2).is_none());
+        assert!(graph.shortest_path(ids[0], ids[4], 5).is_none());
+
+        let subgraph = graph.neighborhood(ids[1], 1).unwrap();
+        let mut members: Vec<Uuid> = subgraph.nodes.iter().map(|node| node.id).collect();
+        members.sort();
+        let mut expected = vec![ids[0], ids[1], ids[2]];
+        expected.sort();
+        assert_eq!(members, expected);
+        assert_eq!(subgraph.edges.len(), 2);
+        assert!(subgraph
+            .nodes
+            .iter()
+            .all(|node| node.connections.is_empty()));
+    }
+
     #[test]
     fn test_insight_type_serialization() {
         let insight_type = InsightType::Pattern;
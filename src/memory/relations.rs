@@ -0,0 +1,323 @@
+//! Typed relations between memories
+//!
+//! `parent_id` only records where a memory was derived from. Relations are
+//! explicit directed edges between two memories of the same namespace,
+//! typed with the reflection engine's `RelationshipType` and weighted by a
+//! strength in `[0, 1]`. They can be followed one hop (`get_neighbors`) or
+//! several (`traverse_relations`), and search results can be expanded with
+//! the memories they are linked to.
+
+use super::error::{MemoryError, Result};
+use super::models::{Memory, SearchResult};
+use super::reflection_engine::RelationshipType;
+use super::storage::MemoryStorage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Deepest traversal accepted from callers
+pub const MAX_TRAVERSAL_DEPTH: usize = 5;
+
+/// A directed, typed edge from `source_id` to `target_id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRelation {
+    pub id: Uuid,
+    pub namespace: String,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub relation_type: RelationshipType,
+    pub strength: f64,
+    pub metadata: serde_json::Value,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create a relation, or update the strength and metadata of an existing
+/// relation of the same type between the same memories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkMemoriesRequest {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub relation_type: RelationshipType,
+    /// Defaults to 1.0
+    pub strength: Option<f64>,
+    pub metadata: Option<serde_json::Value>,
+    pub created_by: Option<String>,
+}
+
+impl LinkMemoriesRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.source_id == self.target_id {
+            return Err(MemoryError::InvalidRequest {
+                message: "A memory cannot be related to itself".to_string(),
+            });
+        }
+        if let Some(strength) = self.strength {
+            if !(0.0..=1.0).contains(&strength) {
+                return Err(MemoryError::InvalidRequest {
+                    message: "Relation strength must be between 0.0 and 1.0".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Which edges of a memory to follow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationDirection {
+    /// Edges starting at the memory
+    Outgoing,
+    /// Edges ending at the memory
+    Incoming,
+    #[default]
+    Both,
+}
+
+impl RelationDirection {
+    pub fn includes_outgoing(&self) -> bool {
+        matches!(self, RelationDirection::Outgoing | RelationDirection::Both)
+    }
+
+    pub fn includes_incoming(&self) -> bool {
+        matches!(self, RelationDirection::Incoming | RelationDirection::Both)
+    }
+}
+
+impl std::str::FromStr for RelationDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "outgoing" => Ok(RelationDirection::Outgoing),
+            "incoming" => Ok(RelationDirection::Incoming),
+            "both" => Ok(RelationDirection::Both),
+            _ => Err(format!("Invalid relation direction: {s}")),
+        }
+    }
+}
+
+/// Edges to follow from a set of memories
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RelationFilter {
+    /// Only these relation types; all types when `None`
+    pub relation_types: Option<Vec<RelationshipType>>,
+    pub direction: RelationDirection,
+    pub min_strength: Option<f64>,
+}
+
+/// Limits of a multi-hop traversal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraversalOptions {
+    pub filter: RelationFilter,
+    /// Number of hops to follow, capped at `MAX_TRAVERSAL_DEPTH`
+    pub max_depth: usize,
+    /// Stop once this many memories have been reached
+    pub max_nodes: usize,
+}
+
+impl Default for TraversalOptions {
+    fn default() -> Self {
+        Self {
+            filter: RelationFilter::default(),
+            max_depth: 1,
+            max_nodes: 50,
+        }
+    }
+}
+
+/// A memory reached by following relations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryNeighbor {
+    pub memory: Memory,
+    /// Edge the memory was first reached through
+    pub relation: MemoryRelation,
+    /// Memory on the other end of `relation`
+    pub via: Uuid,
+    /// Hops from the starting memories
+    pub depth: usize,
+    /// Product of the strengths of the edges on the path
+    pub path_strength: f64,
+}
+
+impl MemoryNeighbor {
+    /// Whether the memory is the target of `relation`, i.e. `via` links to it
+    pub fn is_outgoing(&self) -> bool {
+        self.relation.target_id == self.memory.id
+    }
+}
+
+/// Breadth-first traversal from `starts`. Every memory is reported once, at
+/// its shortest depth and through its strongest edge at that depth; the
+/// starting memories themselves are never reported.
+pub async fn traverse_from<S: MemoryStorage + ?Sized>(
+    storage: &S,
+    starts: &[Uuid],
+    options: &TraversalOptions,
+) -> Result<Vec<MemoryNeighbor>> {
+    let max_depth = options.max_depth.min(MAX_TRAVERSAL_DEPTH);
+    let mut visited: HashSet<Uuid> = starts.iter().copied().collect();
+    let mut path_strengths: HashMap<Uuid, f64> = starts.iter().map(|id| (*id, 1.0)).collect();
+    let mut frontier: Vec<Uuid> = starts.to_vec();
+    let mut neighbors = Vec::new();
+
+    for depth in 1..=max_depth {
+        if frontier.is_empty() || neighbors.len() >= options.max_nodes {
+            break;
+        }
+
+        let frontier_set: HashSet<Uuid> = frontier.iter().copied().collect();
+        let relations = storage.get_relations(&frontier, &options.filter).await?;
+
+        // Best edge into each newly reached memory
+        let mut reached: HashMap<Uuid, (MemoryRelation, Uuid, f64)> = HashMap::new();
+        for relation in relations {
+            let mut ends = Vec::with_capacity(2);
+            if options.filter.direction.includes_outgoing()
+                && frontier_set.contains(&relation.source_id)
+            {
+                ends.push((relation.source_id, relation.target_id));
+            }
+            if options.filter.direction.includes_incoming()
+                && frontier_set.contains(&relation.target_id)
+            {
+                ends.push((relation.target_id, relation.source_id));
+            }
+
+            for (via, next) in ends {
+                if visited.contains(&next) {
+                    continue;
+                }
+                let strength = path_strengths.get(&via).copied().unwrap_or(1.0) * relation.strength;
+                let better = reached
+                    .get(&next)
+                    .is_none_or(|(_, _, best)| strength > *best);
+                if better {
+                    reached.insert(next, (relation.clone(), via, strength));
+                }
+            }
+        }
+
+        let mut ranked: Vec<(Uuid, (MemoryRelation, Uuid, f64))> = reached.into_iter().collect();
+        ranked.sort_by(|a, b| b.1 .2.total_cmp(&a.1 .2).then(a.0.cmp(&b.0)));
+        ranked.truncate(options.max_nodes - neighbors.len());
+
+        let ids: Vec<Uuid> = ranked.iter().map(|(id, _)| *id).collect();
+        let mut memories: HashMap<Uuid, Memory> = storage
+            .get_memories_by_ids(&ids)
+            .await?
+            .into_iter()
+            .map(|memory| (memory.id, memory))
+            .collect();
+
+        frontier = Vec::with_capacity(ranked.len());
+        for (id, (relation, via, path_strength)) in ranked {
+            let Some(memory) = memories.remove(&id) else {
+                continue;
+            };
+            visited.insert(id);
+            path_strengths.insert(id, path_strength);
+            frontier.push(id);
+            neighbors.push(MemoryNeighbor {
+                memory,
+                relation,
+                via,
+                depth,
+                path_strength,
+            });
+        }
+    }
+
+    Ok(neighbors)
+}
+
+/// Memories linked to search results that are not results themselves,
+/// scored as the linking result's combined score times the path strength
+pub async fn expand_search_results<S: MemoryStorage + ?Sized>(
+    storage: &S,
+    results: &[SearchResult],
+    options: &TraversalOptions,
+) -> Result<Vec<(MemoryNeighbor, f32)>> {
+    if results.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let scores: HashMap<Uuid, f32> = results
+        .iter()
+        .map(|r| (r.memory.id, r.combined_score))
+        .collect();
+    let starts: Vec<Uuid> = results.iter().map(|r| r.memory.id).collect();
+    let neighbors = traverse_from(storage, &starts, options).await?;
+
+    // Trace every neighbor back to the search result it was reached from
+    let mut origins: HashMap<Uuid, Uuid> = HashMap::new();
+    for neighbor in &neighbors {
+        let origin = if scores.contains_key(&neighbor.via) {
+            neighbor.via
+        } else {
+            origins.get(&neighbor.via).copied().unwrap_or(neighbor.via)
+        };
+        origins.insert(neighbor.memory.id, origin);
+    }
+
+    let mut expanded: Vec<(MemoryNeighbor, f32)> = neighbors
+        .into_iter()
+        .map(|neighbor| {
+            let origin_score = origins
+                .get(&neighbor.memory.id)
+                .and_then(|origin| scores.get(origin))
+                .copied()
+                .unwrap_or(0.0);
+            let score = origin_score * neighbor.path_strength as f32;
+            (neighbor, score)
+        })
+        .collect();
+    expanded.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_request_validation() {
+        let id = Uuid::new_v4();
+        let request = LinkMemoriesRequest {
+            source_id: id,
+            target_id: Uuid::new_v4(),
+            relation_type: RelationshipType::CausedBy,
+            strength: Some(0.7),
+            metadata: None,
+            created_by: None,
+        };
+        assert!(request.validate().is_ok());
+
+        let self_link = LinkMemoriesRequest {
+            target_id: id,
+            ..request.clone()
+        };
+        assert!(self_link.validate().is_err());
+
+        let too_strong = LinkMemoriesRequest {
+            strength: Some(1.5),
+            ..request
+        };
+        assert!(too_strong.validate().is_err());
+    }
+
+    #[test]
+    fn test_relation_names_round_trip() {
+        for relation in RelationshipType::ALL {
+            assert_eq!(relation.as_str().parse::<RelationshipType>(), Ok(relation));
+        }
+        assert!("friend_of".parse::<RelationshipType>().is_err());
+        assert_eq!(
+            "Incoming".parse::<RelationDirection>(),
+            Ok(RelationDirection::Incoming)
+        );
+    }
+}
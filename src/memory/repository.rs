@@ -1,8 +1,25 @@
+use super::contradictions::{
+    ConflictStatus, MemoryConflict, NewMemoryConflict, ResolveConflictRequest,
+};
+use super::entities::{
+    lookup_keys, normalize_alias, Entity, EntityMention, EntityType, ENTITY_SIMILARITY_THRESHOLD,
+};
 use super::error::{MemoryError, Result};
 use super::event_triggers::EventTriggeredScoringEngine;
+use super::field_encryption::WrappedDataKey;
 use super::math_engine::constants;
 use super::models::*;
-use super::search_backend::SearchBackend;
+use super::pii_policy::VaultedPiiValue;
+use super::reflection_engine::RelationshipType;
+use super::relations::{LinkMemoriesRequest, MemoryRelation, RelationFilter};
+use super::retention_job::{legal_hold_error, LegalHold};
+use super::search_backend::{
+    normalize_query, reciprocal_rank_fusion, refinement_suggestions, SearchBackend,
+    DATE_HISTOGRAM_INTERVALS, DEFAULT_RRF_K, IMPORTANCE_FACET_BUCKETS, MAX_SUGGESTIONS,
+    MAX_TAG_FACETS,
+};
+use super::search_cursor::{query_fingerprint, sort_score, CursorSigner, SearchCursor};
+use super::versioning::is_revised;
 use crate::config::Config;
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
@@ -20,6 +37,106 @@ pub struct MemoryRepository {
     pool: PgPool,
     trigger_engine: Option<Arc<EventTriggeredScoringEngine>>,
     config: Option<Config>,
+    /// Every query is restricted to this namespace
+    namespace: String,
+    /// Model recorded on stored embeddings and expected of query embeddings
+    embedding_model: Option<String>,
+    /// Signs search pagination cursors
+    cursor_signer: CursorSigner,
+}
+
+/// Where a page of search results starts
+struct SearchPage {
+    /// Only memories created at or before this time are searched
+    snapshot_at: DateTime<Utc>,
+    /// Resume after this result instead of at the request's offset
+    after: Option<SearchCursor>,
+}
+
+/// Cursors only stay valid across restarts and instances with a configured secret
+fn cursor_signer_for(config: &Config) -> CursorSigner {
+    match &config.security.search_cursor_secret {
+        Some(secret) => CursorSigner::new(secret.as_bytes()),
+        None => CursorSigner::random(),
+    }
+}
+
+/// Cosine similarity of a memory's embedding to the query vector `vector`
+/// of `dimension`, cast the way `ensure_embedding_index` builds the index
+/// for that dimension so the planner can use it
+fn embedding_similarity(dimension: usize, vector: &str) -> String {
+    format!("1 - (m.embedding::vector({dimension}) <=> {vector}::vector({dimension}))")
+}
+
+fn relation_from_row(row: &sqlx::postgres::PgRow) -> Result<MemoryRelation> {
+    let relation_type: String = row.try_get("relation_type")?;
+    Ok(MemoryRelation {
+        id: row.try_get("id")?,
+        namespace: row.try_get("namespace")?,
+        source_id: row.try_get("source_id")?,
+        target_id: row.try_get("target_id")?,
+        relation_type: relation_type
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        strength: row.try_get("strength")?,
+        metadata: row.try_get("metadata")?,
+        created_by: row.try_get("created_by")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn conflict_from_row(row: &sqlx::postgres::PgRow) -> Result<MemoryConflict> {
+    let status: String = row.try_get("status")?;
+    Ok(MemoryConflict {
+        id: row.try_get("id")?,
+        namespace: row.try_get("namespace")?,
+        memory_id: row.try_get("memory_id")?,
+        conflicting_memory_id: row.try_get("conflicting_memory_id")?,
+        status: status
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        superseded_memory_id: row.try_get("superseded_memory_id")?,
+        reason: row.try_get("reason")?,
+        confidence: row.try_get("confidence")?,
+        detector: row.try_get("detector")?,
+        resolved_by: row.try_get("resolved_by")?,
+        created_at: row.try_get("created_at")?,
+        resolved_at: row.try_get("resolved_at")?,
+    })
+}
+
+/// Entity columns with the entity's aliases, for queries on `entities e`
+const ENTITY_SELECT: &str = "SELECT e.*, ARRAY(SELECT a.alias FROM entity_aliases a \
+     WHERE a.entity_id = e.id ORDER BY a.created_at, a.alias) AS aliases FROM entities e";
+
+fn entity_from_row(row: &sqlx::postgres::PgRow) -> Result<Entity> {
+    let entity_type: String = row.try_get("entity_type")?;
+    let embedding: Option<Vector> = row.try_get("embedding")?;
+    let mention_count: i32 = row.try_get("mention_count")?;
+    Ok(Entity {
+        id: row.try_get("id")?,
+        namespace: row.try_get("namespace")?,
+        entity_type: entity_type
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        canonical_name: row.try_get("canonical_name")?,
+        aliases: row.try_get("aliases")?,
+        embedding: embedding.map(|e| e.to_vec()),
+        mention_count: mention_count as i64,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn legal_hold_from_row(row: &sqlx::postgres::PgRow) -> Result<LegalHold> {
+    Ok(LegalHold {
+        memory_id: row.try_get("memory_id")?,
+        namespace: row.try_get("namespace")?,
+        reason: row.try_get("reason")?,
+        placed_by: row.try_get("placed_by")?,
+        placed_at: row.try_get("placed_at")?,
+    })
 }
 
 /// Safe query builder to prevent SQL injection vulnerabilities
@@ -39,6 +156,7 @@ enum QueryParameter {
     Tier(MemoryTier),
     Uuid(Uuid),
     Vector(Vector),
+    TextArray(Vec<String>),
 }
 
 impl SafeQueryBuilder {
@@ -56,6 +174,16 @@ impl SafeQueryBuilder {
         self
     }
 
+    /// Add a parameterized namespace filter
+    pub fn add_namespace_filter(&mut self, namespace: &str) -> &mut Self {
+        let condition = format!("AND namespace = ${}", self.bind_index);
+        self.query_parts.push(condition);
+        self.parameters
+            .push(QueryParameter::Text(namespace.to_string()));
+        self.bind_index += 1;
+        self
+    }
+
     /// Add a parameterized tier filter
     pub fn add_tier_filter(&mut self, tier: &MemoryTier) -> &mut Self {
         let condition = format!("AND m.tier = ${}", self.bind_index);
@@ -104,14 +232,122 @@ impl SafeQueryBuilder {
     }
 
     /// Add a parameterized similarity threshold
-    pub fn add_similarity_threshold(&mut self, threshold: f64) -> &mut Self {
-        let condition = format!("AND (1 - (m.embedding <=> $1)) >= ${}", self.bind_index);
+    /// Keep memories at least `threshold` similar to the query vector `$1`
+    /// of `dimension`
+    pub fn add_similarity_threshold(&mut self, dimension: usize, threshold: f64) -> &mut Self {
+        let condition = format!(
+            "AND ({}) >= ${}",
+            embedding_similarity(dimension, "$1"),
+            self.bind_index
+        );
         self.query_parts.push(condition);
         self.parameters.push(QueryParameter::Float(threshold));
         self.bind_index += 1;
         self
     }
 
+    /// Restrict to stored embeddings comparable with a query vector of
+    /// `dimension` produced by `model`; vectors stored before model tracking
+    /// are matched on dimension alone. The dimension is inlined so the
+    /// planner can match the predicate of its partial HNSW index.
+    pub fn add_embedding_compatibility(
+        &mut self,
+        dimension: usize,
+        model: Option<&str>,
+    ) -> &mut Self {
+        self.query_parts
+            .push(format!("AND m.embedding_dimension = {dimension}"));
+
+        if let Some(model) = model {
+            let condition = format!(
+                "AND (m.embedding_model = ${} OR m.embedding_model IS NULL)",
+                self.bind_index
+            );
+            self.query_parts.push(condition);
+            self.parameters
+                .push(QueryParameter::Text(model.to_string()));
+            self.bind_index += 1;
+        }
+        self
+    }
+
+    /// Require every tag in `tags` in the memory's `metadata.tags` array
+    pub fn add_tags_filter(&mut self, tags: &[String]) -> &mut Self {
+        let condition = format!("AND m.metadata->'tags' ?& ${}", self.bind_index);
+        self.query_parts.push(condition);
+        self.parameters
+            .push(QueryParameter::TextArray(tags.to_vec()));
+        self.bind_index += 1;
+        self
+    }
+
+    /// Require a link to the entity of `namespace` with one of the
+    /// normalized aliases `keys`
+    pub fn add_entity_filter(&mut self, namespace: &str, keys: &[String]) -> &mut Self {
+        let namespace = self.placeholder(QueryParameter::Text(namespace.to_string()));
+        let keys = self.placeholder(QueryParameter::TextArray(keys.to_vec()));
+        self.query_parts.push(format!(
+            "AND m.id IN (SELECT me.memory_id FROM memory_entities me \
+             JOIN entity_aliases a ON a.entity_id = me.entity_id \
+             WHERE a.namespace = {namespace} AND a.normalized_alias = ANY({keys}))"
+        ));
+        self
+    }
+
+    /// Exclude memories superseded by a contradicting memory
+    pub fn add_superseded_filter(&mut self) -> &mut Self {
+        self.query_parts.push(
+            "AND NOT EXISTS (SELECT 1 FROM memory_conflicts c \
+             WHERE c.superseded_memory_id = m.id AND c.status = 'superseded')"
+                .to_string(),
+        );
+        self
+    }
+
+    /// Only memories whose valid time contains `as_of`; memories without
+    /// `valid_from` are valid from their creation
+    pub fn add_as_of_filter(&mut self, as_of: DateTime<Utc>) -> &mut Self {
+        let as_of = self.placeholder(QueryParameter::DateTime(as_of));
+        self.query_parts.push(format!(
+            "AND COALESCE(m.valid_from, m.created_at) <= {as_of} \
+             AND (m.valid_to IS NULL OR m.valid_to > {as_of})"
+        ));
+        self
+    }
+
+    /// Exclude memories created after a search's snapshot time
+    pub fn add_snapshot_filter(&mut self, snapshot_at: DateTime<Utc>) -> &mut Self {
+        let condition = format!("AND m.created_at <= ${}", self.bind_index);
+        self.query_parts.push(condition);
+        self.parameters.push(QueryParameter::DateTime(snapshot_at));
+        self.bind_index += 1;
+        self
+    }
+
+    /// Keyset condition for `ORDER BY <sort_expr> DESC, m.id`: rows after
+    /// the row with sort value `last` and id `last_id`
+    fn add_keyset_condition(
+        &mut self,
+        sort_expr: &str,
+        last: QueryParameter,
+        last_id: Uuid,
+    ) -> &mut Self {
+        let last = self.placeholder(last);
+        let last_id = self.placeholder(QueryParameter::Uuid(last_id));
+        self.query_parts.push(format!(
+            "AND ({sort_expr} < {last} OR ({sort_expr} = {last} AND m.id > {last_id}))"
+        ));
+        self
+    }
+
+    /// Bind a parameter and return its placeholder for a hand-written condition
+    fn placeholder(&mut self, param: QueryParameter) -> String {
+        let placeholder = format!("${}", self.bind_index);
+        self.parameters.push(param);
+        self.bind_index += 1;
+        placeholder
+    }
+
     /// Add consolidation strength range filter
     pub fn add_consolidation_strength_range(
         &mut self,
@@ -224,6 +460,7 @@ impl SafeQueryBuilder {
                 QueryParameter::Tier(tier) => query.bind(tier),
                 QueryParameter::Uuid(uuid) => query.bind(*uuid),
                 QueryParameter::Vector(vec) => query.bind(vec),
+                QueryParameter::TextArray(values) => query.bind(values),
             };
         }
         query
@@ -246,6 +483,7 @@ impl SafeQueryBuilder {
                 QueryParameter::Tier(tier) => query.bind(tier),
                 QueryParameter::Uuid(uuid) => query.bind(*uuid),
                 QueryParameter::Vector(vec) => query.bind(vec),
+                QueryParameter::TextArray(values) => query.bind(values),
             };
         }
         query
@@ -258,6 +496,9 @@ impl MemoryRepository {
             pool,
             trigger_engine: None,
             config: None,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            embedding_model: None,
+            cursor_signer: CursorSigner::random(),
         }
     }
 
@@ -265,7 +506,10 @@ impl MemoryRepository {
         Self {
             pool,
             trigger_engine: None,
+            cursor_signer: cursor_signer_for(&config),
             config: Some(config),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            embedding_model: None,
         }
     }
 
@@ -277,6 +521,9 @@ impl MemoryRepository {
             pool,
             trigger_engine: Some(trigger_engine),
             config: None,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            embedding_model: None,
+            cursor_signer: CursorSigner::random(),
         }
     }
 
@@ -288,7 +535,10 @@ impl MemoryRepository {
         Self {
             pool,
             trigger_engine: Some(trigger_engine),
+            cursor_signer: cursor_signer_for(&config),
             config: Some(config),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            embedding_model: None,
         }
     }
 
@@ -296,6 +546,38 @@ impl MemoryRepository {
         &self.pool
     }
 
+    /// Namespace this repository reads from and writes to
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// A repository sharing this pool, trigger engine and config whose
+    /// queries are restricted to `namespace`
+    pub fn with_namespace(&self, namespace: impl Into<String>) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            trigger_engine: self.trigger_engine.clone(),
+            config: self.config.clone(),
+            namespace: namespace.into(),
+            embedding_model: self.embedding_model.clone(),
+            cursor_signer: self.cursor_signer.clone(),
+        }
+    }
+
+    /// Embedding model new vectors are attributed to and semantic searches
+    /// are restricted to; `None` disables model checks
+    pub fn embedding_model(&self) -> Option<&str> {
+        self.embedding_model.as_deref()
+    }
+
+    /// A repository like this one whose embeddings come from `model`
+    pub fn with_embedding_model(&self, model: impl Into<String>) -> Self {
+        Self {
+            embedding_model: Some(model.into()),
+            ..self.with_namespace(self.namespace.clone())
+        }
+    }
+
     pub async fn create_memory(&self, request: CreateMemoryRequest) -> Result<Memory> {
         self.create_memory_with_user_context(request, None).await
     }
@@ -306,7 +588,10 @@ impl MemoryRepository {
         user_id: Option<&str>,
     ) -> Result<Memory> {
         let id = Uuid::new_v4();
-        let content_hash = Memory::calculate_content_hash(&request.content);
+        let content_hash = request
+            .content_hash
+            .clone()
+            .unwrap_or_else(|| Memory::calculate_content_hash(&request.content));
         let tier = request.tier.unwrap_or(MemoryTier::Working);
 
         // Check for duplicates (skip in test mode)
@@ -315,10 +600,11 @@ impl MemoryRepository {
 
         if !skip_duplicate_check {
             let duplicate_exists = sqlx::query_scalar::<_, bool>(
-                "SELECT EXISTS(SELECT 1 FROM memories WHERE content_hash = $1 AND tier = $2 AND status = 'active')"
+                "SELECT EXISTS(SELECT 1 FROM memories WHERE content_hash = $1 AND tier = $2 AND namespace = $3 AND status = 'active')"
             )
             .bind(&content_hash)
             .bind(tier)
+            .bind(&self.namespace)
             .fetch_one(&self.pool)
             .await?;
 
@@ -333,8 +619,9 @@ impl MemoryRepository {
         if tier == MemoryTier::Working {
             if let Some(ref config) = self.config {
                 let working_count: i64 = sqlx::query_scalar(
-                    "SELECT COUNT(*) FROM memories WHERE tier = 'working' AND status = 'active'",
+                    "SELECT COUNT(*) FROM memories WHERE tier = 'working' AND status = 'active' AND namespace = $1",
                 )
+                .bind(&self.namespace)
                 .fetch_one(&self.pool)
                 .await?;
 
@@ -348,10 +635,11 @@ impl MemoryRepository {
                     // Find the least recently used memory in working tier
                     let lru_memory_id: Option<Uuid> = sqlx::query_scalar(
                         "SELECT id FROM memories 
-                         WHERE tier = 'working' AND status = 'active'
+                         WHERE tier = 'working' AND status = 'active' AND namespace = $1
                          ORDER BY last_accessed_at ASC
                          LIMIT 1",
                     )
+                    .bind(&self.namespace)
                     .fetch_optional(&self.pool)
                     .await?;
 
@@ -359,9 +647,10 @@ impl MemoryRepository {
                         // Migrate LRU memory to warm tier
                         sqlx::query(
                             "UPDATE memories SET tier = 'warm', updated_at = NOW() 
-                             WHERE id = $1",
+                             WHERE id = $1 AND namespace = $2",
                         )
                         .bind(memory_id)
+                        .bind(&self.namespace)
                         .execute(&self.pool)
                         .await?;
 
@@ -413,6 +702,13 @@ impl MemoryRepository {
             (request.importance_score.unwrap_or(0.5), None)
         };
 
+        validate_valid_time(
+            request.valid_from.unwrap_or_else(Utc::now),
+            request.valid_to,
+        )?;
+
+        let embedding_dimension = request.embedding.as_ref().map(|e| e.len() as i32);
+        let embedding_model = embedding_dimension.and(self.embedding_model.clone());
         let embedding = request.embedding.map(Vector::from);
 
         // Add trigger metadata if triggered
@@ -435,9 +731,10 @@ impl MemoryRepository {
             INSERT INTO memories (
                 id, content, content_hash, embedding, tier, status, 
                 importance_score, metadata, parent_id, expires_at,
-                consolidation_strength, decay_rate
+                consolidation_strength, decay_rate, namespace,
+                embedding_model, embedding_dimension, valid_from, valid_to
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
             RETURNING *
             "#,
         )
@@ -453,6 +750,11 @@ impl MemoryRepository {
         .bind(request.expires_at)
         .bind(1.0_f64) // Default consolidation_strength
         .bind(1.0_f64) // Default decay_rate
+        .bind(&self.namespace)
+        .bind(embedding_model)
+        .bind(embedding_dimension)
+        .bind(request.valid_from)
+        .bind(request.valid_to)
         .fetch_one(&self.pool)
         .await?;
 
@@ -469,11 +771,12 @@ impl MemoryRepository {
             UPDATE memories 
             SET access_count = access_count + 1, 
                 last_accessed_at = NOW()
-            WHERE id = $1 AND status = 'active'
+            WHERE id = $1 AND status = 'active' AND namespace = $2
             RETURNING *
             "#,
         )
         .bind(id)
+        .bind(&self.namespace)
         .fetch_optional(&self.pool)
         .await?
         .ok_or_else(|| MemoryError::NotFound { id: id.to_string() })?;
@@ -487,9 +790,10 @@ impl MemoryRepository {
 
         // Get current memory
         let current = sqlx::query_as::<_, Memory>(
-            "SELECT * FROM memories WHERE id = $1 AND status = 'active' FOR UPDATE",
+            "SELECT * FROM memories WHERE id = $1 AND status = 'active' AND namespace = $2 FOR UPDATE",
         )
         .bind(id)
+        .bind(&self.namespace)
         .fetch_optional(&mut *tx)
         .await?
         .ok_or_else(|| MemoryError::NotFound { id: id.to_string() })?;
@@ -497,22 +801,37 @@ impl MemoryRepository {
         // Update fields
         let content = request.content.as_ref().unwrap_or(&current.content);
         let content_hash = if request.content.is_some() {
-            Memory::calculate_content_hash(content)
+            request
+                .content_hash
+                .clone()
+                .unwrap_or_else(|| Memory::calculate_content_hash(content))
         } else {
             current.content_hash.clone()
         };
 
-        let embedding = request.embedding.map(Vector::from).or(current.embedding);
+        let (embedding_model, embedding_dimension) = match &request.embedding {
+            Some(embedding) => (self.embedding_model.clone(), Some(embedding.len() as i32)),
+            None => (current.embedding_model.clone(), current.embedding_dimension),
+        };
+        let embedding = request
+            .embedding
+            .map(Vector::from)
+            .or_else(|| current.embedding.clone());
         let tier = request.tier.unwrap_or(current.tier);
         let importance_score = request.importance_score.unwrap_or(current.importance_score);
         let metadata = request.metadata.as_ref().unwrap_or(&current.metadata);
         let expires_at = request.expires_at.or(current.expires_at);
+        let valid_from = request.valid_from.or(current.valid_from);
+        let valid_to = request.valid_to.or(current.valid_to);
+        validate_valid_time(valid_from.unwrap_or(current.created_at), valid_to)?;
 
         let updated = sqlx::query_as::<_, Memory>(
             r#"
             UPDATE memories 
             SET content = $2, content_hash = $3, embedding = $4, tier = $5,
                 importance_score = $6, metadata = $7, expires_at = $8,
+                embedding_model = $9, embedding_dimension = $10,
+                valid_from = $11, valid_to = $12,
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -526,6 +845,10 @@ impl MemoryRepository {
         .bind(importance_score)
         .bind(metadata)
         .bind(expires_at)
+        .bind(embedding_model)
+        .bind(embedding_dimension)
+        .bind(valid_from)
+        .bind(valid_to)
         .fetch_one(&mut *tx)
         .await?;
 
@@ -541,137 +864,1106 @@ impl MemoryRepository {
             .await?;
         }
 
+        // Tier-only changes are already covered by the migration history
+        if is_revised(&current, &updated) {
+            self.record_revision(&mut tx, &current, &updated, request.actor, request.reason)
+                .await?;
+        }
+
         tx.commit().await?;
         info!("Updated memory {}", id);
         Ok(updated)
     }
 
-    pub async fn delete_memory(&self, id: Uuid) -> Result<()> {
-        let result = sqlx::query(
-            "UPDATE memories SET status = 'deleted' WHERE id = $1 AND status = 'active'",
-        )
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
+    /// Store `updated` as the next revision of the memory. The first
+    /// recorded update also stores `previous` as revision 1, so history
+    /// starts from the state before any tracked change.
+    async fn record_revision(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        previous: &Memory,
+        updated: &Memory,
+        actor: Option<String>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        // The memory row is locked FOR UPDATE, so revision numbers cannot race
+        let latest: Option<i32> =
+            sqlx::query_scalar("SELECT MAX(revision) FROM memory_revisions WHERE memory_id = $1")
+                .bind(updated.id)
+                .fetch_one(&mut **tx)
+                .await?;
 
-        if result.rows_affected() == 0 {
-            return Err(MemoryError::NotFound { id: id.to_string() });
-        }
+        let latest = match latest {
+            Some(latest) => latest,
+            None => {
+                self.insert_revision(
+                    tx,
+                    previous,
+                    1,
+                    None,
+                    Some("Initial version".to_string()),
+                    previous.updated_at,
+                )
+                .await?;
+                1
+            }
+        };
 
-        info!("Soft deleted memory {}", id);
-        Ok(())
+        self.insert_revision(tx, updated, latest + 1, actor, reason, updated.updated_at)
+            .await
     }
 
-    /// Enhanced search method with memory-aware features for Story 9
-    pub async fn search_memories_enhanced(
+    async fn insert_revision(
         &self,
-        request: crate::memory::enhanced_retrieval::MemoryAwareSearchRequest,
-    ) -> Result<crate::memory::enhanced_retrieval::MemoryAwareSearchResponse> {
-        use crate::memory::enhanced_retrieval::*;
+        tx: &mut Transaction<'_, Postgres>,
+        memory: &Memory,
+        revision: i32,
+        actor: Option<String>,
+        reason: Option<String>,
+        created_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO memory_revisions (
+                memory_id, namespace, revision, content, content_hash, metadata,
+                importance_score, tier, actor, reason, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(memory.id)
+        .bind(&self.namespace)
+        .bind(revision)
+        .bind(&memory.content)
+        .bind(&memory.content_hash)
+        .bind(&memory.metadata)
+        .bind(memory.importance_score)
+        .bind(memory.tier)
+        .bind(actor)
+        .bind(reason)
+        .bind(created_at)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
 
-        let config = EnhancedRetrievalConfig::default();
-        let retrieval_engine = MemoryAwareRetrievalEngine::new(
-            config,
-            std::sync::Arc::new(MemoryRepository::new(self.pool.clone())),
-            None,
-        );
+    /// Revisions of a memory, newest first
+    pub async fn get_memory_history(
+        &self,
+        memory_id: Uuid,
+        limit: Option<i64>,
+    ) -> Result<Vec<MemoryRevision>> {
+        let revisions = sqlx::query_as::<_, MemoryRevision>(
+            r#"
+            SELECT * FROM memory_revisions
+            WHERE memory_id = $1 AND namespace = $2
+            ORDER BY revision DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(memory_id)
+        .bind(&self.namespace)
+        .bind(limit.unwrap_or(50))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(revisions)
+    }
 
-        retrieval_engine.search(request).await
+    pub async fn get_memory_revision(
+        &self,
+        memory_id: Uuid,
+        revision: i32,
+    ) -> Result<MemoryRevision> {
+        sqlx::query_as::<_, MemoryRevision>(
+            "SELECT * FROM memory_revisions WHERE memory_id = $1 AND revision = $2 AND namespace = $3",
+        )
+        .bind(memory_id)
+        .bind(revision)
+        .bind(&self.namespace)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| MemoryError::NotFound {
+            id: format!("{memory_id} revision {revision}"),
+        })
     }
 
-    pub async fn search_memories(&self, request: SearchRequest) -> Result<SearchResponse> {
-        let start_time = Instant::now();
+    /// Active memories among `ids`, without recording an access
+    pub async fn get_memories_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Memory>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let memories = sqlx::query_as::<_, Memory>(
+            "SELECT * FROM memories WHERE id = ANY($1) AND status = 'active' AND namespace = $2",
+        )
+        .bind(ids)
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(memories)
+    }
 
-        let search_type = request
-            .search_type
-            .as_ref()
-            .unwrap_or(&SearchType::Semantic)
-            .clone();
-        let limit = request.limit.unwrap_or(10);
-        let offset = request.offset.unwrap_or(0);
+    /// Create a relation between two active memories, or update the strength
+    /// and metadata of the existing relation of the same type
+    pub async fn link_memories(&self, request: LinkMemoriesRequest) -> Result<MemoryRelation> {
+        request.validate()?;
 
-        let results = match search_type {
-            SearchType::Semantic => self.semantic_search(&request).await?,
-            SearchType::Temporal => self.temporal_search(&request).await?,
-            SearchType::Hybrid => self.hybrid_search(&request).await?,
-            SearchType::FullText => self.fulltext_search(&request).await?,
-        };
+        let found = self
+            .get_memories_by_ids(&[request.source_id, request.target_id])
+            .await?;
+        for id in [request.source_id, request.target_id] {
+            if !found.iter().any(|memory| memory.id == id) {
+                return Err(MemoryError::NotFound { id: id.to_string() });
+            }
+        }
 
-        let total_count = if request.include_facets.unwrap_or(false) {
-            Some(self.count_search_results(&request).await?)
-        } else {
-            None
-        };
+        let row = sqlx::query(
+            r#"
+            INSERT INTO memory_relations (
+                namespace, source_id, target_id, relation_type, strength, metadata, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (source_id, target_id, relation_type) DO UPDATE
+            SET strength = EXCLUDED.strength,
+                metadata = EXCLUDED.metadata,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(request.source_id)
+        .bind(request.target_id)
+        .bind(request.relation_type.as_str())
+        .bind(request.strength.unwrap_or(1.0))
+        .bind(request.metadata.unwrap_or_else(|| serde_json::json!({})))
+        .bind(request.created_by)
+        .fetch_one(&self.pool)
+        .await?;
 
-        let facets = if request.include_facets.unwrap_or(false) {
-            Some(self.generate_search_facets(&request).await?)
-        } else {
-            None
-        };
+        let relation = relation_from_row(&row)?;
+        info!(
+            "Linked memory {} -[{}]-> {}",
+            relation.source_id,
+            relation.relation_type.as_str(),
+            relation.target_id
+        );
+        Ok(relation)
+    }
 
-        let suggestions = if request.query_text.is_some() {
-            Some(self.generate_query_suggestions(&request).await?)
-        } else {
-            None
-        };
+    pub async fn unlink_memories(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+        relation_type: Option<RelationshipType>,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM memory_relations
+            WHERE namespace = $1 AND source_id = $2 AND target_id = $3
+              AND ($4::text IS NULL OR relation_type = $4)
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(source_id)
+        .bind(target_id)
+        .bind(relation_type.map(|r| r.as_str()))
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
 
-        let next_cursor = if results.len() as i32 >= limit {
-            Some(self.generate_cursor(offset + limit as i64, &request))
-        } else {
-            None
-        };
+    /// Relations between active memories touching any of `ids`
+    pub async fn get_relations(
+        &self,
+        ids: &[Uuid],
+        filter: &RelationFilter,
+    ) -> Result<Vec<MemoryRelation>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let relation_types: Option<Vec<&str>> = filter
+            .relation_types
+            .as_ref()
+            .map(|types| types.iter().map(|r| r.as_str()).collect());
 
-        let execution_time_ms = start_time.elapsed().as_millis() as u64;
+        let rows = sqlx::query(
+            r#"
+            SELECT r.*
+            FROM memory_relations r
+            JOIN memories s ON s.id = r.source_id AND s.status = 'active'
+            JOIN memories t ON t.id = r.target_id AND t.status = 'active'
+            WHERE r.namespace = $1
+              AND (($2 AND r.source_id = ANY($4)) OR ($3 AND r.target_id = ANY($4)))
+              AND ($5::text[] IS NULL OR r.relation_type = ANY($5))
+              AND r.strength >= $6
+            ORDER BY r.strength DESC, r.id
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(filter.direction.includes_outgoing())
+        .bind(filter.direction.includes_incoming())
+        .bind(ids)
+        .bind(relation_types)
+        .bind(filter.min_strength.unwrap_or(0.0))
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(SearchResponse {
-            results,
-            total_count,
-            facets,
-            suggestions,
-            next_cursor,
-            execution_time_ms,
-        })
+        rows.iter().map(relation_from_row).collect()
     }
 
-    async fn semantic_search(&self, request: &SearchRequest) -> Result<Vec<SearchResult>> {
-        let query_embedding = if let Some(ref embedding) = request.query_embedding {
-            Vector::from(embedding.clone())
-        } else {
-            return Err(MemoryError::InvalidRequest {
-                message: "Query embedding is required for semantic search".to_string(),
+    /// Resolve `mentions` to entities of the namespace, creating the ones
+    /// not known yet, and link the memory to them
+    pub async fn link_memory_entities(
+        &self,
+        memory_id: Uuid,
+        mentions: Vec<EntityMention>,
+    ) -> Result<Vec<Entity>> {
+        if self.get_memories_by_ids(&[memory_id]).await?.is_empty() {
+            return Err(MemoryError::NotFound {
+                id: memory_id.to_string(),
             });
-        };
+        }
 
-        let limit = request.limit.unwrap_or(10);
-        let offset = request.offset.unwrap_or(0);
-        let threshold = request.similarity_threshold.unwrap_or(0.7);
+        let mut tx = self.pool.begin().await?;
+        let mut entity_ids = Vec::new();
+        for mention in mentions {
+            let keys = mention.lookup_keys();
+            if keys.is_empty() {
+                continue;
+            }
+            let entity_id = self.resolve_entity(&mut tx, &mention, &keys).await?;
 
-        // Use safe query builder to prevent SQL injection - include all computed columns for build_search_results()
-        let mut builder = SafeQueryBuilder::new(
-            "SELECT m.*, 
-                1 - (m.embedding <=> $1) as similarity_score,
-                m.recency_score as temporal_score,
-                m.importance_score,
-                m.relevance_score,
-                COALESCE(m.access_count, 0) as access_count,
-                m.combined_score as combined_score,
-                CASE 
-                    WHEN COALESCE(m.access_count, 0) <= 0 THEN 0.0 
-                    ELSE (LN(COALESCE(m.access_count, 0)::float + 1.0) * 0.1)::float4
-                END as access_frequency_score
-            FROM memories m WHERE m.status = 'active' AND m.embedding IS NOT NULL"
-        );
-        // Set bind index to 2 since $1 is already used for the query embedding
-        builder.bind_index = 2;
+            let canonical = mention.canonical_name();
+            for alias in [mention.name.as_str(), canonical.as_str()] {
+                let normalized = normalize_alias(alias);
+                if normalized.is_empty() {
+                    continue;
+                }
+                sqlx::query(
+                    r#"
+                    INSERT INTO entity_aliases (entity_id, namespace, alias, normalized_alias)
+                    VALUES ($1, $2, $3, $4)
+                    ON CONFLICT (entity_id, normalized_alias) DO NOTHING
+                    "#,
+                )
+                .bind(entity_id)
+                .bind(&self.namespace)
+                .bind(alias)
+                .bind(normalized)
+                .execute(&mut *tx)
+                .await?;
+            }
 
-        // Add filters safely
+            let linked = sqlx::query(
+                r#"
+                INSERT INTO memory_entities (memory_id, entity_id, mention, confidence)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (memory_id, entity_id) DO NOTHING
+                "#,
+            )
+            .bind(memory_id)
+            .bind(entity_id)
+            .bind(&mention.name)
+            .bind(mention.confidence)
+            .execute(&mut *tx)
+            .await?;
+            if linked.rows_affected() > 0 {
+                sqlx::query(
+                    "UPDATE entities SET mention_count = mention_count + 1, updated_at = NOW() WHERE id = $1",
+                )
+                .bind(entity_id)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            if !entity_ids.contains(&entity_id) {
+                entity_ids.push(entity_id);
+            }
+        }
+        tx.commit().await?;
+
+        let mut entities = self.get_entities_by_ids(&entity_ids).await?;
+        entities.sort_by_key(|entity| entity_ids.iter().position(|id| *id == entity.id));
+        debug!("Linked memory {} to {} entities", memory_id, entities.len());
+        Ok(entities)
+    }
+
+    /// Entity a mention refers to: by alias, then by the most similar name
+    /// embedding, otherwise a newly created entity
+    async fn resolve_entity(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        mention: &EntityMention,
+        keys: &[String],
+    ) -> Result<Uuid> {
+        let by_alias: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT e.id
+            FROM entities e
+            JOIN entity_aliases a ON a.entity_id = e.id
+            WHERE a.namespace = $1 AND e.entity_type = $2 AND a.normalized_alias = ANY($3)
+            ORDER BY e.mention_count DESC, e.id
+            LIMIT 1
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(mention.entity_type.as_str())
+        .bind(keys)
+        .fetch_optional(&mut **tx)
+        .await?;
+        if let Some(id) = by_alias {
+            return Ok(id);
+        }
+
+        let embedding = mention.embedding.clone().map(Vector::from);
+        if let Some(embedding) = &embedding {
+            let nearest = sqlx::query(
+                r#"
+                SELECT id, 1 - (embedding <=> $3) AS similarity
+                FROM entities
+                WHERE namespace = $1 AND entity_type = $2
+                  AND embedding IS NOT NULL AND embedding_dimension = $4
+                ORDER BY embedding <=> $3
+                LIMIT 1
+                "#,
+            )
+            .bind(&self.namespace)
+            .bind(mention.entity_type.as_str())
+            .bind(embedding)
+            .bind(embedding.as_slice().len() as i32)
+            .fetch_optional(&mut **tx)
+            .await?;
+            if let Some(row) = nearest {
+                let similarity: f64 = row.try_get("similarity")?;
+                if similarity >= ENTITY_SIMILARITY_THRESHOLD as f64 {
+                    return Ok(row.try_get("id")?);
+                }
+            }
+        }
+
+        let canonical = mention.canonical_name();
+        let id = sqlx::query_scalar(
+            r#"
+            INSERT INTO entities (
+                namespace, entity_type, canonical_name, normalized_name, embedding, embedding_dimension
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (namespace, entity_type, normalized_name) DO UPDATE
+            SET embedding = COALESCE(entities.embedding, EXCLUDED.embedding),
+                embedding_dimension = COALESCE(entities.embedding_dimension, EXCLUDED.embedding_dimension),
+                updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(mention.entity_type.as_str())
+        .bind(&canonical)
+        .bind(normalize_alias(&canonical))
+        .bind(embedding.as_ref())
+        .bind(embedding.as_ref().map(|e| e.as_slice().len() as i32))
+        .fetch_one(&mut **tx)
+        .await?;
+        Ok(id)
+    }
+
+    async fn get_entities_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Entity>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query(&format!(
+            "{ENTITY_SELECT} WHERE e.namespace = $1 AND e.id = ANY($2)"
+        ))
+        .bind(&self.namespace)
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(entity_from_row).collect()
+    }
+
+    /// Entity of any type known by `name` or one of its aliases
+    pub async fn find_entity(&self, name: &str) -> Result<Option<Entity>> {
+        let keys = lookup_keys(name);
+        if keys.is_empty() {
+            return Ok(None);
+        }
+        let row = sqlx::query(&format!(
+            r#"{ENTITY_SELECT}
+            WHERE e.namespace = $1
+              AND EXISTS (
+                  SELECT 1 FROM entity_aliases a
+                  WHERE a.entity_id = e.id AND a.normalized_alias = ANY($2)
+              )
+            ORDER BY e.mention_count DESC, e.id
+            LIMIT 1"#
+        ))
+        .bind(&self.namespace)
+        .bind(&keys)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(entity_from_row).transpose()
+    }
+
+    /// Most mentioned entities of the namespace, only of `entity_type` if given
+    pub async fn list_entities(
+        &self,
+        entity_type: Option<EntityType>,
+        limit: i64,
+    ) -> Result<Vec<Entity>> {
+        let rows = sqlx::query(&format!(
+            r#"{ENTITY_SELECT}
+            WHERE e.namespace = $1 AND ($2::text IS NULL OR e.entity_type = $2)
+            ORDER BY e.mention_count DESC, e.canonical_name
+            LIMIT $3"#
+        ))
+        .bind(&self.namespace)
+        .bind(entity_type.map(|t| t.as_str()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(entity_from_row).collect()
+    }
+
+    /// Entities the memory is linked to
+    pub async fn get_memory_entities(&self, memory_id: Uuid) -> Result<Vec<Entity>> {
+        let rows = sqlx::query(&format!(
+            r#"{ENTITY_SELECT}
+            JOIN memory_entities me ON me.entity_id = e.id
+            WHERE e.namespace = $1 AND me.memory_id = $2
+            ORDER BY me.confidence DESC, e.canonical_name"#
+        ))
+        .bind(&self.namespace)
+        .bind(memory_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(entity_from_row).collect()
+    }
+
+    /// Record a contradiction between two memories. A conflict already
+    /// recorded for the pair keeps its status.
+    pub async fn record_conflict(&self, conflict: NewMemoryConflict) -> Result<MemoryConflict> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO memory_conflicts (
+                namespace, memory_id, conflicting_memory_id, status, superseded_memory_id,
+                reason, confidence, detector, resolved_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $4 = 'open' THEN NULL ELSE NOW() END)
+            ON CONFLICT (memory_id, conflicting_memory_id) DO UPDATE
+            SET confidence = GREATEST(memory_conflicts.confidence, EXCLUDED.confidence)
+            RETURNING *
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(conflict.memory_id)
+        .bind(conflict.conflicting_memory_id)
+        .bind(conflict.status.as_str())
+        .bind(conflict.superseded_memory_id)
+        .bind(&conflict.reason)
+        .bind(conflict.confidence.clamp(0.0, 1.0))
+        .bind(&conflict.detector)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let conflict = conflict_from_row(&row)?;
+        if let Some(superseded) = conflict.superseded_memory_id {
+            self.end_valid_time(superseded, conflict.other_memory(superseded))
+                .await?;
+        }
+        Ok(conflict)
+    }
+
+    /// Conflicts of the namespace, newest first, optionally only those with
+    /// `status` or involving `memory_id`
+    pub async fn list_conflicts(
+        &self,
+        status: Option<ConflictStatus>,
+        memory_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<MemoryConflict>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM memory_conflicts
+            WHERE namespace = $1
+              AND ($2::text IS NULL OR status = $2)
+              AND ($3::uuid IS NULL OR memory_id = $3 OR conflicting_memory_id = $3)
+            ORDER BY created_at DESC, id
+            LIMIT $4
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(status.map(|s| s.as_str()))
+        .bind(memory_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(conflict_from_row).collect()
+    }
+
+    pub async fn resolve_conflict(
+        &self,
+        request: ResolveConflictRequest,
+    ) -> Result<MemoryConflict> {
+        let row = sqlx::query("SELECT * FROM memory_conflicts WHERE id = $1 AND namespace = $2")
+            .bind(request.conflict_id)
+            .bind(&self.namespace)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| MemoryError::NotFound {
+                id: request.conflict_id.to_string(),
+            })?;
+        let previous = conflict_from_row(&row)?;
+        let superseded = request.superseded_memory_id(&previous)?;
+
+        let row = sqlx::query(
+            r#"
+            UPDATE memory_conflicts
+            SET status = $2,
+                superseded_memory_id = $3,
+                resolved_by = $4,
+                resolved_at = CASE WHEN $2 = 'open' THEN NULL ELSE NOW() END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(request.conflict_id)
+        .bind(request.status.as_str())
+        .bind(superseded)
+        .bind(&request.resolved_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let conflict = conflict_from_row(&row)?;
+        if let Some(reverted) = previous
+            .superseded_memory_id
+            .filter(|id| Some(*id) != superseded)
+        {
+            self.restore_valid_time(reverted, previous.other_memory(reverted))
+                .await?;
+        }
+        if let Some(superseded) = superseded {
+            self.end_valid_time(superseded, conflict.other_memory(superseded))
+                .await?;
+        }
+        info!(
+            "Resolved conflict {} as {}",
+            conflict.id,
+            conflict.status.as_str()
+        );
+        Ok(conflict)
+    }
+
+    /// Wrapped PII vault data key of a subject, storing `new_key` for a
+    /// subject without one
+    pub async fn pii_subject_key(&self, subject_id: &str, new_key: &[u8]) -> Result<Vec<u8>> {
+        sqlx::query(
+            "INSERT INTO pii_vault_keys (subject_id, wrapped_key) VALUES ($1, $2) ON CONFLICT (subject_id) DO NOTHING",
+        )
+        .bind(subject_id)
+        .bind(new_key)
+        .execute(&self.pool)
+        .await?;
+        let key = sqlx::query_scalar::<_, Vec<u8>>(
+            "SELECT wrapped_key FROM pii_vault_keys WHERE subject_id = $1",
+        )
+        .bind(subject_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(key)
+    }
+
+    pub async fn vault_pii_value(
+        &self,
+        token: &str,
+        subject_id: &str,
+        kind: &str,
+        ciphertext: &[u8],
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pii_vault (token, namespace, subject_id, kind, ciphertext)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (namespace, token, subject_id) DO NOTHING
+            "#,
+        )
+        .bind(token)
+        .bind(&self.namespace)
+        .bind(subject_id)
+        .bind(kind)
+        .bind(ciphertext)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn vaulted_pii_values(&self, token: &str) -> Result<Vec<VaultedPiiValue>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT v.subject_id, v.kind, v.ciphertext, k.wrapped_key
+            FROM pii_vault v
+            JOIN pii_vault_keys k ON k.subject_id = v.subject_id
+            WHERE v.token = $1 AND v.namespace = $2
+            "#,
+        )
+        .bind(token)
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|row| {
+                Ok(VaultedPiiValue {
+                    subject_id: row.try_get("subject_id")?,
+                    kind: row.try_get("kind")?,
+                    ciphertext: row.try_get("ciphertext")?,
+                    wrapped_key: row.try_get("wrapped_key")?,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn encryption_keys(&self) -> Result<Vec<WrappedDataKey>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT key_id, wrapped_key, master_key_id, created_at
+            FROM memory_encryption_keys
+            ORDER BY created_at, key_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|row| {
+                Ok(WrappedDataKey {
+                    key_id: row.try_get("key_id")?,
+                    wrapped_key: row.try_get("wrapped_key")?,
+                    master_key_id: row.try_get("master_key_id")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn add_encryption_key(&self, key: &WrappedDataKey) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO memory_encryption_keys (key_id, wrapped_key, master_key_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (key_id) DO NOTHING
+            "#,
+        )
+        .bind(&key.key_id)
+        .bind(&key.wrapped_key)
+        .bind(&key.master_key_id)
+        .bind(key.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Re-wrapped data keys replace their stored wrapping in one transaction,
+    /// so a failed rotation leaves every key under the old master key
+    pub async fn rewrap_encryption_keys(
+        &self,
+        master_key_id: &str,
+        wrapped_keys: &[(String, Vec<u8>)],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for (key_id, wrapped_key) in wrapped_keys {
+            sqlx::query(
+                "UPDATE memory_encryption_keys SET wrapped_key = $1, master_key_id = $2 WHERE key_id = $3",
+            )
+            .bind(wrapped_key)
+            .bind(master_key_id)
+            .bind(key_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Namespaces holding active memories; not restricted to this handle's
+    /// namespace, for jobs that visit every namespace
+    pub async fn list_namespaces(&self) -> Result<Vec<String>> {
+        let namespaces = sqlx::query_scalar(
+            "SELECT DISTINCT namespace FROM memories WHERE status = 'active' ORDER BY namespace",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(namespaces)
+    }
+
+    pub async fn memories_created_before(
+        &self,
+        before: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Memory>> {
+        let memories = sqlx::query_as::<_, Memory>(
+            r#"
+            SELECT * FROM memories
+            WHERE namespace = $1 AND status = 'active' AND created_at < $2
+              AND ($3::uuid IS NULL OR id > $3)
+            ORDER BY id
+            LIMIT $4
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(before)
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(memories)
+    }
+
+    pub async fn purge_memory_history(&self, id: Uuid) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM memory_revisions
+            WHERE memory_id = $1 AND namespace = $2
+              AND revision < (
+                  SELECT MAX(revision) FROM memory_revisions
+                  WHERE memory_id = $1 AND namespace = $2
+              )
+            "#,
+        )
+        .bind(id)
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn unlink_memory_entities(&self, memory_id: Uuid) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            WITH unlinked AS (
+                DELETE FROM memory_entities me
+                USING entities e
+                WHERE me.memory_id = $1 AND me.entity_id = e.id AND e.namespace = $2
+                RETURNING me.entity_id
+            )
+            UPDATE entities
+            SET mention_count = GREATEST(mention_count - 1, 0), updated_at = NOW()
+            WHERE id IN (SELECT entity_id FROM unlinked)
+            "#,
+        )
+        .bind(memory_id)
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn forget_pii_values(&self, subject_id: &str, tokens: &[String]) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM pii_vault WHERE namespace = $1 AND subject_id = $2 AND token = ANY($3)",
+        )
+        .bind(&self.namespace)
+        .bind(subject_id)
+        .bind(tokens)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn place_legal_hold(
+        &self,
+        id: Uuid,
+        reason: &str,
+        placed_by: Option<&str>,
+    ) -> Result<LegalHold> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO legal_holds (memory_id, namespace, reason, placed_by)
+            SELECT id, namespace, $3, $4 FROM memories WHERE id = $1 AND namespace = $2
+            ON CONFLICT (memory_id) DO UPDATE
+            SET reason = EXCLUDED.reason, placed_by = EXCLUDED.placed_by, placed_at = NOW()
+            RETURNING memory_id, namespace, reason, placed_by, placed_at
+            "#,
+        )
+        .bind(id)
+        .bind(&self.namespace)
+        .bind(reason)
+        .bind(placed_by)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| MemoryError::NotFound { id: id.to_string() })?;
+
+        info!("Placed legal hold on memory {}", id);
+        legal_hold_from_row(&row)
+    }
+
+    pub async fn release_legal_hold(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM legal_holds WHERE memory_id = $1 AND namespace = $2")
+            .bind(id)
+            .bind(&self.namespace)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn legal_holds(&self) -> Result<Vec<LegalHold>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT memory_id, namespace, reason, placed_by, placed_at
+            FROM legal_holds
+            WHERE namespace = $1
+            ORDER BY placed_at DESC
+            "#,
+        )
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(legal_hold_from_row).collect()
+    }
+
+    /// End the valid time of a superseded memory where the valid time of the
+    /// memory replacing it starts. An end set explicitly is kept.
+    async fn end_valid_time(&self, superseded: Uuid, current: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE memories old
+            SET valid_to = COALESCE(cur.valid_from, cur.created_at)
+            FROM memories cur
+            WHERE old.id = $1 AND cur.id = $2 AND old.namespace = $3
+              AND old.valid_to IS NULL
+              AND COALESCE(old.valid_from, old.created_at) < COALESCE(cur.valid_from, cur.created_at)
+            "#,
+        )
+        .bind(superseded)
+        .bind(current)
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Undo `end_valid_time` when a supersession is withdrawn
+    async fn restore_valid_time(&self, superseded: Uuid, current: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE memories old
+            SET valid_to = NULL
+            FROM memories cur
+            WHERE old.id = $1 AND cur.id = $2 AND old.namespace = $3
+              AND old.valid_to = COALESCE(cur.valid_from, cur.created_at)
+            "#,
+        )
+        .bind(superseded)
+        .bind(current)
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_memory(&self, id: Uuid) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE memories SET status = 'deleted'
+            WHERE id = $1 AND status = 'active' AND namespace = $2
+              AND NOT EXISTS (SELECT 1 FROM legal_holds WHERE memory_id = $1)
+            "#,
+        )
+        .bind(id)
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            let held: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM legal_holds WHERE memory_id = $1 AND namespace = $2)",
+            )
+            .bind(id)
+            .bind(&self.namespace)
+            .fetch_one(&self.pool)
+            .await?;
+            if held {
+                return Err(legal_hold_error(id));
+            }
+            return Err(MemoryError::NotFound { id: id.to_string() });
+        }
+
+        info!("Soft deleted memory {}", id);
+        Ok(())
+    }
+
+    /// Enhanced search method with memory-aware features for Story 9
+    pub async fn search_memories_enhanced(
+        &self,
+        request: crate::memory::enhanced_retrieval::MemoryAwareSearchRequest,
+    ) -> Result<crate::memory::enhanced_retrieval::MemoryAwareSearchResponse> {
+        use crate::memory::enhanced_retrieval::*;
+
+        let config = EnhancedRetrievalConfig::default();
+        let retrieval_engine = MemoryAwareRetrievalEngine::new(
+            config,
+            std::sync::Arc::new(self.with_namespace(self.namespace.clone())),
+            None,
+        );
+
+        retrieval_engine.search(request).await
+    }
+
+    pub async fn search_memories(&self, request: SearchRequest) -> Result<SearchResponse> {
+        let start_time = Instant::now();
+
+        let search_type = request
+            .search_type
+            .as_ref()
+            .unwrap_or(&SearchType::Semantic)
+            .clone();
+        let limit = request.limit.unwrap_or(10);
+        let offset = request.offset.unwrap_or(0);
+
+        let fingerprint = query_fingerprint(&self.namespace, &search_type, &request);
+        let after = match request.cursor.as_deref() {
+            Some(token) => {
+                let cursor = self.cursor_signer.decode(token)?;
+                if cursor.fingerprint != fingerprint {
+                    return Err(MemoryError::InvalidRequest {
+                        message: "Search cursor was issued for a different query".to_string(),
+                    });
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+        let position = after.as_ref().map_or(offset, |cursor| cursor.position);
+        let page = SearchPage {
+            snapshot_at: after
+                .as_ref()
+                .map_or_else(Utc::now, |cursor| cursor.snapshot_at),
+            after,
+        };
+
+        let mut results = match search_type {
+            SearchType::Semantic => self.semantic_search(&request, &page).await?,
+            SearchType::Temporal => self.temporal_search(&request, &page).await?,
+            SearchType::Hybrid => self.hybrid_search(&request, &page).await?,
+            SearchType::FullText => self.fulltext_search(&request, &page).await?,
+        };
+
+        let next_cursor = match results.last() {
+            Some(last) if results.len() as i32 >= limit => {
+                Some(self.cursor_signer.encode(&SearchCursor {
+                    score: sort_score(&search_type, last),
+                    id: last.memory.id,
+                    fingerprint,
+                    snapshot_at: page.snapshot_at,
+                    position: position + results.len() as i64,
+                })?)
+            }
+            _ => None,
+        };
+
+        // Hybrid search's full-text retriever already ranks memories whose
+        // embeddings came from another model
+        let vector_only = match search_type {
+            SearchType::Semantic => true,
+            SearchType::Hybrid => request.query_text.is_none(),
+            SearchType::Temporal | SearchType::FullText => false,
+        };
+        if vector_only && position == 0 && (results.len() as i32) < limit {
+            results = self
+                .fill_from_incompatible_embeddings(&request, results)
+                .await?;
+        }
+
+        let (total_count, facets) = if request.include_facets.unwrap_or(false) {
+            let matched = self.matching_set_query(&request, &search_type)?;
+            let (total_count, facets) = tokio::try_join!(
+                self.count_search_results(&matched),
+                self.generate_search_facets(&matched)
+            )?;
+            (Some(total_count), Some(facets))
+        } else {
+            (None, None)
+        };
+
+        let suggestions = match request.query_text.as_deref() {
+            Some(query_text) if !query_text.trim().is_empty() => {
+                let suggestions = self
+                    .generate_query_suggestions(query_text, &results)
+                    .await?;
+                self.record_search_query(
+                    query_text,
+                    &search_type,
+                    results.len(),
+                    request.subject_id.as_deref(),
+                )
+                .await;
+                Some(suggestions)
+            }
+            _ => None,
+        };
+
+        let execution_time_ms = start_time.elapsed().as_millis() as u64;
+
+        Ok(SearchResponse {
+            results,
+            total_count,
+            facets,
+            suggestions,
+            next_cursor,
+            execution_time_ms,
+        })
+    }
+
+    async fn semantic_search(
+        &self,
+        request: &SearchRequest,
+        page: &SearchPage,
+    ) -> Result<Vec<SearchResult>> {
+        let query_embedding = if let Some(ref embedding) = request.query_embedding {
+            Vector::from(embedding.clone())
+        } else {
+            return Err(MemoryError::InvalidRequest {
+                message: "Query embedding is required for semantic search".to_string(),
+            });
+        };
+
+        let limit = request.limit.unwrap_or(10);
+        let offset = request.offset.unwrap_or(0);
+        let threshold = request.similarity_threshold.unwrap_or(0.7);
+        let dimension = query_embedding.as_slice().len();
+
+        // Use safe query builder to prevent SQL injection - include all computed columns for build_search_results()
+        let mut builder = SafeQueryBuilder::new(&format!(
+            "SELECT m.*, 
+                ({})::float4 as similarity_score,
+                m.recency_score as temporal_score,
+                m.importance_score,
+                m.relevance_score,
+                COALESCE(m.access_count, 0) as access_count,
+                m.combined_score as combined_score,
+                CASE 
+                    WHEN COALESCE(m.access_count, 0) <= 0 THEN 0.0 
+                    ELSE (LN(COALESCE(m.access_count, 0)::float + 1.0) * 0.1)::float4
+                END as access_frequency_score
+            FROM memories m WHERE m.status = 'active' AND m.embedding IS NOT NULL",
+            embedding_similarity(dimension, "$1")
+        ));
+        // Set bind index to 2 since $1 is already used for the query embedding
+        builder.bind_index = 2;
+        builder.add_namespace_filter(&self.namespace);
+        builder.add_embedding_compatibility(dimension, self.embedding_model.as_deref());
+
+        // Add filters safely
         self.add_filters_safe(request, &mut builder)?;
 
         // Add similarity threshold safely
-        builder.add_similarity_threshold(threshold as f64);
+        builder.add_similarity_threshold(dimension, threshold as f64);
 
         // Add ordering and pagination
-        builder.add_condition("ORDER BY similarity_score DESC");
+        let offset = self.add_page_conditions(
+            &mut builder,
+            page,
+            &SearchType::Semantic,
+            Some(dimension),
+            offset,
+        )?;
+        builder.add_condition("ORDER BY similarity_score DESC, m.id");
         builder.add_pagination(limit as usize, offset as usize)?;
 
         // Build query and execute with parameterized binding
@@ -684,7 +1976,11 @@ impl MemoryRepository {
         self.build_search_results(rows, request).await
     }
 
-    async fn temporal_search(&self, request: &SearchRequest) -> Result<Vec<SearchResult>> {
+    async fn temporal_search(
+        &self,
+        request: &SearchRequest,
+        page: &SearchPage,
+    ) -> Result<Vec<SearchResult>> {
         let limit = request.limit.unwrap_or(10);
         let offset = request.offset.unwrap_or(0);
 
@@ -703,64 +1999,176 @@ impl MemoryRepository {
                 END as access_frequency_score
             FROM memories m WHERE m.status = 'active'",
         );
+        builder.add_namespace_filter(&self.namespace);
 
         // Add filters safely
         self.add_filters_safe(request, &mut builder)?;
 
         // Add ordering and pagination
-        builder.add_condition("ORDER BY m.created_at DESC, m.updated_at DESC");
+        let offset =
+            self.add_page_conditions(&mut builder, page, &SearchType::Temporal, None, offset)?;
+        builder.add_condition("ORDER BY m.created_at DESC, m.id");
         builder.add_pagination(limit as usize, offset as usize)?;
 
         // Build query and execute with parameterized binding
         let query = builder.build_query();
         let sqlx_query = builder.bind_parameters(sqlx::query(&query));
 
-        let rows = sqlx_query.fetch_all(&self.pool).await?;
+        let rows = sqlx_query.fetch_all(&self.pool).await?;
+
+        self.build_search_results(rows, request).await
+    }
+
+    /// Run semantic and full-text retrieval concurrently and fuse the two
+    /// rankings with reciprocal rank fusion
+    async fn hybrid_search(
+        &self,
+        request: &SearchRequest,
+        page: &SearchPage,
+    ) -> Result<Vec<SearchResult>> {
+        let k = request.rrf_k.unwrap_or(DEFAULT_RRF_K);
+        if !k.is_finite() || k < 0.0 {
+            return Err(MemoryError::InvalidRequest {
+                message: format!("rrf_k must be a non-negative number, got {k}"),
+            });
+        }
+
+        let has_embedding = request.query_embedding.is_some();
+        let has_text = request
+            .query_text
+            .as_ref()
+            .is_some_and(|text| !text.trim().is_empty());
+        if !has_embedding && !has_text {
+            return Err(MemoryError::InvalidRequest {
+                message: "Query text or embedding is required for hybrid search".to_string(),
+            });
+        }
+
+        let limit = request.limit.unwrap_or(10).max(0) as usize;
+        let offset = match &page.after {
+            Some(_) => 0,
+            None => request.offset.unwrap_or(0).max(0) as usize,
+        };
+        let position = page
+            .after
+            .as_ref()
+            .map_or(offset, |cursor| cursor.position.max(0) as usize);
+
+        // Each retriever ranks a deeper candidate pool than the page so that
+        // results found by only one of them can still fuse into the page
+        let candidates = ((position + limit) * 3).max(50);
+        let retriever_request = SearchRequest {
+            limit: Some(candidates as i32),
+            offset: Some(0),
+            similarity_threshold: Some(request.similarity_threshold.unwrap_or(0.5)),
+            explain_score: Some(false),
+            ..request.clone()
+        };
+        // Fused scores depend on both rankings, so the keyset is applied
+        // after fusion; the retrievers only share the snapshot
+        let retriever_page = SearchPage {
+            snapshot_at: page.snapshot_at,
+            after: None,
+        };
+
+        let semantic = async {
+            if has_embedding {
+                self.semantic_search(&retriever_request, &retriever_page)
+                    .await
+            } else {
+                Ok(Vec::new())
+            }
+        };
+        let fulltext = async {
+            if has_text {
+                self.fulltext_search(&retriever_request, &retriever_page)
+                    .await
+            } else {
+                Ok(Vec::new())
+            }
+        };
+        let (semantic, fulltext) = tokio::try_join!(semantic, fulltext)?;
+
+        debug!(
+            "Hybrid search fusing {} semantic and {} full-text candidates (k = {})",
+            semantic.len(),
+            fulltext.len(),
+            k
+        );
 
-        self.build_search_results(rows, request).await
+        Ok(reciprocal_rank_fusion(
+            semantic,
+            fulltext,
+            k,
+            request.explain_score.unwrap_or(false),
+        )
+        .into_iter()
+        .filter(|result| {
+            page.after.as_ref().is_none_or(|cursor| {
+                cursor.precedes(result.combined_score as f64, result.memory.id)
+            })
+        })
+        .skip(offset)
+        .take(limit)
+        .collect())
     }
 
-    async fn hybrid_search(&self, request: &SearchRequest) -> Result<Vec<SearchResult>> {
-        // Use three-component scoring weights (default: equal weighting)
-        let _weights = request.hybrid_weights.as_ref().unwrap_or(&HybridWeights {
-            semantic_weight: 0.333,
-            temporal_weight: 0.333, // Maps to recency_score
-            importance_weight: 0.334,
-            access_frequency_weight: 0.0, // Included in relevance_score
-        });
+    /// Semantic search only compares vectors from the query's model. While
+    /// other memories still carry vectors from a different model (e.g. during
+    /// a re-embed), fill the rest of the first page with full-text matches
+    /// among them, or refuse if there is nothing to rank them by.
+    async fn fill_from_incompatible_embeddings(
+        &self,
+        request: &SearchRequest,
+        mut results: Vec<SearchResult>,
+    ) -> Result<Vec<SearchResult>> {
+        let Some(query_embedding) = request.query_embedding.as_ref() else {
+            return Ok(results);
+        };
+        let dimension = query_embedding.len() as i32;
+        let model = self.embedding_model.as_deref();
+
+        const INCOMPATIBLE: &str = "m.embedding IS NOT NULL
+            AND (m.embedding_dimension IS DISTINCT FROM $2
+                OR ($3::text IS NOT NULL AND m.embedding_model IS NOT NULL AND m.embedding_model <> $3))";
+
+        let incompatible: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM memories m WHERE m.status = 'active' AND m.namespace = $1 AND {INCOMPATIBLE}"
+        ))
+        .bind(&self.namespace)
+        .bind(dimension)
+        .bind(model)
+        .fetch_one(&self.pool)
+        .await?;
 
-        let query_embedding = if let Some(ref embedding) = request.query_embedding {
-            Vector::from(embedding.clone())
-        } else {
-            return Err(MemoryError::InvalidRequest {
-                message: "Query embedding is required for hybrid search".to_string(),
-            });
+        if incompatible == 0 {
+            return Ok(results);
+        }
+
+        let Some(query_text) = request.query_text.as_ref() else {
+            if results.is_empty() {
+                return Err(MemoryError::InvalidRequest {
+                    message: format!(
+                        "Query embedding ({}, {} dimensions) cannot be compared with {} memories embedded by another model; run `codex-memory database reembed` or search with query text",
+                        model.unwrap_or("unknown model"),
+                        dimension,
+                        incompatible
+                    ),
+                });
+            }
+            return Ok(results);
         };
 
-        let limit = request.limit.unwrap_or(10);
-        let offset = request.offset.unwrap_or(0);
-        let threshold = request.similarity_threshold.unwrap_or(0.5);
+        warn!(
+            "{} memories in namespace {} have embeddings from another model; falling back to full-text matching for them",
+            incompatible, self.namespace
+        );
 
-        // Update component scores which will automatically update the generated combined_score
-        sqlx::query(
+        let remaining = (request.limit.unwrap_or(10) as i64 - results.len() as i64).max(0);
+        let query = format!(
             r#"
-            UPDATE memories 
-            SET recency_score = calculate_recency_score(last_accessed_at, created_at, 0.005),
-                relevance_score = LEAST(1.0, 
-                    0.5 * importance_score + 
-                    0.3 * LEAST(1.0, access_count / 100.0) + 
-                    0.2
-                )
-            WHERE status = 'active' AND embedding IS NOT NULL
-            "#,
-        )
-        .execute(&self.pool)
-        .await?;
-
-        // Use the generated combined_score column for optimal P99 <1ms performance
-        let query = r#"
             SELECT m.*,
-                1 - (m.embedding <=> $1) as similarity_score,
+                ts_rank_cd(to_tsvector('english', m.content), plainto_tsquery('english', $4)) as similarity_score,
                 m.recency_score as temporal_score,
                 m.importance_score,
                 m.relevance_score,
@@ -772,24 +2180,32 @@ impl MemoryRepository {
                 END as access_frequency_score
             FROM memories m
             WHERE m.status = 'active'
-                AND m.embedding IS NOT NULL
-                AND 1 - (m.embedding <=> $1) >= $2
-            ORDER BY m.combined_score DESC, similarity_score DESC
-            LIMIT $3 OFFSET $4
-            "#;
-
-        let rows = sqlx::query(query)
-            .bind(&query_embedding)
-            .bind(threshold)
-            .bind(limit as i64)
-            .bind(offset as i64)
+                AND m.namespace = $1
+                AND {INCOMPATIBLE}
+                AND to_tsvector('english', m.content) @@ plainto_tsquery('english', $4)
+            ORDER BY similarity_score DESC
+            LIMIT $5
+            "#
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(&self.namespace)
+            .bind(dimension)
+            .bind(model)
+            .bind(query_text)
+            .bind(remaining)
             .fetch_all(&self.pool)
             .await?;
 
-        self.build_search_results(rows, request).await
+        results.extend(self.build_search_results(rows, request).await?);
+        Ok(results)
     }
 
-    async fn fulltext_search(&self, request: &SearchRequest) -> Result<Vec<SearchResult>> {
+    async fn fulltext_search(
+        &self,
+        request: &SearchRequest,
+        page: &SearchPage,
+    ) -> Result<Vec<SearchResult>> {
         let query_text =
             request
                 .query_text
@@ -801,8 +2217,8 @@ impl MemoryRepository {
         let limit = request.limit.unwrap_or(10);
         let offset = request.offset.unwrap_or(0);
 
-        let query = r#"
-            SELECT m.*,
+        let mut builder = SafeQueryBuilder::new(
+            "SELECT m.*,
                 ts_rank_cd(to_tsvector('english', m.content), plainto_tsquery('english', $1)) as similarity_score,
                 m.recency_score as temporal_score,
                 m.importance_score,
@@ -815,21 +2231,70 @@ impl MemoryRepository {
                 END as access_frequency_score
             FROM memories m
             WHERE m.status = 'active'
-                AND to_tsvector('english', m.content) @@ plainto_tsquery('english', $1)
-            ORDER BY similarity_score DESC
-            LIMIT $2 OFFSET $3
-            "#;
+                AND to_tsvector('english', m.content) @@ plainto_tsquery('english', $1)",
+        );
+        // $1 is the query text
+        builder.bind_index = 2;
+        builder.add_namespace_filter(&self.namespace);
+        self.add_filters_safe(request, &mut builder)?;
+        let offset =
+            self.add_page_conditions(&mut builder, page, &SearchType::FullText, None, offset)?;
+        builder.add_condition("ORDER BY similarity_score DESC, m.id");
+        builder.add_pagination(limit as usize, offset as usize)?;
 
-        let rows = sqlx::query(query)
-            .bind(query_text)
-            .bind(limit as i64)
-            .bind(offset as i64)
-            .fetch_all(&self.pool)
-            .await?;
+        let query = builder.build_query();
+        let sqlx_query = builder.bind_parameters(sqlx::query(&query).bind(query_text));
+
+        let rows = sqlx_query.fetch_all(&self.pool).await?;
 
         self.build_search_results(rows, request).await
     }
 
+    /// Restrict a search to its snapshot and, when resuming from a cursor,
+    /// to rows after the cursor in the search type's sort order. Semantic
+    /// searches give the `dimension` of their query vector. Returns the
+    /// offset still to apply.
+    fn add_page_conditions(
+        &self,
+        builder: &mut SafeQueryBuilder,
+        page: &SearchPage,
+        search_type: &SearchType,
+        dimension: Option<usize>,
+        offset: i64,
+    ) -> Result<i64> {
+        builder.add_snapshot_filter(page.snapshot_at);
+
+        let Some(cursor) = &page.after else {
+            return Ok(offset);
+        };
+        let similarity;
+        let (sort_expr, last) =
+            match search_type {
+                SearchType::Temporal => {
+                    let created_at = DateTime::from_timestamp_micros(cursor.score as i64)
+                        .ok_or_else(|| MemoryError::InvalidRequest {
+                            message: "Invalid search cursor: bad timestamp".to_string(),
+                        })?;
+                    ("m.created_at", QueryParameter::DateTime(created_at))
+                }
+                // Must match the similarity_score column of fulltext_search
+                SearchType::FullText => (
+                    "ts_rank_cd(to_tsvector('english', m.content), plainto_tsquery('english', $1))",
+                    QueryParameter::Float(cursor.score),
+                ),
+                // Must match the similarity_score column of semantic_search
+                SearchType::Semantic | SearchType::Hybrid => {
+                    let dimension = dimension.ok_or_else(|| MemoryError::InvalidRequest {
+                        message: "Invalid search cursor: no query embedding".to_string(),
+                    })?;
+                    similarity = format!("({})::float4", embedding_similarity(dimension, "$1"));
+                    (similarity.as_str(), QueryParameter::Float(cursor.score))
+                }
+            };
+        builder.add_keyset_condition(sort_expr, last, cursor.id);
+        Ok(0)
+    }
+
     /// Safe version of add_filters using SafeQueryBuilder to prevent SQL injection
     fn add_filters_safe(
         &self,
@@ -851,6 +2316,22 @@ impl MemoryRepository {
             );
         }
 
+        if let Some(tags) = request.tags.as_ref().filter(|tags| !tags.is_empty()) {
+            builder.add_tags_filter(tags);
+        }
+
+        if let Some(entity) = &request.entity {
+            builder.add_entity_filter(&self.namespace, &lookup_keys(entity));
+        }
+
+        // Supersession ends a memory's valid time, so a point in time
+        // already tells which of two conflicting memories was current
+        if let Some(as_of) = request.as_of {
+            builder.add_as_of_filter(as_of);
+        } else if !request.include_superseded.unwrap_or(false) {
+            builder.add_superseded_filter();
+        }
+
         Ok(())
     }
 
@@ -877,7 +2358,10 @@ impl MemoryRepository {
                 id: row.try_get("id")?,
                 content: row.try_get("content")?,
                 content_hash: row.try_get("content_hash")?,
+                namespace: row.try_get("namespace")?,
                 embedding: row.try_get("embedding")?,
+                embedding_model: row.try_get("embedding_model").unwrap_or(None),
+                embedding_dimension: row.try_get("embedding_dimension").unwrap_or(None),
                 tier: row.try_get("tier")?,
                 status: row.try_get("status")?,
                 importance_score: row.try_get("importance_score")?,
@@ -888,6 +2372,8 @@ impl MemoryRepository {
                 created_at: row.try_get("created_at")?,
                 updated_at: row.try_get("updated_at")?,
                 expires_at: row.try_get("expires_at")?,
+                valid_from: None,
+                valid_to: None,
                 consolidation_strength: row.try_get("consolidation_strength").unwrap_or(1.0),
                 decay_rate: row.try_get("decay_rate").unwrap_or(1.0),
                 recall_probability: row.try_get("recall_probability")?,
@@ -923,6 +2409,7 @@ impl MemoryRepository {
                         "recency".to_string(),
                         "importance".to_string(),
                     ],
+                    rank_fusion: None,
                 })
             } else {
                 None
@@ -951,10 +2438,9 @@ impl MemoryRepository {
 
         #[async_trait::async_trait]
         impl SearchBackend for TempSearchBackend {
-            async fn execute_search(
-                &self,
-                _request: &SearchRequest,
-            ) -> Result<Vec<sqlx::postgres::PgRow>> {
+            type Row = sqlx::postgres::PgRow;
+
+            async fn execute_search(&self, _request: &SearchRequest) -> Result<Vec<Self::Row>> {
                 unimplemented!("This is only used for validation")
             }
         }
@@ -965,22 +2451,150 @@ impl MemoryRepository {
         Ok(())
     }
 
-    async fn count_search_results(&self, _request: &SearchRequest) -> Result<i64> {
-        // Simplified count - would implement filtering logic similar to search
-        let count: i64 =
-            sqlx::query_scalar("SELECT COUNT(*) FROM memories WHERE status = 'active'")
-                .fetch_one(&self.pool)
-                .await?;
+    /// Every memory the request matches, ignoring pagination, as a query
+    /// over `memories m` for facet and count aggregations
+    fn matching_set_query(
+        &self,
+        request: &SearchRequest,
+        search_type: &SearchType,
+    ) -> Result<SafeQueryBuilder> {
+        let mut builder = SafeQueryBuilder::new(
+            "SELECT m.id, m.tier, m.created_at, m.importance_score, m.metadata
+            FROM memories m WHERE m.status = 'active'",
+        );
+        builder.add_namespace_filter(&self.namespace);
+        self.add_filters_safe(request, &mut builder)?;
+
+        let semantic = match (search_type, &request.query_embedding) {
+            (SearchType::Semantic | SearchType::Hybrid, Some(embedding)) => {
+                let default_threshold = if matches!(search_type, SearchType::Semantic) {
+                    0.7
+                } else {
+                    0.5
+                };
+                let threshold = request.similarity_threshold.unwrap_or(default_threshold);
+                let dimension = embedding.len();
+                let vector =
+                    builder.placeholder(QueryParameter::Vector(Vector::from(embedding.clone())));
+                let similarity = embedding_similarity(dimension, &vector);
+                let model = match &self.embedding_model {
+                    Some(model) => format!(
+                        "(m.embedding_model = {} OR m.embedding_model IS NULL)",
+                        builder.placeholder(QueryParameter::Text(model.clone()))
+                    ),
+                    None => "TRUE".to_string(),
+                };
+                let threshold = builder.placeholder(QueryParameter::Float(threshold as f64));
+                Some(format!(
+                    "(m.embedding IS NOT NULL AND m.embedding_dimension = {dimension} AND {model} AND {similarity} >= {threshold})"
+                ))
+            }
+            _ => None,
+        };
+
+        let fulltext = match (search_type, request.query_text.as_deref()) {
+            (SearchType::FullText | SearchType::Hybrid, Some(text)) if !text.trim().is_empty() => {
+                let text = builder.placeholder(QueryParameter::Text(text.to_string()));
+                Some(format!(
+                    "to_tsvector('english', m.content) @@ plainto_tsquery('english', {text})"
+                ))
+            }
+            _ => None,
+        };
+
+        let relevance: Vec<String> = semantic.into_iter().chain(fulltext).collect();
+        if !matches!(search_type, SearchType::Temporal) {
+            if relevance.is_empty() {
+                builder.add_condition("AND FALSE");
+            } else {
+                builder.add_condition(&format!("AND ({})", relevance.join(" OR ")));
+            }
+        }
+
+        Ok(builder)
+    }
+
+    async fn count_search_results(&self, matched: &SafeQueryBuilder) -> Result<i64> {
+        let query = format!("SELECT COUNT(*) FROM ({}) matched", matched.build_query());
+        let (count,): (i64,) = matched
+            .bind_parameters_as(sqlx::query_as(&query))
+            .fetch_one(&self.pool)
+            .await?;
         Ok(count)
     }
 
-    async fn generate_search_facets(&self, _request: &SearchRequest) -> Result<SearchFacets> {
-        // Generate tier facets
-        let tier_rows: Vec<(String, i64)> = sqlx::query_as(
-            "SELECT tier, COUNT(*) FROM memories WHERE status = 'active' GROUP BY tier",
-        )
-        .fetch_all(&self.pool)
-        .await?;
+    /// Tier, date, importance and tag facets over the matched set
+    async fn generate_search_facets(&self, matched: &SafeQueryBuilder) -> Result<SearchFacets> {
+        let matched_query = matched.build_query();
+
+        let tier_query =
+            format!("WITH matched AS ({matched_query}) SELECT tier::text, COUNT(*) FROM matched GROUP BY tier");
+
+        let intervals = DATE_HISTOGRAM_INTERVALS
+            .iter()
+            .map(|(unit, max_buckets)| format!("('{unit}', {max_buckets})"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let date_query = format!(
+            r#"
+            WITH matched AS ({matched_query})
+            SELECT unit, bucket, count FROM (
+                SELECT i.unit, i.max_buckets,
+                    date_trunc(i.unit, m.created_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS bucket,
+                    COUNT(*) AS count,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY i.unit
+                        ORDER BY date_trunc(i.unit, m.created_at AT TIME ZONE 'UTC') DESC
+                    ) AS recency
+                FROM matched m
+                CROSS JOIN (VALUES {intervals}) AS i(unit, max_buckets)
+                GROUP BY i.unit, i.max_buckets, date_trunc(i.unit, m.created_at AT TIME ZONE 'UTC')
+            ) buckets
+            WHERE recency <= max_buckets
+            ORDER BY unit, bucket DESC
+            "#
+        );
+
+        // width_bucket returns the index of the first bound above the score
+        let bounds = IMPORTANCE_FACET_BUCKETS[..IMPORTANCE_FACET_BUCKETS.len() - 1]
+            .iter()
+            .map(|(_, max, _)| max.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let importance_query = format!(
+            "WITH matched AS ({matched_query})
+            SELECT width_bucket(importance_score, ARRAY[{bounds}]::float8[]), COUNT(*)
+            FROM matched GROUP BY 1"
+        );
+
+        let tag_query = format!(
+            r#"
+            WITH matched AS ({matched_query})
+            SELECT t.tag, COUNT(*) FROM matched m
+            CROSS JOIN LATERAL (
+                SELECT DISTINCT jsonb_array_elements_text(m.metadata->'tags') AS tag
+                WHERE jsonb_typeof(m.metadata->'tags') = 'array'
+            ) t
+            GROUP BY t.tag
+            ORDER BY COUNT(*) DESC, t.tag
+            LIMIT {MAX_TAG_FACETS}
+            "#
+        );
+
+        let (tier_rows, date_rows, importance_rows, tag_rows) = tokio::try_join!(
+            matched
+                .bind_parameters_as::<(String, i64)>(sqlx::query_as(&tier_query))
+                .fetch_all(&self.pool),
+            matched
+                .bind_parameters_as::<(String, DateTime<Utc>, i64)>(sqlx::query_as(&date_query))
+                .fetch_all(&self.pool),
+            matched
+                .bind_parameters_as::<(i32, i64)>(sqlx::query_as(&importance_query))
+                .fetch_all(&self.pool),
+            matched
+                .bind_parameters_as::<(String, i64)>(sqlx::query_as(&tag_query))
+                .fetch_all(&self.pool),
+        )?;
 
         let mut tiers = HashMap::new();
         for (tier_str, count) in tier_rows {
@@ -989,56 +2603,105 @@ impl MemoryRepository {
             }
         }
 
-        // Generate date histogram (simplified)
-        let date_histogram = vec![DateBucket {
-            date: Utc::now(),
-            count: 10,
-            interval: "day".to_string(),
-        }];
-
-        // Generate importance ranges
-        let importance_ranges = vec![
-            ImportanceRange {
-                min: 0.0,
-                max: 0.3,
-                count: 5,
-                label: "Low".to_string(),
-            },
-            ImportanceRange {
-                min: 0.3,
-                max: 0.7,
-                count: 15,
-                label: "Medium".to_string(),
-            },
-            ImportanceRange {
-                min: 0.7,
-                max: 1.0,
-                count: 8,
-                label: "High".to_string(),
-            },
-        ];
+        let date_histogram = date_rows
+            .into_iter()
+            .map(|(interval, date, count)| DateBucket {
+                date,
+                count,
+                interval,
+            })
+            .collect();
+
+        let importance_counts: HashMap<i32, i64> = importance_rows.into_iter().collect();
+        let importance_ranges = IMPORTANCE_FACET_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(index, (min, max, label))| ImportanceRange {
+                min: *min,
+                max: *max,
+                count: importance_counts.get(&(index as i32)).copied().unwrap_or(0),
+                label: label.to_string(),
+            })
+            .collect();
 
         Ok(SearchFacets {
             tiers,
             date_histogram,
             importance_ranges,
-            tags: HashMap::new(), // Would extract from metadata
+            tags: tag_rows.into_iter().collect(),
         })
     }
 
-    async fn generate_query_suggestions(&self, _request: &SearchRequest) -> Result<Vec<String>> {
-        // Simplified implementation - would use ML model or query history
-        Ok(vec![
-            "recent code changes".to_string(),
-            "function definitions".to_string(),
-            "error handling patterns".to_string(),
-        ])
+    /// Related queries from the namespace's query log that found results,
+    /// followed by refinements from terms common among the results
+    async fn generate_query_suggestions(
+        &self,
+        query_text: &str,
+        results: &[SearchResult],
+    ) -> Result<Vec<String>> {
+        let normalized = normalize_query(query_text);
+        let terms: Vec<String> = normalized.split(' ').map(str::to_string).collect();
+
+        let logged: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT normalized_query
+            FROM search_query_log
+            WHERE namespace = $1
+                AND result_count > 0
+                AND normalized_query <> $2
+                AND string_to_array(normalized_query, ' ') && $3
+                AND created_at > NOW() - INTERVAL '90 days'
+            GROUP BY normalized_query
+            ORDER BY COUNT(*) DESC, MAX(created_at) DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(&normalized)
+        .bind(&terms)
+        .bind(MAX_SUGGESTIONS as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut suggestions = logged;
+        for refinement in refinement_suggestions(query_text, results, MAX_SUGGESTIONS) {
+            if suggestions.len() >= MAX_SUGGESTIONS {
+                break;
+            }
+            if !suggestions.contains(&refinement) {
+                suggestions.push(refinement);
+            }
+        }
+        Ok(suggestions)
     }
 
-    fn generate_cursor(&self, offset: i64, _request: &SearchRequest) -> String {
-        // Simple cursor implementation - would encode more search state in production
-        use base64::{engine::general_purpose::STANDARD, Engine};
-        STANDARD.encode(format!("offset:{offset}"))
+    /// Add a search to the query log; failures only cost future suggestions
+    async fn record_search_query(
+        &self,
+        query_text: &str,
+        search_type: &SearchType,
+        result_count: usize,
+        subject_id: Option<&str>,
+    ) {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO search_query_log
+                (namespace, query_text, normalized_query, search_type, result_count, subject_id)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(query_text)
+        .bind(normalize_query(query_text))
+        .bind(format!("{search_type:?}").to_lowercase())
+        .bind(result_count as i32)
+        .bind(subject_id)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            warn!("Failed to record search query: {}", e);
+        }
     }
 
     // Legacy method for backward compatibility
@@ -1060,13 +2723,14 @@ impl MemoryRepository {
         let memories = sqlx::query_as::<_, Memory>(
             r#"
             SELECT * FROM memories 
-            WHERE tier = $1 AND status = 'active'
+            WHERE tier = $1 AND status = 'active' AND namespace = $3
             ORDER BY importance_score DESC, updated_at DESC
             LIMIT $2
             "#,
         )
         .bind(tier)
         .bind(limit)
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -1083,9 +2747,10 @@ impl MemoryRepository {
 
         // Get current memory with lock
         let current = sqlx::query_as::<_, Memory>(
-            "SELECT * FROM memories WHERE id = $1 AND status = 'active' FOR UPDATE",
+            "SELECT * FROM memories WHERE id = $1 AND status = 'active' AND namespace = $2 FOR UPDATE",
         )
         .bind(id)
+        .bind(&self.namespace)
         .fetch_optional(&mut *tx)
         .await?
         .ok_or_else(|| MemoryError::NotFound { id: id.to_string() })?;
@@ -1174,10 +2839,12 @@ impl MemoryRepository {
             r#"
             SELECT * FROM memories 
             WHERE status = 'active' 
+                AND namespace = $1
                 AND expires_at IS NOT NULL 
                 AND expires_at < NOW()
             "#,
         )
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -1190,10 +2857,12 @@ impl MemoryRepository {
             UPDATE memories 
             SET status = 'deleted' 
             WHERE status = 'active' 
+                AND namespace = $1
                 AND expires_at IS NOT NULL 
                 AND expires_at < NOW()
             "#,
         )
+        .bind(&self.namespace)
         .execute(&self.pool)
         .await?;
 
@@ -1216,6 +2885,7 @@ impl MemoryRepository {
                 SELECT * FROM memories 
                 WHERE tier = 'working' 
                     AND status = 'active'
+                    AND namespace = $2
                     AND (
                         importance_score < 0.3 
                         OR (last_accessed_at IS NOT NULL 
@@ -1230,6 +2900,7 @@ impl MemoryRepository {
                 SELECT * FROM memories 
                 WHERE tier = 'warm' 
                     AND status = 'active'
+                    AND namespace = $2
                     AND importance_score < 0.1 
                     AND updated_at < NOW() - INTERVAL '7 days'
                 ORDER BY importance_score ASC, updated_at ASC
@@ -1246,6 +2917,7 @@ impl MemoryRepository {
 
         let memories = sqlx::query_as::<_, Memory>(query)
             .bind(limit)
+            .bind(&self.namespace)
             .fetch_all(&self.pool)
             .await?;
 
@@ -1256,8 +2928,9 @@ impl MemoryRepository {
     pub async fn get_working_memory_pressure(&self) -> Result<f64> {
         if let Some(ref config) = self.config {
             let working_count: i64 = sqlx::query_scalar(
-                "SELECT COUNT(*) FROM memories WHERE tier = 'working' AND status = 'active'",
+                "SELECT COUNT(*) FROM memories WHERE tier = 'working' AND status = 'active' AND namespace = $1",
             )
+            .bind(&self.namespace)
             .fetch_one(&self.pool)
             .await?;
 
@@ -1281,8 +2954,10 @@ impl MemoryRepository {
                 MAX(access_count) FILTER (WHERE status = 'active') as max_access_count,
                 CAST(AVG(access_count) FILTER (WHERE status = 'active') AS FLOAT8) as avg_access_count
             FROM memories
+            WHERE namespace = $1
             "#,
         )
+        .bind(&self.namespace)
         .fetch_one(&self.pool)
         .await?;
 
@@ -1306,7 +2981,7 @@ impl MemoryRepository {
                 COUNT(*) FILTER (WHERE last_accessed_at IS NULL) as never_accessed,
                 COUNT(*) FILTER (WHERE last_accessed_at > NOW() - INTERVAL '24 hours') as accessed_recently
             FROM memories 
-            WHERE status = 'active' 
+            WHERE status = 'active' AND namespace = $2
             GROUP BY tier
             ORDER BY 
                 CASE tier 
@@ -1318,6 +2993,7 @@ impl MemoryRepository {
             "#,
         )
         .bind(constants::FROZEN_MIGRATION_THRESHOLD)
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -1366,6 +3042,7 @@ impl MemoryRepository {
             SELECT * FROM memories 
             WHERE tier = $1 
             AND status = 'active'
+            AND namespace = $4
             AND (recall_probability < $2 OR recall_probability IS NULL)
             ORDER BY recall_probability ASC NULLS LAST, consolidation_strength ASC
             LIMIT $3
@@ -1374,6 +3051,7 @@ impl MemoryRepository {
         .bind(tier)
         .bind(threshold)
         .bind(limit)
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -1395,13 +3073,14 @@ impl MemoryRepository {
                 decay_rate = $3, 
                 recall_probability = $4,
                 updated_at = NOW()
-            WHERE id = $1 AND status = 'active'
+            WHERE id = $1 AND status = 'active' AND namespace = $5
             "#,
         )
         .bind(memory_id)
         .bind(consolidation_strength)
         .bind(decay_rate)
         .bind(recall_probability)
+        .bind(&self.namespace)
         .execute(&self.pool)
         .await?;
 
@@ -1458,9 +3137,10 @@ impl MemoryRepository {
 
         // Get current memory state
         let memory = sqlx::query_as::<_, Memory>(
-            "SELECT * FROM memories WHERE id = $1 AND status = 'active'",
+            "SELECT * FROM memories WHERE id = $1 AND status = 'active' AND namespace = $2",
         )
         .bind(memory_id)
+        .bind(&self.namespace)
         .fetch_one(&mut *tx)
         .await?;
 
@@ -1573,6 +3253,7 @@ impl MemoryRepository {
             r#"
             SELECT * FROM memories 
             WHERE status = 'active'
+            AND namespace = $2
             AND (next_review_at IS NULL OR next_review_at <= NOW())
             ORDER BY 
                 CASE 
@@ -1585,6 +3266,7 @@ impl MemoryRepository {
             "#,
         )
         .bind(limit)
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -1605,8 +3287,9 @@ impl MemoryRepository {
                 COUNT(CASE WHEN next_review_at <= NOW() THEN 1 END) as due_for_review,
                 AVG(current_interval_days) as avg_current_interval
             FROM memories 
-            WHERE status = 'active'
-            "#
+            WHERE status = 'active' AND namespace = $1
+            "#,
+            self.namespace
         )
         .fetch_one(&self.pool)
         .await?;
@@ -1635,9 +3318,10 @@ impl MemoryRepository {
 
         // Get the memory to freeze with validation
         let memory = sqlx::query_as::<_, Memory>(
-            "SELECT * FROM memories WHERE id = $1 AND status = 'active'",
+            "SELECT * FROM memories WHERE id = $1 AND status = 'active' AND namespace = $2",
         )
         .bind(memory_id)
+        .bind(&self.namespace)
         .fetch_optional(&mut *tx)
         .await?
         .ok_or_else(|| MemoryError::NotFound {
@@ -1699,8 +3383,8 @@ impl MemoryRepository {
                 id, original_memory_id, compressed_content, 
                 original_metadata, original_content_hash, original_embedding,
                 original_tier, freeze_reason, compression_ratio,
-                original_size_bytes, compressed_size_bytes
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                original_size_bytes, compressed_size_bytes, namespace
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             "#,
         )
         .bind(frozen_id)
@@ -1718,14 +3402,16 @@ impl MemoryRepository {
         .bind(compression_ratio)
         .bind(original_size)
         .bind(compressed_size)
+        .bind(&self.namespace)
         .execute(&mut *tx)
         .await?;
 
         // Update original memory to frozen tier
         sqlx::query(
-            "UPDATE memories SET tier = 'frozen', status = 'archived', updated_at = NOW() WHERE id = $1"
+            "UPDATE memories SET tier = 'frozen', status = 'archived', updated_at = NOW() WHERE id = $1 AND namespace = $2"
         )
         .bind(memory_id)
+        .bind(&self.namespace)
         .execute(&mut *tx)
         .await?;
 
@@ -1776,14 +3462,16 @@ impl MemoryRepository {
         let mut tx = self.pool.begin().await?;
 
         // Get the frozen memory details
-        let frozen_memory =
-            sqlx::query_as::<_, FrozenMemory>("SELECT * FROM frozen_memories WHERE id = $1")
-                .bind(frozen_id)
-                .fetch_optional(&mut *tx)
-                .await?
-                .ok_or_else(|| MemoryError::NotFound {
-                    id: frozen_id.to_string(),
-                })?;
+        let frozen_memory = sqlx::query_as::<_, FrozenMemory>(
+            "SELECT * FROM frozen_memories WHERE id = $1 AND namespace = $2",
+        )
+        .bind(frozen_id)
+        .bind(&self.namespace)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| MemoryError::NotFound {
+            id: frozen_id.to_string(),
+        })?;
 
         info!(
             "Unfreezing memory {} (compression_ratio: {:.2}:1)",
@@ -1792,8 +3480,7 @@ impl MemoryRepository {
         );
 
         // Implement intentional 2-5 second delay for frozen memory retrieval
-        let mut rng = rand::thread_rng();
-        let delay_seconds = rng.gen_range(2..=5);
+        let delay_seconds = rand::thread_rng().gen_range(2..=5);
 
         info!(
             "Applying {}-second intentional delay for frozen tier retrieval",
@@ -1853,13 +3540,14 @@ impl MemoryRepository {
                 status = 'active',
                 metadata = $3,
                 updated_at = NOW()
-            WHERE id = $4
+            WHERE id = $4 AND namespace = $5
             "#,
         )
         .bind(&decompressed_data.content)
         .bind(restoration_tier)
         .bind(&decompressed_data.metadata)
         .bind(memory_id)
+        .bind(&self.namespace)
         .execute(&mut *tx)
         .await?
         .rows_affected();
@@ -1870,8 +3558,8 @@ impl MemoryRepository {
                 r#"
                 INSERT INTO memories (
                     id, content, content_hash, embedding, tier, status,
-                    importance_score, metadata, created_at, updated_at
-                ) VALUES ($1, $2, $3, $4, $5, 'active', 0.5, $6, NOW(), NOW())
+                    importance_score, metadata, created_at, updated_at, namespace
+                ) VALUES ($1, $2, $3, $4, $5, 'active', 0.5, $6, NOW(), NOW(), $7)
                 "#,
             )
             .bind(memory_id)
@@ -1880,6 +3568,7 @@ impl MemoryRepository {
             .bind(frozen_memory.original_embedding.as_ref())
             .bind(restoration_tier)
             .bind(&decompressed_data.metadata)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await?;
 
@@ -1938,12 +3627,14 @@ impl MemoryRepository {
         let frozen_memories = sqlx::query_as::<_, FrozenMemory>(
             r#"
             SELECT * FROM frozen_memories 
+            WHERE namespace = $3
             ORDER BY frozen_at DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(limit)
         .bind(offset)
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -1959,15 +3650,16 @@ impl MemoryRepository {
         let frozen_memories = sqlx::query_as::<_, FrozenMemory>(
             r#"
             SELECT * FROM frozen_memories 
-            WHERE 
-                convert_from(compressed_content, 'UTF8') ILIKE $1
-                OR freeze_reason ILIKE $1
+            WHERE namespace = $3
+                AND (convert_from(compressed_content, 'UTF8') ILIKE $1
+                    OR freeze_reason ILIKE $1)
             ORDER BY frozen_at DESC
             LIMIT $2
             "#,
         )
         .bind(format!("%{query}%"))
         .bind(limit)
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -2008,6 +3700,7 @@ impl MemoryRepository {
 
         // Use safe query builder to prevent SQL injection
         let mut builder = SafeQueryBuilder::new("SELECT * FROM memories WHERE status = 'active'");
+        builder.add_namespace_filter(&self.namespace);
 
         // Add consolidation strength range filter
         builder.add_consolidation_strength_range(
@@ -2056,12 +3749,13 @@ impl MemoryRepository {
             SET recency_score = $2, 
                 relevance_score = $3,
                 updated_at = NOW()
-            WHERE id = $1 AND status = 'active'
+            WHERE id = $1 AND status = 'active' AND namespace = $4
             "#,
         )
         .bind(memory_id)
         .bind(recency_score)
         .bind(relevance_score)
+        .bind(&self.namespace)
         .execute(&self.pool)
         .await?;
 
@@ -2082,9 +3776,10 @@ impl MemoryRepository {
                     0.2
                 ),
                 updated_at = NOW()
-            WHERE status = 'active'
+            WHERE status = 'active' AND namespace = $1
             "#,
         )
+        .bind(&self.namespace)
         .execute(&self.pool)
         .await?;
 
@@ -2124,6 +3819,7 @@ impl MemoryRepository {
                 SELECT m.*
                 FROM memories m
                 WHERE m.status = 'active'
+                  AND m.namespace = $3
                   AND m.tier = $1
                 ORDER BY m.combined_score DESC, m.updated_at DESC
                 LIMIT $2
@@ -2131,17 +3827,20 @@ impl MemoryRepository {
             )
             .bind(tier)
             .bind(limit as i64)
+            .bind(&self.namespace)
         } else {
             sqlx::query_as::<_, Memory>(
                 r#"
                 SELECT m.*
                 FROM memories m
                 WHERE m.status = 'active'
+                  AND m.namespace = $2
                 ORDER BY m.combined_score DESC, m.updated_at DESC
                 LIMIT $1
                 "#,
             )
             .bind(limit as i64)
+            .bind(&self.namespace)
         };
 
         let memories = query.fetch_all(&self.pool).await?;
@@ -2166,6 +3865,7 @@ impl MemoryRepository {
     ) -> Result<Vec<Memory>> {
         // Use safe query builder to prevent SQL injection
         let mut builder = SafeQueryBuilder::new("SELECT * FROM memories WHERE status = 'active'");
+        builder.add_namespace_filter(&self.namespace);
 
         // Add time interval condition safely
         builder.add_last_access_interval(min_hours_since_last_processing);
@@ -2225,11 +3925,13 @@ impl MemoryRepository {
             ) as data
             WHERE memories.id = data.memory_id 
             AND memories.status = 'active'
+            AND memories.namespace = $4
             "#,
         )
         .bind(&ids)
         .bind(&strengths)
         .bind(&recall_probs)
+        .bind(&self.namespace)
         .execute(&mut *tx)
         .await?;
 
@@ -2252,11 +3954,13 @@ impl MemoryRepository {
 
         for (memory_id, target_tier) in migrations {
             // Get current tier for migration logging
-            let current_memory: Option<(MemoryTier,)> =
-                sqlx::query_as("SELECT tier FROM memories WHERE id = $1 AND status = 'active'")
-                    .bind(memory_id)
-                    .fetch_optional(&mut *tx)
-                    .await?;
+            let current_memory: Option<(MemoryTier,)> = sqlx::query_as(
+                "SELECT tier FROM memories WHERE id = $1 AND status = 'active' AND namespace = $2",
+            )
+            .bind(memory_id)
+            .bind(&self.namespace)
+            .fetch_optional(&mut *tx)
+            .await?;
 
             if let Some((current_tier,)) = current_memory {
                 // Update the tier
@@ -2303,6 +4007,9 @@ impl MemoryRepository {
         let mut builder = SafeQueryBuilder::new(
             "SELECT * FROM memories WHERE status = 'active' AND (recall_probability < $1 OR recall_probability IS NULL)"
         );
+        // $1 is the threshold bound below
+        builder.bind_index = 2;
+        builder.add_namespace_filter(&self.namespace);
 
         // Add tier filter if specified
         if let Some(tier) = tier {
@@ -2366,10 +4073,11 @@ impl MemoryRepository {
                 COUNT(*) FILTER (WHERE last_accessed_at > NOW() - INTERVAL '24 hours') as recently_accessed,
                 COUNT(*) as total_active_memories
             FROM memories 
-            WHERE status = 'active'
+            WHERE status = 'active' AND namespace = $2
             "#,
         )
         .bind(constants::COLD_MIGRATION_THRESHOLD)
+        .bind(&self.namespace)
         .fetch_one(&self.pool)
         .await?;
 
@@ -2431,12 +4139,14 @@ impl MemoryRepository {
             SELECT * FROM memories 
             WHERE tier = 'cold' 
             AND status = 'active'
+            AND namespace = $2
             AND COALESCE(recall_probability, 0) < 0.2
             ORDER BY recall_probability ASC, last_accessed_at ASC
             LIMIT $1
             "#,
         )
         .bind(batch_size as i64)
+        .bind(&self.namespace)
         .fetch_all(&self.pool)
         .await?;
 
@@ -2459,6 +4169,13 @@ impl MemoryRepository {
                 {
                     Ok(row) => {
                         let frozen_id: Uuid = row.get("frozen_id");
+
+                        // The SQL freeze function predates namespaces
+                        sqlx::query("UPDATE frozen_memories SET namespace = $2 WHERE id = $1")
+                            .bind(frozen_id)
+                            .bind(&self.namespace)
+                            .execute(&mut *tx)
+                            .await?;
                         frozen_ids.push(frozen_id);
 
                         // Estimate space saved (original content vs compressed)
@@ -2577,9 +4294,9 @@ impl MemoryRepository {
                 messages_processed, patterns_extracted, patterns_stored,
                 duplicates_filtered, processing_time_ms, config_snapshot,
                 error_message, retry_count, extraction_time_ms,
-                deduplication_time_ms, storage_time_ms, created_at
+                deduplication_time_ms, storage_time_ms, created_at, namespace
             ) VALUES (
-                $1, $2, $3, $4, $5, 0, 0, 0, 0, 0, $6, NULL, 0, 0, 0, 0, $7
+                $1, $2, $3, $4, $5, 0, 0, 0, 0, 0, $6, NULL, 0, 0, 0, 0, $7, $8
             )
             RETURNING id, session_type as "session_type: HarvestSessionType",
                      trigger_reason, started_at, completed_at,
@@ -2596,7 +4313,8 @@ impl MemoryRepository {
             now,
             HarvestSessionStatus::InProgress as HarvestSessionStatus,
             config_snapshot,
-            now
+            now,
+            self.namespace
         )
         .fetch_one(&self.pool)
         .await
@@ -2625,7 +4343,7 @@ impl MemoryRepository {
 
         // Build dynamic update query
         let mut set_clauses = Vec::new();
-        let mut param_index = 2; // $1 is reserved for session_id
+        let mut param_index = 3; // $1 and $2 are reserved for session_id and namespace
 
         if request.status.is_some() {
             set_clauses.push(format!("status = ${}", param_index));
@@ -2664,7 +4382,7 @@ impl MemoryRepository {
             r#"
             UPDATE harvest_sessions
             SET {}
-            WHERE id = $1
+            WHERE id = $1 AND namespace = $2
             RETURNING id, session_type as "session_type: HarvestSessionType",
                      trigger_reason, started_at, completed_at,
                      status as "status: HarvestSessionStatus",
@@ -2678,7 +4396,7 @@ impl MemoryRepository {
         );
 
         let mut query_builder = sqlx::query_as::<_, HarvestSession>(&query);
-        query_builder = query_builder.bind(session_id);
+        query_builder = query_builder.bind(session_id).bind(&self.namespace);
 
         if let Some(status) = request.status {
             query_builder = query_builder.bind(status as HarvestSessionStatus);
@@ -2733,9 +4451,10 @@ impl MemoryRepository {
                    deduplication_time_ms, storage_time_ms,
                    memory_usage_mb, cpu_usage_percent, created_at
             FROM harvest_sessions
-            WHERE id = $1
+            WHERE id = $1 AND namespace = $2
             "#,
-            session_id
+            session_id,
+            self.namespace
         )
         .fetch_one(&self.pool)
         .await
@@ -2761,8 +4480,10 @@ impl MemoryRepository {
                 COALESCE(AVG(processing_time_ms), 0)::FLOAT as average_processing_time_ms
             FROM harvest_sessions
             WHERE started_at > NOW() - ($1 || ' days')::INTERVAL
+              AND namespace = $2
             "#,
-            days_back
+            days_back,
+            self.namespace
         )
         .fetch_one(&self.pool)
         .await
@@ -2844,12 +4565,16 @@ impl MemoryRepository {
                 (COUNT(*) FILTER (WHERE status = 'stored')::FLOAT / COUNT(*)::FLOAT) as success_rate
             FROM harvest_patterns
             WHERE extracted_at > NOW() - ($2 || ' days')::INTERVAL
+              AND harvest_session_id IN (
+                  SELECT id FROM harvest_sessions WHERE namespace = $3
+              )
             GROUP BY pattern_type
             ORDER BY success_rate DESC, total_stored DESC
             LIMIT $1
             "#,
             limit,
-            days_back
+            days_back,
+            self.namespace
         )
         .fetch_all(&self.pool)
         .await
@@ -3174,6 +4899,7 @@ impl MemoryRepository {
         batch_size: usize,
     ) -> Result<Vec<Memory>> {
         let mut builder = SafeQueryBuilder::new("SELECT * FROM memories WHERE status = 'active'");
+        builder.add_namespace_filter(&self.namespace);
 
         // Add tier filter
         builder.add_tier_filter(&tier);
@@ -3210,10 +4936,11 @@ impl MemoryRepository {
             // Use direct SQL here as this is a simple update without complex query building
             // SafeQueryBuilder is primarily for search queries with dynamic conditions
             let result = sqlx::query(
-                "UPDATE memories SET decay_rate = $1, updated_at = NOW() WHERE id = $2 AND status = 'active'"
+                "UPDATE memories SET decay_rate = $1, updated_at = NOW() WHERE id = $2 AND status = 'active' AND namespace = $3"
             )
             .bind(new_decay_rate)
             .bind(memory_id)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await?;
 
@@ -3240,10 +4967,11 @@ impl MemoryRepository {
             // Use direct SQL here as this is a simple update without complex query building
             // SafeQueryBuilder is primarily for search queries with dynamic conditions
             let result = sqlx::query(
-                "UPDATE memories SET importance_score = $1, updated_at = NOW() WHERE id = $2 AND status = 'active'"
+                "UPDATE memories SET importance_score = $1, updated_at = NOW() WHERE id = $2 AND status = 'active' AND namespace = $3"
             )
             .bind(new_importance_score)
             .bind(memory_id)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await?;
 
@@ -3267,9 +4995,10 @@ impl MemoryRepository {
             // Use direct SQL here as this is a simple update without complex query building
             // SafeQueryBuilder is primarily for search queries with dynamic conditions
             match sqlx::query(
-                "UPDATE memories SET status = 'deleted', updated_at = NOW() WHERE id = $1 AND status = 'active'"
+                "UPDATE memories SET status = 'deleted', updated_at = NOW() WHERE id = $1 AND status = 'active' AND namespace = $2"
             )
             .bind(memory_id)
+            .bind(&self.namespace)
             .execute(&mut *tx)
             .await
             {
@@ -3286,6 +5015,183 @@ impl MemoryRepository {
         Ok(deleted_count)
     }
 
+    /// Active embeddings in this namespace grouped by model and dimension
+    pub async fn get_embedding_model_stats(&self) -> Result<Vec<EmbeddingModelStats>> {
+        let stats = sqlx::query_as::<_, EmbeddingModelStats>(
+            r#"
+            SELECT embedding_model, embedding_dimension, COUNT(*) AS memory_count
+            FROM memories
+            WHERE status = 'active' AND namespace = $1 AND embedding IS NOT NULL
+            GROUP BY embedding_model, embedding_dimension
+            ORDER BY memory_count DESC
+            "#,
+        )
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stats)
+    }
+
+    /// Namespaces holding active memories not yet embedded by `target_model`.
+    /// Unlike other queries this spans every namespace, so the re-embed job
+    /// can visit each of them.
+    pub async fn get_namespaces_needing_reembed(&self, target_model: &str) -> Result<Vec<String>> {
+        let namespaces = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT namespace
+            FROM memories
+            WHERE status = 'active' AND embedding_model IS DISTINCT FROM $1
+            ORDER BY namespace
+            "#,
+        )
+        .bind(target_model)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(namespaces)
+    }
+
+    /// Next memories (by id, after `after_id`) whose embedding is missing or
+    /// was produced by a model other than `target_model`
+    pub async fn get_reembed_candidates(
+        &self,
+        target_model: &str,
+        after_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<ReembedCandidate>> {
+        let candidates = sqlx::query_as::<_, ReembedCandidate>(
+            r#"
+            SELECT id, content, content_hash
+            FROM memories
+            WHERE status = 'active'
+                AND namespace = $1
+                AND embedding_model IS DISTINCT FROM $2
+                AND ($3::uuid IS NULL OR id > $3)
+            ORDER BY id
+            LIMIT $4
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(target_model)
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    /// Number of active memories not yet embedded by `target_model`
+    pub async fn count_reembed_remaining(&self, target_model: &str) -> Result<i64> {
+        let remaining: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM memories WHERE status = 'active' AND namespace = $1 AND embedding_model IS DISTINCT FROM $2",
+        )
+        .bind(&self.namespace)
+        .bind(target_model)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(remaining)
+    }
+
+    /// Store freshly generated embeddings attributed to `model`. Rows whose
+    /// content changed since the candidate was read are left untouched.
+    pub async fn apply_reembedded(
+        &self,
+        model: &str,
+        embeddings: &[(ReembedCandidate, Vec<f32>)],
+    ) -> Result<usize> {
+        if embeddings.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let mut updated = 0;
+
+        for (candidate, embedding) in embeddings {
+            let result = sqlx::query(
+                r#"
+                UPDATE memories
+                SET embedding = $3, embedding_model = $4, embedding_dimension = $5
+                WHERE id = $1 AND content_hash = $2 AND namespace = $6 AND status = 'active'
+                "#,
+            )
+            .bind(candidate.id)
+            .bind(&candidate.content_hash)
+            .bind(Vector::from(embedding.clone()))
+            .bind(model)
+            .bind(embedding.len() as i32)
+            .bind(&self.namespace)
+            .execute(&mut *tx)
+            .await?;
+            updated += result.rows_affected() as usize;
+        }
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Create the HNSW index serving vectors of `dimension` if it is missing
+    pub async fn ensure_embedding_index(&self, dimension: usize) -> Result<()> {
+        // Dimension is an integer we control, not user input
+        let statement = format!(
+            "CREATE INDEX IF NOT EXISTS idx_memories_embedding_hnsw_{dimension} ON memories \
+             USING hnsw ((embedding::vector({dimension})) vector_cosine_ops) \
+             WHERE embedding_dimension = {dimension} AND status = 'active'"
+        );
+        sqlx::query(&statement).execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Checkpoint of the re-embed run for `target_model` in this namespace
+    pub async fn get_reembed_progress(
+        &self,
+        target_model: &str,
+    ) -> Result<Option<ReembedProgress>> {
+        let progress = sqlx::query_as::<_, ReembedProgress>(
+            "SELECT * FROM embedding_reembed_progress WHERE namespace = $1 AND target_model = $2",
+        )
+        .bind(&self.namespace)
+        .bind(target_model)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(progress)
+    }
+
+    pub async fn save_reembed_progress(&self, progress: &ReembedProgress) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO embedding_reembed_progress (
+                namespace, target_model, target_dimension, last_memory_id,
+                memories_reembedded, memories_failed, started_at, updated_at, completed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW(), $8)
+            ON CONFLICT (namespace, target_model) DO UPDATE SET
+                target_dimension = EXCLUDED.target_dimension,
+                last_memory_id = EXCLUDED.last_memory_id,
+                memories_reembedded = EXCLUDED.memories_reembedded,
+                memories_failed = EXCLUDED.memories_failed,
+                started_at = EXCLUDED.started_at,
+                updated_at = NOW(),
+                completed_at = EXCLUDED.completed_at
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(&progress.target_model)
+        .bind(progress.target_dimension)
+        .bind(progress.last_memory_id)
+        .bind(progress.memories_reembedded)
+        .bind(progress.memories_failed)
+        .bind(progress.started_at)
+        .bind(progress.completed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Health check for the memory repository
     #[cfg(feature = "codex-dreams")]
     pub async fn health_check(&self) -> Result<bool> {
@@ -3299,9 +5205,10 @@ impl MemoryRepository {
     #[cfg(feature = "codex-dreams")]
     pub async fn get_memory_by_id(&self, id: Uuid) -> Result<Memory> {
         let memory = sqlx::query_as::<_, Memory>(
-            "SELECT * FROM memories WHERE id = $1 AND status = 'active'",
+            "SELECT * FROM memories WHERE id = $1 AND status = 'active' AND namespace = $2",
         )
         .bind(id)
+        .bind(&self.namespace)
         .fetch_one(&self.pool)
         .await?;
 
@@ -3321,6 +5228,36 @@ pub struct MemoryStatistics {
     pub avg_access_count: Option<f64>,
 }
 
+/// Number of active embeddings produced by one model/dimension pair
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, serde::Deserialize)]
+pub struct EmbeddingModelStats {
+    pub embedding_model: Option<String>,
+    pub embedding_dimension: Option<i32>,
+    pub memory_count: i64,
+}
+
+/// A memory waiting to be embedded by the target model
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReembedCandidate {
+    pub id: Uuid,
+    pub content: String,
+    pub content_hash: String,
+}
+
+/// Resumable checkpoint of a re-embed run in one namespace
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, serde::Deserialize)]
+pub struct ReembedProgress {
+    pub namespace: String,
+    pub target_model: String,
+    pub target_dimension: i32,
+    pub last_memory_id: Option<Uuid>,
+    pub memories_reembedded: i64,
+    pub memories_failed: i64,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, serde::Deserialize)]
 pub struct SimpleConsolidationStats {
     pub migration_candidates: Option<i64>,
@@ -3513,12 +5450,13 @@ mod tests {
 
         // This would be called after the first bind parameter (embedding)
         builder.bind_index = 2; // Simulate that $1 is already used for embedding
-        builder.add_similarity_threshold(0.7);
+        builder.add_similarity_threshold(768, 0.7);
 
         let query = builder.build_query();
 
-        // Verify parameterization with correct bind index
-        assert!(query.contains("embedding <=> $1"));
+        // Verify parameterization with correct bind index, in the form the
+        // per-dimension HNSW index is built on
+        assert!(query.contains("m.embedding::vector(768) <=> $1::vector(768)"));
         assert!(query.contains(">= $2"));
 
         // Verify no raw threshold value
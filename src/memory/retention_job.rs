@@ -0,0 +1,829 @@
+//! Retention Policy Job
+//!
+//! Applies declarative retention rules to stored memories. A rule selects
+//! memories older than its maximum age by tag, namespace, harvested pattern
+//! type, tier or GDPR data category, and soft-deletes, anonymizes or freezes
+//! them. Rules are read from a TOML file (`$CODEX_RETENTION_RULES`, or
+//! `<config dir>/codex-memory/retention.toml`):
+//!
+//! ```toml
+//! [[rule]]
+//! name = "scratch notes"
+//! tags = ["scratch"]
+//! max_age_days = 30
+//! action = "soft_delete"
+//!
+//! [[rule]]
+//! name = "personal data"
+//! category = "Identity"
+//! max_age_days = 365
+//! action = "anonymize"
+//! ```
+//!
+//! Key features:
+//! - The first rule matching a memory, in file order, decides its fate
+//! - Dry runs report every match without changing anything
+//! - Memories under legal hold are reported and left untouched
+//! - Anonymization redacts PII and vault tokens, strips identifying metadata,
+//!   drops entity links and vaulted values and prunes the revisions that
+//!   still hold the original content
+
+use super::error::{MemoryError, Result};
+use super::models::*;
+use super::pii_policy::{namespace_matches, PiiWritePolicy};
+use super::storage::MemoryStorage;
+use crate::embedding::SimpleEmbedder;
+use crate::security::{data_subject, DataCategory, RetentionPolicy, SUBJECT_ID_KEY};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Metadata keys that tie a memory to a person or conversation, removed on
+/// anonymization
+const IDENTIFYING_METADATA_KEYS: &[&str] = &["subject_id", "pii", "context", "source_message_id"];
+
+/// Longest maximum age a rule may have, about a century
+const MAX_AGE_DAYS: u32 = 36_525;
+
+/// What a retention rule does to the memories it selects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// Mark the memory deleted
+    SoftDelete,
+    /// Keep the memory with its personal data removed
+    Anonymize,
+    /// Compress the memory into the frozen tier
+    Freeze,
+}
+
+/// A declarative retention rule. Every selector that is set must match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionRule {
+    pub name: String,
+    /// Memories created longer ago than this are due
+    pub max_age_days: u32,
+    pub action: RetentionAction,
+    /// Memories carrying any of these tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Namespace key such as `acme` or `acme/*`
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Harvested pattern type recorded in `metadata.pattern_type`
+    #[serde(default)]
+    pub pattern_type: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_tier")]
+    pub tier: Option<MemoryTier>,
+    #[serde(default)]
+    pub category: Option<DataCategory>,
+}
+
+fn deserialize_tier<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<MemoryTier>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|tier| tier.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+impl From<&RetentionPolicy> for RetentionRule {
+    /// Policies that do not delete automatically anonymize instead
+    fn from(policy: &RetentionPolicy) -> Self {
+        Self {
+            name: format!("{:?} retention", policy.category),
+            max_age_days: policy.retention_days,
+            action: if policy.auto_delete {
+                RetentionAction::SoftDelete
+            } else {
+                RetentionAction::Anonymize
+            },
+            tags: Vec::new(),
+            namespace: None,
+            pattern_type: None,
+            tier: None,
+            category: Some(policy.category.clone()),
+        }
+    }
+}
+
+impl RetentionRule {
+    /// Creation time before which memories are due at `now`; `None` when
+    /// the maximum age reaches past the earliest representable time
+    fn due_before(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        now.checked_sub_signed(ChronoDuration::try_days(i64::from(self.max_age_days))?)
+    }
+
+    /// Whether the rule may select memories of `namespace`
+    fn covers_namespace(&self, namespace: &str) -> bool {
+        self.namespace
+            .as_deref()
+            .is_none_or(|pattern| namespace_matches(pattern, namespace))
+    }
+
+    /// Whether `memory` is due under this rule at `now`
+    pub fn matches(&self, memory: &Memory, now: DateTime<Utc>) -> bool {
+        let Some(due_before) = self.due_before(now) else {
+            return false;
+        };
+        if memory.created_at > due_before || !self.covers_namespace(&memory.namespace) {
+            return false;
+        }
+        // Nothing left to do for memories the action was already applied to
+        match self.action {
+            RetentionAction::Anonymize if memory.metadata.get("anonymized_at").is_some() => {
+                return false
+            }
+            RetentionAction::Freeze if memory.tier == MemoryTier::Frozen => return false,
+            _ => {}
+        }
+        if self.tier.is_some_and(|tier| memory.tier != tier) {
+            return false;
+        }
+        if let Some(pattern_type) = &self.pattern_type {
+            let recorded = memory.metadata.get("pattern_type").and_then(|t| t.as_str());
+            if !recorded.is_some_and(|recorded| recorded.eq_ignore_ascii_case(pattern_type)) {
+                return false;
+            }
+        }
+        if !self.tags.is_empty() {
+            let tags = memory
+                .metadata
+                .get("tags")
+                .and_then(|t| t.as_array())
+                .map(|tags| tags.iter().filter_map(|t| t.as_str()).collect::<Vec<_>>())
+                .unwrap_or_default();
+            if !self.tags.iter().any(|tag| tags.contains(&tag.as_str())) {
+                return false;
+            }
+        }
+        self.category
+            .as_ref()
+            .is_none_or(|category| *category == memory_category(memory))
+    }
+}
+
+/// GDPR data category of a memory: `metadata.data_category` when set,
+/// `Identity` when PII was detected in it and kept, `Generated` otherwise
+pub fn memory_category(memory: &Memory) -> DataCategory {
+    if let Some(category) = memory
+        .metadata
+        .get("data_category")
+        .and_then(|c| serde_json::from_value(c.clone()).ok())
+    {
+        return category;
+    }
+    let pii_kept = memory
+        .metadata
+        .get("pii")
+        .and_then(|pii| pii.get("action"))
+        .and_then(|action| action.as_str())
+        .is_some_and(|action| action != "redacted");
+    if pii_kept {
+        DataCategory::Identity
+    } else {
+        DataCategory::Generated
+    }
+}
+
+/// Retention rules in the order they are evaluated
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionRules {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<RetentionRule>,
+}
+
+impl RetentionRules {
+    /// Path of the rules file: `$CODEX_RETENTION_RULES` or the default
+    /// config directory
+    pub fn default_path() -> PathBuf {
+        std::env::var("CODEX_RETENTION_RULES")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                dirs::config_dir()
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join("codex-memory")
+                    .join("retention.toml")
+            })
+    }
+
+    /// Rules from the default path; a missing file yields no rules
+    pub fn load_default() -> Result<Self> {
+        Self::load(&Self::default_path())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            MemoryError::Configuration(format!("Failed to read retention rules {path:?}: {e}"))
+        })?;
+        Self::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let rules: Self = toml::from_str(content)
+            .map_err(|e| MemoryError::Configuration(format!("Invalid retention rules: {e}")))?;
+        let mut names = HashSet::new();
+        for rule in &rules.rules {
+            if !names.insert(rule.name.as_str()) {
+                return Err(MemoryError::Configuration(format!(
+                    "Duplicate retention rule: {}",
+                    rule.name
+                )));
+            }
+            if rule.max_age_days > MAX_AGE_DAYS {
+                return Err(MemoryError::Configuration(format!(
+                    "Retention rule '{}': max_age_days must be at most {MAX_AGE_DAYS}",
+                    rule.name
+                )));
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// First rule under which `memory` is due at `now`
+    pub fn rule_for(&self, memory: &Memory, now: DateTime<Utc>) -> Option<&RetentionRule> {
+        self.rules.iter().find(|rule| rule.matches(memory, now))
+    }
+}
+
+/// A memory that must be preserved: it cannot be deleted and the
+/// retention job leaves it alone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalHold {
+    pub memory_id: Uuid,
+    pub namespace: String,
+    pub reason: String,
+    pub placed_by: Option<String>,
+    pub placed_at: DateTime<Utc>,
+}
+
+/// Error for an attempt to delete a memory under legal hold
+pub(crate) fn legal_hold_error(id: Uuid) -> MemoryError {
+    MemoryError::InvalidRequest {
+        message: format!("Memory {id} is under legal hold and cannot be deleted"),
+    }
+}
+
+/// Configuration for the retention job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionJobConfig {
+    /// Seconds between scheduled runs
+    pub interval_seconds: u64,
+
+    /// Memories read per page while looking for due ones
+    pub batch_size: usize,
+
+    /// Report what scheduled runs would do without doing it
+    pub dry_run: bool,
+}
+
+impl Default for RetentionJobConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: 86_400,
+            batch_size: 500,
+            dry_run: false,
+        }
+    }
+}
+
+/// A memory selected by a retention rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionMatch {
+    pub memory_id: Uuid,
+    pub namespace: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Memory> for RetentionMatch {
+    fn from(memory: &Memory) -> Self {
+        Self {
+            memory_id: memory.id,
+            namespace: memory.namespace.clone(),
+            created_at: memory.created_at,
+        }
+    }
+}
+
+/// What a rule selected during a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleOutcome {
+    pub rule: String,
+    pub action: RetentionAction,
+    /// Memories the action was, or in a dry run would be, applied to
+    pub matched: Vec<RetentionMatch>,
+    /// Memories the rule selected that are under legal hold
+    pub held: Vec<RetentionMatch>,
+    pub applied: usize,
+}
+
+/// A memory the action could not be applied to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionFailure {
+    pub memory_id: Uuid,
+    pub rule: String,
+    pub error: String,
+}
+
+/// Result of a retention run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub run_id: Uuid,
+    pub dry_run: bool,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    pub memories_scanned: usize,
+    pub rules: Vec<RuleOutcome>,
+    pub failures: Vec<RetentionFailure>,
+}
+
+impl RetentionReport {
+    fn new(rules: &RetentionRules, dry_run: bool) -> Self {
+        let now = Utc::now();
+        Self {
+            run_id: Uuid::new_v4(),
+            dry_run,
+            started_at: now,
+            completed_at: now,
+            memories_scanned: 0,
+            rules: rules
+                .rules
+                .iter()
+                .map(|rule| RuleOutcome {
+                    rule: rule.name.clone(),
+                    action: rule.action,
+                    matched: Vec::new(),
+                    held: Vec::new(),
+                    applied: 0,
+                })
+                .collect(),
+            failures: Vec::new(),
+        }
+    }
+
+    fn outcome_mut(&mut self, rule: &str) -> &mut RuleOutcome {
+        self.rules
+            .iter_mut()
+            .find(|outcome| outcome.rule == rule)
+            .expect("an outcome for every rule")
+    }
+
+    pub fn total_matched(&self) -> usize {
+        self.rules.iter().map(|outcome| outcome.matched.len()).sum()
+    }
+
+    pub fn total_applied(&self) -> usize {
+        self.rules.iter().map(|outcome| outcome.applied).sum()
+    }
+
+    pub fn total_held(&self) -> usize {
+        self.rules.iter().map(|outcome| outcome.held.len()).sum()
+    }
+}
+
+/// Applies retention rules to every namespace of a store
+pub struct RetentionJob {
+    config: RetentionJobConfig,
+    rules: RetentionRules,
+    storage: Arc<dyn MemoryStorage>,
+    /// Re-embeds anonymized content so the old vector does not outlive it
+    embedder: Option<Arc<SimpleEmbedder>>,
+    redactor: Arc<PiiWritePolicy>,
+    is_running: AtomicBool,
+}
+
+impl RetentionJob {
+    pub fn new(
+        config: RetentionJobConfig,
+        rules: RetentionRules,
+        storage: Arc<dyn MemoryStorage>,
+        redactor: Arc<PiiWritePolicy>,
+    ) -> Self {
+        Self {
+            config,
+            rules,
+            storage,
+            embedder: None,
+            redactor,
+            is_running: AtomicBool::new(false),
+        }
+    }
+
+    /// Re-embed anonymized memories; without an embedder they keep the
+    /// vector of their original content
+    pub fn with_embedder(mut self, embedder: Arc<SimpleEmbedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    pub fn rules(&self) -> &RetentionRules {
+        &self.rules
+    }
+
+    /// Run on the configured interval until stopped
+    pub async fn start(&self) -> Result<()> {
+        if self.is_running.swap(true, Ordering::Relaxed) {
+            return Err(MemoryError::InvalidRequest {
+                message: "Retention job is already running".to_string(),
+            });
+        }
+        if self.rules.is_empty() {
+            info!("No retention rules configured; retention job not started");
+            self.is_running.store(false, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        info!(
+            "Starting retention job with {} rules",
+            self.rules.rules.len()
+        );
+        let mut interval = time::interval(Duration::from_secs(self.config.interval_seconds));
+        while self.is_running.load(Ordering::Relaxed) {
+            interval.tick().await;
+
+            match self.run(self.config.dry_run).await {
+                Ok(report) => info!(
+                    "Retention run {}: {} memories matched, {} actions applied, {} held, {} failed",
+                    report.run_id,
+                    report.total_matched(),
+                    report.total_applied(),
+                    report.total_held(),
+                    report.failures.len()
+                ),
+                Err(e) => error!("Retention run failed: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Request the running job to stop after the current run
+    pub fn stop(&self) {
+        info!("Stopping retention job");
+        self.is_running.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::Relaxed)
+    }
+
+    /// Apply the rules once; a dry run only reports what would be done
+    pub async fn run(&self, dry_run: bool) -> Result<RetentionReport> {
+        let mut report = RetentionReport::new(&self.rules, dry_run);
+        let now = report.started_at;
+        let Some(youngest_due) = self
+            .rules
+            .rules
+            .iter()
+            .filter_map(|rule| rule.due_before(now))
+            .max()
+        else {
+            return Ok(report);
+        };
+
+        for namespace in self.storage.list_namespaces().await? {
+            if !self
+                .rules
+                .rules
+                .iter()
+                .any(|rule| rule.covers_namespace(&namespace))
+            {
+                continue;
+            }
+            let storage = self.storage.with_namespace(&namespace);
+            let held: HashSet<Uuid> = storage
+                .legal_holds()
+                .await?
+                .into_iter()
+                .map(|hold| hold.memory_id)
+                .collect();
+
+            let mut after = None;
+            loop {
+                let page = storage
+                    .memories_created_before(youngest_due, after, self.config.batch_size as i64)
+                    .await?;
+                let Some(last) = page.last() else {
+                    break;
+                };
+                after = Some(last.id);
+                report.memories_scanned += page.len();
+
+                for memory in &page {
+                    let Some(rule) = self.rules.rule_for(memory, now) else {
+                        continue;
+                    };
+                    if held.contains(&memory.id) {
+                        debug!("Memory {} is under legal hold", memory.id);
+                        report.outcome_mut(&rule.name).held.push(memory.into());
+                        continue;
+                    }
+                    report.outcome_mut(&rule.name).matched.push(memory.into());
+                    if dry_run {
+                        continue;
+                    }
+                    match self.apply(storage.as_ref(), rule, memory).await {
+                        Ok(()) => report.outcome_mut(&rule.name).applied += 1,
+                        Err(e) => {
+                            warn!(
+                                "Retention rule '{}' failed on memory {}: {}",
+                                rule.name, memory.id, e
+                            );
+                            report.failures.push(RetentionFailure {
+                                memory_id: memory.id,
+                                rule: rule.name.clone(),
+                                error: e.to_string(),
+                            });
+                        }
+                    }
+                }
+
+                if page.len() < self.config.batch_size {
+                    break;
+                }
+            }
+        }
+
+        report.completed_at = Utc::now();
+        Ok(report)
+    }
+
+    async fn apply(
+        &self,
+        storage: &dyn MemoryStorage,
+        rule: &RetentionRule,
+        memory: &Memory,
+    ) -> Result<()> {
+        let reason = format!("retention rule '{}'", rule.name);
+        match rule.action {
+            RetentionAction::SoftDelete => storage.delete_memory(memory.id).await,
+            RetentionAction::Freeze => storage
+                .freeze_memory(memory.id, Some(reason))
+                .await
+                .map(|_| ()),
+            RetentionAction::Anonymize => {
+                let subject = data_subject(
+                    memory.metadata.get(SUBJECT_ID_KEY).and_then(|s| s.as_str()),
+                    &memory.namespace,
+                );
+                let tokens = self.redactor.tokens(&memory.content);
+                let content = self.redactor.redact(&memory.content);
+                let embedding = match &self.embedder {
+                    Some(embedder) => Some(
+                        embedder
+                            .generate_embedding(&content)
+                            .await
+                            .map_err(|e| MemoryError::EmbeddingGenerationError(e.to_string()))?,
+                    ),
+                    None => None,
+                };
+                let request = UpdateMemoryRequest {
+                    content: Some(content),
+                    embedding,
+                    tier: None,
+                    importance_score: None,
+                    metadata: Some(anonymized_metadata(&memory.metadata, &rule.name)),
+                    expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: Some("retention".to_string()),
+                    reason: Some(reason),
+                    content_hash: None,
+                };
+                storage.update_memory(memory.id, request).await?;
+                // Earlier revisions still hold the original content
+                storage.purge_memory_history(memory.id).await?;
+                storage.unlink_memory_entities(memory.id).await?;
+                if !tokens.is_empty() {
+                    storage.forget_pii_values(&subject, &tokens).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// `metadata` without identifying keys, recording the anonymization
+fn anonymized_metadata(metadata: &Value, rule: &str) -> Value {
+    let mut anonymized = match metadata {
+        Value::Object(object) => object.clone(),
+        _ => serde_json::Map::new(),
+    };
+    for key in IDENTIFYING_METADATA_KEYS {
+        anonymized.remove(*key);
+    }
+    anonymized.insert("anonymized_at".to_string(), json!(Utc::now()));
+    anonymized.insert("retention_rule".to_string(), json!(rule));
+    Value::Object(anonymized)
+}
+
+/// Run `job` in the background; it stops when [`RetentionJob::stop`] is called
+pub fn spawn_retention_job(job: Arc<RetentionJob>) -> tokio::task::JoinHandle<Result<()>> {
+    tokio::spawn(async move { job.start().await })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemoryStorage;
+
+    const RULES: &str = r#"
+        [[rule]]
+        name = "scratch notes"
+        tags = ["scratch"]
+        max_age_days = 30
+        action = "soft_delete"
+
+        [[rule]]
+        name = "personal notes"
+        tags = ["personal"]
+        tier = "working"
+        max_age_days = 90
+        action = "anonymize"
+    "#;
+
+    async fn create(
+        storage: &SqliteMemoryStorage,
+        content: &str,
+        metadata: Value,
+        age_days: i64,
+    ) -> Memory {
+        let memory = storage
+            .create_memory(CreateMemoryRequest {
+                content: content.to_string(),
+                embedding: Some(vec![1.0, 0.0]),
+                metadata: Some(metadata),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        sqlx::query("UPDATE memories SET created_at = ?1 WHERE id = ?2")
+            .bind(Utc::now() - ChronoDuration::days(age_days))
+            .bind(memory.id)
+            .execute(storage.pool())
+            .await
+            .unwrap();
+        memory
+    }
+
+    #[test]
+    fn test_rules_parse_and_match() {
+        let rules = RetentionRules::from_toml(RULES).unwrap();
+        assert_eq!(rules.rules.len(), 2);
+        assert_eq!(rules.rules[1].tier, Some(MemoryTier::Working));
+
+        let now = Utc::now();
+        let memory = Memory {
+            namespace: "acme".to_string(),
+            tier: MemoryTier::Working,
+            metadata: json!({ "tags": ["personal"] }),
+            created_at: now - ChronoDuration::days(100),
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.rule_for(&memory, now).map(|r| r.name.as_str()),
+            Some("personal notes")
+        );
+        let recent = Memory {
+            created_at: now - ChronoDuration::days(10),
+            ..memory.clone()
+        };
+        assert!(rules.rule_for(&recent, now).is_none());
+
+        // Categories and policies
+        assert_eq!(memory_category(&memory), DataCategory::Generated);
+        let flagged = Memory {
+            metadata: json!({ "pii": { "action": "flagged" } }),
+            ..memory.clone()
+        };
+        assert_eq!(memory_category(&flagged), DataCategory::Identity);
+        let rule = RetentionRule::from(&RetentionPolicy {
+            category: DataCategory::Identity,
+            retention_days: 30,
+            auto_delete: true,
+            legal_basis: "consent".to_string(),
+        });
+        assert!(rule.matches(&flagged, now) && !rule.matches(&memory, now));
+        let forever = RetentionRule {
+            max_age_days: u32::MAX,
+            ..rule
+        };
+        assert!(!forever.matches(&flagged, now));
+
+        let duplicate = format!(
+            "{RULES}\n[[rule]]\nname = \"scratch notes\"\nmax_age_days = 1\naction = \"freeze\""
+        );
+        assert!(RetentionRules::from_toml(&duplicate).is_err());
+        let too_old =
+            "[[rule]]\nname = \"forever\"\nmax_age_days = 4294967295\naction = \"freeze\"";
+        assert!(RetentionRules::from_toml(too_old).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_apply_and_legal_holds() {
+        let storage = SqliteMemoryStorage::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let scratch = create(&storage, "scratch idea", json!({ "tags": ["scratch"] }), 40).await;
+        let held = create(&storage, "held idea", json!({ "tags": ["scratch"] }), 40).await;
+        let recent = create(&storage, "recent idea", json!({ "tags": ["scratch"] }), 1).await;
+        let personal = create(
+            &storage,
+            "Mail ops@example.com or [PHONE_0123456789ab] about the launch",
+            json!({ "tags": ["personal"], "subject_id": "alice" }),
+            100,
+        )
+        .await;
+        storage
+            .link_memory_entities(
+                personal.id,
+                vec![crate::memory::EntityMention {
+                    name: "Alice".to_string(),
+                    entity_type: crate::memory::EntityType::Person,
+                    confidence: 1.0,
+                    embedding: None,
+                }],
+            )
+            .await
+            .unwrap();
+        storage.pii_subject_key("alice", b"key").await.unwrap();
+        storage
+            .vault_pii_value("[PHONE_0123456789ab]", "alice", "phone", b"sealed")
+            .await
+            .unwrap();
+        storage
+            .place_legal_hold(held.id, "litigation", Some("legal"))
+            .await
+            .unwrap();
+
+        let job = RetentionJob::new(
+            RetentionJobConfig {
+                batch_size: 2,
+                ..Default::default()
+            },
+            RetentionRules::from_toml(RULES).unwrap(),
+            Arc::new(storage.clone()),
+            Arc::new(PiiWritePolicy::disabled()),
+        );
+
+        let report = job.run(true).await.unwrap();
+        assert_eq!(report.memories_scanned, 3);
+        assert_eq!((report.total_matched(), report.total_held()), (2, 1));
+        assert_eq!(report.total_applied(), 0);
+        assert_eq!(report.rules[0].held[0].memory_id, held.id);
+        storage.get_memory(scratch.id).await.unwrap();
+
+        let report = job.run(false).await.unwrap();
+        assert_eq!(report.total_applied(), 2);
+        assert!(report.failures.is_empty());
+        assert!(storage.get_memory(scratch.id).await.is_err());
+        storage.get_memory(recent.id).await.unwrap();
+
+        let anonymized = storage.get_memory(personal.id).await.unwrap();
+        assert_eq!(
+            anonymized.content,
+            "Mail [EMAIL] or [PHONE] about the launch"
+        );
+        assert!(anonymized.metadata.get("subject_id").is_none());
+        assert_eq!(anonymized.metadata["retention_rule"], "personal notes");
+        let history = storage.memory_history(personal.id, None).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(!history[0].content.contains("example.com"));
+        assert!(storage
+            .get_memory_entities(personal.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(storage
+            .vaulted_pii_values("[PHONE_0123456789ab]")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Anonymized memories are not selected again
+        assert_eq!(job.run(false).await.unwrap().total_matched(), 0);
+
+        // Held memories cannot be deleted until released
+        assert!(matches!(
+            storage.delete_memory(held.id).await,
+            Err(MemoryError::InvalidRequest { .. })
+        ));
+        assert_eq!(storage.legal_holds().await.unwrap().len(), 1);
+        assert!(storage.release_legal_hold(held.id).await.unwrap());
+        storage.delete_memory(held.id).await.unwrap();
+    }
+}
@@ -1,7 +1,13 @@
 use async_trait::async_trait;
-use sqlx::{postgres::PgRow, Column, Row};
-use std::collections::HashSet;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use sqlx::{Column, Row};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
 
+use crate::memory::models::{
+    DateBucket, ImportanceRange, Memory, RankFusionExplanation, ScoreExplanation, SearchFacets,
+    SearchResult,
+};
 use crate::memory::{MemoryError, SearchRequest};
 
 /// Result type for search backend operations
@@ -31,8 +37,11 @@ impl std::error::Error for ColumnValidationError {}
 /// This trait ensures architectural consistency and prevents column mismatch issues.
 #[async_trait]
 pub trait SearchBackend {
+    /// Row type of the database the backend queries
+    type Row: Row;
+
     /// Execute the search operation and return raw database rows
-    async fn execute_search(&self, request: &SearchRequest) -> Result<Vec<PgRow>>;
+    async fn execute_search(&self, request: &SearchRequest) -> Result<Vec<Self::Row>>;
 
     /// Return the set of required columns that must be present in query results
     /// for build_search_results to work correctly
@@ -82,7 +91,7 @@ pub trait SearchBackend {
 
     /// Validate that the provided database rows contain all required columns
     /// This prevents runtime errors when build_search_results tries to access missing columns
-    fn validate_columns(&self, rows: &[PgRow]) -> Result<()> {
+    fn validate_columns(&self, rows: &[Self::Row]) -> Result<()> {
         if rows.is_empty() {
             return Ok(()); // No rows to validate
         }
@@ -125,10 +134,394 @@ pub fn calculate_access_frequency_score(access_count: i32) -> f32 {
     ((access_count as f32 + 1.0).ln() * 0.1).max(0.0)
 }
 
+/// Reciprocal rank fusion constant used when a request does not set `rrf_k`
+pub const DEFAULT_RRF_K: f32 = 60.0;
+
+/// Fuse semantic and full-text rankings with reciprocal rank fusion: each
+/// result scores `1 / (k + rank)` per ranking it appears in. Rank fusion needs
+/// no score normalization, so exact keyword hits (error codes, file names)
+/// surface even when their vectors are not close to the query. Both inputs
+/// must be ordered best first; the fused score replaces `combined_score`.
+pub fn reciprocal_rank_fusion(
+    semantic: Vec<SearchResult>,
+    fulltext: Vec<SearchResult>,
+    k: f32,
+    explain: bool,
+) -> Vec<SearchResult> {
+    struct Fused {
+        result: SearchResult,
+        semantic: Option<(usize, f32)>,
+        fulltext: Option<(usize, f32)>,
+    }
+
+    let mut fused: HashMap<Uuid, Fused> = HashMap::new();
+    for (index, result) in semantic.into_iter().enumerate() {
+        let entry = (index + 1, result.similarity_score);
+        fused.entry(result.memory.id).or_insert(Fused {
+            result,
+            semantic: Some(entry),
+            fulltext: None,
+        });
+    }
+    for (index, result) in fulltext.into_iter().enumerate() {
+        let entry = (index + 1, result.similarity_score);
+        fused
+            .entry(result.memory.id)
+            .and_modify(|f| {
+                f.fulltext.get_or_insert(entry);
+            })
+            .or_insert(Fused {
+                result,
+                semantic: None,
+                fulltext: Some(entry),
+            });
+    }
+
+    let contribution = |rank: Option<(usize, f32)>| rank.map_or(0.0, |(r, _)| 1.0 / (k + r as f32));
+    let mut results: Vec<SearchResult> = fused
+        .into_values()
+        .map(|f| {
+            let semantic_contribution = contribution(f.semantic);
+            let fulltext_contribution = contribution(f.fulltext);
+            let total = semantic_contribution + fulltext_contribution;
+
+            let mut result = f.result;
+            result.combined_score = total;
+            result.score_explanation = explain.then(|| {
+                let mut factors = Vec::new();
+                if let Some((rank, _)) = f.semantic {
+                    factors.push(format!("semantic rank {rank}"));
+                }
+                if let Some((rank, _)) = f.fulltext {
+                    factors.push(format!("full-text rank {rank}"));
+                }
+                ScoreExplanation {
+                    semantic_contribution,
+                    temporal_contribution: 0.0,
+                    importance_contribution: 0.0,
+                    access_frequency_contribution: 0.0,
+                    total_score: total,
+                    factors,
+                    rank_fusion: Some(RankFusionExplanation {
+                        k,
+                        semantic_rank: f.semantic.map(|(rank, _)| rank),
+                        semantic_score: f.semantic.map(|(_, score)| score),
+                        fulltext_rank: f.fulltext.map(|(rank, _)| rank),
+                        fulltext_score: f.fulltext.map(|(_, score)| score),
+                    }),
+                }
+            });
+            result
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.combined_score
+            .partial_cmp(&a.combined_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.memory.id.cmp(&b.memory.id))
+    });
+    results
+}
+
+/// Importance facet buckets as `(min, max, label)`; `max` is exclusive
+/// except for the last bucket
+pub const IMPORTANCE_FACET_BUCKETS: [(f32, f32, &str); 3] =
+    [(0.0, 0.3, "Low"), (0.3, 0.7, "Medium"), (0.7, 1.0, "High")];
+
+/// Date histogram intervals and how many of the most recent non-empty
+/// buckets to report for each
+pub const DATE_HISTOGRAM_INTERVALS: [(&str, usize); 3] = [("day", 30), ("week", 26), ("month", 24)];
+
+/// Most frequent tags reported in facets
+pub const MAX_TAG_FACETS: usize = 50;
+
+/// Start of the `interval` bucket containing `date`, matching Postgres
+/// `date_trunc` (weeks start on Monday)
+pub fn truncate_date(date: DateTime<Utc>, interval: &str) -> DateTime<Utc> {
+    let day = date.date_naive();
+    let start = match interval {
+        "week" => day - Duration::days(day.weekday().num_days_from_monday() as i64),
+        "month" => day.with_day(1).unwrap_or(day),
+        _ => day,
+    };
+    Utc.from_utc_datetime(&start.and_hms_opt(0, 0, 0).unwrap_or_default())
+}
+
+/// Facets over every memory a search matched, for backends that hold the
+/// matched set in memory
+pub fn compute_facets<'a>(memories: impl IntoIterator<Item = &'a Memory>) -> SearchFacets {
+    let mut tiers = HashMap::new();
+    let mut dates: Vec<BTreeMap<DateTime<Utc>, i64>> =
+        vec![BTreeMap::new(); DATE_HISTOGRAM_INTERVALS.len()];
+    let mut importance = [0_i64; IMPORTANCE_FACET_BUCKETS.len()];
+    let mut tags: HashMap<String, i64> = HashMap::new();
+
+    for memory in memories {
+        *tiers.entry(memory.tier).or_insert(0) += 1;
+
+        for (buckets, (interval, _)) in dates.iter_mut().zip(DATE_HISTOGRAM_INTERVALS) {
+            *buckets
+                .entry(truncate_date(memory.created_at, interval))
+                .or_insert(0) += 1;
+        }
+
+        let score = memory.importance_score as f32;
+        let bucket = IMPORTANCE_FACET_BUCKETS
+            .iter()
+            .position(|(_, max, _)| score < *max)
+            .unwrap_or(IMPORTANCE_FACET_BUCKETS.len() - 1);
+        importance[bucket] += 1;
+
+        if let Some(memory_tags) = memory.metadata.get("tags").and_then(|t| t.as_array()) {
+            let unique: HashSet<&str> = memory_tags.iter().filter_map(|t| t.as_str()).collect();
+            for tag in unique {
+                *tags.entry(tag.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let date_histogram = dates
+        .into_iter()
+        .zip(DATE_HISTOGRAM_INTERVALS)
+        .flat_map(|(buckets, (interval, max_buckets))| {
+            buckets
+                .into_iter()
+                .rev()
+                .take(max_buckets)
+                .map(move |(date, count)| DateBucket {
+                    date,
+                    count,
+                    interval: interval.to_string(),
+                })
+        })
+        .collect();
+
+    let importance_ranges = IMPORTANCE_FACET_BUCKETS
+        .iter()
+        .zip(importance)
+        .map(|((min, max, label), count)| ImportanceRange {
+            min: *min,
+            max: *max,
+            count,
+            label: label.to_string(),
+        })
+        .collect();
+
+    let mut ranked_tags: Vec<(String, i64)> = tags.into_iter().collect();
+    ranked_tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked_tags.truncate(MAX_TAG_FACETS);
+
+    SearchFacets {
+        tiers,
+        date_histogram,
+        importance_ranges,
+        tags: ranked_tags.into_iter().collect(),
+    }
+}
+
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "are", "was", "were", "has", "have",
+    "had", "not", "but", "you", "your", "our", "its", "into", "about", "when", "what", "which",
+    "will", "would", "should", "could", "can", "all", "any", "use", "used", "using", "also",
+    "than", "then", "there", "their", "they", "them", "been", "being", "how", "why", "who",
+];
+
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace()
+        .map(|term| {
+            term.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|term| !term.is_empty())
+}
+
+/// Lower-cased query terms joined by single spaces, used to group logged
+/// queries that differ only in case, spacing or punctuation
+pub fn normalize_query(text: &str) -> String {
+    terms(text).collect::<Vec<_>>().join(" ")
+}
+
+/// Most query suggestions returned with a search
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Query refinements from terms that occur in several results but not in
+/// the query, most frequent first
+pub fn refinement_suggestions(query: &str, results: &[SearchResult], max: usize) -> Vec<String> {
+    let query = normalize_query(query);
+    let query_terms: HashSet<&str> = query.split(' ').collect();
+
+    let mut document_frequency: HashMap<String, usize> = HashMap::new();
+    for result in results {
+        let unique: HashSet<String> = terms(&result.memory.content)
+            .filter(|term| {
+                term.chars().count() >= 3
+                    && !term.chars().all(|c| c.is_ascii_digit())
+                    && !STOP_WORDS.contains(&term.as_str())
+                    && !query_terms.contains(term.as_str())
+            })
+            .collect();
+        for term in unique {
+            *document_frequency.entry(term).or_insert(0) += 1;
+        }
+    }
+
+    let mut frequent: Vec<(String, usize)> = document_frequency
+        .into_iter()
+        .filter(|(_, count)| *count >= 2)
+        .collect();
+    frequent.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    frequent
+        .into_iter()
+        .take(max)
+        .map(|(term, _)| format!("{query} {term}"))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn result(id: Uuid, similarity_score: f32) -> SearchResult {
+        SearchResult {
+            memory: Memory {
+                id,
+                ..Memory::default()
+            },
+            similarity_score,
+            temporal_score: None,
+            importance_score: 0.5,
+            access_frequency_score: None,
+            combined_score: similarity_score,
+            score_explanation: None,
+        }
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let semantic = vec![result(a, 0.9), result(b, 0.8)];
+        // An exact identifier match missed by the vector search
+        let fulltext = vec![result(c, 0.4), result(b, 0.2)];
+
+        let fused = reciprocal_rank_fusion(semantic, fulltext, DEFAULT_RRF_K, true);
+        let ids: Vec<Uuid> = fused.iter().map(|r| r.memory.id).collect();
+
+        // b appears in both rankings and wins; a and c tie on rank 1 of one list
+        assert_eq!(ids[0], b);
+        assert_eq!(ids.len(), 3);
+        assert!((fused[0].combined_score - (1.0 / 62.0 + 1.0 / 62.0)).abs() < 1e-6);
+        assert!((fused[1].combined_score - 1.0 / 61.0).abs() < 1e-6);
+
+        let explanation = fused[0]
+            .score_explanation
+            .as_ref()
+            .and_then(|e| e.rank_fusion.as_ref())
+            .expect("rank fusion explanation");
+        assert_eq!(explanation.semantic_rank, Some(2));
+        assert_eq!(explanation.fulltext_rank, Some(2));
+        assert_eq!(explanation.fulltext_score, Some(0.2));
+        // The semantic result is kept, so similarity stays the vector score
+        assert_eq!(fused[0].similarity_score, 0.8);
+    }
+
+    fn memory(created_at: &str, importance_score: f64, tags: &[&str]) -> Memory {
+        Memory {
+            created_at: created_at.parse().expect("timestamp"),
+            importance_score,
+            metadata: serde_json::json!({ "tags": tags }),
+            ..Memory::default()
+        }
+    }
+
+    #[test]
+    fn test_compute_facets() {
+        let memories = [
+            memory("2025-03-03T10:00:00Z", 0.1, &["rust", "db"]),
+            memory("2025-03-04T10:00:00Z", 0.5, &["rust"]),
+            memory("2025-04-20T10:00:00Z", 0.7, &[]),
+            memory("2025-04-20T18:00:00Z", 1.0, &["rust", "rust"]),
+        ];
+
+        let facets = compute_facets(&memories);
+
+        let counts = |interval: &str| -> Vec<(String, i64)> {
+            facets
+                .date_histogram
+                .iter()
+                .filter(|b| b.interval == interval)
+                .map(|b| (b.date.format("%Y-%m-%d").to_string(), b.count))
+                .collect()
+        };
+        assert_eq!(
+            counts("day"),
+            vec![
+                ("2025-04-20".to_string(), 2),
+                ("2025-03-04".to_string(), 1),
+                ("2025-03-03".to_string(), 1)
+            ]
+        );
+        // 2025-03-03 is a Monday
+        assert_eq!(
+            counts("week"),
+            vec![("2025-04-14".to_string(), 2), ("2025-03-03".to_string(), 2)]
+        );
+        assert_eq!(
+            counts("month"),
+            vec![("2025-04-01".to_string(), 2), ("2025-03-01".to_string(), 2)]
+        );
+
+        let importance: Vec<i64> = facets.importance_ranges.iter().map(|r| r.count).collect();
+        assert_eq!(importance, vec![1, 1, 2]);
+
+        assert_eq!(
+            facets
+                .tiers
+                .get(&crate::memory::models::MemoryTier::Working),
+            Some(&4)
+        );
+        assert_eq!(facets.tags.get("rust"), Some(&3));
+        assert_eq!(facets.tags.get("db"), Some(&1));
+    }
+
+    #[test]
+    fn test_query_suggestion_terms() {
+        assert_eq!(
+            normalize_query("  Fix   E0502, in Parser! "),
+            "fix e0502 in parser"
+        );
+
+        let results: Vec<SearchResult> = [
+            "Borrow checker error in the parser module",
+            "Parser borrow issue fixed by cloning",
+            "Unrelated deployment notes",
+        ]
+        .iter()
+        .map(|content| SearchResult {
+            memory: Memory {
+                content: content.to_string(),
+                ..Memory::default()
+            },
+            ..result(Uuid::new_v4(), 0.5)
+        })
+        .collect();
+
+        assert_eq!(
+            refinement_suggestions("Parser", &results, 3),
+            vec!["parser borrow".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion_k_controls_rank_weight() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let fused = reciprocal_rank_fusion(vec![result(a, 0.9)], vec![result(b, 0.5)], 0.0, false);
+
+        assert_eq!(fused[0].combined_score, 1.0);
+        assert!(fused[0].score_explanation.is_none());
+    }
+
     #[test]
     fn test_calculate_access_frequency_score() {
         // Test edge cases
@@ -155,7 +548,9 @@ mod tests {
 
         #[async_trait]
         impl SearchBackend for MockSearchBackend {
-            async fn execute_search(&self, _request: &SearchRequest) -> Result<Vec<PgRow>> {
+            type Row = sqlx::postgres::PgRow;
+
+            async fn execute_search(&self, _request: &SearchRequest) -> Result<Vec<Self::Row>> {
                 unimplemented!()
             }
         }
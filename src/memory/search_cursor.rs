@@ -0,0 +1,247 @@
+//! Signed keyset cursors for search pagination
+//!
+//! A cursor records where the previous page ended as the `(score, id)` of
+//! its last result in the search's sort order, together with a fingerprint
+//! of the query and the snapshot time of the first page. Later pages resume
+//! strictly after that key and only see memories created before the
+//! snapshot, so concurrent inserts neither shift nor duplicate results.
+//! Cursors are signed with HMAC-SHA256 so clients cannot forge positions or
+//! replay a cursor against a different query.
+
+use super::error::{MemoryError, Result};
+use super::models::{SearchRequest, SearchResult, SearchType};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Position after the last result of a search page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchCursor {
+    /// Sort score of the last result: similarity for semantic and full-text
+    /// search, the fused combined score for hybrid search and the creation
+    /// time in microseconds for temporal search
+    pub score: f64,
+    /// Id of the last result, breaking ties between equal scores
+    pub id: Uuid,
+    /// `query_fingerprint` of the request that produced the page
+    pub fingerprint: String,
+    /// Memories created after this time are excluded from every page
+    pub snapshot_at: DateTime<Utc>,
+    /// Results returned by all pages so far
+    pub position: i64,
+}
+
+impl SearchCursor {
+    /// Whether `(score, id)` sorts after this cursor, i.e. by score
+    /// descending and then id ascending
+    pub fn precedes(&self, score: f64, id: Uuid) -> bool {
+        score < self.score || (score == self.score && id > self.id)
+    }
+}
+
+/// Score `result` is sorted by, descending, under `search_type`
+pub fn sort_score(search_type: &SearchType, result: &SearchResult) -> f64 {
+    match search_type {
+        SearchType::Semantic | SearchType::FullText => result.similarity_score as f64,
+        SearchType::Hybrid => result.combined_score as f64,
+        SearchType::Temporal => result.memory.created_at.timestamp_micros() as f64,
+    }
+}
+
+/// Signs and verifies search cursors
+#[derive(Clone)]
+pub struct CursorSigner {
+    key: Arc<[u8]>,
+}
+
+impl CursorSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        Self { key: secret.into() }
+    }
+
+    /// Signer with a random key; its cursors are only valid in this process
+    pub fn random() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self::new(&key)
+    }
+
+    fn mac(&self, payload: &[u8]) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+
+    /// Encode as `<payload>.<signature>`, both URL-safe base64
+    pub fn encode(&self, cursor: &SearchCursor) -> Result<String> {
+        let payload = serde_json::to_vec(cursor)?;
+        let signature = self.mac(&payload).finalize().into_bytes();
+        Ok(format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(&payload),
+            URL_SAFE_NO_PAD.encode(signature)
+        ))
+    }
+
+    /// Verify the signature and decode the cursor
+    pub fn decode(&self, token: &str) -> Result<SearchCursor> {
+        let invalid = |reason: &str| MemoryError::InvalidRequest {
+            message: format!("Invalid search cursor: {reason}"),
+        };
+
+        let (payload, signature) = token.split_once('.').ok_or_else(|| invalid("malformed"))?;
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| invalid("malformed"))?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| invalid("malformed"))?;
+
+        self.mac(&payload)
+            .verify_slice(&signature)
+            .map_err(|_| invalid("signature mismatch"))?;
+
+        serde_json::from_slice(&payload).map_err(|_| invalid("malformed"))
+    }
+}
+
+impl std::fmt::Debug for CursorSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorSigner").finish_non_exhaustive()
+    }
+}
+
+/// Hash of everything that decides which memories a search returns and in
+/// what order. Page size, offset and presentation options are excluded; the
+/// query embedding only counts when there is no query text, since it is
+/// normally derived from the text.
+pub fn query_fingerprint(
+    namespace: &str,
+    search_type: &SearchType,
+    request: &SearchRequest,
+) -> String {
+    let embedding = match (&request.query_text, &request.query_embedding) {
+        (None, Some(embedding)) => {
+            let mut hasher = Sha256::new();
+            for value in embedding {
+                hasher.update(value.to_le_bytes());
+            }
+            Some(hex::encode(hasher.finalize()))
+        }
+        _ => None,
+    };
+
+    let state = json!({
+        "namespace": namespace,
+        "search_type": search_type,
+        "query_text": request.query_text,
+        "query_embedding": embedding,
+        "tier": request.tier,
+        "date_range": request.date_range,
+        "importance_range": request.importance_range,
+        "metadata_filters": request.metadata_filters,
+        "tags": request.tags,
+        "entity": request.entity,
+        "include_superseded": request.include_superseded,
+        "as_of": request.as_of,
+        "similarity_threshold": request.similarity_threshold,
+        "rrf_k": request.rrf_k,
+    });
+
+    hex::encode(Sha256::digest(state.to_string().as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> SearchCursor {
+        SearchCursor {
+            score: 0.82,
+            id: Uuid::new_v4(),
+            fingerprint: "abc".to_string(),
+            snapshot_at: Utc::now(),
+            position: 10,
+        }
+    }
+
+    #[test]
+    fn test_cursor_round_trip_and_tampering() {
+        let signer = CursorSigner::new(b"cursor-secret");
+        let cursor = cursor();
+        let token = signer.encode(&cursor).expect("encode cursor");
+
+        assert_eq!(signer.decode(&token).expect("decode cursor"), cursor);
+
+        let other = CursorSigner::new(b"another-secret");
+        assert!(other.decode(&token).is_err());
+
+        let forged = SearchCursor {
+            position: 0,
+            ..cursor
+        };
+        let forged_payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&forged).expect("json"));
+        let (_, signature) = token.split_once('.').expect("signature");
+        assert!(signer
+            .decode(&format!("{forged_payload}.{signature}"))
+            .is_err());
+        assert!(signer.decode("b2Zmc2V0OjEw").is_err());
+    }
+
+    #[test]
+    fn test_query_fingerprint() {
+        let request = SearchRequest {
+            query_text: Some("deploy checklist".to_string()),
+            limit: Some(10),
+            ..Default::default()
+        };
+        let fingerprint = query_fingerprint("default", &SearchType::Hybrid, &request);
+
+        let next_page = SearchRequest {
+            limit: Some(25),
+            offset: Some(10),
+            query_embedding: Some(vec![0.1, 0.2]),
+            ..request.clone()
+        };
+        assert_eq!(
+            query_fingerprint("default", &SearchType::Hybrid, &next_page),
+            fingerprint
+        );
+
+        let other_query = SearchRequest {
+            query_text: Some("release checklist".to_string()),
+            ..request.clone()
+        };
+        assert_ne!(
+            query_fingerprint("default", &SearchType::Hybrid, &other_query),
+            fingerprint
+        );
+        assert_ne!(
+            query_fingerprint("team-a", &SearchType::Hybrid, &request),
+            fingerprint
+        );
+        assert_ne!(
+            query_fingerprint("default", &SearchType::FullText, &request),
+            fingerprint
+        );
+    }
+
+    #[test]
+    fn test_cursor_order() {
+        let cursor = cursor();
+        assert!(cursor.precedes(0.5, Uuid::nil()));
+        assert!(cursor.precedes(0.82, Uuid::max()));
+        assert!(!cursor.precedes(0.82, cursor.id));
+        assert!(!cursor.precedes(0.9, Uuid::max()));
+    }
+}
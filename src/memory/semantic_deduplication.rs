@@ -432,7 +432,10 @@ impl SemanticDeduplicationEngine {
                 id: row.get("id"),
                 content: row.get("content"),
                 content_hash: row.get("content_hash"),
+                namespace: row.get("namespace"),
                 embedding: row.get("embedding"),
+                embedding_model: row.try_get("embedding_model").unwrap_or(None),
+                embedding_dimension: row.try_get("embedding_dimension").unwrap_or(None),
                 tier: row.get("tier"),
                 status: row.get("status"),
                 importance_score: row.get("importance_score"),
@@ -443,6 +446,8 @@ impl SemanticDeduplicationEngine {
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
                 expires_at: row.get("expires_at"),
+                valid_from: None,
+                valid_to: None,
                 consolidation_strength: row.get("consolidation_strength"),
                 decay_rate: row.get("decay_rate"),
                 recall_probability: row.get("recall_probability"),
@@ -1446,10 +1451,10 @@ impl MemoryMerger {
                 parent_id, created_at, updated_at, expires_at,
                 consolidation_strength, decay_rate, recall_probability,
                 recency_score, relevance_score, is_merged_result,
-                original_memory_count, merge_generation
+                original_memory_count, merge_generation, namespace
             ) VALUES (
                 $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
-                $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22
+                $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23
             ) RETURNING *
         "#;
 
@@ -1483,6 +1488,7 @@ impl MemoryMerger {
                     .unwrap_or(0)
                     + 1,
             ) // merge_generation
+            .bind(&primary_memory.namespace)
             .fetch_one(&mut **transaction)
             .await
             .map_err(|e| MemoryError::DatabaseError {
@@ -1493,7 +1499,10 @@ impl MemoryMerger {
             id: row.get("id"),
             content: row.get("content"),
             content_hash: row.get("content_hash"),
+            namespace: row.get("namespace"),
             embedding: row.get("embedding"),
+            embedding_model: row.try_get("embedding_model").unwrap_or(None),
+            embedding_dimension: row.try_get("embedding_dimension").unwrap_or(None),
             tier: row.get("tier"),
             status: row.get("status"),
             importance_score: row.get("importance_score"),
@@ -1504,6 +1513,8 @@ impl MemoryMerger {
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             expires_at: row.get("expires_at"),
+            valid_from: None,
+            valid_to: None,
             consolidation_strength: row.get("consolidation_strength"),
             decay_rate: row.get("decay_rate"),
             recall_probability: row.get("recall_probability"),
@@ -1539,6 +1550,7 @@ impl MemoryMerger {
             metadata: Some(metadata),
             parent_id: None,
             expires_at: primary_memory.expires_at,
+            ..Default::default()
         };
 
         self.repository.create_memory(create_request).await
@@ -1,5 +1,11 @@
 use crate::embedding::EmbeddingService;
-use crate::memory::{ImportanceAssessmentPipeline, Memory, MemoryRepository, MemoryTier};
+use crate::memory::models::DEFAULT_NAMESPACE;
+use crate::memory::{
+    index_memory_entities, ContradictionDetector, EncryptedMemoryStorage, EntityExtractor,
+    FieldEncryption, ImportanceAssessmentPipeline, Memory, MemoryRepository, MemoryStorage,
+    MemoryTier, PiiWritePolicy,
+};
+use crate::security::{data_subject, SUBJECT_ID_KEY};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use futures::future;
@@ -8,7 +14,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::{Mutex, RwLock, Semaphore};
@@ -71,6 +77,16 @@ pub struct ExtractedMemoryPattern {
     pub source_message_id: Option<String>,
     pub context: String,
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Namespace of the conversation the pattern was extracted from
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+    /// Client that sent the message the pattern was extracted from
+    #[serde(default)]
+    pub subject_id: Option<String>,
+}
+
+fn default_namespace() -> String {
+    DEFAULT_NAMESPACE.to_string()
 }
 
 /// Configuration for the silent harvester
@@ -449,6 +465,8 @@ impl PatternMatcher {
                     source_message_id: None, // Will be set by caller
                     context: context.to_string(),
                     metadata,
+                    namespace: default_namespace(), // Will be set by caller,
+                    subject_id: None,
                 });
             }
         }
@@ -698,6 +716,7 @@ impl CircuitBreaker {
 pub struct DeduplicationService {
     threshold: f64,
     embedding_service: Arc<dyn EmbeddingService>,
+    // (namespace, embedding, inserted at) - patterns only deduplicate within their namespace
     recent_embeddings: Arc<RwLock<VecDeque<(String, Vec<f32>, Instant)>>>,
     max_cache_size: usize,
     cache_cleanup_threshold: f64,
@@ -766,8 +785,8 @@ impl DeduplicationService {
         let recent_embeddings = self.recent_embeddings.read().await;
 
         // Check for duplicates among valid (non-expired) entries
-        for (_, cached_embedding, timestamp) in recent_embeddings.iter() {
-            if now.duration_since(*timestamp) <= self.cache_ttl {
+        for (namespace, cached_embedding, timestamp) in recent_embeddings.iter() {
+            if *namespace == pattern.namespace && now.duration_since(*timestamp) <= self.cache_ttl {
                 let similarity = self.cosine_similarity(&embedding, cached_embedding);
                 if similarity >= self.threshold {
                     trace!(
@@ -784,7 +803,7 @@ impl DeduplicationService {
 
         // Add to cache with timestamp
         let mut cache = self.recent_embeddings.write().await;
-        cache.push_back((pattern.content.clone(), embedding, now));
+        cache.push_back((pattern.namespace.clone(), embedding, now));
 
         // Maintain cache size with aggressive cleanup when approaching limit
         let current_size = cache.len();
@@ -869,6 +888,11 @@ pub struct ConversationMessage {
     pub timestamp: DateTime<Utc>,
     pub role: String, // "user" or "assistant"
     pub context: String,
+    /// Namespace harvested memories are stored in
+    pub namespace: String,
+    /// Client that sent the message, the data subject of the memories
+    /// harvested from it
+    pub subject_id: Option<String>,
 }
 
 /// Bounded message queue with backpressure
@@ -938,6 +962,13 @@ pub struct HarvestingEngine {
     deduplication_service: Arc<DeduplicationService>, // Shared across all tasks
     repository: Arc<MemoryRepository>,
     importance_pipeline: Arc<ImportanceAssessmentPipeline>,
+    embedding_service: Arc<dyn EmbeddingService>,
+    entity_extractor: Arc<EntityExtractor>,
+    contradiction_detector: Arc<ContradictionDetector>,
+    /// The server's policy once set; nothing is screened before
+    pii_policy: std::sync::RwLock<Arc<PiiWritePolicy>>,
+    /// Set once the server has loaded its field encryption keys
+    field_encryption: OnceLock<Arc<FieldEncryption>>,
     metrics: Arc<HarvesterMetrics>,
     message_queue: Arc<Mutex<BoundedMessageQueue>>,
     last_harvest_time: Arc<Mutex<Option<Instant>>>,
@@ -955,7 +986,7 @@ impl HarvestingEngine {
         let pattern_matcher = PatternMatcher::new(&config.pattern_config)?;
         let deduplication_service = Arc::new(DeduplicationService::new(
             config.deduplication_threshold,
-            embedding_service,
+            embedding_service.clone(),
             1000, // Cache size
         ));
 
@@ -971,6 +1002,13 @@ impl HarvestingEngine {
             deduplication_service,
             repository,
             importance_pipeline,
+            contradiction_detector: Arc::new(
+                ContradictionDetector::default().with_embedder(embedding_service.clone()),
+            ),
+            embedding_service,
+            entity_extractor: Arc::new(EntityExtractor::new()),
+            pii_policy: std::sync::RwLock::new(Arc::new(PiiWritePolicy::disabled())),
+            field_encryption: OnceLock::new(),
             metrics,
             message_queue: Arc::new(Mutex::new(message_queue)),
             last_harvest_time: Arc::new(Mutex::new(None)),
@@ -978,6 +1016,27 @@ impl HarvestingEngine {
         })
     }
 
+    /// Encrypt harvested memories before they are stored; only the first
+    /// encryption set takes effect
+    pub fn set_field_encryption(&self, encryption: Arc<FieldEncryption>) {
+        if self.field_encryption.set(encryption).is_err() {
+            warn!("Field encryption of the harvester was already set");
+        }
+    }
+
+    /// Screen harvested content with `policy`, the one the server applies to
+    /// stored content
+    pub fn set_pii_policy(&self, policy: Arc<PiiWritePolicy>) {
+        *self.pii_policy.write().unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    fn pii_policy(&self) -> Arc<PiiWritePolicy> {
+        self.pii_policy
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
     /// Add a message to the processing queue with backpressure
     pub async fn queue_message(&self, message: ConversationMessage) -> Result<()> {
         let mut queue = self.message_queue.lock().await;
@@ -1023,6 +1082,11 @@ impl HarvestingEngine {
                         let dedup_service = self.deduplication_service.clone();
                         let repository = self.repository.clone();
                         let importance_pipeline = self.importance_pipeline.clone();
+                        let embedding_service = self.embedding_service.clone();
+                        let entity_extractor = self.entity_extractor.clone();
+                        let contradiction_detector = self.contradiction_detector.clone();
+                        let pii_policy = self.pii_policy();
+                        let field_encryption = self.field_encryption.get().cloned();
                         let metrics = self.metrics.clone();
                         let last_harvest_time = self.last_harvest_time.clone();
                         let pattern_config = self.config.pattern_config.clone();
@@ -1042,6 +1106,11 @@ impl HarvestingEngine {
                                 deduplication_service: dedup_service,
                                 repository,
                                 importance_pipeline,
+                                embedding_service,
+                                entity_extractor,
+                                contradiction_detector,
+                                pii_policy,
+                                field_encryption,
                                 metrics,
                                 last_harvest_time,
                             };
@@ -1126,6 +1195,8 @@ impl HarvestingEngine {
                     let mut message_patterns = Vec::new();
                     for mut pattern in patterns {
                         pattern.source_message_id = Some(message.id.clone());
+                        pattern.namespace = message.namespace.clone();
+                        pattern.subject_id = message.subject_id.clone();
                         metrics.record_pattern_confidence(pattern.confidence);
                         message_patterns.push(pattern);
                     }
@@ -1174,6 +1245,14 @@ impl HarvestingEngine {
             return Ok(());
         }
 
+        // Deduplication embeds the patterns, so PII is dealt with first
+        let high_confidence_patterns = screen_harvested_patterns(
+            &self.repository,
+            &self.pii_policy(),
+            high_confidence_patterns,
+        )
+        .await;
+
         // Deduplicate patterns in parallel batches
         let dedup_batch_size = 10; // Process 10 patterns at a time
         let mut unique_patterns = Vec::new();
@@ -1257,6 +1336,7 @@ impl HarvestingEngine {
     async fn store_pattern_as_memory(&self, pattern: ExtractedMemoryPattern) -> Result<Memory> {
         // Create metadata for the memory
         let mut metadata = pattern.metadata.clone();
+        metadata.insert(SUBJECT_ID_KEY.to_string(), harvest_subject(&pattern));
         metadata.insert(
             "pattern_type".to_string(),
             serde_json::to_value(&pattern.pattern_type)?,
@@ -1305,14 +1385,29 @@ impl HarvestingEngine {
             metadata: Some(serde_json::Value::Object(metadata.into_iter().collect())),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         // Store the memory
-        self.repository
+        let repository = harvest_storage(
+            &self.repository,
+            self.field_encryption.get(),
+            pattern.namespace,
+        );
+        let memory = repository
             .create_memory(create_request)
             .await
-            .map_err(HarvesterError::RepositoryFailed)
-            .map_err(Into::into)
+            .map_err(HarvesterError::RepositoryFailed)?;
+        index_harvested_entities(
+            repository.as_ref(),
+            &self.entity_extractor,
+            self.embedding_service.as_ref(),
+            &memory,
+        )
+        .await;
+        detect_harvested_conflicts(repository.as_ref(), &self.contradiction_detector, &memory)
+            .await;
+        Ok(memory)
     }
 
     /// Get current metrics summary
@@ -1466,6 +1561,11 @@ struct HarvestingEngineHandle {
     deduplication_service: Arc<DeduplicationService>, // Shared service prevents race conditions
     repository: Arc<MemoryRepository>,
     importance_pipeline: Arc<ImportanceAssessmentPipeline>,
+    embedding_service: Arc<dyn EmbeddingService>,
+    entity_extractor: Arc<EntityExtractor>,
+    contradiction_detector: Arc<ContradictionDetector>,
+    pii_policy: Arc<PiiWritePolicy>,
+    field_encryption: Option<Arc<FieldEncryption>>,
     metrics: Arc<HarvesterMetrics>,
     #[allow(dead_code)] // May be used for future optimizations
     last_harvest_time: Arc<Mutex<Option<Instant>>>,
@@ -1486,6 +1586,8 @@ impl HarvestingEngineHandle {
 
             for mut pattern in patterns {
                 pattern.source_message_id = Some(message.id.clone());
+                pattern.namespace = message.namespace.clone();
+                pattern.subject_id = message.subject_id.clone();
                 self.metrics.record_pattern_confidence(pattern.confidence);
                 all_patterns.push(pattern);
             }
@@ -1504,6 +1606,9 @@ impl HarvestingEngineHandle {
             .into_iter()
             .filter(|p| p.confidence >= self.config.confidence_threshold)
             .collect();
+        let high_confidence_patterns =
+            screen_harvested_patterns(&self.repository, &self.pii_policy, high_confidence_patterns)
+                .await;
 
         // Deduplicate and store
         let mut stored_count = 0;
@@ -1541,6 +1646,7 @@ impl HarvestingEngineHandle {
     async fn store_pattern_as_memory(&self, pattern: ExtractedMemoryPattern) -> Result<Memory> {
         // Create metadata
         let mut metadata = pattern.metadata.clone();
+        metadata.insert(SUBJECT_ID_KEY.to_string(), harvest_subject(&pattern));
         metadata.insert(
             "pattern_type".to_string(),
             serde_json::to_value(&pattern.pattern_type)?,
@@ -1571,13 +1677,108 @@ impl HarvestingEngineHandle {
             metadata: Some(serde_json::Value::Object(metadata.into_iter().collect())),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
-        self.repository
+        let repository = harvest_storage(
+            &self.repository,
+            self.field_encryption.as_ref(),
+            pattern.namespace,
+        );
+        let memory = repository
             .create_memory(create_request)
             .await
-            .map_err(HarvesterError::RepositoryFailed)
-            .map_err(Into::into)
+            .map_err(HarvesterError::RepositoryFailed)?;
+        index_harvested_entities(
+            repository.as_ref(),
+            &self.entity_extractor,
+            self.embedding_service.as_ref(),
+            &memory,
+        )
+        .await;
+        detect_harvested_conflicts(repository.as_ref(), &self.contradiction_detector, &memory)
+            .await;
+        Ok(memory)
+    }
+}
+
+/// Data subject a harvested memory is stored for, the one its PII was
+/// vaulted for
+fn harvest_subject(pattern: &ExtractedMemoryPattern) -> serde_json::Value {
+    data_subject(pattern.subject_id.as_deref(), &pattern.namespace).into()
+}
+
+/// Storage harvested memories of `namespace` are written to, encrypting
+/// them when field encryption is enabled
+fn harvest_storage(
+    repository: &MemoryRepository,
+    encryption: Option<&Arc<FieldEncryption>>,
+    namespace: String,
+) -> Arc<dyn MemoryStorage> {
+    let namespaced: Arc<dyn MemoryStorage> = Arc::new(repository.with_namespace(namespace));
+    match encryption {
+        Some(encryption) => Arc::new(EncryptedMemoryStorage::new(namespaced, encryption.clone())),
+        None => namespaced,
+    }
+}
+
+/// Link a harvested memory to the entities it mentions; the memory is kept
+/// even if this fails
+async fn index_harvested_entities(
+    repository: &dyn MemoryStorage,
+    extractor: &EntityExtractor,
+    embedding_service: &dyn EmbeddingService,
+    memory: &Memory,
+) {
+    if let Err(e) =
+        index_memory_entities(repository, extractor, Some(embedding_service), memory).await
+    {
+        warn!("Failed to index entities of memory {}: {}", memory.id, e);
+    }
+}
+
+/// Apply the PII policy of each pattern's namespace to its content;
+/// patterns the policy rejects are dropped
+async fn screen_harvested_patterns(
+    repository: &MemoryRepository,
+    policy: &PiiWritePolicy,
+    patterns: Vec<ExtractedMemoryPattern>,
+) -> Vec<ExtractedMemoryPattern> {
+    let mut screened = Vec::with_capacity(patterns.len());
+    for mut pattern in patterns {
+        let namespaced = repository.with_namespace(pattern.namespace.clone());
+        let actor = pattern.subject_id.as_deref();
+        match policy.screen(&namespaced, &pattern.content, actor).await {
+            Ok(screening) => {
+                if let Some(pii) = screening.metadata() {
+                    pattern.metadata.insert("pii".to_string(), pii);
+                }
+                pattern.content = screening.content;
+                screened.push(pattern);
+            }
+            Err(e) => warn!("Dropped harvested pattern: {}", e),
+        }
+    }
+    screened
+}
+
+/// Queue conflicts between a harvested memory and stored ones; harvested
+/// corrections supersede the facts they contradict
+async fn detect_harvested_conflicts(
+    repository: &dyn MemoryStorage,
+    detector: &ContradictionDetector,
+    memory: &Memory,
+) {
+    match detector.detect(repository, memory).await {
+        Ok(conflicts) if !conflicts.is_empty() => {
+            debug!(
+                "Harvested memory {} conflicts with {} memories",
+                memory.id,
+                conflicts.len()
+            );
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Failed to check memory {} for conflicts: {}", memory.id, e),
     }
 }
 
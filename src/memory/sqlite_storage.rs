@@ -0,0 +1,2922 @@
+//! Embedded SQLite storage backend
+//!
+//! Keeps memories in a single SQLite file (or `sqlite::memory:`) so the
+//! server can run without PostgreSQL/pgvector. Embeddings are stored as
+//! little-endian `f32` blobs and searched with a brute-force cosine scan,
+//! which is fine for the few thousand memories a local install holds. A
+//! search scans at most 10,000 memories of a namespace, the most important
+//! first; larger stores should use PostgreSQL.
+
+use super::compression::{FrozenMemoryCompression, ZstdCompressionEngine};
+use super::contradictions::{
+    ConflictStatus, MemoryConflict, NewMemoryConflict, ResolveConflictRequest,
+};
+use super::entities::{
+    embedding_similarity, lookup_keys, normalize_alias, Entity, EntityMention, EntityType,
+    ENTITY_SIMILARITY_THRESHOLD,
+};
+use super::error::{MemoryError, Result};
+use super::field_encryption::WrappedDataKey;
+use super::models::*;
+use super::pii_policy::VaultedPiiValue;
+use super::reflection_engine::RelationshipType;
+use super::relations::{LinkMemoriesRequest, MemoryRelation, RelationFilter};
+use super::repository::MemoryStatistics;
+use super::retention_job::{legal_hold_error, LegalHold};
+use super::storage::MemoryStorage;
+use super::versioning::is_revised;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use pgvector::Vector;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::{Row, Sqlite, Transaction};
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Memories read per query while a search scans its candidates
+const CANDIDATE_PAGE_SIZE: i64 = 1_000;
+
+const SCHEMA: &[&str] = &[
+    r#"
+    CREATE TABLE IF NOT EXISTS memories (
+        id BLOB PRIMARY KEY,
+        content TEXT NOT NULL,
+        content_hash TEXT NOT NULL,
+        embedding BLOB,
+        tier TEXT NOT NULL DEFAULT 'working',
+        status TEXT NOT NULL DEFAULT 'active',
+        importance_score REAL NOT NULL DEFAULT 0.5,
+        access_count INTEGER NOT NULL DEFAULT 0,
+        last_accessed_at TEXT,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        parent_id BLOB,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        expires_at TEXT,
+        consolidation_strength REAL NOT NULL DEFAULT 1.0,
+        decay_rate REAL NOT NULL DEFAULT 1.0,
+        recall_probability REAL,
+        next_review_at TEXT,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        valid_from TEXT,
+        valid_to TEXT
+    )
+    "#,
+    "CREATE INDEX IF NOT EXISTS idx_memories_tier_status ON memories (tier, status)",
+    "CREATE INDEX IF NOT EXISTS idx_memories_content_hash ON memories (content_hash, tier)",
+    r#"
+    CREATE TABLE IF NOT EXISTS frozen_memories (
+        id BLOB PRIMARY KEY,
+        original_memory_id BLOB NOT NULL,
+        compressed_content BLOB NOT NULL,
+        original_tier TEXT NOT NULL,
+        freeze_reason TEXT,
+        compression_ratio REAL,
+        frozen_at TEXT NOT NULL,
+        unfrozen_at TEXT,
+        namespace TEXT NOT NULL DEFAULT 'default'
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS migration_history (
+        memory_id BLOB NOT NULL,
+        from_tier TEXT NOT NULL,
+        to_tier TEXT NOT NULL,
+        migration_reason TEXT,
+        migrated_at TEXT NOT NULL
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS memory_revisions (
+        id BLOB PRIMARY KEY,
+        memory_id BLOB NOT NULL,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        revision INTEGER NOT NULL,
+        content TEXT NOT NULL,
+        content_hash TEXT NOT NULL,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        importance_score REAL NOT NULL,
+        tier TEXT NOT NULL,
+        actor TEXT,
+        reason TEXT,
+        created_at TEXT NOT NULL,
+        UNIQUE (memory_id, revision)
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS memory_relations (
+        id BLOB PRIMARY KEY,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        source_id BLOB NOT NULL,
+        target_id BLOB NOT NULL,
+        relation_type TEXT NOT NULL,
+        strength REAL NOT NULL DEFAULT 1.0,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_by TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        UNIQUE (source_id, target_id, relation_type)
+    )
+    "#,
+    "CREATE INDEX IF NOT EXISTS idx_memory_relations_target ON memory_relations (target_id)",
+    r#"
+    CREATE TABLE IF NOT EXISTS entities (
+        id BLOB PRIMARY KEY,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        entity_type TEXT NOT NULL,
+        canonical_name TEXT NOT NULL,
+        normalized_name TEXT NOT NULL,
+        embedding BLOB,
+        mention_count INTEGER NOT NULL DEFAULT 0,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        UNIQUE (namespace, entity_type, normalized_name)
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS entity_aliases (
+        entity_id BLOB NOT NULL,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        alias TEXT NOT NULL,
+        normalized_alias TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        PRIMARY KEY (entity_id, normalized_alias)
+    )
+    "#,
+    "CREATE INDEX IF NOT EXISTS idx_entity_aliases_lookup ON entity_aliases (namespace, normalized_alias)",
+    r#"
+    CREATE TABLE IF NOT EXISTS memory_entities (
+        memory_id BLOB NOT NULL,
+        entity_id BLOB NOT NULL,
+        mention TEXT NOT NULL,
+        confidence REAL NOT NULL DEFAULT 1.0,
+        created_at TEXT NOT NULL,
+        PRIMARY KEY (memory_id, entity_id)
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS memory_conflicts (
+        id BLOB PRIMARY KEY,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        memory_id BLOB NOT NULL,
+        conflicting_memory_id BLOB NOT NULL,
+        status TEXT NOT NULL DEFAULT 'open',
+        superseded_memory_id BLOB,
+        reason TEXT NOT NULL,
+        confidence REAL NOT NULL DEFAULT 1.0,
+        detector TEXT NOT NULL,
+        resolved_by TEXT,
+        created_at TEXT NOT NULL,
+        resolved_at TEXT,
+        UNIQUE (memory_id, conflicting_memory_id)
+    )
+    "#,
+    "CREATE INDEX IF NOT EXISTS idx_memory_conflicts_superseded ON memory_conflicts (superseded_memory_id)",
+    r#"
+    CREATE TABLE IF NOT EXISTS pii_vault_keys (
+        subject_id TEXT PRIMARY KEY,
+        wrapped_key BLOB NOT NULL,
+        created_at TEXT NOT NULL
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS pii_vault (
+        token TEXT NOT NULL,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        subject_id TEXT NOT NULL REFERENCES pii_vault_keys(subject_id) ON DELETE CASCADE,
+        kind TEXT NOT NULL,
+        ciphertext BLOB NOT NULL,
+        created_at TEXT NOT NULL,
+        PRIMARY KEY (namespace, token, subject_id)
+    )
+    "#,
+    "CREATE INDEX IF NOT EXISTS idx_pii_vault_subject ON pii_vault (subject_id)",
+    r#"
+    CREATE TABLE IF NOT EXISTS memory_encryption_keys (
+        key_id TEXT PRIMARY KEY,
+        wrapped_key BLOB NOT NULL,
+        master_key_id TEXT NOT NULL,
+        created_at TEXT NOT NULL
+    )
+    "#,
+    r#"
+    CREATE TABLE IF NOT EXISTS legal_holds (
+        memory_id BLOB PRIMARY KEY REFERENCES memories(id) ON DELETE CASCADE,
+        namespace TEXT NOT NULL DEFAULT 'default',
+        reason TEXT NOT NULL,
+        placed_by TEXT,
+        placed_at TEXT NOT NULL
+    )
+    "#,
+];
+
+/// Tables that gained a `namespace` column after the first release
+const NAMESPACED_TABLES: &[&str] = &["memories", "frozen_memories"];
+
+/// Columns added to `memories` after its first release
+const ADDED_MEMORY_COLUMNS: &[(&str, &str)] = &[("valid_from", "TEXT"), ("valid_to", "TEXT")];
+
+/// Embedded single-file memory store
+#[derive(Clone)]
+pub struct SqliteMemoryStorage {
+    pool: SqlitePool,
+    namespace: String,
+}
+
+impl SqliteMemoryStorage {
+    /// Open (creating if needed) the database at `database_url` and apply the schema
+    pub async fn connect(database_url: &str) -> Result<Self> {
+        let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+
+        // Every connection to `sqlite::memory:` is a separate database, so keep one
+        let max_connections = if database_url.contains(":memory:") {
+            1
+        } else {
+            5
+        };
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect_with(options)
+            .await?;
+
+        let storage = Self {
+            pool,
+            namespace: DEFAULT_NAMESPACE.to_string(),
+        };
+        storage.initialize_schema().await?;
+        info!("Opened embedded SQLite memory storage at {}", database_url);
+        Ok(storage)
+    }
+
+    pub fn pool(&self) -> &SqlitePool {
+        &self.pool
+    }
+
+    async fn initialize_schema(&self) -> Result<()> {
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&self.pool).await?;
+        }
+
+        // SQLite has no ADD COLUMN IF NOT EXISTS
+        for table in NAMESPACED_TABLES {
+            let has_namespace: bool = sqlx::query_scalar(&format!(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('{table}') WHERE name = 'namespace')"
+            ))
+            .fetch_one(&self.pool)
+            .await?;
+            if !has_namespace {
+                sqlx::query(&format!(
+                    "ALTER TABLE {table} ADD COLUMN namespace TEXT NOT NULL DEFAULT 'default'"
+                ))
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+        for (column, column_type) in ADDED_MEMORY_COLUMNS {
+            let exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('memories') WHERE name = ?1)",
+            )
+            .bind(column)
+            .fetch_one(&self.pool)
+            .await?;
+            if !exists {
+                sqlx::query(&format!(
+                    "ALTER TABLE memories ADD COLUMN {column} {column_type}"
+                ))
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_memories_namespace ON memories (namespace, status, tier)",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn fetch_active(&self, id: Uuid) -> Result<Memory> {
+        self.fetch_optional_active(id)
+            .await?
+            .ok_or_else(|| MemoryError::NotFound { id: id.to_string() })
+    }
+
+    async fn fetch_optional_active(&self, id: Uuid) -> Result<Option<Memory>> {
+        let row = sqlx::query(
+            "SELECT * FROM memories WHERE id = ?1 AND status = 'active' AND namespace = ?2",
+        )
+        .bind(id)
+        .bind(&self.namespace)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.as_ref().map(memory_from_row).transpose()
+    }
+
+    async fn record_migration(
+        &self,
+        memory_id: Uuid,
+        from_tier: MemoryTier,
+        to_tier: MemoryTier,
+        reason: Option<String>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO migration_history (memory_id, from_tier, to_tier, migration_reason, migrated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            "#,
+        )
+        .bind(memory_id)
+        .bind(tier_str(from_tier))
+        .bind(tier_str(to_tier))
+        .bind(reason)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Store `updated` as the next revision, preceded by `previous` as
+    /// revision 1 on the first recorded update
+    async fn record_revision(
+        &self,
+        previous: &Memory,
+        updated: &Memory,
+        actor: Option<String>,
+        reason: Option<String>,
+    ) -> Result<()> {
+        let latest: Option<i32> =
+            sqlx::query_scalar("SELECT MAX(revision) FROM memory_revisions WHERE memory_id = ?1")
+                .bind(updated.id)
+                .fetch_one(&self.pool)
+                .await?;
+
+        let latest = match latest {
+            Some(latest) => latest,
+            None => {
+                self.insert_revision(
+                    previous,
+                    1,
+                    None,
+                    Some("Initial version".to_string()),
+                    previous.updated_at,
+                )
+                .await?;
+                1
+            }
+        };
+
+        self.insert_revision(updated, latest + 1, actor, reason, updated.updated_at)
+            .await
+    }
+
+    async fn insert_revision(
+        &self,
+        memory: &Memory,
+        revision: i32,
+        actor: Option<String>,
+        reason: Option<String>,
+        created_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO memory_revisions (
+                id, memory_id, namespace, revision, content, content_hash, metadata,
+                importance_score, tier, actor, reason, created_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(memory.id)
+        .bind(&self.namespace)
+        .bind(revision)
+        .bind(&memory.content)
+        .bind(&memory.content_hash)
+        .bind(serde_json::to_string(&memory.metadata)?)
+        .bind(memory.importance_score)
+        .bind(tier_str(memory.tier))
+        .bind(actor)
+        .bind(reason)
+        .bind(created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// End the valid time of a superseded memory where the valid time of the
+    /// memory replacing it starts. An end set explicitly is kept.
+    async fn end_valid_time(&self, superseded: Uuid, current: Uuid) -> Result<()> {
+        let (Some(old), Some(new)) = (
+            self.fetch_optional_active(superseded).await?,
+            self.fetch_optional_active(current).await?,
+        ) else {
+            return Ok(());
+        };
+        if old.valid_to.is_none() && old.valid_since() < new.valid_since() {
+            sqlx::query("UPDATE memories SET valid_to = ?2 WHERE id = ?1")
+                .bind(superseded)
+                .bind(new.valid_since())
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Undo `end_valid_time` when a supersession is withdrawn
+    async fn restore_valid_time(&self, superseded: Uuid, current: Uuid) -> Result<()> {
+        let (Some(old), Some(new)) = (
+            self.fetch_optional_active(superseded).await?,
+            self.fetch_optional_active(current).await?,
+        ) else {
+            return Ok(());
+        };
+        if old.valid_to == Some(new.valid_since()) {
+            sqlx::query("UPDATE memories SET valid_to = NULL WHERE id = ?1")
+                .bind(superseded)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Load the active memories matching the structural filters of the
+    /// request. Searches are a scan over every active memory of the namespace
+    /// and requested tier, read `CANDIDATE_PAGE_SIZE` rows at a time, so
+    /// large stores belong on PostgreSQL.
+    async fn load_candidates(&self, request: &SearchRequest) -> Result<Vec<Memory>> {
+        let entity_memories = match &request.entity {
+            Some(entity) => Some(self.entity_memory_ids(entity).await?),
+            None => None,
+        };
+        // With `as_of`, the valid time already tells superseded memories apart
+        let superseded: HashSet<Uuid> = if request.include_superseded.unwrap_or(false)
+            || request.as_of.is_some()
+        {
+            HashSet::new()
+        } else {
+            sqlx::query_scalar(
+                "SELECT superseded_memory_id FROM memory_conflicts WHERE namespace = ?1 AND status = 'superseded'",
+            )
+            .bind(&self.namespace)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .collect()
+        };
+
+        let mut memories = Vec::new();
+        let mut scanned: i64 = 0;
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT * FROM memories
+                WHERE status = 'active' AND namespace = ?1 AND (?2 IS NULL OR tier = ?2)
+                ORDER BY importance_score DESC, updated_at DESC, id
+                LIMIT ?3 OFFSET ?4
+                "#,
+            )
+            .bind(&self.namespace)
+            .bind(request.tier.map(tier_str))
+            .bind(CANDIDATE_PAGE_SIZE)
+            .bind(scanned)
+            .fetch_all(&self.pool)
+            .await?;
+
+            for row in &rows {
+                let memory = memory_from_row(row)?;
+                let linked = entity_memories
+                    .as_ref()
+                    .is_none_or(|ids| ids.contains(&memory.id));
+                if linked && !superseded.contains(&memory.id) && matches_filters(&memory, request) {
+                    memories.push(memory);
+                }
+            }
+
+            scanned += rows.len() as i64;
+            if (rows.len() as i64) < CANDIDATE_PAGE_SIZE {
+                break;
+            }
+        }
+        if scanned > CANDIDATE_PAGE_SIZE {
+            debug!(
+                "Search of namespace {} scanned {} memories",
+                self.namespace, scanned
+            );
+        }
+        Ok(memories)
+    }
+
+    /// Ids of memories linked to the entity known by `name`
+    async fn entity_memory_ids(&self, name: &str) -> Result<HashSet<Uuid>> {
+        let keys = lookup_keys(name);
+        if keys.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let sql = format!(
+            r#"
+            SELECT DISTINCT me.memory_id
+            FROM memory_entities me
+            JOIN entity_aliases a ON a.entity_id = me.entity_id
+            WHERE a.namespace = ?1 AND a.normalized_alias IN ({})
+            "#,
+            placeholders(2, keys.len())
+        );
+        let mut query = sqlx::query_scalar(&sql).bind(&self.namespace);
+        for key in &keys {
+            query = query.bind(key);
+        }
+        Ok(query.fetch_all(&self.pool).await?.into_iter().collect())
+    }
+
+    /// Entity a mention refers to: by alias, then by the most similar name
+    /// embedding, otherwise a newly created entity
+    async fn resolve_entity(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        mention: &EntityMention,
+        keys: &[String],
+    ) -> Result<Uuid> {
+        let sql = format!(
+            r#"
+            SELECT e.id
+            FROM entities e
+            JOIN entity_aliases a ON a.entity_id = e.id
+            WHERE a.namespace = ?1 AND e.entity_type = ?2 AND a.normalized_alias IN ({})
+            ORDER BY e.mention_count DESC, e.id
+            LIMIT 1
+            "#,
+            placeholders(3, keys.len())
+        );
+        let mut query = sqlx::query_scalar(&sql)
+            .bind(&self.namespace)
+            .bind(mention.entity_type.as_str());
+        for key in keys {
+            query = query.bind(key);
+        }
+        if let Some(id) = query.fetch_optional(&mut **tx).await? {
+            return Ok(id);
+        }
+
+        if let Some(embedding) = &mention.embedding {
+            let rows = sqlx::query(
+                r#"
+                SELECT id, embedding FROM entities
+                WHERE namespace = ?1 AND entity_type = ?2 AND embedding IS NOT NULL
+                "#,
+            )
+            .bind(&self.namespace)
+            .bind(mention.entity_type.as_str())
+            .fetch_all(&mut **tx)
+            .await?;
+
+            let mut best: Option<(Uuid, f32)> = None;
+            for row in &rows {
+                let stored: Vec<u8> = row.try_get("embedding")?;
+                let similarity = embedding_similarity(embedding, &decode_embedding(&stored));
+                if best.is_none_or(|(_, score)| similarity > score) {
+                    best = Some((row.try_get("id")?, similarity));
+                }
+            }
+            if let Some((id, similarity)) = best {
+                if similarity >= ENTITY_SIMILARITY_THRESHOLD {
+                    return Ok(id);
+                }
+            }
+        }
+
+        let canonical = mention.canonical_name();
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO entities (
+                id, namespace, entity_type, canonical_name, normalized_name, embedding,
+                created_at, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+            ON CONFLICT (namespace, entity_type, normalized_name) DO UPDATE
+            SET embedding = COALESCE(entities.embedding, excluded.embedding),
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(&self.namespace)
+        .bind(mention.entity_type.as_str())
+        .bind(&canonical)
+        .bind(normalize_alias(&canonical))
+        .bind(mention.embedding.as_deref().map(encode_embedding))
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+
+        let id = sqlx::query_scalar(
+            "SELECT id FROM entities WHERE namespace = ?1 AND entity_type = ?2 AND normalized_name = ?3",
+        )
+        .bind(&self.namespace)
+        .bind(mention.entity_type.as_str())
+        .bind(normalize_alias(&canonical))
+        .fetch_one(&mut **tx)
+        .await?;
+        Ok(id)
+    }
+
+    /// Entities for `rows` of the `entities` table, with their aliases
+    async fn entities_from_rows(&self, rows: &[SqliteRow]) -> Result<Vec<Entity>> {
+        let mut entities = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut entity = entity_from_row(row)?;
+            entity.aliases = sqlx::query_scalar(
+                "SELECT alias FROM entity_aliases WHERE entity_id = ?1 ORDER BY created_at, alias",
+            )
+            .bind(entity.id)
+            .fetch_all(&self.pool)
+            .await?;
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+}
+
+fn tier_str(tier: MemoryTier) -> &'static str {
+    match tier {
+        MemoryTier::Working => "working",
+        MemoryTier::Warm => "warm",
+        MemoryTier::Cold => "cold",
+        MemoryTier::Frozen => "frozen",
+    }
+}
+
+fn status_from_str(status: &str) -> MemoryStatus {
+    match status {
+        "migrating" => MemoryStatus::Migrating,
+        "archived" => MemoryStatus::Archived,
+        "deleted" => MemoryStatus::Deleted,
+        _ => MemoryStatus::Active,
+    }
+}
+
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn decode_embedding(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// Fraction of query terms that occur in the content
+/// A candidate memory with its per-signal scores
+struct ScoredMemory {
+    memory: Memory,
+    similarity: f32,
+    text_score: f32,
+    temporal: f32,
+    access_frequency: f32,
+    importance: f32,
+    combined: f32,
+}
+
+impl ScoredMemory {
+    fn to_result(&self, score_explanation: Option<ScoreExplanation>) -> SearchResult {
+        SearchResult {
+            memory: self.memory.clone(),
+            similarity_score: self.similarity,
+            temporal_score: Some(self.temporal),
+            importance_score: self.memory.importance_score,
+            access_frequency_score: Some(self.access_frequency),
+            combined_score: self.combined,
+            score_explanation,
+        }
+    }
+}
+
+/// Results ranked by `score`, best first, skipping candidates it returns `None` for
+fn rank_by(
+    scored: &[ScoredMemory],
+    score: impl Fn(&ScoredMemory) -> Option<f32>,
+) -> Vec<SearchResult> {
+    let mut ranking: Vec<(f32, &ScoredMemory)> = scored
+        .iter()
+        .filter_map(|c| score(c).map(|s| (s, c)))
+        .collect();
+    ranking.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    ranking
+        .into_iter()
+        .map(|(score, c)| SearchResult {
+            similarity_score: score,
+            ..c.to_result(None)
+        })
+        .collect()
+}
+
+/// Pagination cursors of the embedded backend are plain offsets
+fn encode_offset_cursor(offset: usize) -> String {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    STANDARD.encode(format!("offset:{offset}"))
+}
+
+fn decode_offset_cursor(cursor: &str) -> Result<usize> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    STANDARD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|text| text.strip_prefix("offset:")?.parse().ok())
+        .ok_or_else(|| MemoryError::InvalidRequest {
+            message: "Invalid search cursor".to_string(),
+        })
+}
+
+fn text_match_score(query: &str, content: &str) -> f32 {
+    let content = content.to_lowercase();
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|t| t.to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let hits = terms
+        .iter()
+        .filter(|t| content.contains(t.as_str()))
+        .count();
+    hits as f32 / terms.len() as f32
+}
+
+/// Exponential recency decay with a one-week time constant
+fn recency_score(created_at: DateTime<Utc>) -> f32 {
+    let age_hours = (Utc::now() - created_at).num_minutes().max(0) as f64 / 60.0;
+    (-age_hours / 168.0).exp() as f32
+}
+
+fn matches_filters(memory: &Memory, request: &SearchRequest) -> bool {
+    if let Some(tier) = request.tier {
+        if memory.tier != tier {
+            return false;
+        }
+    }
+    if request
+        .as_of
+        .is_some_and(|as_of| !memory.is_valid_at(as_of))
+    {
+        return false;
+    }
+    if let Some(range) = &request.date_range {
+        if range.start.is_some_and(|start| memory.created_at < start) {
+            return false;
+        }
+        if range.end.is_some_and(|end| memory.created_at > end) {
+            return false;
+        }
+    }
+    if let Some(range) = &request.importance_range {
+        let importance = memory.importance_score as f32;
+        if range.min.is_some_and(|min| importance < min) {
+            return false;
+        }
+        if range.max.is_some_and(|max| importance > max) {
+            return false;
+        }
+    }
+    if let Some(tags) = &request.tags {
+        let memory_tags: Vec<&str> = memory
+            .metadata
+            .get("tags")
+            .and_then(|t| t.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+        if !tags.iter().all(|tag| memory_tags.contains(&tag.as_str())) {
+            return false;
+        }
+    }
+    true
+}
+
+fn get_datetime(row: &SqliteRow, column: &str) -> Result<Option<DateTime<Utc>>> {
+    Ok(row.try_get::<Option<DateTime<Utc>>, _>(column)?)
+}
+
+/// `?n, ?n+1, ...` for binding `count` values starting at parameter `first`
+fn placeholders(first: usize, count: usize) -> String {
+    (first..first + count)
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn relation_from_row(row: &SqliteRow) -> Result<MemoryRelation> {
+    let relation_type: String = row.try_get("relation_type")?;
+    let metadata: String = row.try_get("metadata")?;
+    let now = Utc::now();
+
+    Ok(MemoryRelation {
+        id: row.try_get("id")?,
+        namespace: row.try_get("namespace")?,
+        source_id: row.try_get("source_id")?,
+        target_id: row.try_get("target_id")?,
+        relation_type: relation_type
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        strength: row.try_get("strength")?,
+        metadata: serde_json::from_str(&metadata)?,
+        created_by: row.try_get("created_by")?,
+        created_at: get_datetime(row, "created_at")?.unwrap_or(now),
+        updated_at: get_datetime(row, "updated_at")?.unwrap_or(now),
+    })
+}
+
+fn conflict_from_row(row: &SqliteRow) -> Result<MemoryConflict> {
+    let status: String = row.try_get("status")?;
+
+    Ok(MemoryConflict {
+        id: row.try_get("id")?,
+        namespace: row.try_get("namespace")?,
+        memory_id: row.try_get("memory_id")?,
+        conflicting_memory_id: row.try_get("conflicting_memory_id")?,
+        status: status
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        superseded_memory_id: row.try_get("superseded_memory_id")?,
+        reason: row.try_get("reason")?,
+        confidence: row.try_get("confidence")?,
+        detector: row.try_get("detector")?,
+        resolved_by: row.try_get("resolved_by")?,
+        created_at: get_datetime(row, "created_at")?.unwrap_or_else(Utc::now),
+        resolved_at: get_datetime(row, "resolved_at")?,
+    })
+}
+
+/// Entity without its aliases, which live in `entity_aliases`
+fn entity_from_row(row: &SqliteRow) -> Result<Entity> {
+    let entity_type: String = row.try_get("entity_type")?;
+    let embedding: Option<Vec<u8>> = row.try_get("embedding")?;
+    let now = Utc::now();
+
+    Ok(Entity {
+        id: row.try_get("id")?,
+        namespace: row.try_get("namespace")?,
+        entity_type: entity_type
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        canonical_name: row.try_get("canonical_name")?,
+        aliases: Vec::new(),
+        embedding: embedding.as_deref().map(decode_embedding),
+        mention_count: row.try_get("mention_count")?,
+        created_at: get_datetime(row, "created_at")?.unwrap_or(now),
+        updated_at: get_datetime(row, "updated_at")?.unwrap_or(now),
+    })
+}
+
+fn revision_from_row(row: &SqliteRow) -> Result<MemoryRevision> {
+    let tier: String = row.try_get("tier")?;
+    let metadata: String = row.try_get("metadata")?;
+
+    Ok(MemoryRevision {
+        id: row.try_get("id")?,
+        memory_id: row.try_get("memory_id")?,
+        namespace: row.try_get("namespace")?,
+        revision: row.try_get("revision")?,
+        content: row.try_get("content")?,
+        content_hash: row.try_get("content_hash")?,
+        metadata: serde_json::from_str(&metadata)?,
+        importance_score: row.try_get("importance_score")?,
+        tier: tier
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        actor: row.try_get("actor")?,
+        reason: row.try_get("reason")?,
+        created_at: get_datetime(row, "created_at")?.unwrap_or_else(Utc::now),
+    })
+}
+
+fn legal_hold_from_row(row: &SqliteRow) -> Result<LegalHold> {
+    Ok(LegalHold {
+        memory_id: row.try_get("memory_id")?,
+        namespace: row.try_get("namespace")?,
+        reason: row.try_get("reason")?,
+        placed_by: row.try_get("placed_by")?,
+        placed_at: get_datetime(row, "placed_at")?.unwrap_or_else(Utc::now),
+    })
+}
+
+fn memory_from_row(row: &SqliteRow) -> Result<Memory> {
+    let tier: String = row.try_get("tier")?;
+    let status: String = row.try_get("status")?;
+    let metadata: String = row.try_get("metadata")?;
+    let embedding: Option<Vec<f32>> = row
+        .try_get::<Option<Vec<u8>>, _>("embedding")?
+        .map(|bytes| decode_embedding(&bytes));
+    let now = Utc::now();
+
+    Ok(Memory {
+        id: row.try_get("id")?,
+        content: row.try_get("content")?,
+        content_hash: row.try_get("content_hash")?,
+        namespace: row.try_get("namespace")?,
+        embedding_dimension: embedding.as_ref().map(|v| v.len() as i32),
+        embedding: embedding.map(Vector::from),
+        tier: tier
+            .parse()
+            .map_err(|message| MemoryError::InvalidData { message })?,
+        status: status_from_str(&status),
+        importance_score: row.try_get("importance_score")?,
+        access_count: row.try_get("access_count")?,
+        last_accessed_at: get_datetime(row, "last_accessed_at")?,
+        metadata: serde_json::from_str(&metadata)?,
+        parent_id: row.try_get("parent_id")?,
+        created_at: get_datetime(row, "created_at")?.unwrap_or(now),
+        updated_at: get_datetime(row, "updated_at")?.unwrap_or(now),
+        expires_at: get_datetime(row, "expires_at")?,
+        valid_from: get_datetime(row, "valid_from")?,
+        valid_to: get_datetime(row, "valid_to")?,
+        consolidation_strength: row.try_get("consolidation_strength")?,
+        decay_rate: row.try_get("decay_rate")?,
+        recall_probability: row.try_get("recall_probability")?,
+        next_review_at: get_datetime(row, "next_review_at")?,
+        ..Memory::default()
+    })
+}
+
+#[async_trait]
+impl MemoryStorage for SqliteMemoryStorage {
+    fn backend_name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    fn with_namespace(&self, namespace: &str) -> Arc<dyn MemoryStorage> {
+        Arc::new(Self {
+            pool: self.pool.clone(),
+            namespace: namespace.to_string(),
+        })
+    }
+
+    async fn create_memory(&self, request: CreateMemoryRequest) -> Result<Memory> {
+        let id = Uuid::new_v4();
+        let content_hash = request
+            .content_hash
+            .clone()
+            .unwrap_or_else(|| Memory::calculate_content_hash(&request.content));
+        let tier = request.tier.unwrap_or(MemoryTier::Working);
+
+        let skip_duplicate_check =
+            std::env::var("SKIP_DUPLICATE_CHECK").unwrap_or_else(|_| "false".to_string()) == "true";
+
+        if !skip_duplicate_check {
+            let duplicate_exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM memories WHERE content_hash = ?1 AND tier = ?2 AND namespace = ?3 AND status = 'active')",
+            )
+            .bind(&content_hash)
+            .bind(tier_str(tier))
+            .bind(&self.namespace)
+            .fetch_one(&self.pool)
+            .await?;
+
+            if duplicate_exists {
+                return Err(MemoryError::DuplicateContent {
+                    tier: format!("{tier:?}"),
+                });
+            }
+        }
+
+        let now = Utc::now();
+        validate_valid_time(request.valid_from.unwrap_or(now), request.valid_to)?;
+        let metadata = request.metadata.unwrap_or_else(|| serde_json::json!({}));
+
+        sqlx::query(
+            r#"
+            INSERT INTO memories (
+                id, content, content_hash, embedding, tier, status, importance_score,
+                metadata, parent_id, created_at, updated_at, expires_at, namespace,
+                valid_from, valid_to
+            ) VALUES (?1, ?2, ?3, ?4, ?5, 'active', ?6, ?7, ?8, ?9, ?9, ?10, ?11, ?12, ?13)
+            "#,
+        )
+        .bind(id)
+        .bind(&request.content)
+        .bind(&content_hash)
+        .bind(request.embedding.as_deref().map(encode_embedding))
+        .bind(tier_str(tier))
+        .bind(request.importance_score.unwrap_or(0.5))
+        .bind(serde_json::to_string(&metadata)?)
+        .bind(request.parent_id)
+        .bind(now)
+        .bind(request.expires_at)
+        .bind(&self.namespace)
+        .bind(request.valid_from)
+        .bind(request.valid_to)
+        .execute(&self.pool)
+        .await?;
+
+        info!("Created memory {} in tier {:?}", id, tier);
+        self.fetch_active(id).await
+    }
+
+    async fn get_memory(&self, id: Uuid) -> Result<Memory> {
+        let result = sqlx::query(
+            r#"
+            UPDATE memories
+            SET access_count = access_count + 1, last_accessed_at = ?2
+            WHERE id = ?1 AND status = 'active' AND namespace = ?3
+            "#,
+        )
+        .bind(id)
+        .bind(Utc::now())
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(MemoryError::NotFound { id: id.to_string() });
+        }
+
+        let memory = self.fetch_active(id).await?;
+        debug!("Retrieved memory {} from tier {:?}", id, memory.tier);
+        Ok(memory)
+    }
+
+    async fn update_memory(&self, id: Uuid, request: UpdateMemoryRequest) -> Result<Memory> {
+        let current = self.fetch_active(id).await?;
+
+        let content = request.content.as_ref().unwrap_or(&current.content);
+        let content_hash = match (&request.content, &request.content_hash) {
+            (Some(_), Some(content_hash)) => content_hash.clone(),
+            (Some(content), None) => Memory::calculate_content_hash(content),
+            (None, _) => current.content_hash.clone(),
+        };
+        let embedding = match request.embedding {
+            Some(embedding) => Some(embedding),
+            None => current.embedding.as_ref().map(|v| v.as_slice().to_vec()),
+        };
+        let tier = request.tier.unwrap_or(current.tier);
+        let importance_score = request.importance_score.unwrap_or(current.importance_score);
+        let metadata = request.metadata.as_ref().unwrap_or(&current.metadata);
+        let expires_at = request.expires_at.or(current.expires_at);
+        let valid_from = request.valid_from.or(current.valid_from);
+        let valid_to = request.valid_to.or(current.valid_to);
+        validate_valid_time(valid_from.unwrap_or(current.created_at), valid_to)?;
+
+        sqlx::query(
+            r#"
+            UPDATE memories
+            SET content = ?2, content_hash = ?3, embedding = ?4, tier = ?5,
+                importance_score = ?6, metadata = ?7, expires_at = ?8, updated_at = ?9,
+                valid_from = ?10, valid_to = ?11
+            WHERE id = ?1
+            "#,
+        )
+        .bind(id)
+        .bind(content)
+        .bind(&content_hash)
+        .bind(embedding.as_deref().map(encode_embedding))
+        .bind(tier_str(tier))
+        .bind(importance_score)
+        .bind(serde_json::to_string(metadata)?)
+        .bind(expires_at)
+        .bind(Utc::now())
+        .bind(valid_from)
+        .bind(valid_to)
+        .execute(&self.pool)
+        .await?;
+
+        if current.tier != tier {
+            self.record_migration(id, current.tier, tier, Some("Manual update".to_string()))
+                .await?;
+        }
+
+        let updated = self.fetch_active(id).await?;
+        if is_revised(&current, &updated) {
+            self.record_revision(&current, &updated, request.actor, request.reason)
+                .await?;
+        }
+
+        info!("Updated memory {}", id);
+        Ok(updated)
+    }
+
+    async fn delete_memory(&self, id: Uuid) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE memories SET status = 'deleted', updated_at = ?2
+            WHERE id = ?1 AND status = 'active' AND namespace = ?3
+              AND NOT EXISTS (SELECT 1 FROM legal_holds WHERE memory_id = ?1)
+            "#,
+        )
+        .bind(id)
+        .bind(Utc::now())
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            let held: bool = sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM legal_holds WHERE memory_id = ?1 AND namespace = ?2)",
+            )
+            .bind(id)
+            .bind(&self.namespace)
+            .fetch_one(&self.pool)
+            .await?;
+            if held {
+                return Err(legal_hold_error(id));
+            }
+            return Err(MemoryError::NotFound { id: id.to_string() });
+        }
+
+        info!("Soft deleted memory {}", id);
+        Ok(())
+    }
+
+    async fn search_memories(&self, request: SearchRequest) -> Result<SearchResponse> {
+        let start_time = Instant::now();
+
+        let search_type = request.search_type.clone().unwrap_or(SearchType::Semantic);
+        if matches!(search_type, SearchType::Semantic) && request.query_embedding.is_none() {
+            return Err(MemoryError::InvalidRequest {
+                message: "Query embedding is required for semantic search".to_string(),
+            });
+        }
+
+        let limit = request.limit.unwrap_or(10).max(0) as usize;
+        let offset = match request.cursor.as_deref() {
+            Some(cursor) => decode_offset_cursor(cursor)?,
+            None => request.offset.unwrap_or(0).max(0) as usize,
+        };
+        let threshold = request.similarity_threshold.unwrap_or(0.7);
+        let weights = request.hybrid_weights.clone().unwrap_or(HybridWeights {
+            semantic_weight: 0.4,
+            temporal_weight: 0.3,
+            importance_weight: 0.2,
+            access_frequency_weight: 0.1,
+        });
+
+        let candidates = self.load_candidates(&request).await?;
+        let mut scored = Vec::with_capacity(candidates.len());
+
+        for memory in candidates {
+            let similarity = match (&request.query_embedding, &memory.embedding) {
+                (Some(query), Some(embedding)) => cosine_similarity(query, embedding.as_slice()),
+                _ => 0.0,
+            };
+            let text_score = request
+                .query_text
+                .as_deref()
+                .map(|q| text_match_score(q, &memory.content))
+                .unwrap_or(0.0);
+            let temporal = recency_score(memory.created_at);
+            let access_frequency =
+                super::search_backend::calculate_access_frequency_score(memory.access_count);
+            let importance = memory.importance_score as f32;
+
+            let (keep, combined) = match search_type {
+                SearchType::Semantic => (similarity >= threshold, similarity),
+                SearchType::FullText => (text_score > 0.0, text_score),
+                SearchType::Temporal => (true, temporal),
+                // Ranked by reciprocal rank fusion below
+                SearchType::Hybrid => (
+                    (request.query_embedding.is_some() && similarity >= threshold)
+                        || text_score > 0.0,
+                    0.0,
+                ),
+            };
+
+            if keep {
+                scored.push(ScoredMemory {
+                    memory,
+                    similarity,
+                    text_score,
+                    temporal,
+                    access_frequency,
+                    importance,
+                    combined,
+                });
+            }
+        }
+
+        scored.sort_by(|a, b| {
+            b.combined
+                .partial_cmp(&a.combined)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let total_matches = scored.len();
+
+        let include_facets = request.include_facets.unwrap_or(false);
+        let facets = include_facets
+            .then(|| super::search_backend::compute_facets(scored.iter().map(|c| &c.memory)));
+
+        let explain = request.explain_score.unwrap_or(false);
+        let results: Vec<SearchResult> = if matches!(search_type, SearchType::Hybrid) {
+            let semantic = rank_by(&scored, |c| {
+                (request.query_embedding.is_some() && c.similarity >= threshold)
+                    .then_some(c.similarity)
+            });
+            let fulltext = rank_by(&scored, |c| (c.text_score > 0.0).then_some(c.text_score));
+
+            super::search_backend::reciprocal_rank_fusion(
+                semantic,
+                fulltext,
+                request
+                    .rrf_k
+                    .unwrap_or(super::search_backend::DEFAULT_RRF_K),
+                explain,
+            )
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect()
+        } else {
+            scored
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|candidate| {
+                    let score_explanation = explain.then(|| ScoreExplanation {
+                        semantic_contribution: candidate.similarity * weights.semantic_weight,
+                        temporal_contribution: candidate.temporal * weights.temporal_weight,
+                        importance_contribution: candidate.importance * weights.importance_weight,
+                        access_frequency_contribution: candidate.access_frequency
+                            * weights.access_frequency_weight,
+                        total_score: candidate.combined,
+                        factors: vec![format!("brute-force {search_type:?} scan (sqlite)")],
+                        rank_fusion: None,
+                    });
+                    candidate.to_result(score_explanation)
+                })
+                .collect()
+        };
+
+        let suggestions = request.query_text.as_deref().map(|query_text| {
+            super::search_backend::refinement_suggestions(
+                query_text,
+                &results,
+                super::search_backend::MAX_SUGGESTIONS,
+            )
+        });
+        let next_cursor = (offset + results.len() < total_matches)
+            .then(|| encode_offset_cursor(offset + results.len()));
+
+        Ok(SearchResponse {
+            results,
+            total_count: include_facets.then_some(total_matches as i64),
+            facets,
+            suggestions,
+            next_cursor,
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+
+    async fn get_memories_by_tier(
+        &self,
+        tier: MemoryTier,
+        limit: Option<i64>,
+    ) -> Result<Vec<Memory>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM memories
+            WHERE tier = ?1 AND status = 'active' AND namespace = ?3
+            ORDER BY importance_score DESC, updated_at DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(tier_str(tier))
+        .bind(limit.unwrap_or(100))
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(memory_from_row).collect()
+    }
+
+    async fn migrate_memory(
+        &self,
+        id: Uuid,
+        to_tier: MemoryTier,
+        reason: Option<String>,
+    ) -> Result<Memory> {
+        let current = self.fetch_active(id).await?;
+        if current.tier == to_tier {
+            return Ok(current);
+        }
+
+        let valid_transition = matches!(
+            (current.tier, to_tier),
+            (MemoryTier::Working, MemoryTier::Warm)
+                | (MemoryTier::Working, MemoryTier::Cold)
+                | (MemoryTier::Warm, MemoryTier::Cold)
+                | (MemoryTier::Warm, MemoryTier::Working)
+                | (MemoryTier::Cold, MemoryTier::Warm)
+        );
+
+        if !valid_transition {
+            return Err(MemoryError::InvalidTierTransition {
+                from: format!("{:?}", current.tier),
+                to: format!("{to_tier:?}"),
+            });
+        }
+
+        sqlx::query("UPDATE memories SET tier = ?2, updated_at = ?3 WHERE id = ?1")
+            .bind(id)
+            .bind(tier_str(to_tier))
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+
+        self.record_migration(id, current.tier, to_tier, reason)
+            .await?;
+
+        info!(
+            "Migrated memory {} from {:?} to {:?}",
+            id, current.tier, to_tier
+        );
+        self.fetch_active(id).await
+    }
+
+    async fn freeze_memory(
+        &self,
+        memory_id: Uuid,
+        reason: Option<String>,
+    ) -> Result<FreezeMemoryResponse> {
+        let memory = self.fetch_active(memory_id).await?;
+
+        if memory.tier != MemoryTier::Cold {
+            return Err(MemoryError::InvalidRequest {
+                message: format!(
+                    "Can only freeze memories in cold tier, found {:?}",
+                    memory.tier
+                ),
+            });
+        }
+
+        let recall_probability = memory.recall_probability.unwrap_or(0.0);
+        if recall_probability >= 0.2 {
+            return Err(MemoryError::InvalidRequest {
+                message: format!(
+                    "Can only freeze memories with P(r) < 0.2, found {recall_probability:.3}"
+                ),
+            });
+        }
+
+        let compression_result =
+            ZstdCompressionEngine::new().compress_memory_data(&memory.content, &memory.metadata)?;
+        let (compressed_data, _, _, compression_ratio) =
+            FrozenMemoryCompression::to_database_format(compression_result);
+
+        let frozen_id = Uuid::new_v4();
+        let frozen_at = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO frozen_memories (
+                id, original_memory_id, compressed_content, original_tier,
+                freeze_reason, compression_ratio, frozen_at, namespace
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            "#,
+        )
+        .bind(frozen_id)
+        .bind(memory_id)
+        .bind(&compressed_data)
+        .bind(tier_str(memory.tier))
+        .bind(
+            reason
+                .as_deref()
+                .unwrap_or("Auto-frozen: P(r) < 0.2 threshold"),
+        )
+        .bind(compression_ratio)
+        .bind(frozen_at)
+        .bind(&self.namespace)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE memories SET tier = 'frozen', status = 'archived', updated_at = ?2 WHERE id = ?1",
+        )
+        .bind(memory_id)
+        .bind(frozen_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        self.record_migration(
+            memory_id,
+            memory.tier,
+            MemoryTier::Frozen,
+            Some(format!("Frozen with {compression_ratio:.2}:1 compression")),
+        )
+        .await?;
+
+        Ok(FreezeMemoryResponse {
+            frozen_id,
+            compression_ratio: Some(compression_ratio),
+            original_tier: memory.tier,
+            frozen_at,
+        })
+    }
+
+    /// Restores a frozen memory. Unlike PostgreSQL, the embedded backend does
+    /// not apply the intentional retrieval delay.
+    async fn unfreeze_memory(
+        &self,
+        frozen_id: Uuid,
+        target_tier: Option<MemoryTier>,
+    ) -> Result<UnfreezeMemoryResponse> {
+        let row = sqlx::query(
+            "SELECT original_memory_id, compressed_content FROM frozen_memories WHERE id = ?1 AND namespace = ?2 AND unfrozen_at IS NULL",
+        )
+        .bind(frozen_id)
+        .bind(&self.namespace)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| MemoryError::NotFound {
+            id: frozen_id.to_string(),
+        })?;
+
+        let memory_id: Uuid = row.try_get("original_memory_id")?;
+        let compressed: Vec<u8> = row.try_get("compressed_content")?;
+        let data = ZstdCompressionEngine::new().decompress_memory_data(&compressed)?;
+        let restoration_tier = target_tier.unwrap_or(MemoryTier::Warm);
+        let unfrozen_at = Utc::now();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE memories
+            SET content = ?2, metadata = ?3, tier = ?4, status = 'active', updated_at = ?5
+            WHERE id = ?1
+            "#,
+        )
+        .bind(memory_id)
+        .bind(&data.content)
+        .bind(serde_json::to_string(&data.metadata)?)
+        .bind(tier_str(restoration_tier))
+        .bind(unfrozen_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE frozen_memories SET unfrozen_at = ?2 WHERE id = ?1")
+            .bind(frozen_id)
+            .bind(unfrozen_at)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        self.record_migration(
+            memory_id,
+            MemoryTier::Frozen,
+            restoration_tier,
+            Some("Unfrozen".to_string()),
+        )
+        .await?;
+
+        Ok(UnfreezeMemoryResponse {
+            memory_id,
+            retrieval_delay_seconds: 0,
+            restoration_tier,
+            unfrozen_at,
+        })
+    }
+
+    async fn get_statistics(&self) -> Result<MemoryStatistics> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                SUM(CASE WHEN tier = 'working' AND status = 'active' THEN 1 ELSE 0 END) AS working_count,
+                SUM(CASE WHEN tier = 'warm' AND status = 'active' THEN 1 ELSE 0 END) AS warm_count,
+                SUM(CASE WHEN tier = 'cold' AND status = 'active' THEN 1 ELSE 0 END) AS cold_count,
+                SUM(CASE WHEN status = 'active' THEN 1 ELSE 0 END) AS total_active,
+                SUM(CASE WHEN status = 'deleted' THEN 1 ELSE 0 END) AS total_deleted,
+                AVG(CASE WHEN status = 'active' THEN importance_score END) AS avg_importance,
+                MAX(CASE WHEN status = 'active' THEN access_count END) AS max_access_count,
+                AVG(CASE WHEN status = 'active' THEN access_count END) AS avg_access_count
+            FROM memories
+            WHERE namespace = ?1
+            "#,
+        )
+        .bind(&self.namespace)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(MemoryStatistics {
+            working_count: row.try_get("working_count")?,
+            warm_count: row.try_get("warm_count")?,
+            cold_count: row.try_get("cold_count")?,
+            total_active: row.try_get("total_active")?,
+            total_deleted: row.try_get("total_deleted")?,
+            avg_importance: row.try_get("avg_importance")?,
+            max_access_count: row.try_get("max_access_count")?,
+            avg_access_count: row.try_get("avg_access_count")?,
+        })
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        match sqlx::query("SELECT 1").fetch_one(&self.pool).await {
+            Ok(_) => Ok(true),
+            Err(_) => Ok(false),
+        }
+    }
+
+    async fn memory_history(&self, id: Uuid, limit: Option<i64>) -> Result<Vec<MemoryRevision>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM memory_revisions
+            WHERE memory_id = ?1 AND namespace = ?2
+            ORDER BY revision DESC
+            LIMIT ?3
+            "#,
+        )
+        .bind(id)
+        .bind(&self.namespace)
+        .bind(limit.unwrap_or(50))
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(revision_from_row).collect()
+    }
+
+    async fn get_memory_revision(&self, id: Uuid, revision: i32) -> Result<MemoryRevision> {
+        let row = sqlx::query(
+            "SELECT * FROM memory_revisions WHERE memory_id = ?1 AND revision = ?2 AND namespace = ?3",
+        )
+        .bind(id)
+        .bind(revision)
+        .bind(&self.namespace)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| MemoryError::NotFound {
+            id: format!("{id} revision {revision}"),
+        })?;
+        revision_from_row(&row)
+    }
+
+    async fn get_memories_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Memory>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sql = format!(
+            "SELECT * FROM memories WHERE status = 'active' AND namespace = ?1 AND id IN ({})",
+            placeholders(2, ids.len())
+        );
+        let mut query = sqlx::query(&sql).bind(&self.namespace);
+        for id in ids {
+            query = query.bind(*id);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+        rows.iter().map(memory_from_row).collect()
+    }
+
+    async fn link_memories(&self, request: LinkMemoriesRequest) -> Result<MemoryRelation> {
+        request.validate()?;
+        for id in [request.source_id, request.target_id] {
+            self.fetch_active(id).await?;
+        }
+
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            INSERT INTO memory_relations (
+                id, namespace, source_id, target_id, relation_type, strength, metadata,
+                created_by, created_at, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)
+            ON CONFLICT (source_id, target_id, relation_type) DO UPDATE
+            SET strength = excluded.strength,
+                metadata = excluded.metadata,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(&self.namespace)
+        .bind(request.source_id)
+        .bind(request.target_id)
+        .bind(request.relation_type.as_str())
+        .bind(request.strength.unwrap_or(1.0))
+        .bind(serde_json::to_string(
+            &request.metadata.unwrap_or_else(|| serde_json::json!({})),
+        )?)
+        .bind(request.created_by)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        let row = sqlx::query(
+            "SELECT * FROM memory_relations WHERE source_id = ?1 AND target_id = ?2 AND relation_type = ?3",
+        )
+        .bind(request.source_id)
+        .bind(request.target_id)
+        .bind(request.relation_type.as_str())
+        .fetch_one(&self.pool)
+        .await?;
+        relation_from_row(&row)
+    }
+
+    async fn unlink_memories(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+        relation_type: Option<RelationshipType>,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM memory_relations
+            WHERE namespace = ?1 AND source_id = ?2 AND target_id = ?3
+              AND (?4 IS NULL OR relation_type = ?4)
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(source_id)
+        .bind(target_id)
+        .bind(relation_type.map(|r| r.as_str()))
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_relations(
+        &self,
+        ids: &[Uuid],
+        filter: &RelationFilter,
+    ) -> Result<Vec<MemoryRelation>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let id_list = placeholders(4, ids.len());
+        let sql = format!(
+            r#"
+            SELECT r.*
+            FROM memory_relations r
+            JOIN memories s ON s.id = r.source_id AND s.status = 'active'
+            JOIN memories t ON t.id = r.target_id AND t.status = 'active'
+            WHERE r.namespace = ?1
+              AND ((?2 AND r.source_id IN ({id_list})) OR (?3 AND r.target_id IN ({id_list})))
+            ORDER BY r.strength DESC, r.id
+            "#
+        );
+        let mut query = sqlx::query(&sql)
+            .bind(&self.namespace)
+            .bind(filter.direction.includes_outgoing())
+            .bind(filter.direction.includes_incoming());
+        for id in ids {
+            query = query.bind(*id);
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+
+        let mut relations = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let relation = relation_from_row(row)?;
+            let type_matches = filter
+                .relation_types
+                .as_ref()
+                .is_none_or(|types| types.contains(&relation.relation_type));
+            if type_matches && relation.strength >= filter.min_strength.unwrap_or(0.0) {
+                relations.push(relation);
+            }
+        }
+        Ok(relations)
+    }
+
+    async fn link_memory_entities(
+        &self,
+        memory_id: Uuid,
+        mentions: Vec<EntityMention>,
+    ) -> Result<Vec<Entity>> {
+        self.fetch_active(memory_id).await?;
+
+        let mut tx = self.pool.begin().await?;
+        let mut entity_ids = Vec::new();
+        for mention in mentions {
+            let keys = mention.lookup_keys();
+            if keys.is_empty() {
+                continue;
+            }
+            let entity_id = self.resolve_entity(&mut tx, &mention, &keys).await?;
+            let now = Utc::now();
+
+            let canonical = mention.canonical_name();
+            for alias in [mention.name.as_str(), canonical.as_str()] {
+                let normalized = normalize_alias(alias);
+                if normalized.is_empty() {
+                    continue;
+                }
+                sqlx::query(
+                    r#"
+                    INSERT INTO entity_aliases (entity_id, namespace, alias, normalized_alias, created_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5)
+                    ON CONFLICT (entity_id, normalized_alias) DO NOTHING
+                    "#,
+                )
+                .bind(entity_id)
+                .bind(&self.namespace)
+                .bind(alias)
+                .bind(normalized)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            let linked = sqlx::query(
+                r#"
+                INSERT INTO memory_entities (memory_id, entity_id, mention, confidence, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT (memory_id, entity_id) DO NOTHING
+                "#,
+            )
+            .bind(memory_id)
+            .bind(entity_id)
+            .bind(&mention.name)
+            .bind(mention.confidence)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+            if linked.rows_affected() > 0 {
+                sqlx::query(
+                    "UPDATE entities SET mention_count = mention_count + 1, updated_at = ?2 WHERE id = ?1",
+                )
+                .bind(entity_id)
+                .bind(now)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            if !entity_ids.contains(&entity_id) {
+                entity_ids.push(entity_id);
+            }
+        }
+        tx.commit().await?;
+
+        let mut entities = Vec::with_capacity(entity_ids.len());
+        for id in entity_ids {
+            let row = sqlx::query("SELECT * FROM entities WHERE id = ?1")
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?;
+            entities.extend(self.entities_from_rows(&[row]).await?);
+        }
+        Ok(entities)
+    }
+
+    async fn find_entity(&self, name: &str) -> Result<Option<Entity>> {
+        let keys = lookup_keys(name);
+        if keys.is_empty() {
+            return Ok(None);
+        }
+        let sql = format!(
+            r#"
+            SELECT e.*
+            FROM entities e
+            WHERE e.namespace = ?1
+              AND EXISTS (
+                  SELECT 1 FROM entity_aliases a
+                  WHERE a.entity_id = e.id AND a.normalized_alias IN ({})
+              )
+            ORDER BY e.mention_count DESC, e.id
+            LIMIT 1
+            "#,
+            placeholders(2, keys.len())
+        );
+        let mut query = sqlx::query(&sql).bind(&self.namespace);
+        for key in &keys {
+            query = query.bind(key);
+        }
+        let Some(row) = query.fetch_optional(&self.pool).await? else {
+            return Ok(None);
+        };
+        Ok(self.entities_from_rows(&[row]).await?.pop())
+    }
+
+    async fn list_entities(
+        &self,
+        entity_type: Option<EntityType>,
+        limit: i64,
+    ) -> Result<Vec<Entity>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM entities
+            WHERE namespace = ?1 AND (?2 IS NULL OR entity_type = ?2)
+            ORDER BY mention_count DESC, canonical_name
+            LIMIT ?3
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(entity_type.map(|t| t.as_str()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        self.entities_from_rows(&rows).await
+    }
+
+    async fn record_conflict(&self, conflict: NewMemoryConflict) -> Result<MemoryConflict> {
+        let now = Utc::now();
+        let resolved_at = (conflict.status != ConflictStatus::Open).then_some(now);
+        sqlx::query(
+            r#"
+            INSERT INTO memory_conflicts (
+                id, namespace, memory_id, conflicting_memory_id, status, superseded_memory_id,
+                reason, confidence, detector, created_at, resolved_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT (memory_id, conflicting_memory_id) DO UPDATE
+            SET confidence = MAX(memory_conflicts.confidence, excluded.confidence)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(&self.namespace)
+        .bind(conflict.memory_id)
+        .bind(conflict.conflicting_memory_id)
+        .bind(conflict.status.as_str())
+        .bind(conflict.superseded_memory_id)
+        .bind(&conflict.reason)
+        .bind(conflict.confidence.clamp(0.0, 1.0))
+        .bind(&conflict.detector)
+        .bind(now)
+        .bind(resolved_at)
+        .execute(&self.pool)
+        .await?;
+
+        let row = sqlx::query(
+            "SELECT * FROM memory_conflicts WHERE memory_id = ?1 AND conflicting_memory_id = ?2",
+        )
+        .bind(conflict.memory_id)
+        .bind(conflict.conflicting_memory_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let conflict = conflict_from_row(&row)?;
+        if let Some(superseded) = conflict.superseded_memory_id {
+            self.end_valid_time(superseded, conflict.other_memory(superseded))
+                .await?;
+        }
+        Ok(conflict)
+    }
+
+    async fn list_conflicts(
+        &self,
+        status: Option<ConflictStatus>,
+        memory_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<MemoryConflict>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM memory_conflicts
+            WHERE namespace = ?1
+              AND (?2 IS NULL OR status = ?2)
+              AND (?3 IS NULL OR memory_id = ?3 OR conflicting_memory_id = ?3)
+            ORDER BY created_at DESC, id
+            LIMIT ?4
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(status.map(|s| s.as_str()))
+        .bind(memory_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(conflict_from_row).collect()
+    }
+
+    async fn resolve_conflict(&self, request: ResolveConflictRequest) -> Result<MemoryConflict> {
+        let row = sqlx::query("SELECT * FROM memory_conflicts WHERE id = ?1 AND namespace = ?2")
+            .bind(request.conflict_id)
+            .bind(&self.namespace)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or_else(|| MemoryError::NotFound {
+                id: request.conflict_id.to_string(),
+            })?;
+        let previous = conflict_from_row(&row)?;
+        let superseded = request.superseded_memory_id(&previous)?;
+        let resolved_at = (request.status != ConflictStatus::Open).then(Utc::now);
+
+        sqlx::query(
+            r#"
+            UPDATE memory_conflicts
+            SET status = ?2, superseded_memory_id = ?3, resolved_by = ?4, resolved_at = ?5
+            WHERE id = ?1
+            "#,
+        )
+        .bind(request.conflict_id)
+        .bind(request.status.as_str())
+        .bind(superseded)
+        .bind(&request.resolved_by)
+        .bind(resolved_at)
+        .execute(&self.pool)
+        .await?;
+
+        let row = sqlx::query("SELECT * FROM memory_conflicts WHERE id = ?1")
+            .bind(request.conflict_id)
+            .fetch_one(&self.pool)
+            .await?;
+        let conflict = conflict_from_row(&row)?;
+
+        if let Some(reverted) = previous
+            .superseded_memory_id
+            .filter(|id| Some(*id) != superseded)
+        {
+            self.restore_valid_time(reverted, previous.other_memory(reverted))
+                .await?;
+        }
+        if let Some(superseded) = superseded {
+            self.end_valid_time(superseded, conflict.other_memory(superseded))
+                .await?;
+        }
+        Ok(conflict)
+    }
+
+    async fn pii_subject_key(&self, subject_id: &str, new_key: &[u8]) -> Result<Vec<u8>> {
+        sqlx::query(
+            "INSERT INTO pii_vault_keys (subject_id, wrapped_key, created_at) VALUES (?1, ?2, ?3) ON CONFLICT (subject_id) DO NOTHING",
+        )
+        .bind(subject_id)
+        .bind(new_key)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        let key = sqlx::query_scalar::<_, Vec<u8>>(
+            "SELECT wrapped_key FROM pii_vault_keys WHERE subject_id = ?1",
+        )
+        .bind(subject_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(key)
+    }
+
+    async fn vault_pii_value(
+        &self,
+        token: &str,
+        subject_id: &str,
+        kind: &str,
+        ciphertext: &[u8],
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO pii_vault (token, namespace, subject_id, kind, ciphertext, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (namespace, token, subject_id) DO NOTHING
+            "#,
+        )
+        .bind(token)
+        .bind(&self.namespace)
+        .bind(subject_id)
+        .bind(kind)
+        .bind(ciphertext)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn vaulted_pii_values(&self, token: &str) -> Result<Vec<VaultedPiiValue>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT v.subject_id, v.kind, v.ciphertext, k.wrapped_key
+            FROM pii_vault v
+            JOIN pii_vault_keys k ON k.subject_id = v.subject_id
+            WHERE v.token = ?1 AND v.namespace = ?2
+            "#,
+        )
+        .bind(token)
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|row| {
+                Ok(VaultedPiiValue {
+                    subject_id: row.try_get("subject_id")?,
+                    kind: row.try_get("kind")?,
+                    ciphertext: row.try_get("ciphertext")?,
+                    wrapped_key: row.try_get("wrapped_key")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn encryption_keys(&self) -> Result<Vec<WrappedDataKey>> {
+        let rows = sqlx::query(
+            "SELECT key_id, wrapped_key, master_key_id, created_at FROM memory_encryption_keys ORDER BY created_at, key_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|row| {
+                Ok(WrappedDataKey {
+                    key_id: row.try_get("key_id")?,
+                    wrapped_key: row.try_get("wrapped_key")?,
+                    master_key_id: row.try_get("master_key_id")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn add_encryption_key(&self, key: &WrappedDataKey) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO memory_encryption_keys (key_id, wrapped_key, master_key_id, created_at) VALUES (?1, ?2, ?3, ?4) ON CONFLICT (key_id) DO NOTHING",
+        )
+        .bind(&key.key_id)
+        .bind(&key.wrapped_key)
+        .bind(&key.master_key_id)
+        .bind(key.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn rewrap_encryption_keys(
+        &self,
+        master_key_id: &str,
+        wrapped_keys: &[(String, Vec<u8>)],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for (key_id, wrapped_key) in wrapped_keys {
+            sqlx::query(
+                "UPDATE memory_encryption_keys SET wrapped_key = ?1, master_key_id = ?2 WHERE key_id = ?3",
+            )
+            .bind(wrapped_key)
+            .bind(master_key_id)
+            .bind(key_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn list_namespaces(&self) -> Result<Vec<String>> {
+        let namespaces = sqlx::query_scalar(
+            "SELECT DISTINCT namespace FROM memories WHERE status = 'active' ORDER BY namespace",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(namespaces)
+    }
+
+    async fn memories_created_before(
+        &self,
+        before: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Memory>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM memories
+            WHERE namespace = ?1 AND status = 'active' AND created_at < ?2
+              AND (?3 IS NULL OR id > ?3)
+            ORDER BY id
+            LIMIT ?4
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(before)
+        .bind(after)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(memory_from_row).collect()
+    }
+
+    async fn purge_memory_history(&self, id: Uuid) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM memory_revisions
+            WHERE memory_id = ?1 AND namespace = ?2
+              AND revision < (
+                  SELECT MAX(revision) FROM memory_revisions
+                  WHERE memory_id = ?1 AND namespace = ?2
+              )
+            "#,
+        )
+        .bind(id)
+        .bind(&self.namespace)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn unlink_memory_entities(&self, memory_id: Uuid) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            r#"
+            UPDATE entities
+            SET mention_count = MAX(mention_count - 1, 0), updated_at = ?3
+            WHERE namespace = ?2
+              AND id IN (SELECT entity_id FROM memory_entities WHERE memory_id = ?1)
+            "#,
+        )
+        .bind(memory_id)
+        .bind(&self.namespace)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM memory_entities
+            WHERE memory_id = ?1
+              AND entity_id IN (SELECT id FROM entities WHERE namespace = ?2)
+            "#,
+        )
+        .bind(memory_id)
+        .bind(&self.namespace)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn forget_pii_values(&self, subject_id: &str, tokens: &[String]) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut deleted = 0;
+        for token in tokens {
+            deleted += sqlx::query(
+                "DELETE FROM pii_vault WHERE namespace = ?1 AND subject_id = ?2 AND token = ?3",
+            )
+            .bind(&self.namespace)
+            .bind(subject_id)
+            .bind(token)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(deleted)
+    }
+
+    async fn place_legal_hold(
+        &self,
+        id: Uuid,
+        reason: &str,
+        placed_by: Option<&str>,
+    ) -> Result<LegalHold> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO legal_holds (memory_id, namespace, reason, placed_by, placed_at)
+            SELECT id, namespace, ?3, ?4, ?5 FROM memories WHERE id = ?1 AND namespace = ?2
+            ON CONFLICT (memory_id) DO UPDATE
+            SET reason = excluded.reason, placed_by = excluded.placed_by, placed_at = excluded.placed_at
+            RETURNING memory_id, namespace, reason, placed_by, placed_at
+            "#,
+        )
+        .bind(id)
+        .bind(&self.namespace)
+        .bind(reason)
+        .bind(placed_by)
+        .bind(Utc::now())
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| MemoryError::NotFound { id: id.to_string() })?;
+
+        info!("Placed legal hold on memory {}", id);
+        legal_hold_from_row(&row)
+    }
+
+    async fn release_legal_hold(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM legal_holds WHERE memory_id = ?1 AND namespace = ?2")
+            .bind(id)
+            .bind(&self.namespace)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn legal_holds(&self) -> Result<Vec<LegalHold>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT memory_id, namespace, reason, placed_by, placed_at
+            FROM legal_holds
+            WHERE namespace = ?1
+            ORDER BY placed_at DESC
+            "#,
+        )
+        .bind(&self.namespace)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(legal_hold_from_row).collect()
+    }
+
+    async fn get_memory_entities(&self, memory_id: Uuid) -> Result<Vec<Entity>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT e.*
+            FROM entities e
+            JOIN memory_entities me ON me.entity_id = e.id
+            WHERE e.namespace = ?1 AND me.memory_id = ?2
+            ORDER BY me.confidence DESC, e.canonical_name
+            "#,
+        )
+        .bind(&self.namespace)
+        .bind(memory_id)
+        .fetch_all(&self.pool)
+        .await?;
+        self.entities_from_rows(&rows).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::relations::{RelationDirection, TraversalOptions};
+
+    async fn storage() -> SqliteMemoryStorage {
+        SqliteMemoryStorage::connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite should open")
+    }
+
+    fn request(content: &str, embedding: Vec<f32>) -> CreateMemoryRequest {
+        CreateMemoryRequest {
+            content: content.to_string(),
+            embedding: Some(embedding),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_embedding_roundtrip() {
+        let embedding = vec![0.25, -1.5, 3.0];
+        assert_eq!(decode_embedding(&encode_embedding(&embedding)), embedding);
+    }
+
+    #[tokio::test]
+    async fn test_create_get_update_delete() {
+        let storage = storage().await;
+        let memory = storage
+            .create_memory(request("rust ownership rules", vec![1.0, 0.0]))
+            .await
+            .expect("create");
+        assert_eq!(memory.tier, MemoryTier::Working);
+
+        let fetched = storage.get_memory(memory.id).await.expect("get");
+        assert_eq!(fetched.access_count, 1);
+        assert_eq!(fetched.embedding.map(|v| v.to_vec()), Some(vec![1.0, 0.0]));
+
+        let updated = storage
+            .update_memory(
+                memory.id,
+                UpdateMemoryRequest {
+                    content: Some("rust borrowing rules".to_string()),
+                    embedding: None,
+                    tier: Some(MemoryTier::Warm),
+                    importance_score: Some(0.9),
+                    metadata: None,
+                    expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
+                },
+            )
+            .await
+            .expect("update");
+        assert_eq!(updated.content, "rust borrowing rules");
+        assert_eq!(updated.tier, MemoryTier::Warm);
+
+        storage.delete_memory(memory.id).await.expect("delete");
+        assert!(matches!(
+            storage.get_memory(memory.id).await,
+            Err(MemoryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_cursor_pages_through_results() {
+        let storage = storage().await;
+        for content in ["first note", "second note", "third note"] {
+            storage
+                .create_memory(request(content, vec![1.0, 0.0]))
+                .await
+                .expect("create");
+        }
+
+        let page_request = |cursor: Option<String>| SearchRequest {
+            search_type: Some(SearchType::Temporal),
+            limit: Some(2),
+            cursor,
+            ..Default::default()
+        };
+        let first = storage
+            .search_memories(page_request(None))
+            .await
+            .expect("first page");
+        assert_eq!(first.results.len(), 2);
+
+        let second = storage
+            .search_memories(page_request(first.next_cursor.clone()))
+            .await
+            .expect("second page");
+        assert_eq!(second.results.len(), 1);
+        assert!(second.next_cursor.is_none());
+        assert!(first
+            .results
+            .iter()
+            .all(|r| r.memory.id != second.results[0].memory.id));
+
+        assert!(storage
+            .search_memories(page_request(Some("not a cursor".to_string())))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_scans_past_the_first_candidate_page() {
+        let storage = storage().await;
+        for i in 0..CANDIDATE_PAGE_SIZE {
+            storage
+                .create_memory(CreateMemoryRequest {
+                    importance_score: Some(0.9),
+                    ..request(&format!("filler note {}", i), vec![1.0, 0.0])
+                })
+                .await
+                .expect("create filler");
+        }
+        let least_important = storage
+            .create_memory(CreateMemoryRequest {
+                importance_score: Some(0.1),
+                ..request("quarterly budget review", vec![0.0, 1.0])
+            })
+            .await
+            .expect("create");
+
+        let response = storage
+            .search_memories(SearchRequest {
+                query_text: Some("budget".to_string()),
+                search_type: Some(SearchType::FullText),
+                ..Default::default()
+            })
+            .await
+            .expect("search");
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].memory.id, least_important.id);
+    }
+
+    #[tokio::test]
+    async fn test_update_history_and_revert() {
+        let storage = storage().await;
+        let memory = storage
+            .create_memory(request("standup is at 9:30", vec![1.0, 0.0]))
+            .await
+            .expect("create");
+
+        storage
+            .update_memory(
+                memory.id,
+                UpdateMemoryRequest {
+                    content: Some("standup is at 10:00".to_string()),
+                    embedding: Some(vec![0.0, 1.0]),
+                    tier: None,
+                    importance_score: None,
+                    metadata: None,
+                    expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: Some("harvester".to_string()),
+                    reason: Some("Harvested from conversation".to_string()),
+                    content_hash: None,
+                },
+            )
+            .await
+            .expect("update");
+
+        let history = storage
+            .memory_history(memory.id, None)
+            .await
+            .expect("history");
+        assert_eq!(
+            history.iter().map(|r| r.revision).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert_eq!(history[0].actor.as_deref(), Some("harvester"));
+        assert_eq!(history[1].content, "standup is at 9:30");
+
+        let reverted = storage
+            .revert_memory(
+                memory.id,
+                1,
+                Some(vec![1.0, 0.0]),
+                Some("alice".to_string()),
+            )
+            .await
+            .expect("revert");
+        assert_eq!(reverted.content, "standup is at 9:30");
+
+        let latest = storage
+            .get_memory_revision(memory.id, 3)
+            .await
+            .expect("revert revision");
+        assert_eq!(latest.reason.as_deref(), Some("Revert to revision 1"));
+        assert!(storage.get_memory_revision(memory.id, 4).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_relations_and_traversal() {
+        let storage = storage().await;
+        let mut ids = Vec::new();
+        for content in [
+            "outage on friday",
+            "expired certificate",
+            "renewal cron removed",
+        ] {
+            let memory = storage
+                .create_memory(request(content, vec![1.0, 0.0]))
+                .await
+                .expect("create");
+            ids.push(memory.id);
+        }
+        let link = |source_id, target_id, relation_type| LinkMemoriesRequest {
+            source_id,
+            target_id,
+            relation_type,
+            strength: Some(0.8),
+            metadata: None,
+            created_by: Some("alice".to_string()),
+        };
+        storage
+            .link_memories(link(ids[0], ids[1], RelationshipType::CausedBy))
+            .await
+            .expect("link outage");
+        storage
+            .link_memories(link(ids[1], ids[2], RelationshipType::CausedBy))
+            .await
+            .expect("link certificate");
+        assert!(storage
+            .link_memories(link(ids[0], ids[0], RelationshipType::IsA))
+            .await
+            .is_err());
+
+        let neighbors = storage
+            .get_neighbors(ids[1], RelationFilter::default(), 10)
+            .await
+            .expect("neighbors");
+        assert_eq!(neighbors.len(), 2);
+
+        let outgoing = RelationFilter {
+            direction: RelationDirection::Outgoing,
+            ..Default::default()
+        };
+        let options = TraversalOptions {
+            filter: outgoing.clone(),
+            max_depth: 2,
+            max_nodes: 10,
+        };
+        let reached = storage
+            .traverse_relations(ids[0], &options)
+            .await
+            .expect("traverse");
+        assert_eq!(
+            reached
+                .iter()
+                .map(|n| (n.memory.id, n.depth))
+                .collect::<Vec<_>>(),
+            vec![(ids[1], 1), (ids[2], 2)]
+        );
+        assert!((reached[1].path_strength - 0.64).abs() < 1e-9);
+
+        let only_is_a = RelationFilter {
+            relation_types: Some(vec![RelationshipType::IsA]),
+            ..outgoing
+        };
+        assert!(storage
+            .get_neighbors(ids[0], only_is_a, 10)
+            .await
+            .expect("filtered neighbors")
+            .is_empty());
+
+        let removed = storage
+            .unlink_memories(ids[0], ids[1], None)
+            .await
+            .expect("unlink");
+        assert_eq!(removed, 1);
+        assert_eq!(
+            storage
+                .get_neighbors(ids[0], RelationFilter::default(), 10)
+                .await
+                .expect("neighbors after unlink")
+                .len(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_entities_resolve_aliases_and_filter_search() {
+        use crate::memory::entities::{index_memory_entities, EntityExtractor};
+
+        let storage = storage().await;
+        let extractor = EntityExtractor::new();
+        let mut linked = Vec::new();
+        for (content, embedding) in [
+            ("Moved the Atlas service to Postgres", vec![1.0, 0.0]),
+            ("pg vacuum settings need tuning", vec![0.0, 1.0]),
+            ("Lunch with the team", vec![0.5, 0.5]),
+        ] {
+            let memory = storage
+                .create_memory(request(content, embedding))
+                .await
+                .expect("create");
+            index_memory_entities(&storage, &extractor, None, &memory)
+                .await
+                .expect("index entities");
+            linked.push(memory);
+        }
+
+        let postgres = storage
+            .find_entity("PostgreSQL")
+            .await
+            .expect("find")
+            .expect("entity exists");
+        assert_eq!(postgres.canonical_name, "PostgreSQL");
+        assert_eq!(postgres.mention_count, 2);
+        for alias in ["Postgres", "pg"] {
+            assert!(postgres.aliases.contains(&alias.to_string()));
+        }
+
+        let tools = storage
+            .list_entities(Some(EntityType::Tool), 10)
+            .await
+            .expect("list");
+        assert_eq!(tools.len(), 1);
+        let entities = storage
+            .get_memory_entities(linked[0].id)
+            .await
+            .expect("memory entities");
+        assert_eq!(entities.len(), 2);
+
+        let response = storage
+            .search_memories(SearchRequest {
+                search_type: Some(SearchType::Temporal),
+                entity: Some("postgres".to_string()),
+                ..Default::default()
+            })
+            .await
+            .expect("search");
+        let mut ids: Vec<Uuid> = response.results.iter().map(|r| r.memory.id).collect();
+        ids.sort();
+        let mut expected = vec![linked[0].id, linked[1].id];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        // An unknown alias joins the entity with the most similar embedding
+        let mention = |name: &str, embedding: Vec<f32>| EntityMention {
+            name: name.to_string(),
+            entity_type: EntityType::Project,
+            confidence: 0.8,
+            embedding: Some(embedding),
+        };
+        let first = storage
+            .link_memory_entities(linked[2].id, vec![mention("Orion", vec![1.0, 0.0])])
+            .await
+            .expect("link");
+        let second = storage
+            .link_memory_entities(
+                linked[1].id,
+                vec![mention("Orion Platform", vec![0.99, 0.05])],
+            )
+            .await
+            .expect("link");
+        assert_eq!(first[0].id, second[0].id);
+        assert_eq!(second[0].mention_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_contradictions_supersede_and_resolve() {
+        use crate::memory::contradictions::ContradictionDetector;
+
+        let storage = storage().await;
+        let detector = ContradictionDetector::default();
+        let berlin = storage
+            .create_memory(request("Alice lives in Berlin", vec![1.0, 0.0]))
+            .await
+            .expect("create");
+        let lisbon = storage
+            .create_memory(CreateMemoryRequest {
+                metadata: Some(serde_json::json!({ "pattern_type": "Correction" })),
+                ..request("Actually, Alice lives in Lisbon", vec![0.95, 0.1])
+            })
+            .await
+            .expect("create");
+
+        let conflicts = detector.detect(&storage, &lisbon).await.expect("detect");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].status, ConflictStatus::Superseded);
+        assert_eq!(conflicts[0].superseded_memory_id, Some(berlin.id));
+        assert_eq!(conflicts[0].current_memory_id(), Some(lisbon.id));
+
+        let search = |include_superseded| SearchRequest {
+            query_embedding: Some(vec![1.0, 0.0]),
+            include_superseded: Some(include_superseded),
+            ..Default::default()
+        };
+        let current = storage
+            .search_memories(search(false))
+            .await
+            .expect("search");
+        assert_eq!(current.results.len(), 1);
+        assert_eq!(current.results[0].memory.id, lisbon.id);
+        let all = storage.search_memories(search(true)).await.expect("search");
+        assert_eq!(all.results.len(), 2);
+
+        // Without a correction the conflict waits for a resolution
+        let enabled = storage
+            .create_memory(request("The cache is enabled", vec![0.0, 1.0]))
+            .await
+            .expect("create");
+        let disabled = storage
+            .create_memory(request("The cache is not enabled", vec![0.1, 1.0]))
+            .await
+            .expect("create");
+        let conflicts = detector.detect(&storage, &disabled).await.expect("detect");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].status, ConflictStatus::Open);
+        assert_eq!(conflicts[0].conflicting_memory_id, enabled.id);
+
+        let open = storage
+            .list_conflicts(Some(ConflictStatus::Open), None, 10)
+            .await
+            .expect("list");
+        assert_eq!(open.len(), 1);
+
+        let resolved = storage
+            .resolve_conflict(ResolveConflictRequest {
+                conflict_id: open[0].id,
+                status: ConflictStatus::Superseded,
+                current_memory_id: Some(enabled.id),
+                resolved_by: Some("tester".to_string()),
+            })
+            .await
+            .expect("resolve");
+        assert_eq!(resolved.superseded_memory_id, Some(disabled.id));
+        assert!(resolved.resolved_at.is_some());
+        assert!(matches!(
+            storage
+                .resolve_conflict(ResolveConflictRequest {
+                    conflict_id: Uuid::new_v4(),
+                    status: ConflictStatus::BothValid,
+                    current_memory_id: None,
+                    resolved_by: None,
+                })
+                .await,
+            Err(MemoryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_as_of_search_follows_valid_time() {
+        use crate::memory::contradictions::NewMemoryConflict;
+
+        let storage = storage().await;
+        let date = |s: &str| {
+            DateTime::parse_from_rfc3339(s)
+                .expect("date")
+                .with_timezone(&Utc)
+        };
+        let mysql = storage
+            .create_memory(CreateMemoryRequest {
+                valid_from: Some(date("2020-01-01T00:00:00Z")),
+                ..request("The team uses MySQL", vec![1.0, 0.0])
+            })
+            .await
+            .expect("create");
+        let postgres = storage
+            .create_memory(CreateMemoryRequest {
+                valid_from: Some(date("2023-06-01T00:00:00Z")),
+                ..request("The team migrated to Postgres", vec![0.9, 0.1])
+            })
+            .await
+            .expect("create");
+
+        // Superseding ends the old fact where the new one starts
+        storage
+            .record_conflict(NewMemoryConflict {
+                memory_id: postgres.id,
+                conflicting_memory_id: mysql.id,
+                status: ConflictStatus::Superseded,
+                superseded_memory_id: Some(mysql.id),
+                reason: "database changed".to_string(),
+                confidence: 0.9,
+                detector: "test".to_string(),
+            })
+            .await
+            .expect("record");
+        let mysql = storage.get_memory(mysql.id).await.expect("get");
+        assert_eq!(mysql.valid_to, Some(date("2023-06-01T00:00:00Z")));
+
+        let as_of = |at: Option<DateTime<Utc>>| SearchRequest {
+            query_embedding: Some(vec![1.0, 0.0]),
+            as_of: at,
+            ..Default::default()
+        };
+        let then = storage
+            .search_memories(as_of(Some(date("2022-03-15T00:00:00Z"))))
+            .await
+            .expect("search");
+        assert_eq!(then.results.len(), 1);
+        assert_eq!(then.results[0].memory.id, mysql.id);
+        let now = storage
+            .search_memories(as_of(Some(Utc::now())))
+            .await
+            .expect("search");
+        assert_eq!(now.results.len(), 1);
+        assert_eq!(now.results[0].memory.id, postgres.id);
+        let before = storage
+            .search_memories(as_of(Some(date("2019-01-01T00:00:00Z"))))
+            .await
+            .expect("search");
+        assert!(before.results.is_empty());
+
+        let reversed = storage
+            .create_memory(CreateMemoryRequest {
+                valid_from: Some(date("2024-01-01T00:00:00Z")),
+                valid_to: Some(date("2023-01-01T00:00:00Z")),
+                ..request("The office was in Paris", vec![0.0, 1.0])
+            })
+            .await;
+        assert!(matches!(reversed, Err(MemoryError::InvalidRequest { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_content_rejected() {
+        let storage = storage().await;
+        storage
+            .create_memory(request("same content", vec![1.0]))
+            .await
+            .expect("create");
+        let result = storage
+            .create_memory(request("same content", vec![1.0]))
+            .await;
+        assert!(matches!(result, Err(MemoryError::DuplicateContent { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_are_isolated() {
+        let storage = storage().await;
+        let team_a = storage.with_namespace("acme/api");
+        assert_eq!(team_a.namespace(), "acme/api");
+
+        let memory = team_a
+            .create_memory(request("deploy on fridays", vec![1.0, 0.0]))
+            .await
+            .expect("create");
+        assert_eq!(memory.namespace, "acme/api");
+
+        // Same content is not a duplicate in another namespace
+        storage
+            .create_memory(request("deploy on fridays", vec![1.0, 0.0]))
+            .await
+            .expect("create in default namespace");
+
+        assert!(matches!(
+            storage.get_memory(memory.id).await,
+            Err(MemoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            storage.delete_memory(memory.id).await,
+            Err(MemoryError::NotFound { .. })
+        ));
+
+        let response = team_a
+            .search_memories(SearchRequest {
+                query_embedding: Some(vec![1.0, 0.0]),
+                ..Default::default()
+            })
+            .await
+            .expect("search");
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].memory.id, memory.id);
+
+        let stats = team_a.get_statistics().await.expect("stats");
+        assert_eq!(stats.total_active, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_semantic_search_orders_by_similarity() {
+        let storage = storage().await;
+        let near = storage
+            .create_memory(request("near", vec![1.0, 0.1]))
+            .await
+            .expect("create");
+        storage
+            .create_memory(request("far", vec![0.0, 1.0]))
+            .await
+            .expect("create");
+
+        let response = storage
+            .search_memories(SearchRequest {
+                query_embedding: Some(vec![1.0, 0.0]),
+                similarity_threshold: Some(0.5),
+                ..Default::default()
+            })
+            .await
+            .expect("search");
+
+        assert_eq!(response.results.len(), 1);
+        assert_eq!(response.results[0].memory.id, near.id);
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_fuses_keyword_matches() {
+        let storage = storage().await;
+        let near = storage
+            .create_memory(request("connection pooling notes", vec![1.0, 0.1]))
+            .await
+            .expect("create");
+        // Exact error code that the vector search misses
+        let keyword = storage
+            .create_memory(request("fix for error E0502 in parser", vec![0.0, 1.0]))
+            .await
+            .expect("create");
+
+        let response = storage
+            .search_memories(SearchRequest {
+                query_text: Some("E0502".to_string()),
+                query_embedding: Some(vec![1.0, 0.0]),
+                search_type: Some(SearchType::Hybrid),
+                similarity_threshold: Some(0.5),
+                explain_score: Some(true),
+                rrf_k: Some(10.0),
+                entity: None,
+                include_superseded: None,
+                as_of: None,
+                ..Default::default()
+            })
+            .await
+            .expect("search");
+
+        let ids: Vec<Uuid> = response.results.iter().map(|r| r.memory.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&near.id) && ids.contains(&keyword.id));
+
+        let keyword_result = response
+            .results
+            .iter()
+            .find(|r| r.memory.id == keyword.id)
+            .expect("keyword match");
+        let fusion = keyword_result
+            .score_explanation
+            .as_ref()
+            .and_then(|e| e.rank_fusion.as_ref())
+            .expect("rank fusion explanation");
+        assert_eq!(fusion.k, 10.0);
+        assert_eq!(fusion.semantic_rank, None);
+        assert_eq!(fusion.fulltext_rank, Some(1));
+        assert!((keyword_result.combined_score - 1.0 / 11.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_and_freeze() {
+        let storage = storage().await;
+        let memory = storage
+            .create_memory(request("an old fact", vec![0.5, 0.5]))
+            .await
+            .expect("create");
+
+        let result = storage
+            .migrate_memory(memory.id, MemoryTier::Frozen, None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(MemoryError::InvalidTierTransition { .. })
+        ));
+
+        let cold = storage
+            .migrate_memory(memory.id, MemoryTier::Cold, Some("test".to_string()))
+            .await
+            .expect("migrate");
+        assert_eq!(cold.tier, MemoryTier::Cold);
+
+        let frozen = storage
+            .freeze_memory(memory.id, None)
+            .await
+            .expect("freeze");
+        assert_eq!(frozen.original_tier, MemoryTier::Cold);
+
+        let stats = storage.get_statistics().await.expect("stats");
+        assert_eq!(stats.total_active, Some(0));
+
+        let restored = storage
+            .unfreeze_memory(frozen.frozen_id, None)
+            .await
+            .expect("unfreeze");
+        let memory = storage.get_memory(restored.memory_id).await.expect("get");
+        assert_eq!(memory.content, "an old fact");
+        assert_eq!(memory.tier, MemoryTier::Warm);
+    }
+}
@@ -0,0 +1,555 @@
+//! Pluggable storage backends for memories
+//!
+//! `MemoryStorage` is everything the MCP server and the cognitive layer do
+//! with memories: create/get/update/delete, search, tiers and freezing, and
+//! the revisions, relations, entities, conflicts, PII vault, encryption keys
+//! and legal holds attached to them. PostgreSQL (`MemoryRepository`) remains
+//! the production backend; `SqliteMemoryStorage` is an embedded, single-file
+//! alternative for laptops and CI that needs no pgvector. `connect_storage`
+//! picks one from the database URL. Harvesting, insights, tier management and
+//! backups only run on PostgreSQL and reach it through `as_postgres`.
+
+use super::connection::create_pool;
+use super::contradictions::{
+    ConflictStatus, MemoryConflict, NewMemoryConflict, ResolveConflictRequest,
+};
+use super::entities::{Entity, EntityMention, EntityType};
+use super::error::{MemoryError, Result};
+use super::field_encryption::WrappedDataKey;
+use super::models::*;
+use super::pii_policy::VaultedPiiValue;
+use super::reflection_engine::RelationshipType;
+use super::relations::{
+    traverse_from, LinkMemoriesRequest, MemoryNeighbor, MemoryRelation, RelationFilter,
+    TraversalOptions,
+};
+use super::repository::{MemoryRepository, MemoryStatistics};
+use super::retention_job::LegalHold;
+use super::sqlite_storage::SqliteMemoryStorage;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Storage operations shared by all memory backends
+#[async_trait]
+pub trait MemoryStorage: Send + Sync {
+    /// Short identifier of the backend, e.g. `postgres` or `sqlite`
+    fn backend_name(&self) -> &'static str;
+
+    /// Namespace every operation of this handle is restricted to
+    fn namespace(&self) -> &str;
+
+    /// A handle on the same backend restricted to `namespace`
+    fn with_namespace(&self, namespace: &str) -> Arc<dyn MemoryStorage>;
+
+    /// The PostgreSQL repository behind this handle, if that is the backend
+    fn as_postgres(&self) -> Option<&MemoryRepository> {
+        None
+    }
+
+    async fn create_memory(&self, request: CreateMemoryRequest) -> Result<Memory>;
+
+    /// Fetch an active memory, recording the access
+    async fn get_memory(&self, id: Uuid) -> Result<Memory>;
+
+    async fn update_memory(&self, id: Uuid, request: UpdateMemoryRequest) -> Result<Memory>;
+
+    /// Soft delete an active memory
+    async fn delete_memory(&self, id: Uuid) -> Result<()>;
+
+    async fn search_memories(&self, request: SearchRequest) -> Result<SearchResponse>;
+
+    async fn get_memories_by_tier(
+        &self,
+        tier: MemoryTier,
+        limit: Option<i64>,
+    ) -> Result<Vec<Memory>>;
+
+    async fn migrate_memory(
+        &self,
+        id: Uuid,
+        to_tier: MemoryTier,
+        reason: Option<String>,
+    ) -> Result<Memory>;
+
+    async fn freeze_memory(
+        &self,
+        memory_id: Uuid,
+        reason: Option<String>,
+    ) -> Result<FreezeMemoryResponse>;
+
+    async fn unfreeze_memory(
+        &self,
+        frozen_id: Uuid,
+        target_tier: Option<MemoryTier>,
+    ) -> Result<UnfreezeMemoryResponse>;
+
+    async fn get_statistics(&self) -> Result<MemoryStatistics>;
+
+    async fn health_check(&self) -> Result<bool>;
+
+    /// Revisions of a memory, newest first
+    async fn memory_history(&self, id: Uuid, limit: Option<i64>) -> Result<Vec<MemoryRevision>>;
+
+    async fn get_memory_revision(&self, id: Uuid, revision: i32) -> Result<MemoryRevision>;
+
+    /// Restore the content, metadata and importance of `revision`, which is
+    /// recorded as a new revision. `embedding` must be the embedding of the
+    /// revision's content whenever that differs from the current content.
+    async fn revert_memory(
+        &self,
+        id: Uuid,
+        revision: i32,
+        embedding: Option<Vec<f32>>,
+        actor: Option<String>,
+    ) -> Result<Memory> {
+        let target = self.get_memory_revision(id, revision).await?;
+        self.update_memory(
+            id,
+            UpdateMemoryRequest {
+                content: Some(target.content),
+                embedding,
+                tier: None,
+                importance_score: Some(target.importance_score),
+                metadata: Some(target.metadata),
+                expires_at: None,
+                valid_from: None,
+                valid_to: None,
+                actor,
+                reason: Some(format!("Revert to revision {revision}")),
+                content_hash: None,
+            },
+        )
+        .await
+    }
+
+    /// Active memories among `ids`, without recording an access
+    async fn get_memories_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Memory>>;
+
+    /// Create or update a relation between two active memories
+    async fn link_memories(&self, request: LinkMemoriesRequest) -> Result<MemoryRelation>;
+
+    /// Remove relations from `source_id` to `target_id`, only of
+    /// `relation_type` if given. Returns the number removed.
+    async fn unlink_memories(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+        relation_type: Option<RelationshipType>,
+    ) -> Result<u64>;
+
+    /// Relations between active memories that touch any of `ids` in the
+    /// direction of `filter`
+    async fn get_relations(
+        &self,
+        ids: &[Uuid],
+        filter: &RelationFilter,
+    ) -> Result<Vec<MemoryRelation>>;
+
+    /// Resolve `mentions` to canonical entities, creating unknown ones, and
+    /// link the memory to them
+    async fn link_memory_entities(
+        &self,
+        memory_id: Uuid,
+        mentions: Vec<EntityMention>,
+    ) -> Result<Vec<Entity>>;
+
+    /// Entity known by `name` or one of its aliases
+    async fn find_entity(&self, name: &str) -> Result<Option<Entity>>;
+
+    /// Most mentioned entities, only of `entity_type` if given
+    async fn list_entities(
+        &self,
+        entity_type: Option<EntityType>,
+        limit: i64,
+    ) -> Result<Vec<Entity>>;
+
+    /// Entities the memory is linked to
+    async fn get_memory_entities(&self, memory_id: Uuid) -> Result<Vec<Entity>>;
+
+    /// Record a contradiction between two memories
+    async fn record_conflict(&self, conflict: NewMemoryConflict) -> Result<MemoryConflict>;
+
+    /// Conflicts newest first, only with `status` or involving `memory_id`
+    /// if given
+    async fn list_conflicts(
+        &self,
+        status: Option<ConflictStatus>,
+        memory_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<MemoryConflict>>;
+
+    /// Resolve a conflict, leaving the superseded memory out of searches
+    async fn resolve_conflict(&self, request: ResolveConflictRequest) -> Result<MemoryConflict>;
+
+    /// Wrapped PII vault data key of `subject_id`; `new_key` is stored and
+    /// returned when the subject has none yet
+    async fn pii_subject_key(&self, subject_id: &str, new_key: &[u8]) -> Result<Vec<u8>>;
+
+    /// Keep the encrypted value of a match of the named PII pattern in the
+    /// vault under `token`
+    async fn vault_pii_value(
+        &self,
+        token: &str,
+        subject_id: &str,
+        kind: &str,
+        ciphertext: &[u8],
+    ) -> Result<()>;
+
+    /// Encrypted values kept under `token` whose subjects still have a key
+    async fn vaulted_pii_values(&self, token: &str) -> Result<Vec<VaultedPiiValue>>;
+
+    /// Field encryption data keys, oldest first
+    async fn encryption_keys(&self) -> Result<Vec<WrappedDataKey>>;
+
+    /// Store a new field encryption data key; a key with the same id that
+    /// is already stored is kept
+    async fn add_encryption_key(&self, key: &WrappedDataKey) -> Result<()>;
+
+    /// Replace the wrapping of the given data keys, all or none, after the
+    /// master key changed to `master_key_id`
+    async fn rewrap_encryption_keys(
+        &self,
+        master_key_id: &str,
+        wrapped_keys: &[(String, Vec<u8>)],
+    ) -> Result<()>;
+
+    /// Namespaces that hold active memories, across the whole backend
+    async fn list_namespaces(&self) -> Result<Vec<String>>;
+
+    /// Active memories created before `before`, ordered by id and starting
+    /// after `after`, for paging through the namespace
+    async fn memories_created_before(
+        &self,
+        before: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Memory>>;
+
+    /// Delete every revision of a memory but the latest, so its earlier
+    /// content cannot be recovered; returns the number deleted
+    async fn purge_memory_history(&self, id: Uuid) -> Result<u64>;
+
+    /// Remove every entity link of a memory, lowering the mention counts of
+    /// its entities; returns the number of links removed
+    async fn unlink_memory_entities(&self, memory_id: Uuid) -> Result<u64>;
+
+    /// Delete the vaulted values `subject_id` wrote under `tokens`, so they
+    /// can no longer be revealed; returns the number deleted
+    async fn forget_pii_values(&self, subject_id: &str, tokens: &[String]) -> Result<u64>;
+
+    /// Place a memory of the namespace under legal hold, replacing the
+    /// reason of an existing hold
+    async fn place_legal_hold(
+        &self,
+        id: Uuid,
+        reason: &str,
+        placed_by: Option<&str>,
+    ) -> Result<LegalHold>;
+
+    /// Release the legal hold on a memory; false if it had none
+    async fn release_legal_hold(&self, id: Uuid) -> Result<bool>;
+
+    /// Legal holds of the namespace, newest first
+    async fn legal_holds(&self) -> Result<Vec<LegalHold>>;
+
+    /// Memories one hop away from `id`
+    async fn get_neighbors(
+        &self,
+        id: Uuid,
+        filter: RelationFilter,
+        limit: usize,
+    ) -> Result<Vec<MemoryNeighbor>> {
+        let options = TraversalOptions {
+            filter,
+            max_depth: 1,
+            max_nodes: limit,
+        };
+        traverse_from(self, &[id], &options).await
+    }
+
+    /// Memories up to `options.max_depth` hops away from `id`
+    async fn traverse_relations(
+        &self,
+        id: Uuid,
+        options: &TraversalOptions,
+    ) -> Result<Vec<MemoryNeighbor>> {
+        traverse_from(self, &[id], options).await
+    }
+}
+
+#[async_trait]
+impl MemoryStorage for MemoryRepository {
+    fn backend_name(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn namespace(&self) -> &str {
+        MemoryRepository::namespace(self)
+    }
+
+    fn with_namespace(&self, namespace: &str) -> Arc<dyn MemoryStorage> {
+        Arc::new(MemoryRepository::with_namespace(self, namespace))
+    }
+
+    fn as_postgres(&self) -> Option<&MemoryRepository> {
+        Some(self)
+    }
+
+    async fn create_memory(&self, request: CreateMemoryRequest) -> Result<Memory> {
+        MemoryRepository::create_memory(self, request).await
+    }
+
+    async fn get_memory(&self, id: Uuid) -> Result<Memory> {
+        MemoryRepository::get_memory(self, id).await
+    }
+
+    async fn update_memory(&self, id: Uuid, request: UpdateMemoryRequest) -> Result<Memory> {
+        MemoryRepository::update_memory(self, id, request).await
+    }
+
+    async fn delete_memory(&self, id: Uuid) -> Result<()> {
+        MemoryRepository::delete_memory(self, id).await
+    }
+
+    async fn search_memories(&self, request: SearchRequest) -> Result<SearchResponse> {
+        MemoryRepository::search_memories(self, request).await
+    }
+
+    async fn get_memories_by_tier(
+        &self,
+        tier: MemoryTier,
+        limit: Option<i64>,
+    ) -> Result<Vec<Memory>> {
+        MemoryRepository::get_memories_by_tier(self, tier, limit).await
+    }
+
+    async fn migrate_memory(
+        &self,
+        id: Uuid,
+        to_tier: MemoryTier,
+        reason: Option<String>,
+    ) -> Result<Memory> {
+        MemoryRepository::migrate_memory(self, id, to_tier, reason).await
+    }
+
+    async fn freeze_memory(
+        &self,
+        memory_id: Uuid,
+        reason: Option<String>,
+    ) -> Result<FreezeMemoryResponse> {
+        MemoryRepository::freeze_memory(self, memory_id, reason).await
+    }
+
+    async fn unfreeze_memory(
+        &self,
+        frozen_id: Uuid,
+        target_tier: Option<MemoryTier>,
+    ) -> Result<UnfreezeMemoryResponse> {
+        MemoryRepository::unfreeze_memory(self, frozen_id, target_tier).await
+    }
+
+    async fn get_statistics(&self) -> Result<MemoryStatistics> {
+        MemoryRepository::get_statistics(self).await
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        MemoryRepository::health_check(self).await
+    }
+
+    async fn memory_history(&self, id: Uuid, limit: Option<i64>) -> Result<Vec<MemoryRevision>> {
+        MemoryRepository::get_memory_history(self, id, limit).await
+    }
+
+    async fn get_memory_revision(&self, id: Uuid, revision: i32) -> Result<MemoryRevision> {
+        MemoryRepository::get_memory_revision(self, id, revision).await
+    }
+
+    async fn get_memories_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Memory>> {
+        MemoryRepository::get_memories_by_ids(self, ids).await
+    }
+
+    async fn link_memories(&self, request: LinkMemoriesRequest) -> Result<MemoryRelation> {
+        MemoryRepository::link_memories(self, request).await
+    }
+
+    async fn unlink_memories(
+        &self,
+        source_id: Uuid,
+        target_id: Uuid,
+        relation_type: Option<RelationshipType>,
+    ) -> Result<u64> {
+        MemoryRepository::unlink_memories(self, source_id, target_id, relation_type).await
+    }
+
+    async fn get_relations(
+        &self,
+        ids: &[Uuid],
+        filter: &RelationFilter,
+    ) -> Result<Vec<MemoryRelation>> {
+        MemoryRepository::get_relations(self, ids, filter).await
+    }
+
+    async fn link_memory_entities(
+        &self,
+        memory_id: Uuid,
+        mentions: Vec<EntityMention>,
+    ) -> Result<Vec<Entity>> {
+        MemoryRepository::link_memory_entities(self, memory_id, mentions).await
+    }
+
+    async fn find_entity(&self, name: &str) -> Result<Option<Entity>> {
+        MemoryRepository::find_entity(self, name).await
+    }
+
+    async fn list_entities(
+        &self,
+        entity_type: Option<EntityType>,
+        limit: i64,
+    ) -> Result<Vec<Entity>> {
+        MemoryRepository::list_entities(self, entity_type, limit).await
+    }
+
+    async fn get_memory_entities(&self, memory_id: Uuid) -> Result<Vec<Entity>> {
+        MemoryRepository::get_memory_entities(self, memory_id).await
+    }
+
+    async fn record_conflict(&self, conflict: NewMemoryConflict) -> Result<MemoryConflict> {
+        MemoryRepository::record_conflict(self, conflict).await
+    }
+
+    async fn list_conflicts(
+        &self,
+        status: Option<ConflictStatus>,
+        memory_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<MemoryConflict>> {
+        MemoryRepository::list_conflicts(self, status, memory_id, limit).await
+    }
+
+    async fn resolve_conflict(&self, request: ResolveConflictRequest) -> Result<MemoryConflict> {
+        MemoryRepository::resolve_conflict(self, request).await
+    }
+
+    async fn pii_subject_key(&self, subject_id: &str, new_key: &[u8]) -> Result<Vec<u8>> {
+        MemoryRepository::pii_subject_key(self, subject_id, new_key).await
+    }
+
+    async fn vault_pii_value(
+        &self,
+        token: &str,
+        subject_id: &str,
+        kind: &str,
+        ciphertext: &[u8],
+    ) -> Result<()> {
+        MemoryRepository::vault_pii_value(self, token, subject_id, kind, ciphertext).await
+    }
+
+    async fn vaulted_pii_values(&self, token: &str) -> Result<Vec<VaultedPiiValue>> {
+        MemoryRepository::vaulted_pii_values(self, token).await
+    }
+
+    async fn encryption_keys(&self) -> Result<Vec<WrappedDataKey>> {
+        MemoryRepository::encryption_keys(self).await
+    }
+
+    async fn add_encryption_key(&self, key: &WrappedDataKey) -> Result<()> {
+        MemoryRepository::add_encryption_key(self, key).await
+    }
+
+    async fn rewrap_encryption_keys(
+        &self,
+        master_key_id: &str,
+        wrapped_keys: &[(String, Vec<u8>)],
+    ) -> Result<()> {
+        MemoryRepository::rewrap_encryption_keys(self, master_key_id, wrapped_keys).await
+    }
+
+    async fn list_namespaces(&self) -> Result<Vec<String>> {
+        MemoryRepository::list_namespaces(self).await
+    }
+
+    async fn memories_created_before(
+        &self,
+        before: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<Memory>> {
+        MemoryRepository::memories_created_before(self, before, after, limit).await
+    }
+
+    async fn purge_memory_history(&self, id: Uuid) -> Result<u64> {
+        MemoryRepository::purge_memory_history(self, id).await
+    }
+
+    async fn unlink_memory_entities(&self, memory_id: Uuid) -> Result<u64> {
+        MemoryRepository::unlink_memory_entities(self, memory_id).await
+    }
+
+    async fn forget_pii_values(&self, subject_id: &str, tokens: &[String]) -> Result<u64> {
+        MemoryRepository::forget_pii_values(self, subject_id, tokens).await
+    }
+
+    async fn place_legal_hold(
+        &self,
+        id: Uuid,
+        reason: &str,
+        placed_by: Option<&str>,
+    ) -> Result<LegalHold> {
+        MemoryRepository::place_legal_hold(self, id, reason, placed_by).await
+    }
+
+    async fn release_legal_hold(&self, id: Uuid) -> Result<bool> {
+        MemoryRepository::release_legal_hold(self, id).await
+    }
+
+    async fn legal_holds(&self) -> Result<Vec<LegalHold>> {
+        MemoryRepository::legal_holds(self).await
+    }
+}
+
+/// Open a storage backend based on the connection URL scheme.
+///
+/// `postgres://` / `postgresql://` URLs use `MemoryRepository`; `sqlite:` URLs
+/// (e.g. `sqlite://codex.db` or `sqlite::memory:`) use the embedded backend.
+pub async fn connect_storage(
+    database_url: &str,
+    max_connections: u32,
+) -> Result<Arc<dyn MemoryStorage>> {
+    if database_url.starts_with("sqlite:") {
+        let storage = SqliteMemoryStorage::connect(database_url).await?;
+        return Ok(Arc::new(storage));
+    }
+
+    if database_url.starts_with("postgres://") || database_url.starts_with("postgresql://") {
+        let pool = create_pool(database_url, max_connections)
+            .await
+            .map_err(|e| MemoryError::ConnectionPool(e.to_string()))?;
+        return Ok(Arc::new(MemoryRepository::new(pool)));
+    }
+
+    Err(MemoryError::Configuration(
+        "Unsupported storage URL: expected postgres:// or sqlite:".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_connect_storage_selects_sqlite_backend() {
+        let storage = connect_storage("sqlite::memory:", 1)
+            .await
+            .expect("in-memory sqlite should open");
+        assert_eq!(storage.backend_name(), "sqlite");
+        assert!(storage.health_check().await.expect("health check"));
+    }
+
+    #[tokio::test]
+    async fn test_connect_storage_rejects_unknown_scheme() {
+        let result = connect_storage("mysql://localhost/codex", 1).await;
+        assert!(matches!(result, Err(MemoryError::Configuration(_))));
+    }
+}
@@ -23,7 +23,10 @@ fn create_test_memory(
         id: Uuid::new_v4(),
         content: "Test memory content".to_string(),
         content_hash: "test_hash".to_string(),
+        namespace: "default".to_string(),
         embedding: None,
+        embedding_model: None,
+        embedding_dimension: None,
         tier,
         status: MemoryStatus::Active,
         importance_score,
@@ -34,6 +37,8 @@ fn create_test_memory(
         created_at: Utc::now() - Duration::days(1),
         updated_at: Utc::now() - Duration::hours(2),
         expires_at: None,
+        valid_from: None,
+        valid_to: None,
         consolidation_strength: 1.0,
         decay_rate,
         recall_probability: Some(0.8),
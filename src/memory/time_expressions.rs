@@ -0,0 +1,693 @@
+//! Natural-language time expressions
+//!
+//! Resolves phrases such as "yesterday afternoon", "between March and May"
+//! or "two sprints ago" to the date range temporal search filters on. All
+//! times are UTC. A phrase anchored on a named event, such as "since the
+//! v0.1.50 release", resolves through the configured event dates; other
+//! events are handed back for the caller to look up, e.g. in stored memories.
+
+use super::models::DateRange;
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, NaiveTime, Utc, Weekday};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Settings for relative expressions
+#[derive(Debug, Clone)]
+pub struct TimeExpressionConfig {
+    /// Length of a sprint in days
+    pub sprint_length_days: i64,
+    /// Start of any sprint, to align sprints to the team's calendar; without
+    /// it sprints are counted back from now
+    pub sprint_start: Option<DateTime<Utc>>,
+    /// Dates of named events such as releases, keyed by lowercase name
+    pub events: HashMap<String, DateTime<Utc>>,
+}
+
+impl Default for TimeExpressionConfig {
+    fn default() -> Self {
+        Self {
+            sprint_length_days: 14,
+            sprint_start: None,
+            events: HashMap::new(),
+        }
+    }
+}
+
+impl TimeExpressionConfig {
+    /// Read `CODEX_SPRINT_LENGTH_DAYS`, `CODEX_SPRINT_START` and
+    /// `CODEX_TIME_EVENTS`, a JSON object of event names to dates, e.g.
+    /// `{"v0.1.50": "2025-08-01"}`. Invalid values are logged and ignored.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(days) = std::env::var("CODEX_SPRINT_LENGTH_DAYS") {
+            match days.parse::<i64>() {
+                Ok(days) if days > 0 => config.sprint_length_days = days,
+                _ => warn!("Invalid CODEX_SPRINT_LENGTH_DAYS: {}", days),
+            }
+        }
+        if let Ok(start) = std::env::var("CODEX_SPRINT_START") {
+            match parse_date(&start) {
+                Some(start) => config.sprint_start = Some(midnight(start)),
+                None => warn!("Invalid CODEX_SPRINT_START: {}", start),
+            }
+        }
+        if let Ok(events) = std::env::var("CODEX_TIME_EVENTS") {
+            match serde_json::from_str::<HashMap<String, String>>(&events) {
+                Ok(events) => {
+                    for (name, date) in events {
+                        match parse_timestamp(&date) {
+                            Some(at) => {
+                                config.events.insert(name.to_lowercase(), at);
+                            }
+                            None => warn!("Invalid date for event '{}': {}", name, date),
+                        }
+                    }
+                }
+                Err(e) => warn!("Invalid CODEX_TIME_EVENTS: {}", e),
+            }
+        }
+
+        config
+    }
+}
+
+/// A range of time; an open side is unbounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeRange {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    pub fn all() -> Self {
+        Self {
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn between(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+
+    /// Filter for `SearchRequest::date_range`; `None` when unbounded
+    pub fn to_date_range(self) -> Option<DateRange> {
+        (self.start.is_some() || self.end.is_some()).then_some(DateRange {
+            start: self.start,
+            end: self.end,
+        })
+    }
+
+    /// Length of the range when both sides are bounded
+    pub fn span(&self) -> Option<Duration> {
+        Some(self.end? - self.start?)
+    }
+
+    /// Human-readable form echoed back to callers
+    pub fn describe(&self) -> String {
+        let format = |at: DateTime<Utc>| at.format("%Y-%m-%d %H:%M UTC").to_string();
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => format!("{} – {}", format(start), format(end)),
+            (Some(start), None) => format!("since {}", format(start)),
+            (None, Some(end)) => format!("before {}", format(end)),
+            (None, None) => "all time".to_string(),
+        }
+    }
+}
+
+/// How a range relates to a named event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventRelation {
+    Since,
+    Before,
+}
+
+impl EventRelation {
+    /// The range on this side of an event that happened `at`
+    pub fn around(self, at: DateTime<Utc>) -> TimeRange {
+        match self {
+            EventRelation::Since => TimeRange {
+                start: Some(at),
+                end: None,
+            },
+            EventRelation::Before => TimeRange {
+                start: None,
+                end: Some(at),
+            },
+        }
+    }
+}
+
+/// Result of parsing an expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeExpression {
+    Range(TimeRange),
+    /// Relative to an event without a configured date
+    Event {
+        name: String,
+        relation: EventRelation,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+    Sprint,
+}
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+];
+
+const NUMBERS: [&str; 12] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve",
+];
+
+/// Counts above this are not a time anyone means and would overflow the
+/// calendar arithmetic
+const MAX_COUNT: u32 = 10_000;
+
+/// Parses time expressions relative to the current time
+#[derive(Debug, Clone, Default)]
+pub struct TimeExpressionParser {
+    config: TimeExpressionConfig,
+}
+
+impl TimeExpressionParser {
+    pub fn new(config: TimeExpressionConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn parse(&self, text: &str) -> Result<TimeExpression, String> {
+        self.parse_at(text, Utc::now())
+    }
+
+    /// Parse `text` as if it were `now`
+    pub fn parse_at(&self, text: &str, now: DateTime<Utc>) -> Result<TimeExpression, String> {
+        let text = normalize(text);
+        if text.is_empty() {
+            return Err("Time expression is empty".to_string());
+        }
+        let unknown = || format!("Unrecognized time expression: '{text}'");
+
+        if let Some(rest) = text.strip_prefix("between ") {
+            let (from, to) = rest.split_once(" and ").ok_or_else(unknown)?;
+            return self.span(from, to, now).ok_or_else(unknown);
+        }
+        if let Some(rest) = text.strip_prefix("from ") {
+            let (from, to) = rest
+                .split_once(" to ")
+                .or_else(|| rest.split_once(" until "))
+                .ok_or_else(unknown)?;
+            return self.span(from, to, now).ok_or_else(unknown);
+        }
+
+        let anchored = [
+            ("since ", EventRelation::Since),
+            ("after ", EventRelation::Since),
+            ("before ", EventRelation::Before),
+            ("until ", EventRelation::Before),
+        ];
+        for (prefix, relation) in anchored {
+            let Some(rest) = text.strip_prefix(prefix) else {
+                continue;
+            };
+            let at = match self.range(rest, now) {
+                // "since March" starts with March, "after March" once it ends
+                Some(range) if prefix == "after " || prefix == "until " => range.end,
+                Some(range) => range.start,
+                None => match self.config.events.get(&event_name(rest)) {
+                    Some(at) => Some(*at),
+                    None => {
+                        return Ok(TimeExpression::Event {
+                            name: event_name(rest),
+                            relation,
+                        })
+                    }
+                },
+            };
+            return at
+                .map(|at| TimeExpression::Range(relation.around(at)))
+                .ok_or_else(unknown);
+        }
+
+        self.range(&text, now)
+            .map(TimeExpression::Range)
+            .ok_or_else(unknown)
+    }
+
+    /// "between X and Y": from the start of X to the end of Y
+    fn span(&self, from: &str, to: &str, now: DateTime<Utc>) -> Option<TimeExpression> {
+        let from = self.range(from, now)?;
+        let mut to = self.range(to, now)?;
+        // "between November and February" crosses a year boundary
+        if let (Some(start), Some(end)) = (from.start, to.end) {
+            if end <= start && is_month_name(to) {
+                to = TimeRange {
+                    start: to.start.map(|s| s + Months::new(12)),
+                    end: Some(end + Months::new(12)),
+                };
+            }
+        }
+        Some(TimeExpression::Range(TimeRange {
+            start: from.start,
+            end: to.end,
+        }))
+    }
+
+    /// Resolve an expression naming a stretch of time
+    fn range(&self, text: &str, now: DateTime<Utc>) -> Option<TimeRange> {
+        let text = text.strip_prefix("the ").unwrap_or(text);
+        let text = text
+            .strip_prefix("in ")
+            .or_else(|| text.strip_prefix("on "))
+            .or_else(|| text.strip_prefix("during "))
+            .unwrap_or(text);
+        let today = now.date_naive();
+
+        match text {
+            "all" | "all time" | "ever" => return Some(TimeRange::all()),
+            "now" => return Some(TimeRange::between(now, now)),
+            "today" => return Some(TimeRange::between(midnight(today), now)),
+            "yesterday" => return Some(day(today - Duration::days(1))),
+            "last night" => return part_of_day(today - Duration::days(1), "night"),
+            _ => {}
+        }
+
+        if let Some((day_word, part)) = text.split_once(' ') {
+            let date = match day_word {
+                "today" | "this" => Some(today),
+                "yesterday" => Some(today - Duration::days(1)),
+                _ => None,
+            };
+            if let Some(range) = date.and_then(|date| part_of_day(date, part)) {
+                return Some(range);
+            }
+        }
+
+        if let Some(at) = parse_timestamp(text) {
+            return Some(match parse_date(text) {
+                Some(date) => day(date),
+                None => TimeRange::between(at, at),
+            });
+        }
+        if let Some(range) = self.month_or_year(text, today) {
+            return Some(range);
+        }
+        if let Some(range) = weekday(text, today) {
+            return Some(range);
+        }
+
+        let words: Vec<&str> = text.split(' ').collect();
+        match words.as_slice() {
+            ["this" | "current", unit] => self.this(parse_unit(unit)?, now),
+            ["last" | "past" | "previous", unit] => self.last(1, parse_unit(unit)?, now),
+            ["last" | "past", count, unit] => {
+                self.last(parse_count(count)?, parse_unit(unit)?, now)
+            }
+            [count, unit, "ago"] => self.ago(parse_count(count)?, parse_unit(unit)?, now),
+            [unit, "ago"] => self.ago(1, parse_unit(unit)?, now),
+            _ => None,
+        }
+    }
+
+    /// "March", "March 2024", "2024-03" or "2024"; a month without a year is
+    /// its latest occurrence that has started
+    fn month_or_year(&self, text: &str, today: NaiveDate) -> Option<TimeRange> {
+        if let Ok(year) = text.parse::<i32>() {
+            if (1900..=9999).contains(&year) {
+                return Some(TimeRange::between(
+                    midnight(NaiveDate::from_ymd_opt(year, 1, 1)?),
+                    midnight(NaiveDate::from_ymd_opt(year + 1, 1, 1)?),
+                ));
+            }
+        }
+        if let Some((year, month)) = text.split_once('-') {
+            if let (Ok(year), Ok(month)) = (year.parse::<i32>(), month.parse::<u32>()) {
+                return month_range(year, month);
+            }
+        }
+
+        let (name, year) = match text.split_once(' ') {
+            Some((name, year)) => (name, Some(year.parse::<i32>().ok()?)),
+            None => (text, None),
+        };
+        let month = MONTHS
+            .iter()
+            .position(|m| *m == name || (name.len() >= 3 && m.starts_with(name)))?
+            as u32
+            + 1;
+        let year = year.unwrap_or(if month <= today.month() {
+            today.year()
+        } else {
+            today.year() - 1
+        });
+        month_range(year, month)
+    }
+
+    /// From the start of the current unit until now
+    fn this(&self, unit: Unit, now: DateTime<Utc>) -> Option<TimeRange> {
+        let today = now.date_naive();
+        let start = match unit {
+            Unit::Minute | Unit::Hour => return self.last(1, unit, now),
+            Unit::Day => midnight(today),
+            Unit::Week => {
+                midnight(today - Duration::days(today.weekday().num_days_from_monday() as i64))
+            }
+            Unit::Month => month_range(today.year(), today.month())?.start?,
+            Unit::Year => midnight(NaiveDate::from_ymd_opt(today.year(), 1, 1)?),
+            Unit::Sprint => self.sprint_start(now),
+        };
+        Some(TimeRange::between(start, now))
+    }
+
+    /// The last `count` units up to now
+    fn last(&self, count: u32, unit: Unit, now: DateTime<Utc>) -> Option<TimeRange> {
+        Some(TimeRange::between(self.back(now, count, unit)?, now))
+    }
+
+    /// The unit `count` units before the current one; days, months, years
+    /// and configured sprints follow the calendar
+    fn ago(&self, count: u32, unit: Unit, now: DateTime<Utc>) -> Option<TimeRange> {
+        let base = match unit {
+            Unit::Minute | Unit::Hour | Unit::Week => now,
+            Unit::Day => midnight(now.date_naive()) + Duration::days(1),
+            Unit::Month => month_range(now.year(), now.month())?.start? + Months::new(1),
+            Unit::Year => midnight(NaiveDate::from_ymd_opt(now.year() + 1, 1, 1)?),
+            Unit::Sprint if self.config.sprint_start.is_some() => {
+                self.sprint_start(now) + self.sprint_length()
+            }
+            Unit::Sprint => now,
+        };
+        let end = self.back(base, count, unit)?;
+        let start = self.back(end, 1, unit)?;
+        Some(TimeRange::between(start, end))
+    }
+
+    /// `None` when the result falls outside the representable range
+    fn back(&self, from: DateTime<Utc>, count: u32, unit: Unit) -> Option<DateTime<Utc>> {
+        let count_i64 = count as i64;
+        let span = match unit {
+            Unit::Minute => Duration::try_minutes(count_i64)?,
+            Unit::Hour => Duration::try_hours(count_i64)?,
+            Unit::Day => Duration::try_days(count_i64)?,
+            Unit::Week => Duration::try_weeks(count_i64)?,
+            Unit::Month => return from.checked_sub_months(Months::new(count)),
+            Unit::Year => return from.checked_sub_months(Months::new(count.checked_mul(12)?)),
+            Unit::Sprint => self
+                .sprint_length()
+                .checked_mul(i32::try_from(count).ok()?)?,
+        };
+        from.checked_sub_signed(span)
+    }
+
+    fn sprint_length(&self) -> Duration {
+        Duration::days(self.config.sprint_length_days.max(1))
+    }
+
+    /// Start of the sprint containing `now`
+    fn sprint_start(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let length = self.sprint_length();
+        match self.config.sprint_start {
+            Some(anchor) => {
+                let elapsed = (now - anchor).num_seconds();
+                let sprints = elapsed.div_euclid(length.num_seconds());
+                anchor + Duration::seconds(sprints * length.num_seconds())
+            }
+            None => now - length,
+        }
+    }
+}
+
+/// Lowercase with single spaces; `last_week` reads as `last week`
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .replace('_', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['.', '?', '!'])
+        .replace("a couple of ", "2 ")
+        .replace("a few ", "3 ")
+}
+
+/// "the v0.1.50 release" → "v0.1.50"
+fn event_name(text: &str) -> String {
+    let name = text.strip_prefix("the ").unwrap_or(text);
+    let name = name.strip_prefix("release ").unwrap_or(name);
+    name.strip_suffix(" release").unwrap_or(name).to_string()
+}
+
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+}
+
+/// RFC 3339 timestamp or `YYYY-MM-DD` (midnight)
+fn parse_timestamp(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&text.to_uppercase())
+        .map(|at| at.with_timezone(&Utc))
+        .ok()
+        .or_else(|| parse_date(text).map(midnight))
+}
+
+fn parse_unit(word: &str) -> Option<Unit> {
+    match word.trim_end_matches('s') {
+        "minute" | "min" => Some(Unit::Minute),
+        "hour" | "hr" => Some(Unit::Hour),
+        "day" => Some(Unit::Day),
+        "week" => Some(Unit::Week),
+        "month" => Some(Unit::Month),
+        "year" => Some(Unit::Year),
+        "sprint" => Some(Unit::Sprint),
+        _ => None,
+    }
+}
+
+fn parse_count(word: &str) -> Option<u32> {
+    match word {
+        "a" | "an" => Some(1),
+        "couple" => Some(2),
+        "few" => Some(3),
+        _ => word.parse().ok().or_else(|| {
+            NUMBERS
+                .iter()
+                .position(|n| *n == word)
+                .map(|i| i as u32 + 1)
+        }),
+    }
+    .filter(|count| (1..=MAX_COUNT).contains(count))
+}
+
+fn is_month_name(range: TimeRange) -> bool {
+    range
+        .span()
+        .is_some_and(|span| (28..=31).contains(&span.num_days()))
+}
+
+fn day(date: NaiveDate) -> TimeRange {
+    TimeRange::between(midnight(date), midnight(date + Duration::days(1)))
+}
+
+fn month_range(year: i32, month: u32) -> Option<TimeRange> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+    Some(TimeRange::between(
+        midnight(start),
+        midnight(start + Months::new(1)),
+    ))
+}
+
+/// Morning 06–12, afternoon 12–18, evening 18–24 and night 22–06
+fn part_of_day(date: NaiveDate, part: &str) -> Option<TimeRange> {
+    let (from, hours) = match part {
+        "morning" => (6, 6),
+        "afternoon" => (12, 6),
+        "evening" => (18, 6),
+        "night" => (22, 8),
+        _ => return None,
+    };
+    let start = midnight(date) + Duration::hours(from);
+    Some(TimeRange::between(start, start + Duration::hours(hours)))
+}
+
+/// "monday" is the latest Monday, today included; "last monday" the one
+/// before today
+fn weekday(text: &str, today: NaiveDate) -> Option<TimeRange> {
+    let (name, skip_today) = match text.strip_prefix("last ") {
+        Some(name) => (name, true),
+        None => (text, false),
+    };
+    let target = WEEKDAYS.iter().find(|(n, _)| *n == name)?.1;
+    let mut days_back =
+        (7 + today.weekday().num_days_from_monday() - target.num_days_from_monday()) % 7;
+    if days_back == 0 && skip_today {
+        days_back = 7;
+    }
+    Some(day(today - Duration::days(days_back as i64)))
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        parse_timestamp(text).expect("timestamp")
+    }
+
+    fn range(parser: &TimeExpressionParser, text: &str) -> TimeRange {
+        // A Wednesday afternoon
+        match parser.parse_at(text, at("2025-09-17T15:30:00Z")) {
+            Ok(TimeExpression::Range(range)) => range,
+            other => panic!("'{text}' parsed as {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_calendar_expressions() {
+        let parser = TimeExpressionParser::default();
+
+        let afternoon = range(&parser, "yesterday afternoon");
+        assert_eq!(
+            afternoon,
+            TimeRange::between(at("2025-09-16T12:00:00Z"), at("2025-09-16T18:00:00Z"))
+        );
+        let spring = range(&parser, "between March and May");
+        assert_eq!(
+            spring,
+            TimeRange::between(at("2025-03-01"), at("2025-06-01"))
+        );
+        let winter = range(&parser, "between November and February");
+        assert_eq!(
+            winter,
+            TimeRange::between(at("2024-11-01"), at("2025-03-01"))
+        );
+        assert_eq!(
+            range(&parser, "in December"),
+            TimeRange::between(at("2024-12-01"), at("2025-01-01"))
+        );
+        assert_eq!(
+            range(&parser, "last monday"),
+            TimeRange::between(at("2025-09-15"), at("2025-09-16"))
+        );
+        assert_eq!(
+            range(&parser, "two days ago"),
+            TimeRange::between(at("2025-09-15"), at("2025-09-16"))
+        );
+        assert_eq!(
+            range(&parser, "since 2025-09-01").start,
+            Some(at("2025-09-01"))
+        );
+    }
+
+    #[test]
+    fn test_relative_expressions() {
+        let parser = TimeExpressionParser::default();
+        let now = at("2025-09-17T15:30:00Z");
+
+        assert_eq!(
+            range(&parser, "last_week"),
+            TimeRange::between(now - Duration::weeks(1), now)
+        );
+        assert_eq!(
+            range(&parser, "past 3 hours"),
+            TimeRange::between(now - Duration::hours(3), now)
+        );
+        assert_eq!(range(&parser, "all"), TimeRange::all());
+        assert!(range(&parser, "all").to_date_range().is_none());
+        assert!(parser.parse_at("next fortnight-ish", now).is_err());
+
+        assert_eq!(
+            range(&parser, "last 10000 years").start,
+            now.checked_sub_months(Months::new(120_000))
+        );
+        assert!(parser.parse_at("100000000 days ago", now).is_err());
+        assert!(parser.parse_at("last 4294967295 years", now).is_err());
+        assert!(parser.parse_at("10001 sprints ago", now).is_err());
+    }
+
+    #[test]
+    fn test_sprints() {
+        let rolling = TimeExpressionParser::new(TimeExpressionConfig {
+            sprint_length_days: 7,
+            ..Default::default()
+        });
+        let now = at("2025-09-17T15:30:00Z");
+        assert_eq!(
+            range(&rolling, "two sprints ago"),
+            TimeRange::between(now - Duration::weeks(3), now - Duration::weeks(2))
+        );
+
+        // Sprints start every other Monday from 2025-09-01
+        let aligned = TimeExpressionParser::new(TimeExpressionConfig {
+            sprint_length_days: 14,
+            sprint_start: Some(at("2025-09-01")),
+            ..Default::default()
+        });
+        assert_eq!(
+            range(&aligned, "this sprint"),
+            TimeRange::between(at("2025-09-15"), now)
+        );
+        assert_eq!(
+            range(&aligned, "two sprints ago"),
+            TimeRange::between(at("2025-08-18"), at("2025-09-01"))
+        );
+    }
+
+    #[test]
+    fn test_events() {
+        let mut config = TimeExpressionConfig::default();
+        config
+            .events
+            .insert("v0.1.49".to_string(), at("2025-07-01"));
+        let parser = TimeExpressionParser::new(config);
+
+        assert_eq!(
+            range(&parser, "since the v0.1.49 release"),
+            TimeRange {
+                start: Some(at("2025-07-01")),
+                end: None
+            }
+        );
+        assert_eq!(
+            parser.parse_at("since the v0.1.50 release", Utc::now()),
+            Ok(TimeExpression::Event {
+                name: "v0.1.50".to_string(),
+                relation: EventRelation::Since
+            })
+        );
+    }
+}
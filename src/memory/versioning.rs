@@ -0,0 +1,302 @@
+//! Memory revision diffs
+//!
+//! Every update that changes a memory's content, metadata or importance is
+//! stored as an immutable `MemoryRevision`. This module compares two
+//! revisions: content is diffed word by word, metadata by top-level key.
+
+use super::models::{Memory, MemoryRevision, MemoryTier};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+/// Above this many word pairs the changed middle of two texts is reported
+/// as a single replacement instead of running the quadratic LCS
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Whether an update changed anything recorded in a revision; tier-only
+/// changes are tracked by the migration history instead
+pub fn is_revised(previous: &Memory, updated: &Memory) -> bool {
+    previous.content_hash != updated.content_hash
+        || previous.metadata != updated.metadata
+        || previous.importance_score != updated.importance_score
+}
+
+/// A run of words in a content diff
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "text", rename_all = "snake_case")]
+pub enum TextChange {
+    Unchanged(String),
+    Removed(String),
+    Added(String),
+}
+
+/// A top-level metadata key whose value differs between two revisions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataChange {
+    pub key: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Differences between two revisions of the same memory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionDiff {
+    pub memory_id: Uuid,
+    pub from_revision: i32,
+    pub to_revision: i32,
+    /// Empty when the content is identical
+    pub content: Vec<TextChange>,
+    pub metadata: Vec<MetadataChange>,
+    pub importance_score: Option<(f64, f64)>,
+    pub tier: Option<(MemoryTier, MemoryTier)>,
+}
+
+impl RevisionDiff {
+    pub fn between(from: &MemoryRevision, to: &MemoryRevision) -> Self {
+        let content = if from.content_hash == to.content_hash {
+            Vec::new()
+        } else {
+            diff_words(&from.content, &to.content)
+        };
+
+        Self {
+            memory_id: to.memory_id,
+            from_revision: from.revision,
+            to_revision: to.revision,
+            content,
+            metadata: diff_metadata(&from.metadata, &to.metadata),
+            importance_score: (from.importance_score != to.importance_score)
+                .then_some((from.importance_score, to.importance_score)),
+            tier: (from.tier != to.tier).then_some((from.tier, to.tier)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty()
+            && self.metadata.is_empty()
+            && self.importance_score.is_none()
+            && self.tier.is_none()
+    }
+
+    /// Content diff with removals as `[-...-]` and additions as `{+...+}`
+    pub fn render_content(&self) -> String {
+        self.content
+            .iter()
+            .map(|change| match change {
+                TextChange::Unchanged(text) => text.clone(),
+                TextChange::Removed(text) => format!("[-{text}-]"),
+                TextChange::Added(text) => format!("{{+{text}+}}"),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Word-level diff of `before` and `after`, merging consecutive words with
+/// the same operation into one change
+pub fn diff_words(before: &str, after: &str) -> Vec<TextChange> {
+    let old: Vec<&str> = before.split_whitespace().collect();
+    let new: Vec<&str> = after.split_whitespace().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|w| (Op::Unchanged, *w)).collect();
+    if old_middle.len() * new_middle.len() > MAX_DIFF_CELLS {
+        ops.extend(old_middle.iter().map(|w| (Op::Removed, *w)));
+        ops.extend(new_middle.iter().map(|w| (Op::Added, *w)));
+    } else {
+        ops.extend(lcs_ops(old_middle, new_middle));
+    }
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|w| (Op::Unchanged, *w)),
+    );
+
+    let mut changes: Vec<TextChange> = Vec::new();
+    let mut current: Option<(Op, Vec<&str>)> = None;
+    for (op, word) in ops {
+        match &mut current {
+            Some((current_op, words)) if *current_op == op => words.push(word),
+            _ => {
+                if let Some((op, words)) = current.take() {
+                    changes.push(op.change(words.join(" ")));
+                }
+                current = Some((op, vec![word]));
+            }
+        }
+    }
+    if let Some((op, words)) = current {
+        changes.push(op.change(words.join(" ")));
+    }
+    changes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Unchanged,
+    Removed,
+    Added,
+}
+
+impl Op {
+    fn change(self, text: String) -> TextChange {
+        match self {
+            Op::Unchanged => TextChange::Unchanged(text),
+            Op::Removed => TextChange::Removed(text),
+            Op::Added => TextChange::Added(text),
+        }
+    }
+}
+
+/// Edit script from the longest common subsequence of two word lists,
+/// with removals before additions at each change
+fn lcs_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+    // lengths[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push((Op::Unchanged, old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            ops.push((Op::Removed, old[i]));
+            i += 1;
+        } else {
+            ops.push((Op::Added, new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|w| (Op::Removed, *w)));
+    ops.extend(new[j..].iter().map(|w| (Op::Added, *w)));
+    ops
+}
+
+/// Changed top-level keys, sorted by key. Non-object metadata is compared
+/// as a whole under the empty key.
+pub fn diff_metadata(before: &Value, after: &Value) -> Vec<MetadataChange> {
+    match (before.as_object(), after.as_object()) {
+        (Some(old), Some(new)) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            keys.into_iter()
+                .filter(|key| old.get(*key) != new.get(*key))
+                .map(|key| MetadataChange {
+                    key: key.clone(),
+                    before: old.get(key).cloned(),
+                    after: new.get(key).cloned(),
+                })
+                .collect()
+        }
+        _ if before == after => Vec::new(),
+        _ => vec![MetadataChange {
+            key: String::new(),
+            before: Some(before.clone()),
+            after: Some(after.clone()),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn revision(revision: i32, content: &str, metadata: Value, importance: f64) -> MemoryRevision {
+        MemoryRevision {
+            id: Uuid::new_v4(),
+            memory_id: Uuid::nil(),
+            namespace: "default".to_string(),
+            revision,
+            content: content.to_string(),
+            content_hash: Memory::calculate_content_hash(content),
+            metadata,
+            importance_score: importance,
+            tier: MemoryTier::Working,
+            actor: None,
+            reason: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_word_diff() {
+        let changes = diff_words(
+            "The deploy runs on Fridays at noon",
+            "The deploy runs on Tuesdays at noon UTC",
+        );
+        assert_eq!(
+            changes,
+            vec![
+                TextChange::Unchanged("The deploy runs on".to_string()),
+                TextChange::Removed("Fridays".to_string()),
+                TextChange::Added("Tuesdays".to_string()),
+                TextChange::Unchanged("at noon".to_string()),
+                TextChange::Added("UTC".to_string()),
+            ]
+        );
+
+        assert!(diff_words("same text", "same  text").len() == 1);
+        assert_eq!(
+            diff_words("", "new"),
+            vec![TextChange::Added("new".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_revision_diff() {
+        let from = revision(
+            1,
+            "Alice leads the platform team",
+            json!({"tags": ["people"], "source": "harvester"}),
+            0.5,
+        );
+        let to = revision(
+            2,
+            "Bob leads the platform team",
+            json!({"tags": ["people"], "verified": true}),
+            0.8,
+        );
+
+        let diff = RevisionDiff::between(&from, &to);
+        assert_eq!(
+            diff.render_content(),
+            "[-Alice-] {+Bob+} leads the platform team"
+        );
+        assert_eq!(
+            diff.metadata
+                .iter()
+                .map(|c| c.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["source", "verified"]
+        );
+        assert_eq!(diff.metadata[0].after, None);
+        assert_eq!(diff.importance_score, Some((0.5, 0.8)));
+        assert!(diff.tier.is_none());
+
+        assert!(RevisionDiff::between(&to, &to).is_empty());
+    }
+}
@@ -35,6 +35,11 @@ pub struct MetricsCollector {
     pub search_cache_hits_total: IntCounter,
     pub search_cache_misses_total: IntCounter,
 
+    // Embedding cache metrics
+    pub embedding_cache_hits_total: IntCounter,
+    pub embedding_cache_misses_total: IntCounter,
+    pub embedding_cache_hit_ratio: Gauge,
+
     // System metrics
     pub memory_usage_bytes: Gauge,
     pub cpu_usage_percent: Gauge,
@@ -164,6 +169,24 @@ impl MetricsCollector {
         ))?;
         registry.register(Box::new(search_cache_misses_total.clone()))?;
 
+        let embedding_cache_hits_total = IntCounter::with_opts(Opts::new(
+            "embedding_cache_hits_total",
+            "Total number of embeddings served from the embedding cache",
+        ))?;
+        registry.register(Box::new(embedding_cache_hits_total.clone()))?;
+
+        let embedding_cache_misses_total = IntCounter::with_opts(Opts::new(
+            "embedding_cache_misses_total",
+            "Total number of embeddings generated by the provider after a cache miss",
+        ))?;
+        registry.register(Box::new(embedding_cache_misses_total.clone()))?;
+
+        let embedding_cache_hit_ratio = Gauge::with_opts(Opts::new(
+            "embedding_cache_hit_ratio",
+            "Fraction of embedding lookups served from the cache (0-1)",
+        ))?;
+        registry.register(Box::new(embedding_cache_hit_ratio.clone()))?;
+
         let memory_usage_bytes = Gauge::with_opts(Opts::new(
             "memory_usage_bytes",
             "Current memory usage in bytes",
@@ -245,6 +268,9 @@ impl MetricsCollector {
             search_results_count,
             search_cache_hits_total,
             search_cache_misses_total,
+            embedding_cache_hits_total,
+            embedding_cache_misses_total,
+            embedding_cache_hit_ratio,
             memory_usage_bytes,
             cpu_usage_percent,
             uptime_seconds,
@@ -293,6 +319,20 @@ impl MetricsCollector {
         }
     }
 
+    /// Record an embedding cache lookup
+    pub fn record_embedding_cache(&self, hit: bool) {
+        if hit {
+            self.embedding_cache_hits_total.inc();
+        } else {
+            self.embedding_cache_misses_total.inc();
+        }
+
+        let hits = self.embedding_cache_hits_total.get();
+        let total = hits + self.embedding_cache_misses_total.get();
+        self.embedding_cache_hit_ratio
+            .set(hits as f64 / total as f64);
+    }
+
     /// Record a memory migration
     pub fn record_migration(
         &self,
@@ -448,6 +488,23 @@ mod tests {
         assert_eq!(collector.requests_total.get(), 1);
     }
 
+    #[test]
+    fn test_embedding_cache_hit_ratio() {
+        let collector = MetricsCollector::new().expect("metrics collector");
+
+        collector.record_embedding_cache(false);
+        collector.record_embedding_cache(true);
+        collector.record_embedding_cache(true);
+        collector.record_embedding_cache(true);
+
+        assert_eq!(collector.embedding_cache_hits_total.get(), 3);
+        assert_eq!(collector.embedding_cache_misses_total.get(), 1);
+        assert_eq!(collector.embedding_cache_hit_ratio.get(), 0.75);
+        assert!(collector
+            .gather_metrics()
+            .contains("embedding_cache_hit_ratio"));
+    }
+
     #[test]
     fn test_system_metrics_update() {
         let collector = MetricsCollector::new().unwrap();
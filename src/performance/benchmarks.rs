@@ -77,6 +77,7 @@ impl MemoryBenchmarks {
                 metadata: Some(serde_json::json!({"benchmark": true})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
 
             let start = Instant::now();
@@ -103,6 +104,7 @@ impl MemoryBenchmarks {
                 metadata: Some(serde_json::json!({"benchmark": true})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             let memory = self.repository.create_memory(request).await?;
             memory_ids.push(memory.id);
@@ -135,6 +137,7 @@ impl MemoryBenchmarks {
                 metadata: Some(serde_json::json!({"benchmark": true})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             let memory = self.repository.create_memory(request).await?;
             memory_ids.push(memory.id);
@@ -150,6 +153,11 @@ impl MemoryBenchmarks {
                 importance_score: Some(0.7),
                 metadata: None,
                 expires_at: None,
+                valid_from: None,
+                valid_to: None,
+                actor: None,
+                reason: None,
+                content_hash: None,
             };
 
             let start = Instant::now();
@@ -176,6 +184,7 @@ impl MemoryBenchmarks {
                 metadata: Some(serde_json::json!({"benchmark": true})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             let memory = self.repository.create_memory(request).await?;
             memory_ids.push(memory.id);
@@ -211,6 +220,7 @@ impl MemoryBenchmarks {
                 metadata: Some(serde_json::json!({"benchmark": true})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             self.repository.create_memory(request).await?;
         }
@@ -238,6 +248,11 @@ impl MemoryBenchmarks {
                 include_facets: None,
                 ranking_boost: None,
                 explain_score: None,
+                rrf_k: None,
+                entity: None,
+                include_superseded: None,
+                as_of: None,
+                subject_id: None,
             };
 
             let start = Instant::now();
@@ -270,6 +285,7 @@ impl MemoryBenchmarks {
                     metadata: Some(serde_json::json!({"benchmark": true, "batch": batch})),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
                 self.repository.create_memory(request).await?;
             }
@@ -295,6 +311,7 @@ impl MemoryBenchmarks {
                 metadata: Some(serde_json::json!({"benchmark": true})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             let memory = self.repository.create_memory(request).await?;
             memory_ids.push(memory.id);
@@ -190,6 +190,7 @@ impl LoadTester {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
                 repository.create_memory(request).await?;
             }
@@ -214,6 +215,11 @@ impl LoadTester {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
                 repository.search_memories_simple(search_request).await?;
             }
@@ -228,6 +234,11 @@ impl LoadTester {
                         importance_score: Some(importance_score2),
                         metadata: None,
                         expires_at: None,
+                        valid_from: None,
+                        valid_to: None,
+                        actor: None,
+                        reason: None,
+                        content_hash: None,
                     };
                     repository.update_memory(memory_id, update_request).await?;
                 }
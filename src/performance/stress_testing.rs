@@ -287,6 +287,7 @@ impl StressTester {
                         ),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     };
                     repository.create_memory(request).await?;
                 }
@@ -316,6 +317,11 @@ impl StressTester {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
                 repository.search_memories_simple(search_request).await?;
             }
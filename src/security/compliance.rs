@@ -1,8 +1,11 @@
+use crate::memory::FieldEncryption;
+use crate::security::envelope::{self, EnvelopeKey};
 use crate::security::{AuditManager, AuditSeverity, GdprConfig, Result, SecurityError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{PgPool, Row};
-use std::collections::HashMap;
+use sqlx::postgres::PgArguments;
+use sqlx::{PgConnection, PgPool, Postgres, Row};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
@@ -12,6 +15,10 @@ pub struct ComplianceManager {
     config: GdprConfig,
     db_pool: Arc<PgPool>,
     audit_manager: Option<Arc<AuditManager>>,
+    /// Key erasure receipts are signed with
+    receipt_key: Option<EnvelopeKey>,
+    /// Decrypts memory content for portability exports
+    field_encryption: Option<Arc<FieldEncryption>>,
 }
 
 /// Data subject request types
@@ -51,7 +58,7 @@ pub enum RequestStatus {
 }
 
 /// Personal data category
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DataCategory {
     Identity,  // Name, email, etc.
     Contact,   // Address, phone, etc.
@@ -101,16 +108,417 @@ pub struct DataExport {
     pub subject_id: String,
     pub exported_at: DateTime<Utc>,
     pub data_categories: Vec<DataCategory>,
+    /// Rows of each table holding the subject's data, keyed by table name
     pub personal_data: HashMap<String, serde_json::Value>,
     pub metadata: HashMap<String, String>,
 }
 
+/// Layout of `DataExport::personal_data` that `import_personal_data` reads
+pub const SUBJECT_ARCHIVE_FORMAT: &str = "codex-memory-subject-archive/1";
+
+/// Metadata key of the data subject a memory was stored for. Only the server
+/// sets it; clients can neither supply nor change it.
+pub const SUBJECT_ID_KEY: &str = "subject_id";
+
+/// Data subject of a write to `namespace`: the client that made it, else
+/// the namespace's anonymous writers as one subject. Memories carry it as
+/// `metadata.subject_id` and the PII vault keys their values by it, so
+/// erasing it removes both.
+pub fn data_subject(actor: Option<&str>, namespace: &str) -> String {
+    match actor {
+        Some(actor) => actor.to_string(),
+        None => format!("anonymous:{namespace}"),
+    }
+}
+
+/// Proof of an erasure, stored with its request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureReceipt {
+    pub request_id: String,
+    pub subject_id: String,
+    pub processed_by: String,
+    pub erased_at: DateTime<Utc>,
+    /// Rows erased per table
+    pub records_erased: BTreeMap<String, u64>,
+    /// Ids of the erased memories, sorted; verification checks that none
+    /// of them has come back
+    pub memory_ids: Vec<Uuid>,
+    /// Backups taken before the erasure, flagged to have it re-applied
+    /// when they are restored
+    pub backups_flagged: u64,
+    /// HMAC-SHA256 of all fields above under the server's receipt key
+    pub checksum: String,
+}
+
+impl ErasureReceipt {
+    fn new(
+        request_id: &str,
+        subject_id: &str,
+        processed_by: &str,
+        records_erased: BTreeMap<String, u64>,
+        memory_ids: &[Uuid],
+        backups_flagged: u64,
+        key: &[u8],
+    ) -> Self {
+        let mut memory_ids = memory_ids.to_vec();
+        memory_ids.sort();
+
+        let mut receipt = Self {
+            request_id: request_id.to_string(),
+            subject_id: subject_id.to_string(),
+            processed_by: processed_by.to_string(),
+            erased_at: Utc::now(),
+            records_erased,
+            memory_ids,
+            backups_flagged,
+            checksum: String::new(),
+        };
+        receipt.checksum = receipt.compute_checksum(key);
+        receipt
+    }
+
+    fn compute_checksum(&self, key: &[u8]) -> String {
+        let fields = serde_json::json!([
+            self.request_id,
+            self.subject_id,
+            self.processed_by,
+            self.erased_at,
+            self.records_erased,
+            self.memory_ids,
+            self.backups_flagged,
+        ]);
+        envelope::keyed_digest(key, &[fields.to_string().as_bytes()])
+    }
+
+    /// Whether the receipt is unaltered since it was issued under `key`
+    pub fn is_intact(&self, key: &[u8]) -> bool {
+        self.checksum == self.compute_checksum(key)
+    }
+
+    pub fn total_erased(&self) -> u64 {
+        self.records_erased.values().sum()
+    }
+}
+
+/// Outcome of checking an erasure against its receipt and the database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureVerification {
+    pub receipt: ErasureReceipt,
+    pub receipt_intact: bool,
+    /// Rows of the subject found since, per table
+    pub remaining_records: BTreeMap<String, u64>,
+}
+
+impl ErasureVerification {
+    pub fn is_verified(&self) -> bool {
+        self.receipt_intact && self.remaining_records.is_empty()
+    }
+}
+
+/// A table holding data of subjects. Conditions select the rows of one
+/// subject: `$1` is the subject id, `$2` the ids of their memories, `$3` the
+/// harvest sessions and `$4` the deduplication operations that produced them,
+/// `$5` the entities they mention and `$6` the content hashes of their texts.
+struct SubjectTable {
+    name: &'static str,
+    category: DataCategory,
+    /// Rows exported, and reported as remaining after an erasure
+    rows: &'static str,
+    /// Rows erased when that differs from `rows`, to keep rows shared with
+    /// other subjects
+    erase: Option<&'static str>,
+}
+
+/// Memories of a subject: those stored on their behalf, which carry their id
+/// as `metadata.subject_id`. Namespaces are shared, so they say nothing of
+/// whose a memory is.
+const SUBJECT_MEMORIES: &str = "SELECT id FROM memories WHERE metadata->>'subject_id' = $1";
+
+/// Hashes of the texts a subject's memories, revisions and searches embedded,
+/// as the embedding cache keys them. Memories and revisions store the hash
+/// of their plaintext, which stays valid when the content is encrypted.
+const SUBJECT_CONTENT_HASHES: &str = r#"
+    SELECT content_hash FROM memories WHERE id = ANY($2)
+    UNION
+    SELECT content_hash FROM memory_revisions WHERE memory_id = ANY($2)
+    UNION
+    SELECT encode(sha256(convert_to(query_text, 'UTF8')), 'hex')
+    FROM search_query_log WHERE subject_id = $1
+"#;
+
+/// Subject tables whose `content` and `metadata` field encryption covers
+const ENCRYPTED_TABLES: &[&str] = &["memories", "memory_revisions"];
+
+/// Tables with subject data, in the order rows are imported; erasure goes
+/// in reverse so that dependent rows go first
+const SUBJECT_TABLES: &[SubjectTable] = &[
+    SubjectTable {
+        name: "pii_vault_keys",
+        category: DataCategory::Identity,
+        rows: "subject_id = $1",
+        erase: None,
+    },
+    SubjectTable {
+        name: "pii_vault",
+        category: DataCategory::Identity,
+        rows: "subject_id = $1",
+        erase: None,
+    },
+    SubjectTable {
+        name: "harvest_sessions",
+        category: DataCategory::Usage,
+        rows: "id = ANY($3)",
+        erase: Some(
+            "id = ANY($3) AND NOT EXISTS \
+             (SELECT 1 FROM harvest_patterns p WHERE p.harvest_session_id = harvest_sessions.id)",
+        ),
+    },
+    SubjectTable {
+        name: "deduplication_audit_log",
+        category: DataCategory::Metadata,
+        rows: "id = ANY($4)",
+        erase: Some(
+            "id = ANY($4) AND NOT EXISTS \
+             (SELECT 1 FROM memory_merge_history h WHERE h.merge_operation_id = deduplication_audit_log.id)",
+        ),
+    },
+    SubjectTable {
+        name: "memories",
+        category: DataCategory::Generated,
+        rows: "id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "memory_revisions",
+        category: DataCategory::Generated,
+        rows: "memory_id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "memory_relations",
+        category: DataCategory::Generated,
+        rows: "source_id = ANY($2) OR target_id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "memory_conflicts",
+        category: DataCategory::Metadata,
+        rows: "memory_id = ANY($2) OR conflicting_memory_id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "entities",
+        category: DataCategory::Identity,
+        rows: "id = ANY($5)",
+        erase: Some(
+            "id = ANY($5) AND NOT EXISTS \
+             (SELECT 1 FROM memory_entities m WHERE m.entity_id = entities.id)",
+        ),
+    },
+    SubjectTable {
+        name: "entity_aliases",
+        category: DataCategory::Identity,
+        rows: "entity_id = ANY($5)",
+        erase: Some(
+            "entity_id = ANY($5) AND NOT EXISTS \
+             (SELECT 1 FROM memory_entities m WHERE m.entity_id = entity_aliases.entity_id)",
+        ),
+    },
+    SubjectTable {
+        name: "memory_entities",
+        category: DataCategory::Generated,
+        rows: "memory_id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "frozen_memories",
+        category: DataCategory::Generated,
+        rows: "original_memory_id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "harvest_patterns",
+        category: DataCategory::Usage,
+        rows: "memory_id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "memory_merge_history",
+        category: DataCategory::Metadata,
+        rows: "merged_memory_id = ANY($2) OR original_memory_id = ANY($2)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "memory_access_log",
+        category: DataCategory::Usage,
+        rows: "memory_id = ANY($2) OR user_context = $1",
+        erase: None,
+    },
+    SubjectTable {
+        name: "insights",
+        category: DataCategory::Generated,
+        rows: "source_memory_ids && $2",
+        erase: None,
+    },
+    SubjectTable {
+        name: "search_query_log",
+        category: DataCategory::Usage,
+        rows: "subject_id = $1",
+        erase: None,
+    },
+    SubjectTable {
+        name: "embedding_cache",
+        category: DataCategory::Generated,
+        rows: "content_hash = ANY($6)",
+        erase: None,
+    },
+    SubjectTable {
+        name: "audit_events",
+        category: DataCategory::Technical,
+        rows: "user_id = $1",
+        erase: None,
+    },
+];
+
+/// A subject and the ids their rows are found by
+struct SubjectScope {
+    subject_id: String,
+    memory_ids: Vec<Uuid>,
+    session_ids: Vec<Uuid>,
+    operation_ids: Vec<Uuid>,
+    entity_ids: Vec<Uuid>,
+    content_hashes: Vec<String>,
+}
+
+impl SubjectScope {
+    /// Find the rows of `subject_id`. Subject ids naming a namespace are
+    /// refused: other subjects' memories may share it.
+    async fn resolve(conn: &mut PgConnection, subject_id: &str) -> Result<Self> {
+        let names_namespace: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM memories WHERE namespace = $1)")
+                .bind(subject_id)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(gdpr_error("look up namespaces"))?;
+        if names_namespace {
+            return Err(SecurityError::GdprError {
+                message: format!(
+                    "Subject id {subject_id} names a namespace, which other subjects may share; \
+                     use the id the subject's memories carry as metadata.{SUBJECT_ID_KEY}"
+                ),
+            });
+        }
+        Self::with_memories(conn, subject_id, Vec::new()).await
+    }
+
+    /// Find the rows of `subject_id` and of `memory_ids`, memories of the
+    /// subject that may no longer carry their id, e.g. those an erasure
+    /// receipt lists
+    async fn with_memories(
+        conn: &mut PgConnection,
+        subject_id: &str,
+        mut memory_ids: Vec<Uuid>,
+    ) -> Result<Self> {
+        let stamped: Vec<Uuid> = sqlx::query_scalar(SUBJECT_MEMORIES)
+            .bind(subject_id)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(gdpr_error("find the subject's memories"))?;
+        memory_ids.extend(stamped);
+        memory_ids.sort();
+        memory_ids.dedup();
+
+        let mut session_ids = Vec::new();
+        if table_exists(conn, "harvest_patterns").await? {
+            session_ids = sqlx::query_scalar(
+                "SELECT DISTINCT harvest_session_id FROM harvest_patterns WHERE memory_id = ANY($1)",
+            )
+            .bind(&memory_ids)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(gdpr_error("find the subject's harvest sessions"))?;
+        }
+
+        let mut operation_ids = Vec::new();
+        if table_exists(conn, "memory_merge_history").await? {
+            operation_ids = sqlx::query_scalar(
+                "SELECT DISTINCT merge_operation_id FROM memory_merge_history \
+                 WHERE merged_memory_id = ANY($1) OR original_memory_id = ANY($1)",
+            )
+            .bind(&memory_ids)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(gdpr_error("find the subject's deduplication operations"))?;
+        }
+
+        let mut entity_ids = Vec::new();
+        if table_exists(conn, "memory_entities").await? {
+            entity_ids = sqlx::query_scalar(
+                "SELECT DISTINCT entity_id FROM memory_entities WHERE memory_id = ANY($1)",
+            )
+            .bind(&memory_ids)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(gdpr_error("find the entities the subject mentions"))?;
+        }
+
+        let content_hashes = sqlx::query_scalar(SUBJECT_CONTENT_HASHES)
+            .bind(subject_id)
+            .bind(&memory_ids)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(gdpr_error("hash the subject's texts"))?;
+
+        Ok(Self {
+            subject_id: subject_id.to_string(),
+            memory_ids,
+            session_ids,
+            operation_ids,
+            entity_ids,
+            content_hashes,
+        })
+    }
+
+    /// `sql` with the scope bound to its parameters
+    fn bind<'q>(&'q self, sql: &'q str) -> sqlx::query::Query<'q, Postgres, PgArguments> {
+        sqlx::query(sql)
+            .bind(&self.subject_id)
+            .bind(&self.memory_ids)
+            .bind(&self.session_ids)
+            .bind(&self.operation_ids)
+            .bind(&self.entity_ids)
+            .bind(&self.content_hashes)
+    }
+}
+
+fn archive_error(e: crate::memory::MemoryError) -> SecurityError {
+    SecurityError::GdprError {
+        message: format!("Failed to convert encrypted content: {e}"),
+    }
+}
+
+async fn table_exists(conn: &mut PgConnection, table: &str) -> Result<bool> {
+    sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+        .bind(table)
+        .fetch_one(conn)
+        .await
+        .map_err(gdpr_error("look up table"))
+}
+
+fn gdpr_error(action: &'static str) -> impl Fn(sqlx::Error) -> SecurityError {
+    move |e| SecurityError::GdprError {
+        message: format!("Failed to {action}: {e}"),
+    }
+}
+
 impl ComplianceManager {
     pub fn new(config: GdprConfig, db_pool: Arc<PgPool>) -> Self {
         Self {
             config,
             db_pool,
             audit_manager: None,
+            receipt_key: None,
+            field_encryption: None,
         }
     }
 
@@ -119,6 +527,30 @@ impl ComplianceManager {
         self
     }
 
+    /// Sign erasure receipts with a key derived from `secret`. Erasures are
+    /// refused until one is set, since anyone with database access could
+    /// otherwise rewrite a receipt and its checksum together.
+    pub fn with_receipt_key(mut self, secret: &str) -> Self {
+        self.receipt_key = Some(envelope::master_key(secret));
+        self
+    }
+
+    /// Decrypt memory content encrypted at rest in portability exports, and
+    /// encrypt it again on import
+    pub fn with_field_encryption(mut self, encryption: Arc<FieldEncryption>) -> Self {
+        self.field_encryption = Some(encryption);
+        self
+    }
+
+    fn receipt_key(&self) -> Result<&[u8]> {
+        self.receipt_key
+            .as_ref()
+            .map(|key| key.as_slice())
+            .ok_or_else(|| SecurityError::GdprError {
+                message: "No key is configured to sign erasure receipts".to_string(),
+            })
+    }
+
     /// Initialize GDPR compliance system
     pub async fn initialize(&self) -> Result<()> {
         if !self.config.enabled {
@@ -174,8 +606,13 @@ impl ComplianceManager {
             );
         "#;
 
+        // Receipts of erasures, added after the first release
+        let add_receipt_column =
+            "ALTER TABLE gdpr_requests ADD COLUMN IF NOT EXISTS erasure_receipt JSONB;";
+
         for sql in [
             create_requests_table,
+            add_receipt_column,
             create_consent_table,
             create_processing_table,
         ] {
@@ -240,7 +677,7 @@ impl ComplianceManager {
 
         let insert_sql = r#"
             INSERT INTO gdpr_requests (id, request_type, subject_id, subject_email, requested_at, status)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            VALUES ($1::uuid, $2, $3, $4, $5, $6)
         "#;
 
         sqlx::query(insert_sql)
@@ -290,12 +727,16 @@ impl ComplianceManager {
         Ok(request_id)
     }
 
-    /// Process a right to be forgotten request
+    /// Process a right to be forgotten request. All data of the subject is
+    /// erased in one transaction, which either completes the request with a
+    /// receipt or leaves the data and the request as they were. Requests for
+    /// subjects with memories under legal hold fail until the holds are
+    /// released.
     pub async fn process_erasure_request(
         &self,
         request_id: &str,
         processor_id: &str,
-    ) -> Result<()> {
+    ) -> Result<ErasureReceipt> {
         if !self.config.enabled || !self.config.right_to_be_forgotten {
             return Err(SecurityError::GdprError {
                 message: "Right to be forgotten is not enabled".to_string(),
@@ -320,44 +761,135 @@ impl ComplianceManager {
             });
         }
 
+        self.receipt_key()?;
+
         // Update request status
         self.update_request_status(request_id, RequestStatus::InProgress, Some(processor_id))
             .await?;
 
         // Perform data erasure
-        let erasure_result = self.erase_personal_data(&request.subject_id).await;
-
-        match erasure_result {
-            Ok(erased_count) => {
-                // Mark request as completed
-                let notes = format!("Successfully erased {erased_count} data records");
+        match self
+            .erase_personal_data(request_id, processor_id, &request.subject_id)
+            .await
+        {
+            Ok(receipt) => {
+                let notes = format!(
+                    "Successfully erased {} data records (receipt {})",
+                    receipt.total_erased(),
+                    receipt.checksum
+                );
                 self.complete_request(request_id, processor_id, Some(&notes))
                     .await?;
 
                 info!(
                     "Erasure request completed for subject: {} ({} records erased)",
-                    request.subject_id, erased_count
+                    request.subject_id,
+                    receipt.total_erased()
                 );
+                Ok(receipt)
             }
             Err(e) => {
-                // Mark request as partially completed or rejected
-                let notes = format!("Erasure partially failed: {e}");
-                self.update_request_status(
-                    request_id,
-                    RequestStatus::PartiallyCompleted,
-                    Some(processor_id),
-                )
-                .await?;
+                // Nothing was erased; the request stays in progress for a retry
+                let notes = format!("Erasure failed and was rolled back: {e}");
                 self.update_request_notes(request_id, &notes).await?;
 
                 warn!(
-                    "Erasure request partially failed for subject: {}: {}",
+                    "Erasure request failed for subject: {}: {}",
                     request.subject_id, e
                 );
+                Err(e)
             }
         }
+    }
 
-        Ok(())
+    /// Check a completed erasure: its receipt is unaltered and no data of
+    /// the subject, or of the memories it erased, has been found since
+    pub async fn verify_erasure(&self, request_id: &str) -> Result<ErasureVerification> {
+        let key = self.receipt_key()?;
+        let receipt: Option<serde_json::Value> =
+            sqlx::query_scalar("SELECT erasure_receipt FROM gdpr_requests WHERE id = $1::uuid")
+                .bind(request_id)
+                .fetch_optional(self.db_pool.as_ref())
+                .await
+                .map_err(gdpr_error("fetch the erasure receipt"))?
+                .flatten();
+        let receipt: ErasureReceipt = receipt
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| SecurityError::GdprError {
+                message: format!("Invalid erasure receipt: {e}"),
+            })?
+            .ok_or_else(|| SecurityError::GdprError {
+                message: format!("No erasure receipt for request: {request_id}"),
+            })?;
+
+        let mut conn = self
+            .db_pool
+            .acquire()
+            .await
+            .map_err(gdpr_error("connect to the database"))?;
+        let scope =
+            SubjectScope::with_memories(&mut conn, &receipt.subject_id, receipt.memory_ids.clone())
+                .await?;
+        let mut remaining_records = BTreeMap::new();
+        for table in SUBJECT_TABLES {
+            if !table_exists(&mut conn, table.name).await? {
+                continue;
+            }
+            // Rows shared with other subjects were kept on purpose
+            let sql = format!(
+                "SELECT COUNT(*) FROM {} WHERE {}",
+                table.name,
+                table.erase.unwrap_or(table.rows)
+            );
+            let count: i64 = scope
+                .bind(&sql)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(gdpr_error("count remaining records"))?
+                .get(0);
+            if count > 0 {
+                remaining_records.insert(table.name.to_string(), count as u64);
+            }
+        }
+
+        Ok(ErasureVerification {
+            receipt_intact: receipt.is_intact(key),
+            receipt,
+            remaining_records,
+        })
+    }
+
+    /// Erase again the subjects of completed erasure requests, e.g. after
+    /// restoring a backup flagged by them. Returns the records erased.
+    pub async fn reapply_erasures(&self) -> Result<u64> {
+        let subjects: Vec<String> = sqlx::query_scalar(
+            "SELECT DISTINCT subject_id FROM gdpr_requests WHERE request_type = $1 AND status = $2",
+        )
+        .bind(format!("{:?}", DataSubjectRequestType::Erasure))
+        .bind(format!("{:?}", RequestStatus::Completed))
+        .fetch_all(self.db_pool.as_ref())
+        .await
+        .map_err(gdpr_error("list completed erasures"))?;
+
+        let mut total_erased = 0;
+        for subject_id in subjects {
+            let mut tx = self
+                .db_pool
+                .begin()
+                .await
+                .map_err(gdpr_error("start the erasure"))?;
+            let (records_erased, _) = erase_subject(&mut tx, &subject_id).await?;
+            tx.commit()
+                .await
+                .map_err(gdpr_error("commit the erasure"))?;
+            total_erased += records_erased.values().sum::<u64>();
+        }
+
+        if total_erased > 0 {
+            info!("Re-applied erasures: {} records erased", total_erased);
+        }
+        Ok(total_erased)
     }
 
     /// Process a data portability request
@@ -393,7 +925,7 @@ impl ComplianceManager {
                 message: format!("Failed to serialize data export: {e}"),
             })?;
 
-        sqlx::query("UPDATE gdpr_requests SET data_export = $1 WHERE id = $2")
+        sqlx::query("UPDATE gdpr_requests SET data_export = $1::jsonb WHERE id = $2::uuid")
             .bind(&export_json)
             .bind(request_id)
             .execute(self.db_pool.as_ref())
@@ -417,162 +949,168 @@ impl ComplianceManager {
         Ok(data_export)
     }
 
-    /// Erase personal data for a subject
-    async fn erase_personal_data(&self, subject_id: &str) -> Result<u32> {
-        let mut total_erased = 0;
-
-        // Erase from memories table
-        let memory_result: i64 =
-            sqlx::query_scalar("DELETE FROM memories WHERE user_id = $1 RETURNING COUNT(*)")
-                .bind(subject_id)
-                .fetch_optional(self.db_pool.as_ref())
-                .await
-                .map_err(|e| SecurityError::GdprError {
-                    message: format!("Failed to erase memory data: {e}"),
-                })?
-                .unwrap_or(0);
-
-        total_erased += memory_result as u32;
-
-        // Erase from audit_events table (if it exists)
-        let audit_result =
-            sqlx::query_scalar::<_, i64>("DELETE FROM audit_events WHERE user_id = $1")
-                .bind(subject_id)
-                .fetch_optional(self.db_pool.as_ref())
-                .await;
-
-        if let Ok(Some(count)) = audit_result {
-            total_erased += count as u32;
+    /// Erase all data of a subject and store the receipt with the request
+    async fn erase_personal_data(
+        &self,
+        request_id: &str,
+        processor_id: &str,
+        subject_id: &str,
+    ) -> Result<ErasureReceipt> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(gdpr_error("start the erasure"))?;
+        let (records_erased, memory_ids) = erase_subject(&mut tx, subject_id).await?;
+
+        // Backups cannot be rewritten; the ones started before the erasure
+        // may still hold the subject's data and record the request to
+        // re-apply once restored. NOW() is the start of the transaction.
+        let mut backups_flagged = 0;
+        if table_exists(&mut tx, "backup_metadata").await? {
+            backups_flagged = sqlx::query(
+                r#"
+                UPDATE backup_metadata
+                SET metadata = jsonb_set(
+                    COALESCE(metadata, '{}'::jsonb),
+                    '{pending_erasures}',
+                    COALESCE(metadata->'pending_erasures', '[]'::jsonb) || to_jsonb($1::text)
+                )
+                WHERE start_time < NOW()
+                "#,
+            )
+            .bind(request_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(gdpr_error("flag backups"))?
+            .rows_affected();
         }
 
-        // Erase from backup_metadata table (if it exists)
-        let backup_result = sqlx::query_scalar::<_, i64>(
-            "UPDATE backup_metadata SET user_info = NULL WHERE user_info::jsonb ? $1",
-        )
-        .bind(subject_id)
-        .fetch_optional(self.db_pool.as_ref())
-        .await;
-
-        if let Ok(Some(count)) = backup_result {
-            total_erased += count as u32;
-        }
+        let receipt = ErasureReceipt::new(
+            request_id,
+            subject_id,
+            processor_id,
+            records_erased,
+            &memory_ids,
+            backups_flagged,
+            self.receipt_key()?,
+        );
+        let receipt_json =
+            serde_json::to_value(&receipt).map_err(|e| SecurityError::GdprError {
+                message: format!("Failed to serialize erasure receipt: {e}"),
+            })?;
+        sqlx::query("UPDATE gdpr_requests SET erasure_receipt = $1 WHERE id = $2::uuid")
+            .bind(receipt_json)
+            .bind(request_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(gdpr_error("store the erasure receipt"))?;
+        tx.commit()
+            .await
+            .map_err(gdpr_error("commit the erasure"))?;
 
-        // Log the erasure
+        // Log the erasure; the subject's id stays out of the audit trail
         if let Some(audit_manager) = &self.audit_manager {
             let mut details = HashMap::new();
             details.insert(
-                "subject_id".to_string(),
-                serde_json::Value::String(subject_id.to_string()),
+                "request_id".to_string(),
+                serde_json::Value::String(request_id.to_string()),
             );
             details.insert(
                 "records_erased".to_string(),
-                serde_json::Value::Number(total_erased.into()),
+                serde_json::Value::Number(receipt.total_erased().into()),
+            );
+            details.insert(
+                "receipt_checksum".to_string(),
+                serde_json::Value::String(receipt.checksum.clone()),
             );
 
             let _ = audit_manager
                 .log_security_event(
                     "personal_data_erased",
                     AuditSeverity::High,
-                    Some(subject_id),
+                    None,
                     None,
                     details,
                 )
                 .await;
         }
 
-        Ok(total_erased)
+        Ok(receipt)
     }
 
-    /// Export personal data for portability
+    /// Export all data of a subject as an archive `import_personal_data`
+    /// can restore: the complete rows of every table holding their data.
+    /// Memory content encrypted at rest is exported as plaintext.
     async fn export_personal_data(&self, subject_id: &str) -> Result<DataExport> {
         let mut personal_data = HashMap::new();
         let mut data_categories = Vec::new();
-
-        // Export memory data
-        let memory_rows = sqlx::query(
-            "SELECT id, content, tier, created_at, last_accessed_at FROM memories WHERE user_id = $1"
-        )
-        .bind(subject_id)
-        .fetch_all(self.db_pool.as_ref())
-        .await
-        .map_err(|e| SecurityError::GdprError {
-            message: format!("Failed to export memory data: {e}"),
-        })?;
-
-        if !memory_rows.is_empty() {
-            let mut memories = Vec::new();
-            for row in memory_rows {
-                let memory_data = serde_json::json!({
-                    "id": row.get::<String, _>("id"),
-                    "content": row.get::<String, _>("content"),
-                    "tier": row.get::<String, _>("tier"),
-                    "created_at": row.get::<DateTime<Utc>, _>("created_at"),
-                    "last_accessed_at": row.get::<Option<DateTime<Utc>>, _>("last_accessed_at")
-                });
-                memories.push(memory_data);
-            }
-            personal_data.insert("memories".to_string(), serde_json::Value::Array(memories));
-            data_categories.push(DataCategory::Generated);
-        }
-
-        // Export processing records
-        let processing_rows = sqlx::query("SELECT * FROM gdpr_processing WHERE subject_id = $1")
-            .bind(subject_id)
-            .fetch_all(self.db_pool.as_ref())
+        let mut conn = self
+            .db_pool
+            .acquire()
             .await
-            .unwrap_or_default();
+            .map_err(gdpr_error("connect to the database"))?;
 
-        if !processing_rows.is_empty() {
-            let mut processing_records = Vec::new();
-            for row in processing_rows {
-                let record = serde_json::json!({
-                    "id": row.get::<String, _>("id"),
-                    "category": row.get::<String, _>("category"),
-                    "purpose": row.get::<String, _>("purpose"),
-                    "legal_basis": row.get::<String, _>("legal_basis"),
-                    "processed_at": row.get::<DateTime<Utc>, _>("processed_at"),
-                    "retention_until": row.get::<Option<DateTime<Utc>>, _>("retention_until"),
-                    "consent_given": row.get::<Option<bool>, _>("consent_given")
-                });
-                processing_records.push(record);
+        let scope = SubjectScope::resolve(&mut conn, subject_id).await?;
+        for table in SUBJECT_TABLES {
+            if !table_exists(&mut conn, table.name).await? {
+                continue;
             }
-            personal_data.insert(
-                "processing_records".to_string(),
-                serde_json::Value::Array(processing_records),
+            let sql = format!(
+                "SELECT to_jsonb({name}) FROM {name} WHERE {rows}",
+                name = table.name,
+                rows = table.rows
             );
-            data_categories.push(DataCategory::Metadata);
+            let rows: Vec<serde_json::Value> = scope
+                .bind(&sql)
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(|e| SecurityError::GdprError {
+                    message: format!("Failed to export {}: {e}", table.name),
+                })?
+                .iter()
+                .map(|row| self.decrypt_row(table.name, row.get(0)))
+                .collect::<Result<_>>()?;
+            if rows.is_empty() {
+                continue;
+            }
+            personal_data.insert(table.name.to_string(), serde_json::Value::Array(rows));
+            if !data_categories.contains(&table.category) {
+                data_categories.push(table.category.clone());
+            }
         }
 
-        // Export consent records
-        let consent_rows = sqlx::query("SELECT * FROM gdpr_consent WHERE subject_id = $1")
+        // Records of the processing itself, kept alongside the archive
+        for (key, table, category) in [
+            (
+                "processing_records",
+                "gdpr_processing",
+                DataCategory::Metadata,
+            ),
+            ("consent_records", "gdpr_consent", DataCategory::Identity),
+        ] {
+            let rows: Vec<serde_json::Value> = sqlx::query_scalar(&format!(
+                "SELECT to_jsonb({table}) FROM {table} WHERE subject_id = $1"
+            ))
             .bind(subject_id)
-            .fetch_all(self.db_pool.as_ref())
+            .fetch_all(&mut *conn)
             .await
             .unwrap_or_default();
-
-        if !consent_rows.is_empty() {
-            let mut consent_records = Vec::new();
-            for row in consent_rows {
-                let record = serde_json::json!({
-                    "id": row.get::<String, _>("id"),
-                    "purpose": row.get::<String, _>("purpose"),
-                    "consent_given": row.get::<bool, _>("consent_given"),
-                    "given_at": row.get::<DateTime<Utc>, _>("given_at"),
-                    "withdrawn_at": row.get::<Option<DateTime<Utc>>, _>("withdrawn_at"),
-                    "version": row.get::<String, _>("version")
-                });
-                consent_records.push(record);
+            if !rows.is_empty() {
+                personal_data.insert(key.to_string(), serde_json::Value::Array(rows));
+                if !data_categories.contains(&category) {
+                    data_categories.push(category);
+                }
             }
-            personal_data.insert(
-                "consent_records".to_string(),
-                serde_json::Value::Array(consent_records),
-            );
-            data_categories.push(DataCategory::Identity);
         }
 
         let mut metadata = HashMap::new();
         metadata.insert("export_format".to_string(), "JSON".to_string());
         metadata.insert("gdpr_version".to_string(), "2018".to_string());
+        metadata.insert(
+            "archive_format".to_string(),
+            SUBJECT_ARCHIVE_FORMAT.to_string(),
+        );
 
         Ok(DataExport {
             subject_id: subject_id.to_string(),
@@ -583,18 +1121,142 @@ impl ComplianceManager {
         })
     }
 
+    /// Restore an archive exported for portability, in one transaction.
+    /// Rows already present are kept. Returns the number of rows imported.
+    pub async fn import_personal_data(&self, export: &DataExport) -> Result<u64> {
+        if export.metadata.get("archive_format").map(String::as_str) != Some(SUBJECT_ARCHIVE_FORMAT)
+        {
+            return Err(SecurityError::GdprError {
+                message: format!("Data export is not a {SUBJECT_ARCHIVE_FORMAT} archive"),
+            });
+        }
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(gdpr_error("start the import"))?;
+        let mut imported = 0;
+        for table in SUBJECT_TABLES {
+            let Some(rows) = export.personal_data.get(table.name) else {
+                continue;
+            };
+            // Generated columns are derived again from the imported ones
+            let columns: Vec<String> = sqlx::query_scalar(
+                r#"
+                SELECT column_name::text FROM information_schema.columns
+                WHERE table_schema = current_schema() AND table_name = $1
+                  AND is_generated = 'NEVER'
+                ORDER BY ordinal_position
+                "#,
+            )
+            .bind(table.name)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(gdpr_error("read the table columns"))?;
+            if columns.is_empty() {
+                return Err(SecurityError::GdprError {
+                    message: format!("Cannot import {}: the table does not exist", table.name),
+                });
+            }
+
+            let columns = columns
+                .iter()
+                .map(|column| format!("\"{column}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!(
+                "INSERT INTO {name} ({columns}) \
+                 SELECT {columns} FROM jsonb_populate_recordset(NULL::{name}, $1) \
+                 ON CONFLICT DO NOTHING",
+                name = table.name
+            );
+            let rows = match rows {
+                serde_json::Value::Array(rows) => serde_json::Value::Array(
+                    rows.iter()
+                        .map(|row| self.encrypt_row(table.name, row.clone()))
+                        .collect::<Result<_>>()?,
+                ),
+                rows => rows.clone(),
+            };
+            imported += sqlx::query(&sql)
+                .bind(rows)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| SecurityError::GdprError {
+                    message: format!("Failed to import {}: {e}", table.name),
+                })?
+                .rows_affected();
+        }
+        tx.commit().await.map_err(gdpr_error("commit the import"))?;
+
+        info!(
+            "Imported {} records for subject: {}",
+            imported, export.subject_id
+        );
+        Ok(imported)
+    }
+
+    /// An exported row of `table` with its encrypted fields decrypted
+    fn decrypt_row(&self, table: &str, mut row: serde_json::Value) -> Result<serde_json::Value> {
+        let Some(encryption) = self.encryption_of(table) else {
+            return Ok(row);
+        };
+        if let Some(content) = row.get("content").and_then(|c| c.as_str()) {
+            row["content"] = encryption
+                .decrypt_content(content)
+                .map_err(archive_error)?
+                .into();
+        }
+        if let Some(metadata) = row.get_mut("metadata") {
+            *metadata = encryption
+                .decrypt_metadata(metadata.take())
+                .map_err(archive_error)?;
+        }
+        Ok(row)
+    }
+
+    /// An imported row of `table` with the fields encrypted at rest encrypted
+    fn encrypt_row(&self, table: &str, mut row: serde_json::Value) -> Result<serde_json::Value> {
+        let Some(encryption) = self.encryption_of(table) else {
+            return Ok(row);
+        };
+        if let Some(content) = row.get("content").and_then(|c| c.as_str()) {
+            row["content"] = encryption
+                .encrypt_content(content)
+                .map_err(archive_error)?
+                .into();
+        }
+        if let Some(metadata) = row.get_mut("metadata") {
+            *metadata = encryption
+                .encrypt_metadata(metadata.take())
+                .map_err(archive_error)?;
+        }
+        Ok(row)
+    }
+
+    /// Field encryption, if it covers the rows of `table`
+    fn encryption_of(&self, table: &str) -> Option<&FieldEncryption> {
+        self.field_encryption
+            .as_deref()
+            .filter(|_| ENCRYPTED_TABLES.contains(&table))
+    }
+
     /// Get a data subject request
     async fn get_request(&self, request_id: &str) -> Result<DataSubjectRequest> {
-        let row = sqlx::query("SELECT * FROM gdpr_requests WHERE id = $1")
-            .bind(request_id)
-            .fetch_optional(self.db_pool.as_ref())
-            .await
-            .map_err(|e| SecurityError::GdprError {
-                message: format!("Failed to fetch GDPR request: {e}"),
-            })?
-            .ok_or_else(|| SecurityError::GdprError {
-                message: format!("GDPR request not found: {request_id}"),
-            })?;
+        let row = sqlx::query(
+            "SELECT *, id::text AS request_id, data_export::text AS export_json \
+             FROM gdpr_requests WHERE id = $1::uuid",
+        )
+        .bind(request_id)
+        .fetch_optional(self.db_pool.as_ref())
+        .await
+        .map_err(|e| SecurityError::GdprError {
+            message: format!("Failed to fetch GDPR request: {e}"),
+        })?
+        .ok_or_else(|| SecurityError::GdprError {
+            message: format!("GDPR request not found: {request_id}"),
+        })?;
 
         self.row_to_request(row)
     }
@@ -622,7 +1284,7 @@ impl ComplianceManager {
         };
 
         Ok(DataSubjectRequest {
-            id: row.get("id"),
+            id: row.get("request_id"),
             request_type,
             subject_id: row.get("subject_id"),
             subject_email: row.get("subject_email"),
@@ -631,7 +1293,7 @@ impl ComplianceManager {
             processed_at: row.get("processed_at"),
             processed_by: row.get("processed_by"),
             notes: row.get("notes"),
-            data_export: row.get("data_export"),
+            data_export: row.get("export_json"),
         })
     }
 
@@ -641,7 +1303,7 @@ impl ComplianceManager {
         status: RequestStatus,
         processor_id: Option<&str>,
     ) -> Result<()> {
-        sqlx::query("UPDATE gdpr_requests SET status = $1, processed_by = $2 WHERE id = $3")
+        sqlx::query("UPDATE gdpr_requests SET status = $1, processed_by = $2 WHERE id = $3::uuid")
             .bind(format!("{status:?}"))
             .bind(processor_id)
             .bind(request_id)
@@ -661,7 +1323,7 @@ impl ComplianceManager {
         notes: Option<&str>,
     ) -> Result<()> {
         sqlx::query(
-            "UPDATE gdpr_requests SET status = $1, processed_at = $2, processed_by = $3, notes = $4 WHERE id = $5"
+            "UPDATE gdpr_requests SET status = $1, processed_at = $2, processed_by = $3, notes = $4 WHERE id = $5::uuid"
         )
         .bind(format!("{:?}", RequestStatus::Completed))
         .bind(Utc::now())
@@ -678,7 +1340,7 @@ impl ComplianceManager {
     }
 
     async fn update_request_notes(&self, request_id: &str, notes: &str) -> Result<()> {
-        sqlx::query("UPDATE gdpr_requests SET notes = $1 WHERE id = $2")
+        sqlx::query("UPDATE gdpr_requests SET notes = $1 WHERE id = $2::uuid")
             .bind(notes)
             .bind(request_id)
             .execute(self.db_pool.as_ref())
@@ -690,40 +1352,20 @@ impl ComplianceManager {
         Ok(())
     }
 
-    /// Clean up expired data based on retention policies
+    /// Clean up processing records past their retention date. Memories are
+    /// kept or removed by the retention rules of `RetentionJob`.
     pub async fn cleanup_expired_data(&self) -> Result<u32> {
         if !self.config.enabled || !self.config.auto_cleanup {
             return Ok(0);
         }
 
-        let cutoff_date =
-            Utc::now() - chrono::Duration::days(self.config.data_retention_days as i64);
-        let mut total_cleaned = 0;
-
-        // Clean up old memories
-        let memory_result: i64 = sqlx::query_scalar(
-            "DELETE FROM memories WHERE created_at < $1 AND tier = 'Cold' RETURNING COUNT(*)",
-        )
-        .bind(cutoff_date)
-        .fetch_optional(self.db_pool.as_ref())
-        .await
-        .map_err(|e| SecurityError::GdprError {
-            message: format!("Failed to cleanup memory data: {e}"),
-        })?
-        .unwrap_or(0);
-
-        total_cleaned += memory_result as u32;
-
-        // Clean up old processing records
-        let processing_result: i64 = sqlx::query_scalar(
-            "DELETE FROM gdpr_processing WHERE retention_until IS NOT NULL AND retention_until < NOW() RETURNING COUNT(*)"
+        let total_cleaned = sqlx::query(
+            "DELETE FROM gdpr_processing WHERE retention_until IS NOT NULL AND retention_until < NOW()",
         )
-        .fetch_optional(self.db_pool.as_ref())
+        .execute(self.db_pool.as_ref())
         .await
-        .unwrap_or(Some(0))
-        .unwrap_or(0);
-
-        total_cleaned += processing_result as u32;
+        .map_err(gdpr_error("clean up processing records"))?
+        .rows_affected() as u32;
 
         if total_cleaned > 0 {
             info!("Cleaned up {} expired data records", total_cleaned);
@@ -741,6 +1383,55 @@ impl ComplianceManager {
     }
 }
 
+/// Delete every row of a subject, dependent rows first. Returns the rows
+/// erased per table and the ids of the erased memories. Fails while any of
+/// the subject's memories is under legal hold.
+async fn erase_subject(
+    conn: &mut PgConnection,
+    subject_id: &str,
+) -> Result<(BTreeMap<String, u64>, Vec<Uuid>)> {
+    let scope = SubjectScope::resolve(conn, subject_id).await?;
+    if table_exists(conn, "legal_holds").await? {
+        let held: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM legal_holds WHERE memory_id = ANY($1)")
+                .bind(&scope.memory_ids)
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(gdpr_error("check legal holds"))?;
+        if held > 0 {
+            return Err(SecurityError::GdprError {
+                message: format!(
+                    "{held} memories of subject {subject_id} are under legal hold and cannot be erased"
+                ),
+            });
+        }
+    }
+    let mut records_erased = BTreeMap::new();
+    for table in SUBJECT_TABLES.iter().rev() {
+        if !table_exists(conn, table.name).await? {
+            continue;
+        }
+        let sql = format!(
+            "DELETE FROM {} WHERE {}",
+            table.name,
+            table.erase.unwrap_or(table.rows)
+        );
+        let erased = scope
+            .bind(&sql)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| SecurityError::GdprError {
+                message: format!("Failed to erase {}: {e}", table.name),
+            })?
+            .rows_affected();
+        if erased > 0 {
+            debug!("Erased {} rows of {}", erased, table.name);
+            records_erased.insert(table.name.to_string(), erased);
+        }
+    }
+    Ok((records_erased, scope.memory_ids))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -803,6 +1494,65 @@ mod tests {
         assert_eq!(export.metadata.len(), 1);
     }
 
+    #[test]
+    fn test_erasure_receipt_detects_changes() {
+        let mut records_erased = BTreeMap::new();
+        records_erased.insert("memories".to_string(), 3);
+        records_erased.insert("memory_revisions".to_string(), 5);
+        let receipt = ErasureReceipt::new(
+            "request-1",
+            "user123",
+            "dpo",
+            records_erased,
+            &[Uuid::new_v4(), Uuid::new_v4()],
+            2,
+            b"server key",
+        );
+        assert!(receipt.is_intact(b"server key"));
+        assert_eq!(receipt.total_erased(), 8);
+
+        // Survives being stored as JSON
+        let stored: ErasureReceipt =
+            serde_json::from_value(serde_json::to_value(&receipt).unwrap()).unwrap();
+        assert!(stored.is_intact(b"server key"));
+
+        let mut altered = receipt.clone();
+        altered.records_erased.insert("memories".to_string(), 4);
+        assert!(!altered.is_intact(b"server key"));
+
+        let mut altered = receipt.clone();
+        altered.memory_ids.pop();
+        assert!(!altered.is_intact(b"server key"));
+
+        // A checksum recomputed without the key does not pass
+        let mut forged = receipt.clone();
+        forged.records_erased.insert("memories".to_string(), 4);
+        forged.checksum = forged.compute_checksum(b"guessed key");
+        assert!(!forged.is_intact(b"server key"));
+    }
+
+    #[test]
+    fn test_subject_tables_import_parents_first() {
+        let position = |name: &str| {
+            SUBJECT_TABLES
+                .iter()
+                .position(|table| table.name == name)
+                .unwrap_or_else(|| panic!("{name} is not a subject table"))
+        };
+        for (parent, child) in [
+            ("pii_vault_keys", "pii_vault"),
+            ("memories", "memory_revisions"),
+            ("memories", "frozen_memories"),
+            ("entities", "entity_aliases"),
+            ("entities", "memory_entities"),
+            ("harvest_sessions", "harvest_patterns"),
+            ("memories", "harvest_patterns"),
+            ("deduplication_audit_log", "memory_merge_history"),
+        ] {
+            assert!(position(parent) < position(child), "{parent} after {child}");
+        }
+    }
+
     #[test]
     fn test_retention_policy() {
         let policy = RetentionPolicy {
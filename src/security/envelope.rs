@@ -0,0 +1,148 @@
+//! AES-256-GCM envelope encryption
+//!
+//! Data is encrypted under data keys, and data keys are stored wrapped by a
+//! master key that never reaches the database. Sealed values carry their
+//! nonce in front of the ciphertext.
+
+use crate::security::{Result, SecurityError};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// AES-256-GCM key
+pub type EnvelopeKey = Key<Aes256Gcm>;
+
+/// Length of the nonce prepended to every ciphertext
+const NONCE_LEN: usize = 12;
+
+/// HKDF label of the key deterministic nonces are derived under
+const NONCE_KEY_INFO: &[u8] = b"codex-memory deterministic nonce";
+
+/// Master key derived from a secret of any length
+pub fn master_key(secret: &str) -> EnvelopeKey {
+    let digest: [u8; 32] = Sha256::digest(secret.as_bytes()).into();
+    digest.into()
+}
+
+/// A fresh random data key
+pub fn generate_key() -> EnvelopeKey {
+    Aes256Gcm::generate_key(OsRng)
+}
+
+/// Hex HMAC-SHA256 of `parts` under `key`, each part terminated by a zero
+/// byte so that different splits never collide
+pub fn keyed_digest(key: &[u8], parts: &[&[u8]]) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC takes any key length");
+    for part in parts {
+        mac.update(part);
+        mac.update(&[0]);
+    }
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// `data_key` encrypted under `master`
+pub fn wrap_key(master: &EnvelopeKey, data_key: &EnvelopeKey) -> Result<Vec<u8>> {
+    seal(master, data_key.as_slice(), b"")
+}
+
+/// Data key wrapped by [`wrap_key`] under the same master key
+pub fn unwrap_key(master: &EnvelopeKey, wrapped: &[u8]) -> Result<EnvelopeKey> {
+    let data_key = open(master, wrapped, b"")?;
+    if data_key.len() != 32 {
+        return Err(SecurityError::SecretsError {
+            message: "wrapped data key has the wrong length".to_string(),
+        });
+    }
+    Ok(*EnvelopeKey::from_slice(&data_key))
+}
+
+/// `plaintext` encrypted under `key` with a random nonce, bound to `aad`
+pub fn seal(key: &EnvelopeKey, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    seal_with_nonce(key, Aes256Gcm::generate_nonce(OsRng), plaintext, aad)
+}
+
+/// `plaintext` encrypted under `key` with a nonce derived from the plaintext,
+/// so equal plaintexts seal to equal ciphertexts and nothing else repeats.
+/// The nonce is keyed by a separate key derived from `key` with HKDF, so the
+/// encryption key itself is never used as a MAC key.
+pub fn seal_deterministic(key: &EnvelopeKey, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let mut nonce_key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, key.as_slice())
+        .expand(NONCE_KEY_INFO, &mut nonce_key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    let digest = keyed_digest(&nonce_key, &[aad, plaintext]);
+    let digest = hex::decode(&digest[..NONCE_LEN * 2]).expect("hex digest");
+    seal_with_nonce(key, *Nonce::from_slice(&digest), plaintext, aad)
+}
+
+fn seal_with_nonce(
+    key: &EnvelopeKey,
+    nonce: Nonce<<Aes256Gcm as AeadCore>::NonceSize>,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>> {
+    let ciphertext = Aes256Gcm::new(key)
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| SecurityError::SecretsError {
+            message: "failed to encrypt value".to_string(),
+        })?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Plaintext of a value sealed under `key` for the same `aad`
+pub fn open(key: &EnvelopeKey, sealed: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if sealed.len() < NONCE_LEN {
+        return Err(SecurityError::SecretsError {
+            message: "encrypted value is truncated".to_string(),
+        });
+    }
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    Aes256Gcm::new(key)
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|_| SecurityError::SecretsError {
+            message: "encrypted value does not decrypt with this key".to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_sealing() {
+        let key = generate_key();
+        let first = seal_deterministic(&key, b"customer: Acme", b"content").unwrap();
+        assert_eq!(
+            first,
+            seal_deterministic(&key, b"customer: Acme", b"content").unwrap()
+        );
+        assert_ne!(
+            first,
+            seal_deterministic(&key, b"customer: Acme", b"metadata").unwrap()
+        );
+        assert_ne!(first, seal(&key, b"customer: Acme", b"content").unwrap());
+        assert_eq!(open(&key, &first, b"content").unwrap(), b"customer: Acme");
+        assert!(open(&key, &first, b"metadata").is_err());
+
+        let master = master_key("secret");
+        let wrapped = wrap_key(&master, &key).unwrap();
+        assert_eq!(unwrap_key(&master, &wrapped).unwrap(), key);
+        assert!(unwrap_key(&master_key("other"), &wrapped).is_err());
+    }
+}
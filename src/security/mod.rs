@@ -1,7 +1,9 @@
 pub mod audit;
 pub mod auth;
 pub mod compliance;
+pub mod envelope;
 pub mod pii;
+pub mod pii_vault;
 pub mod rate_limit;
 pub mod rbac;
 pub mod secrets;
@@ -14,6 +16,7 @@ pub use audit::*;
 pub use auth::*;
 pub use compliance::*;
 pub use pii::*;
+pub use pii_vault::*;
 pub use rate_limit::*;
 pub use rbac::*;
 pub use secrets::*;
@@ -313,20 +313,10 @@ impl PiiManager {
             high_severity_matches.sort_by(|a, b| b.start.cmp(&a.start));
 
             for pii_match in high_severity_matches {
-                let placeholder = match pii_match.pattern_name.as_str() {
-                    "email" => "[EMAIL]",
-                    "ssn" => "[SSN]",
-                    "credit_card" => "[CREDIT_CARD]",
-                    "phone" => "[PHONE]",
-                    "api_key" => "[API_KEY]",
-                    "password" => "[PASSWORD]",
-                    "jwt_token" => "[JWT_TOKEN]",
-                    "bank_account" => "[BANK_ACCOUNT]",
-                    "drivers_license" => "[DRIVERS_LICENSE]",
-                    _ => "[PII]",
-                };
-
-                anonymized.replace_range(pii_match.start..pii_match.end, placeholder);
+                anonymized.replace_range(
+                    pii_match.start..pii_match.end,
+                    Self::placeholder(&pii_match.pattern_name),
+                );
             }
 
             anonymized
@@ -335,6 +325,64 @@ impl PiiManager {
         }
     }
 
+    /// Generic placeholder replacing a match of the named pattern
+    pub fn placeholder(pattern_name: &str) -> &'static str {
+        match pattern_name {
+            "email" => "[EMAIL]",
+            "ssn" => "[SSN]",
+            "credit_card" => "[CREDIT_CARD]",
+            "phone" => "[PHONE]",
+            "api_key" => "[API_KEY]",
+            "password" => "[PASSWORD]",
+            "jwt_token" => "[JWT_TOKEN]",
+            "bank_account" => "[BANK_ACCOUNT]",
+            "drivers_license" => "[DRIVERS_LICENSE]",
+            _ => "[PII]",
+        }
+    }
+
+    /// PII matches that do not overlap, in order of position. Of overlapping
+    /// matches the one starting first is kept, or the longer one if they
+    /// start together, so each span can be replaced exactly once.
+    pub fn find_matches(&self, content: &str) -> Vec<PiiMatch> {
+        if !self.config.enabled {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<PiiMatch> = self
+            .patterns
+            .iter()
+            .flat_map(|pattern| {
+                pattern.regex.find_iter(content).map(|mat| PiiMatch {
+                    pattern_name: pattern.name.clone(),
+                    severity: pattern.severity.clone(),
+                    start: mat.start(),
+                    end: mat.end(),
+                    matched_text: mat.as_str().to_string(),
+                    masked_text: self.create_mask(mat.as_str(), pattern.mask_char),
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+        let mut end = 0;
+        matches.retain(|m| {
+            let keep = m.start >= end;
+            if keep {
+                end = m.end;
+            }
+            keep
+        });
+        matches
+    }
+
+    /// The most severe of the matches' severities
+    pub fn max_match_severity(&self, matches: &[PiiMatch]) -> PiiSeverity {
+        matches.iter().fold(PiiSeverity::Low, |max, m| {
+            self.max_severity(&max, &m.severity)
+        })
+    }
+
     fn create_mask(&self, text: &str, mask_char: char) -> String {
         if text.len() <= 4 {
             // For short strings, mask everything except first character
@@ -589,6 +637,26 @@ mod tests {
         assert!(!manager.should_anonymize("This is normal text"));
     }
 
+    #[test]
+    fn test_find_matches_skips_overlaps() {
+        let mut config = PiiConfig::default();
+        config.enabled = true;
+        config.detect_patterns.clear();
+        let manager = PiiManager::new(config).unwrap();
+
+        // The SSN also matches the nine-digit patterns; it is reported once
+        let content = "SSN 123-45-6789, account 123456789, mail bob@example.com";
+        let matches = manager.find_matches(content);
+        let spans: Vec<&str> = matches.iter().map(|m| m.matched_text.as_str()).collect();
+        assert_eq!(spans, vec!["123-45-6789", "123456789", "bob@example.com"]);
+        assert!(matches.windows(2).all(|w| w[0].end <= w[1].start));
+        assert!(matches!(
+            manager.max_match_severity(&matches),
+            PiiSeverity::High
+        ));
+        assert_eq!(PiiManager::placeholder("email"), "[EMAIL]");
+    }
+
     #[test]
     fn test_custom_patterns() {
         let config = PiiConfig {
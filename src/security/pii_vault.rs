@@ -0,0 +1,129 @@
+//! Encryption for the PII vault
+//!
+//! Values replaced by tokens are encrypted with AES-256-GCM under a data key
+//! of their subject, and each data key is stored wrapped by the vault's master
+//! key. Deleting a subject's wrapped key crypto-shreds every value encrypted
+//! under it, wherever copies of the ciphertext remain. Tokens are derived
+//! from the value with a keyed hash, so a value keeps its token without the
+//! vault having to be searched by value.
+
+use crate::security::envelope::{self, EnvelopeKey};
+use crate::security::{Result, SecurityError};
+
+/// Permission a client needs to have tokens resolved back to their values
+pub const PII_REVEAL_PERMISSION: &str = "pii:reveal";
+
+/// Hex digits of the keyed hash kept in a token
+const TOKEN_DIGEST_LEN: usize = 12;
+
+/// Encrypts vault values and derives their tokens
+#[derive(Clone)]
+pub struct PiiVaultCipher {
+    master_key: EnvelopeKey,
+    token_key: String,
+}
+
+impl std::fmt::Debug for PiiVaultCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PiiVaultCipher").finish_non_exhaustive()
+    }
+}
+
+impl PiiVaultCipher {
+    /// Cipher keyed by `secret`, which is hashed to the 256-bit master key
+    pub fn new(secret: &str) -> Self {
+        let master_key = envelope::master_key(secret);
+        Self {
+            token_key: envelope::keyed_digest(master_key.as_slice(), &[b"codex-memory pii token"]),
+            master_key,
+        }
+    }
+
+    /// Cipher keyed by `CODEX_PII_VAULT_KEY`, if it is set
+    pub fn from_env() -> Option<Self> {
+        std::env::var("CODEX_PII_VAULT_KEY")
+            .ok()
+            .filter(|secret| !secret.trim().is_empty())
+            .map(|secret| Self::new(secret.trim()))
+    }
+
+    /// Token standing in for `value` of the named pattern in `namespace`,
+    /// e.g. `[EMAIL_3f9a0c1b2d4e]`. The same value always gets the same token.
+    pub fn token(&self, namespace: &str, pattern_name: &str, value: &str) -> String {
+        let digest = envelope::keyed_digest(
+            self.token_key.as_bytes(),
+            &[
+                namespace.as_bytes(),
+                pattern_name.as_bytes(),
+                value.as_bytes(),
+            ],
+        );
+        format!(
+            "[{}_{}]",
+            pattern_name.to_uppercase(),
+            &digest[..TOKEN_DIGEST_LEN]
+        )
+    }
+
+    /// A new subject data key, wrapped by the master key for storage
+    pub fn generate_wrapped_key(&self) -> Result<Vec<u8>> {
+        envelope::wrap_key(&self.master_key, &envelope::generate_key())
+    }
+
+    /// Encrypt `value` for `token` under the subject's wrapped data key
+    pub fn encrypt(&self, wrapped_key: &[u8], token: &str, value: &str) -> Result<Vec<u8>> {
+        let data_key = envelope::unwrap_key(&self.master_key, wrapped_key)?;
+        envelope::seal(&data_key, value.as_bytes(), token.as_bytes())
+    }
+
+    /// Decrypt a value sealed by [`Self::encrypt`] for the same token
+    pub fn decrypt(&self, wrapped_key: &[u8], token: &str, ciphertext: &[u8]) -> Result<String> {
+        let data_key = envelope::unwrap_key(&self.master_key, wrapped_key)?;
+        let value = envelope::open(&data_key, ciphertext, token.as_bytes())?;
+        String::from_utf8(value).map_err(|_| SecurityError::SecretsError {
+            message: "vaulted value is not UTF-8".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_are_stable_and_keyed() {
+        let cipher = PiiVaultCipher::new("vault secret");
+        let token = cipher.token("acme", "email", "ops@example.com");
+        assert_eq!(token, cipher.token("acme", "email", "ops@example.com"));
+        assert!(token.starts_with("[EMAIL_") && token.len() == "[EMAIL_]".len() + 12);
+        assert_ne!(token, cipher.token("other", "email", "ops@example.com"));
+        assert_ne!(
+            token,
+            PiiVaultCipher::new("another secret").token("acme", "email", "ops@example.com")
+        );
+    }
+
+    #[test]
+    fn test_values_round_trip_only_with_their_keys() {
+        let cipher = PiiVaultCipher::new("vault secret");
+        let wrapped = cipher.generate_wrapped_key().unwrap();
+        let sealed = cipher
+            .encrypt(&wrapped, "[EMAIL_1]", "ops@example.com")
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&sealed).contains("example.com"));
+        assert_eq!(
+            cipher.decrypt(&wrapped, "[EMAIL_1]", &sealed).unwrap(),
+            "ops@example.com"
+        );
+
+        // Bound to its token, its subject's key and the master key
+        assert!(cipher.decrypt(&wrapped, "[EMAIL_2]", &sealed).is_err());
+        let other_subject = cipher.generate_wrapped_key().unwrap();
+        assert!(cipher
+            .decrypt(&other_subject, "[EMAIL_1]", &sealed)
+            .is_err());
+        assert!(PiiVaultCipher::new("another secret")
+            .decrypt(&wrapped, "[EMAIL_1]", &sealed)
+            .is_err());
+    }
+}
@@ -56,6 +56,7 @@ async fn test_concurrent_memory_creation_race_free() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
 
                 let memory = env_clone.repository.create_memory(request).await?;
@@ -123,6 +124,7 @@ async fn test_concurrent_read_write_consistency() -> Result<()> {
             metadata: Some(json!({"test": "concurrent_rw", "version": 0})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -201,6 +203,11 @@ async fn test_concurrent_read_write_consistency() -> Result<()> {
                         "writer_id": writer_id
                     })),
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 };
 
                 match env_clone
@@ -303,6 +310,7 @@ async fn test_concurrent_search_operations() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 })
                 .await?;
 
@@ -345,6 +353,11 @@ async fn test_concurrent_search_operations() -> Result<()> {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
 
                 match env_clone.repository.search_memories(search_request).await {
@@ -452,6 +465,7 @@ async fn test_deadlock_prevention() -> Result<()> {
                 metadata: Some(json!({"index": i, "test": "deadlock_prevention"})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -487,6 +501,7 @@ async fn test_deadlock_prevention() -> Result<()> {
                                 metadata: Some(json!({"temp": true, "thread": thread_id})),
                                 parent_id: None,
                                 expires_at: None,
+                                ..Default::default()
                             };
 
                             let temp_memory =
@@ -499,6 +514,11 @@ async fn test_deadlock_prevention() -> Result<()> {
                                 importance_score: Some(0.4),
                                 metadata: None,
                                 expires_at: None,
+                                valid_from: None,
+                                valid_to: None,
+                                actor: None,
+                                reason: None,
+                                content_hash: None,
                             };
 
                             let updated = env_clone
@@ -533,6 +553,11 @@ async fn test_deadlock_prevention() -> Result<()> {
                                 include_facets: None,
                                 ranking_boost: None,
                                 explain_score: None,
+                                rrf_k: None,
+                                entity: None,
+                                include_superseded: None,
+                                as_of: None,
+                                subject_id: None,
                             };
 
                             let _search_results =
@@ -552,6 +577,11 @@ async fn test_deadlock_prevention() -> Result<()> {
                                     importance_score: Some(0.6),
                                     metadata: Some(json!({"updated_by": thread_id, "op": op_num})),
                                     expires_at: None,
+                                    valid_from: None,
+                                    valid_to: None,
+                                    actor: None,
+                                    reason: None,
+                                    content_hash: None,
                                 };
 
                                 let update2 = UpdateMemoryRequest {
@@ -561,6 +591,11 @@ async fn test_deadlock_prevention() -> Result<()> {
                                     importance_score: Some(0.7),
                                     metadata: Some(json!({"updated_by": thread_id, "op": op_num})),
                                     expires_at: None,
+                                    valid_from: None,
+                                    valid_to: None,
+                                    actor: None,
+                                    reason: None,
+                                    content_hash: None,
                                 };
 
                                 let (result1, result2) = tokio::join!(
@@ -599,6 +634,11 @@ async fn test_deadlock_prevention() -> Result<()> {
                                 include_facets: None,
                                 ranking_boost: None,
                                 explain_score: None,
+                                rrf_k: None,
+                                entity: None,
+                                include_superseded: None,
+                                as_of: None,
+                                subject_id: None,
                             };
 
                             let (search_result, memory_result) = tokio::join!(
@@ -691,6 +731,7 @@ async fn test_deadlock_prevention() -> Result<()> {
             metadata: Some(json!({"test": "final_verification"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -752,6 +793,7 @@ async fn test_resource_leak_prevention() -> Result<()> {
                         })),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     })
                     .await?;
 
@@ -776,6 +818,11 @@ async fn test_resource_leak_prevention() -> Result<()> {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
 
                 let _search_result = env_clone.repository.search_memories(search_req).await?;
@@ -788,6 +835,11 @@ async fn test_resource_leak_prevention() -> Result<()> {
                     importance_score: None,
                     metadata: None,
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 };
 
                 let _updated = env_clone
@@ -836,6 +888,7 @@ async fn test_resource_leak_prevention() -> Result<()> {
             metadata: Some(json!({"test": "resource_leak_final"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -861,6 +914,11 @@ async fn test_resource_leak_prevention() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let final_search_results = env.repository.search_memories(final_search).await?;
@@ -44,6 +44,7 @@ async fn create_test_repository_with_consolidation_data(
                 "test_type": "consolidation_performance"
             })),
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(request).await?;
@@ -48,6 +48,7 @@ async fn test_memory_pressure_resilience() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -92,6 +93,7 @@ async fn test_memory_pressure_resilience() -> Result<()> {
                     metadata: Some(large_metadata),
                     parent_id: None,
                     expires_at: Some(Utc::now() + Duration::minutes(5)), // Auto-expire
+                    ..Default::default()
                 }),
             )
             .await
@@ -186,6 +188,11 @@ async fn test_memory_pressure_resilience() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     match timeout(
@@ -248,6 +255,7 @@ async fn test_memory_pressure_resilience() -> Result<()> {
             metadata: Some(json!({"test": "recovery"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -300,6 +308,7 @@ async fn test_intermittent_failure_resilience() -> Result<()> {
                 metadata: Some(json!({"base": true, "index": i})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -343,6 +352,7 @@ async fn test_intermittent_failure_resilience() -> Result<()> {
                                     })),
                                     parent_id: None,
                                     expires_at: None,
+                                    ..Default::default()
                                 })
                                 .await
                                 .map(|m| m.id)
@@ -380,6 +390,11 @@ async fn test_intermittent_failure_resilience() -> Result<()> {
                                                 json!({"updated_by": op_id, "retry": retry}),
                                             ),
                                             expires_at: None,
+                                            valid_from: None,
+                                            valid_to: None,
+                                            actor: None,
+                                            reason: None,
+                                            content_hash: None,
                                         },
                                     )
                                     .await
@@ -408,6 +423,11 @@ async fn test_intermittent_failure_resilience() -> Result<()> {
                                 include_facets: None,
                                 ranking_boost: None,
                                 explain_score: None,
+                                rrf_k: None,
+                                entity: None,
+                                include_superseded: None,
+                                as_of: None,
+                                subject_id: None,
                             };
 
                             env_clone
@@ -543,6 +563,7 @@ async fn test_resource_exhaustion_handling() -> Result<()> {
                         metadata: Some(json!({"connection_test": true, "index": i})),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     })
                     .await
             })
@@ -604,6 +625,7 @@ async fn test_resource_exhaustion_handling() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: Some(Utc::now() + Duration::minutes(1)), // Auto-expire quickly
+                ..Default::default()
             }),
         )
         .await
@@ -663,6 +685,11 @@ async fn test_resource_exhaustion_handling() -> Result<()> {
                 include_facets: Some(true),
                 ranking_boost: None,
                 explain_score: Some(true),
+                rrf_k: None,
+                entity: None,
+                include_superseded: None,
+                as_of: None,
+                subject_id: None,
             };
 
             match timeout(
@@ -713,6 +740,7 @@ async fn test_resource_exhaustion_handling() -> Result<()> {
             metadata: Some(json!({"test": "degradation"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         }),
     )
     .await;
@@ -776,6 +804,7 @@ async fn test_resource_exhaustion_handling() -> Result<()> {
             metadata: Some(json!({"test": "recovery"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -797,6 +826,11 @@ async fn test_resource_exhaustion_handling() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let recovery_results = env.repository.search_memories(recovery_search).await?;
@@ -859,6 +893,7 @@ async fn test_partial_component_failure_resilience() -> Result<()> {
                         metadata: Some(json!({"baseline": true})),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     })
                     .await?;
 
@@ -884,6 +919,11 @@ async fn test_partial_component_failure_resilience() -> Result<()> {
                                 importance_score: None,
                                 metadata: None,
                                 expires_at: None,
+                                valid_from: None,
+                                valid_to: None,
+                                actor: None,
+                                reason: None,
+                                content_hash: None,
                             },
                         )
                         .await?;
@@ -909,6 +949,11 @@ async fn test_partial_component_failure_resilience() -> Result<()> {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
 
                 let _results = env.repository.search_memories(search_request).await?;
@@ -963,6 +1008,7 @@ async fn test_partial_component_failure_resilience() -> Result<()> {
                             metadata: Some(json!({"timeout_test": true, "index": i})),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         }),
                     )
                     .await
@@ -1023,6 +1069,7 @@ async fn test_partial_component_failure_resilience() -> Result<()> {
                             metadata: Some(problematic_metadata),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         })
                         .await
                     {
@@ -1069,6 +1116,11 @@ async fn test_partial_component_failure_resilience() -> Result<()> {
                                         importance_score: Some((0.1 * i as f32) as f64),
                                         metadata: Some(json!({"concurrent": true, "thread": i})),
                                         expires_at: None,
+                                        valid_from: None,
+                                        valid_to: None,
+                                        actor: None,
+                                        reason: None,
+                                        content_hash: None,
                                     },
                                 )
                                 .await
@@ -1149,6 +1201,7 @@ async fn test_partial_component_failure_resilience() -> Result<()> {
             metadata: Some(json!({"health_check": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
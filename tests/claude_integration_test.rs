@@ -136,6 +136,7 @@ async fn test_mcp_protocol_compliance() -> Result<()> {
         })),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = repository.create_memory(memory_request).await?;
@@ -163,6 +164,11 @@ async fn test_mcp_protocol_compliance() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let results = repository.search_memories_simple(search_request).await?;
@@ -200,6 +206,7 @@ async fn test_claude_session_management() -> Result<()> {
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(request).await?;
@@ -225,6 +232,11 @@ async fn test_claude_session_management() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let session_results = repository.search_memories_simple(session_search).await?;
@@ -250,6 +262,11 @@ async fn test_claude_session_management() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let global_results = repository.search_memories_simple(global_search).await?;
@@ -290,6 +307,7 @@ async fn test_claude_desktop_integration() -> Result<()> {
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(request).await?;
@@ -315,6 +333,11 @@ async fn test_claude_desktop_integration() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let context_results = repository.search_memories_simple(context_search).await?;
@@ -341,6 +364,7 @@ async fn test_claude_desktop_integration() -> Result<()> {
         })),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let doc_memory = repository.create_memory(document_request).await?;
@@ -364,6 +388,11 @@ async fn test_claude_desktop_integration() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let doc_results = repository.search_memories_simple(doc_search).await?;
@@ -391,6 +420,7 @@ async fn test_backward_compatibility() -> Result<()> {
         metadata: Some(json!({})), // Minimal metadata
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let legacy_memory = repository.create_memory(legacy_request).await?;
@@ -410,6 +440,7 @@ async fn test_backward_compatibility() -> Result<()> {
         })),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let current_memory = repository.create_memory(current_request).await?;
@@ -433,6 +464,11 @@ async fn test_backward_compatibility() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let search_results = repository.search_memories_simple(search_request).await?;
@@ -478,6 +514,7 @@ async fn test_user_acceptance_scenarios() -> Result<()> {
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         repository.create_memory(request).await?;
@@ -502,6 +539,11 @@ async fn test_user_acceptance_scenarios() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let auth_results = repository.search_memories_simple(auth_search).await?;
@@ -535,6 +577,7 @@ async fn test_user_acceptance_scenarios() -> Result<()> {
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         repository.create_memory(request).await?;
@@ -559,6 +602,11 @@ async fn test_user_acceptance_scenarios() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let research_results = repository.search_memories_simple(research_search).await?;
@@ -589,6 +637,11 @@ async fn test_user_acceptance_scenarios() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let cross_results = repository.search_memories_simple(cross_search).await?;
@@ -271,6 +271,11 @@ async fn test_basic_memory_crud() -> Result<()> {
         importance_score: Some(0.9),
         metadata: None,
         expires_at: None,
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let updated = env
@@ -373,6 +378,11 @@ async fn test_search_functionality() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let search_response = env
@@ -408,6 +418,7 @@ async fn test_concurrent_operations() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
                 repo.create_memory(request).await
             })
@@ -500,6 +511,7 @@ async fn test_error_handling() -> Result<()> {
             metadata: Some(env.get_test_metadata(None)),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await;
 
@@ -49,6 +49,11 @@ fn create_search_request(
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     }
 }
 
@@ -72,6 +77,7 @@ async fn test_complete_memory_lifecycle_workflow() -> Result<()> {
         })),
         parent_id: None,
         expires_at: Some(Utc::now() + Duration::hours(24)),
+        ..Default::default()
     };
 
     let memory = env.repository.create_memory(create_request).await?;
@@ -103,6 +109,11 @@ async fn test_complete_memory_lifecycle_workflow() -> Result<()> {
             "update_count": 1
         })),
         expires_at: None, // Remove expiration
+        actor: None,
+        reason: None,
+        valid_from: None,
+        valid_to: None,
+        content_hash: None,
     };
 
     let updated_memory = env
@@ -130,6 +141,7 @@ async fn test_complete_memory_lifecycle_workflow() -> Result<()> {
             })),
             parent_id: Some(memory.id),
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -190,6 +202,7 @@ async fn test_hierarchical_memory_organization_workflow() -> Result<()> {
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -218,6 +231,7 @@ async fn test_hierarchical_memory_organization_workflow() -> Result<()> {
                 })),
                 parent_id: Some(root_memory.id),
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         category_memories.push((category, category_memory));
@@ -267,6 +281,7 @@ async fn test_hierarchical_memory_organization_workflow() -> Result<()> {
                     })),
                     parent_id: Some(category_memory.id),
                     expires_at: None,
+                    ..Default::default()
                 })
                 .await?;
             all_item_memories.push(item_memory);
@@ -319,6 +334,11 @@ async fn test_hierarchical_memory_organization_workflow() -> Result<()> {
                     importance_score: None,
                     metadata: None,
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 },
             )
             .await?;
@@ -418,6 +438,7 @@ async fn test_search_and_discovery_workflow() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         created_memories.push(memory);
@@ -579,6 +600,7 @@ async fn test_mcp_server_workflow_integration() -> Result<()> {
                 })),
                 parent_id: previous_memory_id,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -637,6 +659,11 @@ async fn test_mcp_server_workflow_integration() -> Result<()> {
                     "updated": true
                 })),
                 expires_at: None,
+                valid_from: None,
+                valid_to: None,
+                actor: None,
+                reason: None,
+                content_hash: None,
             },
         )
         .await?;
@@ -665,6 +692,11 @@ async fn test_mcp_server_workflow_integration() -> Result<()> {
                     importance_score: None,
                     metadata: None,
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 },
             )
             .await?;
@@ -698,6 +730,7 @@ async fn test_mcp_server_workflow_integration() -> Result<()> {
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -745,6 +778,7 @@ async fn test_multi_tier_memory_management_workflow() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: Some(Utc::now() + Duration::hours(48)),
+                ..Default::default()
             })
             .await?;
 
@@ -774,6 +808,7 @@ async fn test_multi_tier_memory_management_workflow() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: Some(Utc::now() + Duration::days(30)),
+                ..Default::default()
             })
             .await?;
 
@@ -803,6 +838,7 @@ async fn test_multi_tier_memory_management_workflow() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None, // No expiration for archived data
+                ..Default::default()
             })
             .await?;
 
@@ -887,6 +923,11 @@ async fn test_multi_tier_memory_management_workflow() -> Result<()> {
                     "migration": "warm_to_working"
                 })),
                 expires_at: Some(Utc::now() + Duration::hours(48)),
+                valid_from: None,
+                valid_to: None,
+                actor: None,
+                reason: None,
+                content_hash: None,
             },
         )
         .await?;
@@ -912,6 +953,11 @@ async fn test_multi_tier_memory_management_workflow() -> Result<()> {
                     "migration": "working_to_warm"
                 })),
                 expires_at: Some(Utc::now() + Duration::days(14)),
+                valid_from: None,
+                valid_to: None,
+                actor: None,
+                reason: None,
+                content_hash: None,
             },
         )
         .await?;
@@ -978,6 +1024,7 @@ async fn test_error_handling_and_recovery_workflow() -> Result<()> {
             metadata: Some(json!({"error_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -1001,6 +1048,11 @@ async fn test_error_handling_and_recovery_workflow() -> Result<()> {
                 importance_score: None,
                 metadata: None,
                 expires_at: None,
+                valid_from: None,
+                valid_to: None,
+                actor: None,
+                reason: None,
+                content_hash: None,
             },
         )
         .await;
@@ -1024,6 +1076,7 @@ async fn test_error_handling_and_recovery_workflow() -> Result<()> {
             metadata: Some(json!({"error_test": true, "invalid_parent": true})),
             parent_id: Some(another_fake_id),
             expires_at: None,
+            ..Default::default()
         })
         .await;
 
@@ -1062,6 +1115,7 @@ async fn test_error_handling_and_recovery_workflow() -> Result<()> {
             metadata: Some(json!({"concurrent_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -1081,6 +1135,11 @@ async fn test_error_handling_and_recovery_workflow() -> Result<()> {
                             importance_score: Some(0.6),
                             metadata: None,
                             expires_at: None,
+                            valid_from: None,
+                            valid_to: None,
+                            actor: None,
+                            reason: None,
+                            content_hash: None,
                         },
                     )
                     .await
@@ -1100,6 +1159,11 @@ async fn test_error_handling_and_recovery_workflow() -> Result<()> {
                             importance_score: Some(0.7),
                             metadata: None,
                             expires_at: None,
+                            valid_from: None,
+                            valid_to: None,
+                            actor: None,
+                            reason: None,
+                            content_hash: None,
                         },
                     )
                     .await
@@ -1133,6 +1197,7 @@ async fn test_error_handling_and_recovery_workflow() -> Result<()> {
             metadata: Some(json!({"recovery_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -37,6 +37,7 @@ async fn test_basic_memory_crud_with_embeddings() -> Result<()> {
         metadata: Some(env.get_test_metadata(None)),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = env.repository.create_memory(create_request).await?;
@@ -60,6 +61,11 @@ async fn test_basic_memory_crud_with_embeddings() -> Result<()> {
         importance_score: Some(0.9),
         metadata: Some(env.get_test_metadata(Some(serde_json::json!({"updated": true})))),
         expires_at: Some(Utc::now() + Duration::hours(24)),
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let updated = env
@@ -167,6 +173,11 @@ async fn test_semantic_search_functionality() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: Some(true),
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let filtered_results = env.repository.search_memories(search_request).await?;
@@ -199,6 +210,11 @@ async fn test_semantic_search_functionality() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let tier_results = env.repository.search_memories(tier_search).await?;
@@ -243,6 +259,11 @@ async fn test_memory_tier_management() -> Result<()> {
         importance_score: Some(0.1), // Very low importance
         metadata: Some(env.get_test_metadata(Some(serde_json::json!({"migration_test": true})))),
         expires_at: None,
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let updated_memory = env
@@ -296,6 +317,7 @@ async fn test_concurrent_memory_operations() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
                 repo.create_memory(request).await
             }
@@ -366,6 +388,11 @@ async fn test_concurrent_memory_operations() -> Result<()> {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
                 repo.search_memories(request).await
             }
@@ -428,6 +455,7 @@ async fn test_performance_under_load() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             repo_clone.create_memory(request).await
         });
@@ -478,6 +506,11 @@ async fn test_performance_under_load() -> Result<()> {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
                 repo.search_memories(request).await
             }
@@ -518,6 +551,7 @@ async fn test_performance_under_load() -> Result<()> {
                             metadata: Some(serde_json::json!({"test_id": test_id, "mixed": true})),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         };
                         repo.create_memory(request).await.map(|_| ())
                     }
@@ -541,6 +575,11 @@ async fn test_performance_under_load() -> Result<()> {
                             include_facets: None,
                             ranking_boost: None,
                             explain_score: None,
+                            rrf_k: None,
+                            entity: None,
+                            include_superseded: None,
+                            as_of: None,
+                            subject_id: None,
                         };
                         repo.search_memories(request).await.map(|_| ())
                     }
@@ -602,6 +641,11 @@ async fn test_error_handling_and_resilience() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     // Should handle gracefully
@@ -624,6 +668,7 @@ async fn test_error_handling_and_resilience() -> Result<()> {
         metadata: Some(serde_json::json!({"test_id": test_id, "size_test": true})),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     // Should either handle gracefully or reject appropriately
@@ -652,6 +697,7 @@ async fn test_error_handling_and_resilience() -> Result<()> {
         metadata: Some(malicious_metadata.clone()),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let metadata_memory = repository.create_memory(metadata_request).await?;
@@ -679,6 +725,7 @@ async fn test_error_handling_and_resilience() -> Result<()> {
                     metadata: Some(serde_json::json!({"test_id": test_id, "stress": true})),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
 
                 if let Ok(memory) = repo.create_memory(request).await {
@@ -720,6 +767,7 @@ async fn test_data_persistence_and_consistency() -> Result<()> {
         })))),
         parent_id: None,
         expires_at: Some(Utc::now() + Duration::hours(24)),
+        ..Default::default()
     };
 
     let created = env.repository.create_memory(comprehensive_request).await?;
@@ -754,6 +802,11 @@ async fn test_data_persistence_and_consistency() -> Result<()> {
             "update_timestamp": Utc::now().to_rfc3339()
         })))),
         expires_at: None, // Remove expiration
+        actor: None,
+        reason: None,
+        valid_from: None,
+        valid_to: None,
+        content_hash: None,
     };
 
     let updated = env
@@ -928,6 +981,11 @@ async fn test_statistics_and_monitoring() -> Result<()> {
         include_facets: Some(true),
         ranking_boost: None,
         explain_score: Some(true),
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let search_response = env.repository.search_memories(search_request).await?;
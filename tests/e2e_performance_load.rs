@@ -53,6 +53,11 @@ fn create_search_request(
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     }
 }
 
@@ -187,6 +192,7 @@ async fn test_working_memory_access_performance() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         memory_ids.push(memory.id);
@@ -254,6 +260,7 @@ async fn test_warm_storage_query_performance() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 })
                 .await?;
             memory_ids.push(memory.id);
@@ -339,6 +346,7 @@ async fn test_cold_storage_retrieval_performance() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         memory_ids.push(memory.id);
@@ -418,6 +426,7 @@ async fn test_concurrent_access_performance() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         memory_ids.push(memory.id);
@@ -518,6 +527,7 @@ async fn test_concurrent_access_performance() -> Result<()> {
                         })),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     })
                     .await;
 
@@ -653,6 +663,7 @@ async fn test_large_dataset_search_performance() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -741,6 +752,11 @@ async fn test_large_dataset_search_performance() -> Result<()> {
             include_facets: None,
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         let start = Instant::now();
@@ -846,6 +862,7 @@ async fn test_tier_migration_performance_impact() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         memory_ids.push(memory.id);
@@ -894,6 +911,11 @@ async fn test_tier_migration_performance_impact() -> Result<()> {
                         "migration_index": i
                     })),
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 },
             )
             .await?;
@@ -960,6 +982,11 @@ async fn test_tier_migration_performance_impact() -> Result<()> {
                             "batch_migrated": true
                         })),
                         expires_at: None,
+                        valid_from: None,
+                        valid_to: None,
+                        actor: None,
+                        reason: None,
+                        content_hash: None,
                     },
                 )
                 .await
@@ -1019,6 +1046,7 @@ async fn test_connection_pool_utilization() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         memory_ids.push(memory.id);
@@ -1147,6 +1175,7 @@ async fn test_connection_pool_utilization() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 })
                 .await
         });
@@ -79,6 +79,7 @@ async fn test_e2e_search_memory_mcp_tool_fulltext() {
         tier: Some(MemoryTier::Working),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let test_memory = repository
@@ -420,6 +421,7 @@ async fn test_e2e_search_consistency_prevention() {
         tier: Some(MemoryTier::Working),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let test_memory = repository
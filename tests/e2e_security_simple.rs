@@ -42,6 +42,11 @@ fn create_search_request(
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     }
 }
 
@@ -193,6 +198,7 @@ async fn test_pii_protection_workflow_e2e() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -242,6 +248,7 @@ async fn test_rate_limiting_workflow_e2e() -> Result<()> {
             metadata: Some(json!({"security_test": "rate_limiting"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -358,6 +365,7 @@ async fn test_input_validation_workflow_e2e() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -498,6 +506,7 @@ async fn test_integrated_security_workflow_simple() -> Result<()> {
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -30,6 +30,7 @@ async fn test_basic_memory_crud_with_embeddings() -> Result<()> {
         metadata: Some(env.get_test_metadata(None)),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = env
@@ -61,6 +62,11 @@ async fn test_basic_memory_crud_with_embeddings() -> Result<()> {
         importance_score: Some(0.9),
         metadata: Some(env.get_test_metadata(Some(serde_json::json!({"updated": true})))),
         expires_at: Some(Utc::now() + Duration::hours(24)),
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let updated = env
@@ -153,6 +159,11 @@ async fn test_semantic_search_functionality() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: Some(true),
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let filtered_results = env
@@ -201,6 +212,7 @@ async fn test_concurrent_memory_operations() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             repo_clone
                 .create_memory(request)
@@ -260,6 +272,7 @@ async fn test_performance_under_load() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
             repo_clone
                 .create_memory(request)
@@ -312,6 +325,7 @@ async fn test_error_handling() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(serde_json::json!({"size_test": true})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     // Should either handle gracefully or reject appropriately
@@ -350,6 +364,7 @@ async fn test_data_persistence_and_consistency() -> Result<()> {
         })))),
         parent_id: None,
         expires_at: Some(Utc::now() + Duration::hours(24)),
+        ..Default::default()
     };
 
     let created = env
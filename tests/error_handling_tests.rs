@@ -44,6 +44,7 @@ async fn test_invalid_input_handling() -> Result<()> {
         metadata: Some(env.get_test_metadata(None)),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let empty_result = env.repository.create_memory(empty_content_request).await;
@@ -67,6 +68,7 @@ async fn test_invalid_input_handling() -> Result<()> {
         metadata: Some(env.get_test_metadata(None)),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let invalid_importance_result = env
@@ -112,6 +114,7 @@ async fn test_invalid_input_handling() -> Result<()> {
         metadata: Some(malformed_metadata),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let malformed_result = env.repository.create_memory(malformed_request).await;
@@ -143,6 +146,7 @@ async fn test_invalid_input_handling() -> Result<()> {
             metadata: Some(env.get_test_metadata(Some(json!({"injection_test": true})))),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let injection_result = env.repository.create_memory(injection_request).await;
@@ -183,6 +187,7 @@ async fn test_large_input_handling() -> Result<()> {
             metadata: Some(env.get_test_metadata(Some(json!({"size_test": size_name})))),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         // Use timeout to prevent hanging on very large content
@@ -244,6 +249,7 @@ async fn test_large_input_handling() -> Result<()> {
         metadata: Some(large_metadata),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let metadata_result = env.repository.create_memory(large_metadata_request).await;
@@ -300,6 +306,11 @@ async fn test_concurrent_access_errors() -> Result<()> {
                         "concurrent_update": true
                     })),
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 };
 
                 repo.update_memory(memory_id, update_request).await
@@ -383,6 +394,11 @@ async fn test_concurrent_access_errors() -> Result<()> {
                             "reader_writer_test": true
                         })),
                         expires_at: None,
+                        valid_from: None,
+                        valid_to: None,
+                        actor: None,
+                        reason: None,
+                        content_hash: None,
                     };
                     repo.update_memory(memory_id, update_request)
                         .await
@@ -444,6 +460,7 @@ async fn test_external_service_failures() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(json!({"embedding_test": true})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let embedding_result = timeout(
@@ -477,6 +494,7 @@ async fn test_external_service_failures() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(json!({"provided_embedding": true})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let provided_embedding_result = env
@@ -515,6 +533,11 @@ async fn test_external_service_failures() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let search_result = timeout(
@@ -575,6 +598,7 @@ async fn test_database_error_handling() -> Result<()> {
                         })),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     };
 
                     match repo.create_memory(request).await {
@@ -620,6 +644,7 @@ async fn test_database_error_handling() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(json!({"long_operation": true})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let long_result = timeout(
@@ -685,6 +710,7 @@ async fn test_data_integrity_after_errors() -> Result<()> {
                             metadata: Some(serde_json::json!({"test_id": test_id, "valid": true})),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         };
                         repo.create_memory(request).await.map(|_| "valid")
                     }
@@ -700,6 +726,7 @@ async fn test_data_integrity_after_errors() -> Result<()> {
                             ),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         };
                         repo.create_memory(request).await.map(|_| "edge_case")
                     }
@@ -717,6 +744,11 @@ async fn test_data_integrity_after_errors() -> Result<()> {
                             importance_score: None,
                             metadata: None,
                             expires_at: None,
+                            valid_from: None,
+                            valid_to: None,
+                            actor: None,
+                            reason: None,
+                            content_hash: None,
                         };
                         repo.update_memory(fake_id, update_request)
                             .await
@@ -815,6 +847,7 @@ async fn test_graceful_degradation() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
 
                 // Use timeout to handle potential slowdowns
@@ -0,0 +1,199 @@
+//! Erasure of one data subject against a migrated database named by
+//! `DATABASE_URL`: rows of another subject in the same namespace, and the
+//! entities both mention, are kept
+
+use anyhow::{Context, Result};
+use codex_memory::memory::models::{CreateMemoryRequest, Memory};
+use codex_memory::security::{ComplianceManager, DataSubjectRequestType, GdprConfig};
+use codex_memory::MemoryRepository;
+use dotenv::dotenv;
+use serde_json::json;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+async fn create_test_pool() -> Result<Option<PgPool>> {
+    let _ = dotenv();
+    let Ok(database_url) = std::env::var("DATABASE_URL") else {
+        println!("⚠️ Skipping GDPR erasure test - DATABASE_URL not set");
+        return Ok(None);
+    };
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .acquire_timeout(Duration::from_secs(10))
+        .connect(&database_url)
+        .await
+        .context("Failed to create test database pool")?;
+    Ok(Some(pool))
+}
+
+fn compliance_manager(pool: &PgPool) -> ComplianceManager {
+    ComplianceManager::new(
+        GdprConfig {
+            enabled: true,
+            right_to_be_forgotten: true,
+            ..GdprConfig::default()
+        },
+        Arc::new(pool.clone()),
+    )
+    .with_receipt_key("gdpr-test receipt key")
+}
+
+async fn store(repository: &MemoryRepository, subject: &str, content: &str) -> Result<Memory> {
+    Ok(repository
+        .create_memory(CreateMemoryRequest {
+            content: content.to_string(),
+            metadata: Some(json!({ "subject_id": subject })),
+            ..Default::default()
+        })
+        .await?)
+}
+
+async fn mention(pool: &PgPool, namespace: &str, memory: &Memory, name: &str) -> Result<Uuid> {
+    let entity_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO entities (namespace, entity_type, canonical_name, normalized_name)
+        VALUES ($1, 'person', $2, lower($2))
+        ON CONFLICT (namespace, entity_type, normalized_name)
+            DO UPDATE SET mention_count = entities.mention_count + 1
+        RETURNING id
+        "#,
+    )
+    .bind(namespace)
+    .bind(name)
+    .fetch_one(pool)
+    .await?;
+    sqlx::query("INSERT INTO memory_entities (memory_id, entity_id, mention) VALUES ($1, $2, $3)")
+        .bind(memory.id)
+        .bind(entity_id)
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(entity_id)
+}
+
+async fn log_search(pool: &PgPool, namespace: &str, subject: &str, query: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO search_query_log \
+         (namespace, query_text, normalized_query, search_type, result_count, subject_id) \
+         VALUES ($1, $2, lower($2), 'semantic', 1, $3)",
+    )
+    .bind(namespace)
+    .bind(query)
+    .bind(subject)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn cache_embedding(pool: &PgPool, text: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO embedding_cache (model, content_hash, embedding, dimension) \
+         VALUES ('gdpr-test', $1, ARRAY[0.5]::real[], 1) ON CONFLICT DO NOTHING",
+    )
+    .bind(Memory::calculate_content_hash(text))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn count(pool: &PgPool, sql: &str, bind: &str) -> Result<i64> {
+    Ok(sqlx::query_scalar(sql).bind(bind).fetch_one(pool).await?)
+}
+
+#[tokio::test]
+async fn test_erasure_keeps_other_subjects_in_a_shared_namespace() -> Result<()> {
+    let Some(pool) = create_test_pool().await? else {
+        return Ok(());
+    };
+    let run = Uuid::new_v4().simple().to_string();
+    let namespace = format!("gdpr-shared-{run}");
+    let alice = format!("alice-{run}");
+    let bob = format!("bob-{run}");
+    let repository = MemoryRepository::new(pool.clone()).with_namespace(namespace.clone());
+
+    let alice_memory = store(&repository, &alice, &format!("Alice met Carol {run}")).await?;
+    let bob_memory = store(&repository, &bob, &format!("Bob met Carol {run}")).await?;
+    let carol = mention(&pool, &namespace, &alice_memory, &format!("Carol {run}")).await?;
+    mention(&pool, &namespace, &bob_memory, &format!("Carol {run}")).await?;
+    let dave = mention(&pool, &namespace, &alice_memory, &format!("Dave {run}")).await?;
+    log_search(&pool, &namespace, &alice, &format!("alice query {run}")).await?;
+    log_search(&pool, &namespace, &bob, &format!("bob query {run}")).await?;
+    cache_embedding(&pool, &alice_memory.content).await?;
+    cache_embedding(&pool, &bob_memory.content).await?;
+
+    let compliance = compliance_manager(&pool);
+    compliance.initialize().await?;
+
+    // A subject id naming the shared namespace is refused
+    let request_id = compliance
+        .submit_request(DataSubjectRequestType::Erasure, &namespace, None)
+        .await?;
+    assert!(compliance
+        .process_erasure_request(&request_id, "gdpr-test")
+        .await
+        .is_err());
+    assert!(repository.get_memory(bob_memory.id).await.is_ok());
+
+    let request_id = compliance
+        .submit_request(DataSubjectRequestType::Erasure, &alice, None)
+        .await?;
+    let receipt = compliance
+        .process_erasure_request(&request_id, "gdpr-test")
+        .await?;
+    assert_eq!(receipt.records_erased.get("memories"), Some(&1));
+
+    assert!(repository.get_memory(alice_memory.id).await.is_err());
+    assert!(repository.get_memory(bob_memory.id).await.is_ok());
+
+    let entity = "SELECT COUNT(*) AS n FROM entities WHERE id::text = $1";
+    assert_eq!(count(&pool, entity, &carol.to_string()).await?, 1);
+    assert_eq!(count(&pool, entity, &dave.to_string()).await?, 0);
+
+    let searches = "SELECT COUNT(*) AS n FROM search_query_log WHERE subject_id = $1";
+    assert_eq!(count(&pool, searches, &alice).await?, 0);
+    assert_eq!(count(&pool, searches, &bob).await?, 1);
+
+    let cached = "SELECT COUNT(*) AS n FROM embedding_cache WHERE content_hash = $1";
+    assert_eq!(count(&pool, cached, &alice_memory.content_hash).await?, 0);
+    assert_eq!(count(&pool, cached, &bob_memory.content_hash).await?, 1);
+
+    let verification = compliance.verify_erasure(&request_id).await?;
+    assert!(verification.is_verified());
+    assert_eq!(verification.receipt.memory_ids, vec![alice_memory.id]);
+
+    // A memory of the erased subject that comes back, e.g. from a backup,
+    // fails verification even without its subject id
+    let restored = repository
+        .create_memory(CreateMemoryRequest {
+            content: format!("Alice restored {run}"),
+            ..Default::default()
+        })
+        .await?;
+    sqlx::query("UPDATE memories SET id = $1 WHERE id = $2")
+        .bind(alice_memory.id)
+        .bind(restored.id)
+        .execute(&pool)
+        .await?;
+    let verification = compliance.verify_erasure(&request_id).await?;
+    assert_eq!(verification.remaining_records.get("memories"), Some(&1));
+    sqlx::query("DELETE FROM memories WHERE id = $1")
+        .bind(alice_memory.id)
+        .execute(&pool)
+        .await?;
+
+    // Clean up the subject that was kept
+    let request_id = compliance
+        .submit_request(DataSubjectRequestType::Erasure, &bob, None)
+        .await?;
+    compliance
+        .process_erasure_request(&request_id, "gdpr-test")
+        .await?;
+    sqlx::query("DELETE FROM gdpr_requests WHERE subject_id = ANY($1)")
+        .bind(vec![namespace, alice, bob])
+        .execute(&pool)
+        .await?;
+    Ok(())
+}
@@ -102,7 +102,10 @@ impl TestMemoryBuilder {
             id: Uuid::new_v4(),
             content: self.content,
             content_hash: String::new(), // Will be generated
+            namespace: "default".to_string(),
             embedding: None,
+            embedding_model: None,
+            embedding_dimension: None,
             tier: self.tier,
             importance_score: self.importance_score,
             recency_score: 1.0,
@@ -118,6 +121,8 @@ impl TestMemoryBuilder {
             access_count: 0,
             access_pattern: None,
             expires_at: None,
+            valid_from: None,
+            valid_to: None,
             recall_probability: Some(1.0),
             consolidation_score: Some(0.5),
         };
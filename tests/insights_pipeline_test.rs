@@ -55,6 +55,7 @@ async fn create_test_memories(repository: &MemoryRepository) -> Result<Vec<Uuid>
             })),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(memory_request).await?;
@@ -90,6 +91,11 @@ async fn test_memory_retrieval(repository: &MemoryRepository) -> Result<()> {
         include_facets: Some(false),
         ranking_boost: None,
         explain_score: Some(true),
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     debug!("Executing temporal search request");
@@ -325,6 +331,7 @@ async fn test_temporal_search_fix_verification() -> Result<()> {
         })),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let test_memory = repository.create_memory(test_memory_request).await?;
@@ -351,6 +358,11 @@ async fn test_temporal_search_fix_verification() -> Result<()> {
         include_facets: Some(false),
         ranking_boost: None,
         explain_score: Some(true),
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     debug!("Executing temporal search that previously failed");
@@ -28,6 +28,7 @@ async fn setup_test_memory(repo: &MemoryRepository) -> Memory {
         metadata: Some(serde_json::json!({"test": "data"})),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
     
     repo.create_memory(memory_request)
@@ -292,6 +293,7 @@ async fn test_system_metrics_snapshots() {
         metadata: Some(serde_json::json!({"tier": "warm"})),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
     let _warm_memory = repo.create_memory(warm_memory_request)
         .await
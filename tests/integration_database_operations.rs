@@ -168,6 +168,7 @@ async fn test_connection_pool_behavior() -> Result<()> {
                     metadata: Some(json!({"concurrent_test": true, "index": i})),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 })
                 .await;
 
@@ -519,6 +520,7 @@ async fn test_connection_recovery() -> Result<()> {
             metadata: Some(json!({"recovery_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -585,6 +587,7 @@ async fn test_data_consistency() -> Result<()> {
             metadata: Some(json!({"type": "parent"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -598,6 +601,7 @@ async fn test_data_consistency() -> Result<()> {
             metadata: Some(json!({"type": "child"})),
             parent_id: Some(parent_memory.id),
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -629,6 +633,11 @@ async fn test_data_consistency() -> Result<()> {
                         importance_score: Some(0.85),
                         metadata: None,
                         expires_at: None,
+                        valid_from: None,
+                        valid_to: None,
+                        actor: None,
+                        reason: None,
+                        content_hash: None,
                     },
                 )
                 .await
@@ -647,6 +656,11 @@ async fn test_data_consistency() -> Result<()> {
                         importance_score: Some(0.9),
                         metadata: None,
                         expires_at: None,
+                        valid_from: None,
+                        valid_to: None,
+                        actor: None,
+                        reason: None,
+                        content_hash: None,
                     },
                 )
                 .await
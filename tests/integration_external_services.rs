@@ -54,6 +54,7 @@ async fn test_embedding_service_integration() -> Result<()> {
             metadata: Some(json!({"embedding_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await;
 
@@ -101,6 +102,7 @@ async fn test_embedding_service_timeout() -> Result<()> {
                     metadata: Some(json!({"timeout_test": true})),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 })
                 .await;
 
@@ -291,6 +293,7 @@ async fn test_network_connectivity() -> Result<()> {
             metadata: Some(json!({"network_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await;
 
@@ -387,6 +390,7 @@ async fn test_service_error_recovery() -> Result<()> {
                 metadata: Some(json!({"error_recovery_test": true, "index": i})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await;
 
@@ -485,6 +485,7 @@ fn test_config() -> Config {
             model: "test-model".to_string(),
             base_url: "http://localhost:11434".to_string(),
             timeout_seconds: 30,
+            cache_enabled: false,
         },
         operational: OperationalConfig {
             max_db_connections: 5,
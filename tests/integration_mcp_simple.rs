@@ -84,6 +84,7 @@ async fn test_mcp_server_repository_integration() -> Result<()> {
             metadata: Some(json!({"mcp_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -159,6 +160,7 @@ async fn test_mcp_server_components() -> Result<()> {
             metadata: Some(json!({"component_test": true, "index": 1})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -172,6 +174,7 @@ async fn test_mcp_server_components() -> Result<()> {
             metadata: Some(json!({"component_test": true, "index": 2})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -193,6 +196,11 @@ async fn test_mcp_server_components() -> Result<()> {
                 importance_score: Some(0.9),
                 metadata: None,
                 expires_at: None,
+                valid_from: None,
+                valid_to: None,
+                actor: None,
+                reason: None,
+                content_hash: None,
             },
         )
         .await?;
@@ -242,6 +250,7 @@ async fn test_mcp_server_concurrent_operations() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 })
                 .await?;
 
@@ -305,6 +314,7 @@ async fn test_mcp_server_statistics() -> Result<()> {
                 metadata: Some(json!({"stats_test": true, "index": i})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
         memory_ids.push(memory.id);
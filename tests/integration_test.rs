@@ -189,6 +189,7 @@ async fn test_mcp_operations_end_to_end() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(json!({"test": true, "source": "mcp"})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = env.repository.create_memory(create_request).await?;
@@ -209,6 +210,11 @@ async fn test_mcp_operations_end_to_end() -> Result<()> {
         importance_score: Some(0.9),
         metadata: Some(env.get_test_metadata(Some(json!({"test": true, "updated": true})))),
         expires_at: None,
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let updated = env
@@ -238,6 +244,11 @@ async fn test_mcp_operations_end_to_end() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let search_results = env
@@ -272,6 +283,7 @@ async fn test_memory_persistence_across_sessions() -> Result<()> {
             metadata: Some(json!({"session": 1, "index": i})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = env.repository.create_memory(request).await?;
@@ -309,6 +321,7 @@ async fn test_context_window_management() -> Result<()> {
             metadata: Some(json!({"context_window": true, "position": i})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = env.repository.create_memory(request).await?;
@@ -339,6 +352,11 @@ async fn test_context_window_management() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let important_results = env
@@ -371,6 +389,11 @@ async fn test_context_window_management() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let page1 = env
@@ -426,6 +449,7 @@ async fn test_embedding_quality_for_code() -> Result<()> {
             metadata: Some(json!({"type": "code", "language": description})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = env.repository.create_memory(request).await?;
@@ -460,6 +484,11 @@ async fn test_embedding_quality_for_code() -> Result<()> {
             include_facets: None,
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         let results = env
@@ -514,6 +543,7 @@ async fn test_concurrent_user_load() -> Result<()> {
                     metadata: Some(json!({"user_id": user_id, "operation_id": op_id})),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
 
                 let memory = repository.create_memory(request).await?;
@@ -539,6 +569,11 @@ async fn test_concurrent_user_load() -> Result<()> {
                 include_facets: None,
                 ranking_boost: None,
                 explain_score: None,
+                rrf_k: None,
+                entity: None,
+                include_superseded: None,
+                as_of: None,
+                subject_id: None,
             };
 
             let _results = repository.search_memories_simple(search_request).await?;
@@ -552,6 +587,11 @@ async fn test_concurrent_user_load() -> Result<()> {
                     importance_score: Some(0.9),
                     metadata: None,
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 };
 
                 repository.update_memory(*memory_id, update_request).await?;
@@ -612,6 +652,11 @@ async fn test_error_handling_and_graceful_degradation() -> Result<()> {
         include_facets: None,
         ranking_boost: None,
         explain_score: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     // This should handle gracefully (return empty results or error)
@@ -632,6 +677,7 @@ async fn test_error_handling_and_graceful_degradation() -> Result<()> {
         metadata: Some(json!({"test": "duplicate"})),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory1 = env.repository.create_memory(request1).await?;
@@ -645,6 +691,7 @@ async fn test_error_handling_and_graceful_degradation() -> Result<()> {
         metadata: Some(json!({"test": "duplicate2"})),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let result2 = env.repository.create_memory(request2).await;
@@ -675,6 +722,7 @@ async fn test_memory_coherence_across_instances() -> Result<()> {
         metadata: Some(json!({"instance": 1})),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = env1.repository.create_memory(request).await?;
@@ -695,6 +743,11 @@ async fn test_memory_coherence_across_instances() -> Result<()> {
         importance_score: Some(0.9),
         metadata: Some(json!({"instance": 2, "updated": true})),
         expires_at: None,
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let _updated = env2
@@ -738,6 +791,7 @@ async fn test_performance_under_load() -> Result<()> {
                 metadata: Some(json!({"perf_test": true, "id": i})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             };
 
             let memory = repository.create_memory(request).await?;
@@ -764,6 +818,11 @@ async fn test_performance_under_load() -> Result<()> {
                 include_facets: None,
                 ranking_boost: None,
                 explain_score: None,
+                rrf_k: None,
+                entity: None,
+                include_superseded: None,
+                as_of: None,
+                subject_id: None,
             };
 
             let _results = repository.search_memories_simple(search_request).await?;
@@ -816,6 +875,7 @@ async fn test_security_and_access_control() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(json!({"security_test": true})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     // Should handle malicious content safely
@@ -832,6 +892,7 @@ async fn test_security_and_access_control() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(json!({"size_test": true})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     // Should either reject or handle large content gracefully
@@ -861,6 +922,7 @@ async fn test_security_and_access_control() -> Result<()> {
         metadata: Some(malicious_metadata.clone()),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let metadata_memory = env.repository.create_memory(metadata_request).await?;
@@ -887,6 +949,7 @@ async fn test_concurrent_access_safety() -> Result<()> {
         metadata: Some(env.get_test_metadata(Some(json!({"shared": true})))),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let shared_memory = env.repository.create_memory(shared_request).await?;
@@ -942,6 +1005,7 @@ async fn test_data_integrity_and_consistency() -> Result<()> {
         })))),
         parent_id: None,
         expires_at: Some(chrono::Utc::now() + chrono::Duration::hours(24)),
+        ..Default::default()
     };
 
     let created = env.repository.create_memory(comprehensive_request).await?;
@@ -43,6 +43,7 @@ async fn create_test_repository_with_data() -> Result<(Arc<MemoryRepository>, Ve
                 "created_for": "n_plus_1_performance_test"
             })),
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(request).await?;
@@ -100,6 +100,7 @@ async fn test_high_volume_memory_creation() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
                 repo_clone.create_memory(request).await
             });
@@ -199,6 +200,7 @@ async fn test_concurrent_user_simulation() -> Result<()> {
                         })),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     };
 
                     let memory = repo_clone.create_memory(create_request).await?;
@@ -230,6 +232,11 @@ async fn test_concurrent_user_simulation() -> Result<()> {
                             include_facets: None,
                             ranking_boost: None,
                             explain_score: None,
+                            rrf_k: None,
+                            entity: None,
+                            include_superseded: None,
+                            as_of: None,
+                            subject_id: None,
                         };
 
                         let _search_results = repo_clone.search_memories(search_request).await?;
@@ -348,6 +355,7 @@ async fn test_search_performance_scaling() -> Result<()> {
                     })),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
                 repo.create_memory(request).await
             }
@@ -391,6 +399,11 @@ async fn test_search_performance_scaling() -> Result<()> {
             include_facets: None,
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         let search_response = env.repository.search_memories(search_request).await?;
@@ -448,6 +461,11 @@ async fn test_search_performance_scaling() -> Result<()> {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
                 repo.search_memories(request).await
             }
@@ -526,6 +544,7 @@ async fn test_memory_tier_performance() -> Result<()> {
                         })),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     };
                     repo.create_memory(request).await
                 }
@@ -573,6 +592,11 @@ async fn test_memory_tier_performance() -> Result<()> {
             include_facets: None,
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         let tier_results = repository.search_memories(tier_search).await?;
@@ -747,6 +771,7 @@ async fn test_resource_usage_and_limits() -> Result<()> {
                         })),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     };
                     repo.create_memory(request).await
                 }
@@ -71,6 +71,9 @@ prop_compose! {
             metadata: Some(metadata),
             parent_id: None,
             expires_at: None,
+            valid_from: None,
+            valid_to: None,
+            content_hash: None,
         }
     }
 }
@@ -226,6 +229,11 @@ prop_compose! {
             importance_score: importance.map(|i| i as f64),
             metadata,
             expires_at: None,
+            valid_from: None,
+            valid_to: None,
+            actor: None,
+            reason: None,
+            content_hash: None,
         }
     }
 }
@@ -380,6 +388,11 @@ async fn prop_search_consistency() -> Result<()> {
                         include_facets: None,
                         ranking_boost: None,
                         explain_score: None,
+                        rrf_k: None,
+                        entity: None,
+                        include_superseded: None,
+                        as_of: None,
+                        subject_id: None,
                     };
 
                     // Run same search twice
@@ -474,6 +487,7 @@ async fn prop_importance_tier_relationship() -> Result<()> {
                     metadata: Some(json!({"test": "prop_importance"})),
                     parent_id: None,
                     expires_at: None,
+                    ..Default::default()
                 };
 
                 let memory = env_clone.repository.create_memory(request).await
@@ -583,6 +597,11 @@ async fn prop_metadata_preservation() -> Result<()> {
                     importance_score: None,
                     metadata: Some(update_metadata.clone()),
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
+                    actor: None,
+                    reason: None,
+                    content_hash: None,
                 };
 
                 let updated = env_clone
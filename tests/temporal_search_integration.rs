@@ -39,6 +39,11 @@ async fn test_temporal_search_functionality() -> Result<(), Box<dyn std::error::
         similarity_threshold: None,
         include_facets: Some(false),
         include_debug_info: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let response = repository.search(&search_request).await?;
@@ -118,6 +123,11 @@ async fn test_temporal_search_with_tier_filter() -> Result<(), Box<dyn std::erro
         similarity_threshold: None,
         include_facets: Some(false),
         include_debug_info: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let response = repository.search(&search_request).await?;
@@ -166,6 +176,11 @@ async fn test_temporal_search_ordering() -> Result<(), Box<dyn std::error::Error
         similarity_threshold: None,
         include_facets: Some(false),
         include_debug_info: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let response = repository.search(&search_request).await?;
@@ -409,6 +409,7 @@ async fn test_repository_integration() {
         metadata: None,
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = repository.create_memory(request).await.unwrap();
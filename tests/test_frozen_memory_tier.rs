@@ -34,6 +34,7 @@ async fn create_test_memories_with_low_recall(
                 metadata: None,
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -68,6 +69,7 @@ async fn test_freeze_memory_with_delay() {
             metadata: None,
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await
         .unwrap();
@@ -227,6 +229,7 @@ async fn test_compression_ratio_requirement() {
             metadata: None,
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await
         .unwrap();
@@ -278,6 +281,7 @@ async fn test_frozen_memory_search_exclusion() {
             metadata: None,
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await
         .unwrap();
@@ -352,6 +356,7 @@ async fn test_recall_probability_migration_rule() {
                 metadata: None,
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -403,6 +408,7 @@ async fn test_data_integrity_after_freeze_unfreeze() {
             metadata: Some(original_metadata.clone()),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await
         .unwrap();
@@ -473,6 +473,7 @@ impl TestEnvironment {
             })))),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         self.repository
@@ -525,6 +526,11 @@ impl TestEnvironment {
             include_facets: None,
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         self.repository
@@ -69,18 +69,21 @@ async fn create_test_config() -> ImportanceAssessmentConfig {
         ReferenceEmbedding {
             name: "memory_command".to_string(),
             embedding: remember_embedding,
+            text: None,
             weight: 0.9,
             category: "memory".to_string(),
         },
         ReferenceEmbedding {
             name: "preference_statement".to_string(),
             embedding: prefer_embedding,
+            text: None,
             weight: 0.7,
             category: "preference".to_string(),
         },
         ReferenceEmbedding {
             name: "decision_statement".to_string(),
             embedding: decide_embedding,
+            text: None,
             weight: 0.8,
             category: "decision".to_string(),
         },
@@ -190,6 +193,46 @@ async fn test_stage2_semantic_similarity() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_text_only_reference_embeddings() -> Result<()> {
+    let content = "I want to remember this preference for future decisions.";
+    let mut config = create_test_config_permissive().await;
+    config.stage2.reference_embeddings = vec![ReferenceEmbedding {
+        name: "same_text".to_string(),
+        embedding: Vec::new(),
+        text: Some(content.to_string()),
+        weight: 1.0,
+        category: "memory".to_string(),
+    }];
+    // A perfect match would otherwise escalate to the (unavailable) LLM stage
+    config.stage2.confidence_threshold = 1.1;
+    let pipeline = ImportanceAssessmentPipeline::new(
+        config,
+        Arc::new(MockEmbeddingService),
+        &Registry::new(),
+    )?;
+
+    let result = pipeline.assess_importance(content).await?;
+
+    let stage2_result = result
+        .stage_results
+        .iter()
+        .find(|r| matches!(r.stage, AssessmentStage::Stage2SemanticSimilarity))
+        .expect("Stage 2 should have executed");
+    match &stage2_result.details {
+        StageDetails::Stage2 {
+            similarity_scores, ..
+        } => {
+            assert_eq!(similarity_scores.len(), 1);
+            assert_eq!(similarity_scores[0].reference_name, "same_text");
+            assert!(similarity_scores[0].similarity > 0.99);
+        }
+        other => panic!("Unexpected stage details: {other:?}"),
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_low_importance_content() -> Result<()> {
     let pipeline = create_test_pipeline().await?;
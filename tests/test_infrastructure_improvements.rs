@@ -276,6 +276,7 @@ impl TestDataGenerator {
                     } else {
                         None
                     },
+                    ..Default::default()
                 }
             })
             .collect()
@@ -366,6 +367,11 @@ async fn test_comprehensive_benchmarking_suite() -> Result<()> {
                     include_facets: None,
                     ranking_boost: None,
                     explain_score: None,
+                    rrf_k: None,
+                    entity: None,
+                    include_superseded: None,
+                    as_of: None,
+                    subject_id: None,
                 };
 
                 let results = env_clone.repository.search_memories(search_request).await?;
@@ -404,6 +410,7 @@ async fn test_comprehensive_benchmarking_suite() -> Result<()> {
                                 metadata: Some(json!({"concurrent": true, "index": i})),
                                 parent_id: None,
                                 expires_at: None,
+                                ..Default::default()
                             })
                             .await
                     });
@@ -568,6 +575,11 @@ async fn test_advanced_data_generation_patterns() -> Result<()> {
             include_facets: None,
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         let search_results = env.repository.search_memories(search_request).await?;
@@ -636,6 +648,7 @@ async fn test_infrastructure_monitoring_observability() -> Result<()> {
             metadata: Some(json!({"monitoring": "baseline"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -664,6 +677,7 @@ async fn test_infrastructure_monitoring_observability() -> Result<()> {
                 })),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
 
@@ -713,6 +727,11 @@ async fn test_infrastructure_monitoring_observability() -> Result<()> {
             include_facets: None,
             ranking_boost: None,
             explain_score: None,
+            rrf_k: None,
+            entity: None,
+            include_superseded: None,
+            as_of: None,
+            subject_id: None,
         };
 
         let search_results = env.repository.search_memories(search_request).await?;
@@ -892,6 +911,7 @@ async fn test_automated_reporting_analysis() -> Result<()> {
                         metadata: Some(json!({"scenario": scenario_name, "index": i})),
                         parent_id: None,
                         expires_at: None,
+                        ..Default::default()
                     })
                     .await
                     .map(|m| m.id),
@@ -909,6 +929,7 @@ async fn test_automated_reporting_analysis() -> Result<()> {
                             ),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         })
                         .await
                         .map(|m| m.id)
@@ -926,6 +947,7 @@ async fn test_automated_reporting_analysis() -> Result<()> {
                                 metadata: Some(json!({"scenario": scenario_name, "valid": true})),
                                 parent_id: None,
                                 expires_at: None,
+                                ..Default::default()
                             })
                             .await
                             .map(|m| m.id)
@@ -948,6 +970,7 @@ async fn test_automated_reporting_analysis() -> Result<()> {
                             metadata: Some(json!({"scenario": scenario_name, "concurrent": true})),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         })
                         .await
                         .map(|m| m.id)
@@ -965,6 +988,7 @@ async fn test_automated_reporting_analysis() -> Result<()> {
                                 metadata: None,
                                 parent_id: None,
                                 expires_at: None,
+                                ..Default::default()
                             })
                             .await
                             .map(|m| m.id)
@@ -981,6 +1005,7 @@ async fn test_automated_reporting_analysis() -> Result<()> {
                                 ),
                                 parent_id: None,
                                 expires_at: None,
+                                ..Default::default()
                             })
                             .await
                             .map(|m| m.id)
@@ -997,6 +1022,7 @@ async fn test_automated_reporting_analysis() -> Result<()> {
                             metadata: Some(json!({"scenario": scenario_name})),
                             parent_id: None,
                             expires_at: None,
+                            ..Default::default()
                         })
                         .await
                         .map(|m| m.id)
@@ -49,6 +49,11 @@ async fn test_hybrid_search(repository: &MemoryRepository) {
         search_type: Some("hybrid".to_string()),
         explain_score: Some(true),
         filters: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let results = repository
@@ -88,6 +93,11 @@ async fn test_fulltext_search(repository: &MemoryRepository) {
         search_type: Some("fulltext".to_string()),
         explain_score: Some(false),
         filters: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let results = repository
@@ -141,6 +151,11 @@ async fn test_ohat_memory_search(repository: &MemoryRepository) {
         search_type: Some("hybrid".to_string()),
         explain_score: Some(false),
         filters: None,
+        rrf_k: None,
+        entity: None,
+        include_superseded: None,
+        as_of: None,
+        subject_id: None,
     };
 
     let results = repository
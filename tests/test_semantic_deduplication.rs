@@ -48,6 +48,7 @@ async fn test_semantic_deduplication_basic() {
             metadata: None,
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await
         .expect("Failed to create test memory 1");
@@ -61,6 +62,7 @@ async fn test_semantic_deduplication_basic() {
             metadata: None,
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await
         .expect("Failed to create test memory 2");
@@ -134,6 +134,8 @@ async fn test_silent_harvester_service() {
         timestamp: Utc::now(),
         role: "user".to_string(),
         context: "productivity_discussion".to_string(),
+        namespace: "default".to_string(),
+        subject_id: None,
     };
 
     let message2 = ConversationMessage {
@@ -142,6 +144,8 @@ async fn test_silent_harvester_service() {
         timestamp: Utc::now(),
         role: "user".to_string(),
         context: "technology_choice".to_string(),
+        namespace: "default".to_string(),
+        subject_id: None,
     };
 
     // Add messages - should trigger processing after 2 messages
@@ -188,6 +192,8 @@ async fn test_deduplication_service() {
         source_message_id: Some(Uuid::new_v4().to_string()),
         context: "test".to_string(),
         metadata: std::collections::HashMap::new(),
+        namespace: "default".to_string(),
+        subject_id: None,
     };
 
     let pattern2 = ExtractedMemoryPattern {
@@ -198,6 +204,8 @@ async fn test_deduplication_service() {
         source_message_id: Some(Uuid::new_v4().to_string()),
         context: "test".to_string(),
         metadata: std::collections::HashMap::new(),
+        namespace: "default".to_string(),
+        subject_id: None,
     };
 
     let pattern3 = ExtractedMemoryPattern {
@@ -208,6 +216,8 @@ async fn test_deduplication_service() {
         source_message_id: Some(Uuid::new_v4().to_string()),
         context: "test".to_string(),
         metadata: std::collections::HashMap::new(),
+        namespace: "default".to_string(),
+        subject_id: None,
     };
 
     // First pattern should not be a duplicate
@@ -255,6 +265,8 @@ async fn test_performance_requirements() {
             timestamp: Utc::now(),
             role: "user".to_string(),
             context: "performance_test".to_string(),
+            namespace: "default".to_string(),
+            subject_id: None,
         });
     }
 
@@ -298,6 +310,8 @@ async fn test_confidence_threshold_filtering() {
             timestamp: Utc::now(),
             role: "user".to_string(),
             context: "strong_preference".to_string(),
+            namespace: "default".to_string(),
+            subject_id: None,
         },
         ConversationMessage {
             id: Uuid::new_v4().to_string(),
@@ -305,6 +319,8 @@ async fn test_confidence_threshold_filtering() {
             timestamp: Utc::now(),
             role: "user".to_string(),
             context: "weak_preference".to_string(),
+            namespace: "default".to_string(),
+            subject_id: None,
         },
     ];
 
@@ -358,6 +374,8 @@ async fn test_silent_mode_operation() {
         timestamp: Utc::now(),
         role: "user".to_string(),
         context: "programming_preference".to_string(),
+        namespace: "default".to_string(),
+        subject_id: None,
     };
 
     // In silent mode, this should not produce any visible output
@@ -461,6 +479,8 @@ async fn test_mcp_integration_simulation() {
             timestamp: Utc::now(),
             role: "user".to_string(),
             context: "mcp_integration_test".to_string(),
+            namespace: "default".to_string(),
+            subject_id: None,
         };
 
         harvester_service
@@ -41,6 +41,7 @@ async fn create_test_memory(
         metadata: None,
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let mut memory = repository.create_memory(request).await?;
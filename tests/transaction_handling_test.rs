@@ -28,6 +28,7 @@ mod transaction_tests {
             metadata: Some(serde_json::json!({})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         // Store the memory
@@ -80,6 +81,7 @@ mod transaction_tests {
             metadata: Some(serde_json::json!({})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
         let memory1 = repo.create_memory(request1).await?;
 
@@ -136,6 +138,7 @@ mod transaction_tests {
             metadata: Some(serde_json::json!({})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repo.create_memory(request).await?;
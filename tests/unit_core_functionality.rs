@@ -168,6 +168,7 @@ async fn test_memory_repository_integration() -> Result<()> {
         metadata: Some(json!({"test_type": "integration", "category": "unit_test"})),
         parent_id: None,
         expires_at: Some(Utc::now() + Duration::hours(1)),
+        ..Default::default()
     };
 
     let created_memory = env.repository.create_memory(create_request).await?;
@@ -198,6 +199,11 @@ async fn test_memory_repository_integration() -> Result<()> {
         importance_score: Some(0.9),
         metadata: Some(json!({"test_type": "integration", "updated": true})),
         expires_at: None, // Remove expiration
+        actor: None,
+        reason: None,
+        valid_from: None,
+        valid_to: None,
+        content_hash: None,
     };
 
     let updated_memory = env
@@ -261,6 +267,11 @@ async fn test_error_conditions() -> Result<()> {
         importance_score: None,
         metadata: None,
         expires_at: None,
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let update_result = env.repository.update_memory(fake_id, update_request).await;
@@ -30,6 +30,7 @@ async fn test_create_memory_basic() -> Result<()> {
         metadata: Some(json!({"test": "create_basic"})),
         parent_id: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = env.repository.create_memory(request).await?;
@@ -62,6 +63,7 @@ async fn test_get_memory_access_tracking() -> Result<()> {
             metadata: Some(json!({"test": "access_tracking"})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -93,6 +95,7 @@ async fn test_update_memory() -> Result<()> {
             metadata: Some(json!({"original": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -104,6 +107,11 @@ async fn test_update_memory() -> Result<()> {
         importance_score: Some(0.8),
         metadata: Some(json!({"updated": true})),
         expires_at: Some(Utc::now() + Duration::hours(1)),
+        valid_from: None,
+        valid_to: None,
+        actor: None,
+        reason: None,
+        content_hash: None,
     };
 
     let updated = env
@@ -138,6 +146,7 @@ async fn test_delete_memory() -> Result<()> {
             metadata: Some(json!({"delete_test": true})),
             parent_id: None,
             expires_at: None,
+            ..Default::default()
         })
         .await?;
 
@@ -179,6 +188,11 @@ async fn test_nonexistent_memory() -> Result<()> {
                 importance_score: None,
                 metadata: None,
                 expires_at: None,
+                valid_from: None,
+                valid_to: None,
+                actor: None,
+                reason: None,
+                content_hash: None,
             },
         )
         .await;
@@ -216,6 +230,7 @@ async fn test_repository_statistics() -> Result<()> {
                 metadata: Some(json!({"stats_test": true, "index": i})),
                 parent_id: None,
                 expires_at: None,
+                ..Default::default()
             })
             .await?;
     }
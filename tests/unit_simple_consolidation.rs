@@ -11,7 +11,10 @@ fn create_test_memory(hours_ago: i64, consolidation_strength: f64, access_count:
         id: Uuid::new_v4(),
         content: "Test memory content".to_string(),
         content_hash: "test_hash".to_string(),
+        namespace: "default".to_string(),
         embedding: None,
+        embedding_model: None,
+        embedding_dimension: None,
         tier: MemoryTier::Working,
         status: MemoryStatus::Active,
         importance_score: 0.5,
@@ -22,6 +25,8 @@ fn create_test_memory(hours_ago: i64, consolidation_strength: f64, access_count:
         created_at: now - Duration::hours(hours_ago * 2),
         updated_at: now - Duration::hours(hours_ago),
         expires_at: None,
+        valid_from: None,
+        valid_to: None,
         consolidation_strength,
         decay_rate: 1.0,
         recall_probability: Some(0.8),
@@ -294,6 +294,8 @@ mod vector_performance_tests {
                     recall_probability: Some(0.9),
                     deleted_at: None,
                     expires_at: None,
+                    valid_from: None,
+                    valid_to: None,
                     access_count: 0,
                     last_error: None,
                 }
@@ -78,6 +78,7 @@ async fn test_working_memory_capacity_enforcement() -> Result<()> {
             parent_id: None,
             metadata: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(request).await?;
@@ -101,6 +102,7 @@ async fn test_working_memory_capacity_enforcement() -> Result<()> {
         parent_id: None,
         metadata: None,
         expires_at: None,
+        ..Default::default()
     };
 
     let memory = repository.create_memory(request).await?;
@@ -138,6 +140,7 @@ async fn test_lru_eviction_order() -> Result<()> {
             parent_id: None,
             metadata: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(request).await?;
@@ -162,6 +165,7 @@ async fn test_lru_eviction_order() -> Result<()> {
         parent_id: None,
         metadata: None,
         expires_at: None,
+        ..Default::default()
     };
 
     repository.create_memory(request).await?;
@@ -206,6 +210,7 @@ async fn test_memory_pressure_metric() -> Result<()> {
             parent_id: None,
             metadata: None,
             expires_at: None,
+            ..Default::default()
         };
         repository.create_memory(request).await?;
     }
@@ -226,6 +231,7 @@ async fn test_memory_pressure_metric() -> Result<()> {
             parent_id: None,
             metadata: None,
             expires_at: None,
+            ..Default::default()
         };
         repository.create_memory(request).await?;
     }
@@ -251,6 +257,7 @@ async fn test_no_eviction_for_other_tiers() -> Result<()> {
             parent_id: None,
             metadata: None,
             expires_at: None,
+            ..Default::default()
         };
 
         let memory = repository.create_memory(request).await?;
@@ -287,6 +294,7 @@ async fn test_automatic_tier_migration_on_pressure() -> Result<()> {
             parent_id: None,
             metadata: None,
             expires_at: None,
+            ..Default::default()
         };
         repository.create_memory(request).await?;
     }
@@ -304,6 +312,7 @@ async fn test_automatic_tier_migration_on_pressure() -> Result<()> {
         parent_id: None,
         metadata: None,
         expires_at: None,
+        ..Default::default()
     };
 
     repository.create_memory(request).await?;
@@ -351,6 +360,7 @@ async fn test_concurrent_memory_creation_at_capacity() -> Result<()> {
             parent_id: None,
             metadata: None,
             expires_at: None,
+            ..Default::default()
         };
         repository.create_memory(request).await?;
     }
@@ -370,6 +380,7 @@ async fn test_concurrent_memory_creation_at_capacity() -> Result<()> {
                 parent_id: None,
                 metadata: None,
                 expires_at: None,
+                ..Default::default()
             };
             repo.create_memory(request)
                 .await